@@ -2,7 +2,8 @@
 //!
 //! Platform-conditional build:
 //! 1. Tauri build (generates Tauri-specific code) — all platforms
-//! 2. macOS: swift-bridge FFI glue, compile Swift OCR bridge, link frameworks
+//! 2. macOS: swift-bridge FFI glue, compile the Swift OCR, document,
+//!    App Intents, share sheet, and focus bridges, link frameworks
 //! 3. Windows: no extra build steps (windows-rs WinRT bindings are auto-generated)
 //!
 //! All generated files go to OUT_DIR (inside target/) to avoid triggering
@@ -22,10 +23,11 @@ fn main() {
     build_swift_ocr_bridge();
 }
 
-/// Build the Swift OCR bridge for macOS.
+/// Build the Swift OCR, document, App Intents, share sheet, and focus
+/// bridges for macOS.
 ///
 /// Uses swift-bridge to generate Rust↔Swift FFI glue, compiles the Swift
-/// source into a static library, and links it with Apple frameworks.
+/// sources into a static library, and links it with Apple frameworks.
 ///
 /// Gated with #[cfg(target_os = "macos")] so the compiler never resolves
 /// swift_bridge_build on Windows/Linux hosts.
@@ -40,10 +42,18 @@ fn build_swift_ocr_bridge() {
 
     println!("cargo:rerun-if-changed=src/ocr/apple_vision.rs");
     println!("cargo:rerun-if-changed=swift-src/ocr_bridge.swift");
+    println!("cargo:rerun-if-changed=src/document/apple.rs");
+    println!("cargo:rerun-if-changed=swift-src/document_bridge.swift");
+    println!("cargo:rerun-if-changed=src/intents/apple.rs");
+    println!("cargo:rerun-if-changed=swift-src/intents_bridge.swift");
+    println!("cargo:rerun-if-changed=src/share/apple.rs");
+    println!("cargo:rerun-if-changed=swift-src/share_bridge.swift");
+    println!("cargo:rerun-if-changed=src/windows/focus_apple.rs");
+    println!("cargo:rerun-if-changed=swift-src/focus_bridge.swift");
 
     // Step 1: Generate FFI glue to OUT_DIR (not inside src-tauri/)
-    swift_bridge_build::parse_bridges(vec!["src/ocr/apple_vision.rs"])
-        .write_all_concatenated(&generated_dir, env!("CARGO_PKG_NAME"));
+    let bridges = vec!["src/ocr/apple_vision.rs", "src/document/apple.rs", "src/intents/apple.rs", "src/share/apple.rs", "src/windows/focus_apple.rs"];
+    swift_bridge_build::parse_bridges(bridges).write_all_concatenated(&generated_dir, env!("CARGO_PKG_NAME"));
 
     // Step 2: Generate bridging header dynamically with absolute paths
     let bridging_header = out_dir.join("bridging-header.h");
@@ -69,6 +79,10 @@ fn build_swift_ocr_bridge() {
         .arg("-import-objc-header")
         .arg(&bridging_header)
         .arg(swift_src_dir.join("ocr_bridge.swift"))
+        .arg(swift_src_dir.join("document_bridge.swift"))
+        .arg(swift_src_dir.join("intents_bridge.swift"))
+        .arg(swift_src_dir.join("share_bridge.swift"))
+        .arg(swift_src_dir.join("focus_bridge.swift"))
         .arg(generated_dir.join("SwiftBridgeCore.swift"))
         .arg(generated_dir.join("omni-glass/omni-glass.swift"))
         .arg("-o")
@@ -90,6 +104,10 @@ fn build_swift_ocr_bridge() {
     println!("cargo:rustc-link-lib=framework=CoreGraphics");
     println!("cargo:rustc-link-lib=framework=Foundation");
     println!("cargo:rustc-link-lib=framework=ImageIO");
+    // NSSharingServicePicker for the share sheet
+    println!("cargo:rustc-link-lib=framework=AppKit");
+    // App Intents (Shortcuts) exist from macOS 13; weak so older systems still launch
+    println!("cargo:rustc-link-arg=-Wl,-weak_framework,AppIntents");
 
     // Swift runtime search paths
     let xcode_path = std::process::Command::new("xcode-select")
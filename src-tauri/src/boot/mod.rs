@@ -0,0 +1,131 @@
+//! Launch sequencing — the tray first, everything else behind it.
+//!
+//! `setup::run` wraps each setup step in `phase`, so the startup report shows
+//! where launch time goes. Only what the tray and hotkeys need runs
+//! before setup returns; the rest warms up on background work started by
+//! `spawn_deferred`:
+//!   - OCR engine warm-up (Vision / Windows.Media.Ocr cold start)
+//!   - the snip history store, so the first snip or history view doesn't
+//!     read and index it
+//!   - plugin discovery, `PLUGIN_DELAY` after launch or at the first snip,
+//!     whichever comes first (`ensure_plugins`)
+//!
+//! Once the deferred work is done, the report is logged in one line;
+//! `get_startup_report` returns it.
+
+pub mod report;
+pub mod setup;
+
+use crate::mcp::{self, loader::PendingApprovals, ToolRegistry};
+use report::{Phase, StartupReport};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+/// How long after launch plugin discovery starts if no snip asks first.
+const PLUGIN_DELAY: Duration = Duration::from_millis(1500);
+
+static LAUNCH: OnceLock<Instant> = OnceLock::new();
+static REPORT: Mutex<Option<StartupReport>> = Mutex::new(None);
+static PLUGINS_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn since_launch(at: Instant) -> f64 {
+    let launch = *LAUNCH.get_or_init(Instant::now);
+    at.saturating_duration_since(launch).as_secs_f64() * 1000.0
+}
+
+fn with_report<T>(change: impl FnOnce(&mut StartupReport) -> T) -> T {
+    let mut guard = REPORT.lock().unwrap_or_else(|e| e.into_inner());
+    change(guard.get_or_insert_with(StartupReport::default))
+}
+
+fn record(name: &str, start: Instant, deferred: bool) {
+    let phase = Phase {
+        name: name.to_string(),
+        start_ms: since_launch(start),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        deferred,
+    };
+    log::debug!("[STARTUP] {} {:.1}ms", phase.name, phase.duration_ms);
+    with_report(|r| r.add(phase));
+}
+
+/// Note the start of launch. Called first thing in `run()`.
+pub fn mark_launch() {
+    LAUNCH.get_or_init(Instant::now);
+}
+
+/// Run a setup step on the critical path, timing it.
+pub fn phase<T>(name: &str, work: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let out = work();
+    record(name, start, false);
+    out
+}
+
+/// Setup is done and the tray is up.
+pub fn tray_ready() {
+    let at = since_launch(Instant::now());
+    with_report(|r| r.tray_ready_ms = Some(at));
+    log::info!("[STARTUP] Tray ready in {:.0}ms", at);
+}
+
+/// Start the deferred warm-ups (see module docs).
+pub fn spawn_deferred(app: &tauri::AppHandle) {
+    std::thread::spawn(|| {
+        let start = Instant::now();
+        crate::ocr::warm_up();
+        record("ocr_warm_up", start, true);
+
+        let start = Instant::now();
+        crate::history::preload();
+        record("history", start, true);
+    });
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(PLUGIN_DELAY).await;
+        ensure_plugins(&app);
+    });
+}
+
+/// Start plugin discovery if it hasn't started yet. Called by the
+/// deferred task and by snip mode, whose menu lists plugin actions.
+pub fn ensure_plugins(app: &tauri::AppHandle) {
+    if PLUGINS_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    // Register built-in tools first, then scan for external plugins.
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let start = Instant::now();
+        let registry = handle.state::<ToolRegistry>();
+        let pending = handle.state::<PendingApprovals>();
+        mcp::builtins::register_builtins(&registry).await;
+        mcp::loader::load_plugins(&registry, &pending).await;
+        let unconnected = mcp::remote::load_remote_servers(&registry).await;
+        mcp::remote::spawn_health_checks(handle.clone(), unconnected);
+        mcp::health::spawn_supervisor(handle.clone());
+        mcp::schedule::spawn_scheduler(handle.clone());
+        crate::session::resume_downloads(&handle).await;
+        record("plugins", start, true);
+        log::info!("[STARTUP] {}", with_report(|r| r.summary()));
+
+        // If any plugins are queued for approval, open the prompt window
+        let has_pending = !pending.queue.lock().await.is_empty();
+        if has_pending {
+            log::info!("[MCP] Opening permission prompt for pending plugins");
+            mcp::approval_commands::open_permission_prompt(&handle);
+        }
+    });
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the startup phases so far, with time to tray and to
+/// fully warm.
+#[tauri::command]
+pub fn get_startup_report() -> StartupReport {
+    with_report(|r| r.clone())
+}
@@ -0,0 +1,83 @@
+//! Startup phase report (pure).
+//!
+//! Each setup step is a `Phase`, offset from the start of `run()`.
+//! Phases run before the tray is up are on the critical path; deferred
+//! ones warm up behind it. `summary` is the one-line form that is logged
+//! once the deferred work is done.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Phase {
+    pub name: String,
+    pub start_ms: f64,
+    pub duration_ms: f64,
+    /// Ran in the background after the tray appeared.
+    pub deferred: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupReport {
+    pub phases: Vec<Phase>,
+    /// When setup returned and the tray was up.
+    pub tray_ready_ms: Option<f64>,
+    /// When the last deferred phase finished.
+    pub warm_ms: Option<f64>,
+}
+
+impl StartupReport {
+    pub fn add(&mut self, phase: Phase) {
+        if phase.deferred {
+            let end = phase.start_ms + phase.duration_ms;
+            self.warm_ms = Some(self.warm_ms.map_or(end, |w| w.max(end)));
+        }
+        self.phases.push(phase);
+    }
+
+    /// "tray in 42ms (tray 20ms, …); warm in 950ms (plugins 900ms, …)",
+    /// each side listing its phases slowest first.
+    pub fn summary(&self) -> String {
+        let list = |deferred: bool| {
+            let mut phases: Vec<&Phase> = self.phases.iter().filter(|p| p.deferred == deferred).collect();
+            phases.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+            phases.iter().map(|p| format!("{} {:.0}ms", p.name, p.duration_ms)).collect::<Vec<_>>().join(", ")
+        };
+        let at = |ms: Option<f64>| ms.map_or("?".to_string(), |ms| format!("{:.0}ms", ms));
+        format!("tray in {} ({}); warm in {} ({})", at(self.tray_ready_ms), list(false), at(self.warm_ms), list(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase(name: &str, start_ms: f64, duration_ms: f64, deferred: bool) -> Phase {
+        Phase { name: name.to_string(), start_ms, duration_ms, deferred }
+    }
+
+    #[test]
+    fn warm_time_is_the_end_of_the_last_deferred_phase() {
+        let mut report = StartupReport::default();
+        report.add(phase("tray", 5.0, 20.0, false));
+        assert_eq!(report.warm_ms, None);
+        report.add(phase("plugins", 1500.0, 400.0, true));
+        report.add(phase("ocr_warm_up", 30.0, 180.0, true));
+        assert_eq!(report.warm_ms, Some(1900.0));
+    }
+
+    #[test]
+    fn summary_lists_each_side_slowest_first() {
+        let mut report = StartupReport::default();
+        report.add(phase("policy", 0.0, 2.0, false));
+        report.add(phase("tray", 2.0, 20.4, false));
+        report.add(phase("history", 30.0, 12.0, true));
+        report.add(phase("ocr_warm_up", 30.0, 180.0, true));
+        report.tray_ready_ms = Some(31.0);
+        assert_eq!(
+            report.summary(),
+            "tray in 31ms (tray 20ms, policy 2ms); warm in 210ms (ocr_warm_up 180ms, history 12ms)"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! App setup — what runs once the Tauri app is built, before the event
+//! loop starts: the tray, listeners that need the app handle, and the
+//! background monitors. Each critical-path step is timed as a phase.
+
+use super::{phase, spawn_deferred, tray_ready};
+use crate::{
+    crash, deeplink, desktop, intents, mcp, net, onboarding, policy, power, presenting, safety, session, settings,
+    tray, triggers, updater,
+};
+
+/// The `setup` hook of the Tauri builder.
+pub fn run(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Omni-Glass starting up");
+    let handle = app.handle();
+    crash::attach(handle);
+
+    // A Linux desktop without a tray host gets the trigger window
+    // instead of failing to start.
+    let tray = phase("tray", || tray::setup_tray(handle));
+    if let Err(e) = &tray {
+        log::error!("[DESKTOP] Tray icon failed: {}", e);
+    }
+    phase("desktop", || desktop::attach(handle, tray.is_ok()));
+    phase("listeners", || {
+        mcp::violations::attach(handle.clone());
+        mcp::devmode::logs::attach(handle.clone());
+        mcp::clipboard::attach(handle.clone());
+        safety::events::attach(handle.clone());
+    });
+    phase("settings", || settings::attach(handle));
+    phase("session_restore", || session::restore(handle));
+    phase("intents", || intents::attach(handle));
+    phase("deeplink", || deeplink::attach(handle));
+    phase("triggers", || triggers::attach(handle));
+    phase("onboarding", || onboarding::open_if_needed(handle));
+    policy::warn_if_rejected(handle);
+    updater::spawn_checker(handle.clone());
+    net::connectivity::spawn_monitor(handle.clone());
+    power::spawn_monitor(handle.clone());
+    presenting::spawn_monitor(handle.clone());
+
+    // OCR warm-up, history, and plugin discovery load behind the tray.
+    spawn_deferred(handle);
+
+    tray_ready();
+    Ok(())
+}
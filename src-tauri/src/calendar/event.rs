@@ -0,0 +1,263 @@
+//! Calendar event: LLM fields validated into an `.ics` file (pure).
+//!
+//! The LLM only reads the snip; everything a calendar app would choke on
+//! is checked here. Dates must exist, times must be real, a missing end
+//! becomes one hour (or one day), an end before the start on the same day
+//! rolls over midnight (`10pm–1am`), and times with a known zone are
+//! converted to UTC (`zones.rs`). A time with no zone stays floating.
+
+use super::zones;
+use serde::Deserialize;
+
+/// Longest event accepted, so a misread year doesn't make a decade-long event.
+const MAX_DAYS: i64 = 31;
+const MAX_TITLE_CHARS: usize = 200;
+const DAY: i64 = 24 * 60;
+
+/// Event fields as the LLM returns them (`EVENT_SCHEMA` in `mod.rs`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Draft {
+    pub title: String,
+    /// `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM[:SS]`, optionally with `Z` or
+    /// an offset.
+    pub start: String,
+    #[serde(default)]
+    pub end: Option<String>,
+    #[serde(default)]
+    pub all_day: bool,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A point in time: minutes since the Unix epoch, in UTC when `utc`,
+/// else in floating local time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Moment {
+    minutes: i64,
+    utc: bool,
+}
+
+/// A validated event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub title: String,
+    start: Moment,
+    end: Moment,
+    pub all_day: bool,
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Event {
+    pub fn from_draft(draft: &Draft) -> Result<Event, String> {
+        let title: String = draft.title.trim().chars().take(MAX_TITLE_CHARS).collect();
+        if title.is_empty() {
+            return Err("the event has no title".to_string());
+        }
+        // A date has no zone; only a time is converted
+        let zone = draft.timezone.as_deref().filter(|z| !z.trim().is_empty() && !draft.all_day);
+        let (start, start_timed) = moment(&draft.start, zone).map_err(|e| format!("start: {}", e))?;
+        let all_day = draft.all_day || !start_timed;
+        let end = match draft.end.as_deref().filter(|e| !e.trim().is_empty()) {
+            Some(end) => moment(end, zone).map_err(|e| format!("end: {}", e))?.0,
+            None if all_day => start,
+            None => Moment { minutes: start.minutes + 60, ..start },
+        };
+        let (start, mut end) = if all_day {
+            // An end date is inclusive in speech, exclusive in iCalendar
+            (start_of_day(start), Moment { minutes: start_of_day(end).minutes + DAY, utc: false })
+        } else {
+            (start, end)
+        };
+        if !all_day && end.minutes <= start.minutes && start.minutes - end.minutes < DAY {
+            end.minutes += DAY;
+        }
+        if end.minutes <= start.minutes {
+            return Err("the event ends before it starts".to_string());
+        }
+        if end.minutes - start.minutes > MAX_DAYS * DAY {
+            return Err(format!("the event is longer than {} days", MAX_DAYS));
+        }
+        let clean = |s: &Option<String>| s.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+        Ok(Event { title, start, end, all_day, location: clean(&draft.location), description: clean(&draft.description) })
+    }
+
+    /// `Team sync — 2026-03-03 14:00–15:00 UTC` for the result note.
+    pub fn describe(&self) -> String {
+        let (date, time) = stamp_parts(self.start);
+        if self.all_day {
+            let days = (self.end.minutes - self.start.minutes) / DAY;
+            let span = if days > 1 { format!(" ({} days)", days) } else { String::new() };
+            return format!("{} — {}{}", self.title, date, span);
+        }
+        let (end_date, end_time) = stamp_parts(self.end);
+        let end = if end_date == date { end_time } else { format!("{} {}", end_date, end_time) };
+        format!("{} — {} {}–{}{}", self.title, date, time, end, if self.start.utc { " UTC" } else { "" })
+    }
+
+    /// The event as an iCalendar document (RFC 5545).
+    pub fn to_ics(&self, uid: &str, now_secs: u64) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//Omni-Glass//Calendar Export//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", uid),
+            format!("DTSTAMP:{}", ics_time(Moment { minutes: (now_secs / 60) as i64, utc: true })),
+        ];
+        if self.all_day {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", ics_date(self.start)));
+            lines.push(format!("DTEND;VALUE=DATE:{}", ics_date(self.end)));
+        } else {
+            lines.push(format!("DTSTART:{}", ics_time(self.start)));
+            lines.push(format!("DTEND:{}", ics_time(self.end)));
+        }
+        lines.push(format!("SUMMARY:{}", escape(&self.title)));
+        if let Some(location) = &self.location {
+            lines.push(format!("LOCATION:{}", escape(location)));
+        }
+        if let Some(description) = &self.description {
+            lines.push(format!("DESCRIPTION:{}", escape(description)));
+        }
+        lines.extend(["END:VEVENT".to_string(), "END:VCALENDAR".to_string()]);
+        lines.iter().map(|l| fold(l)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+    }
+}
+
+/// Parse a date or date-time; the flag says whether a time was given.
+fn moment(text: &str, zone: Option<&str>) -> Result<(Moment, bool), String> {
+    let text = text.trim();
+    let (date, time) = match text.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (text, None),
+    };
+    let parts: Vec<&str> = date.split('-').collect();
+    let [y, m, d] = parts[..] else { return Err(format!("'{}' is not a YYYY-MM-DD date", date)) };
+    let (y, m, d) = match (y.parse::<i64>(), m.parse::<u32>(), d.parse::<u32>()) {
+        (Ok(y), Ok(m), Ok(d)) if (1970..=2100).contains(&y) && (1..=12).contains(&m) => (y, m, d),
+        _ => return Err(format!("'{}' is not a valid date", date)),
+    };
+    if d == 0 || d > zones::days_in_month(y, m) {
+        return Err(format!("{}-{:02} has no day {}", y, m, d));
+    }
+    let day_minutes = zones::days_from_civil(y, m, d) * DAY;
+    let Some(time) = time else {
+        return Ok((Moment { minutes: day_minutes, utc: false }, false));
+    };
+    let split = time.find(['Z', 'z', '+', '-']).unwrap_or(time.len());
+    let (clock, suffix) = time.split_at(split);
+    let fields: Vec<&str> = clock.split(':').collect();
+    let (h, mi) = match fields[..] {
+        [h, mi] | [h, mi, _] => (h.parse::<i64>().ok(), mi.parse::<i64>().ok()),
+        _ => (None, None),
+    };
+    let (Some(h), Some(mi)) = (h, mi) else { return Err(format!("'{}' is not an HH:MM time", clock)) };
+    if !(0..24).contains(&h) || !(0..60).contains(&mi) {
+        return Err(format!("{:02}:{:02} is not a time of day", h, mi));
+    }
+    let local = day_minutes + h * 60 + mi;
+    let offset = if suffix.eq_ignore_ascii_case("z") {
+        Some(0)
+    } else if !suffix.is_empty() {
+        Some(zones::parse_offset(suffix).ok_or_else(|| format!("'{}' is not a UTC offset", suffix))?)
+    } else {
+        zone.and_then(|z| zones::offset_minutes(z, (y, m, d)))
+    };
+    let moment = match offset {
+        Some(offset) => Moment { minutes: local - i64::from(offset), utc: true },
+        None => Moment { minutes: local, utc: false },
+    };
+    Ok((moment, true))
+}
+
+fn start_of_day(m: Moment) -> Moment {
+    Moment { minutes: m.minutes.div_euclid(DAY) * DAY, utc: false }
+}
+
+/// (`2026-03-03`, `14:00`).
+fn stamp_parts(m: Moment) -> (String, String) {
+    let (y, mo, d, h, mi, _) = crate::capture::autosave::utc_parts(m.minutes as u64 * 60);
+    (format!("{:04}-{:02}-{:02}", y, mo, d), format!("{:02}:{:02}", h, mi))
+}
+
+fn ics_date(m: Moment) -> String {
+    stamp_parts(m).0.replace('-', "")
+}
+
+fn ics_time(m: Moment) -> String {
+    let (date, time) = stamp_parts(m);
+    format!("{}T{}00{}", date.replace('-', ""), time.replace(':', ""), if m.utc { "Z" } else { "" })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace("\r\n", "\\n").replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, never inside a UTF-8 character.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(start: &str, end: Option<&str>, timezone: Option<&str>) -> Draft {
+        Draft {
+            title: "Team sync".to_string(),
+            start: start.to_string(),
+            end: end.map(str::to_string),
+            timezone: timezone.map(str::to_string),
+            ..Draft::default()
+        }
+    }
+
+    #[test]
+    fn times_are_validated_and_zoned() {
+        let e = Event::from_draft(&draft("2026-07-14T15:00", Some("2026-07-14T16:30"), Some("PST"))).unwrap();
+        assert_eq!(e.describe(), "Team sync — 2026-07-14 22:00–23:30 UTC");
+        let e = Event::from_draft(&draft("2026-03-03 22:00", Some("2026-03-03 01:00"), None)).unwrap();
+        assert_eq!(e.describe(), "Team sync — 2026-03-03 22:00–2026-03-04 01:00");
+        let e = Event::from_draft(&draft("2026-03-03T09:00+01:00", None, Some("PST"))).unwrap();
+        assert_eq!(e.describe(), "Team sync — 2026-03-03 08:00–09:00 UTC");
+        assert!(Event::from_draft(&draft("2026-02-29T10:00", None, None)).unwrap_err().contains("no day 29"));
+        assert!(Event::from_draft(&draft("2026-03-03T25:00", None, None)).is_err());
+        assert!(Event::from_draft(&draft("March 3", None, None)).is_err());
+        assert!(Event::from_draft(&draft("2026-03-03", Some("2027-03-03"), None)).is_err());
+    }
+
+    #[test]
+    fn ics_is_well_formed() {
+        let mut d = draft("2026-03-03", Some("2026-03-04"), None);
+        d.location = Some("Room 4; Floor 2, HQ".to_string());
+        d.description = Some(format!("Agenda:\n{}", "é".repeat(60)));
+        let e = Event::from_draft(&d).unwrap();
+        assert!(e.all_day);
+        assert_eq!(e.describe(), "Team sync — 2026-03-03 (2 days)");
+        let ics = e.to_ics("abc@omni-glass", 1_772_539_200);
+        assert!(ics.contains("\r\nDTSTART;VALUE=DATE:20260303\r\nDTEND;VALUE=DATE:20260305\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20260303T120000Z\r\n"));
+        assert!(ics.contains("LOCATION:Room 4\\; Floor 2\\, HQ\r\n"));
+        assert!(ics.split("\r\n").all(|l| l.len() <= 75));
+        assert!(ics.contains("DESCRIPTION:Agenda:\\néé"));
+        let timed = Event::from_draft(&draft("2026-03-03T14:00", None, Some("UTC"))).unwrap();
+        assert!(timed.to_ics("x", 0).contains("DTSTART:20260303T140000Z\r\nDTEND:20260303T150000Z\r\n"));
+    }
+}
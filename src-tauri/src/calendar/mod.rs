@@ -0,0 +1,96 @@
+//! Calendar domain — "Add to Calendar" for dates and event announcements.
+//!
+//! The LLM reads the snip into event fields (title, start, end, zone,
+//! location) through the args bridge, which validates them against
+//! `EVENT_SCHEMA` and asks once for a repair. Rust then checks the dates
+//! and converts the zone (`event.rs`, `zones.rs`) and writes an `.ics`
+//! file. The result is a file result: the user saves it, and "Open File"
+//! hands it to the default calendar app with the event filled in.
+
+pub mod event;
+pub mod zones;
+
+use crate::capture::autosave::{slugify, utc_parts};
+use crate::i18n::tf;
+use crate::llm::execute::{ActionResult, ActionResultBody, ActionResultMetadata};
+use event::{Draft, Event};
+use std::sync::LazyLock;
+
+/// Action id, answered here after one structured-extraction LLM call.
+pub const EVENT_ACTION: &str = "add_to_calendar";
+
+const MAX_STEM_CHARS: usize = 40;
+
+/// The fields the LLM is asked for.
+static EVENT_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(|| {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string", "minLength": 1, "description": "Short event title" },
+            "start": {
+                "type": "string",
+                "pattern": "^\\d{4}-\\d{2}-\\d{2}([T ]\\d{2}:\\d{2}(:\\d{2})?(Z|[+-]\\d{2}:?\\d{2})?)?$",
+                "description": "YYYY-MM-DD for an all-day event, else YYYY-MM-DDTHH:MM in the event's own time zone"
+            },
+            "end": { "type": "string", "description": "Same format as start; omit when not stated" },
+            "allDay": { "type": "boolean" },
+            "timezone": {
+                "type": "string",
+                "description": "Time zone exactly as stated (PST, Europe/Berlin, UTC+2); omit when not stated"
+            },
+            "location": { "type": "string", "description": "Address, room, or meeting link" },
+            "description": { "type": "string", "description": "Agenda or other details, briefly" }
+        },
+        "required": ["title", "start"],
+        "additionalProperties": false
+    })
+});
+
+/// Extract the event in `text`, validate it, and return the `.ics` file.
+pub async fn create_event(text: &str) -> ActionResult {
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+    let (y, m, d, ..) = utc_parts(now.as_secs());
+    let weekday = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"]
+        [zones::weekday(zones::days_from_civil(y, m, d)) as usize];
+    let description = format!(
+        "Extract the calendar event from the text. Today is {} {:04}-{:02}-{:02}; resolve relative dates \
+         (\"next Tuesday\") and missing years from it.",
+        weekday, y, m, d
+    );
+    let fields =
+        match crate::llm::plugin_args::generate_plugin_args(EVENT_ACTION, &description, &EVENT_SCHEMA, text).await {
+            Ok(fields) => fields,
+            Err(e) => return failed(&e),
+        };
+    let event = match serde_json::from_value::<Draft>(fields).map_err(|e| e.to_string()).and_then(|d| Event::from_draft(&d)) {
+        Ok(event) => event,
+        Err(e) => return failed(&e),
+    };
+    let uid = format!("{}-{:09}@omni-glass", now.as_secs(), now.subsec_nanos());
+    let ics = event.to_ics(&uid, now.as_secs());
+    let stem = match slugify(&event.title, MAX_STEM_CHARS) {
+        stem if stem.is_empty() => "event".to_string(),
+        stem => stem,
+    };
+    let summary = event.describe();
+    log::info!("[CALENDAR] Event ready: {}", summary);
+    ActionResult {
+        status: "success".to_string(),
+        action_id: EVENT_ACTION.to_string(),
+        result: ActionResultBody {
+            result_type: "file".to_string(),
+            text: Some(ics),
+            file_path: Some(format!("{}.ics", stem)),
+            command: None,
+            clipboard_content: None,
+            mime_type: Some("text/calendar".to_string()),
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata { processing_note: Some(summary), ..ActionResultMetadata::default() }),
+    }
+}
+
+fn failed(error: &str) -> ActionResult {
+    log::warn!("[CALENDAR] No event: {}", error);
+    ActionResult::error(EVENT_ACTION, &tf("error.eventInvalid", &[("error", error)]))
+}
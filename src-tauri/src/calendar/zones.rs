@@ -0,0 +1,156 @@
+//! Time zones for event times, without a tz database (pure).
+//!
+//! An event's zone comes from the LLM as whatever the snip said: `PST`,
+//! `Eastern Time`, `Europe/Berlin`, `UTC+2`. Each known name maps to a
+//! region with a standard offset and a daylight-saving rule, and the
+//! offset is worked out for the event's date. So `3pm PST` on a July
+//! date — a common slip — is read as Pacific Daylight Time, not as an
+//! hour off. Unknown names return `None` and the time stays floating
+//! (the calendar shows it in the viewer's own zone).
+
+/// When a region observes daylight-saving time (by local date; the
+/// changeover hour is ignored).
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dst {
+    None,
+    /// Second Sunday of March to first Sunday of November.
+    Us,
+    /// Last Sunday of March to last Sunday of October.
+    Eu,
+    /// First Sunday of October to first Sunday of April.
+    Au,
+}
+
+/// Names, standard offset in minutes, DST rule.
+const REGIONS: &[(&[&str], i32, Dst)] = &[
+    (&["pt", "pst", "pdt", "pacific", "pacific time", "america/los_angeles", "america/vancouver"], -480, Dst::Us),
+    (&["mt", "mst", "mdt", "mountain", "mountain time", "america/denver", "america/edmonton"], -420, Dst::Us),
+    (&["america/phoenix"], -420, Dst::None),
+    (&["ct", "cst", "cdt", "central", "central time", "america/chicago", "america/mexico_city"], -360, Dst::Us),
+    (&["et", "est", "edt", "eastern", "eastern time", "america/new_york", "america/toronto"], -300, Dst::Us),
+    (&["akst", "akdt", "alaska", "america/anchorage"], -540, Dst::Us),
+    (&["hst", "hawaii", "pacific/honolulu"], -600, Dst::None),
+    (&["utc", "gmt", "z", "zulu", "etc/utc"], 0, Dst::None),
+    (&["bst", "uk", "europe/london", "europe/dublin", "europe/lisbon"], 0, Dst::Eu),
+    (
+        &[
+            "cet", "cest", "europe/berlin", "europe/paris", "europe/madrid", "europe/rome", "europe/amsterdam",
+            "europe/brussels", "europe/vienna", "europe/zurich", "europe/stockholm", "europe/oslo",
+            "europe/copenhagen", "europe/warsaw", "europe/prague",
+        ],
+        60,
+        Dst::Eu,
+    ),
+    (&["eet", "eest", "europe/athens", "europe/helsinki", "europe/kyiv", "europe/kiev", "europe/bucharest"], 120, Dst::Eu),
+    (&["msk", "europe/moscow", "europe/istanbul"], 180, Dst::None),
+    (&["ist", "india", "asia/kolkata", "asia/calcutta"], 330, Dst::None),
+    (&["sgt", "hkt", "awst", "asia/singapore", "asia/hong_kong", "asia/shanghai", "australia/perth"], 480, Dst::None),
+    (&["jst", "kst", "asia/tokyo", "asia/seoul"], 540, Dst::None),
+    (&["aest", "aedt", "australia/sydney", "australia/melbourne", "australia/canberra"], 600, Dst::Au),
+    (&["australia/brisbane"], 600, Dst::None),
+];
+
+/// UTC offset in minutes of `zone` on the local date `(y, m, d)`.
+pub fn offset_minutes(zone: &str, (y, m, d): (i64, u32, u32)) -> Option<i32> {
+    let name = zone.trim().to_lowercase();
+    if let Some(offset) = parse_offset(&name) {
+        return Some(offset);
+    }
+    let (_, standard, dst) = REGIONS.iter().find(|(names, _, _)| names.contains(&name.as_str()))?;
+    Some(standard + if in_dst(*dst, y, m, d) { 60 } else { 0 })
+}
+
+/// `+02:00`, `-0530`, `UTC+2`, `GMT-5`, `utc+05:30`.
+pub fn parse_offset(text: &str) -> Option<i32> {
+    let rest = text.trim().trim_start_matches("utc").trim_start_matches("gmt").trim();
+    let (sign, digits) = match rest.chars().next()? {
+        '+' => (1, &rest[1..]),
+        '-' | '−' => (-1, &rest[rest.chars().next()?.len_utf8()..]),
+        _ => return None,
+    };
+    let digits = digits.trim();
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None if digits.len() == 4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse::<i32>().ok()?),
+        None => (digits.parse::<i32>().ok()?, 0),
+    };
+    (hours <= 14 && minutes < 60).then_some(sign * (hours * 60 + minutes))
+}
+
+fn in_dst(rule: Dst, y: i64, m: u32, d: u32) -> bool {
+    let date = (m, d);
+    match rule {
+        Dst::None => false,
+        Dst::Us => date >= (3, nth_sunday(y, 3, 2)) && date < (11, nth_sunday(y, 11, 1)),
+        Dst::Eu => date >= (3, last_sunday(y, 3)) && date < (10, last_sunday(y, 10)),
+        Dst::Au => date < (4, nth_sunday(y, 4, 1)) || date >= (10, nth_sunday(y, 10, 1)),
+    }
+}
+
+/// Day of the month of the `n`th Sunday.
+fn nth_sunday(y: i64, m: u32, n: u32) -> u32 {
+    let first = weekday(days_from_civil(y, m, 1));
+    1 + (7 - first) % 7 + 7 * (n - 1)
+}
+
+fn last_sunday(y: i64, m: u32) -> u32 {
+    let last = days_in_month(y, m);
+    last - weekday(days_from_civil(y, m, last))
+}
+
+/// 0 = Sunday.
+pub fn weekday(days: i64) -> u32 {
+    (days + 4).rem_euclid(7) as u32
+}
+
+pub fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        4 | 6 | 9 | 11 => 30,
+        2 if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (inverse of
+/// `capture::autosave::utc_parts`).
+pub fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = i64::from((m + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_days_and_sundays() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(weekday(days_from_civil(2026, 10, 16)), 5);
+        assert_eq!(nth_sunday(2026, 3, 2), 8);
+        assert_eq!(nth_sunday(2026, 11, 1), 1);
+        assert_eq!(last_sunday(2026, 3), 29);
+        assert_eq!(last_sunday(2026, 10), 25);
+    }
+
+    #[test]
+    fn offsets_follow_daylight_saving() {
+        assert_eq!(offset_minutes("PST", (2026, 7, 14)), Some(-420));
+        assert_eq!(offset_minutes("Pacific Time", (2026, 1, 14)), Some(-480));
+        assert_eq!(offset_minutes("EDT", (2026, 3, 7)), Some(-300));
+        assert_eq!(offset_minutes("Europe/Berlin", (2026, 3, 29)), Some(120));
+        assert_eq!(offset_minutes("CET", (2026, 3, 28)), Some(60));
+        assert_eq!(offset_minutes("AEST", (2026, 1, 5)), Some(660));
+        assert_eq!(offset_minutes("IST", (2026, 7, 1)), Some(330));
+        assert_eq!(offset_minutes("UTC+5:30", (2026, 7, 1)), Some(330));
+        assert_eq!(offset_minutes("GMT-5", (2026, 7, 1)), Some(-300));
+        assert_eq!(parse_offset("-0800"), Some(-480));
+        assert_eq!(offset_minutes("Mars/Olympus", (2026, 7, 1)), None);
+    }
+}
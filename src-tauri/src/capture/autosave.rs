@@ -0,0 +1,231 @@
+//! Auto-save raw snips — every cropped PNG (and optionally its OCR text)
+//! written to a user-chosen folder.
+//!
+//! Runs as a post-crop hook once the snip is classified, because the
+//! filename template can use the content type and summary. Placeholders:
+//! `{date}` (YYYY-MM-DD), `{time}` (HH-MM-SS), both UTC, `{type}`, and
+//! `{summary}` (slugged). A name that is already taken gets `-2`, `-3`…
+//! appended; files are opened with `create_new`, so nothing is ever
+//! overwritten. Off by default; settings live in
+//! `~/.config/omni-glass/snip-autosave.json`.
+
+use crate::llm::types::ActionMenu;
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_TEMPLATE: &str = "{date}_{time}_{type}_{summary}";
+
+/// Characters of the summary kept in `{summary}`.
+const MAX_SLUG_CHARS: usize = 40;
+/// Characters of a rendered file stem.
+const MAX_STEM_CHARS: usize = 120;
+/// Numbered variants tried before giving up on a name.
+const MAX_COLLISIONS: u32 = 999;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutosaveConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Absolute folder the files go to; created if missing.
+    #[serde(default)]
+    pub folder: String,
+    #[serde(default = "default_template")]
+    pub template: String,
+    /// Also write `<name>.txt` with the OCR text.
+    #[serde(default)]
+    pub write_text: bool,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            folder: dirs::picture_dir()
+                .map(|p| p.join("Omni-Glass").to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            template: default_template(),
+            write_text: false,
+        }
+    }
+}
+
+impl AutosaveConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.template.trim().is_empty() {
+            return Err("Filename template can't be empty".to_string());
+        }
+        if self.template.contains(['/', '\\']) {
+            return Err("Filename template can't contain path separators".to_string());
+        }
+        if self.enabled && !Path::new(&self.folder).is_absolute() {
+            return Err("Choose an absolute folder for saved snips".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase words joined by `-`, ASCII letters and digits only.
+pub fn slugify(text: &str, max_chars: usize) -> String {
+    let mut slug = String::new();
+    for word in text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        if slug.len() + word.len() + 1 > max_chars {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug
+}
+
+/// UTC (year, month, day, hour, minute, second) for Unix seconds.
+pub(crate) fn utc_parts(secs: u64) -> (i64, u32, u32, u32, u32, u32) {
+    let days = (secs / 86_400) as i64;
+    let rem = (secs % 86_400) as u32;
+    // Days-to-civil conversion (proleptic Gregorian, era-based)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day, rem / 3600, rem / 60 % 60, rem % 60)
+}
+
+/// File stem for a snip taken at `now` (Unix seconds).
+pub fn render_stem(template: &str, now: u64, content_type: &str, summary: &str) -> String {
+    let (y, mo, d, h, mi, s) = utc_parts(now);
+    let content_type = slugify(content_type, MAX_SLUG_CHARS);
+    let rendered = template
+        .replace("{date}", &format!("{:04}-{:02}-{:02}", y, mo, d))
+        .replace("{time}", &format!("{:02}-{:02}-{:02}", h, mi, s))
+        .replace("{type}", if content_type.is_empty() { "unknown" } else { &content_type })
+        .replace("{summary}", &slugify(summary, MAX_SLUG_CHARS));
+    let stem: String = rendered
+        .chars()
+        .filter(|c| c.is_alphanumeric() || "-_. ".contains(*c))
+        .take(MAX_STEM_CHARS)
+        .collect();
+    let stem = stem.trim_matches(['-', '_', '.', ' ']);
+    if stem.is_empty() { "snip".to_string() } else { stem.to_string() }
+}
+
+/// `stem`, then `stem-2`, `stem-3`…
+fn numbered(stem: &str, n: u32) -> String {
+    if n == 1 { stem.to_string() } else { format!("{}-{}", stem, n) }
+}
+
+/// Write the PNG (and sidecar) under the first free name; returns the
+/// PNG path. The sidecar takes the same number so the pair matches.
+pub fn write_snip(dir: &Path, stem: &str, png: &[u8], text: Option<&str>) -> Result<PathBuf, String> {
+    use std::io::Write;
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for n in 1..=MAX_COLLISIONS {
+        let name = numbered(stem, n);
+        let png_path = dir.join(format!("{}.png", name));
+        let txt_path = dir.join(format!("{}.txt", name));
+        if text.is_some() && txt_path.exists() {
+            continue;
+        }
+        let mut file = match std::fs::OpenOptions::new().write(true).create_new(true).open(&png_path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", png_path.display(), e)),
+        };
+        file.write_all(png).map_err(|e| format!("Failed to write {}: {}", png_path.display(), e))?;
+        if let Some(text) = text {
+            std::fs::write(&txt_path, text).map_err(|e| format!("Failed to write {}: {}", txt_path.display(), e))?;
+        }
+        return Ok(png_path);
+    }
+    Err(format!("No free filename for '{}' in {}", stem, dir.display()))
+}
+
+static CONFIG: JsonStore<AutosaveConfig> = JsonStore::new("snip-autosave.json");
+
+/// Current auto-save settings.
+pub fn current() -> AutosaveConfig {
+    CONFIG.get()
+}
+
+/// Post-crop hook: save the snip in the background if auto-save is on.
+pub fn save_snip(png: Vec<u8>, ocr_text: &str, menu: &ActionMenu) {
+    let config = current();
+    if !config.enabled {
+        return;
+    }
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let stem = render_stem(&config.template, now, &menu.content_type, &menu.summary);
+    let text = config.write_text.then(|| ocr_text.to_string());
+    tauri::async_runtime::spawn_blocking(move || {
+        match write_snip(Path::new(&config.folder), &stem, &png, text.as_deref()) {
+            Ok(path) => log::info!("[CAPTURE] Auto-saved snip to {}", path.display()),
+            Err(e) => log::warn!("[CAPTURE] Auto-save failed: {}", e),
+        }
+    });
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: current auto-save settings.
+#[tauri::command]
+pub fn get_snip_autosave() -> AutosaveConfig {
+    current()
+}
+
+/// Tauri command: validate and persist auto-save settings.
+#[tauri::command]
+pub fn set_snip_autosave(config: AutosaveConfig) -> Result<(), String> {
+    config.validate()?;
+    let enabled = config.enabled;
+    CONFIG.try_update(|c| {
+        *c = config;
+        Ok(())
+    })?;
+    log::info!("[SETTINGS] Snip auto-save {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_render_date_type_and_summary() {
+        // 2024-02-29 13:05:09 UTC
+        let stem = render_stem(DEFAULT_TEMPLATE, 1_709_211_909, "error", "TypeError: Cannot read 'map' of undefined");
+        assert_eq!(stem, "2024-02-29_13-05-09_error_typeerror-cannot-read-map-of-undefined");
+        assert_eq!(render_stem("{type}/../{summary}", 0, "", ""), "unknown");
+        assert_eq!(render_stem("{summary}", 0, "code", "???"), "snip");
+        assert_eq!(utc_parts(0), (1970, 1, 1, 0, 0, 0));
+        assert_eq!(slugify("A very long summary that goes on", 12), "a-very-long");
+    }
+
+    #[test]
+    fn collisions_get_numbered_and_pairs_stay_matched() {
+        let dir = std::env::temp_dir().join(format!("og-autosave-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let first = write_snip(&dir, "snip", b"png", Some("text")).unwrap();
+        let second = write_snip(&dir, "snip", b"png", None).unwrap();
+        std::fs::write(dir.join("snip-3.txt"), "stray").unwrap();
+        let fourth = write_snip(&dir, "snip", b"png", Some("more")).unwrap();
+        assert_eq!(first.file_name().unwrap(), "snip.png");
+        assert_eq!(second.file_name().unwrap(), "snip-2.png");
+        assert_eq!(fourth.file_name().unwrap(), "snip-4.png");
+        assert_eq!(std::fs::read_to_string(dir.join("snip-4.txt")).unwrap(), "more");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
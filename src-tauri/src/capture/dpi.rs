@@ -0,0 +1,65 @@
+//! Coordinates across overlay, screenshot, and screen (pure, no I/O).
+//!
+//! A snip meets three spaces: the overlay's CSS pixels the selection is
+//! made in, the screenshot's pixels at the monitor's own scale, and the
+//! screen's physical pixels windows are placed in. On Windows every
+//! monitor can have its own DPI (100 %, 125 %, 150 %…), so logical
+//! positions on two monitors don't share a scale; only physical ones
+//! line up across the desktop.
+
+/// x, y, width, height in screenshot pixels.
+pub type PixelRect = (u32, u32, u32, u32);
+
+/// A selection (x, y, width, height in the CSS pixels of an overlay of
+/// `viewport`) in the pixels of a screenshot of `image` size. Edges are
+/// scaled and rounded, not the size, so at a fractional scale a
+/// selection against the right or bottom edge ends on the last pixel
+/// instead of one past it. `None` when nothing of it is on the image.
+pub fn to_screenshot((x, y, width, height): (f64, f64, f64, f64), viewport: (f64, f64), image: (u32, u32)) -> Option<PixelRect> {
+    let scale = |v: f64, css: f64, px: u32| (v * f64::from(px) / css).round().clamp(0.0, f64::from(px)) as u32;
+    let (left, right) = (scale(x, viewport.0, image.0), scale(x + width, viewport.0, image.0));
+    let (top, bottom) = (scale(y, viewport.1, image.1), scale(y + height, viewport.1, image.1));
+    (right > left && bottom > top).then_some((left, top, right - left, bottom - top))
+}
+
+/// `rect` cut to an image of `size`; a rectangle rounded one pixel past
+/// the edge by a scaled display would otherwise fail to crop. `None`
+/// when nothing of it is on the image.
+pub fn fit((x, y, width, height): PixelRect, size: (u32, u32)) -> Option<PixelRect> {
+    let (right, bottom) = (x.saturating_add(width).min(size.0), y.saturating_add(height).min(size.1));
+    (right > x && bottom > y).then_some((x, y, right - x, bottom - y))
+}
+
+/// A point in a monitor's CSS pixels as a physical screen position,
+/// given the monitor's physical `origin` and `scale` factor.
+pub fn to_physical((x, y): (f64, f64), origin: (f64, f64), scale: f64) -> (f64, f64) {
+    (origin.0 + x * scale, origin.1 + y * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selections_scale_to_the_screenshot_without_overshooting() {
+        // 1920x1080 at 125 %: the overlay is 1536x864 CSS pixels
+        let (viewport, image) = ((1536.0, 864.0), (1920, 1080));
+        assert_eq!(to_screenshot((100.0, 100.0, 200.0, 80.0), viewport, image), Some((125, 125, 250, 100)));
+        // Fractional CSS coordinates against the right edge end on it
+        let edge = to_screenshot((1200.3, 0.0, 335.7, 10.0), viewport, image).unwrap();
+        assert_eq!(edge.0 + edge.2, 1920);
+        // Dragged off the screen: cut to it; entirely off: nothing
+        assert_eq!(to_screenshot((-10.0, 800.0, 60.0, 200.0), viewport, image), Some((0, 1000, 63, 80)));
+        assert_eq!(to_screenshot((1600.0, 0.0, 50.0, 50.0), viewport, image), None);
+    }
+
+    #[test]
+    fn rects_fit_the_image_and_points_map_to_physical_pixels() {
+        assert_eq!(fit((1800, 1000, 121, 81), (1920, 1080)), Some((1800, 1000, 120, 80)));
+        assert_eq!(fit((10, 10, 5, 5), (1920, 1080)), Some((10, 10, 5, 5)));
+        assert_eq!(fit((1920, 0, 10, 10), (1920, 1080)), None);
+        // A 100 % monitor right of a 2560-pixel-wide 150 % one
+        assert_eq!(to_physical((100.0, 50.0), (2560.0, 0.0), 1.0), (2660.0, 50.0));
+        assert_eq!(to_physical((100.0, 50.0), (0.0, 0.0), 1.5), (150.0, 75.0));
+    }
+}
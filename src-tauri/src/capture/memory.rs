@@ -0,0 +1,90 @@
+//! Capture memory accounting (`get_memory_stats`).
+//!
+//! A 5K RGBA capture is ~60 MB. `CaptureState` drops it as soon as the
+//! snip is cropped or cancelled; this file keeps the numbers that show
+//! it worked: what is resident now, the high watermark since launch
+//! (logged each time it rises), and how much has been released.
+
+use super::CaptureState;
+use crate::llm;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static RELEASED: AtomicU64 = AtomicU64::new(0);
+static RELEASES: AtomicU64 = AtomicU64::new(0);
+
+/// Image buffers held for snips, in bytes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryStats {
+    /// Pixels of the full-screen capture; 0 once released.
+    pub screenshot_bytes: u64,
+    /// PNG from `crop_region`.
+    pub crop_bytes: u64,
+    /// PNG of the current snip, kept for re-OCR, palette, and sharing.
+    pub snip_crop_bytes: u64,
+    pub total_bytes: u64,
+    /// Highest total seen since launch.
+    pub peak_bytes: u64,
+    /// Full screenshots dropped after cropping or cancelling, and their size.
+    pub releases: u64,
+    pub released_bytes: u64,
+}
+
+fn mb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Note `total` resident bytes; logs and returns `true` on a new watermark.
+pub fn observe(total: u64) -> bool {
+    let raised = total > PEAK.fetch_max(total, Ordering::Relaxed);
+    if raised {
+        log::info!("[MEMORY] New capture memory watermark: {:.1} MB", mb(total));
+    }
+    raised
+}
+
+/// Note a full screenshot of `bytes` being dropped.
+pub fn released(bytes: u64) {
+    RELEASED.fetch_add(bytes, Ordering::Relaxed);
+    RELEASES.fetch_add(1, Ordering::Relaxed);
+    log::info!("[MEMORY] Released full screenshot ({:.1} MB)", mb(bytes));
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: image memory held for snips right now, and its peak.
+#[tauri::command]
+pub fn get_memory_stats(
+    capture: tauri::State<'_, CaptureState>,
+    menu: tauri::State<'_, llm::ActionMenuState>,
+) -> MemoryStats {
+    let (screenshot_bytes, crop_bytes) = capture.resident_bytes();
+    let snip_crop_bytes = menu.crop_png.lock().map_or(0, |png| png.as_ref().map_or(0, |p| p.len() as u64));
+    let total_bytes = screenshot_bytes + crop_bytes + snip_crop_bytes;
+    observe(total_bytes);
+    MemoryStats {
+        screenshot_bytes,
+        crop_bytes,
+        snip_crop_bytes,
+        total_bytes,
+        peak_bytes: PEAK.load(Ordering::Relaxed),
+        releases: RELEASES.load(Ordering::Relaxed),
+        released_bytes: RELEASED.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watermark_only_rises() {
+        assert!(observe(u64::MAX - 1));
+        assert!(!observe(5));
+        assert!(!observe(u64::MAX - 1));
+        assert_eq!(PEAK.load(Ordering::Relaxed), u64::MAX - 1);
+        assert!((mb(60 * 1024 * 1024) - 60.0).abs() < f64::EPSILON);
+    }
+}
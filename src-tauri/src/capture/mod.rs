@@ -3,29 +3,50 @@
 //! This module owns all screen capture functionality.
 //! External code should only use the public functions exported here.
 
+pub mod autosave;
+pub mod dpi;
+pub mod encode;
+pub mod memory;
+pub mod permission;
+pub mod protocol;
 mod region;
+pub mod rules;
 mod screenshot;
 
 pub use region::crop_to_png_bytes;
-pub use screenshot::capture_primary_monitor;
+pub use screenshot::{
+    capture_all_monitors, capture_focused_window, capture_for_snip, capture_primary_monitor, window_titles, CaptureError,
+};
 
 use image::DynamicImage;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 /// Info needed by the overlay to display the screenshot.
 /// Stored in CaptureState so the overlay can fetch it via a Tauri command
 /// (eliminates the race condition where an event fires before JS loads).
 #[derive(Clone, serde::Serialize)]
 pub struct CaptureInfo {
-    pub image_path: String,
+    /// `omni-image://` URL of the screenshot (`protocol.rs`).
+    pub image_url: String,
     pub click_epoch_ms: f64,
 }
 
 /// Thread-safe storage for the current full-screen capture.
 /// Held between capture and crop so the user can draw a rectangle.
+///
+/// Locks are held only to swap or clone an `Arc` — never while capturing,
+/// cropping, or encoding — so the capture task, the image protocol, and
+/// the pipeline don't wait on each other. A poisoned lock is recovered:
+/// the data behind it is replaced wholesale, never left half-written.
 pub struct CaptureState {
-    pub screenshot: Mutex<Option<DynamicImage>>,
-    pub capture_info: Mutex<Option<CaptureInfo>>,
+    screenshot: Mutex<Option<Arc<DynamicImage>>>,
+    capture_info: Mutex<Option<CaptureInfo>>,
+    /// PNG of the last `crop_region`, served at `omni-image://localhost/crop`.
+    crop_png: Mutex<Option<Arc<Vec<u8>>>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 impl CaptureState {
@@ -33,6 +54,68 @@ impl CaptureState {
         Self {
             screenshot: Mutex::new(None),
             capture_info: Mutex::new(None),
+            crop_png: Mutex::new(None),
         }
     }
+
+    /// A snip is starting: the overlay waits until `store` provides its info.
+    pub fn begin(&self) {
+        *lock(&self.capture_info) = None;
+    }
+
+    /// Keep a new capture; `info` is `None` when no overlay shows it.
+    pub fn store(&self, screenshot: DynamicImage, info: Option<CaptureInfo>) {
+        *lock(&self.screenshot) = Some(Arc::new(screenshot));
+        *lock(&self.capture_info) = info;
+        let (screenshot, crop) = self.resident_bytes();
+        memory::observe(screenshot + crop);
+    }
+
+    pub fn screenshot(&self) -> Option<Arc<DynamicImage>> {
+        lock(&self.screenshot).clone()
+    }
+
+    pub fn capture_info(&self) -> Option<CaptureInfo> {
+        lock(&self.capture_info).clone()
+    }
+
+    pub fn set_crop_png(&self, png: Vec<u8>) {
+        *lock(&self.crop_png) = Some(Arc::new(png));
+    }
+
+    pub fn crop_png(&self) -> Option<Arc<Vec<u8>>> {
+        lock(&self.crop_png).clone()
+    }
+
+    /// Drop the full-screen capture once the snip is cropped or the overlay
+    /// closes; returns the bytes freed. The last crop is kept.
+    pub fn release(&self) -> u64 {
+        let released = lock(&self.screenshot).take();
+        *lock(&self.capture_info) = None;
+        let bytes = released.map_or(0, |image| image.as_bytes().len() as u64);
+        if bytes > 0 {
+            memory::released(bytes);
+        }
+        bytes
+    }
+
+    /// Bytes held by the screenshot pixels and the crop PNG.
+    pub fn resident_bytes(&self) -> (u64, u64) {
+        let screenshot = lock(&self.screenshot).as_ref().map_or(0, |image| image.as_bytes().len() as u64);
+        let crop = lock(&self.crop_png).as_ref().map_or(0, |png| png.len() as u64);
+        (screenshot, crop)
+    }
+
+    /// Drop the capture and crop (history purge).
+    pub fn clear(&self) {
+        *lock(&self.screenshot) = None;
+        *lock(&self.capture_info) = None;
+        *lock(&self.crop_png) = None;
+    }
+}
+
+/// Tauri command: connected monitors' names, for the monitor picker.
+#[tauri::command]
+pub fn list_monitors() -> Vec<String> {
+    screenshot::monitor_names()
 }
@@ -0,0 +1,218 @@
+//! Screen Recording permission (macOS).
+//!
+//! Without it, macOS hands back a capture of the wallpaper instead of an
+//! error, so the pipeline would silently OCR nothing. Other platforms
+//! have no such gate; there the question is only whether a monitor can
+//! be enumerated at all (on Wayland, whether the desktop portal let us,
+//! or whether there is a portal to ask).
+//!
+//! `status` turns that into a state and a remedy for the UI. `checked`
+//! spots a capture the OS failed or quietly blanked and offers the fix in
+//! a dialog (`warn`) instead of leaving the user with an empty snip.
+
+use super::CaptureError;
+use crate::desktop::session::{CaptureBackend, Display};
+use crate::error::{Capture, OmniError};
+use crate::i18n::t;
+use image::{DynamicImage, GenericImageView};
+use serde::Serialize;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// Where the Screen Recording switch lives, if the OS has one to open.
+#[cfg(target_os = "macos")]
+const SETTINGS_URL: Option<&str> = Some("x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture");
+#[cfg(not(target_os = "macos"))]
+const SETTINGS_URL: Option<&str> = None;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionState {
+    Granted,
+    /// The user (or the portal) said no; they can change it.
+    Denied,
+    /// No monitor could be captured at all.
+    Unavailable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionStatus {
+    pub state: PermissionState,
+    /// `open_permission_settings` can take the user to the switch.
+    pub can_open_settings: bool,
+    /// What to do about it, when not granted.
+    pub remedy: Option<String>,
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGPreflightScreenCaptureAccess() -> bool;
+    fn CGRequestScreenCaptureAccess() -> bool;
+}
+
+/// Whether this process may capture the screen.
+pub fn screen_capture_allowed() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        // SAFETY: no arguments; reads the TCC state for this process.
+        unsafe { CGPreflightScreenCaptureAccess() }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        xcap::Monitor::all().map(|m| !m.is_empty()).unwrap_or(false)
+    }
+}
+
+/// Ask for access. On macOS this shows the system prompt once; after
+/// that the user has to flip the switch in System Settings (and
+/// relaunch). Returns the state right after asking.
+pub fn request_screen_capture() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        // SAFETY: no arguments; may show the system permission prompt.
+        unsafe { CGRequestScreenCaptureAccess() }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        screen_capture_allowed()
+    }
+}
+
+/// Whether this is a Wayland session, where xcap captures through the
+/// desktop portal.
+fn wayland() -> bool {
+    crate::desktop::display() == Display::Wayland
+}
+
+/// Status from what was probed.
+fn status_from(allowed: bool, macos: bool, wayland: bool) -> PermissionStatus {
+    let (state, remedy) = match (allowed, macos, wayland) {
+        (true, _, _) => (PermissionState::Granted, None),
+        (false, true, _) => (PermissionState::Denied, Some("capture.permissionMacos")),
+        (false, false, true) => (PermissionState::Denied, Some("capture.permissionWayland")),
+        (false, false, false) => (PermissionState::Unavailable, Some("capture.noMonitor")),
+    };
+    PermissionStatus {
+        state,
+        can_open_settings: macos && SETTINGS_URL.is_some(),
+        remedy: remedy.map(t),
+    }
+}
+
+fn status() -> PermissionStatus {
+    // No portal to ask: nothing the user can accept, only install
+    if crate::desktop::capture_backend() == CaptureBackend::Unavailable {
+        return PermissionStatus { state: PermissionState::Unavailable, can_open_settings: false, remedy: Some(t("capture.noPortal")) };
+    }
+    status_from(screen_capture_allowed(), cfg!(target_os = "macos"), wayland())
+}
+
+/// A uniformly black frame: what a denied portal or compositor returns
+/// in place of the screen. Checks a 16×16 grid of pixels.
+fn looks_blank(image: &DynamicImage) -> bool {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return true;
+    }
+    let first = image.get_pixel(0, 0);
+    let dark = first.0[..3].iter().all(|&c| c < 16);
+    dark && (0..16).all(|i| (0..16).all(|j| image.get_pixel(width * i / 16, height * j / 16) == first))
+}
+
+/// Whether a capture that succeeded is really the OS withholding the
+/// screen: macOS without permission returns the wallpaper alone.
+fn withheld(image: &DynamicImage) -> bool {
+    (cfg!(target_os = "macos") && !screen_capture_allowed()) || looks_blank(image)
+}
+
+/// A snip capture, made an error if the OS withheld the screen; the fix
+/// is shown to the user either way it was denied.
+pub fn checked(app: &tauri::AppHandle, capture: Result<DynamicImage, CaptureError>) -> Result<DynamicImage, OmniError> {
+    let denied = OmniError::from(Capture::PermissionDenied);
+    let result = match capture {
+        Ok(image) if withheld(&image) => Err(denied.clone()),
+        other => other.map_err(OmniError::from),
+    };
+    if result.as_ref().err() == Some(&denied) {
+        log::warn!("[CAPTURE] Screen withheld by the OS");
+        warn(app);
+    }
+    result
+}
+
+/// Explain a withheld capture in a dialog, offering to open the setting.
+fn warn(app: &tauri::AppHandle) {
+    let status = status();
+    let body = status.remedy.clone().unwrap_or_else(|| t("capture.blank"));
+    let dialog = app.dialog().message(body).title(t("capture.permissionTitle")).kind(MessageDialogKind::Warning);
+    if !status.can_open_settings {
+        dialog.show(|_| {});
+        return;
+    }
+    dialog
+        .buttons(MessageDialogButtons::OkCancelCustom(t("capture.openSettings"), t("capture.notNow")))
+        .show(|open| {
+            if open {
+                if let Err(e) = open_settings() {
+                    log::warn!("[CAPTURE] {}", e);
+                }
+            }
+        });
+}
+
+/// Open the OS page with the Screen Recording switch. On macOS the app
+/// is only listed there once it has asked, so ask first.
+fn open_settings() -> Result<(), OmniError> {
+    let url = SETTINGS_URL.ok_or(Capture::NoSettings)?;
+    request_screen_capture();
+    crate::links::system_open(url).spawn().map_err(|e| format!("Couldn't open System Settings: {}", e))?;
+    Ok(())
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: whether the screen can be captured, and the fix if not.
+#[tauri::command]
+pub fn get_capture_permission_status() -> PermissionStatus {
+    let status = status();
+    log::info!("[CAPTURE] Permission: {:?}", status.state);
+    status
+}
+
+/// Tauri command: open the OS setting that grants screen capture.
+#[tauri::command]
+pub fn open_permission_settings() -> Result<(), OmniError> {
+    open_settings()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn status_names_the_fix_for_each_platform() {
+        assert_eq!(status_from(true, true, false).state, PermissionState::Granted);
+        assert_eq!(status_from(true, true, false).remedy, None);
+        assert_eq!(status_from(false, true, false).state, PermissionState::Denied);
+        assert_eq!(status_from(false, false, true).state, PermissionState::Denied);
+        assert!(!status_from(false, false, true).can_open_settings);
+        let none = status_from(false, false, false);
+        assert_eq!(none.state, PermissionState::Unavailable);
+        assert!(none.remedy.is_some());
+    }
+
+    #[test]
+    fn only_a_uniform_black_frame_is_blank() {
+        let black = DynamicImage::ImageRgba8(RgbaImage::from_pixel(320, 200, Rgba([0, 0, 0, 255])));
+        assert!(looks_blank(&black));
+        let white = DynamicImage::ImageRgba8(RgbaImage::from_pixel(320, 200, Rgba([255, 255, 255, 255])));
+        assert!(!looks_blank(&white));
+        let mut dock = RgbaImage::from_pixel(320, 200, Rgba([0, 0, 0, 255]));
+        for x in 0..320 {
+            dock.put_pixel(x, 100, Rgba([230, 230, 230, 255]));
+        }
+        assert!(!looks_blank(&DynamicImage::ImageRgba8(dock)));
+    }
+}
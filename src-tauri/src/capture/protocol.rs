@@ -0,0 +1,171 @@
+//! `omni-image://` — the stored screenshot and crop, and history
+//! thumbnails, served straight to the webviews.
+//!
+//! `<img src="omni-image://localhost/screenshot?v=3">` is answered from
+//! `CaptureState` in memory: no temp file, no base64 string over IPC. The
+//! screenshot is encoded as a JPEG preview (`encode.rs`) off the main
+//! thread when the webview asks for it; a crop from `crop_region` is
+//! served as the lossless PNG already encoded; `thumbnail-<id>-<size>`
+//! is a snip's WebP from `thumbnails` (`thumbnail-<id>` is the medium size). On Windows the same
+//! resources are at `http://omni-image.localhost/…`, so `url()` builds the
+//! right form.
+//!
+//! Only the app's own windows (`windows::spec::Window`) are answered, and
+//! without CORS headers: an `<img>` needs none, and no other origin — a
+//! plugin console, a page a webview navigated to — may read the screen.
+
+use super::{encode, CaptureState};
+use crate::thumbnails::variant::Size;
+use crate::windows::spec::Window;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{Manager, Runtime, UriSchemeContext, UriSchemeResponder};
+
+/// Scheme registered in `lib.rs`; allowed in `img-src` by the CSP.
+pub const SCHEME: &str = "omni-image";
+
+/// What the protocol serves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resource {
+    /// The full-screen capture the overlay draws on.
+    Screenshot,
+    /// The last region cropped by `crop_region`.
+    Crop,
+    /// A snip's thumbnail, by history record id.
+    Thumbnail(u64, Size),
+}
+
+impl Resource {
+    fn path(self) -> String {
+        match self {
+            Resource::Screenshot => "screenshot".to_string(),
+            Resource::Crop => "crop".to_string(),
+            Resource::Thumbnail(id, size) => format!("thumbnail-{}-{}", id, size.name()),
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Resource::Screenshot => "image/jpeg",
+            Resource::Crop => "image/png",
+            Resource::Thumbnail(..) => "image/webp",
+        }
+    }
+}
+
+static VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// A fresh URL for `resource`. The version query keeps the webview from
+/// showing an earlier capture it has cached under the same path.
+pub fn url(resource: Resource) -> String {
+    let version = VERSION.fetch_add(1, Ordering::Relaxed) + 1;
+    let base = if cfg!(windows) {
+        format!("http://{}.localhost", SCHEME)
+    } else {
+        format!("{}://localhost", SCHEME)
+    };
+    format!("{}/{}?v={}", base, resource.path(), version)
+}
+
+/// The resource a request path names, or `None` for anything else.
+pub fn resource(path: &str) -> Option<Resource> {
+    match path.trim_start_matches('/') {
+        "screenshot" => Some(Resource::Screenshot),
+        "crop" => Some(Resource::Crop),
+        path => {
+            let thumbnail = path.strip_prefix("thumbnail-")?;
+            let (id, size) = match thumbnail.split_once('-') {
+                Some((id, size)) => (id, Size::parse(size)?),
+                None => (thumbnail, Size::Medium),
+            };
+            id.parse().ok().map(|id| Resource::Thumbnail(id, size))
+        }
+    }
+}
+
+fn load<R: Runtime>(app: &tauri::AppHandle<R>, resource: Resource) -> Result<Option<Vec<u8>>, String> {
+    let state = app.state::<CaptureState>();
+    match resource {
+        Resource::Screenshot => {
+            let quality = if crate::power::saver() { encode::SAVER_PREVIEW_QUALITY } else { encode::PREVIEW_QUALITY };
+            state.screenshot().map(|image| encode::preview_jpeg(&image, quality)).transpose()
+        }
+        Resource::Crop => Ok(state.crop_png().map(|png| png.to_vec())),
+        Resource::Thumbnail(id, size) => Ok(crate::thumbnails::load(id, size)),
+    }
+}
+
+fn respond(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(body)
+        .unwrap_or_default()
+}
+
+/// Whether the webview `label` may load captures: one of the app's own windows.
+fn serves(label: &str) -> bool {
+    Window::from_label(label).is_some()
+}
+
+/// Protocol handler, registered with `register_asynchronous_uri_scheme_protocol`.
+pub fn handle<R: Runtime>(ctx: UriSchemeContext<'_, R>, request: Request<Vec<u8>>, responder: UriSchemeResponder) {
+    let app = ctx.app_handle().clone();
+    let path = request.uri().path().to_string();
+    if !serves(ctx.webview_label()) {
+        log::warn!("[CAPTURE] Refused {} to webview '{}'", path, ctx.webview_label());
+        responder.respond(respond(StatusCode::FORBIDDEN, "text/plain", b"Forbidden".to_vec()));
+        return;
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let start = std::time::Instant::now();
+        let response = match resource(&path).map(|r| (r, load(&app, r))) {
+            Some((resource, Ok(Some(bytes)))) => {
+                log::info!("[CAPTURE] Served {} ({} bytes) in {}ms", path, bytes.len(), start.elapsed().as_millis());
+                respond(StatusCode::OK, resource.content_type(), bytes)
+            }
+            Some((_, Err(e))) => {
+                log::warn!("[CAPTURE] Could not serve {}: {}", path, e);
+                respond(StatusCode::INTERNAL_SERVER_ERROR, "text/plain", e.into_bytes())
+            }
+            _ => respond(StatusCode::NOT_FOUND, "text/plain", b"Not found".to_vec()),
+        };
+        responder.respond(response);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_are_versioned() {
+        let first = url(Resource::Screenshot);
+        let second = url(Resource::Screenshot);
+        assert_ne!(first, second);
+        let prefix = if cfg!(windows) { "http://omni-image.localhost/screenshot?v=" } else { "omni-image://localhost/screenshot?v=" };
+        assert!(first.starts_with(prefix));
+        assert!(url(Resource::Crop).contains("/crop?v="));
+    }
+
+    #[test]
+    fn resolves_paths() {
+        assert_eq!(resource("/screenshot"), Some(Resource::Screenshot));
+        assert_eq!(resource("crop"), Some(Resource::Crop));
+        assert_eq!(resource("/thumbnail-17"), Some(Resource::Thumbnail(17, Size::Medium)));
+        assert_eq!(resource("/thumbnail-17-small"), Some(Resource::Thumbnail(17, Size::Small)));
+        assert_eq!(resource("/thumbnail-x"), None);
+        assert_eq!(resource("/thumbnail-17-huge"), None);
+        assert_eq!(resource("/../etc/passwd"), None);
+        assert_eq!(resource("/"), None);
+    }
+
+    #[test]
+    fn only_app_windows_are_served() {
+        assert!(serves("overlay"));
+        assert!(serves("action-menu"));
+        assert!(!serves("plugin-console-com.example"));
+        assert!(!serves("permission-prompt"));
+    }
+}
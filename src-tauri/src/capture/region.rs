@@ -3,8 +3,7 @@
 //! This module has zero infrastructure dependencies.
 //! It takes pixel data in, returns pixel data out.
 
-use image::{DynamicImage, ImageFormat};
-use std::io::Cursor;
+use image::DynamicImage;
 
 /// Crops a `DynamicImage` to the specified rectangle and returns PNG bytes.
 ///
@@ -41,12 +40,7 @@ pub fn crop_to_png_bytes(
 
     let cropped = image.crop_imm(x, y, width, height);
 
-    let mut png_bytes: Vec<u8> = Vec::new();
-    cropped
-        .write_to(&mut Cursor::new(&mut png_bytes), ImageFormat::Png)
-        .map_err(|e| CropError::EncodingFailed(e.to_string()))?;
-
-    Ok(png_bytes)
+    super::encode::png_fast(&cropped).map_err(CropError::EncodingFailed)
 }
 
 #[derive(Debug, thiserror::Error)]
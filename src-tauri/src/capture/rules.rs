@@ -0,0 +1,221 @@
+//! Capture preferences: which monitor to snip, which apps' windows must
+//! never be captured, keyboard selection steps, and what to do while the
+//! screen is shared (pure, no I/O).
+//!
+//! `monitor` picks the display a snip captures: unset means the primary
+//! one, a number is a 1-based position in the OS monitor list ("2"), and
+//! anything else matches a monitor name. An unknown choice falls back to
+//! the primary monitor.
+//!
+//! Each app rule matches windows whose owning app name contains `app`
+//! (ignoring case). `blank` paints over the window's area in the
+//! screenshot before anything else sees it; `refuse` stops the snip when
+//! such a window is visible on the captured monitor. Rules look at window
+//! frames, not at what is on top, so a blanked area can hide part of a
+//! window overlapping it.
+
+use crate::presenting::config::PresentingPrefs;
+use crate::selection::config::KeyboardPrefs;
+use serde::{Deserialize, Serialize};
+
+pub const RULE_ACTIONS: &[&str] = &["blank", "refuse"];
+/// App rules allowed in settings.
+const MAX_RULES: usize = 100;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturePrefs {
+    /// Monitor to capture: `None` (primary), a 1-based index, or a name.
+    #[serde(default)]
+    pub monitor: Option<String>,
+    #[serde(default)]
+    pub app_rules: Vec<AppRule>,
+    /// Arrow-key steps of the keyboard selection (see selection/).
+    #[serde(default)]
+    pub keyboard: KeyboardPrefs,
+    /// Warn or pause while the screen is shared (see presenting/).
+    #[serde(default)]
+    pub presenting: PresentingPrefs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AppRule {
+    /// Part of the app name, e.g. `1Password`.
+    pub app: String,
+    /// `"blank"` or `"refuse"`.
+    #[serde(default = "default_action")]
+    pub action: String,
+}
+
+fn default_action() -> String {
+    "blank".to_string()
+}
+
+impl CapturePrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.monitor.as_deref().is_some_and(|m| m.trim().is_empty()) {
+            return Err("Monitor choice is empty".to_string());
+        }
+        if self.app_rules.len() > MAX_RULES {
+            return Err(format!("Up to {} capture rules are allowed", MAX_RULES));
+        }
+        for rule in &self.app_rules {
+            if rule.app.trim().is_empty() {
+                return Err("A capture rule needs an app name".to_string());
+            }
+            if !RULE_ACTIONS.contains(&rule.action.as_str()) {
+                return Err(format!("Unknown capture rule action: {}", rule.action));
+            }
+        }
+        self.keyboard.validate()?;
+        self.presenting.validate()
+    }
+
+    /// The first rule matching `app_name`, if any.
+    pub fn rule_for(&self, app_name: &str) -> Option<&AppRule> {
+        let app_name = app_name.to_lowercase();
+        self.app_rules.iter().find(|r| app_name.contains(&r.app.trim().to_lowercase()))
+    }
+}
+
+/// A monitor as the picker sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub name: String,
+    pub primary: bool,
+}
+
+/// Index of the monitor to capture: the choice if it matches, else the
+/// primary monitor, else the first one.
+pub fn pick_monitor(monitors: &[MonitorInfo], choice: Option<&str>) -> Option<usize> {
+    let chosen = choice.map(str::trim).and_then(|c| match c.parse::<usize>() {
+        Ok(n) => n.checked_sub(1).filter(|i| *i < monitors.len()),
+        Err(_) => monitors.iter().position(|m| m.name.eq_ignore_ascii_case(c)),
+    });
+    chosen
+        .or_else(|| monitors.iter().position(|m| m.primary))
+        .or_else(|| (!monitors.is_empty()).then_some(0))
+}
+
+/// A rectangle; window frames use the OS's global coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// The overlap of `self` and `other`, if any.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let bottom = (self.y + self.height as i32).min(other.y + other.height as i32);
+        (right > x && bottom > y).then(|| Rect { x, y, width: (right - x) as u32, height: (bottom - y) as u32 })
+    }
+}
+
+/// An on-screen window and the app that owns it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowInfo {
+    pub app: String,
+    pub frame: Rect,
+    pub minimized: bool,
+}
+
+/// What to do with a capture of one monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// Capture, painting over these areas (screenshot pixels). `apps`
+    /// names the blanked apps, for the log.
+    Capture { blank: Vec<Rect>, apps: Vec<String> },
+    /// Don't capture; a window of this app is showing.
+    Refuse(String),
+}
+
+/// Apply the app rules to the windows visible on `monitor` (global
+/// coordinates). `scale` converts monitor coordinates to screenshot
+/// pixels.
+pub fn judge(prefs: &CapturePrefs, windows: &[WindowInfo], monitor: &Rect, scale: f64) -> Verdict {
+    let mut blank = Vec::new();
+    let mut apps: Vec<String> = Vec::new();
+    for window in windows.iter().filter(|w| !w.minimized) {
+        let Some(rule) = prefs.rule_for(&window.app) else { continue };
+        let Some(visible) = window.frame.intersect(monitor) else { continue };
+        if rule.action == "refuse" {
+            return Verdict::Refuse(window.app.clone());
+        }
+        let px = |v: i64| (v as f64 * scale).round().max(0.0);
+        blank.push(Rect {
+            x: px((visible.x - monitor.x) as i64) as i32,
+            y: px((visible.y - monitor.y) as i64) as i32,
+            width: px(visible.width as i64).max(1.0) as u32,
+            height: px(visible.height as i64).max(1.0) as u32,
+        });
+        if !apps.contains(&window.app) {
+            apps.push(window.app.clone());
+        }
+    }
+    Verdict::Capture { blank, apps }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs() -> CapturePrefs {
+        CapturePrefs {
+            monitor: Some("2".into()),
+            app_rules: vec![
+                AppRule { app: "1password".into(), action: "blank".into() },
+                AppRule { app: "Bank".into(), action: "refuse".into() },
+            ],
+            keyboard: KeyboardPrefs::default(),
+            presenting: PresentingPrefs::default(),
+        }
+    }
+
+    fn window(app: &str, x: i32, y: i32, minimized: bool) -> WindowInfo {
+        WindowInfo { app: app.into(), frame: Rect { x, y, width: 400, height: 300 }, minimized }
+    }
+
+    #[test]
+    fn monitors_are_picked_by_index_name_or_primary() {
+        let monitors = vec![
+            MonitorInfo { name: "Built-in Retina Display".into(), primary: true },
+            MonitorInfo { name: "DELL U2720Q".into(), primary: false },
+        ];
+        assert_eq!(pick_monitor(&monitors, Some("2")), Some(1));
+        assert_eq!(pick_monitor(&monitors, Some("dell u2720q")), Some(1));
+        assert_eq!(pick_monitor(&monitors, Some("3")), Some(0));
+        assert_eq!(pick_monitor(&monitors, Some("0")), Some(0));
+        assert_eq!(pick_monitor(&monitors, None), Some(0));
+        assert_eq!(pick_monitor(&[], None), None);
+        assert!(prefs().validate().is_ok());
+        assert!(CapturePrefs { monitor: Some(" ".into()), ..prefs() }.validate().is_err());
+    }
+
+    #[test]
+    fn windows_are_blanked_or_refused_on_the_captured_monitor() {
+        let monitor = Rect { x: 1440, y: 0, width: 1920, height: 1080 };
+        let windows = vec![
+            window("1Password 7", 1340, 100, false),
+            window("Safari", 1500, 100, false),
+            window("1Password 7", 1500, 500, true),
+            window("MyBank", 0, 0, false),
+        ];
+        let verdict = judge(&prefs(), &windows, &monitor, 2.0);
+        assert_eq!(
+            verdict,
+            Verdict::Capture {
+                blank: vec![Rect { x: 0, y: 200, width: 600, height: 600 }],
+                apps: vec!["1Password 7".into()],
+            }
+        );
+        let on_screen = [window("MyBank", 1500, 100, false)];
+        assert_eq!(judge(&prefs(), &on_screen, &monitor, 1.0), Verdict::Refuse("MyBank".into()));
+    }
+}
@@ -4,8 +4,10 @@
 //! If xcap fails on macOS 26.3, this file is the one we replace
 //! with a ScreenCaptureKit FFI implementation.
 
-use image::DynamicImage;
-use xcap::Monitor;
+use super::rules::{self, CapturePrefs, MonitorInfo, Rect, Verdict, WindowInfo};
+use crate::desktop::session::CaptureBackend;
+use image::{DynamicImage, Rgba};
+use xcap::{Monitor, Window};
 
 /// Captures the primary monitor's screen as a `DynamicImage`.
 ///
@@ -31,6 +33,159 @@ pub fn capture_primary_monitor() -> Result<DynamicImage, CaptureError> {
     Ok(DynamicImage::ImageRgba8(image))
 }
 
+/// On Wayland xcap captures through the desktop portal; without one it
+/// would hang or hand back nothing, so a snip stops here instead.
+fn portal_ready() -> Result<(), CaptureError> {
+    match crate::desktop::capture_backend() {
+        CaptureBackend::Unavailable => Err(CaptureError::NoPortal),
+        CaptureBackend::Direct | CaptureBackend::Portal => Ok(()),
+    }
+}
+
+/// Captures the monitor chosen in `prefs`, with the app rules applied:
+/// windows of `blank` apps are painted over, and a visible window of a
+/// `refuse` app stops the capture with `CaptureError::Refused`.
+///
+/// With rules set, a window list that can't be read refuses the snip
+/// rather than risk capturing a blocked app.
+pub fn capture_for_snip(prefs: &CapturePrefs) -> Result<DynamicImage, CaptureError> {
+    portal_ready()?;
+    let monitors = Monitor::all().map_err(|e| CaptureError::MonitorEnumeration(e.to_string()))?;
+    let infos: Vec<MonitorInfo> = monitors
+        .iter()
+        .map(|m| MonitorInfo { name: m.name().unwrap_or_default(), primary: m.is_primary().unwrap_or(false) })
+        .collect();
+    let index = rules::pick_monitor(&infos, prefs.monitor.as_deref()).ok_or(CaptureError::NoPrimaryMonitor)?;
+    let windows = if prefs.app_rules.is_empty() { Vec::new() } else { windows()? };
+    let image = capture_monitor(&monitors[index], prefs, &windows)?;
+    log::info!("[CAPTURE] Captured monitor {} ({})", index + 1, infos[index].name);
+    Ok(DynamicImage::ImageRgba8(image))
+}
+
+/// Captures every monitor with the app rules applied as for a snip,
+/// each with its frame. A `refuse` app on any monitor stops them all.
+pub fn capture_all_monitors(prefs: &CapturePrefs) -> Result<Vec<(DynamicImage, Rect)>, CaptureError> {
+    portal_ready()?;
+    let monitors = Monitor::all().map_err(|e| CaptureError::MonitorEnumeration(e.to_string()))?;
+    let windows = if prefs.app_rules.is_empty() { Vec::new() } else { windows()? };
+    let captured = monitors
+        .iter()
+        .map(|m| Ok((DynamicImage::ImageRgba8(capture_monitor(m, prefs, &windows)?), frame_of(m))))
+        .collect::<Result<Vec<_>, CaptureError>>()?;
+    log::info!("[CAPTURE] Captured all {} monitor(s)", captured.len());
+    Ok(captured)
+}
+
+fn frame_of(monitor: &Monitor) -> Rect {
+    Rect {
+        x: monitor.x().unwrap_or(0),
+        y: monitor.y().unwrap_or(0),
+        width: monitor.width().unwrap_or(0),
+        height: monitor.height().unwrap_or(0),
+    }
+}
+
+/// One monitor's pixels, refused or painted over by the app rules.
+fn capture_monitor(monitor: &Monitor, prefs: &CapturePrefs, windows: &[WindowInfo]) -> Result<image::RgbaImage, CaptureError> {
+    let frame = frame_of(monitor);
+    // The scale is only known once captured; a refusal must come first.
+    if let Verdict::Refuse(app) = rules::judge(prefs, windows, &frame, 1.0) {
+        return Err(CaptureError::Refused(app));
+    }
+
+    let mut image = monitor
+        .capture_image()
+        .map_err(|e| CaptureError::CaptureFailed(e.to_string()))?;
+    let scale = if frame.width > 0 { f64::from(image.width()) / f64::from(frame.width) } else { 1.0 };
+    if let Verdict::Capture { blank, apps } = rules::judge(prefs, windows, &frame, scale) {
+        for area in &blank {
+            paint_over(&mut image, area);
+        }
+        if !apps.is_empty() {
+            log::info!("[CAPTURE] Blanked {} window(s) of {}", blank.len(), apps.join(", "));
+        }
+    }
+    Ok(image)
+}
+
+/// Captures the focused window of another app (a window snip), with its
+/// frame. Only the window itself is captured, so other apps' rules don't
+/// apply; a window of an app with any capture rule is refused.
+pub fn capture_focused_window(prefs: &CapturePrefs) -> Result<(DynamicImage, Rect), CaptureError> {
+    portal_ready()?;
+    let own = std::process::id();
+    let all = Window::all().map_err(|e| CaptureError::WindowList(e.to_string()))?;
+    // Front to back; the focused window, else the frontmost one
+    let candidates: Vec<Window> =
+        all.into_iter().filter(|w| w.pid().ok() != Some(own) && !w.is_minimized().unwrap_or(true)).collect();
+    let window = candidates
+        .iter()
+        .find(|w| w.is_focused().unwrap_or(false))
+        .or_else(|| candidates.first())
+        .ok_or(CaptureError::NoWindow)?;
+    let app = window.app_name().unwrap_or_default();
+    if prefs.rule_for(&app).is_some() {
+        return Err(CaptureError::Refused(app));
+    }
+    let frame = Rect {
+        x: window.x().unwrap_or(0),
+        y: window.y().unwrap_or(0),
+        width: window.width().unwrap_or(0),
+        height: window.height().unwrap_or(0),
+    };
+    let image = window.capture_image().map_err(|e| CaptureError::CaptureFailed(e.to_string()))?;
+    log::info!("[CAPTURE] Captured focused window of {}", app);
+    Ok((DynamicImage::ImageRgba8(image), frame))
+}
+
+/// Monitor names in OS order (the order `CapturePrefs::monitor` indexes).
+pub fn monitor_names() -> Vec<String> {
+    Monitor::all()
+        .map(|all| all.iter().map(|m| m.name().unwrap_or_default()).collect())
+        .unwrap_or_default()
+}
+
+/// The owning app and title of every window of other apps, for
+/// telling whether the screen is being shared (presenting/).
+pub fn window_titles() -> Result<Vec<(String, String)>, CaptureError> {
+    let own = std::process::id();
+    let all = Window::all().map_err(|e| CaptureError::WindowList(e.to_string()))?;
+    Ok(all
+        .iter()
+        .filter(|w| w.pid().ok() != Some(own))
+        .map(|w| (w.app_name().unwrap_or_default(), w.title().unwrap_or_default()))
+        .collect())
+}
+
+/// Every window the OS reports, with its owning app.
+fn windows() -> Result<Vec<WindowInfo>, CaptureError> {
+    let all = Window::all().map_err(|e| CaptureError::WindowList(e.to_string()))?;
+    Ok(all
+        .iter()
+        .map(|w| WindowInfo {
+            app: w.app_name().unwrap_or_default(),
+            frame: Rect {
+                x: w.x().unwrap_or(0),
+                y: w.y().unwrap_or(0),
+                width: w.width().unwrap_or(0),
+                height: w.height().unwrap_or(0),
+            },
+            minimized: w.is_minimized().unwrap_or(false),
+        })
+        .collect())
+}
+
+fn paint_over(image: &mut image::RgbaImage, area: &Rect) {
+    let (x0, y0) = (area.x.max(0) as u32, area.y.max(0) as u32);
+    let x1 = (x0 + area.width).min(image.width());
+    let y1 = (y0 + area.height).min(image.height());
+    for y in y0..y1 {
+        for x in x0..x1 {
+            image.put_pixel(x, y, Rgba([0, 0, 0, 255]));
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CaptureError {
     #[error("Failed to enumerate monitors: {0}")]
@@ -41,4 +196,16 @@ pub enum CaptureError {
 
     #[error("Screen capture failed: {0}")]
     CaptureFailed(String),
+
+    #[error("Can't check capture rules, window list unavailable: {0}")]
+    WindowList(String),
+
+    #[error("Snip refused: a window of {0} is on screen")]
+    Refused(String),
+
+    #[error("No window to capture")]
+    NoWindow,
+
+    #[error("No desktop portal for screen capture on Wayland")]
+    NoPortal,
 }
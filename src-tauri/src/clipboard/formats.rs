@@ -0,0 +1,193 @@
+//! Clipboard flavors built from a snip or an action result (pure, no I/O).
+//!
+//! One copy puts several representations on the clipboard at once, and
+//! the app pasted into picks the richest it understands: plain text
+//! always; HTML (code as `<pre>`, CSV as a table, prose as paragraphs);
+//! RTF with syntax colors for code (`rtf.rs`); the crop as PNG; and a CSV
+//! result as a file, so it can be pasted into Finder or Explorer.
+
+use super::rtf;
+
+/// What the copied text is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Text,
+    /// Source code or a shell command; the language is a hint for colors.
+    Code(Option<String>),
+    /// Comma-separated values; `name` is the file name offered.
+    Csv { name: String },
+}
+
+/// Everything one copy writes. `text` is always present.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Flavors {
+    pub text: String,
+    pub html: Option<String>,
+    pub rtf: Option<String>,
+    pub png: Option<Vec<u8>>,
+    /// (file name, contents) to write to a temp file and copy as a file.
+    pub file: Option<(String, String)>,
+}
+
+impl Flavors {
+    pub fn plain(text: &str) -> Self {
+        Self { text: text.to_string(), ..Self::default() }
+    }
+
+    /// Keep only `flavor` (`"all"` keeps everything). Plain text stays
+    /// alongside HTML and RTF as the fallback; `image` and `file` are
+    /// written alone. `Err` when the flavor isn't available.
+    pub fn select(mut self, flavor: &str) -> Result<Self, String> {
+        let missing = |what: &str| Err(format!("No {} to copy for this result", what));
+        match flavor {
+            "all" => Ok(self),
+            "text" => Ok(Self::plain(&self.text)),
+            "html" if self.html.is_some() => Ok(Self { rtf: None, png: None, file: None, ..self }),
+            "rtf" if self.rtf.is_some() => Ok(Self { html: None, png: None, file: None, ..self }),
+            "image" => match self.png.take() {
+                Some(png) => Ok(Self { png: Some(png), ..Self::default() }),
+                None => missing("image"),
+            },
+            "file" => match self.file.take() {
+                Some(file) => Ok(Self { file: Some(file), ..Self::default() }),
+                None => missing("file"),
+            },
+            "html" | "rtf" => missing(flavor),
+            _ => Err(format!("Unknown clipboard flavor: {}", flavor)),
+        }
+    }
+}
+
+/// Build every flavor that fits `text` of `kind`; `png` is the crop.
+pub fn build(text: &str, kind: &Kind, png: Option<Vec<u8>>) -> Flavors {
+    let (html, rtf, file) = match kind {
+        Kind::Text => (prose_html(text), None, None),
+        Kind::Code(language) => (
+            format!("<pre><code>{}</code></pre>", escape_html(text)),
+            Some(rtf::highlighted(text, language.as_deref())),
+            None,
+        ),
+        Kind::Csv { name } => (csv_html(text), None, Some((name.clone(), text.to_string()))),
+    };
+    Flavors { text: text.to_string(), html: Some(html), rtf, png, file }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Paragraphs for blank-line-separated text, `<pre>` for fenced blocks.
+fn prose_html(text: &str) -> String {
+    let mut out = String::new();
+    for (i, part) in text.split("```").enumerate() {
+        if i % 2 == 1 {
+            let code = part.split_once('\n').map_or(part, |(_, body)| body);
+            out.push_str(&format!("<pre><code>{}</code></pre>", escape_html(code.trim_end())));
+            continue;
+        }
+        for para in part.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+            out.push_str(&format!("<p>{}</p>", escape_html(para).replace('\n', "<br>")));
+        }
+    }
+    out
+}
+
+/// Split one CSV line, honoring double-quoted fields.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// An HTML table; the first line is the header row.
+fn csv_html(text: &str) -> String {
+    let mut out = String::from("<table>");
+    for (i, line) in text.lines().filter(|l| !l.trim().is_empty()).enumerate() {
+        let tag = if i == 0 { "th" } else { "td" };
+        out.push_str("<tr>");
+        for field in csv_fields(line) {
+            out.push_str(&format!("<{0}>{1}</{0}>", tag, escape_html(&field)));
+        }
+        out.push_str("</tr>");
+    }
+    out.push_str("</table>");
+    out
+}
+
+/// Windows `CF_HTML`: the fragment wrapped in a document, behind a header
+/// of byte offsets.
+pub fn cf_html(fragment: &str) -> String {
+    let pre = "<html><body><!--StartFragment-->";
+    let post = "<!--EndFragment--></body></html>";
+    let header = |offsets: [usize; 4]| {
+        format!(
+            "Version:0.9\r\nStartHTML:{:010}\r\nEndHTML:{:010}\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n",
+            offsets[0], offsets[1], offsets[2], offsets[3]
+        )
+    };
+    // Offsets are written as 10 digits, so the header length is fixed.
+    let start_html = header([0; 4]).len();
+    let start_fragment = start_html + pre.len();
+    let end_fragment = start_fragment + fragment.len();
+    let end_html = end_fragment + post.len();
+    format!("{}{}{}{}", header([start_html, end_html, start_fragment, end_fragment]), pre, fragment, post)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_kind_gets_its_flavors() {
+        let code = build("if a < b { x() }", &Kind::Code(Some("rust".into())), Some(vec![1, 2]));
+        assert_eq!(code.html.as_deref(), Some("<pre><code>if a &lt; b { x() }</code></pre>"));
+        assert!(code.rtf.as_deref().unwrap().starts_with("{\\rtf1"));
+        assert_eq!(code.png, Some(vec![1, 2]));
+
+        let csv = build("name,note\n\"Smith, J\",\"say \"\"hi\"\"\"\n", &Kind::Csv { name: "export.csv".into() }, None);
+        assert_eq!(
+            csv.html.as_deref(),
+            Some("<table><tr><th>name</th><th>note</th></tr><tr><td>Smith, J</td><td>say &quot;hi&quot;</td></tr></table>")
+        );
+        assert_eq!(csv.file.as_ref().map(|f| f.0.as_str()), Some("export.csv"));
+
+        let prose = build("Line one\nline two\n\n```sh\nls -la\n```", &Kind::Text, None);
+        assert_eq!(prose.html.as_deref(), Some("<p>Line one<br>line two</p><pre><code>ls -la</code></pre>"));
+        assert!(prose.rtf.is_none());
+    }
+
+    #[test]
+    fn flavors_can_be_picked_one_at_a_time() {
+        let code = build("x", &Kind::Code(None), Some(vec![7]));
+        let image = code.clone().select("image").unwrap();
+        assert_eq!((image.png, image.text.as_str()), (Some(vec![7]), ""));
+        let html = code.clone().select("html").unwrap();
+        assert!(html.html.is_some() && html.rtf.is_none() && html.text == "x");
+        assert!(code.clone().select("file").is_err());
+        assert!(code.select("gif").is_err());
+    }
+
+    #[test]
+    fn cf_html_offsets_point_at_the_fragment() {
+        let out = cf_html("<b>hi</b>");
+        let offset = |key: &str| -> usize {
+            let start = out.find(key).unwrap() + key.len();
+            out[start..start + 10].parse().unwrap()
+        };
+        assert_eq!(&out[offset("StartFragment:")..offset("EndFragment:")], "<b>hi</b>");
+        assert_eq!(offset("EndHTML:"), out.len());
+        assert!(out[offset("StartHTML:")..].starts_with("<html>"));
+    }
+}
@@ -0,0 +1,142 @@
+//! Clipboard domain — every copy the app makes.
+//!
+//! A copy writes several flavors at once (`formats.rs`): plain text,
+//! HTML, RTF with syntax colors for code (`rtf.rs`), the crop as an image
+//! when copying the snip itself, and a CSV result as a file. The OS write
+//! lives in `platform.rs`. `copy_result` copies a snip or one of its
+//! answers, from the current action menu or from history; Copy Image on
+//! a picture snip copies the crop alone.
+
+pub mod formats;
+mod platform;
+pub mod rtf;
+
+use crate::history;
+use crate::i18n::t;
+use crate::llm;
+use formats::{Flavors, Kind};
+
+/// Local actions that copy the snip's own text.
+const SNIP_COPY_ACTIONS: &[&str] = &["copy_text", "copy_code", "copy_traceback", "copy_command"];
+
+/// Put `flavors` on the clipboard, replacing what's there.
+pub fn write(flavors: &Flavors) -> Result<(), String> {
+    platform::write(flavors)?;
+    log::info!(
+        "[CLIPBOARD] Copied {} chars (html: {}, rtf: {}, image: {}, file: {})",
+        flavors.text.len(),
+        flavors.html.is_some(),
+        flavors.rtf.is_some(),
+        flavors.png.is_some(),
+        flavors.file.is_some()
+    );
+    Ok(())
+}
+
+/// The clipboard's plain text (empty when it holds none).
+pub fn read_text() -> Result<String, String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {}", e))?;
+    match clipboard.get_text() {
+        Ok(text) => Ok(text),
+        Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+        Err(e) => Err(format!("Could not read the clipboard: {}", e)),
+    }
+}
+
+/// Flavors for the snip's OCR text. Code and errors are copied as code.
+fn snip_flavors(
+    text: &str,
+    action_id: &str,
+    content_type: &str,
+    language: Option<String>,
+    png: Option<Vec<u8>>,
+) -> Flavors {
+    let as_code = action_id != "copy_text" || matches!(content_type, "code" | "error");
+    let kind = if as_code { Kind::Code(language) } else { Kind::Text };
+    formats::build(text, &kind, png)
+}
+
+/// Flavors for an action result: a command as code, a CSV file as a
+/// table plus the file, anything else as text.
+fn result_flavors(result: &llm::ActionResult) -> Result<Flavors, String> {
+    let body = &result.result;
+    if result.status != "success" {
+        return Err(t("error.nothingToCopy"));
+    }
+    let (text, kind) = match body.result_type.as_str() {
+        "command" => (body.command.as_ref(), Kind::Code(Some("shell".to_string()))),
+        "clipboard" => (body.clipboard_content.as_ref().or(body.text.as_ref()), Kind::Text),
+        "file" => {
+            let name = body.file_path.clone().unwrap_or_else(|| "export.csv".to_string());
+            let csv = name.to_ascii_lowercase().ends_with(".csv") || body.mime_type.as_deref() == Some("text/csv");
+            (body.text.as_ref(), if csv { Kind::Csv { name } } else { Kind::Text })
+        }
+        _ => (body.text.as_ref(), Kind::Text),
+    };
+    let text = text.filter(|s| !s.is_empty()).ok_or_else(|| t("error.nothingToCopy"))?;
+    Ok(formats::build(text, &kind, None))
+}
+
+/// Flavors from the snip currently in the action menu.
+fn from_current(state: &llm::ActionMenuState, action_id: &str) -> Result<Flavors, String> {
+    if action_id == crate::picture::COPY_ACTION {
+        let png = state.crop_png.lock().map_err(|e| e.to_string())?.clone();
+        return Ok(Flavors { png: Some(png.ok_or_else(|| t("error.imageUnavailable"))?), ..Flavors::default() });
+    }
+    let text = state.ocr_text.lock().map_err(|e| e.to_string())?.clone();
+    let text = text.ok_or_else(|| t("error.nothingToCopy"))?;
+    if SNIP_COPY_ACTIONS.contains(&action_id) {
+        let menu = state.menu.lock().map_err(|e| e.to_string())?.clone();
+        let (content_type, language) =
+            menu.map_or((String::new(), None), |m| (m.content_type, m.detected_language));
+        let png = state.crop_png.lock().map_err(|e| e.to_string())?.clone();
+        return Ok(snip_flavors(&text, action_id, &content_type, language, png));
+    }
+    let last = state.last_result.lock().map_err(|e| e.to_string())?.clone();
+    let result = last
+        .filter(|(snip, r)| *snip == text && r.action_id == action_id)
+        .map(|(_, r)| r)
+        .or_else(|| history::cached_result(&text, action_id))
+        .ok_or_else(|| t("error.nothingToCopy"))?;
+    result_flavors(&result)
+}
+
+/// Flavors from a history record: its text, or its latest answer to
+/// `action_id`.
+fn from_history(snip_id: u64, action_id: &str) -> Result<Flavors, String> {
+    history::with_records(|records| {
+        let record = records.iter().find(|r| r.id == snip_id).ok_or_else(|| t("error.snipNotFound"))?;
+        if SNIP_COPY_ACTIONS.contains(&action_id) {
+            return Ok(snip_flavors(&record.text, action_id, &record.content_type, None, None));
+        }
+        let answer = record
+            .answers
+            .iter()
+            .rev()
+            .find(|a| a.action_id == action_id)
+            .ok_or_else(|| t("error.nothingToCopy"))?;
+        match &answer.result {
+            Some(result) => result_flavors(result),
+            None => Ok(formats::build(&answer.text, &Kind::Text, None)),
+        }
+    })
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: copy a snip or one of its answers in every flavor that
+/// fits, or only `flavor` (`text`, `html`, `rtf`, `image`, `file`).
+/// `snip_id` picks a history record; without it the current snip is used.
+#[tauri::command]
+pub fn copy_result(
+    state: tauri::State<'_, llm::ActionMenuState>,
+    snip_id: Option<u64>,
+    action_id: String,
+    flavor: Option<String>,
+) -> Result<(), String> {
+    let flavors = match snip_id {
+        Some(id) => from_history(id, &action_id)?,
+        None => from_current(&state, &action_id)?,
+    };
+    write(&flavors.select(flavor.as_deref().unwrap_or("all"))?)
+}
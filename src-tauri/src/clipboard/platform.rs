@@ -0,0 +1,166 @@
+//! Writing flavors to the OS clipboard.
+//!
+//! arboard sets one representation at a time, so macOS and Windows go
+//! through the system's own scripting instead, which can put every
+//! flavor on the clipboard in one change:
+//!   - macOS: an AppleScript record passed to `osascript`
+//!   - Windows: a `DataObject` built by PowerShell (needs STA)
+//!
+//! Elsewhere arboard writes HTML with a plain-text alternative, or the
+//! image (or the file's path) when it's the only flavor. Payloads are
+//! staged in a temp directory; a CSV result stays there so the pasted
+//! file reference keeps working.
+
+use super::formats::Flavors;
+use std::path::{Path, PathBuf};
+
+fn staging_dir() -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join("omni-glass-clipboard");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to prepare clipboard files: {}", e))?;
+    Ok(dir)
+}
+
+/// Write the CSV (or other) file flavor and return its path.
+fn stage_file(name: &str, contents: &str) -> Result<PathBuf, String> {
+    let name = Path::new(name).file_name().and_then(|n| n.to_str()).unwrap_or("export.csv");
+    let path = staging_dir()?.join(name);
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    Ok(path)
+}
+
+pub fn write(flavors: &Flavors) -> Result<(), String> {
+    let file = match &flavors.file {
+        Some((name, contents)) => Some(stage_file(name, contents)?),
+        None => None,
+    };
+    if cfg!(target_os = "macos") {
+        write_macos(flavors, file.as_deref())
+    } else if cfg!(target_os = "windows") {
+        write_windows(flavors, file.as_deref())
+    } else {
+        write_arboard(flavors, file.as_deref())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn write_macos(flavors: &Flavors, file: Option<&Path>) -> Result<(), String> {
+    let mut fields = Vec::new();
+    if !flavors.text.is_empty() {
+        fields.push(format!("«class utf8»:«data utf8{}»", hex(flavors.text.as_bytes())));
+    }
+    if let Some(html) = &flavors.html {
+        fields.push(format!("«class HTML»:«data HTML{}»", hex(html.as_bytes())));
+    }
+    if let Some(rtf) = &flavors.rtf {
+        fields.push(format!("«class RTF »:«data RTF {}»", hex(rtf.as_bytes())));
+    }
+    if let Some(png) = &flavors.png {
+        fields.push(format!("«class PNGf»:«data PNGf{}»", hex(png)));
+    }
+    if let Some(path) = file {
+        let quoted = path.to_string_lossy().replace('\\', "\\\\").replace('"', "\\\"");
+        fields.push(format!("«class furl»:(POSIX file \"{}\")", quoted));
+    }
+    run_script("osascript", &["-"], &format!("set the clipboard to {{{}}}", fields.join(", ")))
+}
+
+fn write_windows(flavors: &Flavors, file: Option<&Path>) -> Result<(), String> {
+    let dir = staging_dir()?;
+    let stage = |name: &str, bytes: &[u8]| -> Result<String, String> {
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).map_err(|e| format!("Failed to write clipboard data: {}", e))?;
+        Ok(ps_quote(&path))
+    };
+    let mut script = String::from(
+        "Add-Type -AssemblyName System.Windows.Forms, System.Drawing\n\
+         $d = New-Object System.Windows.Forms.DataObject\n",
+    );
+    if !flavors.text.is_empty() {
+        let path = stage("clip.txt", flavors.text.as_bytes())?;
+        script.push_str(&format!("$d.SetText([IO.File]::ReadAllText({}), 'UnicodeText')\n", path));
+    }
+    if let Some(html) = &flavors.html {
+        // CF_HTML is UTF-8 bytes; a string would be re-encoded.
+        let path = stage("clip.html", super::formats::cf_html(html).as_bytes())?;
+        script.push_str(&format!(
+            "$d.SetData('HTML Format', (New-Object IO.MemoryStream(,[IO.File]::ReadAllBytes({}))))\n",
+            path
+        ));
+    }
+    if let Some(rtf) = &flavors.rtf {
+        let path = stage("clip.rtf", rtf.as_bytes())?;
+        script.push_str(&format!("$d.SetText([IO.File]::ReadAllText({}), 'Rtf')\n", path));
+    }
+    if let Some(png) = &flavors.png {
+        let path = stage("clip.png", png)?;
+        script.push_str(&format!(
+            "$d.SetImage([Drawing.Image]::FromStream((New-Object IO.MemoryStream(,[IO.File]::ReadAllBytes({})))))\n",
+            path
+        ));
+    }
+    if let Some(path) = file {
+        script.push_str(&format!(
+            "$f = New-Object Collections.Specialized.StringCollection\n[void]$f.Add({})\n$d.SetFileDropList($f)\n",
+            ps_quote(path)
+        ));
+    }
+    script.push_str("[Windows.Forms.Clipboard]::SetDataObject($d, $true)\n");
+    run_script("powershell", &["-NoProfile", "-NonInteractive", "-STA", "-Command", "-"], &script)
+}
+
+/// A PowerShell single-quoted string literal.
+fn ps_quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "''"))
+}
+
+fn run_script(program: &str, args: &[&str], script: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+    child
+        .stdin
+        .take()
+        .ok_or("Clipboard helper has no stdin")?
+        .write_all(script.as_bytes())
+        .map_err(|e| format!("Failed to write clipboard data: {}", e))?;
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Clipboard write failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+fn write_arboard(flavors: &Flavors, file: Option<&Path>) -> Result<(), String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+    if flavors.text.is_empty() {
+        if let Some(png) = &flavors.png {
+            let rgba = image::load_from_memory(png).map_err(|e| e.to_string())?.to_rgba8();
+            let (width, height) = rgba.dimensions();
+            let data = arboard::ImageData {
+                width: width as usize,
+                height: height as usize,
+                bytes: rgba.into_raw().into(),
+            };
+            return clipboard.set_image(data).map_err(|e| e.to_string());
+        }
+        if let Some(path) = file {
+            return clipboard.set_text(path.to_string_lossy()).map_err(|e| e.to_string());
+        }
+    }
+    match &flavors.html {
+        Some(html) => clipboard.set_html(html, Some(&flavors.text)),
+        None => clipboard.set_text(&flavors.text),
+    }
+    .map_err(|e| e.to_string())
+}
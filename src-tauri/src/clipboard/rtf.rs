@@ -0,0 +1,148 @@
+//! RTF with syntax colors for copied code (pure, no I/O).
+//!
+//! The highlighter is deliberately small: one keyword list covering the
+//! common languages, plus strings, numbers, and `//`, `#`, and `--` line
+//! comments. That is enough for a paste into Pages, Word, or Mail to look
+//! like code; it is not a parser. Text is set in Menlo / Consolas.
+
+/// Color table entries, 1-based as RTF `\cfN` indexes them.
+const COLORS: &str = r"{\colortbl;\red0\green0\blue0;\red170\green13\blue145;\red196\green26\blue22;\red28\green0\blue207;\red0\green116\blue0;}";
+const PLAIN: u8 = 1;
+const KEYWORD: u8 = 2;
+const STRING: u8 = 3;
+const NUMBER: u8 = 4;
+const COMMENT: u8 = 5;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "def", "default", "do",
+    "elif", "else", "enum", "export", "extends", "false", "fn", "for", "from", "func", "function", "if",
+    "impl", "import", "in", "interface", "let", "match", "mod", "mut", "new", "nil", "none", "null",
+    "package", "pub", "return", "self", "static", "struct", "switch", "this", "throw", "trait", "true",
+    "try", "type", "use", "var", "void", "while", "with", "yield", "None", "True", "False",
+];
+
+/// Languages whose `#` starts a comment (elsewhere it's an attribute,
+/// preprocessor line, or selector).
+const HASH_COMMENTS: &[&str] = &["python", "shell", "bash", "sh", "ruby", "yaml", "toml", "perl", "r"];
+
+/// Languages whose `--` starts a comment.
+const DASH_COMMENTS: &[&str] = &["sql", "lua", "haskell"];
+
+/// `code` as an RTF document with keywords, strings, numbers, and
+/// comments colored. `language` is a lowercase hint such as `python`.
+pub fn highlighted(code: &str, language: Option<&str>) -> String {
+    let language = language.unwrap_or("").to_ascii_lowercase();
+    let hash = language.is_empty() || HASH_COMMENTS.contains(&language.as_str());
+    let dash = DASH_COMMENTS.contains(&language.as_str());
+    let mut body = String::new();
+    for (i, line) in code.lines().enumerate() {
+        if i > 0 {
+            body.push_str("\\line\n");
+        }
+        for (color, token) in tokenize(line, hash, dash) {
+            if color == PLAIN {
+                body.push_str(&escape(token));
+            } else {
+                body.push_str(&format!("{{\\cf{} {}}}", color, escape(token)));
+            }
+        }
+    }
+    format!(
+        "{{\\rtf1\\ansi\\deff0{{\\fonttbl{{\\f0\\fmodern Menlo;}}{{\\f1\\fmodern Consolas;}}}}{}\n\\f0\\fs22\\cf{} {}}}",
+        COLORS, PLAIN, body
+    )
+}
+
+/// Split one line into colored runs.
+fn tokenize(line: &str, hash: bool, dash: bool) -> Vec<(u8, &str)> {
+    let mut out = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let len = if rest.starts_with("//") || (hash && c == '#') || (dash && rest.starts_with("--")) {
+            out.push((COMMENT, rest));
+            break;
+        } else if c == '"' || c == '\'' || c == '`' {
+            let end = string_end(rest, c);
+            out.push((STRING, &rest[..end]));
+            end
+        } else if c.is_ascii_digit() {
+            let end = rest.find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '_')).unwrap_or(rest.len());
+            out.push((NUMBER, &rest[..end]));
+            end
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest.find(|ch: char| !(ch.is_alphanumeric() || ch == '_')).unwrap_or(rest.len());
+            let word = &rest[..end];
+            out.push((if KEYWORDS.contains(&word) { KEYWORD } else { PLAIN }, word));
+            end
+        } else {
+            out.push((PLAIN, &rest[..c.len_utf8()]));
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    out
+}
+
+/// Byte length of the string literal opening `rest`, through its closing
+/// quote (or the end of the line), honoring `\` escapes.
+fn string_end(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, ch) in rest.char_indices().skip(1) {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if ch == quote => return i + ch.len_utf8(),
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+/// RTF-escape `text`: control characters get a backslash, tabs become
+/// `\tab`, and anything outside ASCII becomes `\uN?`.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' | '{' | '}' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\t' => out.push_str("\\tab "),
+            c if c.is_ascii() => out.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_colored() {
+        let tokens = tokenize(r#"let s = "a \" b"; // done"#, false, false);
+        assert_eq!(
+            tokens.iter().filter(|(c, _)| *c != PLAIN).collect::<Vec<_>>(),
+            vec![&(KEYWORD, "let"), &(STRING, r#""a \" b""#), &(COMMENT, "// done")]
+        );
+        assert_eq!(tokenize("x = 42 # n", true, false)[4], (NUMBER, "42"));
+        assert_eq!(tokenize("#[derive(Debug)]", false, false)[0], (PLAIN, "#"));
+        assert_eq!(tokenize("SELECT 1 -- one", false, true).last(), Some(&(COMMENT, "-- one")));
+    }
+
+    #[test]
+    fn output_is_escaped_rtf() {
+        let rtf = highlighted("fn main() {\n\tprint(\"é\");\n}", Some("rust"));
+        assert!(rtf.starts_with("{\\rtf1\\ansi"));
+        assert!(rtf.ends_with('}'));
+        assert!(rtf.contains("{\\cf2 fn} main() \\{\\line\n\\tab print({\\cf3 \"\\u233?\"});\\line\n\\}"));
+        assert_eq!(escape("😀"), "\\u-10179?\\u-8704?");
+    }
+}
@@ -5,16 +5,19 @@
 //!
 //! Complex multi-step commands live in pipeline.rs instead.
 
-use crate::capture::CaptureState;
+use crate::capture::{protocol, CaptureState};
+use crate::error::{Capture, Export, OmniError};
 use crate::llm;
 use crate::mcp;
 use crate::safety;
+use crate::windows::{self, spec::Window};
 use tauri::Manager;
 
 /// Tauri command: crop the stored screenshot to the given rectangle.
 ///
 /// Called by the frontend overlay when the user releases the mouse.
-/// Returns base64-encoded PNG of the cropped region.
+/// Returns an `omni-image://` URL of the cropped region's PNG, which the
+/// webview loads directly instead of decoding a base64 string.
 #[tauri::command]
 pub fn crop_region(
     state: tauri::State<'_, CaptureState>,
@@ -22,21 +25,13 @@ pub fn crop_region(
     y: u32,
     width: u32,
     height: u32,
-) -> Result<String, String> {
+) -> Result<String, OmniError> {
     let start = std::time::Instant::now();
 
-    let guard = state.screenshot.lock().map_err(|e| e.to_string())?;
-    let screenshot = guard
-        .as_ref()
-        .ok_or("No screenshot available — capture first")?;
-
-    let png_bytes = crate::capture::crop_to_png_bytes(screenshot, x, y, width, height)
-        .map_err(|e| e.to_string())?;
+    let screenshot = state.screenshot().ok_or(Capture::NoScreenshot)?;
 
-    let base64_png = base64::Engine::encode(
-        &base64::engine::general_purpose::STANDARD,
-        &png_bytes,
-    );
+    let png_bytes = crate::capture::crop_to_png_bytes(&screenshot, x, y, width, height)
+        .map_err(|e| Capture::Failed(e.to_string()))?;
 
     let crop_ms = start.elapsed().as_millis();
     log::info!(
@@ -44,20 +39,22 @@ pub fn crop_region(
         width, height, x, y, crop_ms, png_bytes.len()
     );
 
-    Ok(base64_png)
+    state.set_crop_png(png_bytes);
+    Ok(protocol::url(protocol::Resource::Crop))
 }
 
-/// Tauri command: get capture info (screenshot path + click timestamp).
+/// Tauri command: get capture info (screenshot URL + click timestamp).
 ///
 /// Called by the overlay on load. This replaces the event-based approach
-/// which raced — the event fired before JS was ready to listen.
+/// which raced — the event fired before JS was ready to listen. The
+/// capture runs in the background, so an overlay that loads first gets
+/// an error here and asks again on `capture-ready`.
 #[tauri::command]
 pub fn get_capture_info(
     state: tauri::State<'_, CaptureState>,
 ) -> Result<crate::capture::CaptureInfo, String> {
-    let guard = state.capture_info.lock().map_err(|e| e.to_string())?;
-    guard
-        .clone()
+    state
+        .capture_info()
         .ok_or("No capture info available".to_string())
 }
 
@@ -74,36 +71,27 @@ pub fn get_ocr_text(
         .ok_or("No OCR text available".to_string())
 }
 
-/// Tauri command: copy text to the system clipboard.
+/// Tauri command: copy plain text to the system clipboard.
 ///
-/// Uses arboard for native clipboard access — works reliably
-/// unlike navigator.clipboard in transparent webview windows.
+/// Goes through the clipboard module (native access — works reliably
+/// unlike navigator.clipboard in transparent webview windows). Snips and
+/// action results use `clipboard::copy_result` for rich formats.
 #[tauri::command]
 pub fn copy_to_clipboard(text: String) -> Result<(), String> {
-    let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
-    clipboard
-        .set_text(&text)
-        .map_err(|e| e.to_string())?;
-    log::info!("[ACTION] Copied {} chars to clipboard", text.len());
-    Ok(())
+    crate::clipboard::write(&crate::clipboard::formats::Flavors::plain(&text))
 }
 
-/// Tauri command: close the overlay and clean up capture state.
+/// Tauri command: dismiss the overlay (Escape, a click without a drag, a
+/// failed snip). Capture state is freed when the window is destroyed.
 #[tauri::command]
 pub fn close_overlay(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("overlay") {
-        window.close().map_err(|e| e.to_string())?;
-    }
-    Ok(())
+    windows::overlay::dismiss(&app)
 }
 
 /// Tauri command: close the action menu window.
 #[tauri::command]
 pub fn close_action_menu(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("action-menu") {
-        window.close().map_err(|e| e.to_string())?;
-    }
-    Ok(())
+    windows::close(&app, Window::ActionMenu)
 }
 
 /// Tauri command: close the permission prompt window.
@@ -128,12 +116,30 @@ pub fn get_action_menu(
         .ok_or("No action menu available".to_string())
 }
 
+/// Tauri command: the default action queued for the current snip, once.
+#[tauri::command]
+pub fn take_auto_action(state: tauri::State<'_, llm::ActionMenuState>) -> Option<String> {
+    state.auto_action.lock().ok()?.take()
+}
+
+/// Tauri command: the current snip's prompt-injection warning, once.
+#[tauri::command]
+pub fn take_injection_warning(
+    state: tauri::State<'_, llm::ActionMenuState>,
+) -> Option<crate::safety::injection::InjectionScan> {
+    state.injection_warning.lock().ok()?.take()
+}
+
 /// Tauri command: run a confirmed shell command.
 ///
 /// Only called after the user explicitly clicks "Run" in the confirmation
 /// dialog. Runs the command via the default shell and returns its output.
 #[tauri::command]
 pub async fn run_confirmed_command(command: String) -> Result<String, String> {
+    if !crate::policy::precedence::is_command_execution_allowed(crate::policy::current()) {
+        return Err("Command execution is disabled by your organization's policy".to_string());
+    }
+
     // Double-check safety before executing
     let check = safety::command_check::is_command_safe(&command);
     if !check.safe {
@@ -187,10 +193,7 @@ pub fn write_to_desktop(filename: String, content: String) -> Result<String, Str
 /// Tauri command: close the text launcher window.
 #[tauri::command]
 pub fn close_text_launcher(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("text-launcher") {
-        window.close().map_err(|e| e.to_string())?;
-    }
-    Ok(())
+    windows::close(&app, Window::Launcher)
 }
 
 /// Tauri command: get display names of loaded plugins.
@@ -217,15 +220,15 @@ pub fn close_tray_menu(app: tauri::AppHandle) -> Result<(), String> {
 /// Tauri command: start the snip (capture) flow.
 ///
 /// Called from the tray menu "Snip Screen" option. Delegates to the same
-/// start_snip_mode function that was previously triggered by tray click.
+/// start_snip_mode function that was previously triggered by tray click;
+/// returns as soon as the capture has been started.
 #[tauri::command]
-pub fn start_snip(app: tauri::AppHandle) -> Result<(), String> {
+pub fn start_snip(app: tauri::AppHandle) {
     let click_epoch_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_millis() as f64;
-    crate::tray::start_snip_mode(&app, click_epoch_ms)
-        .map_err(|e| e.to_string())
+    crate::tray::start_snip_mode(&app, click_epoch_ms);
 }
 
 /// Tauri command: open the text launcher window.
@@ -233,21 +236,19 @@ pub fn start_snip(app: tauri::AppHandle) -> Result<(), String> {
 /// Called from the tray menu "Type Command" option.
 #[tauri::command]
 pub fn open_text_launcher(app: tauri::AppHandle) -> Result<(), String> {
-    crate::show_text_launcher(&app);
-    Ok(())
+    windows::show(&app, Window::Launcher)
 }
 
 /// Tauri command: write file to a user-chosen path (from save dialog).
 ///
-/// The frontend shows a native save-file picker and passes the chosen path here.
+/// The frontend shows a native save-file picker and passes the chosen path
+/// here; it is checked against the path policy (`policy/paths.rs`).
 #[tauri::command]
-pub fn write_file_to_path(file_path: String, content: String) -> Result<String, String> {
-    if !safety::command_check::is_path_safe(&file_path) {
-        return Err("Unsafe file path".to_string());
-    }
+pub fn write_file_to_path(file_path: String, content: String) -> Result<String, OmniError> {
+    crate::policy::check_save_path(&file_path).map_err(Export::PathRefused)?;
 
     std::fs::write(&file_path, &content)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+        .map_err(|e| Export::Write(e.to_string()))?;
 
     log::info!("[EXPORT] Wrote file: {}", file_path);
     Ok(file_path)
@@ -0,0 +1,272 @@
+//! Command registry — every Tauri command the frontend can invoke,
+//! grouped by the domain that owns it. `lib.rs` hands the result to
+//! `invoke_handler`; a new command is added here next to its domain.
+
+use crate::{
+    boot,
+    capture,
+    clipboard,
+    commands,
+    compare,
+    crash,
+    deeplink,
+    desktop,
+    diagnostics,
+    document,
+    editor,
+    email,
+    export,
+    extract,
+    history,
+    i18n,
+    launcher,
+    links,
+    mcp,
+    net,
+    onboarding,
+    palette,
+    picture,
+    pipeline,
+    pipeline_execute,
+    pipeline_prefetch,
+    pipeline_text,
+    policy,
+    power,
+    qr,
+    replay,
+    safety,
+    search,
+    selection,
+    session,
+    settings,
+    settings_commands,
+    share,
+    stats,
+    triggers,
+    updater,
+    webhooks,
+    windows,
+};
+
+/// The app's invoke handler.
+pub fn handler() -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
+    tauri::generate_handler![
+        // Simple commands (commands.rs)
+        commands::crop_region,
+        commands::get_capture_info,
+        commands::get_ocr_text,
+        commands::copy_to_clipboard,
+        commands::close_overlay,
+        commands::close_action_menu,
+        commands::close_permission_prompt,
+        commands::get_action_menu,
+        commands::take_auto_action,
+        commands::take_injection_warning,
+        commands::run_confirmed_command,
+        commands::write_to_desktop,
+        commands::write_file_to_path,
+        commands::close_text_launcher,
+        commands::close_tray_menu,
+        commands::start_snip,
+        commands::open_text_launcher,
+        commands::get_plugin_names,
+        // Pipeline commands (pipeline.rs / pipeline_prefetch.rs / pipeline_execute.rs / pipeline_text.rs)
+        pipeline::process_snip,
+        pipeline_prefetch::prefetch_ocr,
+        pipeline_execute::execute_action,
+        pipeline_text::execute_text_command,
+        // Multi-format clipboard (clipboard/mod.rs)
+        clipboard::copy_result,
+        // Table and Markdown export (export/mod.rs)
+        export::export_table,
+        export::export_markdown,
+        export::save::save_result,
+        export::save::save_snip_image,
+        // Share sheet (share/mod.rs)
+        share::share_result,
+        // Open in editor (editor/mod.rs)
+        editor::get_editors,
+        // Email drafts (email/mod.rs)
+        email::open_email,
+        // Link extraction and opening (links/mod.rs)
+        links::extract_links,
+        links::open_link,
+        // QR codes (qr/mod.rs)
+        qr::generate_qr,
+        // Color palettes (palette/mod.rs)
+        palette::extract_palette,
+        // Document straightening (document/mod.rs)
+        document::straighten_document,
+        // Picture snips (picture/mod.rs)
+        picture::find_similar_on_screen,
+        picture::snip_similar,
+        // Regex extraction workbench (extract/mod.rs)
+        extract::run_extraction,
+        extract::export_extraction,
+        extract::suggest_pattern,
+        // omniglass:// deep links (deeplink/mod.rs)
+        deeplink::take_launcher_query,
+        // Launcher palette (launcher/mod.rs)
+        launcher::search_launcher,
+        launcher::run_launcher_item,
+        // Find text on screen (search/mod.rs)
+        search::find_on_screen,
+        search::show_screen_match,
+        // Outbound webhooks (webhooks/mod.rs)
+        webhooks::set_webhook_secret,
+        webhooks::test_webhook,
+        webhooks::get_webhook_deliveries,
+        // Stream Deck / MIDI triggers (triggers/mod.rs)
+        triggers::get_trigger_status,
+        triggers::set_trigger_mapping,
+        triggers::save_region_preset,
+        triggers::regenerate_trigger_token,
+        triggers::learn_trigger,
+        // Settings commands (settings_commands.rs)
+        settings_commands::get_provider_config,
+        settings_commands::set_active_provider,
+        settings_commands::save_api_key,
+        settings_commands::test_provider,
+        settings_commands::close_settings,
+        settings_commands::open_settings,
+        settings_commands::get_ocr_mode,
+        settings_commands::set_ocr_mode,
+        // Typed user settings (settings/mod.rs)
+        settings::get_settings,
+        settings::update_settings,
+        settings::switch_profile,
+        settings::startup::set_autostart,
+        settings::startup::set_background_mode,
+        settings::hotkeys::get_snip_hotkey,
+        // Proxy and custom CA (net/mod.rs)
+        net::set_proxy_password,
+        net::test_connectivity,
+        net::connectivity::get_connectivity,
+        net::connectivity::set_offline_mode,
+        // Adaptive quality mode (power/mod.rs)
+        power::get_quality_mode,
+        power::set_quality_mode,
+        // UI language (i18n/mod.rs)
+        i18n::get_locale,
+        i18n::set_locale,
+        // Snip auto-save (capture/autosave.rs)
+        capture::autosave::get_snip_autosave,
+        capture::autosave::set_snip_autosave,
+        // Capture monitor choice (capture/mod.rs)
+        capture::list_monitors,
+        // Screen capture permission (capture/permission.rs)
+        capture::permission::get_capture_permission_status,
+        capture::permission::open_permission_settings,
+        // Capture memory (capture/memory.rs)
+        capture::memory::get_memory_stats,
+        // Keyboard selection + announcements (selection/mod.rs)
+        selection::start_keyboard_selection,
+        selection::nudge_selection,
+        selection::expand_selection,
+        selection::confirm_selection,
+        selection::take_announcement,
+        // Snip history + fuzzy lexical search (history/commands.rs)
+        history::commands::get_snip_history,
+        history::commands::fuzzy_search_history,
+        history::commands::set_snip_pinned,
+        history::commands::set_snip_tags,
+        history::commands::set_snip_notes,
+        history::commands::get_snip_tags,
+        // Before/after snip comparison (compare/mod.rs)
+        compare::compare_snips,
+        compare::compare_recent_snips,
+        // History retention + incognito (history/privacy.rs)
+        history::privacy::get_history_privacy,
+        history::privacy::set_history_retention,
+        history::privacy::set_history_paused,
+        history::privacy::purge_all_history,
+        // Usage statistics for the Insights panel (stats/mod.rs)
+        stats::get_stats,
+        stats::get_stats_csv,
+        // Desktop support (desktop/mod.rs)
+        desktop::get_desktop_support,
+        // Startup phase report (boot/mod.rs)
+        boot::get_startup_report,
+        // Crash and hang reports (crash/mod.rs)
+        crash::get_last_crash,
+        // Diagnostics bundle and snip timing (diagnostics/mod.rs)
+        diagnostics::export_diagnostics,
+        diagnostics::get_last_snip_trace,
+        // Recorded-snip replay for debugging (replay/mod.rs)
+        replay::replay_snip,
+        // First-run setup (onboarding/mod.rs)
+        onboarding::get_onboarding_status,
+        onboarding::request_screen_capture_permission,
+        onboarding::run_onboarding_self_test,
+        onboarding::complete_onboarding,
+        // In-app updates (updater/mod.rs)
+        updater::check_for_updates,
+        updater::install_update,
+        updater::skip_update,
+        // Session restore (session/mod.rs)
+        session::take_restored_result,
+        session::mark_result_viewed,
+        session::set_window_pinned,
+        // Result window (windows/result.rs)
+        windows::result::get_shown_result,
+        windows::result::get_text_spans,
+        windows::result::pop_out_result,
+        windows::result::reopen_last_result,
+        windows::result::open_history_result,
+        windows::result::pin_result,
+        // Workspaces (history/workspace.rs)
+        history::workspace::get_workspaces,
+        history::workspace::create_workspace,
+        history::workspace::switch_workspace,
+        history::workspace::set_workspace_plugin_enabled,
+        // Enterprise policy (policy/mod.rs)
+        policy::get_policy,
+        // Outbound data monitor (safety/outbound_guard.rs)
+        safety::outbound_guard::get_outbound_stats,
+        // Safety event log (safety/events.rs)
+        safety::events::get_safety_events,
+        // MCP approval commands (approval_commands.rs)
+        mcp::approval_commands::get_pending_approvals,
+        mcp::approval_commands::approve_plugin,
+        // Plugin permission violations (mcp/violations.rs)
+        mcp::violations::get_permission_violations,
+        // Remote MCP servers (mcp/remote/mod.rs)
+        mcp::remote::get_remote_servers,
+        mcp::remote::save_remote_auth_header,
+        // Plugin install/uninstall (mcp/install/mod.rs)
+        mcp::install::install_plugin,
+        mcp::install::uninstall_plugin,
+        mcp::install::catalog::list_catalog_plugins,
+        // Per-plugin / per-tool enablement (mcp/enablement.rs)
+        mcp::enablement::get_plugin_enablement,
+        mcp::enablement::set_plugin_enabled,
+        mcp::enablement::set_tool_enabled,
+        // Plugin secrets in the OS keychain (mcp/secrets.rs)
+        mcp::secrets::set_plugin_secret,
+        mcp::secrets::get_plugin_secret_status,
+        // Plugin-local storage (mcp/storage/mod.rs)
+        mcp::storage::get_plugin_storage_usage,
+        mcp::storage::clear_plugin_storage,
+        // Brokered plugin clipboard grants (mcp/clipboard.rs)
+        mcp::clipboard::get_clipboard_grants,
+        mcp::clipboard::revoke_clipboard_grants,
+        // Plugin permission audit + revocation (mcp/permission_audit.rs)
+        mcp::permission_audit::get_plugin_permissions,
+        mcp::permission_audit::revoke_plugin_permission,
+        // Plugin health: restarts + circuit breaker (mcp/health/mod.rs)
+        mcp::health::get_plugin_health,
+        // Plugin launcher slash commands (mcp/slash_commands.rs)
+        mcp::slash_commands::get_launcher_commands,
+        // Scheduled plugin tasks (mcp/schedule/)
+        mcp::schedule::get_scheduled_tasks,
+        mcp::schedule::set_task_approved,
+        mcp::schedule::history::get_task_history,
+        // Plugin dev mode: hot reload + log console (mcp/devmode/)
+        mcp::devmode::start_plugin_dev,
+        mcp::devmode::stop_plugin_dev,
+        mcp::devmode::get_dev_plugins,
+        mcp::devmode::open_plugin_console,
+        mcp::devmode::logs::stream_plugin_logs,
+        mcp::devmode::logs::stop_plugin_logs,
+    ]
+}
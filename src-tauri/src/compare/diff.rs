@@ -0,0 +1,129 @@
+//! Line diff of two texts (pure, no I/O).
+//!
+//! A longest-common-subsequence diff over lines, after trimming the lines
+//! both texts start and end with. OCR of the same screen twice rarely
+//! matches word for word, so lines are compared with surrounding
+//! whitespace ignored. Texts too long for the LCS table are shown as
+//! replaced whole rather than diffed slowly.
+
+use serde::Serialize;
+
+/// Largest LCS table (lines before × lines after) worth filling.
+const MAX_CELLS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Change {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub change: Change,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diff {
+    pub lines: Vec<DiffLine>,
+    pub added: usize,
+    pub removed: usize,
+    /// Share of lines the texts have in common, 0–1.
+    pub similarity: f32,
+}
+
+fn line(change: Change, text: &str) -> DiffLine {
+    DiffLine { change, text: text.to_string() }
+}
+
+/// How `after` differs from `before`, line by line.
+pub fn lines(before: &str, after: &str) -> Diff {
+    let (old, new): (Vec<&str>, Vec<&str>) = (before.lines().collect(), after.lines().collect());
+    let same = |a: &str, b: &str| a.trim() == b.trim();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| same(a, b)).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| same(a, b)).count();
+    let (old_mid, new_mid) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+    let mut out: Vec<DiffLine> = old[..prefix].iter().map(|l| line(Change::Same, l)).collect();
+    if old_mid.len() * new_mid.len() > MAX_CELLS {
+        out.extend(old_mid.iter().map(|l| line(Change::Removed, l)));
+        out.extend(new_mid.iter().map(|l| line(Change::Added, l)));
+    } else {
+        out.extend(middle(old_mid, new_mid, same));
+    }
+    out.extend(new[new.len() - suffix..].iter().map(|l| line(Change::Same, l)));
+
+    let count = |change| out.iter().filter(|l| l.change == change).count();
+    let (added, removed, kept) = (count(Change::Added), count(Change::Removed), count(Change::Same));
+    let total = old.len() + new.len();
+    let similarity = if total == 0 { 1.0 } else { (2 * kept) as f32 / total as f32 };
+    Diff { lines: out, added, removed, similarity }
+}
+
+/// LCS diff of the lines between the common prefix and suffix.
+fn middle(old: &[&str], new: &[&str], same: impl Fn(&str, &str) -> bool) -> Vec<DiffLine> {
+    // lcs[i][j]: common lines of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if same(old[i], new[j]) { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j, mut out) = (0, 0, Vec::with_capacity(old.len() + new.len()));
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && same(old[i], new[j]) {
+            out.push(line(Change::Same, new[j]));
+            (i, j) = (i + 1, j + 1);
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(line(Change::Added, new[j]));
+            j += 1;
+        } else {
+            out.push(line(Change::Removed, old[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(diff: &Diff) -> Vec<(Change, &str)> {
+        diff.lines.iter().map(|l| (l.change, l.text.as_str())).collect()
+    }
+
+    #[test]
+    fn changed_lines_are_marked_and_the_rest_kept() {
+        let before = "TypeError: x is undefined\n  at render (app.js:10)\n  at main (app.js:3)";
+        let after = "TypeError: x is undefined\n  at render (app.js:12)\n  at main (app.js:3)\nDone";
+        let diff = lines(before, after);
+        assert_eq!(
+            changes(&diff),
+            vec![
+                (Change::Same, "TypeError: x is undefined"),
+                (Change::Added, "  at render (app.js:12)"),
+                (Change::Removed, "  at render (app.js:10)"),
+                (Change::Same, "  at main (app.js:3)"),
+                (Change::Added, "Done"),
+            ]
+        );
+        assert_eq!((diff.added, diff.removed), (2, 1));
+        assert!((diff.similarity - 4.0 / 7.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn identical_and_empty_texts() {
+        // Indentation OCR picked up differently is not a change
+        let diff = lines("a\n  b", "a\nb");
+        assert_eq!((diff.added, diff.removed, diff.similarity), (0, 0, 1.0));
+        assert_eq!(lines("", "").similarity, 1.0);
+        let diff = lines("", "new");
+        assert_eq!(changes(&diff), vec![(Change::Added, "new")]);
+        assert_eq!(diff.similarity, 0.0);
+    }
+}
@@ -0,0 +1,129 @@
+//! Snip comparison — two or more history snips side by side.
+//!
+//! For before/after debugging: snip an error, change something, snip it
+//! again, and compare. `compare_snips` assembles what a split view needs
+//! from history: each snip's thumbnail URL, OCR text, and answers, oldest
+//! first, and for each neighbouring pair a line diff of the texts and of
+//! the answers both snips got from the same action (`diff.rs`). Texts are
+//! the redacted ones history stores; nothing is re-read or re-run.
+
+pub mod diff;
+
+use crate::capture::protocol::{self, Resource};
+use crate::history::store::SnipRecord;
+use crate::thumbnails::variant::Size;
+use diff::Diff;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Most snips compared at once.
+const MAX_COMPARED: usize = 4;
+
+/// One snip in a comparison.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparedSnip {
+    pub id: u64,
+    /// Unix seconds.
+    pub created_at: u64,
+    pub content_type: String,
+    pub summary: String,
+    pub text: String,
+    /// `omni-image://` URL of the large thumbnail; fails to load if the
+    /// snip has none.
+    pub image_url: String,
+    /// Newest answer per action id.
+    pub answers: BTreeMap<String, String>,
+}
+
+/// An action both snips of a pair were answered by.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnswerDiff {
+    pub action_id: String,
+    pub diff: Diff,
+}
+
+/// How `after` differs from `before`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairDiff {
+    pub before: u64,
+    pub after: u64,
+    pub text: Diff,
+    pub answers: Vec<AnswerDiff>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    /// Oldest first.
+    pub snips: Vec<ComparedSnip>,
+    /// `pairs[i]` compares `snips[i]` with `snips[i + 1]`.
+    pub pairs: Vec<PairDiff>,
+}
+
+fn compared(record: &SnipRecord) -> ComparedSnip {
+    // Answers are stored oldest first; the newest of each action wins
+    let answers = record.answers.iter().map(|a| (a.action_id.clone(), a.text.clone())).collect();
+    ComparedSnip {
+        id: record.id,
+        created_at: record.created_at,
+        content_type: record.content_type.clone(),
+        summary: record.summary.clone(),
+        text: record.text.clone(),
+        image_url: protocol::url(Resource::Thumbnail(record.id, Size::Large)),
+        answers,
+    }
+}
+
+fn pair(before: &ComparedSnip, after: &ComparedSnip) -> PairDiff {
+    let answers = before
+        .answers
+        .iter()
+        .filter_map(|(action_id, old)| {
+            let new = after.answers.get(action_id)?;
+            Some(AnswerDiff { action_id: action_id.clone(), diff: diff::lines(old, new) })
+        })
+        .collect();
+    PairDiff { before: before.id, after: after.id, text: diff::lines(&before.text, &after.text), answers }
+}
+
+/// Compare the snips `ids`, oldest first.
+fn compare(ids: &[u64]) -> Result<Comparison, String> {
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    if !(2..=MAX_COMPARED).contains(&ids.len()) {
+        return Err(format!("Compare between 2 and {} different snips", MAX_COMPARED));
+    }
+    let mut snips = crate::history::with_records(|records| {
+        ids.iter()
+            .map(|id| records.iter().find(|r| r.id == *id).map(compared).ok_or_else(|| format!("No snip #{} in history", id)))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    snips.sort_by_key(|s| (s.created_at, s.id));
+    let pairs = snips.windows(2).map(|w| pair(&w[0], &w[1])).collect();
+    Ok(Comparison { snips, pairs })
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: compare 2–4 history snips by id.
+#[tauri::command]
+pub fn compare_snips(ids: Vec<u64>) -> Result<Comparison, String> {
+    compare(&ids)
+}
+
+/// Tauri command: compare the newest `count` snips (default 2) of a
+/// workspace (default: the active one) — "before" and "after" of the
+/// last fix.
+#[tauri::command]
+pub fn compare_recent_snips(count: Option<usize>, workspace: Option<String>) -> Result<Comparison, String> {
+    let count = count.unwrap_or(2).clamp(2, MAX_COMPARED);
+    let workspace = workspace.unwrap_or_else(crate::history::workspace::active);
+    let ids: Vec<u64> = crate::history::with_records(|records| {
+        records.iter().rev().filter(|r| r.in_scope(Some(&workspace))).take(count).map(|r| r.id).collect()
+    });
+    compare(&ids)
+}
@@ -0,0 +1,108 @@
+//! Times with a zone (`3:30 PM PST`, `15:00 CET`, `9am UTC+2`) and their
+//! conversion to other zones (pure, except `local_zone`).
+//!
+//! Zones and their daylight-saving rules come from `calendar::zones`, so
+//! the offset is right for the date the time is read on. Only upper-case
+//! abbreviations count (`ET`, not `et`), and a time needs minutes or
+//! am/pm, so prose like "at 5 PT sessions" is not taken for a time.
+
+use crate::calendar::zones;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Abbreviations recognized in text, longest first.
+const ZONES: &[&str] = &[
+    "AKST", "AKDT", "AEST", "AEDT", "AWST", "CEST", "EEST", "PST", "PDT", "MST", "MDT", "CST", "CDT", "EST", "EDT",
+    "HST", "UTC", "GMT", "BST", "CET", "EET", "MSK", "IST", "SGT", "HKT", "JST", "KST", "PT", "MT", "CT", "ET",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockTime {
+    pub hour: u32,
+    pub minute: u32,
+    /// As written: `PST`, `UTC+2`.
+    pub zone: String,
+    pub raw: String,
+}
+
+static TIME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\b(?P<h>\d{{1,2}})(?::(?P<m>\d{{2}}))?\s?(?P<ampm>(?i:[ap]\.?m\.?))?\s?\(?(?P<zone>(?:UTC|GMT)\s?[+−-]\s?\d{{1,2}}(?::?\d{{2}})?|(?:{})\b)",
+        ZONES.join("|")
+    ))
+    .unwrap()
+});
+
+/// Times with a recognized zone in `text`, in order.
+pub fn times(text: &str) -> Vec<ClockTime> {
+    TIME_RE
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let hour: u32 = caps["h"].parse().ok()?;
+            let minute: u32 = caps.name("m").map_or(Some(0), |m| m.as_str().parse().ok())?;
+            let hour = match caps.name("ampm").map(|a| a.as_str().to_lowercase()) {
+                Some(ampm) if (1..=12).contains(&hour) => hour % 12 + if ampm.starts_with('p') { 12 } else { 0 },
+                Some(_) => return None,
+                None if caps.name("m").is_some() && hour < 24 => hour,
+                None => return None,
+            };
+            (minute < 60).then(|| ClockTime {
+                hour,
+                minute,
+                zone: caps["zone"].replace(' ', ""),
+                raw: caps[0].trim_start_matches('(').trim().to_string(),
+            })
+        })
+        .take(super::detect::MAX_FOUND)
+        .collect()
+}
+
+/// `time` on the date `(y, m, d)` in `target`: hour, minute, and the day
+/// shift (-1, 0, +1). `None` when either zone is unknown.
+pub fn convert(time: &ClockTime, date: (i64, u32, u32), target: &str) -> Option<(u32, u32, i32)> {
+    let from = zones::offset_minutes(&time.zone, date)?;
+    let to = zones::offset_minutes(target, date)?;
+    let minutes = (time.hour * 60 + time.minute) as i32 - from + to;
+    let shift = minutes.div_euclid(24 * 60);
+    let minutes = minutes.rem_euclid(24 * 60) as u32;
+    Some((minutes / 60, minutes % 60, shift))
+}
+
+/// The system's zone when `calendar::zones` knows it (`TZ`, or the
+/// `/etc/localtime` link on macOS and Linux).
+pub fn local_zone() -> Option<String> {
+    let name = std::env::var("TZ").ok().filter(|tz| !tz.is_empty()).or_else(|| {
+        let link = std::fs::read_link("/etc/localtime").ok()?;
+        let link = link.to_string_lossy();
+        Some(link.split_once("zoneinfo/")?.1.to_string())
+    })?;
+    let name = name.trim_start_matches(':').to_string();
+    zones::offset_minutes(&name, (2000, 1, 1)).map(|_| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_zoned_times() {
+        let found = times("Call at 3:30 PM PST (or 15:00 CET), standup 9am UTC+2. At 5 PT sessions, 10 et al.");
+        let summary: Vec<(u32, u32, &str)> = found.iter().map(|t| (t.hour, t.minute, t.zone.as_str())).collect();
+        assert_eq!(summary, vec![(15, 30, "PST"), (15, 0, "CET"), (9, 0, "UTC+2")]);
+        assert_eq!(found[0].raw, "3:30 PM PST");
+        assert!(times("12:00 am UTC")[0].hour == 0 && times("12 pm UTC")[0].hour == 12);
+        assert!(times("13pm UTC").is_empty() && times("25:00 UTC").is_empty());
+    }
+
+    #[test]
+    fn converts_across_days_and_dst() {
+        let pst = times("3:30 PM PST").remove(0);
+        // July: Pacific is on daylight time whatever the abbreviation says
+        assert_eq!(convert(&pst, (2026, 7, 1), "CET"), Some((0, 30, 1)));
+        assert_eq!(convert(&pst, (2026, 1, 15), "UTC"), Some((23, 30, 0)));
+        assert_eq!(convert(&pst, (2026, 1, 15), "Asia/Tokyo"), Some((8, 30, 1)));
+        let tokyo = times("08:00 JST").remove(0);
+        assert_eq!(convert(&tokyo, (2026, 1, 15), "America/New_York"), Some((18, 0, -1)));
+        assert_eq!(convert(&tokyo, (2026, 1, 15), "Mars/Olympus"), None);
+    }
+}
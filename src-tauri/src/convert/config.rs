@@ -0,0 +1,84 @@
+//! Conversion settings (`convert`) — target currencies and zones, and
+//! where exchange rates come from (pure, no I/O).
+
+use super::detect::CURRENCIES;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_RATES_URL: &str = "https://api.frankfurter.app/latest";
+const MAX_TARGETS: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertPrefs {
+    /// ISO codes amounts are converted into (each amount skips its own).
+    #[serde(default = "default_currencies")]
+    pub currencies: Vec<String>,
+    /// Exchange-rate source; Frankfurter and open.er-api responses are read.
+    #[serde(default = "default_rates_url")]
+    pub rates_url: String,
+    /// Zones times are converted into, as abbreviations or IANA names;
+    /// empty converts into the system zone.
+    #[serde(default)]
+    pub time_zones: Vec<String>,
+}
+
+impl Default for ConvertPrefs {
+    fn default() -> Self {
+        Self { currencies: default_currencies(), rates_url: default_rates_url(), time_zones: Vec::new() }
+    }
+}
+
+fn default_currencies() -> Vec<String> {
+    vec!["USD".to_string(), "EUR".to_string()]
+}
+
+fn default_rates_url() -> String {
+    DEFAULT_RATES_URL.to_string()
+}
+
+impl ConvertPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.currencies.len() > MAX_TARGETS || self.time_zones.len() > MAX_TARGETS {
+            return Err(format!("At most {} conversion targets of each kind", MAX_TARGETS));
+        }
+        if let Some(code) = self.currencies.iter().find(|c| !CURRENCIES.contains(&c.as_str())) {
+            return Err(format!("Unknown currency: '{}'", code));
+        }
+        let url = reqwest::Url::parse(&self.rates_url).map_err(|e| format!("Invalid exchange-rate URL: {}", e))?;
+        if url.scheme() != "https" {
+            return Err("Exchange-rate URL must use https".to_string());
+        }
+        let known = |zone: &String| crate::calendar::zones::offset_minutes(zone, (2000, 1, 1)).is_some();
+        if let Some(zone) = self.time_zones.iter().find(|z| !known(z)) {
+            return Err(format!("Unknown time zone: '{}'", zone));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        let prefs: ConvertPrefs = serde_json::from_str("{}").unwrap();
+        assert_eq!(prefs, ConvertPrefs::default());
+        assert_eq!(prefs.currencies, vec!["USD", "EUR"]);
+        assert!(prefs.validate().is_ok());
+    }
+
+    #[test]
+    fn validation() {
+        let with = |edit: fn(&mut ConvertPrefs)| {
+            let mut prefs = ConvertPrefs::default();
+            edit(&mut prefs);
+            prefs.validate()
+        };
+        assert!(with(|p| p.time_zones = vec!["Europe/Berlin".into(), "PST".into(), "UTC+5:30".into()]).is_ok());
+        assert!(with(|p| p.time_zones = vec!["Mars/Olympus".into()]).is_err());
+        assert!(with(|p| p.currencies = vec!["usd".into()]).is_err());
+        assert!(with(|p| p.rates_url = "http://rates.example.com".into()).is_err());
+        assert!(with(|p| p.currencies = vec!["USD".into(); 9]).is_err());
+    }
+}
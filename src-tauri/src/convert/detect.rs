@@ -0,0 +1,169 @@
+//! Finding amounts of money and measurements in OCR text (pure).
+//!
+//! Money is a currency symbol or ISO code next to a number (`$1,299`,
+//! `12,50 €`, `EUR 40`); a measurement is a number followed by a known
+//! unit (`5 km`, `72°F`, `2.5 lbs`). Numbers may use either decimal
+//! separator: a lone comma followed by exactly three digits is a
+//! thousands separator, otherwise it is the decimal point.
+
+use super::units::{self, Unit, UNITS};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// At most this many of each kind are converted per snip.
+pub const MAX_FOUND: usize = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Money {
+    pub amount: f64,
+    /// ISO 4217 code.
+    pub currency: &'static str,
+    /// As written in the snip.
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measure {
+    pub value: f64,
+    pub unit: &'static Unit,
+    pub raw: String,
+}
+
+/// ISO codes recognized when written out.
+pub const CURRENCIES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CNY", "INR", "CAD", "AUD", "NZD", "CHF", "SEK", "NOK", "DKK", "PLN", "CZK", "HUF",
+    "RON", "TRY", "MXN", "BRL", "KRW", "SGD", "HKD", "ZAR", "ILS", "THB", "PHP", "IDR", "MYR",
+];
+
+/// Symbols, longest first so `US$` wins over `$`.
+const SYMBOLS: &[(&str, &str)] = &[
+    ("US$", "USD"),
+    ("CA$", "CAD"),
+    ("AU$", "AUD"),
+    ("NZ$", "NZD"),
+    ("HK$", "HKD"),
+    ("C$", "CAD"),
+    ("A$", "AUD"),
+    ("S$", "SGD"),
+    ("R$", "BRL"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₩", "KRW"),
+    ("₺", "TRY"),
+    ("₪", "ILS"),
+];
+
+const NUMBER: &str = r"\d(?:[\d,.\x{2009}\x{202F}]*\d)?";
+
+fn alternation(items: impl Iterator<Item = String>) -> String {
+    items.map(|s| regex::escape(&s)).collect::<Vec<_>>().join("|")
+}
+
+static MONEY_RES: LazyLock<[Regex; 3]> = LazyLock::new(|| {
+    let symbols = alternation(SYMBOLS.iter().map(|(s, _)| s.to_string()));
+    let codes = alternation(CURRENCIES.iter().map(|c| c.to_string()));
+    [
+        Regex::new(&format!(r"(?P<cur>{symbols}|\b(?:{codes}))\s?(?P<num>{NUMBER})")).unwrap(),
+        Regex::new(&format!(r"(?P<num>{NUMBER})\s?(?P<cur>[€£¥₹₩₺₪]|(?:{codes})\b)")).unwrap(),
+        // Trailing `$` as in Canadian French: `12,50 $`
+        Regex::new(&format!(r"(?P<num>{NUMBER})\s(?P<cur>\$)(?:\s|$)")).unwrap(),
+    ]
+});
+
+static MEASURE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let mut names: Vec<&str> = UNITS.iter().flat_map(|u| u.names.iter().copied()).collect();
+    names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+    let names = names
+        .iter()
+        .map(|n| if n.chars().count() == 1 { regex::escape(n) } else { format!("(?i:{})", regex::escape(n)) })
+        .collect::<Vec<_>>()
+        .join("|");
+    // The unit must end the word: `5 m` but not `5 min`
+    Regex::new(&format!(r"(?P<num>-?{NUMBER})\s?(?P<unit>{names})(?:[^\p{{L}}\d]|$)")).unwrap()
+});
+
+/// `1,299.00`, `1.299,00`, `12,5`, `1 299` (thin space) → a number.
+pub fn parse_number(raw: &str) -> Option<f64> {
+    let digits: String = raw.chars().filter(|c| !matches!(c, ' ' | '\u{2009}' | '\u{202F}')).collect();
+    let last_dot = digits.rfind('.');
+    let last_comma = digits.rfind(',');
+    let normalized = match (last_dot, last_comma) {
+        (Some(d), Some(c)) if c > d => digits.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => digits.replace(',', ""),
+        (None, Some(c)) if digits.len() - c - 1 == 3 => digits.replace(',', ""),
+        (None, Some(_)) if digits.matches(',').count() == 1 => digits.replace(',', "."),
+        (None, Some(_)) => digits.replace(',', ""),
+        (Some(d), None) if digits.matches('.').count() > 1 && digits.len() - d - 1 == 3 => digits.replace('.', ""),
+        _ => digits,
+    };
+    normalized.parse().ok().filter(|v: &f64| v.is_finite())
+}
+
+/// Amounts of money in `text`, in order, without overlaps.
+pub fn money(text: &str) -> Vec<Money> {
+    let mut found: Vec<(usize, usize, Money)> = Vec::new();
+    for re in MONEY_RES.iter() {
+        for caps in re.captures_iter(text) {
+            let whole = caps.get(0).unwrap();
+            if found.iter().any(|(s, e, _)| whole.start() < *e && *s < whole.end()) {
+                continue;
+            }
+            let cur = &caps["cur"];
+            let currency = SYMBOLS
+                .iter()
+                .find(|(s, _)| *s == cur)
+                .map(|(_, code)| *code)
+                .or_else(|| CURRENCIES.iter().find(|c| **c == cur).copied());
+            if let (Some(currency), Some(amount)) = (currency, parse_number(&caps["num"])) {
+                found.push((whole.start(), whole.end(), Money { amount, currency, raw: whole.as_str().trim().to_string() }));
+            }
+        }
+    }
+    found.sort_by_key(|(start, _, _)| *start);
+    found.into_iter().map(|(_, _, m)| m).take(MAX_FOUND).collect()
+}
+
+/// Measurements in `text`, in order.
+pub fn measures(text: &str) -> Vec<Measure> {
+    MEASURE_RE
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let unit = units::find(&caps["unit"])?;
+            let value = parse_number(&caps["num"])?;
+            let raw = format!("{} {}", caps["num"].trim(), unit.symbol);
+            Some(Measure { value, unit, raw })
+        })
+        .take(MAX_FOUND)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_money() {
+        assert_eq!(parse_number("1,299.50"), Some(1299.5));
+        assert_eq!(parse_number("1.299,50"), Some(1299.5));
+        assert_eq!(parse_number("12,5"), Some(12.5));
+        assert_eq!(parse_number("1,299"), Some(1299.0));
+        assert_eq!(parse_number("1.000.000"), Some(1_000_000.0));
+        let found = money("Total: $1,299.00 (about 1.199,50 € or GBP 1,020). Fee US$5");
+        let summary: Vec<(f64, &str)> = found.iter().map(|m| (m.amount, m.currency)).collect();
+        assert_eq!(summary, vec![(1299.0, "USD"), (1199.5, "EUR"), (1020.0, "GBP"), (5.0, "USD")]);
+        assert_eq!(found[3].raw, "US$5");
+        assert!(money("Version 2.5 of USDA rules, 40 items").is_empty());
+    }
+
+    #[test]
+    fn finds_measurements() {
+        let found = measures("Run 5 km, then 3.1 miles; 72°F outside, box is 2.5 lbs. Wait 5 min in 10 M rows");
+        let summary: Vec<(f64, &str)> = found.iter().map(|m| (m.value, m.unit.symbol)).collect();
+        assert_eq!(summary, vec![(5.0, "km"), (3.1, "mi"), (72.0, "°F"), (2.5, "lb")]);
+        assert_eq!(found[2].raw, "72 °F");
+        assert_eq!(measures("-40 °C")[0].value, -40.0);
+    }
+}
@@ -0,0 +1,189 @@
+//! Conversion domain — currency, unit, and time zone actions, computed
+//! locally.
+//!
+//! When a snip holds amounts of money, measurements, or times with a
+//! zone (`detect.rs`, `clock.rs`), the matching "Convert" actions are
+//! added to its menu, whatever the classifier offered. Running one makes
+//! no LLM call: units (`units.rs`) and zones (`calendar::zones`) are
+//! converted offline, and currencies use exchange rates fetched at most
+//! once a day from `convert.ratesUrl` (`rates.rs`). Targets come from the
+//! `convert` settings (`config.rs`).
+
+pub mod clock;
+pub mod config;
+pub mod detect;
+pub mod rates;
+pub mod units;
+
+use crate::i18n::{t, tf};
+use crate::llm::execute::{ActionResult, ActionResultBody, ActionResultMetadata};
+use crate::llm::types::{Action, ActionMenu};
+
+pub const CURRENCY_ACTION: &str = "convert_currency";
+pub const UNITS_ACTION: &str = "convert_units";
+pub const TIME_ACTION: &str = "convert_timezone";
+
+/// Action id, label key, icon.
+const ACTIONS: &[(&str, &str, &str)] = &[
+    (CURRENCY_ACTION, "menu.convertCurrency", "currency"),
+    (UNITS_ACTION, "menu.convertUnits", "ruler"),
+    (TIME_ACTION, "menu.convertTime", "clock"),
+];
+
+pub fn is_action(action_id: &str) -> bool {
+    ACTIONS.iter().any(|(id, _, _)| *id == action_id)
+}
+
+/// The conversion actions that apply to `text`.
+pub fn actions(text: &str) -> Vec<&'static str> {
+    let mut found = Vec::new();
+    if !detect::money(text).is_empty() {
+        found.push(CURRENCY_ACTION);
+    }
+    if !detect::measures(text).is_empty() {
+        found.push(UNITS_ACTION);
+    }
+    if !clock::times(text).is_empty() {
+        found.push(TIME_ACTION);
+    }
+    found
+}
+
+/// Append the conversion actions for `text` that `menu` lacks, after its
+/// own actions. Returns whether any were added.
+pub fn augment(menu: &mut ActionMenu, text: &str) -> bool {
+    let mut added = false;
+    for id in actions(text) {
+        if menu.actions.iter().any(|a| a.id == id) {
+            continue;
+        }
+        let (_, label, icon) = ACTIONS.iter().find(|(a, _, _)| *a == id).expect("action table");
+        let priority = menu.actions.iter().map(|a| a.priority).max().unwrap_or(0).saturating_add(1);
+        menu.actions.push(Action {
+            id: id.to_string(),
+            label: t(label),
+            icon: icon.to_string(),
+            priority,
+            description: t(&format!("{}Description", label)),
+            requires_execution: true,
+        });
+        added = true;
+    }
+    added
+}
+
+/// The result of a conversion action on `text`, or `None` when
+/// `action_id` isn't one.
+pub async fn execute(action_id: &str, text: &str) -> Option<ActionResult> {
+    let prefs = crate::settings::current().convert;
+    let (lines, note) = match action_id {
+        CURRENCY_ACTION => match currency_lines(text, &prefs).await {
+            Ok(converted) => converted,
+            Err(e) => return Some(ActionResult::error(action_id, &tf("error.ratesUnavailable", &[("error", e.as_str())]))),
+        },
+        UNITS_ACTION => (unit_lines(text), t("convert.offlineNote")),
+        TIME_ACTION => time_lines(text, &prefs),
+        _ => return None,
+    };
+    if lines.is_empty() {
+        return Some(ActionResult::error(action_id, &t("error.nothingToConvert")));
+    }
+    log::info!("[CONVERT] {}: {} conversions", action_id, lines.len());
+    Some(ActionResult {
+        status: "success".to_string(),
+        action_id: action_id.to_string(),
+        result: ActionResultBody {
+            result_type: "text".to_string(),
+            text: Some(format!("{}\n\n{}", lines.join("\n"), note)),
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: None,
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata {
+            processing_note: Some("Converted locally (no LLM call)".to_string()),
+            ..ActionResultMetadata::default()
+        }),
+    })
+}
+
+async fn currency_lines(text: &str, prefs: &config::ConvertPrefs) -> Result<(Vec<String>, String), String> {
+    let found = detect::money(text);
+    if found.is_empty() {
+        return Ok((Vec::new(), String::new()));
+    }
+    let rates = rates::load(&prefs.rates_url).await?;
+    let lines = found
+        .iter()
+        .filter_map(|money| {
+            let converted: Vec<String> = prefs
+                .currencies
+                .iter()
+                .filter(|code| code.as_str() != money.currency)
+                .filter_map(|code| Some(format_money(rates.convert(money.amount, money.currency, code)?, code)))
+                .collect();
+            (!converted.is_empty()).then(|| format!("{} = {}", money.raw, converted.join(" · ")))
+        })
+        .collect();
+    let host = reqwest::Url::parse(&rates.source).ok().and_then(|u| u.host_str().map(String::from)).unwrap_or_default();
+    Ok((lines, tf("convert.ratesNote", &[("date", rates.date.as_str()), ("source", host.as_str())])))
+}
+
+fn unit_lines(text: &str) -> Vec<String> {
+    detect::measures(text)
+        .iter()
+        .map(|m| {
+            let (value, unit) = units::convert(m.value, m.unit);
+            format!("{} = {} {}", m.raw, units::format_value(value), unit.symbol)
+        })
+        .collect()
+}
+
+fn time_lines(text: &str, prefs: &config::ConvertPrefs) -> (Vec<String>, String) {
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+    let (y, m, d, ..) = crate::capture::autosave::utc_parts(now.as_secs());
+    let targets = match prefs.time_zones.as_slice() {
+        [] => vec![clock::local_zone().unwrap_or_else(|| "UTC".to_string())],
+        zones => zones.to_vec(),
+    };
+    let lines = clock::times(text)
+        .iter()
+        .filter_map(|time| {
+            let converted: Vec<String> = targets
+                .iter()
+                .filter(|zone| !zone.eq_ignore_ascii_case(&time.zone))
+                .filter_map(|zone| {
+                    let (hour, minute, shift) = clock::convert(time, (y, m, d), zone)?;
+                    let day = match shift {
+                        1 => format!(" ({})", t("convert.nextDay")),
+                        -1 => format!(" ({})", t("convert.previousDay")),
+                        _ => String::new(),
+                    };
+                    Some(format!("{:02}:{:02} {}{}", hour, minute, zone, day))
+                })
+                .collect();
+            (!converted.is_empty()).then(|| format!("{} = {}", time.raw, converted.join(" · ")))
+        })
+        .collect();
+    (lines, tf("convert.timeNote", &[("date", format!("{:04}-{:02}-{:02}", y, m, d).as_str())]))
+}
+
+/// `1,115.40 EUR`; no decimals for currencies without minor units.
+fn format_money(amount: f64, code: &str) -> String {
+    let decimals = if matches!(code, "JPY" | "KRW" | "HUF" | "IDR") { 0 } else { 2 };
+    let fixed = format!("{:.*}", decimals, amount.abs());
+    let (whole, fraction) = fixed.split_once('.').map_or((fixed.as_str(), None), |(w, f)| (w, Some(f)));
+    let mut grouped = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if amount < 0.0 { "-" } else { "" };
+    match fraction {
+        Some(f) => format!("{}{}.{} {}", sign, grouped, f, code),
+        None => format!("{}{} {}", sign, grouped, code),
+    }
+}
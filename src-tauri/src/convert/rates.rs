@@ -0,0 +1,152 @@
+//! Exchange rates — fetched at most once a day, cached on disk.
+//!
+//! The source is `convert.ratesUrl` (Frankfurter by default); both the
+//! Frankfurter shape (`{base, date, rates}`) and the open.er-api shape
+//! (`{base_code, time_last_update_utc, rates}`) are read. The cache
+//! remembers which URL it came from, so changing the source refetches.
+//! When offline, or when the fetch fails, a stale cache is still used and
+//! the result says how old it is.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const MAX_AGE_SECS: u64 = 24 * 60 * 60;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Rates {
+    /// The URL these rates were fetched from.
+    pub source: String,
+    pub base: String,
+    /// The date the source publishes for the rates.
+    pub date: String,
+    pub fetched_at: u64,
+    /// Units of each currency per one `base`.
+    pub rates: BTreeMap<String, f64>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(alias = "base_code")]
+    base: String,
+    #[serde(default, alias = "time_last_update_utc")]
+    date: String,
+    rates: BTreeMap<String, f64>,
+}
+
+impl Rates {
+    /// Read a rates response from `source`.
+    pub fn parse(source: &str, json: &str, fetched_at: u64) -> Result<Self, String> {
+        let response: Response = serde_json::from_str(json).map_err(|e| format!("Unexpected rates response: {}", e))?;
+        let mut rates = response.rates;
+        rates.retain(|_, rate| rate.is_finite() && *rate > 0.0);
+        rates.insert(response.base.clone(), 1.0);
+        Ok(Self { source: source.to_string(), base: response.base, date: response.date, fetched_at, rates })
+    }
+
+    /// `amount` of `from` in `to`, through the base currency.
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        Some(amount / self.rates.get(from)? * self.rates.get(to)?)
+    }
+
+    pub fn is_stale(&self, now: u64) -> bool {
+        now.saturating_sub(self.fetched_at) >= MAX_AGE_SECS
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("exchange-rates.json"))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn read_cache(source: &str) -> Option<Rates> {
+    let raw = std::fs::read_to_string(cache_path()?).ok()?;
+    serde_json::from_str::<Rates>(&raw).ok().filter(|r| r.source == source)
+}
+
+fn write_cache(rates: &Rates) {
+    let Some(path) = cache_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = serde_json::to_string(rates).map_err(|e| e.to_string()).and_then(|json| {
+        std::fs::write(&path, json).map_err(|e| e.to_string())
+    }) {
+        log::warn!("[CONVERT] Failed to cache exchange rates: {}", e);
+    }
+}
+
+async fn fetch(source: &str) -> Result<Rates, String> {
+    let response = crate::net::client()
+        .get(source)
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    Rates::parse(source, &body, now_secs())
+}
+
+/// Today's rates from `source`: the cache while fresh, else a fetch,
+/// else the stale cache.
+pub async fn load(source: &str) -> Result<Rates, String> {
+    let cached = read_cache(source);
+    if let Some(rates) = cached.as_ref().filter(|r| !r.is_stale(now_secs())) {
+        return Ok(rates.clone());
+    }
+    let fetched = match crate::net::connectivity::check() {
+        Ok(()) => fetch(source).await,
+        Err(e) => Err(e.to_string()),
+    };
+    match (fetched, cached) {
+        (Ok(rates), _) => {
+            log::info!("[CONVERT] Fetched {} exchange rates ({})", rates.rates.len(), rates.date);
+            write_cache(&rates);
+            Ok(rates)
+        }
+        (Err(e), Some(stale)) => {
+            log::warn!("[CONVERT] Using cached rates from {}: {}", stale.date, e);
+            Ok(stale)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_both_response_shapes() {
+        let frankfurter = r#"{"amount":1.0,"base":"EUR","date":"2026-10-15","rates":{"USD":1.1646,"GBP":0.8672}}"#;
+        let rates = Rates::parse("https://api.frankfurter.app/latest", frankfurter, 100).unwrap();
+        assert_eq!((rates.base.as_str(), rates.date.as_str()), ("EUR", "2026-10-15"));
+        assert_eq!(rates.rates.get("EUR"), Some(&1.0));
+        let er_api = r#"{"result":"success","base_code":"USD","time_last_update_utc":"Thu, 15 Oct 2026 00:02:31 +0000","rates":{"USD":1,"JPY":149.5,"BAD":-1}}"#;
+        let rates = Rates::parse("https://open.er-api.com/v6/latest/USD", er_api, 100).unwrap();
+        assert_eq!(rates.base, "USD");
+        assert!(!rates.rates.contains_key("BAD"));
+        assert!(Rates::parse("x", r#"{"error":"nope"}"#, 0).is_err());
+    }
+
+    #[test]
+    fn cross_rates_and_staleness() {
+        let json = r#"{"base":"EUR","date":"2026-10-15","rates":{"USD":1.25,"GBP":0.8}}"#;
+        let rates = Rates::parse("src", json, 1_000).unwrap();
+        assert_eq!(rates.convert(10.0, "EUR", "USD"), Some(12.5));
+        assert_eq!(rates.convert(12.5, "USD", "GBP"), Some(8.0));
+        assert_eq!(rates.convert(1.0, "USD", "XYZ"), None);
+        assert!(!rates.is_stale(1_000 + MAX_AGE_SECS - 1));
+        assert!(rates.is_stale(1_000 + MAX_AGE_SECS));
+    }
+}
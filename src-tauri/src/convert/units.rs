@@ -0,0 +1,172 @@
+//! Unit math — length, mass, volume, speed, area, temperature (pure).
+//!
+//! Every unit converts to its dimension's base unit (metre, kilogram,
+//! litre, metre per second, square metre, kelvin) as `value * factor +
+//! offset`; only temperatures have an offset. A metric measurement is
+//! shown in US customary units and the other way round, in the unit that
+//! suits the size (`5 km` → `3.11 mi`, `300 m` → `984 ft`).
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Speed,
+    Area,
+    Temperature,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Unit {
+    /// How results are written.
+    pub symbol: &'static str,
+    /// Spellings recognized in text; matched case-insensitively except
+    /// single letters (`m`, `g`), which are too easily something else.
+    pub names: &'static [&'static str],
+    pub dimension: Dimension,
+    pub metric: bool,
+    factor: f64,
+    offset: f64,
+}
+
+const fn unit(symbol: &'static str, names: &'static [&'static str], dimension: Dimension, metric: bool, factor: f64) -> Unit {
+    Unit { symbol, names, dimension, metric, factor, offset: 0.0 }
+}
+
+use Dimension::*;
+
+pub const UNITS: &[Unit] = &[
+    unit("mm", &["mm", "millimeter", "millimeters", "millimetre", "millimetres"], Length, true, 0.001),
+    unit("cm", &["cm", "centimeter", "centimeters", "centimetre", "centimetres"], Length, true, 0.01),
+    unit("m", &["m", "meter", "meters", "metre", "metres"], Length, true, 1.0),
+    unit("km", &["km", "kilometer", "kilometers", "kilometre", "kilometres"], Length, true, 1000.0),
+    unit("in", &["inch", "inches", "″"], Length, false, 0.0254),
+    unit("ft", &["ft", "foot", "feet", "′"], Length, false, 0.3048),
+    unit("yd", &["yd", "yard", "yards"], Length, false, 0.9144),
+    unit("mi", &["mi", "mile", "miles"], Length, false, 1609.344),
+    unit("g", &["g", "gram", "grams"], Mass, true, 0.001),
+    unit("kg", &["kg", "kilogram", "kilograms", "kilo", "kilos"], Mass, true, 1.0),
+    unit("oz", &["oz", "ounce", "ounces"], Mass, false, 0.028_349_523_125),
+    unit("lb", &["lb", "lbs", "pound", "pounds"], Mass, false, 0.453_592_37),
+    unit("ml", &["ml", "milliliter", "milliliters", "millilitre", "millilitres"], Volume, true, 0.001),
+    unit("l", &["l", "L", "liter", "liters", "litre", "litres"], Volume, true, 1.0),
+    unit("fl oz", &["fl oz", "fl. oz", "fluid ounce", "fluid ounces"], Volume, false, 0.029_573_529_562_5),
+    unit("cup", &["cup", "cups"], Volume, false, 0.236_588_236_5),
+    unit("gal", &["gal", "gallon", "gallons"], Volume, false, 3.785_411_784),
+    unit("km/h", &["km/h", "kph", "kmh"], Speed, true, 1.0 / 3.6),
+    unit("m/s", &["m/s"], Speed, true, 1.0),
+    unit("mph", &["mph"], Speed, false, 0.447_04),
+    unit("m²", &["m²", "m2", "sq m", "square meters", "square metres"], Area, true, 1.0),
+    unit("ha", &["ha", "hectare", "hectares"], Area, true, 10_000.0),
+    unit("ft²", &["ft²", "sq ft", "square feet"], Area, false, 0.092_903_04),
+    unit("acre", &["acre", "acres"], Area, false, 4_046.856_422_4),
+    Unit { symbol: "°C", names: &["°c", "° c", "celsius"], dimension: Temperature, metric: true, factor: 1.0, offset: 273.15 },
+    Unit {
+        symbol: "°F",
+        names: &["°f", "° f", "fahrenheit"],
+        dimension: Temperature,
+        metric: false,
+        factor: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+    },
+];
+
+/// The unit spelled `name`, if known.
+pub fn find(name: &str) -> Option<&'static Unit> {
+    let lower = name.trim().to_lowercase();
+    UNITS.iter().find(|u| {
+        u.names.iter().any(|n| if n.chars().count() == 1 { *n == name.trim() } else { *n == lower })
+    })
+}
+
+fn by_symbol(symbol: &str) -> &'static Unit {
+    UNITS.iter().find(|u| u.symbol == symbol).expect("unit table")
+}
+
+/// The unit of the other system that reads best for a value of `base`
+/// base units.
+fn counterpart(from: &Unit, base: f64) -> &'static Unit {
+    let size = base.abs();
+    let symbol = match (from.dimension, from.metric) {
+        (Length, true) if size < 1.0 => "in",
+        (Length, true) if size < 1000.0 => "ft",
+        (Length, true) => "mi",
+        (Length, false) if size < 1.0 => "cm",
+        (Length, false) if size < 1000.0 => "m",
+        (Length, false) => "km",
+        (Mass, true) if size < 0.5 => "oz",
+        (Mass, true) => "lb",
+        (Mass, false) if size < 1.0 => "g",
+        (Mass, false) => "kg",
+        (Volume, true) if size < 0.5 => "fl oz",
+        (Volume, true) => "gal",
+        (Volume, false) if size < 1.0 => "ml",
+        (Volume, false) => "l",
+        (Speed, true) => "mph",
+        (Speed, false) => "km/h",
+        (Area, true) if size < 4000.0 => "ft²",
+        (Area, true) => "acre",
+        (Area, false) if size < 10_000.0 => "m²",
+        (Area, false) => "ha",
+        (Temperature, true) => "°F",
+        (Temperature, false) => "°C",
+    };
+    by_symbol(symbol)
+}
+
+/// `value` in `from`, converted to the other system.
+pub fn convert(value: f64, from: &Unit) -> (f64, &'static Unit) {
+    let base = value * from.factor + from.offset;
+    let to = counterpart(from, base);
+    ((base - to.offset) / to.factor, to)
+}
+
+/// A number with about four significant digits, without trailing zeros.
+pub fn format_value(value: f64) -> String {
+    let digits = if value.abs() >= 1000.0 {
+        0
+    } else if value.abs() >= 100.0 {
+        1
+    } else if value.abs() >= 1.0 {
+        2
+    } else {
+        4
+    };
+    let text = format!("{:.*}", digits, value);
+    let text = if text.contains('.') { text.trim_end_matches('0').trim_end_matches('.').to_string() } else { text };
+    if text == "-0" { "0".to_string() } else { text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converted(value: f64, name: &str) -> String {
+        let (v, unit) = convert(value, find(name).unwrap());
+        format!("{} {}", format_value(v), unit.symbol)
+    }
+
+    #[test]
+    fn converts_between_systems() {
+        assert_eq!(converted(5.0, "km"), "3.11 mi");
+        assert_eq!(converted(300.0, "m"), "984.3 ft");
+        assert_eq!(converted(12.0, "inches"), "30.48 cm");
+        assert_eq!(converted(2.0, "lbs"), "907.2 g");
+        assert_eq!(converted(100.0, "°F"), "37.78 °C");
+        assert_eq!(converted(-40.0, "Celsius"), "-40 °F");
+        assert_eq!(converted(60.0, "mph"), "96.56 km/h");
+        assert_eq!(converted(2.0, "l"), "0.5283 gal");
+        assert_eq!(converted(1200.0, "sq ft"), "111.5 m²");
+    }
+
+    #[test]
+    fn single_letters_are_case_sensitive() {
+        assert_eq!(find("m").map(|u| u.symbol), Some("m"));
+        assert_eq!(find("M"), None);
+        assert_eq!(find("G"), None);
+        assert_eq!(find("KM").map(|u| u.symbol), Some("km"));
+        assert_eq!(find("L").map(|u| u.symbol), Some("l"));
+        assert_eq!(format_value(1234.6), "1235");
+        assert_eq!(format_value(0.5), "0.5");
+    }
+}
@@ -0,0 +1,202 @@
+//! Crash safety — panics and hangs are reported and recovered from.
+//!
+//! `install` sets a panic hook at the top of `run()`. A panic anywhere —
+//! the tray callback, a hotkey, a `spawn_blocking` task — writes a
+//! `CrashReport` to `~/.config/omni-glass/crashes/` and, once `attach` has
+//! run, recovers on a fresh thread:
+//!   - the overlay is closed, so a half-started snip can't cover the screen
+//!     (after a hang, only when the capture hung)
+//!   - the tray is rebuilt, or recreated if it is gone
+//!   - the user gets a notification that a report was saved
+//!
+//! Tray and hotkey callbacks run through `guard`, which stops the unwind
+//! there instead of letting it reach the event loop. Pipeline state needs
+//! no extra saving: `session.json` is rewritten on every change, so a
+//! snip cut short by a crash that does end the process reopens at the
+//! next launch, where `attach` tells the user what happened.
+//!
+//! Work that can hang without failing registers a `watch`. The watchdog
+//! thread reports each one past its limit as a hang and recovers the
+//! same way; the work itself is left to finish or time out.
+
+pub mod report;
+pub mod watchdog;
+
+use crate::i18n::{t, tf};
+use report::{CrashReport, Kind};
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use watchdog::Watches;
+
+/// How often the watchdog checks watched work.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(2);
+/// Reports kept on disk; older ones are deleted.
+const MAX_REPORTS: usize = 10;
+/// Written with each report; removed once the user has been told.
+const PENDING_FILE: &str = "pending";
+/// Stage of a panic outside any span.
+const NO_STAGE: &str = "-";
+
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+static WATCHES: LazyLock<Mutex<Watches>> = LazyLock::new(Mutex::default);
+
+fn crashes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("crashes"))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Report files, oldest first.
+fn report_files() -> Vec<PathBuf> {
+    let Some(dir) = crashes_dir() else { return Vec::new() };
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn save(report: &CrashReport) {
+    let Some(dir) = crashes_dir() else { return };
+    let _ = std::fs::create_dir_all(&dir);
+    if let Err(e) = std::fs::write(dir.join(report.file_name()), report.render()) {
+        log::warn!("[CRASH] Failed to write crash report: {}", e);
+    }
+    let _ = std::fs::write(dir.join(PENDING_FILE), report.file_name());
+    let files = report_files();
+    for old in files.iter().take(files.len().saturating_sub(MAX_REPORTS)) {
+        let _ = std::fs::remove_file(old);
+    }
+}
+
+fn version() -> String {
+    APP.get().map_or(env!("CARGO_PKG_VERSION").to_string(), |app| app.package_info().version.to_string())
+}
+
+fn on_panic(info: &std::panic::PanicHookInfo<'_>) {
+    let thread = std::thread::current();
+    let stage = tracing::Span::current().metadata().map_or(NO_STAGE, |m| m.name()).to_string();
+    let report = CrashReport {
+        kind: Kind::Panic,
+        at: now_secs(),
+        version: version(),
+        thread: thread.name().unwrap_or("unnamed").to_string(),
+        stage,
+        message: report::panic_message(info.payload()),
+        location: info.location().map(|l| l.to_string()),
+        backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+    };
+    log::error!("[CRASH] Panic in {} ({}): {}", report.thread, report.stage, report.message);
+    save(&report);
+    if let Some(app) = APP.get().cloned() {
+        std::thread::spawn(move || recover(&app, Kind::Panic, &report.stage));
+    }
+}
+
+/// Install the panic hook. Called at the top of `run()`, after the logger.
+pub fn install() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default(info);
+        on_panic(info);
+    }));
+}
+
+/// Enable recovery, start the watchdog, and tell the user if the last
+/// run ended in a crash.
+pub fn attach(app: &tauri::AppHandle) {
+    let _ = APP.set(app.clone());
+    let pending = crashes_dir().map(|d| d.join(PENDING_FILE)).filter(|p| p.exists());
+    if let Some(path) = pending {
+        let _ = std::fs::remove_file(path);
+        log::warn!("[CRASH] The previous run crashed; report in {:?}", crashes_dir());
+        notify(app, t("crash.previousTitle"), t("crash.previousBody"));
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCHDOG_INTERVAL);
+        let hung = WATCHES.lock().unwrap_or_else(|e| e.into_inner()).overdue(Instant::now());
+        for hung in hung {
+            log::error!("[CRASH] {} has not finished after {}s", hung.label, hung.elapsed.as_secs());
+            save(&CrashReport {
+                kind: Kind::Hang,
+                at: now_secs(),
+                version: version(),
+                thread: "watchdog".to_string(),
+                stage: hung.label.clone(),
+                message: format!("no progress for {}s", hung.elapsed.as_secs()),
+                location: None,
+                backtrace: String::new(),
+            });
+            recover(&app, Kind::Hang, &hung.label);
+        }
+    });
+}
+
+fn notify(app: &tauri::AppHandle, title: String, body: String) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("[CRASH] Failed to show notification: {}", e);
+    }
+}
+
+/// Put the app back in a usable state after a panic or hang in `stage`.
+fn recover(app: &tauri::AppHandle, kind: Kind, stage: &str) {
+    log::info!("[CRASH] Recovering from {} in {}", kind.as_str(), stage);
+    // A hung OCR or plugin call leaves the overlay of the next snip alone
+    if kind == Kind::Panic || stage == "capture" {
+        if let Some(overlay) = app.get_webview_window("overlay") {
+            let _ = overlay.destroy();
+        }
+    }
+    let tray_app = app.clone();
+    let _ = app.run_on_main_thread(move || crate::tray::restart(&tray_app));
+    let _ = app.emit("app-recovered", serde_json::json!({ "kind": kind.as_str(), "stage": stage }));
+
+    let key = if kind == Kind::Hang { "crash.hungBody" } else { "crash.recoveredBody" };
+    let stage = if stage == NO_STAGE { t("crash.unknownStage") } else { stage.to_string() };
+    notify(app, t("crash.recoveredTitle"), tf(key, &[("stage", stage.as_str())]));
+    if let Some(dir) = crashes_dir() {
+        let _ = std::fs::remove_file(dir.join(PENDING_FILE));
+    }
+}
+
+/// Run a tray or hotkey callback; a panic in it is recovered from (by the
+/// hook) instead of unwinding into the event loop.
+pub fn guard(work: impl FnOnce()) {
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(work));
+}
+
+/// Watched work; the watch ends when this is dropped.
+pub struct Watch(u64);
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        WATCHES.lock().unwrap_or_else(|e| e.into_inner()).finish(self.0);
+    }
+}
+
+/// Watch `label` work for the watchdog: past `limit` it counts as hung.
+pub fn watch(label: impl Into<String>, limit: Duration) -> Watch {
+    Watch(WATCHES.lock().unwrap_or_else(|e| e.into_inner()).start(label.into(), limit, Instant::now()))
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the latest crash or hang report, or `null`.
+#[tauri::command]
+pub fn get_last_crash() -> Option<String> {
+    report_files().last().and_then(|p| std::fs::read_to_string(p).ok())
+}
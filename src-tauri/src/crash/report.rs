@@ -0,0 +1,111 @@
+//! Crash and hang reports (pure, no I/O).
+//!
+//! A `CrashReport` is what the panic hook and the watchdog write to the
+//! crashes directory: what failed, where, and in which pipeline stage.
+//! Snip text never goes in; only the panic message, which the caller
+//! scrubs before it is shown in the diagnostics bundle.
+
+use std::any::Any;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Panic,
+    Hang,
+}
+
+impl Kind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Kind::Panic => "panic",
+            Kind::Hang => "hang",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashReport {
+    pub kind: Kind,
+    /// Seconds since the epoch.
+    pub at: u64,
+    pub version: String,
+    pub thread: String,
+    /// Stage that failed: the innermost span for a panic, the watched
+    /// work for a hang.
+    pub stage: String,
+    pub message: String,
+    /// `file:line:column` of the panic.
+    pub location: Option<String>,
+    pub backtrace: String,
+}
+
+impl CrashReport {
+    /// File name in the crashes directory; sorts oldest first.
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.txt", self.at, self.kind.as_str())
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "Omni-Glass {} {}\nat: {}\nthread: {}\nstage: {}\nmessage: {}\n",
+            self.version,
+            self.kind.as_str(),
+            self.at,
+            self.thread,
+            self.stage,
+            self.message
+        );
+        if let Some(location) = &self.location {
+            out.push_str(&format!("location: {}\n", location));
+        }
+        if !self.backtrace.is_empty() {
+            out.push_str("\nbacktrace:\n");
+            out.push_str(&self.backtrace);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The message a panic was raised with (`panic!("...")` gives a `&str`
+/// or a `String`; anything else has no text).
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_the_panic_message() {
+        let caught = std::panic::catch_unwind(|| panic!("index {} out of range", 3)).unwrap_err();
+        assert_eq!(panic_message(caught.as_ref()), "index 3 out of range");
+        assert_eq!(panic_message(&"static"), "static");
+        assert_eq!(panic_message(&42u8), "Box<dyn Any>");
+    }
+
+    #[test]
+    fn renders_location_and_backtrace_only_when_known() {
+        let mut report = CrashReport {
+            kind: Kind::Hang,
+            at: 1_700_000_000,
+            version: "1.2.0".to_string(),
+            thread: "tokio-runtime-worker".to_string(),
+            stage: "capture".to_string(),
+            message: "no progress for 12s".to_string(),
+            location: None,
+            backtrace: String::new(),
+        };
+        assert_eq!(report.file_name(), "1700000000-hang.txt");
+        assert_eq!(
+            report.render(),
+            "Omni-Glass 1.2.0 hang\nat: 1700000000\nthread: tokio-runtime-worker\nstage: capture\nmessage: no progress for 12s\n"
+        );
+        report.location = Some("src/tray.rs:10:5".to_string());
+        assert!(report.render().ends_with("location: src/tray.rs:10:5\n"));
+    }
+}
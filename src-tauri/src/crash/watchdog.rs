@@ -0,0 +1,83 @@
+//! Watched work and overdue detection (pure, no I/O).
+//!
+//! Work that can hang without failing — a screen capture, OCR, a WASM
+//! plugin call — registers a watch with a time limit and drops it when
+//! done. The watchdog thread asks `overdue` every few seconds; each
+//! overdue watch is reported once, however long it then stays open.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+struct Watch {
+    label: String,
+    started: Instant,
+    limit: Duration,
+    reported: bool,
+}
+
+/// Work past its limit: what it is and how long it has run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hung {
+    pub label: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Default)]
+pub struct Watches {
+    next_id: u64,
+    open: HashMap<u64, Watch>,
+}
+
+impl Watches {
+    pub fn start(&mut self, label: String, limit: Duration, now: Instant) -> u64 {
+        self.next_id += 1;
+        self.open.insert(self.next_id, Watch { label, started: now, limit, reported: false });
+        self.next_id
+    }
+
+    pub fn finish(&mut self, id: u64) {
+        self.open.remove(&id);
+    }
+
+    /// Watches past their limit that haven't been reported yet.
+    pub fn overdue(&mut self, now: Instant) -> Vec<Hung> {
+        let mut hung: Vec<Hung> = self
+            .open
+            .values_mut()
+            .filter(|w| !w.reported && now.saturating_duration_since(w.started) > w.limit)
+            .map(|w| {
+                w.reported = true;
+                Hung { label: w.label.clone(), elapsed: now.saturating_duration_since(w.started) }
+            })
+            .collect();
+        hung.sort_by_key(|h| std::cmp::Reverse(h.elapsed));
+        hung
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overdue_work_once() {
+        let start = Instant::now();
+        let mut watches = Watches::default();
+        watches.start("capture".to_string(), Duration::from_secs(10), start);
+        watches.start("ocr".to_string(), Duration::from_secs(20), start);
+
+        assert!(watches.overdue(start + Duration::from_secs(5)).is_empty());
+        let hung = watches.overdue(start + Duration::from_secs(11));
+        assert_eq!(hung, vec![Hung { label: "capture".to_string(), elapsed: Duration::from_secs(11) }]);
+        assert!(watches.overdue(start + Duration::from_secs(15)).is_empty());
+    }
+
+    #[test]
+    fn finished_work_is_never_overdue() {
+        let start = Instant::now();
+        let mut watches = Watches::default();
+        let id = watches.start("plugin".to_string(), Duration::from_secs(1), start);
+        watches.finish(id);
+        assert!(watches.overdue(start + Duration::from_secs(60)).is_empty());
+    }
+}
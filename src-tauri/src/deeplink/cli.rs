@@ -0,0 +1,93 @@
+//! Command-line flags as deep links (pure, no I/O).
+//!
+//! `omni-glass --snip` or `omni-glass --launcher "convert 5 km"` does what
+//! the matching `omniglass://` link does, so scripts and launchers that
+//! can't open URLs can still drive the app. Each flag becomes a link and
+//! goes through `parse.rs` like any other, with the same validation.
+//!
+//! | Flag | Link |
+//! |---|---|
+//! | `--snip` | `omniglass://snip` |
+//! | `--snip-region x,y,w,h` | `omniglass://snip?region=x,y,w,h` |
+//! | `--launcher [query]` | `omniglass://launcher?q=...` |
+//! | `--ask [question]` | `omniglass://ask?q=...` |
+//! | `--ocr <path>` | `omniglass://ocr?path=...` |
+//! | `--history <id>` | `omniglass://history/<id>` |
+
+use super::parse::SCHEME;
+use reqwest::Url;
+
+/// Whether `arg` is itself an `omniglass:` link.
+pub fn is_link(arg: &str) -> bool {
+    arg.to_ascii_lowercase().starts_with(&format!("{}:", SCHEME))
+}
+
+/// The links `args` ask for (program name excluded). Links passed as
+/// arguments and arguments the OS adds (`-psn_…`) are skipped.
+pub fn links(args: &[String]) -> Result<Vec<String>, String> {
+    let mut links = Vec::new();
+    let mut args = args.iter().map(String::as_str).peekable();
+    while let Some(flag) = args.next() {
+        // A value is the next argument unless that is another flag or a link
+        let mut value = || args.next_if(|a| !a.starts_with("--") && !is_link(a));
+        let link = match flag {
+            "--snip" => link("snip", &[]),
+            "--snip-region" => link("snip", &[("region", required(flag, value())?)]),
+            "--launcher" => link("launcher", &value().map(|q| ("q", q)).into_iter().collect::<Vec<_>>()),
+            "--ask" => link("ask", &value().map(|q| ("q", q)).into_iter().collect::<Vec<_>>()),
+            "--ocr" => link("ocr", &[("path", required(flag, value())?)]),
+            "--history" => format!("{}://history/{}", SCHEME, required(flag, value())?),
+            _ => continue,
+        };
+        links.push(link);
+    }
+    Ok(links)
+}
+
+fn required<'a>(flag: &str, value: Option<&'a str>) -> Result<&'a str, String> {
+    value.ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn link(route: &str, params: &[(&str, &str)]) -> String {
+    let base = format!("{}://{}", SCHEME, route);
+    match params {
+        [] => base,
+        _ => Url::parse_with_params(&base, params).map(String::from).unwrap_or(base),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse::{parse, DeepLink, Region};
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn parsed(list: &[&str]) -> Vec<DeepLink> {
+        links(&args(list)).unwrap().iter().map(|l| parse(l).unwrap()).collect()
+    }
+
+    #[test]
+    fn flags_become_links() {
+        assert_eq!(parsed(&["--snip"]), [DeepLink::Snip]);
+        assert_eq!(
+            parsed(&["--snip-region", "10,20,300,200"]),
+            [DeepLink::SnipRegion(Region { x: 10, y: 20, width: 300, height: 200 })]
+        );
+        assert_eq!(
+            parsed(&["--launcher", "5 km in miles & more", "--ask"]),
+            [DeepLink::Launcher { query: Some("5 km in miles & more".into()) }, DeepLink::AskClipboard { question: None }]
+        );
+        assert_eq!(parsed(&["--history", "42"]), [DeepLink::History { id: 42 }]);
+    }
+
+    #[test]
+    fn links_and_unknown_arguments_are_skipped() {
+        assert!(links(&args(&["-psn_0_12345", "omniglass://snip", "--verbose"])).unwrap().is_empty());
+        assert_eq!(parsed(&["--launcher", "omniglass://ask"]), [DeepLink::Launcher { query: None }]);
+        assert!(links(&args(&["--ocr"])).unwrap_err().contains("--ocr"));
+        assert!(links(&args(&["--snip-region", "--snip"])).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Deep-link domain — `omniglass://` URLs, so Raycast, Alfred, Shortcuts,
+//! and scripts can drive Omni-Glass.
+//!
+//! Links arrive three ways: at launch (`get_current`), while running on
+//! macOS (`on_open_url`), and on Windows / Linux as the arguments of a
+//! second instance, which the single-instance plugin forwards to the
+//! running one (`handle_args`). Command-line flags (`--snip`, …) become
+//! links too (`cli.rs`), at launch and from a second instance. `parse.rs`
+//! validates each link before anything runs; an invalid link is logged and
+//! ignored. The `ocr` and `ask` links run automation intents (intents/).
+//!
+//! A second launch with nothing to do brings the running app forward,
+//! unless `focusExisting` is off.
+
+pub mod cli;
+pub mod parse;
+
+use crate::capture::{self, CaptureState};
+use crate::intents::Intent;
+use crate::llm::ActionMenu;
+use crate::windows::{self, spec::Window, WindowEvent};
+use image::DynamicImage;
+use parse::{DeepLink, Region};
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Query handed to the text launcher (`take_launcher_query`).
+static LAUNCHER_QUERY: Mutex<Option<String>> = Mutex::new(None);
+
+/// Handle the link the app was launched with, and listen for more.
+/// Called once from `setup`.
+pub fn attach(app: &tauri::AppHandle) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+    // Installers register the scheme; dev builds and AppImages do it here
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        log::warn!("[DEEPLINK] Could not register {}://: {}", parse::SCHEME, e);
+    }
+    let listener = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle(&listener, url.as_str());
+        }
+    });
+    match app.deep_link().get_current() {
+        Ok(urls) => urls.unwrap_or_default().iter().for_each(|url| handle(app, url.as_str())),
+        Err(e) => log::warn!("[DEEPLINK] Could not read the launch link: {}", e),
+    }
+    let args: Vec<String> = std::env::args().collect();
+    cli_links(&args).iter().for_each(|link| handle(app, link));
+}
+
+/// Single-instance callback: run a second instance's `omniglass:` links
+/// and command-line flags here, or bring this instance forward.
+pub fn handle_args(app: &tauri::AppHandle, args: &[String]) {
+    let mut links: Vec<String> = args.iter().filter(|a| cli::is_link(a)).cloned().collect();
+    links.extend(cli_links(args));
+    if links.is_empty() {
+        focus_existing(app);
+    }
+    links.iter().for_each(|link| handle(app, link));
+}
+
+/// Links asked for by the flags in `args` (a full argv).
+fn cli_links(args: &[String]) -> Vec<String> {
+    cli::links(args.get(1..).unwrap_or_default()).unwrap_or_else(|e| {
+        log::warn!("[DEEPLINK] Ignored arguments: {}", e);
+        Vec::new()
+    })
+}
+
+/// A plain second launch: show the window the user is most likely after.
+fn focus_existing(app: &tauri::AppHandle) {
+    if !crate::settings::current().focus_existing {
+        log::info!("[DEEPLINK] Second launch ignored (focusExisting is off)");
+        return;
+    }
+    if windows::focus_first(app, &[Window::ActionMenu, Window::Launcher]) {
+        return;
+    }
+    if let Err(e) = windows::show(app, Window::Settings) {
+        log::warn!("[DEEPLINK] {}", e);
+    }
+}
+
+/// Validate and run one deep link.
+pub fn handle(app: &tauri::AppHandle, url: &str) {
+    let link = match parse::parse(url) {
+        Ok(link) => link,
+        Err(e) => {
+            log::warn!("[DEEPLINK] Ignored link: {}", e);
+            return;
+        }
+    };
+    let done = match link {
+        DeepLink::Snip => {
+            log::info!("[DEEPLINK] Snip");
+            let now_ms = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            crate::tray::start_snip_mode(app, now_ms.as_millis() as f64);
+            Ok(())
+        }
+        DeepLink::SnipRegion(region) => snip_region(app, region),
+        DeepLink::Launcher { query } => {
+            log::info!("[DEEPLINK] Launcher (query: {} chars)", query.as_ref().map_or(0, |q| q.chars().count()));
+            *LAUNCHER_QUERY.lock().unwrap_or_else(|e| e.into_inner()) = query;
+            // An open launcher fills its input now, a new one on load; the
+            // user still presses Enter, so a web page can't run a command
+            windows::show(app, Window::Launcher).map(|()| windows::send(app, WindowEvent::LauncherQuery))
+        }
+        DeepLink::History { id } => open_history(app, id),
+        DeepLink::OcrImage { path } => {
+            crate::intents::run_linked(app, Intent::OcrImage { path });
+            Ok(())
+        }
+        DeepLink::AskClipboard { question } => {
+            confirm_ask(app, question);
+            Ok(())
+        }
+    };
+    if let Err(e) = done {
+        log::warn!("[DEEPLINK] {}", e);
+    }
+}
+
+/// Run the Ask About Clipboard intent once the user agrees to send the
+/// clipboard to the provider; any web page can open an `ask` link.
+fn confirm_ask(app: &tauri::AppHandle, question: Option<String>) {
+    use crate::i18n::{t, tf};
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+    let shown = question.clone().unwrap_or_else(|| t("intents.askDefault"));
+    let handle = app.clone();
+    app.dialog()
+        .message(tf("intents.askConfirmMessage", &[("question", &shown)]))
+        .title(t("intents.askConfirmTitle"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(t("intents.askConfirmSend"), t("intents.askConfirmCancel")))
+        .show(move |confirmed| {
+            if confirmed {
+                crate::intents::run_linked(&handle, Intent::AskClipboard { question });
+            } else {
+                log::info!("[DEEPLINK] Ask link declined");
+            }
+        });
+}
+
+/// Capture the screen and run the snip pipeline on `region`, no overlay.
+/// Also used by trigger presets and "resnip last" (triggers/). While the
+/// screen is shared it runs once confirmed, or not at all (`presenting::
+/// gate`); a failure then is only logged.
+pub(crate) fn snip_region(app: &tauri::AppHandle, region: Region) -> Result<(), String> {
+    if crate::presenting::sharing().is_none() {
+        return capture_region(app, region);
+    }
+    crate::presenting::gate(app, move |app| {
+        if let Err(e) = capture_region(app, region) {
+            log::warn!("[DEEPLINK] {}", e);
+        }
+    });
+    Ok(())
+}
+
+fn capture_region(app: &tauri::AppHandle, region: Region) -> Result<(), String> {
+    log::info!("[DEEPLINK] Snip region {}x{} at {},{}", region.width, region.height, region.x, region.y);
+    let screenshot = crate::tray::checked_capture(app, capture::capture_for_snip(&crate::settings::current().capture))?;
+    if region.x + region.width > screenshot.width() || region.y + region.height > screenshot.height() {
+        return Err(format!("Region is outside the {}x{} screen", screenshot.width(), screenshot.height()));
+    }
+    // The menu opens under the region; the screenshot is in physical pixels
+    let scale = app.primary_monitor().ok().flatten().map_or(1.0, |m| m.scale_factor());
+    let menu = (region.x as f64 / scale, (region.y + region.height) as f64 / scale);
+    run_snip(app, screenshot, region, menu);
+    Ok(())
+}
+
+/// Store `screenshot` and run the snip pipeline on `region` of it, the
+/// action menu opening at `menu` (logical pixels). Also used by the
+/// tray's window and monitor snips.
+pub(crate) fn run_snip(app: &tauri::AppHandle, screenshot: DynamicImage, region: Region, menu: (f64, f64)) {
+    app.state::<CaptureState>().store(screenshot, None);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Region { x, y, width, height } = region;
+        if let Err(e) = crate::pipeline::process_snip(app, x, y, width, height, menu.0, menu.1).await {
+            log::warn!("[DEEPLINK] Region snip failed: {}", e);
+        }
+    });
+}
+
+/// Reopen a history snip: its newest answer, or its menu when it has none.
+pub(crate) fn open_history(app: &tauri::AppHandle, id: u64) -> Result<(), String> {
+    log::info!("[DEEPLINK] History snip #{}", id);
+    let snip = crate::history::with_records(|records| {
+        let record = records.iter().find(|r| r.id == id)?;
+        let answer = record.answers.iter().rev().find_map(|a| a.result.clone());
+        Some((record.text.clone(), record.menu.clone(), answer))
+    });
+    match snip.ok_or_else(|| format!("No snip #{} in history", id))? {
+        (text, _, Some(result)) => crate::session::show_result(app, text, result),
+        (text, menu, None) => crate::session::reopen_menu(app, text, menu.unwrap_or_else(ActionMenu::fallback), None),
+    }
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the query an `omniglass://launcher?q=` link left for
+/// the text launcher. Returned once.
+#[tauri::command]
+pub fn take_launcher_query() -> Option<String> {
+    LAUNCHER_QUERY.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
@@ -0,0 +1,184 @@
+//! `omniglass://` URL parsing and validation (pure, no I/O).
+//!
+//! Deep links come from other apps and from web pages, so everything is
+//! checked here before anything runs: the scheme, the route, that the
+//! route takes no unexpected parameters, and every value's range.
+
+use reqwest::Url;
+
+pub const SCHEME: &str = "omniglass";
+
+/// Longest launcher query or question accepted, in characters.
+pub const MAX_QUERY_CHARS: usize = 2000;
+/// Longest image path accepted, in characters.
+const MAX_PATH_CHARS: usize = 4096;
+/// Smallest region side, in pixels (the overlay's minimum drag).
+const MIN_REGION_SIDE: u32 = 4;
+/// Largest coordinate or side, in pixels.
+const MAX_REGION_COORD: u32 = 32_768;
+
+/// A screen rectangle in screenshot pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    /// `omniglass://snip`: the snip overlay, as from the tray.
+    Snip,
+    /// `omniglass://snip?region=x,y,w,h`: snip that region, no overlay.
+    SnipRegion(Region),
+    /// `omniglass://launcher` or `omniglass://launcher?q=...`: the text
+    /// launcher, running `q` when given.
+    Launcher { query: Option<String> },
+    /// `omniglass://history/<id>`: a snip from history.
+    History { id: u64 },
+    /// `omniglass://ocr?path=...`: the OCR Image intent on an image file.
+    OcrImage { path: String },
+    /// `omniglass://ask` or `omniglass://ask?q=...`: the Ask About
+    /// Clipboard intent, with an optional question.
+    AskClipboard { question: Option<String> },
+}
+
+/// Parse and validate one deep link.
+pub fn parse(raw: &str) -> Result<DeepLink, String> {
+    let url = Url::parse(raw.trim()).map_err(|e| format!("Not a valid URL: {}", e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Not an {}:// link", SCHEME));
+    }
+    let route = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let segments: Vec<&str> = url.path().split('/').filter(|s| !s.is_empty()).collect();
+    let params: Vec<(String, String)> = url.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+    let param = |name: &str| -> Result<Option<String>, String> {
+        if let Some((unknown, _)) = params.iter().find(|(k, _)| k != name) {
+            return Err(format!("Unknown parameter '{}' for {}", unknown, route));
+        }
+        match params.iter().filter(|(k, _)| k == name).count() {
+            0 | 1 => Ok(params.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())),
+            _ => Err(format!("Parameter '{}' given more than once", name)),
+        }
+    };
+    match (route.as_str(), segments.as_slice()) {
+        ("snip", []) => match param("region")? {
+            None => Ok(DeepLink::Snip),
+            Some(region) => parse_region(&region).map(DeepLink::SnipRegion),
+        },
+        ("launcher", []) => Ok(DeepLink::Launcher { query: query_text(param("q")?)? }),
+        ("ask", []) => Ok(DeepLink::AskClipboard { question: query_text(param("q")?)? }),
+        ("ocr", []) => {
+            let path = param("path")?.ok_or("Expected omniglass://ocr?path=<image file>")?;
+            if path.chars().count() > MAX_PATH_CHARS || path.chars().any(char::is_control) || !is_absolute(&path) {
+                return Err(format!("'{}' is not an absolute file path", path));
+            }
+            Ok(DeepLink::OcrImage { path })
+        }
+        ("history", [id]) => {
+            param("")?;
+            let id = id.parse().map_err(|_| format!("'{}' is not a history id", id))?;
+            Ok(DeepLink::History { id })
+        }
+        ("history", _) => Err("Expected omniglass://history/<id>".to_string()),
+        _ => Err(format!("Unknown deep link '{}'", route)),
+    }
+}
+
+/// Free text from a link, without control characters; blank is none.
+fn query_text(raw: Option<String>) -> Result<Option<String>, String> {
+    let text = raw.map(|q| q.chars().filter(|c| !c.is_control()).collect::<String>());
+    match text.as_deref().map(str::trim) {
+        Some(q) if q.chars().count() > MAX_QUERY_CHARS => Err(format!("Query is longer than {} characters", MAX_QUERY_CHARS)),
+        Some("") | None => Ok(None),
+        Some(q) => Ok(Some(q.to_string())),
+    }
+}
+
+/// Absolute on macOS / Linux (`/...`) or Windows (`C:\...`, `\\server\...`),
+/// whichever platform parses it.
+fn is_absolute(path: &str) -> bool {
+    let b = path.as_bytes();
+    path.starts_with('/')
+        || path.starts_with("\\\\")
+        || (b.len() > 2 && b[0].is_ascii_alphabetic() && b[1] == b':' && matches!(b[2], b'\\' | b'/'))
+}
+
+/// `x,y,w,h` in whole pixels.
+fn parse_region(value: &str) -> Result<Region, String> {
+    let numbers: Vec<u32> = value
+        .split(',')
+        .map(|n| n.trim().parse::<u32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("Region '{}' must be four whole numbers: x,y,w,h", value))?;
+    let [x, y, width, height] = numbers[..] else {
+        return Err(format!("Region '{}' must be four whole numbers: x,y,w,h", value));
+    };
+    if width < MIN_REGION_SIDE || height < MIN_REGION_SIDE {
+        return Err(format!("Region must be at least {0}x{0} pixels", MIN_REGION_SIDE));
+    }
+    if [x, y, width, height].iter().any(|n| *n > MAX_REGION_COORD) || x + width > MAX_REGION_COORD || y + height > MAX_REGION_COORD {
+        return Err("Region is outside any screen".to_string());
+    }
+    Ok(Region { x, y, width, height })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes() {
+        assert_eq!(parse("omniglass://snip"), Ok(DeepLink::Snip));
+        assert_eq!(parse("omniglass://snip/"), Ok(DeepLink::Snip));
+        assert_eq!(
+            parse("omniglass://snip?region=10,20,300,%20200"),
+            Ok(DeepLink::SnipRegion(Region { x: 10, y: 20, width: 300, height: 200 }))
+        );
+        assert_eq!(
+            parse("omniglass://launcher?q=convert%2010%20USD%20to%20EUR"),
+            Ok(DeepLink::Launcher { query: Some("convert 10 USD to EUR".to_string()) })
+        );
+        assert_eq!(parse("omniglass://launcher?q=+"), Ok(DeepLink::Launcher { query: None }));
+        assert_eq!(parse("OMNIGLASS://History/42"), Ok(DeepLink::History { id: 42 }));
+        assert_eq!(
+            parse("omniglass://ocr?path=%2FUsers%2Fme%2FScan%201.png"),
+            Ok(DeepLink::OcrImage { path: "/Users/me/Scan 1.png".to_string() })
+        );
+        assert_eq!(
+            parse("omniglass://ocr?path=C%3A%5Cscans%5Ca.jpg"),
+            Ok(DeepLink::OcrImage { path: "C:\\scans\\a.jpg".to_string() })
+        );
+        assert_eq!(parse("omniglass://ask"), Ok(DeepLink::AskClipboard { question: None }));
+        assert_eq!(
+            parse("omniglass://ask?q=summarize"),
+            Ok(DeepLink::AskClipboard { question: Some("summarize".to_string()) })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        for bad in [
+            "https://snip",
+            "omniglass://settings",
+            "omniglass://snip?region=1,2,3",
+            "omniglass://snip?region=0,0,2,2",
+            "omniglass://snip?region=-1,0,100,100",
+            "omniglass://snip?region=40000,0,100,100",
+            "omniglass://snip?region=1,1,9,9&region=2,2,9,9",
+            "omniglass://snip?rect=1,1,9,9",
+            "omniglass://history/abc",
+            "omniglass://history/1/2",
+            "omniglass://history/1?x=1",
+            "omniglass://ocr",
+            "omniglass://ocr?path=scan.png",
+            "omniglass://ocr?path=..%2Fscan.png",
+            "omniglass://ask?question=hi",
+        ] {
+            assert!(parse(bad).is_err(), "{}", bad);
+        }
+        let long = format!("omniglass://launcher?q={}", "a".repeat(MAX_QUERY_CHARS + 1));
+        assert!(parse(&long).unwrap_err().contains("longer"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Desktop integration — where the tray and screen capture can work.
+//!
+//! macOS and Windows always have a tray and a capture API. Linux depends
+//! on the desktop: the tray icon is a StatusNotifierItem (AppIndicator),
+//! shown only when something owns `org.kde.StatusNotifierWatcher`
+//! (Plasma and most desktops do; GNOME only with the AppIndicator
+//! extension), and on Wayland the screen is captured through the desktop
+//! portal, `org.freedesktop.portal.Desktop`. Both are asked of the
+//! session bus with `dbus-send` or `gdbus`, so no D-Bus library is linked
+//! for two questions; when neither tool answers, the tray and capture
+//! are assumed to work and left to fail on their own.
+//!
+//! `attach` opens the floating trigger window (`windows::spec::Window::
+//! Trigger`) where no tray icon will show. `capture_backend` gates every
+//! snip: on Wayland without a portal, capture fails with
+//! `CaptureError::NoPortal` before an overlay opens.
+
+pub mod session;
+
+use serde::Serialize;
+use session::{CaptureBackend, Display, Shell};
+use std::process::Command;
+
+/// What this desktop supports, for settings and the trigger window.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSupport {
+    pub display: Display,
+    pub shell: Shell,
+    /// A tray host is running.
+    pub tray: bool,
+    pub capture: CaptureBackend,
+    /// What to install when the tray or capture is missing.
+    pub remedy: Option<String>,
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// The display server (`Other` off Linux).
+pub fn display() -> Display {
+    if !cfg!(target_os = "linux") {
+        return Display::Other;
+    }
+    session::display(env("XDG_SESSION_TYPE").as_deref(), env("WAYLAND_DISPLAY").is_some(), env("DISPLAY").is_some())
+}
+
+pub fn shell() -> Shell {
+    session::shell(env("XDG_CURRENT_DESKTOP").as_deref())
+}
+
+/// Whether `name` has an owner on the session bus; `None` when neither
+/// `dbus-send` nor `gdbus` gave an answer.
+fn name_has_owner(name: &str) -> Option<bool> {
+    let string_arg = format!("string:{}", name);
+    let dbus_send =
+        ["dbus-send", "--session", "--print-reply", "--dest=org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.NameHasOwner", string_arg.as_str()];
+    let gdbus = ["gdbus", "call", "--session", "--dest", "org.freedesktop.DBus", "--object-path", "/org/freedesktop/DBus", "--method", "org.freedesktop.DBus.NameHasOwner", name];
+    [&dbus_send[..], &gdbus[..]].into_iter().find_map(|argv| {
+        let out = Command::new(argv[0]).args(&argv[1..]).output().ok()?;
+        out.status.success().then(|| session::parse_has_owner(&String::from_utf8_lossy(&out.stdout))).flatten()
+    })
+}
+
+/// Whether a tray icon will be shown.
+pub fn tray_host() -> bool {
+    !cfg!(target_os = "linux") || name_has_owner("org.kde.StatusNotifierWatcher").unwrap_or(true)
+}
+
+/// How the screen can be captured now.
+pub fn capture_backend() -> CaptureBackend {
+    let display = display();
+    let portal = if display == Display::Wayland { name_has_owner("org.freedesktop.portal.Desktop") } else { None };
+    session::capture_backend(display, portal)
+}
+
+fn support() -> DesktopSupport {
+    let (shell, tray, capture) = (shell(), tray_host(), capture_backend());
+    let remedy = match (tray, capture) {
+        (_, CaptureBackend::Unavailable) => Some(crate::i18n::t("capture.noPortal")),
+        (false, _) => Some(crate::i18n::t(session::tray_remedy(shell))),
+        _ => None,
+    };
+    DesktopSupport { display: display(), shell, tray, capture, remedy }
+}
+
+/// Open the trigger window if there is no tray icon to click, because
+/// the tray failed to build (`tray_built`) or nothing hosts it. Called in
+/// `setup` after the tray.
+pub fn attach(app: &tauri::AppHandle, tray_built: bool) {
+    if tray_built && tray_host() {
+        return;
+    }
+    log::warn!("[DESKTOP] No tray icon on {:?} ({:?}); opening the trigger window", shell(), display());
+    if let Err(e) = crate::windows::open_trigger(app) {
+        log::error!("[DESKTOP] {}", e);
+    }
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: display server, shell, tray and capture support, and
+/// what to install when one is missing.
+#[tauri::command]
+pub fn get_desktop_support() -> DesktopSupport {
+    support()
+}
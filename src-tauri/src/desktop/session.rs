@@ -0,0 +1,124 @@
+//! What kind of Linux desktop this is (pure, no I/O).
+//!
+//! Decided from the session's environment variables and from D-Bus
+//! answers `mod.rs` gets with `dbus-send` or `gdbus`; both are parsed
+//! here so the decisions can be tested without a desktop.
+
+use serde::Serialize;
+
+/// The display server the app runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Display {
+    X11,
+    Wayland,
+    /// macOS, Windows, or a Linux session that says neither.
+    Other,
+}
+
+/// The desktop shell, as far as tray support differs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Shell {
+    /// Shows tray icons only with the AppIndicator extension.
+    Gnome,
+    /// Hosts StatusNotifierItems itself (Plasma, and most others that
+    /// implement the watcher: XFCE, Cinnamon, MATE, Budgie, LXQt).
+    Kde,
+    Other,
+}
+
+/// How a region of the screen can be captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaptureBackend {
+    /// The platform's own API (macOS, Windows) or X11.
+    Direct,
+    /// Wayland through `org.freedesktop.portal.Desktop`.
+    Portal,
+    /// Wayland without a desktop portal: no way to capture.
+    Unavailable,
+}
+
+/// From `XDG_SESSION_TYPE`, or `WAYLAND_DISPLAY` / `DISPLAY` being set
+/// when the session type is missing (started outside a login manager).
+pub fn display(session_type: Option<&str>, wayland_display: bool, x11_display: bool) -> Display {
+    match session_type.map(str::to_ascii_lowercase).as_deref() {
+        Some("wayland") => Display::Wayland,
+        Some("x11") => Display::X11,
+        _ if wayland_display => Display::Wayland,
+        _ if x11_display => Display::X11,
+        _ => Display::Other,
+    }
+}
+
+/// From `XDG_CURRENT_DESKTOP`, a colon-separated list (`ubuntu:GNOME`).
+pub fn shell(current_desktop: Option<&str>) -> Shell {
+    let names: Vec<String> = current_desktop.unwrap_or_default().split(':').map(str::to_ascii_lowercase).collect();
+    if names.iter().any(|n| n == "gnome" || n == "gnome-classic") {
+        Shell::Gnome
+    } else if names.iter().any(|n| !n.is_empty()) {
+        Shell::Kde
+    } else {
+        Shell::Other
+    }
+}
+
+/// The answer to `org.freedesktop.DBus.NameHasOwner`, as printed by
+/// `dbus-send --print-reply` (`boolean true`) or `gdbus call` (`(true,)`).
+pub fn parse_has_owner(stdout: &str) -> Option<bool> {
+    let words: Vec<&str> = stdout.split(|c: char| !c.is_ascii_alphabetic()).filter(|w| !w.is_empty()).collect();
+    match words.last().copied() {
+        Some("true") => Some(true),
+        Some("false") => Some(false),
+        _ => None,
+    }
+}
+
+/// Capture on `display`, given whether a desktop portal answered
+/// (`None`: couldn't ask, so let the capture try).
+pub fn capture_backend(display: Display, portal: Option<bool>) -> CaptureBackend {
+    match (display, portal) {
+        (Display::Wayland, Some(false)) => CaptureBackend::Unavailable,
+        (Display::Wayland, _) => CaptureBackend::Portal,
+        _ => CaptureBackend::Direct,
+    }
+}
+
+/// i18n key saying how to get a tray icon on `shell`.
+pub fn tray_remedy(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Gnome => "desktop.trayGnome",
+        Shell::Kde | Shell::Other => "desktop.trayMissing",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_and_shells_are_recognized() {
+        assert_eq!(display(Some("wayland"), false, true), Display::Wayland);
+        assert_eq!(display(Some("x11"), true, true), Display::X11);
+        assert_eq!(display(None, true, true), Display::Wayland);
+        assert_eq!(display(Some("tty"), false, true), Display::X11);
+        assert_eq!(display(None, false, false), Display::Other);
+        assert_eq!(shell(Some("ubuntu:GNOME")), Shell::Gnome);
+        assert_eq!(shell(Some("KDE")), Shell::Kde);
+        assert_eq!(shell(Some("X-Cinnamon")), Shell::Kde);
+        assert_eq!(shell(None), Shell::Other);
+        assert_eq!(tray_remedy(Shell::Gnome), "desktop.trayGnome");
+    }
+
+    #[test]
+    fn dbus_answers_decide_the_capture_backend() {
+        assert_eq!(parse_has_owner("method return time=1.2 sender=org.freedesktop.DBus\n   boolean true\n"), Some(true));
+        assert_eq!(parse_has_owner("(false,)\n"), Some(false));
+        assert_eq!(parse_has_owner("Error org.freedesktop.DBus.Error.ServiceUnknown"), None);
+        assert_eq!(capture_backend(Display::Wayland, Some(true)), CaptureBackend::Portal);
+        assert_eq!(capture_backend(Display::Wayland, None), CaptureBackend::Portal);
+        assert_eq!(capture_backend(Display::Wayland, Some(false)), CaptureBackend::Unavailable);
+        assert_eq!(capture_backend(Display::X11, Some(false)), CaptureBackend::Direct);
+    }
+}
@@ -0,0 +1,73 @@
+//! In-memory log ring buffer.
+//!
+//! Installed as the global logger in place of `env_logger::init()`. Every
+//! record at `info` or above is kept (last `CAPACITY` lines) whatever
+//! `RUST_LOG` says, then handed to env_logger, which still filters and
+//! prints to stderr as before. A diagnostics bundle can therefore include
+//! recent logs even though nothing is written to disk.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Lines kept (oldest dropped first).
+const CAPACITY: usize = 2000;
+/// Characters kept of one message.
+const MAX_LINE_CHARS: usize = 2000;
+
+static LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+struct RingLogger {
+    stderr: env_logger::Logger,
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= LevelFilter::Info || self.stderr.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= LevelFilter::Info {
+            push(record);
+        }
+        if self.stderr.matches(record) {
+            self.stderr.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+    }
+}
+
+fn push(record: &Record) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (y, mo, d, h, mi, s) = crate::capture::autosave::utc_parts(now);
+    let message: String = record.args().to_string().chars().take(MAX_LINE_CHARS).collect();
+    let line = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z {:<5} {}: {}",
+        y, mo, d, h, mi, s, record.level(), record.target(), message
+    );
+    let mut lines = LINES.lock().unwrap_or_else(|e| e.into_inner());
+    if lines.len() == CAPACITY {
+        lines.pop_front();
+    }
+    lines.push_back(line);
+}
+
+/// Install the logger. Called once, first thing in `run()`.
+pub fn init() {
+    let stderr = env_logger::Builder::from_default_env().build();
+    let max = stderr.filter().max(LevelFilter::Info);
+    if log::set_boxed_logger(Box::new(RingLogger { stderr })).is_ok() {
+        log::set_max_level(max);
+    }
+}
+
+/// Buffered lines, oldest first.
+pub fn lines() -> Vec<String> {
+    LINES.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+}
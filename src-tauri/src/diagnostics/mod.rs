@@ -0,0 +1,107 @@
+//! Diagnostics bundle for bug reports.
+//!
+//! `export_diagnostics` writes a zip the user can attach to an issue:
+//! recent logs from the in-memory ring buffer (`logbuf.rs`), settings with
+//! secrets stripped (`scrub.rs`), the enterprise policy, system and
+//! hardware info, OCR and LLM latency and usage figures, plugin health,
+//! connectivity, the stage timings of the last snip (`spans.rs`), and the
+//! latest crash or hang report.
+//! Nothing in it is snip text unless the user asks to include the last
+//! snip (a `replay/` fixture under `snip/`), and nothing is sent
+//! anywhere — the user picks where the file goes.
+
+pub mod logbuf;
+pub mod scrub;
+pub mod spans;
+pub mod waterfall;
+
+use serde_json::{json, Value};
+use std::io::Write;
+
+/// Stats window included in the bundle.
+const STATS_DAYS: u32 = 30;
+
+/// First line of a command's stdout, if it ran.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let out = std::process::Command::new(program).args(args).output().ok()?;
+    let text = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (out.status.success() && !text.is_empty()).then_some(text)
+}
+
+/// OS, CPU, and memory details. Model and memory are macOS-only.
+fn system_info(app: &tauri::AppHandle) -> Value {
+    let sysctl = |name: &str| {
+        if cfg!(target_os = "macos") {
+            command_output("sysctl", &["-n", name])
+        } else {
+            None
+        }
+    };
+    json!({
+        "appVersion": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "osVersion": if cfg!(target_os = "macos") { command_output("sw_vers", &["-productVersion"]) } else { None },
+        "arch": std::env::consts::ARCH,
+        "cpus": std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        "cpuModel": sysctl("machdep.cpu.brand_string"),
+        "hardwareModel": sysctl("hw.model"),
+        "memoryBytes": sysctl("hw.memsize").and_then(|m| m.parse::<u64>().ok()),
+        "connectivity": crate::net::connectivity::status(),
+    })
+}
+
+/// Bundle entries as (file name, contents).
+fn collect(app: &tauri::AppHandle) -> Vec<(&'static str, String)> {
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+
+    let mut settings = serde_json::to_value(crate::settings::stored()).unwrap_or(Value::Null);
+    scrub::scrub_json(&mut settings);
+    let logs: Vec<String> = logbuf::lines().iter().map(|l| scrub::scrub_line(l)).collect();
+    let stats = serde_json::to_value(crate::stats::get_stats(Some(STATS_DAYS))).unwrap_or(Value::Null);
+    let plugins = serde_json::to_value(crate::mcp::health::get_plugin_health()).unwrap_or(Value::Null);
+    let trace = spans::last().map(|t| t.render()).unwrap_or_default();
+    let crash = crate::crash::get_last_crash().map(|r| r.lines().map(scrub::scrub_line).collect::<Vec<_>>().join("\n"));
+
+    vec![
+        ("system.json", pretty(&system_info(app))),
+        ("settings.json", pretty(&settings)),
+        ("policy.json", pretty(&crate::policy::get_policy())),
+        ("stats.json", pretty(&stats)),
+        ("plugins.json", pretty(&plugins)),
+        ("logs.txt", logs.join("\n") + "\n"),
+        ("trace.txt", trace),
+        ("crash.txt", crash.unwrap_or_default()),
+    ]
+}
+
+/// Tauri command: the stage timings of the latest snip — capture through
+/// classify, and execute once an action runs — or `null` before the first.
+#[tauri::command]
+pub fn get_last_snip_trace() -> Option<waterfall::SnipTrace> {
+    spans::last()
+}
+
+/// Tauri command: write the diagnostics zip to `file_path` (from a save
+/// dialog), with the last snip's replay fixture if `include_snip`.
+/// Returns the path written.
+#[tauri::command]
+pub async fn export_diagnostics(app: tauri::AppHandle, file_path: String, include_snip: Option<bool>) -> Result<String, String> {
+    crate::policy::check_save_path(&file_path)?;
+    let file = std::fs::File::create(&file_path).map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut entries: Vec<(String, Vec<u8>)> =
+        collect(&app).into_iter().map(|(name, contents)| (name.to_string(), contents.into_bytes())).collect();
+    if let Some(snip) = crate::replay::last().filter(|_| include_snip.unwrap_or(false)) {
+        let dir = crate::replay::fixture::BUNDLE_DIR;
+        entries.extend(snip.entries().into_iter().map(|(name, bytes)| (format!("{}{}", dir, name), bytes)));
+    }
+    for (name, contents) in entries {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(&contents).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| format!("Failed to write diagnostics: {}", e))?;
+
+    log::info!("[DIAGNOSTICS] Wrote bundle: {}", file_path);
+    Ok(file_path)
+}
@@ -0,0 +1,95 @@
+//! Secret stripping for the diagnostics bundle (pure, no I/O).
+//!
+//! Settings are scrubbed by key: any field whose camelCase name ends in
+//! `key`, `token`, `secret`, `password`, or `username` is replaced, at
+//! any depth. Log lines are scrubbed by pattern: API keys, bearer tokens,
+//! credentials in URLs, and `key=` style query parameters. Snip text is
+//! never logged in the first place, so nothing else is rewritten.
+
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+pub const REDACTED: &str = "[redacted]";
+
+/// Last camelCase word of a field name that marks it as a secret.
+const SECRET_WORDS: &[&str] = &["key", "token", "secret", "password", "username"];
+
+static LINE_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    vec![
+        // Provider keys (sk-ant-…, AIza…) and generic sk-/api- style tokens
+        (Regex::new(r"\b(sk|pk|api|key|token|secret)[-_][A-Za-z0-9_-]{16,}").unwrap(), REDACTED),
+        (Regex::new(r"\bAIza[0-9A-Za-z_-]{30,}").unwrap(), REDACTED),
+        // Authorization headers
+        (Regex::new(r"(?i)\b(bearer|basic)\s+[A-Za-z0-9._~+/=-]{8,}").unwrap(), "$1 [redacted]"),
+        // user:password@ in URLs
+        (Regex::new(r"://[^/\s:@]+:[^/\s@]+@").unwrap(), "://[redacted]@"),
+        // ?key=… / &token=…
+        (Regex::new(r"(?i)([?&](key|token|api_key|access_token)=)[^&\s]+").unwrap(), "${1}[redacted]"),
+    ]
+});
+
+/// Whether `name` (camelCase or snake_case) names a secret.
+fn is_secret_field(name: &str) -> bool {
+    let last = name.rsplit(['_', '-']).next().unwrap_or(name);
+    let word_start = last.rfind(|c: char| c.is_ascii_uppercase()).unwrap_or(0);
+    SECRET_WORDS.contains(&last[word_start..].to_ascii_lowercase().as_str())
+}
+
+/// Replace every secret field (and its whole value) in `value`.
+pub fn scrub_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (name, field) in map.iter_mut() {
+                if is_secret_field(name) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    scrub_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(scrub_json),
+        _ => {}
+    }
+}
+
+/// `line` with anything that looks like a credential replaced.
+pub fn scrub_line(line: &str) -> String {
+    LINE_PATTERNS
+        .iter()
+        .fold(line.to_string(), |text, (re, with)| re.replace_all(&text, *with).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_fields_are_replaced_at_any_depth() {
+        let mut settings = serde_json::json!({
+            "hotkeys": { "snip": "CmdOrCtrl+Shift+S" },
+            "actions": { "keyBindings": { "c": "copy_text" } },
+            "network": { "proxyUsername": "alice", "proxyHost": "proxy.corp", "caBundle": null },
+            "remote": [{ "api_key": "abc", "authToken": null }],
+        });
+        scrub_json(&mut settings);
+        assert_eq!(settings["hotkeys"]["snip"], "CmdOrCtrl+Shift+S");
+        assert_eq!(settings["actions"]["keyBindings"]["c"], "copy_text");
+        assert_eq!(settings["network"]["proxyUsername"], REDACTED);
+        assert_eq!(settings["network"]["proxyHost"], "proxy.corp");
+        assert_eq!(settings["remote"][0]["api_key"], REDACTED);
+        assert!(settings["remote"][0]["authToken"].is_null());
+    }
+
+    #[test]
+    fn credentials_are_removed_from_log_lines() {
+        assert_eq!(
+            scrub_line("[LLM] key sk-ant-REDACTED failed"),
+            "[LLM] key [redacted] failed"
+        );
+        assert_eq!(scrub_line("Authorization: Bearer abc.def.ghi123"), "Authorization: Bearer [redacted]");
+        assert_eq!(scrub_line("GET http://bob:pw@proxy:3128/x"), "GET http://[redacted]@proxy:3128/x");
+        assert_eq!(scrub_line("url ?alt=sse&key=AIzaSy123"), "url ?alt=sse&key=[redacted]");
+        assert_eq!(scrub_line("[OCR] 120ms, 42 chars"), "[OCR] 120ms, 42 chars");
+    }
+}
@@ -0,0 +1,239 @@
+//! Per-snip timing from `tracing` spans.
+//!
+//! Pipeline stages run inside spans (`capture`, `crop`, `encode`, `ocr`,
+//! `redact`, `classify`, `execute`, ...). `SpanRecorder` is the global
+//! `tracing` subscriber: it is only interested in this crate's spans, so
+//! dependencies' spans (hyper, h2, wasmtime) cost nothing, and each span
+//! that closes becomes a stage of the current snip's `SnipTrace`. `begin`
+//! starts a new trace when snip mode opens; `last` is the latest one.
+//!
+//! A span is timed from creation to close, so a span instrumenting a
+//! future covers the whole await, waiting included.
+
+use super::waterfall::{SnipTrace, Stage};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+/// Module path prefix of the spans worth recording.
+const CRATE: &str = env!("CARGO_CRATE_NAME");
+
+static RECORDER: LazyLock<Arc<SpanRecorder>> = LazyLock::new(Arc::default);
+
+thread_local! {
+    /// Spans entered on this thread, innermost last.
+    static ENTERED: RefCell<Vec<Id>> = const { RefCell::new(Vec::new()) };
+}
+
+struct OpenSpan {
+    metadata: &'static Metadata<'static>,
+    start: Instant,
+    depth: usize,
+    fields: BTreeMap<String, String>,
+    /// Handles to the span still alive (`clone_span` / `try_close`).
+    refs: usize,
+}
+
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+#[derive(Default)]
+pub struct SpanRecorder {
+    next_id: AtomicU64,
+    open: Mutex<HashMap<u64, OpenSpan>>,
+    /// The current trace and the instant its offsets count from.
+    trace: Mutex<Option<(Instant, SnipTrace)>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl SpanRecorder {
+    /// Start a new trace; stages that began before it are left out.
+    pub fn begin(&self) {
+        let started_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        *lock(&self.trace) = Some((Instant::now(), SnipTrace::new(started_at)));
+    }
+
+    pub fn last(&self) -> Option<SnipTrace> {
+        lock(&self.trace).as_ref().map(|(_, trace)| trace.clone())
+    }
+
+    fn close(&self, span: OpenSpan) {
+        let duration_ms = span.start.elapsed().as_secs_f64() * 1000.0;
+        log::debug!("[TRACE] {} {:.1}ms", span.metadata.name(), duration_ms);
+        let mut trace = lock(&self.trace);
+        let Some((origin, trace)) = trace.as_mut() else { return };
+        let Some(offset) = span.start.checked_duration_since(*origin) else { return };
+        trace.push(Stage {
+            name: span.metadata.name().to_string(),
+            start_ms: offset.as_secs_f64() * 1000.0,
+            duration_ms,
+            depth: span.depth,
+            fields: span.fields,
+        });
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if self.enabled(metadata) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_span() && metadata.target().split("::").next() == Some(CRATE)
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let parent = match attrs.parent() {
+            Some(parent) => Some(parent.clone()),
+            None if attrs.is_contextual() => ENTERED.with(|e| e.borrow().last().cloned()),
+            None => None,
+        };
+        let mut open = lock(&self.open);
+        let depth = parent.and_then(|p| open.get(&p.into_u64())).map_or(0, |p| p.depth + 1);
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut Fields(&mut fields));
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let span = OpenSpan { metadata: attrs.metadata(), start: Instant::now(), depth, fields, refs: 1 };
+        open.insert(id, span);
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(span) = lock(&self.open).get_mut(&span.into_u64()) {
+            values.record(&mut Fields(&mut span.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|e| e.borrow_mut().push(span.clone()));
+    }
+
+    fn exit(&self, span: &Id) {
+        ENTERED.with(|e| {
+            let mut entered = e.borrow_mut();
+            if let Some(at) = entered.iter().rposition(|id| id == span) {
+                entered.remove(at);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(open) = lock(&self.open).get_mut(&span.into_u64()) {
+            open.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let closed = {
+            let mut open = lock(&self.open);
+            match open.get_mut(&span.into_u64()) {
+                Some(s) if s.refs > 1 => {
+                    s.refs -= 1;
+                    None
+                }
+                Some(_) => open.remove(&span.into_u64()),
+                None => None,
+            }
+        };
+        closed.map(|s| self.close(s)).is_some()
+    }
+
+    fn current_span(&self) -> Current {
+        let current = ENTERED.with(|e| e.borrow().last().cloned());
+        match current.and_then(|id| Some((lock(&self.open).get(&id.into_u64())?.metadata, id))) {
+            Some((metadata, id)) => Current::new(id, metadata),
+            None => Current::none(),
+        }
+    }
+}
+
+/// Install the recorder as the global `tracing` subscriber. Called once
+/// at startup, next to `logbuf::init`.
+pub fn init() {
+    if tracing::subscriber::set_global_default(RECORDER.clone()).is_err() {
+        log::warn!("[TRACE] A tracing subscriber is already set — snip timing disabled");
+    }
+}
+
+/// Start timing a new snip.
+pub fn begin() {
+    RECORDER.begin();
+}
+
+/// The latest snip's stages so far.
+pub fn last() -> Option<SnipTrace> {
+    RECORDER.last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::info_span;
+
+    fn recorder() -> Arc<SpanRecorder> {
+        let recorder = Arc::new(SpanRecorder::default());
+        recorder.begin();
+        recorder
+    }
+
+    #[test]
+    fn records_nested_stages_with_fields() {
+        let recorder = recorder();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let classify = info_span!("classify", provider = "gemini");
+            info_span!(parent: &classify, "redact").in_scope(|| {});
+            classify.in_scope(|| info_span!("parse").in_scope(|| {}));
+        });
+        let trace = recorder.last().unwrap();
+        let stages: Vec<(&str, usize)> = trace.stages.iter().map(|s| (s.name.as_str(), s.depth)).collect();
+        assert_eq!(stages.len(), 3);
+        assert_eq!(stages[0], ("classify", 0));
+        assert!(stages[1..].iter().all(|&(_, depth)| depth == 1));
+        assert_eq!(trace.stages[0].fields["provider"], "gemini");
+    }
+
+    #[test]
+    fn stage_closes_when_the_last_handle_drops() {
+        let recorder = recorder();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let span = info_span!("execute");
+            let clone = span.clone();
+            drop(span);
+            assert!(recorder.last().unwrap().stages.is_empty());
+            drop(clone);
+        });
+        assert_eq!(recorder.last().unwrap().stages.len(), 1);
+    }
+}
@@ -0,0 +1,111 @@
+//! Per-snip timing waterfall (pure).
+//!
+//! A `SnipTrace` holds the stages of one snip in start order, each with
+//! its offset from the start of the trace (snip mode opening) and its
+//! duration. `render` draws it as text bars for logs and bug reports.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Width of the bar column in `render`, in characters.
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stage {
+    pub name: String,
+    /// Offset from the start of the trace.
+    pub start_ms: f64,
+    pub duration_ms: f64,
+    /// Nesting under other stages; 0 = top level.
+    pub depth: usize,
+    /// The span's fields, e.g. `provider` or `speculative`.
+    pub fields: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipTrace {
+    /// Unix time the trace started, in ms.
+    pub started_at: u64,
+    /// End of the last stage to finish.
+    pub total_ms: f64,
+    pub stages: Vec<Stage>,
+}
+
+impl SnipTrace {
+    pub fn new(started_at: u64) -> Self {
+        Self { started_at, ..Self::default() }
+    }
+
+    /// Add a finished stage. Stages close inside-out, so each is placed
+    /// by start, then depth, to keep parents before their children.
+    pub fn push(&mut self, stage: Stage) {
+        self.total_ms = self.total_ms.max(stage.start_ms + stage.duration_ms);
+        let at = self.stages.partition_point(|s| {
+            s.start_ms < stage.start_ms || (s.start_ms == stage.start_ms && s.depth <= stage.depth)
+        });
+        self.stages.insert(at, stage);
+    }
+
+    /// One line per stage: the indented name, a bar placed on the
+    /// trace's time axis, then duration and offset.
+    pub fn render(&self) -> String {
+        let scale = BAR_WIDTH as f64 / self.total_ms.max(1.0);
+        let name_width = self.stages.iter().map(|s| s.depth * 2 + s.name.len()).max().unwrap_or(0);
+        self.stages
+            .iter()
+            .map(|s| {
+                let lead = ((s.start_ms * scale) as usize).min(BAR_WIDTH - 1);
+                let len = ((s.duration_ms * scale).round() as usize).clamp(1, BAR_WIDTH - lead);
+                let name = format!("{}{}", "  ".repeat(s.depth), s.name);
+                format!(
+                    "{:<width$} |{}{}{}| {:>8.1}ms @ {:.1}ms\n",
+                    name,
+                    " ".repeat(lead),
+                    "#".repeat(len),
+                    " ".repeat(BAR_WIDTH - lead - len),
+                    s.duration_ms,
+                    s.start_ms,
+                    width = name_width
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, start_ms: f64, duration_ms: f64, depth: usize) -> Stage {
+        Stage { name: name.to_string(), start_ms, duration_ms, depth, fields: BTreeMap::new() }
+    }
+
+    #[test]
+    fn orders_stages_by_start_with_parents_first() {
+        let mut trace = SnipTrace::new(0);
+        trace.push(stage("ocr", 120.0, 30.0, 0));
+        trace.push(stage("redact", 200.0, 1.0, 1));
+        trace.push(stage("capture", 0.0, 80.0, 0));
+        trace.push(stage("execute", 200.0, 900.0, 0));
+        let names: Vec<&str> = trace.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["capture", "ocr", "execute", "redact"]);
+        assert_eq!(trace.total_ms, 1100.0);
+    }
+
+    #[test]
+    fn renders_bars_on_a_shared_axis() {
+        let mut trace = SnipTrace::new(0);
+        trace.push(stage("capture", 0.0, 100.0, 0));
+        trace.push(stage("classify", 100.0, 100.0, 0));
+        trace.push(stage("redact", 100.0, 0.1, 1));
+        let rendered = trace.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("capture  |####################                    |"));
+        assert!(lines[1].starts_with("classify |                    ####################|"));
+        assert!(lines[2].starts_with("  redact |                    #                   |"));
+        assert!(lines[1].ends_with("100.0ms @ 100.0ms"));
+    }
+}
@@ -0,0 +1,45 @@
+//! Document outlines (macOS) — `VNDetectDocumentSegmentationRequest` via
+//! swift-bridge.
+//!
+//! `swift-src/document_bridge.swift` wraps the pixels in a `CGImage`
+//! without copying, as the OCR bridge does, and returns the most
+//! confident outline's corners.
+
+use super::quad::Quad;
+use crate::ocr::RgbaView;
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct DocumentQuad {
+        found: bool,
+        confidence: f64,
+        top_left_x: f64,
+        top_left_y: f64,
+        top_right_x: f64,
+        top_right_y: f64,
+        bottom_right_x: f64,
+        bottom_right_y: f64,
+        bottom_left_x: f64,
+        bottom_left_y: f64,
+    }
+
+    extern "Swift" {
+        fn detect_document_in_rgba(data: &[u8], width: u32, height: u32, bytes_per_row: u32) -> DocumentQuad;
+    }
+}
+
+/// The document outline in `view`, in its pixels, with Vision's confidence.
+pub fn detect(view: &RgbaView) -> Option<(Quad, f64)> {
+    let q = ffi::detect_document_in_rgba(view.data, view.width, view.height, view.stride as u32);
+    if !q.found {
+        return None;
+    }
+    let corners = [
+        (q.top_left_x, q.top_left_y),
+        (q.top_right_x, q.top_right_y),
+        (q.bottom_right_x, q.bottom_right_y),
+        (q.bottom_left_x, q.bottom_left_y),
+    ];
+    Some((Quad::from_normalized(corners, view.width, view.height), q.confidence))
+}
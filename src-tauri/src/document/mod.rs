@@ -0,0 +1,118 @@
+//! Document domain — "Straighten Document" for paper shown on screen.
+//!
+//! A photo of a page, receipt, or card on screen is usually tilted and
+//! keystoned, and OCR reads it poorly. On macOS, Vision's document
+//! segmentation finds the page's outline in the snip (`apple.rs`); when
+//! one is found that is worth it (`quad.rs`), the menu offers a
+//! `straighten_document` action. The action menu calls
+//! `straighten_document`, which warps the page upright (`warp.rs`), reads
+//! it again at the accurate level, and makes the result the snip's crop
+//! and text, so later actions use the straightened page. Runs offline.
+//!
+//! Other platforms have no segmentation engine; the action isn't offered.
+
+#[cfg(target_os = "macos")]
+mod apple;
+pub mod quad;
+pub mod warp;
+
+use crate::i18n::t;
+use crate::llm;
+use crate::llm::types::{Action, ActionMenu};
+use crate::ocr::{self, RecognitionLevel};
+use image::{DynamicImage, RgbaImage};
+use quad::Quad;
+use serde::Serialize;
+
+/// Action id, handled by the action menu through `straighten_document`.
+pub const STRAIGHTEN_ACTION: &str = "straighten_document";
+
+/// Outlines Vision is less sure of than this aren't offered.
+#[cfg(target_os = "macos")]
+const MIN_CONFIDENCE: f64 = 0.6;
+
+/// A straightened page: the image and what OCR read from it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Straightened {
+    /// Base64-encoded PNG.
+    pub png: String,
+    pub width: u32,
+    pub height: u32,
+    pub text: String,
+}
+
+/// The outline of a document in `image` worth straightening, if any.
+pub fn detect(image: &RgbaImage) -> Option<Quad> {
+    #[cfg(target_os = "macos")]
+    {
+        let view = ocr::RgbaView::crop(image, 0, 0, image.width(), image.height())?;
+        let (quad, confidence) = apple::detect(&view)?;
+        log::info!("[DOCUMENT] Outline found (confidence {:.2}): {:?}", confidence, quad);
+        (confidence >= MIN_CONFIDENCE && quad.worth_straightening(image.width(), image.height())).then_some(quad)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = image;
+        None
+    }
+}
+
+/// Append the straighten action when the snip `cropped` shows a document
+/// worth straightening. Returns whether it was added.
+pub fn augment(menu: &mut ActionMenu, cropped: &DynamicImage) -> bool {
+    if !cfg!(target_os = "macos") || menu.actions.iter().any(|a| a.id == STRAIGHTEN_ACTION) {
+        return false;
+    }
+    let found = match cropped.as_rgba8() {
+        Some(rgba) => detect(rgba),
+        None => detect(&cropped.to_rgba8()),
+    };
+    if found.is_none() {
+        return false;
+    }
+    let priority = menu.actions.iter().map(|a| a.priority).max().unwrap_or(0).saturating_add(1);
+    menu.actions.push(Action {
+        id: STRAIGHTEN_ACTION.to_string(),
+        label: t("menu.straightenDocument"),
+        icon: "document".to_string(),
+        priority,
+        description: t("menu.straightenDocumentDescription"),
+        requires_execution: false,
+    });
+    true
+}
+
+/// Find, straighten, and read the page in `png`.
+fn straighten_png(png: &[u8]) -> Result<(Vec<u8>, Straightened), String> {
+    let image = image::load_from_memory(png).map_err(|e| format!("Could not read the snip: {}", e))?.to_rgba8();
+    let quad = detect(&image).ok_or("No document found in the snip")?;
+    let page = warp::straighten(&image, &quad).ok_or("The document's outline is too thin to straighten")?;
+    let (width, height) = page.dimensions();
+    let view = ocr::RgbaView::crop(&page, 0, 0, width, height).ok_or("The straightened page is empty")?;
+    let text = ocr::recognize_text_from_rgba(&view, RecognitionLevel::Accurate).text;
+    let page_png = crate::capture::encode::png_fast(&DynamicImage::ImageRgba8(page)).map_err(|e| format!("PNG encode failed: {}", e))?;
+    log::info!("[DOCUMENT] Straightened to {}x{}, {} chars", width, height, text.len());
+    let png = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &page_png);
+    Ok((page_png, Straightened { png, width, height, text }))
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: straighten the document in the current snip and read
+/// it again. The straightened page and its text replace the snip's, so
+/// Copy Text and the other actions use them.
+#[tauri::command]
+pub async fn straighten_document(state: tauri::State<'_, llm::ActionMenuState>) -> Result<Straightened, String> {
+    let png = {
+        let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
+        guard.clone().ok_or("No snip image available — snip first")?
+    };
+    let (page_png, straightened) =
+        tauri::async_runtime::spawn_blocking(move || straighten_png(&png)).await.map_err(|e| e.to_string())??;
+    if !straightened.text.is_empty() {
+        *state.ocr_text.lock().map_err(|e| e.to_string())? = Some(straightened.text.clone());
+    }
+    *state.crop_png.lock().map_err(|e| e.to_string())? = Some(page_png);
+    Ok(straightened)
+}
@@ -0,0 +1,93 @@
+//! A document's outline in a snip, and what to make of it (pure, no I/O).
+//!
+//! Vision reports the four corners normalized to 0–1 with the origin at
+//! the bottom left; here they are pixels with the origin at the top left,
+//! as everywhere else in the app.
+
+/// A point in image pixels.
+pub type Point = (f64, f64);
+
+/// Below this share of the snip, the outline is a detail, not the page.
+const MIN_AREA: f64 = 0.15;
+/// Corners this close to the snip's (as a share of its size) mean the
+/// snip already is the page; straightening would change nothing.
+const CORNER_TOLERANCE: f64 = 0.03;
+
+/// Four corners of a document, clockwise from the top left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quad {
+    pub top_left: Point,
+    pub top_right: Point,
+    pub bottom_right: Point,
+    pub bottom_left: Point,
+}
+
+impl Quad {
+    /// From Vision's normalized corners (top left, top right, bottom
+    /// right, bottom left; origin bottom left) in a `width`×`height` image.
+    pub fn from_normalized(corners: [Point; 4], width: u32, height: u32) -> Quad {
+        let (w, h) = (f64::from(width), f64::from(height));
+        let [tl, tr, br, bl] = corners.map(|(x, y)| (x.clamp(0.0, 1.0) * w, (1.0 - y.clamp(0.0, 1.0)) * h));
+        Quad { top_left: tl, top_right: tr, bottom_right: br, bottom_left: bl }
+    }
+
+    pub fn corners(&self) -> [Point; 4] {
+        [self.top_left, self.top_right, self.bottom_right, self.bottom_left]
+    }
+
+    /// Area by the shoelace formula.
+    pub fn area(&self) -> f64 {
+        let c = self.corners();
+        let twice: f64 = (0..4).map(|i| c[i].0 * c[(i + 1) % 4].1 - c[(i + 1) % 4].0 * c[i].1).sum();
+        twice.abs() / 2.0
+    }
+
+    /// Size of the straightened page: the longer of each pair of opposite
+    /// sides, so no side is shrunk.
+    pub fn output_size(&self) -> (u32, u32) {
+        let len = |a: Point, b: Point| (a.0 - b.0).hypot(a.1 - b.1);
+        let width = len(self.top_left, self.top_right).max(len(self.bottom_left, self.bottom_right));
+        let height = len(self.top_left, self.bottom_left).max(len(self.top_right, self.bottom_right));
+        (width.round().max(1.0) as u32, height.round().max(1.0) as u32)
+    }
+
+    /// Whether straightening a `width`×`height` snip to this outline is
+    /// worth offering: the page is a good part of the snip, and it is
+    /// tilted, skewed, or smaller than the snip.
+    pub fn worth_straightening(&self, width: u32, height: u32) -> bool {
+        let (w, h) = (f64::from(width), f64::from(height));
+        if w == 0.0 || h == 0.0 || self.area() < MIN_AREA * w * h {
+            return false;
+        }
+        let snip = [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)];
+        let near = |a: Point, b: Point| (a.0 - b.0).abs() <= CORNER_TOLERANCE * w && (a.1 - b.1).abs() <= CORNER_TOLERANCE * h;
+        !self.corners().iter().zip(snip).all(|(c, s)| near(*c, s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vision_corners_become_top_left_pixels() {
+        let quad = Quad::from_normalized([(0.25, 0.75), (0.75, 0.75), (0.75, 0.25), (0.25, 0.25)], 1000, 500);
+        assert_eq!(quad.top_left, (250.0, 125.0));
+        assert_eq!(quad.bottom_right, (750.0, 375.0));
+        assert_eq!(quad.area(), 500.0 * 250.0);
+        assert_eq!(quad.output_size(), (500, 250));
+        // A trapezoid keeps its longer sides
+        let tilted = Quad { top_left: (20.0, 0.0), top_right: (80.0, 0.0), bottom_right: (100.0, 50.0), bottom_left: (0.0, 50.0) };
+        assert_eq!(tilted.output_size(), (100, 54));
+    }
+
+    #[test]
+    fn only_a_tilted_or_smaller_page_is_worth_straightening() {
+        let whole = Quad::from_normalized([(0.01, 0.99), (0.99, 0.99), (0.99, 0.01), (0.01, 0.01)], 800, 600);
+        assert!(!whole.worth_straightening(800, 600));
+        let tilted = Quad::from_normalized([(0.1, 0.95), (0.85, 0.9), (0.9, 0.1), (0.05, 0.05)], 800, 600);
+        assert!(tilted.worth_straightening(800, 600));
+        let speck = Quad::from_normalized([(0.4, 0.6), (0.5, 0.6), (0.5, 0.5), (0.4, 0.5)], 800, 600);
+        assert!(!speck.worth_straightening(800, 600));
+    }
+}
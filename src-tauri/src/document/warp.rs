@@ -0,0 +1,116 @@
+//! Perspective correction: a document's outline to an upright rectangle
+//! (pure, no I/O).
+//!
+//! A homography maps each pixel of the output rectangle to a point inside
+//! the outline in the snip, which is sampled bilinearly. That undoes the
+//! tilt, keystone, and rotation of a page photographed at an angle.
+
+use super::quad::{Point, Quad};
+use image::{Rgba, RgbaImage};
+
+/// 3×3 projective transform, row-major, last entry 1.
+type Homography = [f64; 9];
+
+/// The page inside `quad`, straightened to `quad.output_size()`. `None`
+/// when the image is empty or the outline is degenerate (three corners
+/// on a line).
+pub fn straighten(image: &RgbaImage, quad: &Quad) -> Option<RgbaImage> {
+    if image.width() == 0 || image.height() == 0 {
+        return None;
+    }
+    let (width, height) = quad.output_size();
+    let (w, h) = (f64::from(width), f64::from(height));
+    let h_map = homography([(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)], quad.corners())?;
+    Some(RgbaImage::from_fn(width, height, |x, y| {
+        let (sx, sy) = apply(&h_map, (f64::from(x) + 0.5, f64::from(y) + 0.5));
+        sample(image, sx - 0.5, sy - 0.5)
+    }))
+}
+
+/// The transform taking each of `from` to the matching `to`.
+fn homography(from: [Point; 4], to: [Point; 4]) -> Option<Homography> {
+    // Two equations per correspondence in the eight unknowns h0..h7
+    let mut rows = [[0.0; 9]; 8];
+    for (i, ((x, y), (u, v))) in from.into_iter().zip(to).enumerate() {
+        rows[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -x * u, -y * u, u];
+        rows[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -x * v, -y * v, v];
+    }
+    let h = solve(rows)?;
+    Some([h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0])
+}
+
+/// Gaussian elimination with partial pivoting on an augmented 8×9 system.
+fn solve(mut rows: [[f64; 9]; 8]) -> Option<[f64; 8]> {
+    for col in 0..8 {
+        let pivot = (col..8).max_by(|&a, &b| rows[a][col].abs().total_cmp(&rows[b][col].abs()))?;
+        if rows[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        rows.swap(col, pivot);
+        for row in 0..8 {
+            if row != col {
+                let (pivot_row, factor) = (rows[col], rows[row][col] / rows[col][col]);
+                for (value, pivot) in rows[row].iter_mut().zip(pivot_row).skip(col) {
+                    *value -= factor * pivot;
+                }
+            }
+        }
+    }
+    Some(std::array::from_fn(|i| rows[i][8] / rows[i][i]))
+}
+
+fn apply(h: &Homography, (x, y): Point) -> Point {
+    let w = h[6] * x + h[7] * y + h[8];
+    ((h[0] * x + h[1] * y + h[2]) / w, (h[3] * x + h[4] * y + h[5]) / w)
+}
+
+/// Bilinear sample at pixel coordinates (pixel centers at integers),
+/// clamped to the image's edge.
+fn sample(image: &RgbaImage, x: f64, y: f64) -> Rgba<u8> {
+    let (max_x, max_y) = (f64::from(image.width() - 1), f64::from(image.height() - 1));
+    let (x, y) = (x.clamp(0.0, max_x), y.clamp(0.0, max_y));
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(image.width() - 1), (y0 + 1).min(image.height() - 1));
+    let (fx, fy) = (x - f64::from(x0), y - f64::from(y0));
+    let (a, b, c, d) = (image.get_pixel(x0, y0), image.get_pixel(x1, y0), image.get_pixel(x0, y1), image.get_pixel(x1, y1));
+    Rgba(std::array::from_fn(|i| {
+        let top = f64::from(a[i]) * (1.0 - fx) + f64::from(b[i]) * fx;
+        let bottom = f64::from(c[i]) * (1.0 - fx) + f64::from(d[i]) * fx;
+        (top * (1.0 - fy) + bottom * fy).round() as u8
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn homography_maps_corners_onto_corners() {
+        let quad = [(12.0, 5.0), (90.0, 20.0), (85.0, 70.0), (3.0, 60.0)];
+        let h = homography([(0.0, 0.0), (100.0, 0.0), (100.0, 50.0), (0.0, 50.0)], quad).unwrap();
+        for (from, to) in [(0.0, 0.0), (100.0, 0.0), (100.0, 50.0), (0.0, 50.0)].into_iter().zip(quad) {
+            let (x, y) = apply(&h, from);
+            assert!((x - to.0).abs() < 1e-6 && (y - to.1).abs() < 1e-6, "{:?} -> {:?}", from, (x, y));
+        }
+        // Three corners on a line: no transform
+        assert!(homography([(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (0.0, 1.0)], quad).is_none());
+    }
+
+    #[test]
+    fn an_inset_page_is_cut_out_upright() {
+        // White page (rows 10..30, columns 20..60) with a black top row, on gray
+        let mut image = RgbaImage::from_pixel(80, 40, Rgba([128, 128, 128, 255]));
+        for y in 10..30 {
+            for x in 20..60 {
+                let shade = if y == 10 { 0 } else { 255 };
+                image.put_pixel(x, y, Rgba([shade, shade, shade, 255]));
+            }
+        }
+        let quad = Quad { top_left: (20.0, 10.0), top_right: (60.0, 10.0), bottom_right: (60.0, 30.0), bottom_left: (20.0, 30.0) };
+        let page = straighten(&image, &quad).unwrap();
+        assert_eq!(page.dimensions(), (40, 20));
+        assert_eq!(page.get_pixel(20, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(page.get_pixel(20, 10), &Rgba([255, 255, 255, 255]));
+        assert_eq!(page.get_pixel(39, 19), &Rgba([255, 255, 255, 255]));
+    }
+}
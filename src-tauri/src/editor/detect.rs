@@ -0,0 +1,211 @@
+//! Which editor to open, how to launch it, and what to put in the file
+//! (pure, no I/O).
+//!
+//! `editor.preferred` in settings is one of:
+//!   - unset or `auto`: the first known GUI editor on PATH, then
+//!     `$VISUAL` / `$EDITOR`, then a known terminal editor
+//!   - a known id (`vscode`, `zed`, `vim`, …)
+//!   - any other command line, run with the file appended (or put where
+//!     `{file}` is)
+//!
+//! Terminal editors are opened in a new terminal window by the caller.
+
+use serde::{Deserialize, Serialize};
+
+/// (id, program, label, runs in a terminal), in detection order.
+pub const KNOWN: &[(&str, &str, &str, bool)] = &[
+    ("vscode", "code", "VS Code", false),
+    ("cursor", "cursor", "Cursor", false),
+    ("zed", "zed", "Zed", false),
+    ("sublime", "subl", "Sublime Text", false),
+    ("nvim", "nvim", "Neovim", true),
+    ("vim", "vim", "Vim", true),
+    ("nano", "nano", "nano", true),
+];
+
+/// Longest custom editor command accepted in settings.
+const MAX_COMMAND_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EditorPrefs {
+    /// Known editor id or a command line; `None` detects one.
+    #[serde(default)]
+    pub preferred: Option<String>,
+}
+
+impl EditorPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(preferred) = &self.preferred else { return Ok(()) };
+        if preferred.trim().is_empty() || preferred.contains(['\n', '\r']) {
+            return Err("Editor command must be a single non-empty line".to_string());
+        }
+        if preferred.chars().count() > MAX_COMMAND_CHARS {
+            return Err(format!("Editor command is over {} characters", MAX_COMMAND_CHARS));
+        }
+        Ok(())
+    }
+}
+
+/// How to start the editor on a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Launch {
+    pub label: String,
+    pub program: String,
+    /// Arguments, with `{file}` where the path goes.
+    pub args: Vec<String>,
+    pub terminal: bool,
+}
+
+impl Launch {
+    /// The arguments with the path filled in.
+    pub fn args_for(&self, file: &str) -> Vec<String> {
+        self.args.iter().map(|a| a.replace("{file}", file)).collect()
+    }
+}
+
+fn known(id: &str, program: &str, label: &str, terminal: bool) -> Launch {
+    let mut args = vec!["{file}".to_string()];
+    if id == "vscode" || id == "cursor" {
+        // Reuse the open window instead of starting a new one
+        args.insert(0, "--reuse-window".to_string());
+    }
+    Launch { label: label.to_string(), program: program.to_string(), args, terminal }
+}
+
+/// Parse a command line (`code --wait`, `"C:\Program Files\…\x.exe" -n`)
+/// into a launch; `{file}` is appended when it isn't there.
+fn custom(command: &str) -> Option<Launch> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in command.trim().chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            _ => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    if words.is_empty() {
+        return None;
+    }
+    let program = words.remove(0);
+    if !words.iter().any(|w| w.contains("{file}")) {
+        words.push("{file}".to_string());
+    }
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(&program).trim_end_matches(".exe").to_string();
+    let terminal = KNOWN.iter().any(|(_, p, _, t)| *t && *p == name) || matches!(name.as_str(), "vi" | "emacs" | "micro" | "hx");
+    let label = KNOWN.iter().find(|(_, p, ..)| *p == name).map_or(name.clone(), |(_, _, l, _)| l.to_string());
+    Some(Launch { label, program, args: words, terminal })
+}
+
+/// The editor to use. `on_path` says whether a program can be found;
+/// `env_editor` is `$VISUAL` or `$EDITOR`.
+pub fn choose(preferred: Option<&str>, env_editor: Option<&str>, on_path: impl Fn(&str) -> bool) -> Option<Launch> {
+    match preferred.map(str::trim) {
+        None | Some("") | Some("auto") => {}
+        Some(id) => {
+            return match KNOWN.iter().find(|(known_id, ..)| *known_id == id) {
+                Some(&(id, program, label, terminal)) => Some(known(id, program, label, terminal)),
+                None => custom(id),
+            };
+        }
+    }
+    let gui = KNOWN.iter().find(|(_, program, _, terminal)| !terminal && on_path(program));
+    if let Some(&(id, program, label, terminal)) = gui {
+        return Some(known(id, program, label, terminal));
+    }
+    if let Some(launch) = env_editor.filter(|e| !e.trim().is_empty()).and_then(custom) {
+        return Some(launch);
+    }
+    KNOWN
+        .iter()
+        .find(|(_, program, _, terminal)| *terminal && on_path(program))
+        .map(|&(id, program, label, terminal)| known(id, program, label, terminal))
+}
+
+/// File extension for a detected language; `txt` when unknown, `log`
+/// for an error with no language.
+pub fn extension(language: Option<&str>, content_type: &str) -> &'static str {
+    let language = language.unwrap_or("").trim().to_ascii_lowercase();
+    match language.as_str() {
+        "python" | "py" => "py",
+        "javascript" | "js" | "node" => "js",
+        "typescript" | "ts" => "ts",
+        "tsx" => "tsx",
+        "jsx" => "jsx",
+        "rust" | "rs" => "rs",
+        "go" | "golang" => "go",
+        "java" => "java",
+        "kotlin" => "kt",
+        "swift" => "swift",
+        "c" => "c",
+        "c++" | "cpp" => "cpp",
+        "c#" | "csharp" => "cs",
+        "ruby" | "rb" => "rb",
+        "php" => "php",
+        "shell" | "bash" | "sh" | "zsh" => "sh",
+        "powershell" => "ps1",
+        "sql" => "sql",
+        "html" => "html",
+        "css" => "css",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "markdown" | "md" => "md",
+        "lua" => "lua",
+        "haskell" => "hs",
+        _ if content_type == "error" => "log",
+        _ => "txt",
+    }
+}
+
+/// The first fenced code block in an answer, with its language tag;
+/// `None` when the answer has no fence.
+pub fn fenced_code(answer: &str) -> Option<(String, Option<String>)> {
+    let start = answer.find("```")?;
+    let rest = &answer[start + 3..];
+    let (tag, body) = rest.split_once('\n')?;
+    let end = body.find("\n```").map_or(body.len(), |i| i + 1);
+    let tag = tag.trim();
+    Some((body[..end].to_string(), (!tag.is_empty()).then(|| tag.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editors_are_chosen_in_order() {
+        let on_path = |p: &str| matches!(p, "zed" | "vim");
+        assert_eq!(choose(None, Some("nano"), on_path).unwrap().label, "Zed");
+        let none = |_: &str| false;
+        let env = choose(Some("auto"), Some("nvim -p"), none).unwrap();
+        assert_eq!((env.program.as_str(), env.args_for("/t/a.py"), env.terminal), ("nvim", vec!["-p".into(), "/t/a.py".into()], true));
+        assert_eq!(choose(None, None, |p| p == "vim").unwrap().label, "Vim");
+        assert_eq!(choose(None, None, none), None);
+        let vscode = choose(Some("vscode"), None, none).unwrap();
+        assert_eq!(vscode.args_for("a.rs"), vec!["--reuse-window", "a.rs"]);
+        let custom = choose(Some(r#""C:\Program Files\Notepad++\notepad++.exe" -n1 {file}"#), None, none).unwrap();
+        assert_eq!(custom.program, r"C:\Program Files\Notepad++\notepad++.exe");
+        assert_eq!((custom.label.as_str(), custom.terminal), ("notepad++", false));
+        assert!(EditorPrefs { preferred: Some("code\n--wait".into()) }.validate().is_err());
+    }
+
+    #[test]
+    fn extensions_and_fixed_code() {
+        assert_eq!(extension(Some("Python"), "code"), "py");
+        assert_eq!(extension(None, "error"), "log");
+        assert_eq!(extension(Some("klingon"), "code"), "txt");
+        let answer = "The variable is misspelled.\n\n```python\nprint(total)\n```\nDone.";
+        assert_eq!(fenced_code(answer), Some(("print(total)\n".to_string(), Some("python".to_string()))));
+        assert_eq!(fenced_code("no code here"), None);
+    }
+}
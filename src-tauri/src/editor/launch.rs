@@ -0,0 +1,90 @@
+//! Start an editor on a file.
+//!
+//! GUI editors are spawned directly. Terminal editors need a window:
+//!   - macOS: a new Terminal.app window via `osascript`
+//!   - Windows: a new console via `cmd /C start`
+//!   - Linux: the first of `x-terminal-emulator`, `gnome-terminal`,
+//!     `konsole`, `xterm` on PATH
+//!
+//! With no editor at all, the file goes to the system's text viewer
+//! (`open -t`, Notepad, `xdg-open`).
+
+use super::detect::Launch;
+use std::path::Path;
+use std::process::Command;
+
+/// Linux terminals and the flag that precedes the command to run.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const TERMINALS: &[(&str, &str)] =
+    &[("x-terminal-emulator", "-e"), ("gnome-terminal", "--"), ("konsole", "-e"), ("xterm", "-e")];
+
+pub fn open(editor: Option<&Launch>, file: &Path) -> Result<(), String> {
+    let path = file.to_string_lossy();
+    let mut command = match editor {
+        Some(editor) if editor.terminal => in_terminal(&editor.program, &editor.args_for(&path))?,
+        Some(editor) => {
+            let mut command = Command::new(&editor.program);
+            command.args(editor.args_for(&path));
+            command
+        }
+        None => system_viewer(&path),
+    };
+    command.spawn().map(drop).map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn system_viewer(path: &str) -> Command {
+    let mut command = Command::new("open");
+    command.args(["-t", path]);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn system_viewer(path: &str) -> Command {
+    let mut command = Command::new("notepad");
+    command.arg(path);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn system_viewer(path: &str) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(path);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn in_terminal(program: &str, args: &[String]) -> Result<Command, String> {
+    let line = std::iter::once(program).chain(args.iter().map(String::as_str)).map(sh_quote).collect::<Vec<_>>().join(" ");
+    let script = format!(
+        "tell application \"Terminal\"\nactivate\ndo script \"{}\"\nend tell",
+        line.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    let mut command = Command::new("osascript");
+    command.args(["-e", script.as_str()]);
+    Ok(command)
+}
+
+#[cfg(target_os = "windows")]
+fn in_terminal(program: &str, args: &[String]) -> Result<Command, String> {
+    let mut command = Command::new("cmd");
+    command.args(["/C", "start", "", program]).args(args);
+    Ok(command)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn in_terminal(program: &str, args: &[String]) -> Result<Command, String> {
+    let (terminal, flag) = TERMINALS
+        .iter()
+        .find(|(terminal, _)| which::which(terminal).is_ok())
+        .ok_or("No terminal emulator found for a terminal editor")?;
+    let mut command = Command::new(terminal);
+    command.args([*flag, program]).args(args);
+    Ok(command)
+}
+
+/// Single-quote a word for a POSIX shell.
+#[cfg(target_os = "macos")]
+fn sh_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
@@ -0,0 +1,128 @@
+//! Editor domain — "Open in Editor" for code and error snips.
+//!
+//! The action writes the snip's code to a temp file named for its
+//! language (`snip-<date>-<time>.py`) and opens it in the user's editor.
+//! When a fix was just suggested for the snip and it holds a code block,
+//! the fixed code is written instead. Nothing leaves the machine, so the
+//! action runs offline and isn't cached or kept in history.
+//!
+//! The editor comes from `editor.preferred` in settings, or is detected
+//! (`detect.rs`); `launch.rs` starts it, in a terminal when it needs one.
+
+pub mod detect;
+mod launch;
+
+use crate::capture::autosave::utc_parts;
+use crate::i18n::{t, tf};
+use crate::llm::execute::{ActionResult, ActionResultBody, ActionResultMetadata};
+use crate::llm::ActionMenuState;
+use detect::{Launch, KNOWN};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Action id, answered here instead of by the LLM.
+pub const OPEN_ACTION: &str = "open_in_editor";
+
+/// Actions whose answer may carry fixed code.
+const FIX_ACTIONS: &[&str] = &["suggest_fix", "fix_error", "fix_syntax", "fix_code", "format_code"];
+
+fn on_path(program: &str) -> bool {
+    which::which(program).is_ok()
+}
+
+/// The editor settings ask for, or the detected one.
+fn current_editor() -> Option<Launch> {
+    let preferred = crate::settings::current().editor.preferred;
+    let env_editor = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).ok();
+    detect::choose(preferred.as_deref(), env_editor.as_deref(), on_path)
+}
+
+/// The code to open and its language: the fixed code from the latest
+/// fix for this snip, else the snip's own text.
+fn code_for(state: &ActionMenuState, text: &str) -> (String, Option<String>, String) {
+    let menu = state.menu.lock().ok().and_then(|m| m.clone());
+    let (content_type, language) = menu.map_or((String::new(), None), |m| (m.content_type, m.detected_language));
+    let fixed = state.last_result.lock().ok().and_then(|last| {
+        let (snip, result) = last.as_ref()?;
+        let answer = result.result.text.as_deref().filter(|_| snip == text && FIX_ACTIONS.contains(&result.action_id.as_str()))?;
+        detect::fenced_code(answer)
+    });
+    match fixed {
+        Some((code, tag)) => (code, tag.or(language), content_type),
+        None => (text.to_string(), language, content_type),
+    }
+}
+
+fn temp_file(extension: &str, code: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join("omni-glass-editor");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp folder: {}", e))?;
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (y, mo, d, h, mi, s) = utc_parts(now);
+    let path = dir.join(format!("snip-{:04}{:02}{:02}-{:02}{:02}{:02}.{}", y, mo, d, h, mi, s, extension));
+    let code = if code.ends_with('\n') { code.to_string() } else { format!("{}\n", code) };
+    std::fs::write(&path, code).map_err(|e| format!("Failed to write temp file: {}", e))?;
+    Ok(path)
+}
+
+/// Run the action on the snip in the action menu.
+pub fn open_snip(state: &ActionMenuState, text: &str) -> ActionResult {
+    let (code, language, content_type) = code_for(state, text);
+    let path = match temp_file(detect::extension(language.as_deref(), &content_type), &code) {
+        Ok(path) => path,
+        Err(e) => return ActionResult::error(OPEN_ACTION, &e),
+    };
+    let editor = current_editor();
+    let label = editor.as_ref().map_or_else(|| t("editor.systemViewer"), |e| e.label.clone());
+    if let Err(e) = launch::open(editor.as_ref(), &path) {
+        log::warn!("[EDITOR] Failed to start {}: {}", label, e);
+        return ActionResult::error(OPEN_ACTION, &tf("error.editorFailed", &[("editor", label.as_str()), ("error", e.as_str())]));
+    }
+    log::info!("[EDITOR] Opened {} in {}", path.display(), label);
+    let path = path.display().to_string();
+    ActionResult {
+        status: "success".to_string(),
+        action_id: OPEN_ACTION.to_string(),
+        result: ActionResultBody {
+            result_type: "text".to_string(),
+            text: Some(tf("editor.opened", &[("editor", label.as_str()), ("file", path.as_str())])),
+            file_path: Some(path),
+            command: None,
+            clipboard_content: None,
+            mime_type: None,
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata {
+            processing_note: Some("Opened locally (no LLM call)".to_string()),
+            ..ActionResultMetadata::default()
+        }),
+    }
+}
+
+/// A known editor and whether it is installed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditorInfo {
+    pub id: String,
+    pub label: String,
+    pub installed: bool,
+    pub terminal: bool,
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: known editors, and the label of the one "auto" picks.
+#[tauri::command]
+pub fn get_editors() -> serde_json::Value {
+    let editors: Vec<EditorInfo> = KNOWN
+        .iter()
+        .map(|&(id, program, label, terminal)| EditorInfo {
+            id: id.to_string(),
+            label: label.to_string(),
+            installed: on_path(program),
+            terminal,
+        })
+        .collect();
+    let env_editor = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).ok();
+    let detected = detect::choose(None, env_editor.as_deref(), on_path).map(|e| e.label);
+    serde_json::json!({ "editors": editors, "detected": detected })
+}
@@ -0,0 +1,174 @@
+//! Email draft: validated fields and the `mailto:` URL (pure, no I/O).
+//!
+//! The LLM writes the fields; everything after that is deterministic.
+//! Addresses that don't look like `name@host.tld` are dropped rather than
+//! guessed at, the subject is one line, and the URL follows RFC 6068:
+//! every byte outside the unreserved set is percent-encoded, line breaks
+//! become `%0D%0A`. A URL longer than mail clients and `ShellExecute`
+//! accept gets its body shortened, never its recipients or subject.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest `mailto:` URL handed to the system (Windows and Outlook stop
+/// near 2048 characters).
+pub const MAX_MAILTO_LEN: usize = 2000;
+const MAX_SUBJECT_CHARS: usize = 200;
+const MAX_RECIPIENTS: usize = 20;
+
+/// A structured email, the `email` part of an `"email"` result.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailDraft {
+    #[serde(default)]
+    pub to: Vec<String>,
+    #[serde(default)]
+    pub cc: Vec<String>,
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl EmailDraft {
+    /// The draft with bad addresses dropped and text tidied, or an error
+    /// when nothing is left to send.
+    pub fn validated(&self) -> Result<EmailDraft, String> {
+        let addresses = |list: &[String]| -> Vec<String> {
+            let mut out: Vec<String> = Vec::new();
+            for address in list.iter().map(|a| a.trim().trim_start_matches("mailto:")) {
+                if is_address(address) && !out.iter().any(|o| o.eq_ignore_ascii_case(address)) {
+                    out.push(address.to_string());
+                }
+            }
+            out.truncate(MAX_RECIPIENTS);
+            out
+        };
+        let subject = self.subject.split_whitespace().collect::<Vec<_>>().join(" ");
+        let body = self.body.replace("\r\n", "\n").trim().to_string();
+        if subject.is_empty() && body.is_empty() {
+            return Err("the draft has no subject and no body".to_string());
+        }
+        Ok(EmailDraft {
+            to: addresses(&self.to),
+            cc: addresses(&self.cc),
+            subject: subject.chars().take(MAX_SUBJECT_CHARS).collect(),
+            body,
+        })
+    }
+
+    /// Plain-text form for copying, history, and Markdown export.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if !self.to.is_empty() {
+            text.push_str(&format!("To: {}\n", self.to.join(", ")));
+        }
+        if !self.cc.is_empty() {
+            text.push_str(&format!("Cc: {}\n", self.cc.join(", ")));
+        }
+        text.push_str(&format!("Subject: {}\n\n{}", self.subject, self.body));
+        text
+    }
+
+    /// The draft as a `mailto:` URL of at most `max_len` bytes.
+    pub fn mailto(&self, max_len: usize) -> String {
+        let url = |body: &str| {
+            let to = self.to.iter().map(|a| encode(a)).collect::<Vec<_>>().join(",");
+            let mut params = Vec::new();
+            if !self.cc.is_empty() {
+                params.push(format!("cc={}", self.cc.iter().map(|a| encode(a)).collect::<Vec<_>>().join(",")));
+            }
+            if !self.subject.is_empty() {
+                params.push(format!("subject={}", encode(&self.subject)));
+            }
+            if !body.is_empty() {
+                params.push(format!("body={}", encode(&body.replace('\n', "\r\n"))));
+            }
+            let query = if params.is_empty() { String::new() } else { format!("?{}", params.join("&")) };
+            format!("mailto:{}{}", to, query)
+        };
+        let full = url(&self.body);
+        if full.len() <= max_len {
+            return full;
+        }
+        // Longest body prefix (in chars) that still fits, with a marker
+        let chars: Vec<char> = self.body.chars().collect();
+        let shortened = |n: usize| format!("{}\n[…]", chars[..n].iter().collect::<String>().trim_end());
+        let (mut lo, mut hi) = (0, chars.len());
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if url(&shortened(mid)).len() <= max_len {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        url(&shortened(lo))
+    }
+}
+
+/// `local@host.tld`, no spaces or URL delimiters.
+pub fn is_address(text: &str) -> bool {
+    let Some((local, domain)) = text.rsplit_once('@') else { return false };
+    let allowed = |c: char| c.is_alphanumeric() || "!#$%&'*+-/=^_`{|}~.".contains(c);
+    !local.is_empty()
+        && local.len() <= 64
+        && local.chars().all(allowed)
+        && domain.contains('.')
+        && domain.split('.').all(|l| !l.is_empty() && l.chars().all(|c| c.is_alphanumeric() || c == '-'))
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters and `@`.
+fn encode(text: &str) -> String {
+    let mut out = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~@".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(to: &[&str], subject: &str, body: &str) -> EmailDraft {
+        EmailDraft {
+            to: to.iter().map(|s| s.to_string()).collect(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+            ..EmailDraft::default()
+        }
+    }
+
+    #[test]
+    fn drafts_are_cleaned() {
+        let d = draft(&["ada@example.com", "ADA@example.com", "not an address", "mailto:bob@x.io"], " Re:\n  Q3  plan ", "Hi,\r\n\r\nSounds good.\n");
+        let d = d.validated().unwrap();
+        assert_eq!(d.to, vec!["ada@example.com", "bob@x.io"]);
+        assert_eq!(d.subject, "Re: Q3 plan");
+        assert_eq!(d.body, "Hi,\n\nSounds good.");
+        assert_eq!(d.to_text(), "To: ada@example.com, bob@x.io\nSubject: Re: Q3 plan\n\nHi,\n\nSounds good.");
+        assert!(draft(&[], " ", "\n").validated().is_err());
+        assert!(!is_address("a@localhost") && !is_address("@x.io") && !is_address("a b@x.io"));
+    }
+
+    #[test]
+    fn mailto_is_encoded_and_bounded() {
+        let mut d = draft(&["ada@example.com", "bob@x.io"], "Q3 & Q4 — plan?", "Line 1\nLine 2 = 100%");
+        d.cc = vec!["cc@x.io".to_string()];
+        assert_eq!(
+            d.mailto(MAX_MAILTO_LEN),
+            "mailto:ada@example.com,bob@x.io?cc=cc@x.io&subject=Q3%20%26%20Q4%20%E2%80%94%20plan%3F\
+             &body=Line%201%0D%0ALine%202%20%3D%20100%25"
+        );
+        assert_eq!(draft(&[], "Hi", "").mailto(MAX_MAILTO_LEN), "mailto:?subject=Hi");
+        let long = draft(&["ada@example.com"], "Notes", &"é word ".repeat(400));
+        let url = long.mailto(MAX_MAILTO_LEN);
+        assert!(url.len() <= MAX_MAILTO_LEN && url.len() > MAX_MAILTO_LEN - 40);
+        assert!(url.starts_with("mailto:ada@example.com?subject=Notes&body=%C3%A9%20word"));
+        assert!(url.ends_with("%0D%0A%5B%E2%80%A6%5D"));
+    }
+}
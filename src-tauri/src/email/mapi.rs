@@ -0,0 +1,98 @@
+//! Simple MAPI on Windows: hand the draft to the default mail client.
+//!
+//! `MAPISendMailW` (Windows 8+) opens the client's compose window with no
+//! length limit, where a `mailto:` URL is cut near 2048 characters. It
+//! fails when no MAPI client is registered (common with web mail), and
+//! the caller then falls back to `mailto:`.
+
+use super::draft::EmailDraft;
+use std::ffi::c_void;
+use std::ptr::{null, null_mut};
+
+const MAPI_TO: u32 = 1;
+const MAPI_CC: u32 = 2;
+const MAPI_LOGON_UI: u32 = 0x1;
+const MAPI_DIALOG: u32 = 0x8;
+const SUCCESS_SUCCESS: u32 = 0;
+const MAPI_USER_ABORT: u32 = 1;
+
+#[repr(C)]
+struct MapiRecipDescW {
+    reserved: u32,
+    recip_class: u32,
+    name: *const u16,
+    address: *const u16,
+    eid_size: u32,
+    entry_id: *mut c_void,
+}
+
+#[repr(C)]
+struct MapiMessageW {
+    reserved: u32,
+    subject: *const u16,
+    note_text: *const u16,
+    message_type: *const u16,
+    date_received: *const u16,
+    conversation_id: *const u16,
+    flags: u32,
+    originator: *mut MapiRecipDescW,
+    recip_count: u32,
+    recips: *mut MapiRecipDescW,
+    file_count: u32,
+    files: *mut c_void,
+}
+
+#[link(name = "mapi32")]
+extern "system" {
+    fn MAPISendMailW(session: usize, ui_param: usize, message: *const MapiMessageW, flags: u32, reserved: u32) -> u32;
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Show the compose window. Blocks until it closes, so call it off the
+/// async runtime. Cancelling the window counts as success.
+pub fn send(draft: &EmailDraft) -> Result<(), String> {
+    let recipients: Vec<(u32, Vec<u16>)> = draft
+        .to
+        .iter()
+        .map(|a| (MAPI_TO, a))
+        .chain(draft.cc.iter().map(|a| (MAPI_CC, a)))
+        .map(|(class, a)| (class, wide(&format!("SMTP:{}", a))))
+        .collect();
+    let mut recips: Vec<MapiRecipDescW> = recipients
+        .iter()
+        .map(|(class, address)| MapiRecipDescW {
+            reserved: 0,
+            recip_class: *class,
+            name: address.as_ptr().wrapping_add(5),
+            address: address.as_ptr(),
+            eid_size: 0,
+            entry_id: null_mut(),
+        })
+        .collect();
+    let subject = wide(&draft.subject);
+    let body = wide(&draft.body.replace('\n', "\r\n"));
+    let message = MapiMessageW {
+        reserved: 0,
+        subject: subject.as_ptr(),
+        note_text: body.as_ptr(),
+        message_type: null(),
+        date_received: null(),
+        conversation_id: null(),
+        flags: 0,
+        originator: null_mut(),
+        recip_count: recips.len() as u32,
+        recips: if recips.is_empty() { null_mut() } else { recips.as_mut_ptr() },
+        file_count: 0,
+        files: null_mut(),
+    };
+    // SAFETY: every pointer refers to a NUL-terminated buffer or array
+    // owned by this frame, which outlives the synchronous call.
+    let code = unsafe { MAPISendMailW(0, 0, &message, MAPI_LOGON_UI | MAPI_DIALOG, 0) };
+    match code {
+        SUCCESS_SUCCESS | MAPI_USER_ABORT => Ok(()),
+        code => Err(format!("MAPISendMailW failed with code {}", code)),
+    }
+}
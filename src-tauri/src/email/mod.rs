@@ -0,0 +1,110 @@
+//! Email domain — "Draft Email" about a snip, opened in the mail client.
+//!
+//! One LLM call writes the draft as structured fields (recipients,
+//! subject, body) through the args bridge, validated against
+//! `DRAFT_SCHEMA`. The result has type `"email"` and carries the fields in
+//! `result.email`; `text` holds a plain-text copy for history and copying.
+//! `open_email` then hands the draft to the system mail client: Simple
+//! MAPI on Windows (`mapi.rs`), a `mailto:` URL built in `draft.rs`
+//! everywhere else and as the Windows fallback. Nothing is ever sent;
+//! the user reviews and sends from their client.
+
+pub mod draft;
+#[cfg(target_os = "windows")]
+mod mapi;
+
+use crate::i18n::tf;
+use crate::llm::execute::{ActionResult, ActionResultBody};
+use draft::{EmailDraft, MAX_MAILTO_LEN};
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// Action id, answered here after one structured LLM call.
+pub const DRAFT_ACTION: &str = "draft_email";
+
+const DRAFT_DESCRIPTION: &str = "Write an email about the text. If the text is a message or email, write the reply \
+     to it; otherwise write a short email sharing or asking about it. Use only recipient addresses that appear \
+     in the text (the sender, for a reply); leave the lists empty otherwise. Match the language of the text. \
+     The body is plain text with no signature placeholder.";
+
+/// The fields the LLM is asked for.
+static DRAFT_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(|| {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "to": { "type": "array", "items": { "type": "string" }, "maxItems": 20 },
+            "cc": { "type": "array", "items": { "type": "string" }, "maxItems": 20 },
+            "subject": { "type": "string", "minLength": 1, "maxLength": 200 },
+            "body": { "type": "string", "minLength": 1 }
+        },
+        "required": ["subject", "body"],
+        "additionalProperties": false
+    })
+});
+
+/// Draft an email about `text`.
+pub async fn compose(text: &str) -> ActionResult {
+    let fields =
+        match crate::llm::plugin_args::generate_plugin_args(DRAFT_ACTION, DRAFT_DESCRIPTION, &DRAFT_SCHEMA, text).await {
+            Ok(fields) => fields,
+            Err(e) => return failed(&e),
+        };
+    let draft = match serde_json::from_value::<EmailDraft>(fields).map_err(|e| e.to_string()).and_then(|d| d.validated()) {
+        Ok(draft) => draft,
+        Err(e) => return failed(&e),
+    };
+    log::info!("[EMAIL] Draft ready: {} recipient(s), {} body chars", draft.to.len() + draft.cc.len(), draft.body.len());
+    ActionResult {
+        status: "success".to_string(),
+        action_id: DRAFT_ACTION.to_string(),
+        result: ActionResultBody {
+            result_type: "email".to_string(),
+            text: Some(draft.to_text()),
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: None,
+            email: Some(draft),
+        },
+        metadata: None,
+    }
+}
+
+fn failed(error: &str) -> ActionResult {
+    log::warn!("[EMAIL] No draft: {}", error);
+    ActionResult::error(DRAFT_ACTION, &tf("error.emailFailed", &[("error", error)]))
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// How `open_email` handed the draft over.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Opened {
+    /// `"mapi"` or `"mailto"`.
+    pub via: String,
+    /// The body was cut to fit the `mailto:` length limit.
+    pub shortened: bool,
+}
+
+/// Tauri command: open `draft` in the system mail client.
+#[tauri::command]
+pub async fn open_email(draft: EmailDraft) -> Result<Opened, String> {
+    let draft = draft.validated()?;
+    #[cfg(target_os = "windows")]
+    {
+        let for_mapi = draft.clone();
+        match tauri::async_runtime::spawn_blocking(move || mapi::send(&for_mapi)).await.map_err(|e| e.to_string())? {
+            Ok(()) => {
+                log::info!("[EMAIL] Draft handed to the mail client via MAPI");
+                return Ok(Opened { via: "mapi".to_string(), shortened: false });
+            }
+            Err(e) => log::warn!("[EMAIL] {}; falling back to mailto", e),
+        }
+    }
+    let url = draft.mailto(MAX_MAILTO_LEN);
+    let shortened = url != draft.mailto(usize::MAX);
+    crate::links::system_open(&url).spawn().map_err(|e| format!("Failed to open the mail client: {}", e))?;
+    log::info!("[EMAIL] Draft handed to the mail client via mailto ({} bytes, shortened: {})", url.len(), shortened);
+    Ok(Opened { via: "mailto".to_string(), shortened })
+}
@@ -0,0 +1,273 @@
+//! Categorized errors for Tauri commands.
+//!
+//! Commands on the snip, execute, and text-launcher paths, and those
+//! for capture permission, plugins, saving, and the network settings,
+//! return `OmniError` instead of a bare `String`. It reaches the frontend as
+//! `{"kind", "code", "message"}`: `code` is stable (`llm.no_api_key`,
+//! `capture.permission_denied`, …) so the UI can branch on it, and
+//! `message` is localized for display.
+//!
+//! `OmniError` converts to and from `String`, so `?` works across
+//! functions that still return `Result<_, String>`; a plain string
+//! becomes `Other`.
+
+use crate::i18n::{t, tf};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OmniError {
+    Capture(Capture),
+    /// Cropping, encoding, or recognizing the snip failed.
+    Ocr(String),
+    Llm(Llm),
+    Safety(Safety),
+    /// A plugin tool can't run or failed, or a plugin couldn't be
+    /// installed, approved, or changed.
+    Plugin(String),
+    Export(Export),
+    Network(Network),
+    /// Anything not yet categorized.
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Capture {
+    /// Screen Recording access is off (macOS).
+    PermissionDenied,
+    /// No screenshot is stored to crop from.
+    NoScreenshot,
+    /// A capture rule refuses snipping this app's window.
+    Refused(String),
+    Failed(String),
+    /// The OS has no screen capture setting to open.
+    NoSettings,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Llm {
+    NoApiKey,
+    /// The `offlineMode` setting is on.
+    OfflineMode,
+    /// The provider's host didn't answer the last probe.
+    Offline,
+    /// The provider call failed or its response was unusable.
+    Request(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Export {
+    /// The path is a system or credential folder, or policy blocks it.
+    PathRefused(String),
+    /// The snip has nothing to save in that format.
+    Nothing(String),
+    /// The file couldn't be written.
+    Write(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Network {
+    /// The proxy or CA bundle settings can't be applied.
+    Settings(String),
+    /// The keychain refused to store or remove the proxy password.
+    Keychain(String),
+    InvalidUrl(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Safety {
+    /// Local-only mode, set directly or by the active profile.
+    LocalOnly { profile: Option<String> },
+    /// The user declined to continue after an outbound anomaly.
+    OutboundPaused { reason: String },
+}
+
+impl OmniError {
+    /// Category the frontend can branch on first.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Capture(_) => "capture",
+            Self::Ocr(_) => "ocr",
+            Self::Llm(_) => "llm",
+            Self::Safety(_) => "safety",
+            Self::Plugin(_) => "plugin",
+            Self::Export(_) => "export",
+            Self::Network(_) => "network",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Stable `kind.reason` code; never localized or reworded.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Capture(Capture::PermissionDenied) => "capture.permission_denied",
+            Self::Capture(Capture::NoScreenshot) => "capture.no_screenshot",
+            Self::Capture(Capture::Refused(_)) => "capture.refused",
+            Self::Capture(Capture::Failed(_)) => "capture.failed",
+            Self::Capture(Capture::NoSettings) => "capture.no_settings",
+            Self::Ocr(_) => "ocr.failed",
+            Self::Llm(Llm::NoApiKey) => "llm.no_api_key",
+            Self::Llm(Llm::OfflineMode) => "llm.offline_mode",
+            Self::Llm(Llm::Offline) => "llm.offline",
+            Self::Llm(Llm::Request(_)) => "llm.request_failed",
+            Self::Safety(Safety::LocalOnly { .. }) => "safety.local_only",
+            Self::Safety(Safety::OutboundPaused { .. }) => "safety.outbound_paused",
+            Self::Plugin(_) => "plugin.failed",
+            Self::Export(Export::PathRefused(_)) => "export.path_refused",
+            Self::Export(Export::Nothing(_)) => "export.nothing",
+            Self::Export(Export::Write(_)) => "export.write_failed",
+            Self::Network(Network::Settings(_)) => "network.settings",
+            Self::Network(Network::Keychain(_)) => "network.keychain",
+            Self::Network(Network::InvalidUrl(_)) => "network.invalid_url",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Cloud calls are off by choice or unreachable, as opposed to failing.
+    pub fn cloud_unavailable(&self) -> bool {
+        matches!(self, Self::Safety(Safety::LocalOnly { .. }) | Self::Llm(Llm::OfflineMode | Llm::Offline))
+    }
+
+    /// User-facing message in the current locale.
+    pub fn message(&self) -> String {
+        match self {
+            Self::Capture(Capture::PermissionDenied) => t("error.capturePermission"),
+            Self::Capture(Capture::NoScreenshot) => t("error.noScreenshot"),
+            Self::Capture(Capture::Refused(app)) => tf("error.captureRefused", &[("app", app)]),
+            Self::Capture(Capture::Failed(e)) => tf("error.captureFailed", &[("error", e)]),
+            Self::Capture(Capture::NoSettings) => t("error.noPermissionSettings"),
+            Self::Ocr(e) => tf("error.ocrFailed", &[("error", e)]),
+            Self::Llm(Llm::NoApiKey) => t("error.noApiKey"),
+            Self::Llm(Llm::OfflineMode) => t("error.offlineMode"),
+            Self::Llm(Llm::Offline) => t("error.offline"),
+            Self::Llm(Llm::Request(e)) => tf("error.llmFailed", &[("error", e)]),
+            Self::Safety(Safety::LocalOnly { profile: Some(name) }) => tf("error.localOnlyProfile", &[("profile", name)]),
+            Self::Safety(Safety::LocalOnly { profile: None }) => t("error.localOnly"),
+            Self::Safety(Safety::OutboundPaused { reason }) => tf("error.outboundPaused", &[("reason", reason)]),
+            Self::Plugin(e) => tf("error.pluginFailed", &[("error", e)]),
+            Self::Export(Export::PathRefused(e) | Export::Nothing(e)) => e.clone(),
+            Self::Export(Export::Write(e)) => tf("error.writeFailed", &[("error", e)]),
+            Self::Network(Network::Settings(e)) => tf("error.networkSettings", &[("error", e)]),
+            Self::Network(Network::Keychain(e)) => tf("error.keychain", &[("error", e)]),
+            Self::Network(Network::InvalidUrl(e)) => tf("error.invalidUrl", &[("error", e)]),
+            Self::Other(e) => e.clone(),
+        }
+    }
+}
+
+impl fmt::Display for OmniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl std::error::Error for OmniError {}
+
+impl Serialize for OmniError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("OmniError", 3)?;
+        s.serialize_field("kind", self.kind())?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", &self.message())?;
+        s.end()
+    }
+}
+
+impl From<Capture> for OmniError {
+    fn from(e: Capture) -> Self {
+        Self::Capture(e)
+    }
+}
+
+impl From<Llm> for OmniError {
+    fn from(e: Llm) -> Self {
+        Self::Llm(e)
+    }
+}
+
+impl From<Safety> for OmniError {
+    fn from(e: Safety) -> Self {
+        Self::Safety(e)
+    }
+}
+
+impl From<Export> for OmniError {
+    fn from(e: Export) -> Self {
+        Self::Export(e)
+    }
+}
+
+impl From<Network> for OmniError {
+    fn from(e: Network) -> Self {
+        Self::Network(e)
+    }
+}
+
+/// A failed capture is most often missing permission; ask the OS which.
+impl From<crate::capture::CaptureError> for OmniError {
+    fn from(e: crate::capture::CaptureError) -> Self {
+        use crate::capture::{permission, CaptureError};
+        match e {
+            CaptureError::Refused(app) => Capture::Refused(app).into(),
+            CaptureError::NoPortal => Capture::Failed(t("capture.noPortal")).into(),
+            _ if !permission::screen_capture_allowed() => Capture::PermissionDenied.into(),
+            e => Capture::Failed(e.to_string()).into(),
+        }
+    }
+}
+
+impl From<String> for OmniError {
+    fn from(e: String) -> Self {
+        Self::Other(e)
+    }
+}
+
+impl From<&str> for OmniError {
+    fn from(e: &str) -> Self {
+        Self::Other(e.to_string())
+    }
+}
+
+impl From<OmniError> for String {
+    fn from(e: OmniError) -> Self {
+        e.message()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_prefixed_by_kind() {
+        let errors = [
+            OmniError::from(Capture::PermissionDenied),
+            Capture::NoScreenshot.into(),
+            Capture::Refused("1Password".into()).into(),
+            OmniError::Ocr("decode".into()),
+            Llm::NoApiKey.into(),
+            Llm::Offline.into(),
+            Safety::LocalOnly { profile: None }.into(),
+            OmniError::Plugin("crashed".into()),
+            Export::Write("disk full".into()).into(),
+            Network::InvalidUrl("relative URL".into()).into(),
+        ];
+        for e in &errors {
+            assert!(e.code().starts_with(e.kind()), "{} is not under {}", e.code(), e.kind());
+        }
+        assert_eq!(OmniError::from(Llm::NoApiKey).code(), "llm.no_api_key");
+        assert!(OmniError::from(Llm::OfflineMode).cloud_unavailable());
+        assert!(!OmniError::from(Safety::OutboundPaused { reason: "volume".into() }).cloud_unavailable());
+    }
+
+    #[test]
+    fn serializes_kind_code_and_message() {
+        let json = serde_json::to_value(OmniError::from("Window closed".to_string())).unwrap();
+        assert_eq!(json, serde_json::json!({"kind": "other", "code": "other", "message": "Window closed"}));
+
+        let json = serde_json::to_value(OmniError::from(Llm::Request("HTTP 500".into()))).unwrap();
+        assert_eq!((json["kind"].as_str(), json["code"].as_str()), (Some("llm"), Some("llm.request_failed")));
+        assert!(json["message"].as_str().unwrap().contains("HTTP 500"));
+        assert_eq!(String::from(OmniError::Other("as is".into())), "as is");
+    }
+}
@@ -0,0 +1,174 @@
+//! CSV / TSV text, CSV parsing, and number handling (pure, no I/O).
+//!
+//! Cells are written as OCR read them; only quoting changes. A field is
+//! quoted when it holds the delimiter, a quote, a line break, or edge
+//! whitespace. A text cell starting with `=`, `+`, `-`, or `@` gets a
+//! leading `'` so a spreadsheet doesn't run it as a formula.
+//!
+//! Numbers may use either decimal separator: `decimal_separator` decides
+//! per table from the cells that are unambiguous (`1,5` or `1.234,56`
+//! versus `1.5` or `1,234.56`), and `parse_number` reads a cell with it.
+
+use crate::ocr::table_detect::Table;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Tsv,
+    Xlsx,
+}
+
+impl Format {
+    /// Format for a file name's extension; `None` for anything else.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let ext = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// `table` as delimited text with CRLF line ends (RFC 4180).
+pub fn write(table: &Table, delimiter: char) -> String {
+    let decimal = decimal_separator(table);
+    let mut out = String::new();
+    for row in &table.rows {
+        let line: Vec<String> = row.iter().map(|cell| field(cell, delimiter, decimal)).collect();
+        out.push_str(&line.join(&delimiter.to_string()));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn field(cell: &str, delimiter: char, decimal: char) -> String {
+    let cell = if cell.starts_with(['=', '+', '-', '@']) && parse_number(cell, decimal).is_none() {
+        format!("'{}", cell)
+    } else {
+        cell.to_string()
+    };
+    let needs_quotes = cell.contains([delimiter, '"', '\n', '\r']) || cell.trim() != cell;
+    if needs_quotes {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell
+    }
+}
+
+/// Parse CSV (quoted fields may hold commas, quotes, and line breaks)
+/// into a rectangular table; `None` without at least two columns.
+pub fn parse_csv(text: &str) -> Option<Table> {
+    let mut rows: Vec<Vec<String>> = vec![vec![String::new()]];
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let row = rows.last_mut().unwrap();
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                row.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(String::new()),
+            '\r' if !quoted => {}
+            '\n' if !quoted => rows.push(vec![String::new()]),
+            _ => row.last_mut().unwrap().push(c),
+        }
+    }
+    rows.retain(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+    let columns = rows.iter().map(Vec::len).max()?;
+    if columns < 2 {
+        return None;
+    }
+    for row in &mut rows {
+        row.resize(columns, String::new());
+    }
+    Some(Table { rows })
+}
+
+/// `,` when the table's numbers use a decimal comma, else `.`.
+pub fn decimal_separator(table: &Table) -> char {
+    let (mut comma, mut dot) = (0, 0);
+    for cell in table.rows.iter().skip(1).flatten() {
+        let digits = cell.trim().trim_start_matches(['-', '+', '$', '€', '£', '¥']).trim_end_matches('%').trim();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == ',' || c == '.') {
+            continue;
+        }
+        match (digits.rfind(','), digits.rfind('.')) {
+            (Some(c), Some(d)) if c > d => comma += 1,
+            (Some(_), Some(_)) => dot += 1,
+            (Some(c), None) if digits.len() - c - 1 != 3 => comma += 1,
+            (None, Some(d)) if digits.len() - d - 1 != 3 => dot += 1,
+            _ => {}
+        }
+    }
+    if comma > dot {
+        ','
+    } else {
+        '.'
+    }
+}
+
+/// The cell as a number, or `None` for text. Currency symbols, percent
+/// signs, and thousands separators are dropped; leading zeros (`0042`,
+/// an ID or ZIP code) keep the cell as text.
+pub fn parse_number(cell: &str, decimal: char) -> Option<f64> {
+    let trimmed = cell.trim().trim_start_matches(['$', '€', '£', '¥']).trim_end_matches(['%', '€']).trim();
+    let unsigned = trimmed.trim_start_matches(['-', '+']);
+    if unsigned.is_empty() || !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    if unsigned.len() > 1 && unsigned.starts_with('0') && !unsigned[1..].starts_with(decimal) {
+        return None;
+    }
+    let grouping = if decimal == ',' { '.' } else { ',' };
+    let plain: String = trimmed
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\u{a0}' | '\u{202f}' | '\'') && *c != grouping)
+        .map(|c| if c == decimal { '.' } else { c })
+        .collect();
+    plain.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(rows: &[&[&str]]) -> Table {
+        Table { rows: rows.iter().map(|r| r.iter().map(|c| c.to_string()).collect()).collect() }
+    }
+
+    #[test]
+    fn fields_are_quoted_and_formulas_defused() {
+        let t = table(&[&["Name", "Note"], &["Smith, J", "say \"hi\""], &["=HYPERLINK(\"x\")", "-12.5"]]);
+        assert_eq!(
+            write(&t, ','),
+            "Name,Note\r\n\"Smith, J\",\"say \"\"hi\"\"\"\r\n\"'=HYPERLINK(\"\"x\"\")\",-12.5\r\n"
+        );
+        assert_eq!(write(&t, '\t').lines().nth(1), Some("Smith, J\t\"say \"\"hi\"\"\""));
+        assert_eq!(parse_csv(&write(&t, ',')).unwrap().rows[1], t.rows[1]);
+        assert_eq!(parse_csv("a,b\n\"multi\nline\",2\n").unwrap().rows[1][0], "multi\nline");
+        assert_eq!(Format::from_path("/tmp/Report.XLSX"), Some(Format::Xlsx));
+        assert_eq!(Format::from_path("notes.txt"), None);
+    }
+
+    #[test]
+    fn decimal_separators_follow_the_table() {
+        let german = table(&[&["Artikel", "Preis"], &["Apfel", "1,20"], &["Summe", "1.234,56"], &["Stück", "1.000"]]);
+        assert_eq!(decimal_separator(&german), ',');
+        assert_eq!(parse_number("1.234,56", ','), Some(1234.56));
+        assert_eq!(parse_number("€ 1,20", ','), Some(1.2));
+
+        let english = table(&[&["Item", "Price"], &["Apple", "$1.20"], &["Total", "1,234.56"], &["Units", "1,000"]]);
+        assert_eq!(decimal_separator(&english), '.');
+        assert_eq!(parse_number("1,234.56", '.'), Some(1234.56));
+        assert_eq!(parse_number("1,000", '.'), Some(1000.0));
+        assert_eq!(parse_number("-7%", '.'), Some(-7.0));
+        assert_eq!(parse_number("0042", '.'), None);
+        assert_eq!(parse_number("0.5", '.'), Some(0.5));
+        assert_eq!(parse_number("v1.2", '.'), None);
+        assert_eq!(parse_number("12 apples", '.'), None);
+    }
+}
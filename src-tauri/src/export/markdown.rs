@@ -0,0 +1,230 @@
+//! Markdown document for a snip and its answers (pure, no I/O).
+//!
+//! The layout comes from a template (settings `export.markdownTemplate`)
+//! with these placeholders:
+//!   - `{title}`: the classify summary, or `Snip #<id>`
+//!   - `{date}`: when the snip was taken, `YYYY-MM-DD HH:MM UTC`
+//!   - `{type}`: content type (`code`, `table`, …)
+//!   - `{image}`: the crop as a Markdown image, or nothing
+//!   - `{code}`: the OCR text in a fenced block tagged with its language
+//!   - `{text}`: the OCR text as is
+//!   - `{results}`: one `##` section per answer
+//!   - `{tags}`, `{notes}`: the snip's tags (`#tag`) and notes
+//!
+//! Unknown placeholders are left as written, and runs of blank lines left
+//! by empty ones are collapsed.
+
+use crate::capture::autosave::utc_parts;
+use crate::llm::execute::ActionResult;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_TEMPLATE: &str = "# {title}\n\n_{date} · {type}_ {tags}\n\n{image}\n\n{code}\n\n{results}\n\n{notes}\n";
+
+/// Longest template accepted in settings.
+const MAX_TEMPLATE_CHARS: usize = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPrefs {
+    /// Layout of `export_markdown` documents (placeholders above).
+    #[serde(default = "default_template")]
+    pub markdown_template: String,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+impl Default for ExportPrefs {
+    fn default() -> Self {
+        Self { markdown_template: default_template() }
+    }
+}
+
+impl ExportPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.markdown_template.trim().is_empty() {
+            return Err("Markdown template is empty".to_string());
+        }
+        if self.markdown_template.chars().count() > MAX_TEMPLATE_CHARS {
+            return Err(format!("Markdown template is over {} characters", MAX_TEMPLATE_CHARS));
+        }
+        Ok(())
+    }
+}
+
+/// Everything a document is built from.
+#[derive(Debug, Clone, Default)]
+pub struct SnipDoc {
+    pub id: u64,
+    pub summary: String,
+    /// Unix seconds.
+    pub created_at: u64,
+    pub content_type: String,
+    pub language: Option<String>,
+    pub text: String,
+    /// Image target: a relative file name or a `data:` URI.
+    pub image: Option<String>,
+    /// (section heading, answer) in the order they were given.
+    pub results: Vec<(String, Answer)>,
+    pub tags: Vec<String>,
+    pub notes: String,
+}
+
+/// One answer: the full result when kept, else its text.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Result(Box<ActionResult>),
+    Text(String),
+}
+
+pub fn render(template: &str, doc: &SnipDoc) -> String {
+    let (y, mo, d, h, mi, _) = utc_parts(doc.created_at);
+    let title = match doc.summary.trim() {
+        "" => format!("Snip #{}", doc.id),
+        summary => summary.to_string(),
+    };
+    let results: Vec<String> =
+        doc.results.iter().map(|(heading, answer)| format!("## {}\n\n{}", heading, answer_markdown(answer))).collect();
+    let tags: Vec<String> = doc.tags.iter().map(|t| format!("#{}", t)).collect();
+    let image = doc.image.as_ref().map(|target| format!("![{}]({})", title.replace(['[', ']'], ""), target));
+    let filled = template
+        .replace("{title}", &title)
+        .replace("{date}", &format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", y, mo, d, h, mi))
+        .replace("{type}", &doc.content_type)
+        .replace("{image}", image.as_deref().unwrap_or(""))
+        .replace("{code}", &fenced(&doc.text, doc.language.as_deref().unwrap_or("")))
+        .replace("{text}", &doc.text)
+        .replace("{results}", &results.join("\n\n"))
+        .replace("{tags}", &tags.join(" "))
+        .replace("{notes}", doc.notes.trim());
+    collapse_blank_lines(&filled)
+}
+
+/// `text` in a code fence longer than any backtick run inside it.
+pub fn fenced(text: &str, language: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}", fence, language, text.trim_end(), fence)
+}
+
+fn answer_markdown(answer: &Answer) -> String {
+    let result = match answer {
+        Answer::Text(text) => return text.trim().to_string(),
+        Answer::Result(result) => &result.result,
+    };
+    let text = result.text.as_deref().unwrap_or("").trim();
+    match result.result_type.as_str() {
+        "command" => {
+            let command = fenced(result.command.as_deref().unwrap_or(""), "sh");
+            if text.is_empty() { command } else { format!("{}\n\n{}", text, command) }
+        }
+        "file" => {
+            let name = result.file_path.as_deref().unwrap_or("export");
+            let language = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+            format!("**{}**\n\n{}", name, fenced(text, language))
+        }
+        "clipboard" => result.clipboard_content.as_deref().unwrap_or(text).trim().to_string(),
+        _ => text.to_string(),
+    }
+}
+
+/// At most one blank line in a row and none at either end, outside
+/// code fences; fenced text is kept exactly.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = false;
+    let mut fence: Option<&str> = None;
+    for line in text.lines() {
+        let marker = line.trim_start();
+        let marker = &marker[..marker.len() - marker.trim_start_matches('`').len()];
+        match fence {
+            Some(open) if marker == open && line.trim() == open => fence = None,
+            Some(_) => {
+                out.push_str(line);
+                out.push('\n');
+                continue;
+            }
+            None if marker.len() >= 3 => fence = Some(marker),
+            None => {}
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank = !out.is_empty();
+            continue;
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// `explain_error` → `Explain error`, for answers with no menu label.
+pub fn humanize(action_id: &str) -> String {
+    let words = action_id.replace(['_', '-'], " ");
+    let mut chars = words.trim().chars();
+    chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::execute::ActionResultBody;
+
+    fn command_result() -> ActionResult {
+        ActionResult {
+            status: "success".into(),
+            action_id: "suggest_fix".into(),
+            result: ActionResultBody {
+                result_type: "command".into(),
+                text: Some("Install the missing module:".into()),
+                file_path: None,
+                command: Some("npm install left-pad".into()),
+                clipboard_content: None,
+                mime_type: None,
+                email: None,
+            },
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn default_template_renders_a_complete_document() {
+        let doc = SnipDoc {
+            id: 7,
+            summary: "Missing module error".into(),
+            created_at: 1_700_000_000,
+            content_type: "error".into(),
+            language: Some("javascript".into()),
+            text: "Error: Cannot find module 'left-pad'".into(),
+            image: Some("snip-7.png".into()),
+            results: vec![
+                ("Explain Error".into(), Answer::Text("The package isn't installed.\n".into())),
+                ("Suggest fix".into(), Answer::Result(Box::new(command_result()))),
+            ],
+            tags: vec!["node".into()],
+            notes: String::new(),
+        };
+        assert_eq!(
+            render(DEFAULT_TEMPLATE, &doc),
+            "# Missing module error\n\n_2023-11-14 22:13 UTC · error_ #node\n\n![Missing module error](snip-7.png)\n\n\
+             ```javascript\nError: Cannot find module 'left-pad'\n```\n\n## Explain Error\n\nThe package isn't installed.\n\n\
+             ## Suggest fix\n\nInstall the missing module:\n\n```sh\nnpm install left-pad\n```\n"
+        );
+    }
+
+    #[test]
+    fn custom_templates_and_awkward_text() {
+        let doc = SnipDoc { id: 3, text: "use ```rust fences```".into(), ..SnipDoc::default() };
+        assert_eq!(render("{title}: {image}{code} {unknown}", &doc), "Snip #3: ````\nuse ```rust fences```\n```` {unknown}\n");
+        let code = SnipDoc { text: "a = 1\n\n\nb = 2  ".into(), ..SnipDoc::default() };
+        assert_eq!(render("{code}\n\n\n{notes}", &code), "```\na = 1\n\n\nb = 2\n```\n");
+        assert_eq!(humanize("export_csv"), "Export csv");
+        assert!(ExportPrefs { markdown_template: " ".into() }.validate().is_err());
+        assert!(ExportPrefs::default().validate().is_ok());
+    }
+}
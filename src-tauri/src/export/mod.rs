@@ -0,0 +1,174 @@
+//! Export domain — tables to CSV, TSV, and XLSX without the LLM.
+//!
+//! When OCR text splits cleanly into rows and cells
+//! (`ocr::table_detect`), `export_csv` is answered here: the CSV is
+//! built in Rust, so quoting is always valid and nothing leaves the
+//! machine. Only text with no detectable structure goes to the LLM.
+//! `export_table` saves any CSV result (local or LLM) as CSV, TSV, or
+//! XLSX, picked by the file extension.
+//!
+//! `export_markdown` turns a history snip and its answers into a Markdown
+//! document (`markdown.rs`) for issues, wikis, or Obsidian.
+//!
+//! `save_result` saves a snip as PNG, text, CSV, or PDF (`pdf.rs`) through
+//! the native save dialog (`save.rs`); `save_snip_image` saves the crop
+//! of the snip in the action menu. Every path a user picks is checked
+//! by the path policy (`policy::check_save_path`).
+
+pub mod delimited;
+pub mod markdown;
+pub mod pdf;
+pub mod save;
+pub mod xlsx;
+
+use crate::error::{Export, OmniError};
+use crate::history::{self, store::SnipRecord};
+use crate::llm::execute::{ActionResult, ActionResultBody, ActionResultMetadata};
+use crate::llm::ActionMenuState;
+use crate::ocr::table_detect::{self, Table};
+use delimited::Format;
+use markdown::{Answer, SnipDoc};
+use std::path::Path;
+
+/// Actions that turn a snip into a spreadsheet file.
+pub const EXPORT_ACTIONS: &[&str] = &["export_csv", "export_to_csv", "extract_data"];
+
+/// Longest file name stem built from the header row.
+const MAX_STEM: usize = 40;
+
+/// The export result for `text`, built locally, or `None` when the
+/// action isn't an export or no table structure was found.
+pub fn local_result(action_id: &str, text: &str) -> Option<ActionResult> {
+    if !EXPORT_ACTIONS.contains(&action_id) {
+        return None;
+    }
+    let table = table_detect::detect(text)?;
+    log::info!("[EXPORT] Table detected locally: {} rows x {} columns", table.rows.len(), table.columns());
+    Some(ActionResult {
+        status: "success".to_string(),
+        action_id: action_id.to_string(),
+        result: ActionResultBody {
+            result_type: "file".to_string(),
+            text: Some(delimited::write(&table, ',')),
+            file_path: Some(format!("{}.csv", file_stem(&table))),
+            command: None,
+            clipboard_content: None,
+            mime_type: Some("text/csv".to_string()),
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata {
+            processing_note: Some(format!("Exported locally: {} rows, {} columns", table.rows.len(), table.columns())),
+            ..ActionResultMetadata::default()
+        }),
+    })
+}
+
+/// `region_q1_sales_export` from the first header cells.
+fn file_stem(table: &Table) -> String {
+    let header = table.rows[0].iter().take(3).map(String::as_str).collect::<Vec<_>>().join(" ");
+    let mut stem = String::new();
+    for c in header.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            stem.push(c);
+        } else if !stem.is_empty() && !stem.ends_with('_') {
+            stem.push('_');
+        }
+    }
+    let stem: String = stem.chars().take(MAX_STEM).collect();
+    match stem.trim_end_matches('_') {
+        "" => "table_export".to_string(),
+        stem => format!("{}_export", stem),
+    }
+}
+
+/// The snip's document parts, from its history record.
+fn snip_doc(snip_id: u64) -> Result<(SnipDoc, Option<u64>), String> {
+    history::with_records(|records| {
+        let record = records.iter().find(|r| r.id == snip_id).ok_or_else(|| crate::i18n::t("error.snipNotFound"))?;
+        let results = record
+            .answers
+            .iter()
+            .map(|a| {
+                let answer = a.result.clone().map_or_else(|| Answer::Text(a.text.clone()), |r| Answer::Result(Box::new(r)));
+                (answer_label(record, &a.action_id), answer)
+            })
+            .collect();
+        let doc = SnipDoc {
+            id: record.id,
+            summary: record.summary.clone(),
+            created_at: record.created_at,
+            content_type: record.content_type.clone(),
+            language: record.menu.as_ref().and_then(|m| m.detected_language.clone()),
+            text: record.text.clone(),
+            image: None,
+            results,
+            tags: record.tags.iter().cloned().collect(),
+            notes: record.notes.clone(),
+        };
+        Ok((doc, record.image_hash))
+    })
+}
+
+/// Heading for an answer: its menu label, else the humanized action id.
+fn answer_label(record: &SnipRecord, action_id: &str) -> String {
+    let from_menu = record.menu.as_ref().and_then(|m| m.actions.iter().find(|a| a.id == action_id));
+    from_menu.map_or_else(|| markdown::humanize(action_id), |a| a.label.clone())
+}
+
+/// The crop of the snip in the action menu, if it is this snip (history
+/// keeps no images, only their hash). Also used by share/.
+pub(crate) fn current_crop(state: &ActionMenuState, image_hash: Option<u64>) -> Option<Vec<u8>> {
+    let png = state.crop_png.lock().ok()?.clone()?;
+    let decoded = image::load_from_memory(&png).ok()?;
+    (Some(history::dedupe::image_hash(&decoded)) == image_hash).then_some(png)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: save a CSV result to a user-chosen path as CSV, TSV,
+/// or XLSX, by the path's extension. Returns the path written.
+#[tauri::command]
+pub fn export_table(file_path: String, content: String) -> Result<String, OmniError> {
+    crate::policy::check_save_path(&file_path).map_err(Export::PathRefused)?;
+    let format = Format::from_path(&file_path).ok_or_else(|| Export::PathRefused("Save as .csv, .tsv, or .xlsx".into()))?;
+    let table = delimited::parse_csv(&content).ok_or_else(|| Export::Nothing("The result has no table to export".into()))?;
+    let bytes = match format {
+        Format::Csv => delimited::write(&table, ',').into_bytes(),
+        Format::Tsv => delimited::write(&table, '\t').into_bytes(),
+        Format::Xlsx => xlsx::write(&table).map_err(Export::Write)?,
+    };
+    std::fs::write(&file_path, bytes).map_err(|e| Export::Write(e.to_string()))?;
+    log::info!("[EXPORT] Wrote {:?} table ({} rows): {}", format, table.rows.len(), file_path);
+    Ok(file_path)
+}
+
+/// Tauri command: a snip from history as Markdown — crop, OCR text in a
+/// fenced block, and its answers — laid out by `export.markdownTemplate`.
+/// With `file_path` the document is also written there, with the crop
+/// saved next to it; otherwise the crop is inlined as a data URI.
+#[tauri::command]
+pub fn export_markdown(
+    state: tauri::State<'_, ActionMenuState>,
+    snip_id: u64,
+    file_path: Option<String>,
+) -> Result<String, OmniError> {
+    let (mut doc, image_hash) = snip_doc(snip_id).map_err(Export::Nothing)?;
+    let png = current_crop(&state, image_hash);
+    let template = crate::settings::current().export.markdown_template;
+    let Some(file_path) = file_path else {
+        doc.image = png.map(|png| {
+            format!("data:image/png;base64,{}", base64::Engine::encode(&base64::engine::general_purpose::STANDARD, png))
+        });
+        return Ok(markdown::render(&template, &doc));
+    };
+    crate::policy::check_save_path(&file_path).map_err(Export::PathRefused)?;
+    if let Some(png) = png {
+        let image_path = Path::new(&file_path).with_extension("png");
+        std::fs::write(&image_path, png).map_err(|e| Export::Write(e.to_string()))?;
+        doc.image = image_path.file_name().map(|n| n.to_string_lossy().replace(' ', "%20"));
+    }
+    let document = markdown::render(&template, &doc);
+    std::fs::write(&file_path, &document).map_err(|e| Export::Write(e.to_string()))?;
+    log::info!("[EXPORT] Wrote Markdown for snip #{}: {}", snip_id, file_path);
+    Ok(document)
+}
@@ -0,0 +1,176 @@
+//! Minimal PDF writer: a title, an optional JPEG, and monospaced text
+//! (pure, no I/O).
+//!
+//! A4 pages with the standard Helvetica-Bold and Courier fonts, so nothing
+//! is embedded but the image. Text is WinAnsi: Latin-1 characters come
+//! through, anything else prints as `?`. Long lines wrap at the right
+//! margin and text flows onto as many pages as it needs.
+
+/// A JPEG to place under the title, stored as is (`/DCTDecode`).
+pub struct Jpeg {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const TITLE_SIZE: f32 = 16.0;
+const TEXT_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 11.0;
+const GAP: f32 = 14.0;
+/// Tallest the image may be drawn, in points.
+const MAX_IMAGE_HEIGHT: f32 = 400.0;
+/// Courier glyphs are 0.6 em wide: characters per line at `TEXT_SIZE`.
+const LINE_CHARS: usize = ((PAGE_WIDTH - 2.0 * MARGIN) / (TEXT_SIZE * 0.6)) as usize;
+
+pub fn write(title: &str, text: &str, image: Option<&Jpeg>) -> Vec<u8> {
+    let mut pages = Vec::new();
+    let mut y = PAGE_HEIGHT - MARGIN - TITLE_SIZE;
+    let mut content = format!("BT /F1 {} Tf {} {} Td ({}) Tj ET\n", TITLE_SIZE, MARGIN, y, escape(title));
+    y -= GAP;
+    if let Some(jpeg) = image {
+        let scale = ((PAGE_WIDTH - 2.0 * MARGIN) / jpeg.width as f32).min(MAX_IMAGE_HEIGHT / jpeg.height as f32).min(1.0);
+        let (w, h) = (jpeg.width as f32 * scale, jpeg.height as f32 * scale);
+        y -= h;
+        content.push_str(&format!("q {:.2} 0 0 {:.2} {} {:.2} cm /Im1 Do Q\n", w, h, MARGIN, y));
+        y -= GAP;
+    }
+    for line in wrap(text) {
+        if y - LINE_HEIGHT < MARGIN {
+            pages.push(std::mem::take(&mut content));
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= LINE_HEIGHT;
+        content.push_str(&format!("BT /F2 {} Tf {} {:.2} Td ({}) Tj ET\n", TEXT_SIZE, MARGIN, y, escape(&line)));
+    }
+    pages.push(content);
+    assemble(&pages, image)
+}
+
+/// Objects: 1 catalog, 2 page tree, 3–4 fonts, 5 the image (if any),
+/// then a page and its content stream per page.
+fn assemble(pages: &[String], image: Option<&Jpeg>) -> Vec<u8> {
+    let first_page = 5 + image.is_some() as usize;
+    let kids: Vec<String> = (0..pages.len()).map(|i| format!("{} 0 R", first_page + 2 * i)).collect();
+    let xobject = if image.is_some() { " /XObject << /Im1 5 0 R >>" } else { "" };
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len()).into_bytes(),
+        font("Helvetica-Bold"),
+        font("Courier"),
+    ];
+    if let Some(jpeg) = image {
+        let mut obj = format!(
+            "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 \
+             /Filter /DCTDecode /Length {} >>\nstream\n",
+            jpeg.width,
+            jpeg.height,
+            jpeg.data.len()
+        )
+        .into_bytes();
+        obj.extend_from_slice(&jpeg.data);
+        obj.extend_from_slice(b"\nendstream");
+        objects.push(obj);
+    }
+    for (i, content) in pages.iter().enumerate() {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Contents {} 0 R \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >>{} >> >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                first_page + 2 * i + 1,
+                xobject
+            )
+            .into_bytes(),
+        );
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content).into_bytes());
+    }
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, body) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        out.extend_from_slice(body);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).as_bytes(),
+    );
+    out
+}
+
+fn font(name: &str) -> Vec<u8> {
+    format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", name).into_bytes()
+}
+
+/// Text split into lines of at most `LINE_CHARS`, tabs as four spaces.
+fn wrap(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.replace('\t', "    ").lines() {
+        let chars: Vec<char> = line.trim_end().chars().collect();
+        if chars.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(chars.chunks(LINE_CHARS).map(|chunk| chunk.iter().collect::<String>()));
+    }
+    lines
+}
+
+/// A PDF string body: delimiters escaped, Latin-1 as octal escapes, the
+/// rest as `?`.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            '\u{a0}'..='\u{ff}' => out.push_str(&format!("\\{:03o}", c as u32)),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xref_points_at_every_object() {
+        let jpeg = Jpeg { width: 800, height: 200, data: vec![0xff, 0xd8, 0xff, 0xd9] };
+        let text = (0..150).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
+        let pdf = write("Quarterly (Q1) sales", &text, Some(&jpeg));
+        let raw = String::from_utf8_lossy(&pdf);
+        assert!(raw.starts_with("%PDF-1.4") && raw.ends_with("%%EOF\n"));
+        assert!(raw.contains("/Count 3 "));
+        assert!(raw.contains("(Quarterly \\(Q1\\) sales)"));
+        assert!(raw.contains("q 495.00 0 0 123.75 50 "));
+        let startxref: usize = raw.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        let xref = String::from_utf8_lossy(&pdf[startxref..]);
+        let entries: Vec<&str> = xref.lines().skip(3).take_while(|l| l.ends_with(" n ")).collect();
+        assert_eq!(entries.len(), 11);
+        for (i, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn text_is_escaped_and_wrapped() {
+        assert_eq!(escape("a\\b café ✓"), "a\\\\b caf\\351 ?");
+        let long = "x".repeat(LINE_CHARS + 5);
+        assert_eq!(wrap(&format!("\tA\n\n{}", long)), vec!["    A".to_string(), String::new(), "x".repeat(LINE_CHARS), "xxxxx".into()]);
+    }
+}
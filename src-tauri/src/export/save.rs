@@ -0,0 +1,153 @@
+//! Save a snip to a file through the native save dialog.
+//!
+//! The dialog opens with a name from the auto-save template
+//! (`capture::autosave::render_stem`). The content is built before the
+//! dialog opens, so a snip with nothing to save in that format fails
+//! without asking for a path. The chosen path is checked against the
+//! path policy (`policy::check_save_path`) and the file is listed on the
+//! snip in history. `save_snip_image` saves the current crop, for picture
+//! snips, which have no text for history to keep them by.
+
+use super::{answer_label, current_crop, delimited, pdf};
+use crate::capture::autosave;
+use crate::error::{Export, OmniError};
+use crate::history::{self, store::SnipRecord};
+use crate::i18n::t;
+use crate::llm::ActionMenuState;
+use crate::ocr::table_detect;
+use tauri_plugin_dialog::DialogExt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Image,
+    Text,
+    Csv,
+    Pdf,
+}
+
+impl Kind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "image" => Some(Self::Image),
+            "text" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+
+    /// (history kind, extension, dialog filter name)
+    fn describe(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Self::Image => ("image", "png", "PNG image"),
+            Self::Text => ("text", "txt", "Text"),
+            Self::Csv => ("csv", "csv", "CSV"),
+            Self::Pdf => ("pdf", "pdf", "PDF"),
+        }
+    }
+}
+
+/// The file's bytes for `kind`.
+fn content(kind: Kind, record: &SnipRecord, png: Option<Vec<u8>>) -> Result<Vec<u8>, String> {
+    match kind {
+        Kind::Image => png.ok_or_else(|| t("error.imageUnavailable")),
+        Kind::Text => Ok(record.text.clone().into_bytes()),
+        Kind::Csv => {
+            if let Some(table) = table_detect::detect(&record.text) {
+                return Ok(delimited::write(&table, ',').into_bytes());
+            }
+            // No structure in the text: the newest CSV an action produced.
+            record
+                .answers
+                .iter()
+                .rev()
+                .filter_map(|a| a.result.as_ref())
+                .find(|r| r.result.file_path.as_deref().is_some_and(|p| p.to_lowercase().ends_with(".csv")))
+                .and_then(|r| r.result.text.clone())
+                .map(String::into_bytes)
+                .ok_or_else(|| t("error.noTable"))
+        }
+        Kind::Pdf => {
+            let title = match record.summary.trim() {
+                "" => format!("Snip #{}", record.id),
+                summary => summary.to_string(),
+            };
+            let mut text = record.text.trim_end().to_string();
+            for answer in &record.answers {
+                text.push_str(&format!("\n\n== {} ==\n{}", answer_label(record, &answer.action_id), answer.text.trim()));
+            }
+            let jpeg = png.as_deref().and_then(to_jpeg);
+            Ok(pdf::write(&title, &text, jpeg.as_ref()))
+        }
+    }
+}
+
+/// Re-encode the crop as JPEG, which PDF can hold without decoding.
+fn to_jpeg(png: &[u8]) -> Option<pdf::Jpeg> {
+    let rgb = image::load_from_memory(png).ok()?.to_rgb8();
+    let mut data = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut data, 85).encode_image(&rgb).ok()?;
+    Some(pdf::Jpeg { width: rgb.width(), height: rgb.height(), data })
+}
+
+/// Ask where to save; `None` if the user cancelled.
+async fn pick_path(app: &tauri::AppHandle, file_name: &str, kind: Kind) -> Option<String> {
+    let (_, extension, filter) = kind.describe();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog().file().set_file_name(file_name).add_filter(filter, &[extension]).save_file(move |path| {
+        let _ = tx.send(path);
+    });
+    rx.await.ok().flatten().map(|path| path.to_string())
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: save a history snip as `image` (the crop, PNG), `text`
+/// (OCR text), `csv` (its table), or `pdf` (crop, text, and answers).
+/// Returns the path written, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn save_result(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ActionMenuState>,
+    snip_id: u64,
+    kind: String,
+) -> Result<Option<String>, OmniError> {
+    let kind = Kind::parse(&kind).ok_or_else(|| format!("Unknown save format '{}'", kind))?;
+    let record = history::with_records(|records| records.iter().find(|r| r.id == snip_id).cloned())
+        .ok_or_else(|| Export::Nothing(t("error.snipNotFound")))?;
+    let bytes = content(kind, &record, current_crop(&state, record.image_hash)).map_err(Export::Nothing)?;
+
+    let (kind_name, extension, _) = kind.describe();
+    let stem = autosave::render_stem(&autosave::current().template, record.created_at, &record.content_type, &record.summary);
+    let Some(chosen) = pick_path(&app, &format!("{}.{}", stem, extension), kind).await else {
+        return Ok(None);
+    };
+    let path = crate::policy::check_save_path(&chosen).map_err(Export::PathRefused)?;
+    std::fs::write(&path, bytes).map_err(|e| Export::Write(e.to_string()))?;
+    let path = path.display().to_string();
+    log::info!("[EXPORT] Saved snip #{} as {}: {}", snip_id, kind_name, path);
+    history::record_export(snip_id, kind_name, &path);
+    Ok(Some(path))
+}
+
+/// Tauri command: save the current snip's crop as PNG (Save Image on a
+/// picture snip). Returns the path written, or `None` if cancelled.
+#[tauri::command]
+pub async fn save_snip_image(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ActionMenuState>,
+) -> Result<Option<String>, OmniError> {
+    let png = state.crop_png.lock().map_err(|e| e.to_string())?.clone().ok_or_else(|| Export::Nothing(t("error.imageUnavailable")))?;
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+    let menu = state.menu.lock().map_err(|e| e.to_string())?.clone();
+    let (content_type, summary) = menu.map_or_else(|| ("image".to_string(), String::new()), |m| (m.content_type, m.summary));
+    let stem = autosave::render_stem(&autosave::current().template, now, &content_type, &summary);
+    let Some(chosen) = pick_path(&app, &format!("{}.png", stem), Kind::Image).await else {
+        return Ok(None);
+    };
+    let path = crate::policy::check_save_path(&chosen).map_err(Export::PathRefused)?;
+    std::fs::write(&path, png).map_err(|e| Export::Write(e.to_string()))?;
+    let path = path.display().to_string();
+    log::info!("[EXPORT] Saved snip image: {}", path);
+    Ok(Some(path))
+}
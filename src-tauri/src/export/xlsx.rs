@@ -0,0 +1,120 @@
+//! Minimal XLSX writer: one sheet, inline strings, numbers as numbers.
+//!
+//! An XLSX file is a zip of a few XML parts. Writing them directly keeps
+//! a spreadsheet crate out of the build; there are no styles, so the
+//! header row is the first row as is.
+
+use super::delimited::{decimal_separator, parse_number};
+use crate::ocr::table_detect::Table;
+use std::io::Write;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+
+/// The workbook file for `table`.
+pub fn write(table: &Table) -> Result<Vec<u8>, String> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let sheet = sheet_xml(table);
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES),
+        ("_rels/.rels", ROOT_RELS),
+        ("xl/workbook.xml", WORKBOOK),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS),
+        ("xl/worksheets/sheet1.xml", sheet.as_str()),
+    ];
+    for (name, body) in parts {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(body.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+}
+
+/// The worksheet part. Cells that parse as numbers (with the table's
+/// decimal separator) are numeric; the header row is always text.
+fn sheet_xml(table: &Table) -> String {
+    let decimal = decimal_separator(table);
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (r, row) in table.rows.iter().enumerate() {
+        xml.push_str(&format!("<row r=\"{}\">", r + 1));
+        for (c, cell) in row.iter().enumerate().filter(|(_, cell)| !cell.is_empty()) {
+            let reference = format!("{}{}", column_name(c), r + 1);
+            match parse_number(cell, decimal).filter(|_| r > 0) {
+                Some(n) => xml.push_str(&format!("<c r=\"{}\"><v>{}</v></c>", reference, n)),
+                None => xml.push_str(&format!(
+                    "<c r=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                    reference,
+                    escape_xml(cell)
+                )),
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+/// `A`, `B`, … `Z`, `AA`, … for a 0-based column index.
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Escape markup and drop control characters XML can't hold.
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .fold(String::with_capacity(text.len()), |mut out, c| {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                _ => out.push(c),
+            }
+            out
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_are_typed_and_addressed() {
+        assert_eq!([0, 25, 26, 701, 702].map(column_name), ["A", "Z", "AA", "ZZ", "AAA"]);
+        let table = Table {
+            rows: vec![
+                vec!["Item".into(), "2024".into()],
+                vec!["A & B <x>".into(), "1.234,5".into()],
+                vec!["Zip".into(), "0042".into()],
+            ],
+        };
+        let xml = sheet_xml(&table);
+        assert!(xml.contains(r#"<c r="B1" t="inlineStr"><is><t xml:space="preserve">2024</t></is></c>"#));
+        assert!(xml.contains(r#"<c r="A2" t="inlineStr"><is><t xml:space="preserve">A &amp; B &lt;x&gt;</t></is></c>"#));
+        assert!(xml.contains(r#"<c r="B2"><v>1234.5</v></c>"#));
+        assert!(xml.contains(r#"<c r="B3" t="inlineStr"><is><t xml:space="preserve">0042</t></is></c>"#));
+        assert!(write(&table).unwrap().starts_with(b"PK"));
+    }
+}
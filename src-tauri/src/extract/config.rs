@@ -0,0 +1,70 @@
+//! Saved extraction recipes (`extract.recipes`) (pure, no I/O).
+//!
+//! A recipe is a named pattern the workbench can run again on the next
+//! snip — the ID format of a log, an order number, an error code.
+
+use super::pattern;
+use serde::{Deserialize, Serialize};
+
+const MAX_RECIPES: usize = 50;
+const MAX_NAME_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractPrefs {
+    #[serde(default)]
+    pub recipes: Vec<Recipe>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Recipe {
+    pub name: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+impl ExtractPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.recipes.len() > MAX_RECIPES {
+            return Err(format!("At most {} extraction recipes", MAX_RECIPES));
+        }
+        for (i, recipe) in self.recipes.iter().enumerate() {
+            let name = recipe.name.trim();
+            if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+                return Err(format!("Recipe names must be 1-{} characters", MAX_NAME_CHARS));
+            }
+            if self.recipes[..i].iter().any(|r| r.name.trim().eq_ignore_ascii_case(name)) {
+                return Err(format!("Duplicate recipe name '{}'", name));
+            }
+            pattern::compile(&recipe.pattern, recipe.case_insensitive).map_err(|e| format!("Recipe '{}': {}", name, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(name: &str, pattern: &str) -> Recipe {
+        serde_json::from_value(serde_json::json!({ "name": name, "pattern": pattern })).unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        let prefs: ExtractPrefs = serde_json::from_str("{}").unwrap();
+        assert!(prefs.recipes.is_empty() && prefs.validate().is_ok());
+        assert!(!recipe("Ticket", r"[A-Z]+-\d+").case_insensitive);
+    }
+
+    #[test]
+    fn validation() {
+        let prefs = |recipes: Vec<Recipe>| ExtractPrefs { recipes }.validate();
+        assert!(prefs(vec![recipe("Ticket", r"[A-Z]+-\d+"), recipe("Request id", r"req=(\w+)")]).is_ok());
+        assert!(prefs(vec![recipe("Ticket", "a"), recipe("ticket", "b")]).is_err());
+        assert!(prefs(vec![recipe(" ", "a")]).is_err());
+        assert!(prefs(vec![recipe("Broken", "(a")]).is_err());
+    }
+}
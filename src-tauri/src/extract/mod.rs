@@ -0,0 +1,132 @@
+//! Extraction workbench — pull every match of a regex out of a snip.
+//!
+//! The action menu's workbench (`extract_regex`) takes a pattern the
+//! user types, picks from their saved recipes (`extract.recipes`,
+//! `config.rs`), or asks the LLM to write from a description. The
+//! pattern always runs here in Rust (`pattern.rs`), so matches and
+//! capture groups are exact and instant, and rerunning a recipe on the
+//! next log screenshot never calls the LLM. Matches export as CSV through
+//! the usual file-result save flow.
+
+pub mod config;
+pub mod pattern;
+
+use crate::error::{Export, OmniError};
+use crate::llm::execute::{ActionResultBody, ActionResultMetadata};
+use crate::llm::{self, ActionResult};
+use pattern::Extraction;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Action id, handled by the action menu through the commands below.
+pub const WORKBENCH_ACTION: &str = "extract_regex";
+
+/// The fields the LLM is asked for when writing a pattern.
+static PATTERN_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(|| {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "pattern": {
+                "type": "string",
+                "minLength": 1,
+                "maxLength": pattern::MAX_PATTERN_CHARS,
+                "description": "Rust regex syntax (no lookaround, no backreferences); named groups (?P<name>...) for parts worth their own column"
+            },
+            "caseInsensitive": { "type": "boolean" },
+            "explanation": { "type": "string", "description": "One short sentence on what the pattern matches" }
+        },
+        "required": ["pattern"],
+        "additionalProperties": false
+    })
+});
+
+/// A pattern written by the LLM.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestedPattern {
+    pub pattern: String,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    #[serde(default)]
+    pub explanation: String,
+}
+
+fn snip_text(state: &llm::ActionMenuState) -> Result<String, String> {
+    let guard = state.ocr_text.lock().map_err(|e| e.to_string())?;
+    guard.clone().ok_or_else(|| "No OCR text available — snip first".to_string())
+}
+
+fn extract(state: &llm::ActionMenuState, pattern: &str, case_insensitive: bool) -> Result<Extraction, String> {
+    let re = pattern::compile(pattern, case_insensitive)?;
+    Ok(pattern::run(&re, &snip_text(state)?))
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: matches and capture groups of `pattern` in the snip.
+#[tauri::command]
+pub fn run_extraction(
+    state: tauri::State<'_, llm::ActionMenuState>,
+    pattern: String,
+    case_insensitive: bool,
+) -> Result<Extraction, String> {
+    let found = extract(&state, &pattern, case_insensitive)?;
+    log::info!("[EXTRACT] {} matches, {} columns", found.rows.len(), found.columns.len());
+    Ok(found)
+}
+
+/// Tauri command: the matches as a CSV file result, for the save dialog.
+#[tauri::command]
+pub fn export_extraction(
+    state: tauri::State<'_, llm::ActionMenuState>,
+    pattern: String,
+    case_insensitive: bool,
+) -> Result<ActionResult, OmniError> {
+    let found = extract(&state, &pattern, case_insensitive)?;
+    if found.rows.is_empty() {
+        return Err(Export::Nothing("Nothing matched".to_string()).into());
+    }
+    Ok(ActionResult {
+        status: "success".to_string(),
+        action_id: WORKBENCH_ACTION.to_string(),
+        result: ActionResultBody {
+            result_type: "file".to_string(),
+            text: Some(crate::export::delimited::write(&found.to_table(), ',')),
+            file_path: Some("matches.csv".to_string()),
+            command: None,
+            clipboard_content: None,
+            mime_type: Some("text/csv".to_string()),
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata {
+            processing_note: Some(format!("Extracted locally: {} matches", found.rows.len())),
+            ..ActionResultMetadata::default()
+        }),
+    })
+}
+
+/// Tauri command: ask the LLM for a pattern that pulls `request` out of
+/// the snip. The pattern is checked to compile before it is returned.
+#[tauri::command]
+pub async fn suggest_pattern(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, llm::ActionMenuState>,
+    request: String,
+) -> Result<SuggestedPattern, String> {
+    let request = request.trim();
+    if request.is_empty() {
+        return Err("Describe what to extract".to_string());
+    }
+    let text = snip_text(&state)?;
+    let chars = text.chars().count() + request.chars().count();
+    crate::safety::outbound_guard::guard_outbound(&app, "execute", chars).await?;
+    let description = format!(
+        "Write one regular expression that extracts the following from the text, matching every occurrence: {}",
+        request
+    );
+    let fields = llm::plugin_args::generate_plugin_args(WORKBENCH_ACTION, &description, &PATTERN_SCHEMA, &text).await?;
+    let suggested: SuggestedPattern = serde_json::from_value(fields).map_err(|e| e.to_string())?;
+    pattern::compile(&suggested.pattern, suggested.case_insensitive)?;
+    log::info!("[EXTRACT] Suggested pattern: {}", suggested.pattern);
+    Ok(suggested)
+}
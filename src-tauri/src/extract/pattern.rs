@@ -0,0 +1,93 @@
+//! Applying a user's regex to snip text (pure).
+//!
+//! Each match becomes a row: the whole match, then one cell per capture
+//! group, named after the group (`(?P<id>…)`) or numbered. Patterns are
+//! compiled with a size cap, the same guard plugin trigger patterns get,
+//! and at most `MAX_MATCHES` rows are kept.
+
+use crate::ocr::table_detect::Table;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+pub const MAX_PATTERN_CHARS: usize = 500;
+const MAX_MATCHES: usize = 1000;
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Extraction {
+    /// `match`, then one name per capture group.
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// More than `MAX_MATCHES` matched; the rest were dropped.
+    pub truncated: bool,
+}
+
+impl Extraction {
+    /// The rows under a header row, for CSV export.
+    pub fn to_table(&self) -> Table {
+        Table { rows: std::iter::once(self.columns.clone()).chain(self.rows.iter().cloned()).collect() }
+    }
+}
+
+pub fn compile(pattern: &str, case_insensitive: bool) -> Result<Regex, String> {
+    if pattern.is_empty() {
+        return Err("Enter a pattern".to_string());
+    }
+    if pattern.chars().count() > MAX_PATTERN_CHARS {
+        return Err(format!("Pattern longer than {} characters", MAX_PATTERN_CHARS));
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .multi_line(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| format!("Invalid pattern: {}", e))
+}
+
+/// Every non-empty match of `re` in `text`.
+pub fn run(re: &Regex, text: &str) -> Extraction {
+    let columns = std::iter::once("match".to_string())
+        .chain(re.capture_names().enumerate().skip(1).map(|(i, name)| name.map_or_else(|| i.to_string(), String::from)))
+        .collect();
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    for caps in re.captures_iter(text).filter(|c| !c[0].is_empty()) {
+        if rows.len() == MAX_MATCHES {
+            truncated = true;
+            break;
+        }
+        rows.push(caps.iter().map(|m| m.map_or_else(String::new, |m| m.as_str().to_string())).collect());
+    }
+    Extraction { columns, rows, truncated }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "12:00 ERROR req=ab12 user=7\n12:01 INFO req=cd34\n12:02 error req=ef56 user=9";
+
+    #[test]
+    fn rows_and_named_columns() {
+        let re = compile(r"(?P<level>ERROR|INFO) req=(\w+)(?: user=(\d+))?", false).unwrap();
+        let found = run(&re, LOG);
+        assert_eq!(found.columns, vec!["match", "level", "2", "3"]);
+        assert_eq!(found.rows.len(), 2);
+        assert_eq!(found.rows[1], vec!["INFO req=cd34", "INFO", "cd34", ""]);
+        let ci = run(&compile(r"^\S+ error", true).unwrap(), LOG);
+        assert_eq!(ci.rows.len(), 2);
+        assert_eq!(found.to_table().rows[0], found.columns);
+    }
+
+    #[test]
+    fn limits() {
+        assert!(compile("", false).is_err());
+        assert!(compile("(unclosed", false).is_err());
+        assert!(compile(&"a".repeat(MAX_PATTERN_CHARS + 1), false).is_err());
+        let many = "x ".repeat(MAX_MATCHES + 5);
+        let found = run(&compile("x", false).unwrap(), &many);
+        assert!(found.truncated && found.rows.len() == MAX_MATCHES);
+        assert!(run(&compile("y*", false).unwrap(), "abc").rows.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! Pins, tags, and notes on history snips (pure, no I/O).
+//!
+//! Tags are normalized so `#API`, `api` and ` Api ` are the same tag:
+//! trimmed, leading `#` dropped, lowercased, inner spaces turned into
+//! `-`. Tags and notes are indexed with the snip, so searching for a tag
+//! or something written in a note finds it.
+
+use super::store::{HistoryStore, SnipRecord};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Tags per snip.
+const MAX_TAGS: usize = 10;
+/// Characters per tag.
+const MAX_TAG_CHARS: usize = 32;
+/// Characters per note.
+const MAX_NOTES_CHARS: usize = 4000;
+
+/// Normalize a user tag, or `None` if nothing usable is left.
+pub fn normalize_tag(raw: &str) -> Option<String> {
+    let tag = raw.trim().trim_start_matches('#').trim().to_lowercase();
+    let tag: String = tag.split_whitespace().collect::<Vec<_>>().join("-");
+    let valid = tag.chars().all(|c| c.is_alphanumeric() || "-_./".contains(c));
+    (valid && !tag.is_empty() && tag.chars().count() <= MAX_TAG_CHARS).then_some(tag)
+}
+
+impl HistoryStore {
+    fn record_mut(&mut self, id: u64) -> Result<&mut SnipRecord, String> {
+        self.records.iter_mut().find(|r| r.id == id).ok_or_else(|| format!("No snip #{} in history", id))
+    }
+
+    pub fn set_pinned(&mut self, id: u64, pinned: bool) -> Result<(), String> {
+        self.record_mut(id)?.pinned = pinned;
+        Ok(())
+    }
+
+    /// Replace a snip's tags; returns them normalized.
+    pub fn set_tags(&mut self, id: u64, raw: &[String]) -> Result<BTreeSet<String>, String> {
+        let mut tags = BTreeSet::new();
+        for t in raw {
+            tags.insert(normalize_tag(t).ok_or_else(|| format!("Invalid tag '{}'", t))?);
+        }
+        if tags.len() > MAX_TAGS {
+            return Err(format!("A snip can have at most {} tags", MAX_TAGS));
+        }
+        self.record_mut(id)?.tags = tags.clone();
+        self.reindex(id);
+        Ok(tags)
+    }
+
+    pub fn set_notes(&mut self, id: u64, notes: &str) -> Result<(), String> {
+        if notes.chars().count() > MAX_NOTES_CHARS {
+            return Err(format!("Notes are limited to {} characters", MAX_NOTES_CHARS));
+        }
+        self.record_mut(id)?.notes = notes.trim().to_string();
+        self.reindex(id);
+        Ok(())
+    }
+
+    /// Every tag in use (in `workspace`, or anywhere) with its snip count.
+    pub fn tag_counts(&self, workspace: Option<&str>) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for tag in self.records.iter().filter(|r| r.in_scope(workspace)).flat_map(|r| &r.tags) {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_normalized() {
+        assert_eq!(normalize_tag(" #API "), Some("api".to_string()));
+        assert_eq!(normalize_tag("Prod  Config"), Some("prod-config".to_string()));
+        assert_eq!(normalize_tag("#"), None);
+        assert_eq!(normalize_tag("a,b"), None);
+        assert_eq!(normalize_tag(&"x".repeat(MAX_TAG_CHARS + 1)), None);
+    }
+
+    #[test]
+    fn pinned_and_tagged_snips_are_listed_and_found() {
+        let mut store = HistoryStore::default();
+        let endpoint = store.add_snip("POST /v2/orders/{id}/refund", None, None, "default", 1);
+        let other = store.add_snip("Lunch menu", None, None, "default", 2);
+        store.set_pinned(endpoint, true).unwrap();
+        let tags = store.set_tags(endpoint, &["#API".into(), "billing".into()]).unwrap();
+        assert_eq!(tags.into_iter().collect::<Vec<_>>(), vec!["api", "billing"]);
+        store.set_notes(endpoint, "  use the idempotency key  ").unwrap();
+
+        assert_eq!(store.list(None, None, false, 10)[0].id, endpoint);
+        assert_eq!(store.list(None, None, false, 10)[1].id, other);
+        assert_eq!(store.list(None, Some("api"), false, 10).len(), 1);
+        assert!(store.list(None, Some("nope"), false, 10).is_empty());
+        assert_eq!(store.list(None, None, true, 10).len(), 1);
+        assert_eq!(store.tag_counts(Some("default")).get("billing"), Some(&1));
+        assert_eq!(store.search("idempotency key", 1, None)[0].record.id, endpoint);
+
+        assert!(store.set_tags(endpoint, &["bad,tag".into()]).is_err());
+        assert!(store.set_pinned(999, true).is_err());
+    }
+}
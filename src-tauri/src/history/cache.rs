@@ -0,0 +1,95 @@
+//! Action-result cache keyed on (snip, action) (pure, no I/O).
+//!
+//! Each stored answer keeps the full `ActionResult` it came from, so
+//! running the same action on the same snip again (same workspace, text
+//! equal after normalization) returns it instantly instead of calling
+//! the LLM. Regenerating invalidates the cached results for that pair
+//! before running fresh. Command results are never served from the
+//! cache: policy and the command blocklist must see every run.
+
+use super::dedupe::normalize_text;
+use super::store::HistoryStore;
+use crate::llm::execute::ActionResult;
+
+/// Result types that are safe to serve again.
+const CACHEABLE_TYPES: &[&str] = &["text", "file", "clipboard", "email"];
+
+impl HistoryStore {
+    /// Newest cached result of `action_id` on a snip with this text,
+    /// marked with when it was produced.
+    pub fn cached_result(&self, text: &str, action_id: &str, workspace: &str) -> Option<ActionResult> {
+        let normalized = normalize_text(text);
+        let answer = self
+            .records
+            .iter()
+            .rev()
+            .filter(|r| r.workspace == workspace && normalize_text(&r.text) == normalized)
+            .flat_map(|r| r.answers.iter().rev())
+            .find(|a| a.action_id == action_id && a.result.is_some())?;
+        let mut result = answer.result.clone()?;
+        if result.status != "success" || !CACHEABLE_TYPES.contains(&result.result.result_type.as_str()) {
+            return None;
+        }
+        let metadata = result.metadata.get_or_insert_with(Default::default);
+        metadata.cached_at = Some(answer.created_at);
+        Some(result)
+    }
+
+    /// Drop the cached results of `action_id` on snips with this text;
+    /// the answers stay in history. Returns how many were dropped.
+    pub fn invalidate_results(&mut self, text: &str, action_id: &str, workspace: &str) -> usize {
+        let normalized = normalize_text(text);
+        let mut dropped = 0;
+        for record in self.records.iter_mut().filter(|r| r.workspace == workspace && normalize_text(&r.text) == normalized) {
+            for answer in record.answers.iter_mut().filter(|a| a.action_id == action_id && a.result.is_some()) {
+                answer.result = None;
+                dropped += 1;
+            }
+        }
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::execute::ActionResultBody;
+
+    fn result(action_id: &str, result_type: &str, text: &str) -> ActionResult {
+        ActionResult {
+            status: "success".to_string(),
+            action_id: action_id.to_string(),
+            result: ActionResultBody {
+                result_type: result_type.to_string(),
+                text: Some(text.to_string()),
+                file_path: None,
+                command: None,
+                clipboard_content: None,
+                mime_type: None,
+                email: None,
+            },
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn cached_results_are_keyed_on_snip_and_action() {
+        let mut store = HistoryStore::default();
+        let snip = "TypeError: x is undefined";
+        store.add_snip(snip, None, None, "default", 1);
+        store.add_answer(snip, "explain_error", "first", Some(result("explain_error", "text", "first")), 2).unwrap();
+        store.add_answer(snip, "explain_error", "second", Some(result("explain_error", "text", "second")), 3).unwrap();
+        store.add_answer(snip, "suggest_fix", "rm -rf", Some(result("suggest_fix", "command", "rm -rf")), 4).unwrap();
+
+        let hit = store.cached_result("typeerror:  x is UNDEFINED", "explain_error", "default").unwrap();
+        assert_eq!(hit.result.text.as_deref(), Some("second"));
+        assert_eq!(hit.metadata.unwrap().cached_at, Some(3));
+        assert!(store.cached_result(snip, "explain_error", "Acme").is_none());
+        assert!(store.cached_result(snip, "suggest_fix", "default").is_none());
+        assert!(store.cached_result("other text", "explain_error", "default").is_none());
+
+        assert_eq!(store.invalidate_results(snip, "explain_error", "default"), 2);
+        assert!(store.cached_result(snip, "explain_error", "default").is_none());
+        assert_eq!(store.list(None, None, false, 1)[0].answers.len(), 3);
+    }
+}
@@ -0,0 +1,71 @@
+//! Tauri commands for the history panel and launcher: list, search,
+//! pin, tag, and annotate snips.
+
+use super::store::{SearchHit, SnipRecord};
+use super::{annotate, update, with_store, workspace};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Upper bound on `k` / `limit` from the frontend.
+const MAX_RESULTS: usize = 50;
+
+/// Workspace a query covers: the active one by default, every
+/// workspace for `"*"`.
+fn scope(workspace: Option<String>) -> Option<String> {
+    match workspace.as_deref() {
+        None => Some(workspace::active()),
+        Some("*") => None,
+        Some(_) => workspace,
+    }
+}
+
+/// Tauri command: snips, pinned first then newest. `tag` and
+/// `pinned_only` narrow the list (launcher favorites, tag views).
+#[tauri::command]
+pub fn get_snip_history(
+    limit: Option<usize>,
+    tag: Option<String>,
+    pinned_only: Option<bool>,
+    workspace: Option<String>,
+) -> Vec<SnipRecord> {
+    let tag = tag.as_deref().and_then(annotate::normalize_tag);
+    let limit = limit.unwrap_or(MAX_RESULTS).min(MAX_RESULTS);
+    let scope = scope(workspace);
+    with_store(|store| store.list(scope.as_deref(), tag.as_deref(), pinned_only.unwrap_or(false), limit))
+}
+
+/// Tauri command: fuzzy lexical search — the `k` snips sharing the most
+/// words and word fragments with `query`, typos and OCR slips included.
+/// Synonyms don't match: there is no language model behind it.
+#[tauri::command]
+pub fn fuzzy_search_history(query: String, k: usize, workspace: Option<String>) -> Vec<SearchHit> {
+    let k = k.clamp(1, MAX_RESULTS);
+    let scope = scope(workspace);
+    let hits = with_store(|store| store.search(&query, k, scope.as_deref()));
+    log::info!("[HISTORY] Search returned {} hits", hits.len());
+    hits
+}
+
+/// Tauri command: pin or unpin a snip.
+#[tauri::command]
+pub fn set_snip_pinned(id: u64, pinned: bool) -> Result<(), String> {
+    update(|store| store.set_pinned(id, pinned))
+}
+
+/// Tauri command: replace a snip's tags; returns them normalized.
+#[tauri::command]
+pub fn set_snip_tags(id: u64, tags: Vec<String>) -> Result<BTreeSet<String>, String> {
+    update(|store| store.set_tags(id, &tags))
+}
+
+/// Tauri command: set a snip's free-form notes.
+#[tauri::command]
+pub fn set_snip_notes(id: u64, notes: String) -> Result<(), String> {
+    update(|store| store.set_notes(id, &notes))
+}
+
+/// Tauri command: tags in use with their snip counts.
+#[tauri::command]
+pub fn get_snip_tags(workspace: Option<String>) -> BTreeMap<String, usize> {
+    let scope = scope(workspace);
+    with_store(|store| store.tag_counts(scope.as_deref()))
+}
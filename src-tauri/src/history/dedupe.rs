@@ -0,0 +1,106 @@
+//! Duplicate-snip detection (pure, no I/O).
+//!
+//! A new snip is a duplicate of a recent one when both signals agree:
+//! its perceptual hash (dHash of the crop) is within `MAX_HASH_DISTANCE`
+//! bits, and its OCR text is the same once case and whitespace are
+//! normalized. The hash tolerates re-snipping a few pixels off; the text
+//! check keeps a dashboard whose numbers changed from counting as the
+//! same snip. A duplicate reuses the earlier classification, and its
+//! earlier answers are offered instead of calling the LLM again.
+
+use super::store::{HistoryStore, SnipRecord};
+use image::DynamicImage;
+
+/// How far back a snip counts as "recent".
+const DUPLICATE_WINDOW_SECS: u64 = 24 * 60 * 60;
+/// Differing hash bits (of 64) still treated as the same image.
+const MAX_HASH_DISTANCE: u32 = 6;
+
+/// 64-bit difference hash: shrink to 9×8 grayscale, one bit per
+/// horizontally adjacent pixel pair (left brighter than right).
+pub fn image_hash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, image::imageops::FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let bit = small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(bit);
+        }
+    }
+    hash
+}
+
+pub fn hamming(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Lowercase with whitespace runs collapsed — OCR line breaks and
+/// spacing vary between otherwise identical snips.
+pub fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+impl HistoryStore {
+    /// Newest recent snip in `workspace` matching `text` (already
+    /// redacted) and `hash` that still has a reusable classification.
+    pub fn find_duplicate(&self, text: &str, hash: u64, workspace: &str, now: u64) -> Option<&SnipRecord> {
+        let normalized = normalize_text(text);
+        if normalized.is_empty() {
+            return None;
+        }
+        self.records
+            .iter()
+            .rev()
+            .take_while(|r| now.saturating_sub(r.created_at) <= DUPLICATE_WINDOW_SECS)
+            .filter(|r| r.menu.is_some() && r.workspace == workspace)
+            .filter(|r| r.image_hash.is_some_and(|h| hamming(h, hash) <= MAX_HASH_DISTANCE))
+            .find(|r| normalize_text(&r.text) == normalized)
+    }
+
+    /// Newest snip in `workspace` with this text (normalized) that has
+    /// an answer.
+    pub fn latest_answered(&self, text: &str, workspace: &str) -> Option<&SnipRecord> {
+        let normalized = normalize_text(text);
+        self.records
+            .iter()
+            .rev()
+            .find(|r| !r.answers.is_empty() && r.workspace == workspace && normalize_text(&r.text) == normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::types::ActionMenu;
+    use image::{Rgb, RgbImage};
+
+    fn stripes(offset: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(180, 80, |x, _| {
+            if ((x + offset) / 20) & 1 == 0 { Rgb([240, 240, 240]) } else { Rgb([20, 20, 20]) }
+        }))
+    }
+
+    #[test]
+    fn hash_tolerates_small_shifts_only() {
+        let base = image_hash(&stripes(0));
+        assert!(hamming(base, image_hash(&stripes(1))) <= MAX_HASH_DISTANCE);
+        assert!(hamming(base, image_hash(&stripes(20))) > MAX_HASH_DISTANCE);
+    }
+
+    #[test]
+    fn duplicates_need_matching_text_hash_and_recency() {
+        let mut store = HistoryStore::default();
+        let hash = image_hash(&stripes(0));
+        let id = store.add_snip("CPU  87%\nMemory 4.1 GB", Some(&ActionMenu::fallback()), Some(hash), "default", 1_000);
+
+        assert_eq!(store.find_duplicate("cpu 87% memory 4.1 gb", hash ^ 0b11, "default", 2_000).map(|r| r.id), Some(id));
+        assert!(store.find_duplicate("CPU 91% Memory 4.1 GB", hash, "default", 2_000).is_none());
+        assert!(store.find_duplicate("CPU 87% Memory 4.1 GB", !hash, "default", 2_000).is_none());
+        assert!(store.find_duplicate("CPU 87% Memory 4.1 GB", hash, "default", 1_000 + DUPLICATE_WINDOW_SECS + 1).is_none());
+        assert!(store.find_duplicate("CPU 87% Memory 4.1 GB", hash, "Acme", 2_000).is_none());
+
+        assert!(store.latest_answered("CPU 87% Memory 4.1 GB", "default").is_none());
+        store.add_answer("CPU  87%\nMemory 4.1 GB", "explain", "Normal load", None, 1_500).unwrap();
+        assert_eq!(store.latest_answered("cpu 87% memory 4.1 gb", "default").map(|r| r.id), Some(id));
+    }
+}
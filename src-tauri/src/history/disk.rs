@@ -0,0 +1,85 @@
+//! Writing the history store to disk, off the hot path.
+//!
+//! The file holds up to 1,000 snips of up to 20,000 characters each, so a
+//! write can take a while. Changes only mark the store dirty and wake a
+//! writer thread; it waits `SAVE_DELAY` so a burst (a snip, then its
+//! answers) becomes one write, serializes under the store lock, and
+//! writes the file after releasing it. `flush` writes at once (quit,
+//! update restart); `erase` shreds the file for a purge.
+
+use super::store::HistoryStore;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// How long the writer waits for more changes before writing.
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Set after a change; cleared when the store is serialized for writing.
+static DIRTY: AtomicBool = AtomicBool::new(false);
+/// Held from serializing to the end of the write, and by `erase`, so an
+/// older snapshot can never land on disk after a purge.
+static WRITE: Mutex<()> = Mutex::new(());
+static WAKE: OnceLock<mpsc::Sender<()>> = OnceLock::new();
+
+/// Note a change to the store; it is written within `SAVE_DELAY`. Call
+/// after releasing the store lock.
+pub(super) fn schedule_save() {
+    DIRTY.store(true, Ordering::SeqCst);
+    let wake = WAKE.get_or_init(|| {
+        let (wake, woken) = mpsc::channel::<()>();
+        let spawned = std::thread::Builder::new().name("history-writer".to_string()).spawn(move || {
+            while woken.recv().is_ok() {
+                std::thread::sleep(SAVE_DELAY);
+                while woken.try_recv().is_ok() {}
+                flush();
+            }
+        });
+        if let Err(e) = spawned {
+            log::warn!("[HISTORY] Failed to start history writer: {}", e);
+        }
+        wake
+    });
+    if wake.send(()).is_err() {
+        // No writer thread: write now rather than never
+        flush();
+    }
+}
+
+/// Write pending changes now. Called by the writer thread, and on quit
+/// and before an update restart so nothing recorded is lost.
+pub fn flush() {
+    let _write = WRITE.lock().unwrap_or_else(|e| e.into_inner());
+    let json = {
+        let guard = super::STORE.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(store) = guard.as_ref() else { return };
+        if !DIRTY.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        serde_json::to_string(store)
+    };
+    let Some(path) = super::store_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match json {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("[HISTORY] Failed to write snip history: {}", e);
+            }
+        }
+        Err(e) => log::warn!("[HISTORY] Failed to serialize snip history: {}", e),
+    }
+}
+
+/// Empty the store and shred its file (history purge).
+pub(super) fn erase() -> Result<(), String> {
+    let _write = WRITE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut guard = super::STORE.lock().unwrap_or_else(|e| e.into_inner());
+    *guard = Some(HistoryStore::default());
+    DIRTY.store(false, Ordering::SeqCst);
+    match super::store_path() {
+        Some(path) => crate::persist::shred(&path),
+        None => Ok(()),
+    }
+}
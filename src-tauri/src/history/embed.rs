@@ -0,0 +1,148 @@
+//! Fuzzy lexical search for snip history: hashed word and trigram
+//! vectors in a flat cosine index.
+//!
+//! This is not semantic search — no model is involved, so synonyms
+//! ("error" / "failure") don't match. Vectors are feature hashes: every
+//! word and every character trigram inside a word is hashed into one of
+//! `DIMS` buckets with a ±1 sign, and the vector is L2-normalized. Word
+//! features catch shared vocabulary ("undefined", "map"); trigrams catch
+//! spelling variants and OCR slips ("TypeErr0r", "undefine"). Nothing
+//! leaves the machine, and the same text always yields the same vector.
+//! The index is a plain list scanned per query — history is capped, so a
+//! scan over a few thousand vectors is sub-millisecond.
+
+/// Vector width. Power of two so the bucket is a mask.
+pub const DIMS: usize = 512;
+
+/// Hits below this similarity are noise, not recall.
+const MIN_SCORE: f32 = 0.08;
+
+/// Weight of a whole-word feature relative to one trigram.
+const WORD_WEIGHT: f32 = 2.0;
+
+/// Words too common to say anything about a snip.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "about", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on", "or",
+    "that", "the", "this", "to", "was", "with",
+];
+
+pub type Embedding = Vec<f32>;
+
+/// FNV-1a — stable across runs and Rust versions, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn add_feature(vector: &mut [f32], feature: &str, weight: f32) {
+    let hash = fnv1a(feature.as_bytes());
+    let bucket = (hash as usize) & (DIMS - 1);
+    let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+    vector[bucket] += sign * weight;
+}
+
+/// Embed text. Empty or stopword-only text yields the zero vector.
+pub fn embed(text: &str) -> Embedding {
+    let mut vector = vec![0.0; DIMS];
+    let lower = text.to_lowercase();
+    let words = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty() && !STOPWORDS.contains(w));
+    for word in words {
+        add_feature(&mut vector, word, WORD_WEIGHT);
+        let padded: Vec<char> = format!("<{}>", word).chars().collect();
+        for gram in padded.windows(3) {
+            add_feature(&mut vector, &format!("#{}", gram.iter().collect::<String>()), 1.0);
+        }
+    }
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// Cosine similarity of two normalized vectors.
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Vectors keyed by snip id.
+#[derive(Debug, Clone, Default)]
+pub struct FlatIndex {
+    entries: Vec<(u64, Embedding)>,
+}
+
+impl FlatIndex {
+    /// Add or replace the vector for `id`.
+    pub fn upsert(&mut self, id: u64, vector: Embedding) {
+        match self.entries.iter_mut().find(|(entry_id, _)| *entry_id == id) {
+            Some(entry) => entry.1 = vector,
+            None => self.entries.push((id, vector)),
+        }
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.entries.retain(|(entry_id, _)| *entry_id != id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Top `k` ids by similarity to `query`, best first.
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(u64, f32)> {
+        let mut hits: Vec<(u64, f32)> = self
+            .entries
+            .iter()
+            .map(|(id, vector)| (*id, cosine(query, vector)))
+            .filter(|(_, score)| *score >= MIN_SCORE)
+            .collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.cmp(&a.0)));
+        hits.truncate(k);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embeddings_are_normalized_and_stable() {
+        let v = embed("TypeError: Cannot read properties of undefined (reading 'map')");
+        assert_eq!(v.len(), DIMS);
+        assert!((cosine(&v, &v) - 1.0).abs() < 1e-5);
+        assert_eq!(v, embed("TypeError: Cannot read properties of undefined (reading 'map')"));
+        assert!(embed("the of and").iter().all(|x| *x == 0.0));
+    }
+
+    #[test]
+    fn recall_prefers_the_related_snip() {
+        let mut index = FlatIndex::default();
+        index.upsert(1, embed("Quarterly revenue table: Q1 4.2M, Q2 5.1M, Q3 4.8M"));
+        index.upsert(2, embed("TypeError: Cannot read properties of undefined (reading 'map')\n at render (App.jsx:42)"));
+        index.upsert(3, embed("Meeting notes: ship the onboarding flow next sprint"));
+        let hits = index.search(&embed("the stack trace about undefined map"), 2);
+        assert_eq!(hits[0].0, 2);
+        assert!(hits.iter().all(|(_, score)| *score >= MIN_SCORE));
+
+        // OCR slips still land near the original
+        let hits = index.search(&embed("revenu tabel Q2"), 1);
+        assert_eq!(hits[0].0, 1);
+    }
+
+    #[test]
+    fn upsert_replaces_and_remove_drops() {
+        let mut index = FlatIndex::default();
+        index.upsert(7, embed("first"));
+        index.upsert(7, embed("second version"));
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.search(&embed("second version"), 5)[0].0, 7);
+        index.remove(7);
+        assert!(index.search(&embed("second version"), 5).is_empty());
+    }
+}
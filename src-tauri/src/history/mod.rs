@@ -0,0 +1,234 @@
+//! Snip history — every snip's OCR text and the answers it produced,
+//! with fuzzy search over them.
+//!
+//! `process_snip` records each snip once classify finishes; the execute
+//! step attaches action results to it, and serves them again when the
+//! same action runs on the same snip (`cache.rs`). Users can pin, tag,
+//! and annotate snips (`annotate.rs`); files saved from a snip are listed
+//! on it. A repeat of a recent snip reuses its classification and offers
+//! its last answer (`dedupe.rs`).
+//!
+//! Text is redacted before it is stored. Snips are filed under the active
+//! workspace, and queries default to it (`workspace.rs`). Retention and
+//! incognito mode decide what is kept at all (`privacy.rs`). Each snip
+//! keeps thumbnails of its crop (`crate::thumbnails`).
+//!
+//! Records persist to `~/.config/omni-glass/snip-history.json`, written
+//! in the background a moment after each change (`disk.rs`). The fuzzy
+//! lexical index (`embed.rs`) is rebuilt in memory on first use, so
+//! "undefined map stacktrace" finds the `TypeError` snip despite OCR slips
+//! and word forms, without any network call. The frontend's commands are
+//! in `commands.rs`.
+
+pub mod annotate;
+pub mod cache;
+pub mod commands;
+pub mod dedupe;
+pub mod disk;
+pub mod embed;
+pub mod privacy;
+pub mod store;
+pub mod workspace;
+
+use crate::llm::execute::{ActionResult, ActionResultBody, ActionResultMetadata};
+use crate::llm::types::{Action, ActionMenu};
+use crate::safety::redact;
+use image::DynamicImage;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use store::{HistoryStore, SnipRecord};
+use tauri::Emitter;
+
+/// Action offered on a duplicate snip; answered from history.
+pub const REUSE_ACTION: &str = "reuse_result";
+
+/// In-memory copy with its index; `None` until first use.
+static STORE: Mutex<Option<HistoryStore>> = Mutex::new(None);
+
+fn store_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("snip-history.json"))
+}
+
+fn load_from_disk() -> HistoryStore {
+    let mut store: HistoryStore = store_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    store.prune(privacy::current().retention, now_secs());
+    store.rebuild_index();
+    store
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Read the store under the lock.
+fn with_store<T>(read: impl FnOnce(&HistoryStore) -> T) -> T {
+    let mut guard = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    read(guard.get_or_insert_with(load_from_disk))
+}
+
+/// Load the store now instead of on first use (startup warm-up, `boot`).
+pub fn preload() {
+    with_store(|_| ());
+}
+
+/// Read every record under the lock (used by `stats`).
+pub fn with_records<T>(read: impl FnOnce(&[SnipRecord]) -> T) -> T {
+    with_store(|store| read(store.records()))
+}
+
+/// Apply `change` and, if it succeeded, have it written in the background
+/// (`disk.rs`).
+fn update<T>(change: impl FnOnce(&mut HistoryStore) -> Result<T, String>) -> Result<T, String> {
+    let out = {
+        let mut guard = STORE.lock().unwrap_or_else(|e| e.into_inner());
+        change(guard.get_or_insert_with(load_from_disk))?
+    };
+    disk::schedule_save();
+    Ok(out)
+}
+
+/// Record a classified snip and a thumbnail of its crop, then emit
+/// `history-changed` once the thumbnail is written. Empty OCR text is not
+/// kept, nor anything while history is paused; a fallback menu (classify
+/// failed) is not kept for reuse.
+pub fn record_snip(
+    app: &tauri::AppHandle,
+    text: &str,
+    menu: &ActionMenu,
+    image_hash: u64,
+    image: &DynamicImage,
+) {
+    if text.trim().is_empty() || !privacy::recording() {
+        return;
+    }
+    let clean = redact::redact_sensitive_data(text).cleaned_text;
+    let reusable = (menu.content_type != "unknown").then_some(menu);
+    let workspace = workspace::active();
+    let retention = privacy::current().retention;
+    let id = update(|store| {
+        let id = store.add_snip(&clean, reusable, Some(image_hash), &workspace, now_secs());
+        store.prune(retention, now_secs());
+        Ok(id)
+    });
+    let Ok(id) = id else { return };
+    log::info!("[HISTORY] Recorded snip #{} ({} chars)", id, clean.len());
+    let app = app.clone();
+    crate::thumbnails::queue(id, image.clone(), move || {
+        let _ = app.emit("history-changed", id);
+    });
+    crate::thumbnails::sweep(&with_records(|records| records.iter().map(|r| r.id).collect()));
+}
+
+/// The stored menu of a recent duplicate of this snip, with a
+/// "Reuse Previous Result" action on top when it was answered before.
+pub fn reusable_menu(text: &str, image_hash: u64) -> Option<ActionMenu> {
+    let clean = redact::redact_sensitive_data(text).cleaned_text;
+    let workspace = workspace::active();
+    let (id, mut menu, answered) = with_store(|store| {
+        let record = store.find_duplicate(&clean, image_hash, &workspace, now_secs())?;
+        Some((record.id, record.menu.clone()?, store.latest_answered(&clean, &workspace).is_some()))
+    })?;
+    if answered {
+        menu.actions.retain(|a| a.id != REUSE_ACTION);
+        menu.actions.insert(0, Action {
+            id: REUSE_ACTION.to_string(),
+            label: "Reuse Previous Result".to_string(),
+            icon: "history".to_string(),
+            priority: 0,
+            description: "Show the answer from the last time this was snipped".to_string(),
+            requires_execution: true,
+        });
+    }
+    log::info!("[HISTORY] Snip matches #{} — reusing its classification", id);
+    Some(menu)
+}
+
+/// The last answer given for this snip text, as an action result.
+pub fn reuse_result(text: &str) -> ActionResult {
+    let clean = redact::redact_sensitive_data(text).cleaned_text;
+    let workspace = workspace::active();
+    let answer = with_store(|store| store.latest_answered(&clean, &workspace).and_then(|r| r.answers.last().cloned()));
+    let Some(answer) = answer else {
+        return ActionResult::error(REUSE_ACTION, "No earlier result for this snip");
+    };
+    ActionResult {
+        status: "success".to_string(),
+        action_id: REUSE_ACTION.to_string(),
+        result: ActionResultBody {
+            result_type: "text".to_string(),
+            text: Some(answer.text),
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: Some("text/markdown".to_string()),
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata {
+            tokens_used: None,
+            processing_note: Some(format!("Reused result of '{}' (no LLM call)", answer.action_id)),
+            cached_at: None,
+        }),
+    }
+}
+
+/// Attach an action's result to the snip it ran on; it is kept
+/// (redacted) for the result cache. Returns the snip's id once recorded.
+pub fn record_answer(snip_text: &str, result: &ActionResult) -> Option<u64> {
+    let cached = result.metadata.as_ref().is_some_and(|m| m.cached_at.is_some());
+    if result.status != "success" || result.action_id == REUSE_ACTION || cached || !privacy::recording() {
+        return None;
+    }
+    let body = &result.result;
+    let answer = body.text.as_ref().or(body.command.as_ref()).or(body.clipboard_content.as_ref())?;
+    let clean_snip = redact::redact_sensitive_data(snip_text).cleaned_text;
+    let clean_answer = redact::redact_sensitive_data(answer).cleaned_text;
+    let mut kept = result.clone();
+    for field in [&mut kept.result.text, &mut kept.result.command, &mut kept.result.clipboard_content] {
+        if let Some(value) = field.as_mut() {
+            *value = redact::redact_sensitive_data(value).cleaned_text;
+        }
+    }
+    update(|store| store.add_answer(&clean_snip, &result.action_id, &clean_answer, Some(kept), now_secs()))
+        .map_err(|e| log::warn!("[HISTORY] Answer for '{}' not recorded: {}", result.action_id, e))
+        .ok()
+}
+
+/// Snip `id`'s text and its newest kept result of `action_id`.
+pub fn stored_result(id: u64, action_id: &str) -> Option<(String, ActionResult)> {
+    with_store(|store| {
+        let record = store.records().iter().find(|r| r.id == id)?;
+        let answer = record.answers.iter().rev().find(|a| a.action_id == action_id)?;
+        Some((record.text.clone(), answer.result.clone()?))
+    })
+}
+
+/// A cached result of `action_id` for this snip text, if one is kept.
+pub fn cached_result(snip_text: &str, action_id: &str) -> Option<ActionResult> {
+    let clean = redact::redact_sensitive_data(snip_text).cleaned_text;
+    let workspace = workspace::active();
+    with_store(|store| store.cached_result(&clean, action_id, &workspace))
+}
+
+/// Forget cached results of `action_id` for this snip text (regenerate).
+pub fn invalidate_results(snip_text: &str, action_id: &str) {
+    let clean = redact::redact_sensitive_data(snip_text).cleaned_text;
+    let workspace = workspace::active();
+    let dropped = update(|store| Ok(store.invalidate_results(&clean, action_id, &workspace)));
+    log::info!("[HISTORY] Invalidated {} cached '{}' results", dropped.unwrap_or_default(), action_id);
+}
+
+/// Remember that snip `id` was saved to `path` (not while paused).
+pub fn record_export(id: u64, kind: &str, path: &str) {
+    if !privacy::recording() {
+        return;
+    }
+    if let Err(e) = update(|store| store.add_export(id, kind, path, now_secs())) {
+        log::warn!("[HISTORY] Export of snip #{} not recorded: {}", id, e);
+    }
+}
@@ -0,0 +1,202 @@
+//! History retention and incognito mode.
+//!
+//! Retention decides how long snips are kept: forever (up to the store's
+//! cap), the last N days, the newest N snips, or nothing at all. Pinned
+//! snips are exempt from every rule — pinning is an explicit "keep this".
+//! Pausing history (incognito, toggled from the tray) records nothing
+//! while it's on: no snips, no answers, no auto-saved files. Reading
+//! existing history still works.
+//!
+//! `purge_all_history` overwrites the history file and the last screen
+//! capture before deleting them, and clears the in-memory store. The
+//! other records of past snips go too: the session file's interrupted
+//! snip and unviewed answer, usage stats, and the webhook delivery log.
+//! Settings live in `~/.config/omni-glass/history-privacy.json`; the
+//! active profile (`settings::profiles`) can impose its own retention.
+
+use super::store::{HistoryStore, SnipRecord};
+use crate::persist::{self, JsonStore};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+/// Longest day-based retention.
+const MAX_DAYS: u32 = 3650;
+/// Largest item-based retention (the store's own cap).
+const MAX_ITEMS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum Retention {
+    /// Keep everything, up to the store's cap.
+    #[default]
+    Forever,
+    Days { days: u32 },
+    Items { count: usize },
+    /// Record nothing and drop what is there.
+    Nothing,
+}
+
+impl Retention {
+    pub(crate) fn validate(self) -> Result<(), String> {
+        match self {
+            Retention::Days { days } if days == 0 || days > MAX_DAYS => {
+                Err(format!("Keep between 1 and {} days", MAX_DAYS))
+            }
+            Retention::Items { count } if count == 0 || count > MAX_ITEMS => {
+                Err(format!("Keep between 1 and {} snips", MAX_ITEMS))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacySettings {
+    #[serde(default)]
+    pub retention: Retention,
+    /// Incognito: record nothing until resumed.
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl HistoryStore {
+    /// Apply `retention` at `now`; returns how many snips were dropped.
+    pub fn prune(&mut self, retention: Retention, now: u64) -> usize {
+        let keep: Box<dyn Fn(&SnipRecord) -> bool> = match retention {
+            Retention::Forever => return 0,
+            Retention::Nothing => Box::new(|r| r.pinned),
+            Retention::Days { days } => {
+                let cutoff = now.saturating_sub(u64::from(days) * SECS_PER_DAY);
+                Box::new(move |r| r.pinned || r.created_at >= cutoff)
+            }
+            Retention::Items { count } => {
+                let unpinned: Vec<u64> = self.records.iter().filter(|r| !r.pinned).map(|r| r.id).collect();
+                let first_kept = unpinned.len().saturating_sub(count);
+                let oldest_kept = unpinned.get(first_kept).copied().unwrap_or(u64::MAX);
+                Box::new(move |r| r.pinned || r.id >= oldest_kept)
+            }
+        };
+        self.retain_records(keep)
+    }
+}
+
+static SETTINGS: JsonStore<PrivacySettings> = JsonStore::new("history-privacy.json");
+
+/// Retention and pause state as the user set them.
+fn stored() -> PrivacySettings {
+    SETTINGS.get()
+}
+
+/// Current retention and pause state. The active profile's retention
+/// rule, if it has one, replaces the user's own.
+pub fn current() -> PrivacySettings {
+    let mut settings = stored();
+    if let Some(retention) = crate::settings::active_profile().and_then(|p| p.history) {
+        settings.retention = retention;
+    }
+    settings
+}
+
+/// Whether new snips and answers may be recorded.
+pub fn recording() -> bool {
+    let settings = current();
+    !settings.paused && settings.retention != Retention::Nothing
+}
+
+fn update(change: impl FnOnce(&mut PrivacySettings)) -> Result<PrivacySettings, String> {
+    SETTINGS.update(|settings| {
+        change(settings);
+        settings.clone()
+    })
+}
+
+/// Pause or resume recording; notify windows and the tray.
+pub fn set_paused(app: &tauri::AppHandle, paused: bool) -> Result<(), String> {
+    update(|s| s.paused = paused)?;
+    log::info!("[HISTORY] Recording {}", if paused { "paused (incognito)" } else { "resumed" });
+    let _ = app.emit("history-paused-changed", paused);
+    Ok(())
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: retention rule and pause state as the user set them
+/// (a profile's retention rule is listed in its settings instead).
+#[tauri::command]
+pub fn get_history_privacy() -> PrivacySettings {
+    stored()
+}
+
+/// Tauri command: change retention and prune existing history to it;
+/// returns how many snips were dropped.
+#[tauri::command]
+pub fn set_history_retention(retention: Retention) -> Result<usize, String> {
+    retention.validate()?;
+    update(|s| s.retention = retention)?;
+    let dropped = super::update(|store| Ok(store.prune(retention, super::now_secs())))?;
+    log::info!("[HISTORY] Retention set to {:?}; dropped {} snips", retention, dropped);
+    Ok(dropped)
+}
+
+/// Tauri command: turn incognito mode on or off.
+#[tauri::command]
+pub fn set_history_paused(app: tauri::AppHandle, paused: bool) -> Result<(), String> {
+    set_paused(&app, paused)
+}
+
+/// Tauri command: erase all history, including pinned snips, the last
+/// screen capture, the session's snip text, usage stats, and webhook
+/// deliveries. Auto-saved snips in the user's folder are kept.
+#[tauri::command]
+pub fn purge_all_history(app: tauri::AppHandle) -> Result<(), String> {
+    super::disk::erase()?;
+    crate::thumbnails::purge()?;
+    persist::shred(&std::env::temp_dir().join(crate::tray::CAPTURE_FILE))?;
+    app.state::<crate::capture::CaptureState>().clear();
+    crate::session::purge()?;
+    crate::stats::usage::purge()?;
+    crate::webhooks::delivery::purge()?;
+    log::info!("[HISTORY] Purged all history");
+    let _ = app.emit("history-purged", ());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with(n: u64) -> HistoryStore {
+        let mut store = HistoryStore::default();
+        for i in 1..=n {
+            store.add_snip(&format!("snip {}", i), None, None, "default", i * SECS_PER_DAY);
+        }
+        store
+    }
+
+    #[test]
+    fn retention_rules_spare_pinned_snips() {
+        let mut store = store_with(5);
+        store.set_pinned(1, true).unwrap();
+        assert_eq!(store.prune(Retention::Items { count: 2 }, 0), 2);
+        let ids: Vec<u64> = store.list(None, None, false, 10).iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 5, 4]);
+
+        let mut store = store_with(5);
+        assert_eq!(store.prune(Retention::Days { days: 2 }, 5 * SECS_PER_DAY), 2);
+        assert_eq!(store.prune(Retention::Forever, u64::MAX), 0);
+        store.set_pinned(4, true).unwrap();
+        assert_eq!(store.prune(Retention::Nothing, 0), 2);
+        assert!(store.search("snip 3", 5, None).iter().all(|h| h.record.id == 4));
+    }
+
+    #[test]
+    fn retention_bounds_are_checked() {
+        assert!(Retention::Days { days: 0 }.validate().is_err());
+        assert!(Retention::Items { count: MAX_ITEMS + 1 }.validate().is_err());
+        assert!(Retention::Items { count: 50 }.validate().is_ok());
+        let parsed: Retention = serde_json::from_str(r#"{"mode":"days","days":30}"#).unwrap();
+        assert_eq!(parsed, Retention::Days { days: 30 });
+    }
+}
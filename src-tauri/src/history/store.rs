@@ -0,0 +1,294 @@
+//! Snip history records and their search index (pure, no I/O).
+//!
+//! Records are kept oldest first and capped at `MAX_RECORDS`; the oldest
+//! unpinned snip is dropped when a new one arrives. Each record is indexed
+//! over its OCR text, classify summary, answers, tags, and notes, so a
+//! search can land on what was snipped, what the LLM said about it, or
+//! what the user wrote down.
+
+use super::embed::{self, FlatIndex};
+use crate::llm::execute::ActionResult;
+use crate::llm::types::ActionMenu;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Snips kept on disk.
+const MAX_RECORDS: usize = 1000;
+/// Characters kept of snip text and of each answer.
+const MAX_TEXT_CHARS: usize = 20_000;
+/// Answers kept per snip.
+const MAX_ANSWERS: usize = 10;
+/// Saved files remembered per snip.
+const MAX_EXPORTS: usize = 20;
+
+/// One result an action produced for a snip.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipAnswer {
+    pub action_id: String,
+    pub text: String,
+    /// Unix seconds.
+    pub created_at: u64,
+    /// The full result, served again by the result cache (`cache.rs`);
+    /// `None` once invalidated.
+    #[serde(default)]
+    pub result: Option<ActionResult>,
+}
+
+/// A file a snip was saved to (`export::save_result`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipExport {
+    /// `image`, `text`, `csv`, or `pdf`.
+    pub kind: String,
+    pub path: String,
+    /// Unix seconds.
+    pub created_at: u64,
+}
+
+/// One snip: what OCR read, how it was classified, what came back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipRecord {
+    pub id: u64,
+    /// Unix seconds.
+    pub created_at: u64,
+    pub text: String,
+    #[serde(default)]
+    pub content_type: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub answers: Vec<SnipAnswer>,
+    /// Pinned snips are listed first and never dropped by the cap.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Normalized user tags (`annotate::normalize_tag`).
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    #[serde(default)]
+    pub notes: String,
+    /// Classification reused when the same snip comes back (`dedupe.rs`).
+    #[serde(default)]
+    pub menu: Option<ActionMenu>,
+    /// Perceptual hash of the crop.
+    #[serde(default)]
+    pub image_hash: Option<u64>,
+    /// Workspace the snip was taken in (`workspace.rs`).
+    #[serde(default = "default_workspace")]
+    pub workspace: String,
+    /// Files saved from this snip, oldest first.
+    #[serde(default)]
+    pub exports: Vec<SnipExport>,
+}
+
+fn default_workspace() -> String {
+    super::workspace::DEFAULT_WORKSPACE.to_string()
+}
+
+impl SnipRecord {
+    /// Whether the record belongs to `workspace` (`None`: every workspace).
+    pub fn in_scope(&self, workspace: Option<&str>) -> bool {
+        workspace.is_none_or(|w| self.workspace == w)
+    }
+
+    /// Everything a search should match against.
+    fn indexed_text(&self) -> String {
+        let mut parts = vec![self.text.as_str(), self.summary.as_str(), self.notes.as_str()];
+        parts.extend(self.tags.iter().map(String::as_str));
+        parts.extend(self.answers.iter().map(|a| a.text.as_str()));
+        parts.join("\n")
+    }
+}
+
+/// A search result.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub score: f32,
+    pub record: SnipRecord,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryStore {
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    pub(super) records: Vec<SnipRecord>,
+    /// Rebuilt from `records` on load; never persisted.
+    #[serde(skip)]
+    index: FlatIndex,
+}
+
+fn capped(text: &str) -> String {
+    text.chars().take(MAX_TEXT_CHARS).collect()
+}
+
+impl HistoryStore {
+    pub(super) fn reindex(&mut self, id: u64) {
+        if let Some(record) = self.records.iter().find(|r| r.id == id) {
+            self.index.upsert(id, embed::embed(&record.indexed_text()));
+        }
+    }
+
+    /// Embed every record. Call after deserializing.
+    pub fn rebuild_index(&mut self) {
+        self.index = FlatIndex::default();
+        for record in &self.records {
+            self.index.upsert(record.id, embed::embed(&record.indexed_text()));
+        }
+    }
+
+    /// Record a snip; returns its id.
+    pub fn add_snip(&mut self, text: &str, menu: Option<&ActionMenu>, image_hash: Option<u64>, workspace: &str, now: u64) -> u64 {
+        self.next_id += 1;
+        let record = SnipRecord {
+            id: self.next_id,
+            created_at: now,
+            text: capped(text),
+            content_type: menu.map(|m| m.content_type.clone()).unwrap_or_default(),
+            summary: menu.map(|m| m.summary.clone()).unwrap_or_default(),
+            answers: Vec::new(),
+            pinned: false,
+            tags: BTreeSet::new(),
+            notes: String::new(),
+            menu: menu.cloned(),
+            image_hash,
+            workspace: workspace.to_string(),
+            exports: Vec::new(),
+        };
+        let id = record.id;
+        self.records.push(record);
+        self.reindex(id);
+        while self.records.len() > MAX_RECORDS {
+            let Some(oldest) = self.records.iter().position(|r| !r.pinned) else { break };
+            let dropped = self.records.remove(oldest);
+            self.index.remove(dropped.id);
+        }
+        self.next_id
+    }
+
+    /// Attach an answer (and the result to cache, if any) to the newest
+    /// snip with exactly this text.
+    pub fn add_answer(
+        &mut self,
+        snip_text: &str,
+        action_id: &str,
+        answer: &str,
+        result: Option<ActionResult>,
+        now: u64,
+    ) -> Result<u64, String> {
+        let snip_text = capped(snip_text);
+        let record = self
+            .records
+            .iter_mut()
+            .rev()
+            .find(|r| r.text == snip_text)
+            .ok_or("This snip is no longer in history")?;
+        record.answers.push(SnipAnswer { action_id: action_id.to_string(), text: capped(answer), created_at: now, result });
+        if record.answers.len() > MAX_ANSWERS {
+            record.answers.remove(0);
+        }
+        let id = record.id;
+        self.reindex(id);
+        Ok(id)
+    }
+
+    /// Remember that snip `id` was saved to `path`.
+    pub fn add_export(&mut self, id: u64, kind: &str, path: &str, now: u64) -> Result<(), String> {
+        let record = self.records.iter_mut().find(|r| r.id == id).ok_or_else(|| format!("No snip #{} in history", id))?;
+        record.exports.push(SnipExport { kind: kind.to_string(), path: path.to_string(), created_at: now });
+        if record.exports.len() > MAX_EXPORTS {
+            record.exports.remove(0);
+        }
+        Ok(())
+    }
+
+    /// Every record, oldest first.
+    pub fn records(&self) -> &[SnipRecord] {
+        &self.records
+    }
+
+    /// Drop every record `keep` rejects; returns how many went.
+    pub(super) fn retain_records(&mut self, keep: impl Fn(&SnipRecord) -> bool) -> usize {
+        let before = self.records.len();
+        let index = &mut self.index;
+        self.records.retain(|r| {
+            let kept = keep(r);
+            if !kept {
+                index.remove(r.id);
+            }
+            kept
+        });
+        before - self.records.len()
+    }
+
+    /// Pinned snips first, then newest first; optionally one workspace,
+    /// one tag, or only pinned snips.
+    pub fn list(&self, workspace: Option<&str>, tag: Option<&str>, pinned_only: bool, limit: usize) -> Vec<SnipRecord> {
+        let matching = |r: &&SnipRecord| {
+            r.in_scope(workspace) && tag.is_none_or(|t| r.tags.contains(t)) && (!pinned_only || r.pinned)
+        };
+        let pinned = self.records.iter().rev().filter(|r| r.pinned);
+        let unpinned = self.records.iter().rev().filter(|r| !r.pinned);
+        pinned.chain(unpinned).filter(matching).take(limit).cloned().collect()
+    }
+
+    /// Top `k` snips by lexical similarity, best first.
+    pub fn search(&self, query: &str, k: usize, workspace: Option<&str>) -> Vec<SearchHit> {
+        self.index
+            .search(&embed::embed(query), self.records.len())
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let record = self.records.iter().find(|r| r.id == id && r.in_scope(workspace))?.clone();
+                Some(SearchHit { score, record })
+            })
+            .take(k)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_searchable_and_reload_keeps_hits() {
+        let mut store = HistoryStore::default();
+        store.add_snip("Q1 4.2M Q2 5.1M", None, None, "default", 10);
+        let id = store.add_snip("at render (App.jsx:42)", None, None, "Acme", 20);
+        store.add_answer("at render (App.jsx:42)", "explain_error", "items is undefined when map runs", None, 30).unwrap();
+        assert!(store.add_answer("never snipped", "explain_error", "x", None, 40).is_err());
+
+        let hits = store.search("undefined map render trace", 5, None);
+        assert_eq!(hits[0].record.id, id);
+        assert_eq!(hits[0].record.answers[0].action_id, "explain_error");
+
+        let json = serde_json::to_string(&store).unwrap();
+        let mut reloaded: HistoryStore = serde_json::from_str(&json).unwrap();
+        assert!(reloaded.search("undefined map", 5, None).is_empty());
+        reloaded.rebuild_index();
+        assert_eq!(reloaded.search("undefined map", 5, Some("Acme"))[0].record.id, id);
+        assert!(reloaded.search("undefined map", 5, Some("default")).is_empty());
+        assert_eq!(reloaded.list(Some("default"), None, false, 5).len(), 1);
+    }
+
+    #[test]
+    fn oldest_snips_are_dropped_past_the_cap() {
+        let mut store = HistoryStore::default();
+        for i in 0..MAX_RECORDS + 2 {
+            store.add_snip(&format!("snip number {}", i), None, None, "default", i as u64);
+        }
+        assert_eq!(store.records.len(), MAX_RECORDS);
+        assert_eq!(store.index.len(), MAX_RECORDS);
+        assert_eq!(store.list(None, None, false, 1)[0].text, format!("snip number {}", MAX_RECORDS + 1));
+        assert_eq!(store.records[0].id, 3);
+
+        // A pinned snip outlives the cap
+        store.records[0].pinned = true;
+        store.add_snip("one more", None, None, "default", 0);
+        assert_eq!(store.records[0].id, 3);
+        assert_eq!(store.records[1].id, 5);
+    }
+}
@@ -0,0 +1,191 @@
+//! Workspaces — named project contexts for history and plugins.
+//!
+//! Exactly one workspace is active. New snips are filed under it, history
+//! queries default to it, and plugins it hides are treated as disabled
+//! (`mcp::enablement::current` adds them) until another workspace is
+//! active. The `default` workspace always exists and can't be removed.
+//!
+//! State lives in `~/.config/omni-glass/workspaces.json`, cached after
+//! first use. Switching emits `workspace-changed`, on which the tray menu
+//! rebuilds its workspace list.
+
+use crate::persist::{self, JsonStore};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use tauri::Emitter;
+
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+const MAX_WORKSPACES: usize = 20;
+const MAX_NAME_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    /// Unix seconds.
+    #[serde(default)]
+    pub created_at: u64,
+    /// Plugins hidden while this workspace is active.
+    #[serde(default)]
+    pub disabled_plugins: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStore {
+    pub active: String,
+    pub workspaces: BTreeMap<String, Workspace>,
+}
+
+impl Default for WorkspaceStore {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_WORKSPACE.to_string(),
+            workspaces: BTreeMap::from([(DEFAULT_WORKSPACE.to_string(), Workspace::default())]),
+        }
+    }
+}
+
+/// Trim and validate a workspace name.
+fn clean_name(raw: &str) -> Result<String, String> {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() || name.chars().count() > MAX_NAME_CHARS || name == "*" {
+        return Err(format!("Workspace names are 1-{} characters", MAX_NAME_CHARS));
+    }
+    Ok(name)
+}
+
+impl WorkspaceStore {
+    /// Existing workspace matching `raw`, ignoring case.
+    fn find(&self, raw: &str) -> Option<String> {
+        let name = clean_name(raw).ok()?;
+        self.workspaces.keys().find(|k| k.eq_ignore_ascii_case(&name)).cloned()
+    }
+
+    pub fn create(&mut self, raw: &str, now: u64) -> Result<String, String> {
+        let name = clean_name(raw)?;
+        if self.find(&name).is_some() {
+            return Err(format!("Workspace '{}' already exists", name));
+        }
+        if self.workspaces.len() >= MAX_WORKSPACES {
+            return Err(format!("At most {} workspaces", MAX_WORKSPACES));
+        }
+        self.workspaces.insert(name.clone(), Workspace { created_at: now, ..Workspace::default() });
+        Ok(name)
+    }
+
+    pub fn switch(&mut self, raw: &str) -> Result<String, String> {
+        let name = self.find(raw).ok_or_else(|| format!("No workspace '{}'", raw.trim()))?;
+        self.active = name.clone();
+        Ok(name)
+    }
+
+    pub fn set_plugin_enabled(&mut self, raw: &str, plugin_id: &str, enabled: bool) -> Result<(), String> {
+        let name = self.find(raw).ok_or_else(|| format!("No workspace '{}'", raw.trim()))?;
+        let disabled = &mut self.workspaces.entry(name).or_default().disabled_plugins;
+        if enabled {
+            disabled.remove(plugin_id);
+        } else {
+            disabled.insert(plugin_id.to_string());
+        }
+        Ok(())
+    }
+
+    /// Plugins hidden by the active workspace.
+    pub fn active_disabled_plugins(&self) -> BTreeSet<String> {
+        self.workspaces.get(&self.active).map(|w| w.disabled_plugins.clone()).unwrap_or_default()
+    }
+}
+
+static STORE: JsonStore<WorkspaceStore> = JsonStore::with_loader("workspaces.json", load);
+
+/// The stored workspaces, with `default` present and active if none is.
+fn load(path: &Path) -> WorkspaceStore {
+    let mut store: WorkspaceStore = persist::read_json(path);
+    store.workspaces.entry(DEFAULT_WORKSPACE.to_string()).or_default();
+    if !store.workspaces.contains_key(&store.active) {
+        store.active = DEFAULT_WORKSPACE.to_string();
+    }
+    store
+}
+
+/// Current workspace state.
+pub fn current() -> WorkspaceStore {
+    STORE.get()
+}
+
+/// Name of the active workspace.
+pub fn active() -> String {
+    current().active
+}
+
+fn update<T>(change: impl FnOnce(&mut WorkspaceStore) -> Result<T, String>) -> Result<T, String> {
+    STORE.try_update(change)
+}
+
+/// Make `name` active; notify windows and the tray.
+pub fn switch_to(app: &tauri::AppHandle, name: &str) -> Result<String, String> {
+    let name = update(|s| s.switch(name))?;
+    log::info!("[HISTORY] Switched to workspace '{}'", name);
+    let _ = app.emit("workspace-changed", &name);
+    Ok(name)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: all workspaces and which one is active.
+#[tauri::command]
+pub fn get_workspaces() -> WorkspaceStore {
+    current()
+}
+
+/// Tauri command: create a workspace (does not switch to it).
+#[tauri::command]
+pub fn create_workspace(app: tauri::AppHandle, name: String) -> Result<String, String> {
+    let name = update(|s| s.create(&name, super::now_secs()))?;
+    log::info!("[HISTORY] Created workspace '{}'", name);
+    crate::tray::refresh_menu(&app);
+    Ok(name)
+}
+
+/// Tauri command: make a workspace active.
+#[tauri::command]
+pub fn switch_workspace(app: tauri::AppHandle, name: String) -> Result<String, String> {
+    switch_to(&app, &name)
+}
+
+/// Tauri command: show or hide a plugin while a workspace is active.
+#[tauri::command]
+pub fn set_workspace_plugin_enabled(workspace: String, plugin_id: String, enabled: bool) -> Result<(), String> {
+    update(|s| s.set_plugin_enabled(&workspace, &plugin_id, enabled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_and_switch_ignore_case() {
+        let mut s = WorkspaceStore::default();
+        assert_eq!(s.create("  Client  Acme ", 5).unwrap(), "Client Acme");
+        assert!(s.create("client acme", 6).is_err());
+        assert!(s.create("*", 6).is_err());
+        assert_eq!(s.switch("CLIENT ACME").unwrap(), "Client Acme");
+        assert_eq!(s.active, "Client Acme");
+        assert!(s.switch("nope").is_err());
+        assert_eq!(s.active, "Client Acme");
+    }
+
+    #[test]
+    fn hidden_plugins_follow_the_active_workspace() {
+        let mut s = WorkspaceStore::default();
+        s.create("Personal", 1).unwrap();
+        s.set_plugin_enabled("personal", "com.work.jira", false).unwrap();
+        assert!(s.active_disabled_plugins().is_empty());
+        s.switch("Personal").unwrap();
+        assert!(s.active_disabled_plugins().contains("com.work.jira"));
+        s.set_plugin_enabled("Personal", "com.work.jira", true).unwrap();
+        assert!(s.active_disabled_plugins().is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! Translation catalogs: parsing, lookup with fallback, and placeholders
+//! (pure, no I/O).
+//!
+//! A translation file is a JSON object of message ids to strings; nested
+//! objects are flattened with `.` (`{"tray": {"quit": "…"}}` defines
+//! `tray.quit`). A catalog stacks layers from most to least specific —
+//! `pt-BR`, then `pt`, then English — so a partial translation shows
+//! English only for what it leaves out, and an unknown id shows the id.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub type Messages = HashMap<String, String>;
+
+/// Locale used for anything a translation leaves out.
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Parse a translation file.
+pub fn parse(raw: &str) -> Result<Messages, String> {
+    let value: Value = serde_json::from_str(raw).map_err(|e| format!("Invalid translation file: {}", e))?;
+    let mut messages = Messages::new();
+    flatten("", &value, &mut messages)?;
+    Ok(messages)
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Messages) -> Result<(), String> {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let id = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&id, child, out)?;
+            }
+            Ok(())
+        }
+        Value::String(text) if !prefix.is_empty() => {
+            out.insert(prefix.to_string(), text.clone());
+            Ok(())
+        }
+        _ => Err(format!("Translation '{}' must be a string", prefix)),
+    }
+}
+
+/// A BCP 47-style tag (`de-DE`) from an OS or user value such as
+/// `de_DE.UTF-8` or `DE-de`. `None` for `C`, `POSIX`, or anything that
+/// isn't a language tag.
+pub fn normalize(raw: &str) -> Option<String> {
+    let tag = raw.split(['.', '@']).next().unwrap_or("").replace('_', "-");
+    let mut parts = tag.split('-').filter(|p| !p.is_empty());
+    let language = parts.next()?.to_ascii_lowercase();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut out = language;
+    for part in parts {
+        if part.len() > 8 || !part.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        out.push('-');
+        out.push_str(&if part.len() == 2 { part.to_ascii_uppercase() } else { part.to_string() });
+    }
+    Some(out)
+}
+
+/// Tags to try for `tag`, most specific first: `zh-Hant-TW`, `zh-Hant`, `zh`.
+pub fn candidates(tag: &str) -> Vec<String> {
+    let parts: Vec<&str> = tag.split('-').collect();
+    (1..=parts.len()).rev().map(|n| parts[..n].join("-")).collect()
+}
+
+/// Replace `{name}` placeholders with `args`; unknown ones are left as is.
+pub fn format(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// Messages for one locale, with fallbacks.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    /// The locale asked for (normalized).
+    pub locale: String,
+    /// Most specific first; English last.
+    layers: Vec<Messages>,
+}
+
+impl Catalog {
+    pub fn new(locale: String, layers: Vec<Messages>) -> Self {
+        Self { locale, layers }
+    }
+
+    /// The message for `id`, or `id` itself when no layer has it.
+    pub fn get<'a>(&'a self, id: &'a str) -> &'a str {
+        self.layers.iter().find_map(|layer| layer.get(id)).map_or(id, String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_normalized() {
+        assert_eq!(normalize("de_DE.UTF-8").as_deref(), Some("de-DE"));
+        assert_eq!(normalize("pt-br").as_deref(), Some("pt-BR"));
+        assert_eq!(normalize("zh-Hant-TW").as_deref(), Some("zh-Hant-TW"));
+        assert_eq!(normalize("C"), None);
+        assert_eq!(normalize("POSIX"), None);
+        assert_eq!(normalize("../../etc"), None);
+        assert_eq!(candidates("zh-Hant-TW"), vec!["zh-Hant-TW", "zh-Hant", "zh"]);
+    }
+
+    #[test]
+    fn lookup_falls_back_layer_by_layer() {
+        let en = parse(r#"{"tray": {"quit": "Quit Omni-Glass", "snip": "Snip Screen"}, "error.offline": "Offline"}"#).unwrap();
+        assert_eq!(en.get("error.offline").map(String::as_str), Some("Offline"));
+        let de = parse(r#"{"tray": {"quit": "Omni-Glass beenden"}}"#).unwrap();
+        let catalog = Catalog::new("de-AT".into(), vec![de, en]);
+        assert_eq!(catalog.get("tray.quit"), "Omni-Glass beenden");
+        assert_eq!(catalog.get("tray.snip"), "Snip Screen");
+        assert_eq!(catalog.get("tray.missing"), "tray.missing");
+        assert!(parse(r#"{"tray": {"quit": 1}}"#).is_err());
+        assert_eq!(
+            format("Install Update ({version})", &[("version", "1.2.0")]),
+            "Install Update (1.2.0)"
+        );
+    }
+}
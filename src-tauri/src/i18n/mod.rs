@@ -0,0 +1,155 @@
+//! Localized strings for everything the Rust side shows the user:
+//! fallback and local action menus, tray menu and tooltip, and the error
+//! messages commands return.
+//!
+//! Translations ship as JSON files in `src-tauri/locales/` and are built
+//! into the binary. Files in `~/.config/omni-glass/locales/` (same format,
+//! e.g. `fr.json`) add languages or override single messages. The locale
+//! is the `locale` setting, or the system language when unset. Changing
+//! it rebuilds the tray menu and emits `locale-changed` with the tag.
+
+pub mod catalog;
+
+use catalog::{Catalog, Messages, FALLBACK_LOCALE};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
+
+/// Translations built into the binary.
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.json")),
+    ("de", include_str!("../../locales/de.json")),
+    ("es", include_str!("../../locales/es.json")),
+];
+
+/// The catalog in use; `None` until first lookup or after a locale change.
+static CATALOG: Mutex<Option<Arc<Catalog>>> = Mutex::new(None);
+
+fn user_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("locales"))
+}
+
+/// The OS language: `LC_ALL` / `LC_MESSAGES` / `LANG`, then the macOS
+/// `AppleLocale` default.
+fn system_locale() -> Option<String> {
+    let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find_map(|v| catalog::normalize(&v));
+    if from_env.is_some() || !cfg!(target_os = "macos") {
+        return from_env;
+    }
+    let out = std::process::Command::new("defaults").args(["read", "-g", "AppleLocale"]).output().ok()?;
+    catalog::normalize(String::from_utf8_lossy(&out.stdout).trim())
+}
+
+/// One layer: the user's file for `tag` over the bundled one.
+fn layer(tag: &str) -> Option<Messages> {
+    let mut messages = BUNDLED
+        .iter()
+        .find(|(t, _)| *t == tag)
+        .and_then(|(_, raw)| catalog::parse(raw).map_err(|e| log::error!("[I18N] Bundled {}: {}", tag, e)).ok())
+        .unwrap_or_default();
+    let user = user_dir().map(|d| d.join(format!("{}.json", tag)));
+    if let Some(raw) = user.and_then(|p| std::fs::read_to_string(p).ok()) {
+        match catalog::parse(&raw) {
+            Ok(overrides) => messages.extend(overrides),
+            Err(e) => log::warn!("[I18N] Ignoring user translation {}: {}", tag, e),
+        }
+    }
+    (!messages.is_empty()).then_some(messages)
+}
+
+fn build() -> Catalog {
+    let locale = crate::settings::current()
+        .locale
+        .or_else(system_locale)
+        .unwrap_or_else(|| FALLBACK_LOCALE.to_string());
+    let mut tags = catalog::candidates(&locale);
+    tags.push(FALLBACK_LOCALE.to_string());
+    tags.dedup();
+    log::info!("[I18N] Locale: {}", locale);
+    Catalog::new(locale, tags.iter().filter_map(|t| layer(t)).collect())
+}
+
+fn current() -> Arc<Catalog> {
+    let mut guard = CATALOG.lock().unwrap_or_else(|e| e.into_inner());
+    guard.get_or_insert_with(|| Arc::new(build())).clone()
+}
+
+/// The message for `id` in the current locale.
+pub fn t(id: &str) -> String {
+    current().get(id).to_string()
+}
+
+/// The message for `id` with `{name}` placeholders filled in.
+pub fn tf(id: &str, args: &[(&str, &str)]) -> String {
+    catalog::format(current().get(id), args)
+}
+
+/// Locales with a bundled or user translation file, sorted.
+pub fn available() -> Vec<String> {
+    let mut tags: Vec<String> = BUNDLED.iter().map(|(t, _)| t.to_string()).collect();
+    let user_files = user_dir().and_then(|d| std::fs::read_dir(d).ok()).into_iter().flatten().flatten();
+    for entry in user_files {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if let Some(tag) = name.strip_suffix(".json").and_then(catalog::normalize) {
+            tags.push(tag);
+        }
+    }
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Drop the cached catalog and tell the app. Called by `settings::update`
+/// when `locale` changes.
+pub fn reload(app: &tauri::AppHandle) {
+    let locale = {
+        let mut guard = CATALOG.lock().unwrap_or_else(|e| e.into_inner());
+        guard.insert(Arc::new(build())).locale.clone()
+    };
+    let _ = app.emit("locale-changed", &locale);
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleInfo {
+    /// Locale in use.
+    pub locale: String,
+    /// The `locale` setting; `None` means the system language.
+    pub setting: Option<String>,
+    pub available: Vec<String>,
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: locale in use, the setting, and installed translations.
+#[tauri::command]
+pub fn get_locale() -> LocaleInfo {
+    LocaleInfo {
+        locale: current().locale.clone(),
+        setting: crate::settings::current().locale,
+        available: available(),
+    }
+}
+
+/// Tauri command: switch language (`de`, `pt-BR`); `null` follows the
+/// system. Returns the locale now in use.
+#[tauri::command]
+pub fn set_locale(app: tauri::AppHandle, locale: Option<String>) -> Result<String, String> {
+    let locale = match locale {
+        Some(raw) => {
+            let tag = catalog::normalize(&raw).ok_or_else(|| tf("error.unknownLocale", &[("locale", &raw)]))?;
+            let installed = available();
+            if !catalog::candidates(&tag).iter().any(|t| installed.contains(t)) {
+                return Err(tf("error.unknownLocale", &[("locale", &raw)]));
+            }
+            Some(tag)
+        }
+        None => None,
+    };
+    crate::settings::update(&app, serde_json::json!({ "locale": locale }))?;
+    Ok(current().locale.clone())
+}
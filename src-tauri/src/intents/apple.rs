@@ -0,0 +1,80 @@
+//! App Intents bridge (macOS) — Shortcuts actions backed by `mod.rs`.
+//!
+//! `swift-src/intents_bridge.swift` declares the intents; their
+//! `perform()` calls these functions on a background queue and waits for
+//! the reply. Shortcuts may launch the app to run an intent, so calls
+//! that need the app wait for `setup` to hand it over first.
+
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// How long an intent waits for a freshly launched app to start.
+const STARTUP_WAIT: Duration = Duration::from_secs(15);
+
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct IntentReply {
+        ok: bool,
+        /// The intent's output, or the error message.
+        text: String,
+    }
+
+    extern "Rust" {
+        fn intent_take_snip() -> IntentReply;
+        fn intent_ocr_image(path: String) -> IntentReply;
+        fn intent_ask_clipboard(question: String) -> IntentReply;
+    }
+}
+
+pub fn attach(app: &tauri::AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+fn app() -> Result<&'static tauri::AppHandle, String> {
+    let start = Instant::now();
+    loop {
+        if let Some(app) = APP.get() {
+            return Ok(app);
+        }
+        if start.elapsed() > STARTUP_WAIT {
+            return Err("Omni-Glass did not finish starting".to_string());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn reply(outcome: Result<String, String>) -> ffi::IntentReply {
+    match outcome {
+        Ok(text) => ffi::IntentReply { ok: true, text },
+        Err(text) => {
+            log::warn!("[INTENTS] {}", text);
+            ffi::IntentReply { ok: false, text }
+        }
+    }
+}
+
+fn intent_take_snip() -> ffi::IntentReply {
+    reply(app().and_then(|app| {
+        // The overlay window is made on the main thread, as from the tray
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = app.clone();
+        app.run_on_main_thread(move || {
+            let _ = tx.send(super::take_snip(&handle));
+        })
+        .map_err(|e| e.to_string())?;
+        rx.recv().map_err(|e| e.to_string())??;
+        Ok(String::new())
+    }))
+}
+
+fn intent_ocr_image(path: String) -> ffi::IntentReply {
+    reply(tauri::async_runtime::block_on(super::ocr_image(Path::new(&path))))
+}
+
+fn intent_ask_clipboard(question: String) -> ffi::IntentReply {
+    reply(app().and_then(|app| tauri::async_runtime::block_on(super::ask_about_clipboard(app, Some(&question)))))
+}
@@ -0,0 +1,149 @@
+//! Intents domain — Omni-Glass steps for OS-level automations.
+//!
+//! Three intents: Take Snip, OCR Image, and Ask About Clipboard. On
+//! macOS they are App Intents, listed in Shortcuts (`apple.rs` and
+//! `swift-src/intents_bridge.swift`); Shortcuts gets OCR text and answers
+//! back as the intent's output. Elsewhere they are `omniglass://` links
+//! (`omniglass://ocr?path=`, `omniglass://ask?q=`, see deeplink/) that
+//! Power Automate, Task Scheduler, or a script can open; links cannot
+//! return a value, so the result goes to the clipboard and a notification.
+
+#[cfg(target_os = "macos")]
+mod apple;
+
+use crate::i18n::{t, tf};
+use crate::llm::execute::{ActionResult, ActionResultBody};
+use crate::notifications::{self, notice::Notice, Reopen};
+use std::path::Path;
+use tauri::Manager;
+
+/// Largest image OCR Image accepts, in bytes.
+const MAX_IMAGE_BYTES: u64 = 50 * 1024 * 1024;
+/// Clipboard text sent with a question, in characters.
+const MAX_CLIPBOARD_CHARS: usize = 20_000;
+
+/// An intent run from a link, with its input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Intent {
+    OcrImage { path: String },
+    AskClipboard { question: Option<String> },
+}
+
+/// Make the app reachable for App Intents (macOS). Called once from `setup`.
+pub fn attach(app: &tauri::AppHandle) {
+    #[cfg(target_os = "macos")]
+    apple::attach(app);
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+}
+
+/// Take Snip: the snip overlay, as from the tray.
+pub fn take_snip(app: &tauri::AppHandle) -> Result<(), String> {
+    log::info!("[INTENTS] Take Snip");
+    let now_ms = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+    crate::tray::start_snip_mode(app, now_ms.as_millis() as f64);
+    Ok(())
+}
+
+/// OCR Image: the text in an image file (any format the `image` crate reads).
+pub async fn ocr_image(path: &Path) -> Result<String, String> {
+    log::info!("[INTENTS] OCR Image");
+    let size = std::fs::metadata(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?.len();
+    if size > MAX_IMAGE_BYTES {
+        return Err(format!("Image is larger than {} MB", MAX_IMAGE_BYTES / 1024 / 1024));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let png = tauri::async_runtime::spawn_blocking(move || -> Result<Vec<u8>, String> {
+        let image = image::load_from_memory(&bytes).map_err(|e| format!("Not an image: {}", e))?;
+        let mut png = Vec::new();
+        image
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| format!("Could not convert the image: {}", e))?;
+        Ok(png)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    // No one is waiting on a snip here, so take the accurate pass
+    let level = crate::ocr::RecognitionLevel::Accurate;
+    let output = tauri::async_runtime::spawn_blocking(move || crate::ocr::recognize_text_from_bytes(png, level))
+        .await
+        .map_err(|e| e.to_string())?;
+    if output.recognition_level == "error" {
+        return Err(output.text.trim_start_matches("ERROR: ").to_string());
+    }
+    log::info!("[INTENTS] OCR Image: {} chars", output.char_count);
+    Ok(output.text)
+}
+
+/// Ask About Clipboard: the clipboard's text and `question` (default:
+/// explain it) through the text launcher pipeline, so routing, plugins,
+/// and the outbound guard apply as for a typed command.
+pub async fn ask_about_clipboard(app: &tauri::AppHandle, question: Option<&str>) -> Result<String, String> {
+    log::info!("[INTENTS] Ask About Clipboard");
+    let clipboard = crate::clipboard::read_text()?;
+    if clipboard.trim().is_empty() {
+        return Err("The clipboard has no text".to_string());
+    }
+    let clipboard: String = clipboard.chars().take(MAX_CLIPBOARD_CHARS).collect();
+    let question = question.map(str::trim).filter(|q| !q.is_empty()).unwrap_or("Explain this");
+    // A leading slash would route the whole command to a plugin tool
+    if question.starts_with('/') {
+        return Err("Plugin commands can't be asked about the clipboard".to_string());
+    }
+    let command = format!("{}:\n\n{}", question, clipboard);
+    let result = crate::pipeline_text::execute_text_command(app.clone(), command, app.state()).await?;
+    if result.status != "success" {
+        return Err(result.text);
+    }
+    Ok([Some(result.text), result.clipboard_content, result.command]
+        .into_iter()
+        .flatten()
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or_default())
+}
+
+/// Run an intent from a link: the result goes to the clipboard and a
+/// notification (Open shows it in the action menu).
+pub fn run_linked(app: &tauri::AppHandle, intent: Intent) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let (label, action_id, outcome) = match &intent {
+            Intent::OcrImage { path } => (t("intents.ocrImage"), "intent:ocr_image", ocr_image(Path::new(path)).await),
+            Intent::AskClipboard { question } => {
+                (t("intents.askClipboard"), "intent:ask_clipboard", ask_about_clipboard(&app, question.as_deref()).await)
+            }
+        };
+        let result = match outcome {
+            Ok(text) => {
+                if let Err(e) = crate::clipboard::write(&crate::clipboard::formats::Flavors::plain(&text)) {
+                    log::warn!("[INTENTS] Copy failed: {}", e);
+                }
+                text_result(action_id, text)
+            }
+            Err(e) => {
+                log::warn!("[INTENTS] {} failed: {}", label, e);
+                ActionResult::error(action_id, &e)
+            }
+        };
+        let key = if result.status == "success" { "notify.ready" } else { "notify.failed" };
+        let title = tf(key, &[("action", label.as_str())]);
+        notifications::notify(&app, Notice::for_result(title.clone(), &result), Reopen { snip_id: None, snip_text: title, result });
+    });
+}
+
+fn text_result(action_id: &str, text: String) -> ActionResult {
+    ActionResult {
+        status: "success".to_string(),
+        action_id: action_id.to_string(),
+        result: ActionResultBody {
+            result_type: "text".to_string(),
+            text: Some(text),
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: None,
+            email: None,
+        },
+        metadata: None,
+    }
+}
@@ -0,0 +1,84 @@
+//! Fuzzy matching for the launcher palette (pure, no I/O).
+//!
+//! A query matches when its characters appear in the candidate in order,
+//! ignoring case and spaces (`snscr` matches "Snip Screen"). The score
+//! favours matches at word starts and runs of consecutive characters, so
+//! "set" ranks "Settings" above "Reset history"; gaps cost a little.
+
+/// Points for every matched character.
+const MATCH: i32 = 1;
+/// Extra when a character starts a word.
+const WORD_START: i32 = 8;
+/// Extra when a character follows the previous match directly.
+const CONSECUTIVE: i32 = 5;
+/// Extra when the whole query is a prefix of the candidate.
+const PREFIX: i32 = 10;
+/// Cost per skipped character between matches (capped per gap).
+const GAP: i32 = 1;
+const MAX_GAP_COST: i32 = 5;
+
+/// Score of `candidate` for `query`, or `None` if it doesn't match. An
+/// empty query matches everything with 0.
+pub fn score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = candidate.chars().collect();
+    let mut total = 0;
+    let mut next = 0;
+    let mut last: Option<usize> = None;
+    for i in 0..chars.len() {
+        if next == query.len() {
+            break;
+        }
+        if !chars[i].to_lowercase().eq(std::iter::once(query[next])) {
+            continue;
+        }
+        total += MATCH;
+        if i == 0 || !chars[i - 1].is_alphanumeric() || (chars[i].is_uppercase() && chars[i - 1].is_lowercase()) {
+            total += WORD_START;
+        }
+        match last {
+            Some(prev) if prev + 1 == i => total += CONSECUTIVE,
+            Some(prev) => total -= (GAP * (i - prev - 1) as i32).min(MAX_GAP_COST),
+            None => {}
+        }
+        last = Some(i);
+        next += 1;
+    }
+    if next < query.len() {
+        return None;
+    }
+    let compact: String = query.iter().collect();
+    if candidate.to_lowercase().starts_with(&compact) {
+        total += PREFIX;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_in_order_ignoring_case_and_spaces() {
+        assert!(score("snscr", "Snip Screen").is_some());
+        assert!(score("SNIP", "snip screen").is_some());
+        assert!(score("snip scr", "Snip Screen").is_some());
+        assert_eq!(score("", "anything"), Some(0));
+        assert_eq!(score("rcs", "Snip Screen"), None);
+        assert_eq!(score("snipp", "Snip"), None);
+    }
+
+    #[test]
+    fn word_starts_and_runs_rank_higher() {
+        let settings = score("set", "Settings").unwrap();
+        let reset = score("set", "Reset history").unwrap();
+        assert!(settings > reset);
+        // Initials beat letters scattered inside words
+        assert!(score("ph", "Pause History").unwrap() > score("ph", "Graphs").unwrap());
+        // A run beats the same letters spread out inside words
+        assert!(score("copy", "/copy").unwrap() > score("copy", "Scope yard").unwrap());
+    }
+}
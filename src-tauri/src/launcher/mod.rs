@@ -0,0 +1,184 @@
+//! Launcher palette — what the text launcher offers as you type.
+//!
+//! The launcher window (`windows::Window::Launcher`, opened from the tray,
+//! a deep link, a trigger, or the `hotkeys.launcher` shortcut) asks
+//! `search_launcher` on every keystroke. Built-in actions, plugin slash
+//! commands, and history snips are fuzzy-matched (`fuzzy.rs`) and ranked
+//! together; picking one runs it with `run_launcher_item`. A query the
+//! user submits without picking anything goes to the LLM router
+//! (`execute_text_command`), as before, unless it is a
+//! `find on screen: <text>` command (`search/`), which the launcher runs
+//! itself.
+
+pub mod fuzzy;
+
+use crate::i18n::{t, tf};
+use crate::triggers::config::TriggerAction;
+use crate::windows::{self, spec::Window};
+use serde::Serialize;
+
+/// Items returned per query.
+const MAX_ITEMS: usize = 8;
+/// Newest history snips searched.
+const HISTORY_SCANNED: usize = 200;
+/// Characters of a snip shown as its title or detail.
+const PREVIEW_CHARS: usize = 80;
+/// Id prefix of history items; the snip id follows.
+const HISTORY_PREFIX: &str = "history:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemKind {
+    /// Run by `run_launcher_item`.
+    Action,
+    /// A plugin slash command; the launcher fills in `/name `.
+    Command,
+    /// A history snip; `run_launcher_item` reopens it.
+    History,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteItem {
+    pub id: String,
+    pub kind: ItemKind,
+    pub title: String,
+    pub detail: String,
+    #[serde(skip)]
+    score: i32,
+}
+
+impl PaletteItem {
+    fn new(id: String, kind: ItemKind, title: String, detail: String) -> Self {
+        Self { id, kind, title, detail, score: 0 }
+    }
+}
+
+/// One line of `text`, shortened for display.
+fn preview(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default();
+    match line.char_indices().nth(PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+fn actions() -> Vec<PaletteItem> {
+    let action = |id: &str, title: String, detail: &str| PaletteItem::new(id.to_string(), ItemKind::Action, title, t(detail));
+    let history = if crate::history::privacy::current().paused {
+        action("resume_history", t("launcher.resumeHistory"), "launcher.historyDetail")
+    } else {
+        action("pause_history", t("launcher.pauseHistory"), "launcher.historyDetail")
+    };
+    let mut items = vec![
+        action("snip", t("tray.snip"), "launcher.snipDetail"),
+        action("resnip_last", t("launcher.resnipLast"), "launcher.snipDetail"),
+        action("settings", t("launcher.settings"), "launcher.settingsDetail"),
+        action("find_on_screen", t("launcher.findOnScreen"), "launcher.findDetail"),
+        history,
+    ];
+    for (n, preset) in crate::settings::current().triggers.presets.iter().enumerate() {
+        let title = tf("launcher.preset", &[("name", &preset.name)]);
+        items.push(action(&format!("preset:{}", n + 1), title, "launcher.snipDetail"));
+    }
+    items
+}
+
+fn commands() -> Vec<PaletteItem> {
+    crate::mcp::slash_commands::get_launcher_commands()
+        .into_iter()
+        .map(|c| {
+            let detail = if c.command.description.is_empty() { c.usage.clone() } else { c.command.description.clone() };
+            PaletteItem::new(c.command.name.clone(), ItemKind::Command, format!("/{}", c.command.name), detail)
+        })
+        .collect()
+}
+
+fn history() -> Vec<PaletteItem> {
+    crate::history::commands::get_snip_history(Some(HISTORY_SCANNED), None, None, None)
+        .into_iter()
+        .map(|record| {
+            let (title, detail) = match record.summary.trim() {
+                "" => (preview(&record.text), record.content_type.clone()),
+                summary => (preview(summary), preview(&record.text)),
+            };
+            PaletteItem::new(format!("{}{}", HISTORY_PREFIX, record.id), ItemKind::History, title, detail)
+        })
+        .collect()
+}
+
+/// Keep the items matching `query`, best first. The title counts fully,
+/// the detail half; on a tie actions come before commands before history.
+fn rank(query: &str, items: Vec<PaletteItem>) -> Vec<PaletteItem> {
+    let mut matched: Vec<PaletteItem> = items
+        .into_iter()
+        .filter_map(|mut item| {
+            let detail = || fuzzy::score(query, &item.detail).map(|s| s / 2);
+            let score = fuzzy::score(query, &item.title).or_else(detail)?;
+            item.score = score;
+            Some(item)
+        })
+        .collect();
+    matched.sort_by(|a, b| b.score.cmp(&a.score).then(a.kind.cmp(&b.kind)));
+    matched.truncate(MAX_ITEMS);
+    matched
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: palette items for what is typed in the launcher.
+/// Nothing for an empty query, a slash command being typed (those get
+/// argument completions instead), or a find-on-screen command.
+#[tauri::command]
+pub fn search_launcher(query: String) -> Vec<PaletteItem> {
+    let query = query.trim();
+    if query.is_empty() || query.starts_with('/') || crate::search::matcher::query_of(query).is_some() {
+        return Vec::new();
+    }
+    let items = actions().into_iter().chain(commands()).chain(history()).collect();
+    rank(query, items)
+}
+
+/// Tauri command: run a palette action or reopen a history snip. The
+/// launcher closes first so a snip doesn't capture it.
+#[tauri::command]
+pub fn run_launcher_item(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    log::info!("[LAUNCHER] Running '{}'", id);
+    windows::close(&app, Window::Launcher)?;
+    if let Some(snip) = id.strip_prefix(HISTORY_PREFIX) {
+        let snip = snip.parse().map_err(|_| format!("Invalid history item '{}'", id))?;
+        return crate::deeplink::open_history(&app, snip);
+    }
+    match id.as_str() {
+        "settings" => windows::show(&app, Window::Settings),
+        "pause_history" | "resume_history" => crate::history::privacy::set_paused(&app, id == "pause_history"),
+        _ => crate::triggers::run(&app, TriggerAction::parse(&id)?, &crate::settings::current().triggers.presets),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(title: &str, kind: ItemKind) -> PaletteItem {
+        PaletteItem::new(title.to_lowercase(), kind, title.to_string(), String::new())
+    }
+
+    #[test]
+    fn best_matches_first_and_non_matches_dropped() {
+        let items = vec![
+            item("Reset the router", ItemKind::History),
+            item("Settings", ItemKind::Action),
+            item("Snip Screen", ItemKind::Action),
+        ];
+        let ids: Vec<String> = rank("set", items).into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, ["settings", "reset the router"]);
+    }
+
+    #[test]
+    fn previews_take_the_first_line_and_are_shortened() {
+        assert_eq!(preview("\n  Invoice 42  \nTotal: 10"), "Invoice 42");
+        let long = "x".repeat(PREVIEW_CHARS + 5);
+        assert_eq!(preview(&long).chars().count(), PREVIEW_CHARS + 1);
+    }
+}
@@ -4,30 +4,79 @@
 //! No business logic lives here — only module declarations, plugin
 //! registration, state management, and the command registry.
 //!
-//! Commands are split across:
+//! Every command is registered in commands_registry.rs; setup (tray,
+//! listeners, monitors) runs in boot/setup.rs. Commands are split across:
 //!   - commands.rs           — simple one-step commands (crop, close, clipboard, file I/O)
-//!   - pipeline.rs           — multi-step orchestration (process_snip, execute_action)
+//!   - pipeline.rs           — multi-step orchestration (process_snip)
+//!   - pipeline_execute.rs   — execute step (execute_action)
 //!   - settings_commands.rs  — settings panel + provider resolution
 
+mod boot;
+mod calendar;
 mod capture;
+mod clipboard;
 mod commands;
+mod commands_registry;
+mod compare;
+mod convert;
+mod crash;
+mod deeplink;
+mod desktop;
+mod diagnostics;
+mod document;
+mod editor;
+mod email;
+mod error;
+mod export;
+mod extract;
+pub mod history;
+mod i18n;
+mod intents;
+mod launcher;
+mod links;
 pub mod llm;
 pub mod mcp;
-mod ocr;
+mod net;
+mod notifications;
+pub mod ocr;
+mod onboarding;
+mod palette;
+mod persist;
+mod picture;
 mod pipeline;
+mod pipeline_execute;
+mod pipeline_prefetch;
 mod pipeline_text;
+pub mod policy;
+mod power;
+mod presenting;
+mod qr;
+mod replay;
 pub mod safety;
+mod search;
+mod selection;
 pub mod settings_commands;
+mod session;
+mod settings;
+mod share;
+mod stats;
+mod structured;
+mod thumbnails;
 mod tray;
+mod triggers;
+mod updater;
+mod webhooks;
+mod windows;
 
 use capture::CaptureState;
 use mcp::loader::PendingApprovals;
 use mcp::ToolRegistry;
-use tauri::Manager;
 
 /// Entry point — called by Tauri runtime.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    boot::mark_launch();
+
     // Load .env.local → .env from project root.
     // Uses CARGO_MANIFEST_DIR (compile-time path to src-tauri/) to reliably
     // find the project root regardless of the binary's working directory.
@@ -45,120 +94,48 @@ pub fn run() {
         }
     }
 
-    env_logger::init();
+    // Logs go to stderr as before and into a ring buffer for diagnostics.
+    diagnostics::logbuf::init();
+    // Pipeline stage spans, collected per snip for get_last_snip_trace.
+    diagnostics::spans::init();
+    // Panics anywhere are reported and recovered from (crash/mod.rs).
+    crash::install();
+
+    // Enterprise policy must be in place before any setting is resolved.
+    boot::phase("policy", policy::init);
 
     tauri::Builder::default()
+        // Must come first: a second launch (an omniglass:// link on
+        // Windows / Linux, CLI flags, or a plain relaunch) hands its
+        // arguments over and exits, so there is only ever one tray.
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| deeplink::handle_args(app, &args)))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
-        // Global shortcut plugin — available for opt-in hotkey binding.
-        // Not registered by default; tray menu is the primary entry point.
-        // .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
+        // Global shortcut plugin — bound only to hotkeys set in settings
+        // (plus a fallback snip key on Windows, settings/shortcut.rs);
+        // the tray menu stays the primary entry point.
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        // Login item, toggled from settings (settings/startup.rs).
+        .plugin(tauri_plugin_autostart::init(tauri_plugin_autostart::MacosLauncher::LaunchAgent, None))
+        // Signed self-updates; channels and scheduling in updater/.
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        // Screenshot and crop bytes for the webviews, straight from memory.
+        .register_asynchronous_uri_scheme_protocol(capture::protocol::SCHEME, capture::protocol::handle)
         .manage(CaptureState::new())
         .manage(llm::ActionMenuState::new())
         .manage(ToolRegistry::new())
         .manage(PendingApprovals::new())
-        .invoke_handler(tauri::generate_handler![
-            // Simple commands (commands.rs)
-            commands::crop_region,
-            commands::get_capture_info,
-            commands::get_ocr_text,
-            commands::copy_to_clipboard,
-            commands::close_overlay,
-            commands::close_action_menu,
-            commands::close_permission_prompt,
-            commands::get_action_menu,
-            commands::run_confirmed_command,
-            commands::write_to_desktop,
-            commands::write_file_to_path,
-            commands::close_text_launcher,
-            commands::close_tray_menu,
-            commands::start_snip,
-            commands::open_text_launcher,
-            commands::get_plugin_names,
-            // Pipeline commands (pipeline.rs / pipeline_text.rs)
-            pipeline::process_snip,
-            pipeline::execute_action,
-            pipeline_text::execute_text_command,
-            // Settings commands (settings_commands.rs)
-            settings_commands::get_provider_config,
-            settings_commands::set_active_provider,
-            settings_commands::save_api_key,
-            settings_commands::test_provider,
-            settings_commands::close_settings,
-            settings_commands::open_settings,
-            settings_commands::get_ocr_mode,
-            settings_commands::set_ocr_mode,
-            // MCP approval commands (approval_commands.rs)
-            mcp::approval_commands::get_pending_approvals,
-            mcp::approval_commands::approve_plugin,
-        ])
-        .setup(|app| {
-            log::info!("Omni-Glass starting up");
-
-            // Warm up Vision Framework to avoid cold-start penalty on first snip
-            let warm_start = std::time::Instant::now();
-            ocr::warm_up();
-            log::info!(
-                "[OCR] Vision warm-up complete in {}ms",
-                warm_start.elapsed().as_millis()
-            );
-
-            tray::setup_tray(app.handle())?;
-
-            // Load MCP plugins asynchronously (non-blocking).
-            // Register built-in tools first, then scan for external plugins.
-            let handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
-                let registry = handle.state::<ToolRegistry>();
-                let pending = handle.state::<PendingApprovals>();
-                mcp::builtins::register_builtins(&registry).await;
-                mcp::loader::load_plugins(&registry, &pending).await;
-
-                // If any plugins are queued for approval, open the prompt window
-                let has_pending = !pending.queue.lock().await.is_empty();
-                if has_pending {
-                    log::info!("[MCP] Opening permission prompt for pending plugins");
-                    let _ = tauri::WebviewWindowBuilder::new(
-                        &handle,
-                        "permission-prompt",
-                        tauri::WebviewUrl::App("permission-prompt.html".into()),
-                    )
-                    .title("Plugin Permissions")
-                    .inner_size(460.0, 380.0)
-                    .resizable(false)
-                    .center()
-                    .build();
-                }
-            });
-
-            log::info!("System tray initialized — ready for snips");
-            Ok(())
-        })
-        .run(tauri::generate_context!())
-        .expect("Error running Omni-Glass");
-}
-
-/// Open (or focus) the text launcher window.
-pub(crate) fn show_text_launcher(app: &tauri::AppHandle) {
-    if let Some(window) = app.get_webview_window("text-launcher") {
-        let _ = window.set_focus();
-        return;
-    }
-    match tauri::WebviewWindowBuilder::new(
-        app,
-        "text-launcher",
-        tauri::WebviewUrl::App("text-launcher.html".into()),
-    )
-    .title("Omni-Glass")
-    .inner_size(600.0, 72.0)
-    .resizable(false)
-    .decorations(false)
-    .transparent(true)
-    .always_on_top(true)
-    .center()
-    .build()
-    {
-        Ok(_) => log::info!("[TEXT_LAUNCHER] Window opened"),
-        Err(e) => log::error!("[TEXT_LAUNCHER] Failed to open: {}", e),
-    }
+        .manage(safety::outbound_guard::OutboundMonitor::new())
+        .invoke_handler(commands_registry::handler())
+        .setup(boot::setup::run)
+        .build(tauri::generate_context!())
+        .expect("Error running Omni-Glass")
+        .run(|_, event| {
+            // History is written in the background; finish the last write
+            if let tauri::RunEvent::Exit = event {
+                history::disk::flush();
+            }
+        });
 }
@@ -0,0 +1,199 @@
+//! URLs and domains in OCR text (pure, no I/O).
+//!
+//! Three shapes are found: `http(s)://…`, `www.…`, and bare domains
+//! (`docs.rs/regex`) whose top-level domain is a common one, so file
+//! names such as `main.rs` or `setup.py` aren't taken for links. Email
+//! addresses are skipped.
+//!
+//! OCR artifacts are repaired before a link is kept:
+//!   - a mangled scheme: `https ://`, `https;//`, `htps://`, `http5://`
+//!   - `1` / `0` read for `l` / `o` between letters of a host label
+//!     (`goog1e.com`), and `c0m` / `corn` for `com`
+//!   - trailing sentence punctuation and unbalanced closing brackets
+//!
+//! A link without a scheme gets `https://`. Hosts are lowercased.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Top-level domains accepted on a bare domain (no scheme, no `www.`).
+const BARE_TLDS: &[&str] = &[
+    "com", "org", "net", "io", "dev", "app", "ai", "co", "edu", "gov", "me", "info", "biz", "us", "uk", "de", "fr",
+    "es", "it", "nl", "ca", "au", "jp", "in", "ch", "se", "no", "fi", "dk", "pl", "br", "cn", "tv", "xyz", "gg", "ly",
+    "site", "online", "tech", "cloud", "page",
+];
+
+/// Top-level domains OCR commonly garbles, with their repair.
+const TLD_FIXES: &[(&str, &str)] = &[("c0m", "com"), ("corn", "com"), ("0rg", "org"), ("i0", "io")];
+
+static SCHEME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bh[tf]{1,3}p(s|5)?\s?[:;]?\s?/\s?/\s?").unwrap());
+static CANDIDATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)https?://[^\s<>"'`]+|\bwww\.[^\s<>"'`]+|\b(?:[a-z0-9-]+\.)+[a-z0-9]{2,6}(?:[:/][^\s<>"'`]*)?"#)
+        .unwrap()
+});
+
+/// A link found in the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found {
+    /// Normalized, with a scheme.
+    pub url: String,
+    pub host: String,
+    /// The link as OCR read it, when its host was repaired.
+    pub corrected_from: Option<String>,
+}
+
+/// Every link in `text`, in order of appearance, without duplicates.
+pub fn extract(text: &str) -> Vec<Found> {
+    let text = SCHEME_RE.replace_all(text, |c: &regex::Captures| {
+        if c.get(1).is_some() { "https://" } else { "http://" }
+    });
+    let mut found: Vec<Found> = Vec::new();
+    for m in CANDIDATE_RE.find_iter(&text) {
+        if text[..m.start()].ends_with(['@', '.', '/']) {
+            continue;
+        }
+        if let Some(link) = parse(m.as_str()).filter(|l| found.iter().all(|f| f.url != l.url)) {
+            found.push(link);
+        }
+    }
+    found
+}
+
+/// One candidate as a link, or `None` if it isn't one.
+pub fn parse(candidate: &str) -> Option<Found> {
+    let raw = trim_trailing(candidate.trim());
+    let (scheme, rest) = match raw.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme.to_ascii_lowercase()), rest),
+        None => (None, raw),
+    };
+    if scheme.as_deref().is_some_and(|s| s != "http" && s != "https") {
+        return None;
+    }
+    let split = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(split);
+    let (userinfo, host_port) = match authority.rsplit_once('@') {
+        Some((user, host)) => (Some(user), host),
+        None => (None, authority),
+    };
+    let (host, port) = match host_port.rsplit_once(':') {
+        Some((host, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => (host, Some(port)),
+        Some((host, "")) => (host, None),
+        _ => (host_port, None),
+    };
+    let original_host = host.trim_end_matches('.').to_lowercase();
+    let host = repair_host(&original_host);
+    let bare = scheme.is_none() && !host.starts_with("www.");
+    if !valid_host(&host, bare) {
+        return None;
+    }
+    let mut url = format!("{}://", scheme.as_deref().unwrap_or("https"));
+    if let Some(user) = userinfo {
+        url.push_str(user);
+        url.push('@');
+    }
+    url.push_str(&host);
+    if let Some(port) = port {
+        url.push(':');
+        url.push_str(port);
+    }
+    url.push_str(path);
+    let corrected_from = (host != original_host).then(|| raw.to_string());
+    Some(Found { url, host, corrected_from })
+}
+
+/// Drop sentence punctuation and closing brackets with no opening one.
+fn trim_trailing(mut s: &str) -> &str {
+    loop {
+        let Some(last) = s.chars().last() else { return s };
+        let open = match last {
+            ')' => '(',
+            ']' => '[',
+            '}' => '{',
+            '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '"' | '>' => {
+                s = &s[..s.len() - 1];
+                continue;
+            }
+            _ => return s,
+        };
+        if s.matches(open).count() >= s.matches(last).count() {
+            return s;
+        }
+        s = &s[..s.len() - 1];
+    }
+}
+
+fn repair_host(host: &str) -> String {
+    let mut labels: Vec<String> = host.split('.').map(str::to_string).collect();
+    let last = labels.len() - 1;
+    if let Some((_, fixed)) = TLD_FIXES.iter().find(|(bad, _)| *bad == labels[last]) {
+        labels[last] = fixed.to_string();
+    }
+    let is_ip = labels.iter().all(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_digit()));
+    if !is_ip {
+        for label in &mut labels[..last] {
+            *label = repair_label(label);
+        }
+    }
+    labels.join(".")
+}
+
+/// `1` → `l` and `0` → `o` between two letters.
+fn repair_label(label: &str) -> String {
+    let chars: Vec<char> = label.chars().collect();
+    let letter = |i: Option<usize>| i.and_then(|i| chars.get(i)).is_some_and(|c| c.is_ascii_alphabetic());
+    (0..chars.len())
+        .map(|i| match chars[i] {
+            '1' if letter(i.checked_sub(1)) && letter(Some(i + 1)) => 'l',
+            '0' if letter(i.checked_sub(1)) && letter(Some(i + 1)) => 'o',
+            c => c,
+        })
+        .collect()
+}
+
+fn valid_host(host: &str, bare: bool) -> bool {
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() < 2 || labels.iter().any(|l| l.is_empty() || l.starts_with('-') || l.ends_with('-')) {
+        return false;
+    }
+    if labels.len() == 4 && labels.iter().all(|l| l.parse::<u8>().is_ok()) {
+        return !bare;
+    }
+    let tld = labels[labels.len() - 1];
+    let chars_ok = labels.iter().all(|l| l.chars().all(|c| c.is_alphanumeric() || c == '-'));
+    let tld_ok = tld.chars().all(char::is_alphabetic) && tld.chars().count() >= 2;
+    chars_ok && tld_ok && (!bare || BARE_TLDS.contains(&tld))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(text: &str) -> Vec<String> {
+        extract(text).into_iter().map(|f| f.url).collect()
+    }
+
+    #[test]
+    fn links_are_found_and_normalized() {
+        let text = "Docs: https ://Docs.Rust-Lang.org/std/ (see www.example.com/a_(b)).\n\
+                    Mirror at github.com/rust-lang, mail me@corp.com, run main.rs or setup.py";
+        assert_eq!(
+            urls(text),
+            vec!["https://docs.rust-lang.org/std/", "https://www.example.com/a_(b)", "https://github.com/rust-lang"]
+        );
+        assert_eq!(urls("htps;//x.io/p?q=1, http5://y.dev."), vec!["https://x.io/p?q=1", "https://y.dev"]);
+        assert_eq!(urls("http://10.0.0.1:8080/admin and 10.0.0.2"), vec!["http://10.0.0.1:8080/admin"]);
+        assert!(urls("v1.2.3 e.g. node.js config.json").is_empty());
+    }
+
+    #[test]
+    fn ocr_artifacts_are_repaired() {
+        let found = extract("Visit goog1e.c0m/search today");
+        assert_eq!(found[0].url, "https://google.com/search");
+        assert_eq!(found[0].corrected_from.as_deref(), Some("goog1e.c0m/search"));
+        assert_eq!(parse("https://example.corn").unwrap().url, "https://example.com");
+        assert_eq!(parse("paypa1.com").unwrap().host, "paypa1.com");
+        assert_eq!(parse("https://web3.example.com").unwrap().corrected_from, None);
+        assert_eq!(parse("ftp://files.example.com"), None);
+    }
+}
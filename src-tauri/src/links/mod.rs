@@ -0,0 +1,89 @@
+//! Links domain — find URLs in a snip and open them safely.
+//!
+//! `extract.rs` finds links in the OCR text deterministically and repairs
+//! OCR artifacts (`goog1e.c0m`, `https ://`). `phishing.rs` flags links
+//! that look like phishing. The `open_url` action lists every link found;
+//! a single clean link opens right away, anything else waits for the user
+//! to pick one, and a flagged link opens only once the user confirms it.
+//! No LLM call is made.
+
+pub mod extract;
+pub mod phishing;
+
+use crate::i18n::t;
+use crate::llm::ActionMenuState;
+use serde::Serialize;
+use std::process::Command;
+
+/// Action id, answered by the action menu through the commands below.
+pub const OPEN_ACTION: &str = "open_url";
+
+/// A link in the snip, as shown in the disambiguation list.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    pub url: String,
+    pub host: String,
+    /// The text as OCR read it, when it was repaired.
+    pub corrected_from: Option<String>,
+    /// Localized phishing warnings; empty when none apply.
+    pub warnings: Vec<String>,
+}
+
+fn link(found: extract::Found) -> Link {
+    let warnings = phishing::warnings(&found.url, &found.host).into_iter().map(|w| t(&format!("links.{}", w))).collect();
+    Link { url: found.url, host: found.host, corrected_from: found.corrected_from, warnings }
+}
+
+/// The system handler for `url`: the browser, or the mail client for
+/// `mailto:`.
+#[cfg(target_os = "macos")]
+pub(crate) fn system_open(url: &str) -> Command {
+    let mut command = Command::new("open");
+    command.arg(url);
+    command
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn system_open(url: &str) -> Command {
+    let mut command = Command::new("rundll32");
+    command.args(["url.dll,FileProtocolHandler", url]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn system_open(url: &str) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(url);
+    command
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: every link in the snip in the action menu, normalized,
+/// with its phishing warnings.
+#[tauri::command]
+pub fn extract_links(state: tauri::State<'_, ActionMenuState>) -> Result<Vec<Link>, String> {
+    let text = state.ocr_text.lock().map_err(|e| e.to_string())?.clone().ok_or("No OCR text available — snip first")?;
+    let links: Vec<Link> = extract::extract(&text).into_iter().map(link).collect();
+    log::info!("[LINKS] {} link(s) found, {} flagged", links.len(), links.iter().filter(|l| !l.warnings.is_empty()).count());
+    Ok(links)
+}
+
+/// Tauri command: open `url` in the default browser. The link is checked
+/// again here; a flagged link needs `confirmed`.
+#[tauri::command]
+pub fn open_link(url: String, confirmed: bool) -> Result<(), String> {
+    let found = extract::parse(&url).filter(|f| f.url == url).ok_or_else(|| t("error.noLink"))?;
+    let warnings = phishing::warnings(&found.url, &found.host);
+    if !warnings.is_empty() && !confirmed {
+        return Err(t("error.linkNeedsConfirm"));
+    }
+    system_open(&found.url).spawn().map_err(|e| format!("Failed to open browser: {}", e))?;
+    if warnings.is_empty() {
+        log::info!("[LINKS] Opened {}", found.host);
+    } else {
+        log::warn!("[LINKS] Opened {} after confirmation ({})", found.host, warnings.join(", "));
+    }
+    Ok(())
+}
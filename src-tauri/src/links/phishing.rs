@@ -0,0 +1,123 @@
+//! Phishing-pattern heuristic for a link before it is opened (pure).
+//!
+//! This is no blocklist: it flags shapes that are common in phishing and
+//! rare in links people mean to open. Each warning is a code the caller
+//! turns into text (`links.<code>` in the locale files):
+//!
+//!   - `ipHost` — a raw IP address instead of a name
+//!   - `punycode` / `nonAscii` — an internationalized host that may
+//!     imitate a familiar one (`xn--pypal-4ve.com`)
+//!   - `userinfo` — `https://paypal.com@evil.io` really goes to `evil.io`
+//!   - `lookalike` — the domain spells a brand with digits or letter pairs
+//!     (`paypa1.com`, `rnicrosoft.com`)
+//!   - `brandMisuse` — a brand name as a word outside its own domain
+//!     (`paypal.com.secure-login.io`, `apple-id-verify.com`)
+//!   - `suspiciousTld` — a top-level domain phishing favours
+//!   - `deepSubdomain` — five or more labels
+//!   - `insecure` — plain `http://`
+
+/// Brands phishing most often imitates.
+const BRANDS: &[&str] = &[
+    "paypal", "google", "apple", "microsoft", "amazon", "facebook", "instagram", "netflix", "github", "linkedin",
+    "dropbox", "chase", "wellsfargo", "bankofamerica", "icloud", "outlook", "office365", "coinbase", "binance",
+];
+
+const SUSPICIOUS_TLDS: &[&str] =
+    &["zip", "mov", "xyz", "top", "tk", "ml", "ga", "cf", "gq", "click", "country", "work", "loan"];
+
+/// Second-level labels under which a registrable domain has three labels
+/// (`example.co.uk`).
+const SECOND_LEVELS: &[&str] = &["co", "com", "org", "net", "ac", "gov", "edu"];
+
+/// Lookalike spellings, undone to compare against `BRANDS`.
+const LOOKALIKES: &[(&str, &str)] = &[("rn", "m"), ("vv", "w"), ("1", "l"), ("0", "o"), ("3", "e"), ("5", "s")];
+
+/// Warning codes for `url` with host `host`, most serious first.
+pub fn warnings(url: &str, host: &str) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    let labels: Vec<&str> = host.split('.').collect();
+    let is_ip = labels.iter().all(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_digit()));
+    if is_ip {
+        out.push("ipHost");
+    }
+    if labels.iter().any(|l| l.starts_with("xn--")) {
+        out.push("punycode");
+    }
+    if !host.is_ascii() {
+        out.push("nonAscii");
+    }
+    let authority = url.split_once("://").map_or(url, |(_, rest)| rest);
+    if authority.split(['/', '?', '#']).next().is_some_and(|a| a.contains('@')) {
+        out.push("userinfo");
+    }
+    if !is_ip {
+        let registrable = registrable(&labels);
+        let name = registrable.first().copied().unwrap_or_default();
+        let has_brand = |label: &str| label.split('-').any(|word| BRANDS.contains(&word));
+        if !BRANDS.contains(&name) && BRANDS.contains(&undo_lookalikes(name).as_str()) {
+            out.push("lookalike");
+        } else if !BRANDS.contains(&name)
+            && (has_brand(name) || labels[..labels.len() - registrable.len()].iter().any(|l| has_brand(l)))
+        {
+            out.push("brandMisuse");
+        }
+        if SUSPICIOUS_TLDS.contains(labels.last().unwrap_or(&"")) {
+            out.push("suspiciousTld");
+        }
+    }
+    if labels.len() >= 5 {
+        out.push("deepSubdomain");
+    }
+    if url.starts_with("http://") {
+        out.push("insecure");
+    }
+    out
+}
+
+/// The labels that make up the registrable domain: the last two, or the
+/// last three for `co.uk`-style suffixes.
+fn registrable<'a>(labels: &[&'a str]) -> Vec<&'a str> {
+    let n = labels.len();
+    let take = if n >= 3 && labels[n - 1].len() == 2 && SECOND_LEVELS.contains(&labels[n - 2]) { 3 } else { 2 };
+    labels[n.saturating_sub(take)..].to_vec()
+}
+
+fn undo_lookalikes(label: &str) -> String {
+    LOOKALIKES.iter().fold(label.to_string(), |s, (fake, real)| s.replace(fake, real))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(url: &str) -> Vec<&'static str> {
+        let host = url.split("://").nth(1).unwrap().split(['/', ':']).next().unwrap();
+        let host = host.rsplit('@').next().unwrap();
+        warnings(url, host)
+    }
+
+    #[test]
+    fn ordinary_links_pass() {
+        for url in [
+            "https://docs.rust-lang.org/std/",
+            "https://github.com/rust-lang/rust",
+            "https://accounts.google.com/signin",
+            "https://www.bbc.co.uk/news",
+            "https://login.microsoft.com",
+        ] {
+            assert!(check(url).is_empty(), "{url}: {:?}", check(url));
+        }
+    }
+
+    #[test]
+    fn phishing_shapes_are_flagged() {
+        assert_eq!(check("https://paypa1.com/login"), vec!["lookalike"]);
+        assert_eq!(check("https://rnicrosoft.com"), vec!["lookalike"]);
+        assert_eq!(check("https://paypal.com.secure-login.io/x"), vec!["brandMisuse"]);
+        assert_eq!(check("https://apple-id-verify.top"), vec!["brandMisuse", "suspiciousTld"]);
+        assert_eq!(check("http://192.168.4.20/admin"), vec!["ipHost", "insecure"]);
+        assert_eq!(check("https://paypal.com@evil.io"), vec!["userinfo"]);
+        assert_eq!(check("https://xn--pypal-4ve.com"), vec!["punycode"]);
+        assert_eq!(check("https://a.b.c.d.example.com"), vec!["deepSubdomain"]);
+    }
+}
@@ -0,0 +1,107 @@
+//! JSON Schema validation for LLM-generated plugin tool arguments.
+//!
+//! Plugin tools publish an MCP `inputSchema`. Arguments produced by the
+//! args bridge are checked against it before the tool is called, and the
+//! error list is fed back to the LLM for one repair attempt.
+
+use serde_json::Value;
+
+/// Maximum validation errors reported back (keeps the repair prompt small).
+const MAX_ERRORS: usize = 10;
+
+/// Validate `args` against `schema`.
+///
+/// Returns human-readable errors (`/path: message`) on failure. A schema
+/// that itself fails to compile only gets the `required` field check —
+/// a broken plugin schema should not block every call.
+pub fn validate_args(args: &Value, schema: &Value) -> Result<(), Vec<String>> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(v) => v,
+        Err(e) => {
+            log::warn!("[ARGS_BRIDGE] Tool schema does not compile ({}); checking required fields only", e);
+            return check_required(args, schema);
+        }
+    };
+    let errors: Vec<String> = validator
+        .iter_errors(args)
+        .take(MAX_ERRORS)
+        .map(|e| {
+            let path = e.instance_path.to_string();
+            let path = if path.is_empty() { "/".to_string() } else { path };
+            format!("{}: {}", path, e)
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Fallback: every `required` property is present on an object.
+fn check_required(args: &Value, schema: &Value) -> Result<(), Vec<String>> {
+    let Some(required) = schema.get("required").and_then(|r| r.as_array()) else {
+        return Ok(());
+    };
+    let Some(obj) = args.as_object() else {
+        return Err(vec!["/: arguments must be a JSON object".to_string()]);
+    };
+    let missing: Vec<String> = required
+        .iter()
+        .filter_map(|f| f.as_str())
+        .filter(|name| !obj.contains_key(*name))
+        .map(|name| format!("/: missing required field \"{}\"", name))
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string", "minLength": 1 },
+                "priority": { "type": "string", "enum": ["low", "high"] },
+                "labels": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["title"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn accepts_valid_args() {
+        let args = json!({ "title": "Crash on save", "priority": "high", "labels": ["bug"] });
+        assert!(validate_args(&args, &issue_schema()).is_ok());
+    }
+
+    #[test]
+    fn reports_paths_for_each_error() {
+        let args = json!({ "priority": "urgent", "labels": [1], "extra": true });
+        let errors = validate_args(&args, &issue_schema()).unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("title")));
+        assert!(errors.iter().any(|e| e.starts_with("/priority")));
+        assert!(errors.iter().any(|e| e.starts_with("/labels/0")));
+        assert!(errors.iter().any(|e| e.contains("extra")));
+    }
+
+    #[test]
+    fn rejects_non_object() {
+        assert!(validate_args(&json!("just text"), &issue_schema()).is_err());
+    }
+
+    #[test]
+    fn broken_schema_falls_back_to_required_check() {
+        let schema = json!({ "type": "not-a-type", "required": ["title"] });
+        assert!(validate_args(&json!({ "title": "x" }), &schema).is_ok());
+        assert!(validate_args(&json!({}), &schema).is_err());
+    }
+}
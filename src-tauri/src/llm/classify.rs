@@ -2,22 +2,26 @@
 //!
 //! Streams the response and emits Tauri events as data becomes available:
 //! - "action-menu-skeleton" at TTFT (~300ms) with contentType + summary
+//! - "action-menu-item" for each action as its JSON object completes
 //! - "action-menu-complete" when the full ActionMenu JSON is parsed
 
-use super::prompts::{self, CLASSIFY_SYSTEM_PROMPT, MAX_TOKENS, MODEL};
+use super::prompts::{self, CLASSIFY_SYSTEM_PROMPT, MAX_TOKENS};
+use super::provider;
 use super::streaming;
-use super::types::{ActionMenu, ActionMenuSkeleton};
-use tauri::Emitter;
+use super::menu_stream::MenuStream;
+use super::types::ActionMenu;
+use tauri::{Emitter, Runtime};
 
 /// Call Claude API with streaming to classify OCR text.
 ///
 /// Emits Tauri events as the response streams:
 /// - "action-menu-skeleton" when contentType + summary are available
+/// - "action-menu-item" for each action as soon as it is complete
 /// - "action-menu-complete" when the full ActionMenu is parsed
 ///
 /// Always returns a valid ActionMenu (fallback on any error).
-pub async fn classify_streaming(
-    app: &tauri::AppHandle,
+pub async fn classify_streaming<R: Runtime>(
+    app: &tauri::AppHandle<R>,
     text: &str,
     has_table: bool,
     has_code: bool,
@@ -58,18 +62,18 @@ pub async fn classify_streaming(
     let user_message = prompts::build_classify_message(text, confidence, has_table, has_code, plugin_tools);
 
     log::info!("[LLM] Provider: anthropic (streaming)");
-    log::info!("[LLM] Model: {}", MODEL);
+    log::info!("[LLM] Model: {}", provider::model_for("anthropic"));
 
     let start = std::time::Instant::now();
 
-    let client = reqwest::Client::new();
+    let client = super::http::client("anthropic");
     let mut response = match client
-        .post("https://api.anthropic.com/v1/messages")
+        .post(format!("{}/v1/messages", super::http::api_base("anthropic")))
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
         .json(&serde_json::json!({
-            "model": MODEL,
+            "model": provider::model_for("anthropic"),
             "max_tokens": MAX_TOKENS,
             "stream": true,
             "system": CLASSIFY_SYSTEM_PROMPT,
@@ -111,7 +115,7 @@ pub async fn classify_streaming(
     // Stream SSE events, accumulate text content
     let mut accumulated_text = String::new();
     let mut sse_buffer = String::new();
-    let mut skeleton_emitted = false;
+    let mut menu_stream = MenuStream::new(start);
     let mut ttft_logged = false;
     let mut input_tokens: u64 = 0;
 
@@ -135,23 +139,8 @@ pub async fn classify_streaming(
                                 }
                                 accumulated_text.push_str(&text_delta);
 
-                                // Try to extract skeleton from partial JSON
-                                if !skeleton_emitted {
-                                    if let Some((ct, summary)) =
-                                        streaming::try_extract_skeleton(&accumulated_text)
-                                    {
-                                        let skeleton = ActionMenuSkeleton {
-                                            content_type: ct,
-                                            summary,
-                                        };
-                                        log::info!(
-                                            "[LLM] Skeleton emitted at {}ms",
-                                            start.elapsed().as_millis()
-                                        );
-                                        let _ = app.emit("action-menu-skeleton", &skeleton);
-                                        skeleton_emitted = true;
-                                    }
-                                }
+                                // Skeleton, then each action, from the partial JSON
+                                menu_stream.update(app, &accumulated_text);
                             }
                         }
                         "message_start" => {
@@ -183,6 +172,7 @@ pub async fn classify_streaming(
                                         + output_tokens as f64 * 4.0)
                                         / 1_000_000.0;
                                     log::info!("[LLM] Estimated cost: ${:.6}", cost);
+                                    crate::stats::record_llm_call("anthropic", input_tokens, output_tokens, cost, start.elapsed().as_millis() as u64);
                                 }
                             }
                         }
@@ -200,6 +190,7 @@ pub async fn classify_streaming(
 
     let api_ms = start.elapsed().as_millis();
     eprintln!("[CLASSIFY] Stream complete: {}ms, accumulated {} chars", api_ms, accumulated_text.len());
+    crate::replay::record_classify(&accumulated_text);
 
     // Parse the full accumulated text as ActionMenu
     let json_str = streaming::strip_code_fences(&accumulated_text);
@@ -247,13 +238,13 @@ pub async fn classify(
     );
     let start = std::time::Instant::now();
 
-    let response = reqwest::Client::new()
-        .post("https://api.anthropic.com/v1/messages")
+    let response = super::http::client("anthropic")
+        .post(format!("{}/v1/messages", super::http::api_base("anthropic")))
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
         .json(&serde_json::json!({
-            "model": MODEL,
+            "model": provider::model_for("anthropic"),
             "max_tokens": MAX_TOKENS,
             "system": CLASSIFY_SYSTEM_PROMPT,
             "messages": [{"role": "user", "content": user_message}]
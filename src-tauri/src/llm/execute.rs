@@ -7,16 +7,19 @@
 //! Unlike CLASSIFY, EXECUTE does NOT stream progressively to the UI.
 //! The user already clicked a button and expects a brief wait.
 
+use crate::email::draft::EmailDraft;
 use crate::safety;
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 
+use super::agent::{self, extract_anthropic_text};
 use super::prompts_execute::{self, EXECUTE_MAX_TOKENS, EXECUTE_SYSTEM_PROMPT};
 use super::streaming;
 
 // ── Types ──────────────────────────────────────────────────────────
 
 /// The result of an EXECUTE action, returned by the LLM.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionResult {
     pub status: String, // "success" | "error" | "needs_confirmation"
@@ -25,23 +28,30 @@ pub struct ActionResult {
     pub metadata: Option<ActionResultMetadata>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionResultBody {
     #[serde(rename = "type")]
-    pub result_type: String, // "text" | "file" | "command" | "clipboard"
+    pub result_type: String, // "text" | "file" | "command" | "clipboard" | "email"
     pub text: Option<String>,
     pub file_path: Option<String>,
     pub command: Option<String>,
     pub clipboard_content: Option<String>,
     pub mime_type: Option<String>,
+    /// Recipients, subject, and body of an `"email"` result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailDraft>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionResultMetadata {
     pub tokens_used: Option<u32>,
     pub processing_note: Option<String>,
+    /// Set when served from the history cache: when the result was
+    /// first produced (Unix seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_at: Option<u64>,
 }
 
 impl ActionResult {
@@ -57,6 +67,7 @@ impl ActionResult {
                 command: None,
                 clipboard_content: None,
                 mime_type: None,
+                email: None,
             },
             metadata: None,
         }
@@ -70,80 +81,56 @@ impl ActionResult {
 /// Steps:
 /// 1. Pre-flight: redact sensitive data
 /// 2. Build action-specific user message
-/// 3. Call Claude (non-streaming — accumulate full response)
+/// 3. Call Claude (non-streaming; may read plugin resources — `agent.rs`)
 /// 4. Parse ActionResult JSON
 /// 5. Post-flight: validate command safety
 pub async fn execute_action_anthropic(
     action_id: &str,
     extracted_text: &str,
+    registry: &crate::mcp::ToolRegistry,
 ) -> ActionResult {
-    let start = std::time::Instant::now();
-
     // 1. Pre-flight: redact sensitive data before sending to cloud
-    let redaction = safety::redact::redact_sensitive_data(extracted_text);
+    let redaction = tracing::info_span!("redact").in_scope(|| safety::redact::redact_sensitive_data(extracted_text));
     let clean_text = &redaction.cleaned_text;
 
     // 2. Build the action-specific user message
     let user_message = prompts_execute::build_execute_message(action_id, clean_text, "macos");
     log::info!("[EXECUTE] Action: {}, text length: {}", action_id, clean_text.len());
 
-    // 3. Call Claude API (non-streaming, accumulate full response)
-    let api_key = match std::env::var("ANTHROPIC_API_KEY") {
-        Ok(k) if !k.is_empty() => {
-            eprintln!("[EXECUTE] API key found ({} chars)", k.len());
-            k
-        }
-        _ => {
-            eprintln!("[EXECUTE] ERROR: No API key");
-            return ActionResult::error(action_id, "No API key configured. Add your Anthropic API key in Settings.");
+    // 3. Call Claude API (non-streaming, accumulate full response) —
+    // or, for a replayed snip, take its recorded response (replay/)
+    let response = match crate::replay::execute_body(action_id) {
+        Some(recorded) => recorded,
+        None => {
+            let api_key = match std::env::var("ANTHROPIC_API_KEY") {
+                Ok(k) if !k.is_empty() => {
+                    eprintln!("[EXECUTE] API key found ({} chars)", k.len());
+                    k
+                }
+                _ => {
+                    eprintln!("[EXECUTE] ERROR: No API key");
+                    return ActionResult::error(action_id, "No API key configured. Add your Anthropic API key in Settings.");
+                }
+            };
+            let messages = vec![serde_json::json!({"role": "user", "content": user_message})];
+            let request = agent::send_messages(&api_key, EXECUTE_SYSTEM_PROMPT, EXECUTE_MAX_TOKENS, messages, registry);
+            request.instrument(tracing::info_span!("llm_request")).await
         }
     };
-
-    let model = super::prompts::MODEL;
-    let client = reqwest::Client::new();
-    let resp = client
-        .post("https://api.anthropic.com/v1/messages")
-        .header("x-api-key", &api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("content-type", "application/json")
-        .json(&serde_json::json!({
-            "model": model,
-            "max_tokens": EXECUTE_MAX_TOKENS,
-            "system": EXECUTE_SYSTEM_PROMPT,
-            "messages": [{"role": "user", "content": user_message}]
-        }))
-        .send()
-        .await;
-
-    let resp = match resp {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("[EXECUTE] HTTP request FAILED: {}", e);
-            log::error!("[EXECUTE] API request failed: {}", e);
-            return ActionResult::error(action_id, &format!("API request failed: {}", e));
+    let body = match response {
+        Ok(b) => {
+            crate::replay::record_execute(action_id, &b);
+            b
         }
-    };
-
-    let status_code = resp.status();
-    let body = match resp.text().await {
-        Ok(b) => b,
         Err(e) => {
-            eprintln!("[EXECUTE] Failed to read response body: {}", e);
-            return ActionResult::error(action_id, &format!("Failed to read response: {}", e));
+            eprintln!("[EXECUTE] Request FAILED: {}", e);
+            log::error!("[EXECUTE] {}", e);
+            return ActionResult::error(action_id, &e);
         }
     };
 
-    if !status_code.is_success() {
-        eprintln!("[EXECUTE] API error {}: {}", status_code, &body[..500.min(body.len())]);
-        log::error!("[EXECUTE] API returned {}: {}", status_code, &body[..200.min(body.len())]);
-        return ActionResult::error(action_id, &format!("API error ({})", status_code));
-    }
-
     eprintln!("[EXECUTE] API returned 200, {} bytes", body.len());
 
-    let llm_ms = start.elapsed().as_millis();
-    log::info!("[EXECUTE] LLM response in {}ms", llm_ms);
-
     // 4. Extract text content from Anthropic response
     let response_text = extract_anthropic_text(&body);
     let response_text = match response_text {
@@ -187,8 +174,15 @@ pub async fn execute_action_anthropic(
         result.result.result_type
     );
 
-    // 6. Post-flight: command safety check
+    // 6. Post-flight: command policy + safety check
     if result.result.result_type == "command" {
+        if !crate::policy::precedence::is_command_execution_allowed(crate::policy::current()) {
+            log::warn!("[EXECUTE] Command result suppressed by policy");
+            return ActionResult::error(
+                action_id,
+                "Command execution is disabled by your organization's policy",
+            );
+        }
         if let Some(ref cmd) = result.result.command {
             let check = safety::command_check::is_command_safe(cmd);
             if !check.safe {
@@ -240,6 +234,7 @@ fn salvage_text_from_json(raw: &str, action_id: &str) -> Option<ActionResult> {
             command,
             clipboard_content: None,
             mime_type: None,
+            email: None,
         },
         metadata: None,
     })
@@ -279,15 +274,3 @@ fn extract_json_string_field(json: &str, key: &str) -> Option<String> {
         search_from = abs_pos + pattern.len();
     }
 }
-
-/// Extract the text content from an Anthropic Messages API response.
-fn extract_anthropic_text(body: &str) -> Option<String> {
-    let parsed: serde_json::Value = serde_json::from_str(body).ok()?;
-    let content = parsed.get("content")?.as_array()?;
-    for block in content {
-        if block.get("type")?.as_str()? == "text" {
-            return block.get("text")?.as_str().map(|s| s.to_string());
-        }
-    }
-    None
-}
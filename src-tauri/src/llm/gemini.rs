@@ -2,6 +2,7 @@
 //!
 //! Mirrors the Anthropic streaming implementation in classify.rs:
 //! - "action-menu-skeleton" emitted when contentType + summary are parsed
+//! - "action-menu-item" emitted for each action as its object completes
 //! - "action-menu-complete" emitted when full ActionMenu JSON is available
 //!
 //! Key differences from Anthropic:
@@ -13,8 +14,9 @@
 
 use super::prompts::CLASSIFY_SYSTEM_PROMPT;
 use super::streaming;
-use super::types::{ActionMenu, ActionMenuSkeleton};
-use tauri::Emitter;
+use super::menu_stream::MenuStream;
+use super::types::ActionMenu;
+use tauri::{Emitter, Runtime};
 
 pub const GEMINI_MODEL: &str = "gemini-2.0-flash";
 pub const GEMINI_MAX_TOKENS: u32 = 512;
@@ -29,10 +31,11 @@ const OUTPUT_COST_PER_MILLION: f64 = 0.40;
 ///
 /// Same contract as `classify_streaming` in classify.rs:
 /// - Emits "action-menu-skeleton" as soon as contentType + summary are available
+/// - Emits "action-menu-item" for each action as soon as it is complete
 /// - Emits "action-menu-complete" when the full JSON is parsed
 /// - Always returns a valid ActionMenu (fallback on any error)
-pub async fn classify_streaming_gemini(
-    app: &tauri::AppHandle,
+pub async fn classify_streaming_gemini<R: Runtime>(
+    app: &tauri::AppHandle<R>,
     text: &str,
     has_table: bool,
     has_code: bool,
@@ -59,17 +62,20 @@ pub async fn classify_streaming_gemini(
     let user_message = super::prompts::build_classify_message(text, confidence, has_table, has_code, plugin_tools);
 
     log::info!("[LLM] Provider: gemini (streaming)");
-    log::info!("[LLM] Model: {}", GEMINI_MODEL);
+    let model = super::provider::model_for("gemini");
+    log::info!("[LLM] Model: {}", model);
 
     let start = std::time::Instant::now();
 
     // Gemini streaming endpoint — API key in URL query param
     let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
-        GEMINI_MODEL, api_key
+        "{}/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+        super::http::api_base("gemini"),
+        model,
+        api_key
     );
 
-    let client = reqwest::Client::new();
+    let client = super::http::client("gemini");
     let mut response = match client
         .post(&url)
         .header("content-type", "application/json")
@@ -124,7 +130,7 @@ pub async fn classify_streaming_gemini(
     // Stream SSE events, accumulate text content
     let mut accumulated_text = String::new();
     let mut sse_buffer = String::new();
-    let mut skeleton_emitted = false;
+    let mut menu_stream = MenuStream::new(start);
     let mut ttft_logged = false;
     let mut input_tokens: u64 = 0;
     let mut output_tokens: u64 = 0;
@@ -148,23 +154,8 @@ pub async fn classify_streaming_gemini(
                         }
                         accumulated_text.push_str(&text_delta);
 
-                        // Try to extract skeleton from partial JSON
-                        if !skeleton_emitted {
-                            if let Some((ct, summary)) =
-                                streaming::try_extract_skeleton(&accumulated_text)
-                            {
-                                let skeleton = ActionMenuSkeleton {
-                                    content_type: ct,
-                                    summary,
-                                };
-                                log::info!(
-                                    "[LLM] Skeleton emitted at {}ms",
-                                    start.elapsed().as_millis()
-                                );
-                                let _ = app.emit("action-menu-skeleton", &skeleton);
-                                skeleton_emitted = true;
-                            }
-                        }
+                        // Skeleton, then each action, from the partial JSON
+                        menu_stream.update(app, &accumulated_text);
                     }
 
                     // Extract token usage from usageMetadata (present in final chunk)
@@ -186,6 +177,7 @@ pub async fn classify_streaming_gemini(
 
     let api_ms = start.elapsed().as_millis();
     log::info!("[LLM] Stream complete: {}ms", api_ms);
+    crate::replay::record_classify(&accumulated_text);
 
     // Log token usage and cost
     if input_tokens > 0 || output_tokens > 0 {
@@ -195,6 +187,7 @@ pub async fn classify_streaming_gemini(
             + output_tokens as f64 * OUTPUT_COST_PER_MILLION)
             / 1_000_000.0;
         log::info!("[LLM] Estimated cost: ${:.6}", cost);
+        crate::stats::record_llm_call("gemini", input_tokens, output_tokens, cost, api_ms as u64);
     }
 
     // Parse accumulated text as ActionMenu
@@ -0,0 +1,104 @@
+//! Pooled HTTP clients for the LLM providers.
+//!
+//! One client per provider, built from `net::client_builder()` (proxy and
+//! CA settings) and kept between snips, so the TLS connection to the
+//! provider's API outlives a single classify or execute call: idle
+//! connections stay pooled, HTTP/2 pings keep them open, and TCP
+//! keep-alive notices dead ones. A network settings change (`net::reset`)
+//! rebuilds them. `preconnect` opens the connection while the user is
+//! still selecting, so classify's first byte doesn't wait on a handshake.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Whole-request limit; a streamed classify or an execute ends far sooner.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 4;
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+const HTTP2_PING_INTERVAL: Duration = Duration::from_secs(30);
+const HTTP2_PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Clients by provider, and the `net::epoch` they were built in.
+static CLIENTS: Mutex<Option<(u64, HashMap<&'static str, reqwest::Client>)>> = Mutex::new(None);
+
+/// Canonical provider id; anything unknown is Anthropic, as in `provider::model_for`.
+fn provider_key(provider: &str) -> &'static str {
+    match provider {
+        "gemini" => "gemini",
+        _ => "anthropic",
+    }
+}
+
+/// Origin of a provider's API. `OMNI_GLASS_ANTHROPIC_URL` and
+/// `OMNI_GLASS_GEMINI_URL` override it (the mock server in
+/// `tests/classify_golden.rs` uses them).
+pub fn api_base(provider: &str) -> String {
+    let (env, default) = match provider_key(provider) {
+        "gemini" => ("OMNI_GLASS_GEMINI_URL", "https://generativelanguage.googleapis.com"),
+        _ => ("OMNI_GLASS_ANTHROPIC_URL", "https://api.anthropic.com"),
+    };
+    std::env::var(env)
+        .ok()
+        .filter(|url| !url.is_empty())
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| default.to_string())
+}
+
+fn build() -> Result<reqwest::Client, String> {
+    crate::net::client_builder()?
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .http2_keep_alive_interval(HTTP2_PING_INTERVAL)
+        .http2_keep_alive_timeout(HTTP2_PING_TIMEOUT)
+        .http2_keep_alive_while_idle(true)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// The pooled client for `provider`. If it can't be built, logs why and
+/// returns the shared `net::client()`.
+pub fn client(provider: &str) -> reqwest::Client {
+    let epoch = crate::net::epoch();
+    let mut guard = CLIENTS.lock().unwrap_or_else(|e| e.into_inner());
+    let (built_in, clients) = guard.get_or_insert_with(|| (epoch, HashMap::new()));
+    if *built_in != epoch {
+        clients.clear();
+        *built_in = epoch;
+    }
+    clients
+        .entry(provider_key(provider))
+        .or_insert_with(|| {
+            build().unwrap_or_else(|e| {
+                log::error!("[LLM] {} — using the shared client", e);
+                crate::net::client()
+            })
+        })
+        .clone()
+}
+
+/// Open (or keep warm) the connection to the active provider's API in
+/// the background. Skipped in local-only or offline mode and when the
+/// provider has no key.
+pub fn preconnect() {
+    tauri::async_runtime::spawn(async {
+        if crate::settings::current().local_only || !crate::net::connectivity::status().online {
+            return;
+        }
+        let provider = crate::settings_commands::resolve_provider();
+        if !super::provider::is_provider_configured(&provider) {
+            return;
+        }
+        let start = Instant::now();
+        let url = api_base(&provider);
+        match client(&provider).head(&url).send().await {
+            Ok(_) => log::info!("[LLM] Connection to {} ready in {}ms", url, start.elapsed().as_millis()),
+            Err(e) => log::debug!("[LLM] Preconnect to {} failed: {}", url, e),
+        }
+    });
+}
@@ -0,0 +1,169 @@
+//! Image budgets for vision calls (pure, no I/O).
+//!
+//! A snip sent to a vision model is billed by its pixels and slowed by
+//! its upload size, and a Retina crop can be 4000×3000. `fit` brings a
+//! crop within the provider's budget before it is attached:
+//!   - downscale to the provider's optimal resolution (long edge and
+//!     total pixels), keeping the aspect ratio
+//!   - keep the lossless PNG when it fits the size cap (text stays crisp),
+//!     else fall back to JPEG at decreasing quality, shrinking further only
+//!     if that is still too big
+//!
+//! The crop's existing PNG is reused untouched when it is already within
+//! budget. Before/after sizes are logged.
+
+use crate::capture::encode;
+use base64::Engine;
+use image::imageops::FilterType;
+use image::DynamicImage;
+
+/// JPEG qualities tried, in order, when the PNG is over the size cap.
+const JPEG_QUALITIES: [u8; 3] = [85, 70, 55];
+/// Scale applied per round when even the lowest JPEG quality is too big.
+const SHRINK_STEP: f64 = 0.75;
+
+/// Limits for one provider's image input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageBudget {
+    pub max_long_edge: u32,
+    pub max_pixels: u64,
+    /// Encoded bytes, before base64.
+    pub max_bytes: usize,
+}
+
+/// Budget for `provider`'s vision input.
+///
+/// Anthropic resizes anything over 1568px on the long edge or ~1.15MP
+/// server-side anyway, and caps an image at 5MB of base64. Gemini tiles
+/// images into 768px squares, each billed alike, so 2×2 tiles is the
+/// sweet spot for a snip; the inline request limit is far above our cap.
+pub fn budget_for(provider: &str) -> ImageBudget {
+    match provider {
+        "gemini" => ImageBudget { max_long_edge: 1536, max_pixels: 1536 * 1536, max_bytes: 7 * 1024 * 1024 },
+        _ => ImageBudget { max_long_edge: 1568, max_pixels: 1_150_000, max_bytes: 3_750_000 },
+    }
+}
+
+/// An encoded image within budget, ready to attach.
+#[derive(Debug, Clone)]
+pub struct FittedImage {
+    pub bytes: Vec<u8>,
+    pub media_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FittedImage {
+    fn base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(&self.bytes)
+    }
+
+    /// Anthropic Messages `image` content block.
+    pub fn anthropic_block(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": self.media_type, "data": self.base64() }
+        })
+    }
+
+    /// Gemini `inline_data` part.
+    pub fn gemini_part(&self) -> serde_json::Value {
+        serde_json::json!({ "inline_data": { "mime_type": self.media_type, "data": self.base64() } })
+    }
+}
+
+/// Dimensions within the budget's long edge and pixel count.
+fn target_size(width: u32, height: u32, budget: &ImageBudget) -> (u32, u32) {
+    let long = width.max(height).max(1) as f64;
+    let pixels = (width as u64 * height as u64).max(1) as f64;
+    let scale = (budget.max_long_edge as f64 / long).min((budget.max_pixels as f64 / pixels).sqrt()).min(1.0);
+    let scaled = |side: u32| ((side as f64 * scale).round() as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+/// Smallest acceptable encoding of `image` under `max_bytes`, if any.
+fn encode_within(image: &DynamicImage, max_bytes: usize) -> Result<Option<(Vec<u8>, &'static str)>, String> {
+    let png = encode::png_fast(image)?;
+    if png.len() <= max_bytes {
+        return Ok(Some((png, "image/png")));
+    }
+    for quality in JPEG_QUALITIES {
+        let jpeg = encode::preview_jpeg(image, quality)?;
+        if jpeg.len() <= max_bytes {
+            return Ok(Some((jpeg, "image/jpeg")));
+        }
+    }
+    Ok(None)
+}
+
+/// `image` fitted to `budget`. `png` is the crop's existing encoding,
+/// reused as-is when no resize or re-encode is needed.
+pub fn fit(image: &DynamicImage, png: Option<&[u8]>, budget: &ImageBudget) -> Result<FittedImage, String> {
+    let (width, height) = (image.width(), image.height());
+    let (mut w, mut h) = target_size(width, height, budget);
+    let before = png.map_or(String::new(), |p| format!(" ({} KB)", p.len() / 1024));
+
+    if (w, h) == (width, height) {
+        if let Some(png) = png.filter(|p| p.len() <= budget.max_bytes) {
+            log::info!("[LLM] Image {}x{}{} within budget, sent as-is", width, height, before);
+            return Ok(FittedImage { bytes: png.to_vec(), media_type: "image/png", width, height });
+        }
+    }
+    loop {
+        let resized = if (w, h) == (width, height) { image.clone() } else { image.resize_exact(w, h, FilterType::CatmullRom) };
+        if let Some((bytes, media_type)) = encode_within(&resized, budget.max_bytes)? {
+            log::info!(
+                "[LLM] Image {}x{}{} → {}x{} {} ({} KB)",
+                width, height, before, w, h, media_type, bytes.len() / 1024
+            );
+            return Ok(FittedImage { bytes, media_type, width: w, height: h });
+        }
+        if w <= 1 && h <= 1 {
+            return Err(format!("Image can't fit in {} bytes", budget.max_bytes));
+        }
+        (w, h) = (((w as f64 * SHRINK_STEP) as u32).max(1), ((h as f64 * SHRINK_STEP) as u32).max(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn noisy(width: u32, height: u32) -> DynamicImage {
+        let mut seed = 7u32;
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |_, _| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let [a, b, c, _] = seed.to_le_bytes();
+            Rgba([a, b, c, 255])
+        }))
+    }
+
+    #[test]
+    fn downscales_to_the_long_edge_and_pixel_budget() {
+        let budget = ImageBudget { max_long_edge: 400, max_pixels: 60_000, max_bytes: usize::MAX };
+        assert_eq!(target_size(4000, 3000, &budget), (283, 212));
+        assert_eq!(target_size(800, 100, &budget), (400, 50));
+        assert_eq!(target_size(120, 80, &budget), (120, 80));
+
+        let small = noisy(40, 30);
+        let png = encode::png_fast(&small).unwrap();
+        let fitted = fit(&small, Some(&png), &budget).unwrap();
+        assert_eq!((fitted.bytes, fitted.media_type), (png, "image/png"));
+    }
+
+    #[test]
+    fn falls_back_to_jpeg_then_shrinks_to_meet_the_size_cap() {
+        let image = noisy(300, 200);
+        let png_len = encode::png_fast(&image).unwrap().len();
+        let budget = ImageBudget { max_long_edge: 1000, max_pixels: 1_000_000, max_bytes: png_len / 3 };
+        let fitted = fit(&image, None, &budget).unwrap();
+        assert_eq!(fitted.media_type, "image/jpeg");
+        assert!(fitted.bytes.len() <= budget.max_bytes);
+
+        let tiny = ImageBudget { max_bytes: 2_000, ..budget };
+        let fitted = fit(&image, None, &tiny).unwrap();
+        assert!(fitted.bytes.len() <= 2_000);
+        assert!(fitted.width < 300 && fitted.height < 200);
+    }
+}
@@ -0,0 +1,185 @@
+//! Incremental classify output — the menu appears piece by piece.
+//!
+//! Providers accumulate the streamed JSON text and hand it to
+//! `MenuStream::update` after every delta. It emits:
+//!   - "action-menu-skeleton" once contentType + summary are complete
+//!   - "action-menu-item" for each object in `actions` as soon as its
+//!     closing brace arrives, so the frontend renders actions one by one
+//!
+//! `ActionScanner` walks only the text added since the last call,
+//! keeping just enough state (nesting depth, string/escape, the last key)
+//! to know where each action object starts and ends. "action-menu-complete"
+//! still carries the authoritative, fully parsed menu.
+//!
+//! `replay_classify` plays recorded classify text through the same events
+//! (`replay/`).
+
+use super::types::{Action, ActionMenu, ActionMenuSkeleton};
+use std::time::Duration;
+use tauri::{Emitter, Runtime};
+
+/// Delta size and pace of a replayed classify stream.
+const REPLAY_DELTA_BYTES: usize = 24;
+const REPLAY_DELTA_INTERVAL: Duration = Duration::from_millis(15);
+
+/// Finds complete objects in the top-level `actions` array of a
+/// growing JSON text.
+#[derive(Debug, Default)]
+pub struct ActionScanner {
+    /// Bytes of the text already scanned.
+    pos: usize,
+    /// Seen the top-level `{` (anything before it, like a code fence, is skipped).
+    started: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    string_start: usize,
+    /// Last string closed at the top level — the key of the next value.
+    last_key: String,
+    /// Depth inside the `actions` array while it is open.
+    actions_depth: Option<usize>,
+    object_start: Option<usize>,
+}
+
+impl ActionScanner {
+    /// Actions completed by the text added since the last call. `text` is
+    /// the whole accumulated output; it only ever grows.
+    pub fn feed(&mut self, text: &str) -> Vec<Action> {
+        let bytes = text.as_bytes();
+        let mut found = Vec::new();
+        for i in self.pos..bytes.len() {
+            let b = bytes[i];
+            if self.in_string {
+                match (self.escaped, b) {
+                    (true, _) => self.escaped = false,
+                    (false, b'\\') => self.escaped = true,
+                    (false, b'"') => {
+                        self.in_string = false;
+                        if self.depth == 1 {
+                            self.last_key = text[self.string_start..i].to_string();
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            if !self.started {
+                self.started = b == b'{';
+                self.depth = usize::from(self.started);
+                continue;
+            }
+            match b {
+                b'"' => {
+                    self.in_string = true;
+                    self.string_start = i + 1;
+                }
+                b'[' | b'{' => {
+                    if b == b'[' && self.depth == 1 && self.last_key == "actions" {
+                        self.actions_depth = Some(2);
+                    } else if b == b'{' && self.actions_depth == Some(self.depth) {
+                        self.object_start = Some(i);
+                    }
+                    self.depth += 1;
+                }
+                b']' | b'}' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    match (self.actions_depth, self.object_start) {
+                        (Some(d), Some(start)) if b == b'}' && self.depth == d => {
+                            self.object_start = None;
+                            if let Ok(action) = serde_json::from_str::<Action>(&text[start..=i]) {
+                                found.push(action);
+                            }
+                        }
+                        (Some(_), _) if b == b']' && self.depth == 1 => self.actions_depth = None,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.pos = bytes.len();
+        found
+    }
+}
+
+/// Streaming state of one classify call.
+pub struct MenuStream {
+    start: std::time::Instant,
+    skeleton_emitted: bool,
+    actions: ActionScanner,
+}
+
+impl MenuStream {
+    pub fn new(start: std::time::Instant) -> Self {
+        Self { start, skeleton_emitted: false, actions: ActionScanner::default() }
+    }
+
+    /// Emit whatever `accumulated` has completed since the last call.
+    pub fn update<R: Runtime>(&mut self, app: &tauri::AppHandle<R>, accumulated: &str) {
+        if !self.skeleton_emitted {
+            if let Some((content_type, summary)) = super::streaming::try_extract_skeleton(accumulated) {
+                log::info!("[LLM] Skeleton emitted at {}ms", self.start.elapsed().as_millis());
+                let _ = app.emit("action-menu-skeleton", &ActionMenuSkeleton { content_type, summary });
+                self.skeleton_emitted = true;
+            }
+        }
+        for action in self.actions.feed(accumulated) {
+            log::info!("[LLM] Action '{}' streamed at {}ms", action.id, self.start.elapsed().as_millis());
+            let _ = app.emit("action-menu-item", &action);
+        }
+    }
+}
+
+/// Stream recorded classify text as a live call would: fixed-size deltas
+/// at a fixed pace, then "action-menu-complete" with the parsed menu (or
+/// the fallback, as for a live response that doesn't parse).
+pub async fn replay_classify<R: Runtime>(app: &tauri::AppHandle<R>, raw: &str) -> ActionMenu {
+    let mut stream = MenuStream::new(std::time::Instant::now());
+    let mut end = 0;
+    while end < raw.len() {
+        end = (end + REPLAY_DELTA_BYTES).min(raw.len());
+        while !raw.is_char_boundary(end) {
+            end += 1;
+        }
+        stream.update(app, &raw[..end]);
+        tokio::time::sleep(REPLAY_DELTA_INTERVAL).await;
+    }
+    let menu = serde_json::from_str::<ActionMenu>(&super::streaming::strip_code_fences(raw)).unwrap_or_else(|e| {
+        log::warn!("[LLM] Recorded classify text does not parse: {}", e);
+        ActionMenu::fallback()
+    });
+    let _ = app.emit("action-menu-complete", &menu);
+    menu
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MENU: &str = r#"```json
+{"contentType": "error", "summary": "A {weird} \"quoted\" [summary]", "actions": [
+  {"id": "explain_error", "label": "Explain", "icon": "lightbulb", "priority": 1, "description": "Why it broke {x}", "requiresExecution": true},
+  {"id": "search_error", "label": "Search", "icon": "search", "priority": 2, "description": "Look it up", "requiresExecution": false},
+  {"id": "broken"}
+], "detectedLanguage": null, "confidence": 0.9}
+```"#;
+
+    #[test]
+    fn emits_each_action_once_as_it_completes() {
+        let mut scanner = ActionScanner::default();
+        let mut ids = Vec::new();
+        for end in (0..=MENU.len()).filter(|&i| MENU.is_char_boundary(i)) {
+            ids.extend(scanner.feed(&MENU[..end]).into_iter().map(|a| a.id));
+        }
+        assert_eq!(ids, ["explain_error", "search_error"]);
+
+        let first_done = MENU.find("true}").unwrap() + "true}".len();
+        assert_eq!(ActionScanner::default().feed(&MENU[..first_done]).len(), 1);
+    }
+
+    #[test]
+    fn ignores_objects_outside_actions() {
+        let text = r#"{"summary": "actions", "meta": [{"id": "a", "label": "A", "icon": "x", "priority": 1, "description": "", "requiresExecution": false}], "actions": []}"#;
+        assert!(ActionScanner::default().feed(text).is_empty());
+    }
+}
@@ -9,22 +9,35 @@
 //!
 //! Shared:
 //!   - streaming.rs — SSE parsing + partial JSON extraction
+//!   - menu_stream.rs — skeleton and per-action events while classify streams (or replays)
 //!   - provider.rs  — provider metadata + configuration checks
+//!   - args_schema.rs — JSON Schema validation of plugin tool arguments
+//!   - agent.rs     — Messages calls that may read plugin resources, plugin prompts
+//!   - http.rs      — pooled per-provider HTTP clients + preconnect
+//!   - image_budget.rs — fit snip images to each provider's vision limits
+//!   - vision.rs    — describe a snip from its pixels (picture snips)
 
+pub mod agent;
+pub mod args_schema;
 mod classify;
 pub mod execute;
 mod gemini;
+pub mod http;
+pub mod image_budget;
+mod menu_stream;
 pub mod plugin_args;
 pub mod provider;
 pub mod prompts;
-mod prompts_execute;
+pub mod prompts_execute;
 pub mod prompts_text_command;
 pub mod streaming;
 pub mod types;
+pub mod vision;
 
 pub use classify::{classify, classify_streaming};
 pub use execute::{execute_action_anthropic, ActionResult};
-pub use gemini::classify_streaming_gemini;
+pub use gemini::{classify_streaming_gemini, GEMINI_MODEL};
+pub use menu_stream::replay_classify;
 pub use types::{ActionMenu, ActionMenuSkeleton};
 
 use std::sync::Mutex;
@@ -36,6 +49,13 @@ pub struct ActionMenuState {
     pub ocr_text: Mutex<Option<String>>,
     /// Cropped PNG bytes — stored so execute_action can re-OCR with .accurate.
     pub crop_png: Mutex<Option<Vec<u8>>>,
+    /// Default action queued for this snip (settings `actions.defaults`),
+    /// taken once by the action menu.
+    pub auto_action: Mutex<Option<String>>,
+    /// Injection scan of a suspicious snip, taken once by the action menu.
+    pub injection_warning: Mutex<Option<crate::safety::injection::InjectionScan>>,
+    /// Latest result and the OCR text it answered, for `copy_result`.
+    pub last_result: Mutex<Option<(String, ActionResult)>>,
 }
 
 impl ActionMenuState {
@@ -44,6 +64,9 @@ impl ActionMenuState {
             menu: Mutex::new(None),
             ocr_text: Mutex::new(None),
             crop_png: Mutex::new(None),
+            auto_action: Mutex::new(None),
+            injection_warning: Mutex::new(None),
+            last_result: Mutex::new(None),
         }
     }
 }
@@ -50,3 +50,15 @@ pub fn is_provider_configured(provider_id: &str) -> bool {
         .map(|k| !k.is_empty())
         .unwrap_or(false)
 }
+
+/// Model ID for a provider: the user's override from settings, else the
+/// built-in default. A version pinned by enterprise policy wins over both.
+pub fn model_for(provider_id: &str) -> String {
+    let settings = crate::settings::current();
+    let (chosen, builtin) = match provider_id {
+        "gemini" => (settings.models.gemini, super::gemini::GEMINI_MODEL),
+        _ => (settings.models.anthropic, super::prompts::MODEL),
+    };
+    let default = chosen.as_deref().unwrap_or(builtin);
+    crate::policy::precedence::effective_model(crate::policy::current(), provider_id, default).to_string()
+}
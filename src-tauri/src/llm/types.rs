@@ -2,13 +2,15 @@
 //!
 //! These match the JSON schema from the LLM Integration PRD Section 6.
 //! The LLM returns JSON that deserializes directly into these types.
+//! Menus built here without the LLM use localized labels (see i18n/).
 
+use crate::i18n::t;
 use serde::{Deserialize, Serialize};
 
 /// The action menu returned by the CLASSIFY pipeline.
 ///
 /// Rendered as a popup near the snip location.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionMenu {
     pub content_type: String,
@@ -19,7 +21,7 @@ pub struct ActionMenu {
 }
 
 /// A single action the user can take on snipped content.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Action {
     pub id: String,
@@ -46,34 +48,83 @@ impl ActionMenu {
         Self {
             content_type: "unknown".to_string(),
             confidence: 0.0,
-            summary: "Could not analyze content".to_string(),
+            summary: t("menu.couldNotAnalyze"),
             detected_language: None,
             actions: vec![
                 Action {
                     id: "copy_text".to_string(),
-                    label: "Copy Text".to_string(),
+                    label: t("menu.copyText"),
                     icon: "clipboard".to_string(),
                     priority: 1,
-                    description: "Copy the extracted text to clipboard".to_string(),
+                    description: t("menu.copyTextDescription"),
                     requires_execution: false,
                 },
                 Action {
                     id: "explain".to_string(),
-                    label: "Explain This".to_string(),
+                    label: t("menu.explain"),
                     icon: "lightbulb".to_string(),
                     priority: 2,
-                    description: "Explain what this content means".to_string(),
+                    description: t("menu.explainDescription"),
                     requires_execution: true,
                 },
                 Action {
                     id: "search_web".to_string(),
-                    label: "Search Web".to_string(),
+                    label: t("menu.searchWeb"),
                     icon: "search".to_string(),
                     priority: 3,
-                    description: "Search for this text online".to_string(),
+                    description: t("menu.searchWebDescription"),
                     requires_execution: false,
                 },
             ],
         }
     }
+
+    /// Menu built without the LLM (local-only mode, offline), from the
+    /// content types the local heuristics found. Only actions that keep
+    /// the text on this machine are offered; a table is exported in Rust
+    /// (`export/`), code opens in the editor (`editor/`), logs and lists go
+    /// to the regex workbench (`extract/`), and a link can be shown as a QR
+    /// code (`qr/`), so none of them needs the LLM.
+    pub fn local(reason: &str, content_types: &[&str]) -> Self {
+        let mut ids = Vec::new();
+        if content_types.contains(&"table") {
+            ids.push(("export_csv", "menu.exportCsv", "table"));
+        }
+        if content_types.contains(&"error") {
+            ids.push(("copy_traceback", "menu.copyError", "clipboard"));
+        }
+        if content_types.contains(&"code") {
+            ids.push(("copy_code", "menu.copyCode", "clipboard"));
+        }
+        if content_types.contains(&"code") || content_types.contains(&"error") {
+            ids.push((crate::editor::OPEN_ACTION, "menu.openInEditor", "code"));
+        }
+        if ["error", "table", "list", "kv_pairs"].iter().any(|t| content_types.contains(t)) {
+            ids.push((crate::extract::WORKBENCH_ACTION, "menu.extractRegex", "regex"));
+        }
+        if content_types.contains(&"url") {
+            ids.push((crate::links::OPEN_ACTION, "menu.openUrl", "link"));
+            ids.push((crate::qr::SHOW_ACTION, "menu.showQr", "qr"));
+        }
+        ids.push(("copy_text", "menu.copyText", "clipboard"));
+        let actions = ids
+            .into_iter()
+            .zip(1u8..)
+            .map(|((id, label, icon), priority)| Action {
+                id: id.to_string(),
+                label: t(label),
+                icon: icon.to_string(),
+                priority,
+                description: t(&format!("{}Description", label)),
+                requires_execution: matches!(id, "export_csv" | crate::editor::OPEN_ACTION),
+            })
+            .collect();
+        Self {
+            content_type: content_types.iter().find(|t| **t != "mixed").unwrap_or(&"unknown").to_string(),
+            confidence: 0.0,
+            summary: reason.to_string(),
+            detected_language: None,
+            actions,
+        }
+    }
 }
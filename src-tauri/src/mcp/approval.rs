@@ -116,6 +116,12 @@ pub fn record_denial(store: &mut ApprovalStore, plugin_id: &str) {
     );
 }
 
+/// Forget every decision for a plugin (after uninstall).
+pub fn forget_plugin(store: &mut ApprovalStore, plugin_id: &str) {
+    store.approved.remove(plugin_id);
+    store.denied.remove(plugin_id);
+}
+
 /// SHA-256 hash of the serialized permissions. Deterministic because
 /// Permissions is a struct (not HashMap), so field order is stable.
 pub fn hash_permissions(permissions: &Permissions) -> String {
@@ -146,6 +152,7 @@ mod tests {
             entry: "index.js".to_string(),
             permissions: perms,
             configuration: None,
+            extensions: Default::default(),
         }
     }
 
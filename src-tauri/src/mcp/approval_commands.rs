@@ -3,9 +3,12 @@
 //! These commands are called by the permission-prompt.ts frontend
 //! to display pending plugin approvals and record user decisions.
 
+use crate::error::OmniError;
 use crate::mcp::approval;
 use crate::mcp::loader::PendingApprovals;
-use crate::mcp::manifest::PluginManifest;
+use crate::mcp::manifest::{PluginManifest, Runtime};
+use crate::mcp::permissions;
+use crate::mcp::sandbox;
 use crate::mcp::registry::ToolRegistry;
 use crate::mcp::sandbox::risk::{self, RiskLevel};
 use serde::Serialize;
@@ -21,6 +24,17 @@ pub struct PendingPlugin {
     pub permissions: crate::mcp::manifest::Permissions,
     pub risk_level: RiskLevel,
     pub is_update: bool, // true if PermissionsChanged (not NeedsApproval)
+    /// Why the plugin can't start on this system, if it can't
+    /// (`permissions::check_enforceable`).
+    pub unenforceable: Option<String>,
+}
+
+/// Why a native plugin won't start here; WASM plugins need no sandbox.
+fn unenforceable(manifest: &PluginManifest) -> Option<String> {
+    if manifest.runtime == Runtime::Wasm {
+        return None;
+    }
+    permissions::check_enforceable(&manifest.permissions, sandbox::OS_SANDBOX).err()
 }
 
 /// Get all plugins awaiting user approval.
@@ -39,6 +53,7 @@ pub async fn get_pending_approvals(
             permissions: manifest.permissions.clone(),
             risk_level: risk::calculate_risk(&manifest.permissions),
             is_update: *is_update,
+            unenforceable: unenforceable(manifest),
         })
         .collect())
 }
@@ -54,7 +69,7 @@ pub async fn approve_plugin(
     approved: bool,
     pending: tauri::State<'_, PendingApprovals>,
     registry: tauri::State<'_, ToolRegistry>,
-) -> Result<(), String> {
+) -> Result<(), OmniError> {
     // Find and remove the plugin from the pending queue
     let entry = {
         let mut queue = pending.queue.lock().await;
@@ -70,21 +85,40 @@ pub async fn approve_plugin(
     if approved {
         if let Some((manifest, plugin_dir, _)) = entry {
             approval::record_approval(&mut store, &manifest);
-            approval::save_approvals(&store)?;
+            approval::save_approvals(&store).map_err(OmniError::Plugin)?;
 
             // Load the plugin now
-            crate::mcp::loader::load_approved_plugin(&manifest, &plugin_dir, &registry).await?;
+            crate::mcp::loader::load_approved_plugin(&manifest, &plugin_dir, &registry).await.map_err(OmniError::Plugin)?;
             log::info!("[APPROVAL] Plugin '{}' approved and loaded", plugin_id);
         }
     } else {
         approval::record_denial(&mut store, &plugin_id);
-        approval::save_approvals(&store)?;
+        approval::save_approvals(&store).map_err(OmniError::Plugin)?;
         log::info!("[APPROVAL] Plugin '{}' denied", plugin_id);
     }
 
     Ok(())
 }
 
+/// Open (or focus) the permission prompt window for queued plugins.
+pub fn open_permission_prompt(app: &tauri::AppHandle) {
+    use tauri::Manager;
+    if let Some(window) = app.get_webview_window("permission-prompt") {
+        let _ = window.set_focus();
+        return;
+    }
+    let _ = tauri::WebviewWindowBuilder::new(
+        app,
+        "permission-prompt",
+        tauri::WebviewUrl::App("permission-prompt.html".into()),
+    )
+    .title("Plugin Permissions")
+    .inner_size(460.0, 400.0)
+    .resizable(false)
+    .center()
+    .build();
+}
+
 /// Serializable summary for the frontend.
 impl From<&PluginManifest> for PendingPlugin {
     fn from(m: &PluginManifest) -> Self {
@@ -96,6 +130,7 @@ impl From<&PluginManifest> for PendingPlugin {
             permissions: m.permissions.clone(),
             risk_level: risk::calculate_risk(&m.permissions),
             is_update: false,
+            unenforceable: unenforceable(m),
         }
     }
 }
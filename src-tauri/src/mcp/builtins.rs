@@ -54,6 +54,48 @@ pub async fn register_builtins(registry: &ToolRegistry) {
             description: "Explain this content clearly and concisely".to_string(),
             input_schema: None,
         },
+        RegisteredTool {
+            plugin_id: "builtin".to_string(),
+            name: "open_in_editor".to_string(),
+            display_name: "Open in Editor".to_string(),
+            description: "Open the code (or its suggested fix) in the user's editor".to_string(),
+            input_schema: None,
+        },
+        RegisteredTool {
+            plugin_id: "builtin".to_string(),
+            name: "open_url".to_string(),
+            display_name: "Open Link".to_string(),
+            description: "Open a link from the text in the browser, after a phishing check".to_string(),
+            input_schema: None,
+        },
+        RegisteredTool {
+            plugin_id: "builtin".to_string(),
+            name: "add_to_calendar".to_string(),
+            display_name: "Add to Calendar".to_string(),
+            description: "Turn an event in the text into an .ics file for the calendar app".to_string(),
+            input_schema: None,
+        },
+        RegisteredTool {
+            plugin_id: "builtin".to_string(),
+            name: "draft_email".to_string(),
+            display_name: "Draft Email".to_string(),
+            description: "Write a reply or an email about the text and open it in the mail client".to_string(),
+            input_schema: None,
+        },
+        RegisteredTool {
+            plugin_id: "builtin".to_string(),
+            name: "show_qr".to_string(),
+            display_name: "Show as QR".to_string(),
+            description: "Show a link, Wi-Fi login, or short text as a QR code to scan with a phone".to_string(),
+            input_schema: None,
+        },
+        RegisteredTool {
+            plugin_id: "builtin".to_string(),
+            name: "extract_regex".to_string(),
+            display_name: "Extract with Regex".to_string(),
+            description: "Pull every match of a regex (typed, saved, or written by the AI) out of the text and export it as CSV".to_string(),
+            input_schema: None,
+        },
         RegisteredTool {
             plugin_id: "builtin".to_string(),
             name: "translate_text".to_string(),
@@ -0,0 +1,44 @@
+//! Resources and prompts for `McpServer` — resources/list, resources/read,
+//! prompts/list, prompts/get.
+//!
+//! Only the first page of each list is read (`nextCursor` is ignored);
+//! callers cap how many entries they keep anyway.
+
+use super::McpServer;
+use crate::mcp::types::{McpPrompt, McpResource};
+use serde_json::Value;
+
+impl McpServer {
+    /// Discover resources via resources/list.
+    pub async fn list_resources(&mut self) -> Result<Vec<McpResource>, String> {
+        let resp = self.request("resources/list", None).await?;
+        let list = resp.get("resources").cloned().unwrap_or(Value::Array(vec![]));
+        serde_json::from_value(list).map_err(|e| format!("Bad resources/list: {}", e))
+    }
+
+    /// Read a resource; returns the `contents` array.
+    pub async fn read_resource(&mut self, uri: &str) -> Result<Vec<Value>, String> {
+        let resp = self.request("resources/read", Some(serde_json::json!({ "uri": uri }))).await?;
+        match resp.get("contents") {
+            Some(Value::Array(contents)) => Ok(contents.clone()),
+            _ => Err(format!("Bad resources/read result for '{}'", uri)),
+        }
+    }
+
+    /// Discover prompt templates via prompts/list.
+    pub async fn list_prompts(&mut self) -> Result<Vec<McpPrompt>, String> {
+        let resp = self.request("prompts/list", None).await?;
+        let list = resp.get("prompts").cloned().unwrap_or(Value::Array(vec![]));
+        serde_json::from_value(list).map_err(|e| format!("Bad prompts/list: {}", e))
+    }
+
+    /// Fill a prompt template; returns the `messages` array.
+    pub async fn get_prompt(&mut self, name: &str, arguments: Value) -> Result<Vec<Value>, String> {
+        let params = serde_json::json!({ "name": name, "arguments": arguments });
+        let resp = self.request("prompts/get", Some(params)).await?;
+        match resp.get("messages") {
+            Some(Value::Array(messages)) => Ok(messages.clone()),
+            _ => Err(format!("Bad prompts/get result for '{}'", name)),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! JSON-RPC framing for `McpServer` — requests, notifications, and the
+//! response reader.
+//!
+//! While waiting for a response the plugin may send its own requests
+//! (storage, clipboard — see `mcp/host_rpc.rs`). Those are answered inline,
+//! and the time spent on them (e.g. a clipboard prompt) does not count
+//! against the plugin's timeout.
+
+use super::McpServer;
+use crate::mcp::host_rpc::{self, HostCaps};
+use crate::mcp::types::*;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::time::Instant;
+
+impl McpServer {
+    /// Send a JSON-RPC request and wait for the matching response.
+    pub(super) async fn request(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_with_timeout(method, params, super::REQUEST_TIMEOUT_SECS).await
+    }
+
+    pub(super) async fn request_with_timeout(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        timeout_secs: u64,
+    ) -> Result<serde_json::Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let req = JsonRpcRequest::new(id, method, params);
+        self.send_message(&serde_json::to_value(&req).unwrap()).await?;
+        let resp = self.read_response(id, timeout_secs).await?;
+
+        if let Some(err) = resp.error {
+            return Err(format!("[MCP] '{}' {}: {}", self.plugin_id, method, err));
+        }
+
+        resp.result.ok_or_else(|| {
+            format!(
+                "[MCP] '{}' {}: response had neither result nor error",
+                self.plugin_id, method
+            )
+        })
+    }
+
+    /// Send a JSON-RPC notification (no id, no response expected).
+    pub(super) async fn notify(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<(), String> {
+        let notif = JsonRpcNotification::new(method, params);
+        self.send_message(&serde_json::to_value(&notif).unwrap())
+            .await
+    }
+
+    /// Write a single NDJSON line to the child's stdin.
+    async fn send_message(&mut self, value: &serde_json::Value) -> Result<(), String> {
+        let mut line = serde_json::to_string(value)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+        line.push('\n');
+
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("[MCP] '{}' stdin write failed: {}", self.plugin_id, e))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("[MCP] '{}' stdin flush failed: {}", self.plugin_id, e))?;
+
+        Ok(())
+    }
+
+    /// Read lines from stdout until we find a response matching the given id.
+    /// Answers host requests from the plugin and skips other non-matching
+    /// messages. On timeout the server is assumed hung and killed.
+    async fn read_response(&mut self, expected_id: u64, timeout_secs: u64) -> Result<JsonRpcResponse, String> {
+        let mut deadline = Instant::now() + Duration::from_secs(timeout_secs);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = tokio::time::timeout_at(deadline, self.stdout.read_line(&mut line)).await;
+            let Ok(read) = read else {
+                log::warn!("[MCP] '{}' timed out after {}s — killing", self.plugin_id, timeout_secs);
+                let _ = self.child.start_kill();
+                self.killed = true;
+                return Err(format!("[MCP] '{}' request timed out after {}s", self.plugin_id, timeout_secs));
+            };
+            let n = read.map_err(|e| format!("[MCP] '{}' stdout read failed: {}", self.plugin_id, e))?;
+
+            if n == 0 {
+                return Err(format!(
+                    "[MCP] '{}' stdout closed (process exited?)",
+                    self.plugin_id
+                ));
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            // Requests from the plugin are answered inline; the host's time
+            // (possibly waiting on the user) extends the deadline
+            if let Some(request) = host_rpc::parse_request(trimmed) {
+                let started = Instant::now();
+                let caps = HostCaps { clipboard: self.clipboard_declared };
+                let reply = host_rpc::handle(&self.plugin_id, caps, request).await;
+                self.send_message(&reply).await?;
+                deadline += started.elapsed();
+                continue;
+            }
+
+            // Try to parse as a JSON-RPC response
+            match serde_json::from_str::<JsonRpcResponse>(trimmed) {
+                Ok(resp) if resp.id == Some(expected_id) => return Ok(resp),
+                Ok(_) => continue, // Non-matching id or notification
+                Err(_) => {
+                    // Not valid JSON-RPC — could be server log output, skip
+                    log::debug!(
+                        "[MCP] '{}' ignoring non-JSON line: {}",
+                        self.plugin_id,
+                        &trimmed[..trimmed.len().min(100)]
+                    );
+                    continue;
+                }
+            }
+        }
+    }
+}
@@ -3,16 +3,25 @@
 //! Spawns a child process implementing the MCP server protocol,
 //! communicates via NDJSON on stdin/stdout, and provides typed
 //! methods for the initialize → tools/list → tools/call lifecycle.
+//! Request/response framing lives in `framing.rs`; resources/prompts
+//! requests in `catalog.rs`.
+
+mod catalog;
+mod framing;
 
 use crate::mcp::types::*;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+use std::sync::atomic::AtomicU64;
+use tokio::io::{AsyncWriteExt, BufReader, BufWriter};
 use tokio::process::{Child, ChildStdin, ChildStdout};
 
 /// Default timeout for any single JSON-RPC request (seconds).
 const REQUEST_TIMEOUT_SECS: u64 = 15;
 
+/// Timeout for tools/call (seconds). A server that misses it is killed
+/// as hung; the health supervisor restarts it.
+pub const TOOL_CALL_TIMEOUT_SECS: u64 = 30;
+
 /// An active connection to an MCP server process.
 pub struct McpServer {
     pub plugin_id: String,
@@ -20,6 +29,14 @@ pub struct McpServer {
     stdin: BufWriter<ChildStdin>,
     stdout: BufReader<ChildStdout>,
     next_id: AtomicU64,
+    /// Killed by us after a call timed out (that call already counted as
+    /// a failure).
+    killed: bool,
+    /// Manifest declared `clipboard` — may use the brokered clipboard
+    /// (`mcp/clipboard.rs`). Set by the loader after spawn.
+    pub clipboard_declared: bool,
+    /// What the server advertised in `initialize` (resources, prompts).
+    pub capabilities: ServerCapabilities,
 }
 
 impl McpServer {
@@ -58,6 +75,10 @@ impl McpServer {
             .stdout
             .take()
             .ok_or_else(|| format!("No stdout for MCP server '{}'", plugin_id))?;
+        // Drain stderr so the pipe never fills; surfaces sandbox file denials
+        if let Some(stderr) = child.stderr.take() {
+            crate::mcp::sandbox::stderr_watch::watch(plugin_id, stderr);
+        }
 
         Ok(Self {
             plugin_id: plugin_id.to_string(),
@@ -65,6 +86,9 @@ impl McpServer {
             stdin: BufWriter::new(stdin),
             stdout: BufReader::new(stdout),
             next_id: AtomicU64::new(1),
+            killed: false,
+            clipboard_declared: false,
+            capabilities: ServerCapabilities::default(),
         })
     }
 
@@ -93,7 +117,7 @@ impl McpServer {
     pub async fn initialize(&mut self) -> Result<ServerInfo, String> {
         let params = InitializeParams {
             protocol_version: "2024-11-05".to_string(),
-            capabilities: ClientCapabilities {},
+            capabilities: ClientCapabilities::default(),
             client_info: ClientInfo {
                 name: "omni-glass".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
@@ -112,6 +136,10 @@ impl McpServer {
                 name: None,
                 version: None,
             });
+        self.capabilities = resp
+            .get("capabilities")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
 
         // Send initialized notification (no response expected)
         self.notify("notifications/initialized", None).await?;
@@ -157,13 +185,25 @@ impl McpServer {
             "arguments": arguments,
         });
 
-        let resp = self.request("tools/call", Some(params)).await?;
+        let resp = self
+            .request_with_timeout("tools/call", Some(params), TOOL_CALL_TIMEOUT_SECS)
+            .await?;
         let result: ToolResult =
             serde_json::from_value(resp).map_err(|e| format!("Bad tools/call result: {}", e))?;
 
         Ok(result)
     }
 
+    /// Whether the child process has exited (crashed or killed).
+    pub fn has_exited(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(Some(_)))
+    }
+
+    /// Whether it was killed for missing a timeout rather than crashing.
+    pub fn was_killed(&self) -> bool {
+        self.killed
+    }
+
     /// Gracefully shutdown: close stdin → wait briefly → kill.
     pub async fn shutdown(&mut self) {
         // Close stdin to signal EOF
@@ -185,116 +225,4 @@ impl McpServer {
             }
         }
     }
-
-    // ── Internal: JSON-RPC framing ──────────────────────────────────
-
-    /// Send a JSON-RPC request and wait for the matching response.
-    async fn request(
-        &mut self,
-        method: &str,
-        params: Option<serde_json::Value>,
-    ) -> Result<serde_json::Value, String> {
-        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
-        let req = JsonRpcRequest::new(id, method, params);
-
-        self.send_message(&serde_json::to_value(&req).unwrap())
-            .await?;
-
-        let resp = self.read_response(id).await?;
-
-        if let Some(err) = resp.error {
-            return Err(format!("[MCP] '{}' {}: {}", self.plugin_id, method, err));
-        }
-
-        resp.result.ok_or_else(|| {
-            format!(
-                "[MCP] '{}' {}: response had neither result nor error",
-                self.plugin_id, method
-            )
-        })
-    }
-
-    /// Send a JSON-RPC notification (no id, no response expected).
-    async fn notify(
-        &mut self,
-        method: &str,
-        params: Option<serde_json::Value>,
-    ) -> Result<(), String> {
-        let notif = JsonRpcNotification::new(method, params);
-        self.send_message(&serde_json::to_value(&notif).unwrap())
-            .await
-    }
-
-    /// Write a single NDJSON line to the child's stdin.
-    async fn send_message(&mut self, value: &serde_json::Value) -> Result<(), String> {
-        let mut line = serde_json::to_string(value)
-            .map_err(|e| format!("JSON serialize failed: {}", e))?;
-        line.push('\n');
-
-        self.stdin
-            .write_all(line.as_bytes())
-            .await
-            .map_err(|e| format!("[MCP] '{}' stdin write failed: {}", self.plugin_id, e))?;
-        self.stdin
-            .flush()
-            .await
-            .map_err(|e| format!("[MCP] '{}' stdin flush failed: {}", self.plugin_id, e))?;
-
-        Ok(())
-    }
-
-    /// Read lines from stdout until we find a response matching the given id.
-    /// Skips notifications and other non-matching messages.
-    async fn read_response(&mut self, expected_id: u64) -> Result<JsonRpcResponse, String> {
-        let timeout = std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS);
-
-        tokio::time::timeout(timeout, async {
-            let mut line = String::new();
-            loop {
-                line.clear();
-                let n = self
-                    .stdout
-                    .read_line(&mut line)
-                    .await
-                    .map_err(|e| format!("[MCP] '{}' stdout read failed: {}", self.plugin_id, e))?;
-
-                if n == 0 {
-                    return Err(format!(
-                        "[MCP] '{}' stdout closed (process exited?)",
-                        self.plugin_id
-                    ));
-                }
-
-                let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-
-                // Try to parse as a JSON-RPC response
-                match serde_json::from_str::<JsonRpcResponse>(trimmed) {
-                    Ok(resp) if resp.id == Some(expected_id) => return Ok(resp),
-                    Ok(_) => {
-                        // Non-matching id or notification — skip
-                        continue;
-                    }
-                    Err(_) => {
-                        // Not valid JSON-RPC — could be server log output, skip
-                        log::debug!(
-                            "[MCP] '{}' ignoring non-JSON line: {}",
-                            self.plugin_id,
-                            &trimmed[..trimmed.len().min(100)]
-                        );
-                        continue;
-                    }
-                }
-            }
-        })
-        .await
-        .map_err(|_| {
-            format!(
-                "[MCP] '{}' request timed out after {}s",
-                self.plugin_id, REQUEST_TIMEOUT_SECS
-            )
-        })?
-    }
 }
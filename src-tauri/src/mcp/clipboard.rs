@@ -0,0 +1,236 @@
+//! Brokered clipboard — plugins never touch the OS clipboard directly.
+//!
+//! A plugin that declares `clipboard: true` may send
+//! `omniglass/clipboard/read` or `omniglass/clipboard/write` (`{text}`)
+//! requests (`host_rpc.rs`). Each access needs the user's consent: a
+//! native prompt offers "Allow Once", "Always Allow", or "Deny", and
+//! "Always Allow" is stored per plugin and direction in
+//! `~/.config/omni-glass/clipboard-grants.json`. Plugins that did not
+//! declare the permission, or whose permission the user revoked
+//! (`permission_audit.rs`), are refused and reported as violations. Every
+//! decision is recorded in the safety event log.
+
+use crate::mcp::host_rpc::{RpcError, DENIED, INVALID_PARAMS, METHOD_NOT_FOUND, SERVER_ERROR};
+use crate::mcp::permission_audit;
+use crate::mcp::violations::{self, ViolationKind};
+use crate::persist::JsonStore;
+use crate::safety::events::{self, SafetyEventKind};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult};
+
+/// Method prefix routed here by `host_rpc`.
+pub const METHOD_PREFIX: &str = "omniglass/clipboard/";
+
+/// Largest text a plugin may write.
+const MAX_WRITE_CHARS: usize = 1_000_000;
+
+const ALLOW_ONCE: &str = "Allow Once";
+const ALWAYS_ALLOW: &str = "Always Allow";
+const DENY: &str = "Deny";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    Read,
+    Write,
+}
+
+/// Persistent "Always Allow" grants, per plugin.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ClipboardGrants {
+    #[serde(default)]
+    pub plugins: BTreeMap<String, BTreeSet<Access>>,
+}
+
+impl ClipboardGrants {
+    pub fn is_granted(&self, plugin_id: &str, access: Access) -> bool {
+        self.plugins.get(plugin_id).is_some_and(|a| a.contains(&access))
+    }
+
+    pub fn grant(&mut self, plugin_id: &str, access: Access) {
+        self.plugins.entry(plugin_id.to_string()).or_default().insert(access);
+    }
+
+    pub fn revoke(&mut self, plugin_id: &str) {
+        self.plugins.remove(plugin_id);
+    }
+}
+
+/// The user's answer to a clipboard prompt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Decision {
+    Once,
+    Always,
+    Deny,
+}
+
+static GRANTS: JsonStore<ClipboardGrants> = JsonStore::new("clipboard-grants.json");
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Give the broker an app handle for its prompts. Called from lib.rs.
+pub fn attach(app: tauri::AppHandle) {
+    let _ = APP.set(app);
+}
+
+pub fn load_grants() -> ClipboardGrants {
+    GRANTS.get()
+}
+
+/// Apply `change` to the stored grants and persist them.
+pub fn update_grants(change: impl FnOnce(&mut ClipboardGrants)) -> Result<(), String> {
+    GRANTS.update(change)
+}
+
+/// Handle one `omniglass/clipboard/*` request from a plugin.
+pub async fn dispatch(plugin_id: &str, declared: bool, op: &str, params: &Value) -> Result<Value, RpcError> {
+    let (access, kind) = match op {
+        "read" => (Access::Read, SafetyEventKind::ClipboardRead),
+        "write" => (Access::Write, SafetyEventKind::ClipboardWrite),
+        other => return Err((METHOD_NOT_FOUND, format!("Unknown clipboard method '{}'", other))),
+    };
+    let text = match access {
+        Access::Write => {
+            let text = params.get("text").and_then(Value::as_str);
+            let text = text.ok_or((INVALID_PARAMS, "Missing string param 'text'".to_string()))?;
+            if text.chars().count() > MAX_WRITE_CHARS {
+                return Err((INVALID_PARAMS, format!("Text exceeds {} characters", MAX_WRITE_CHARS)));
+            }
+            Some(text.to_string())
+        }
+        Access::Read => None,
+    };
+
+    if !declared {
+        violations::report(plugin_id, ViolationKind::Clipboard, op);
+        events::record(kind, plugin_id, false, "clipboard permission not declared");
+        return Err((DENIED, "Plugin did not declare the clipboard permission".to_string()));
+    }
+    if permission_audit::is_revoked(plugin_id, "clipboard") {
+        violations::report(plugin_id, ViolationKind::Clipboard, op);
+        events::record(kind, plugin_id, false, "clipboard permission revoked");
+        return Err((DENIED, "The clipboard permission was revoked by the user".to_string()));
+    }
+
+    let detail = if load_grants().is_granted(plugin_id, access) {
+        "persistent grant"
+    } else {
+        match ask_user(plugin_id, access).await {
+            Decision::Once => "allowed once",
+            Decision::Always => {
+                if let Err(e) = update_grants(|g| g.grant(plugin_id, access)) {
+                    log::warn!("[SAFETY] Failed to save clipboard grant: {}", e);
+                }
+                "always allowed by user"
+            }
+            Decision::Deny => {
+                events::record(kind, plugin_id, false, "denied by user");
+                return Err((DENIED, "User denied clipboard access".to_string()));
+            }
+        }
+    };
+
+    let result = tauri::async_runtime::spawn_blocking(move || -> Result<Value, String> {
+        let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+        match text {
+            Some(text) => clipboard.set_text(text).map(|_| json!({})).map_err(|e| e.to_string()),
+            None => Ok(json!({ "text": clipboard.get_text().unwrap_or_default() })),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+
+    events::record(kind, plugin_id, result.is_ok(), detail);
+    if result.is_ok() {
+        permission_audit::record_use(plugin_id, "clipboard");
+    }
+    result.map_err(|e| (SERVER_ERROR, format!("Clipboard error: {}", e)))
+}
+
+/// Native prompt for one clipboard access. No app handle = deny.
+async fn ask_user(plugin_id: &str, access: Access) -> Decision {
+    let Some(app) = APP.get() else {
+        return Decision::Deny;
+    };
+    let action = match access {
+        Access::Read => "read your clipboard",
+        Access::Write => "replace your clipboard contents",
+    };
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(format!("The plugin '{}' wants to {}.", plugin_id, action))
+        .title("Clipboard Access")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            ALLOW_ONCE.to_string(),
+            ALWAYS_ALLOW.to_string(),
+            DENY.to_string(),
+        ))
+        .show_with_result(move |result| {
+            let _ = tx.send(result);
+        });
+    decision(rx.await.ok())
+}
+
+/// Access is granted only on a button that says so. Depending on the
+/// platform the first button comes back as `Yes` or by its label; the
+/// others, a closed dialog, or a dropped channel deny.
+fn decision(result: Option<MessageDialogResult>) -> Decision {
+    match result {
+        Some(MessageDialogResult::Yes) => Decision::Once,
+        Some(MessageDialogResult::Custom(label)) if label == ALLOW_ONCE => Decision::Once,
+        Some(MessageDialogResult::Custom(label)) if label == ALWAYS_ALLOW => Decision::Always,
+        _ => Decision::Deny,
+    }
+}
+
+/// Tauri command: persistent clipboard grants for the settings panel.
+#[tauri::command]
+pub fn get_clipboard_grants() -> ClipboardGrants {
+    load_grants()
+}
+
+/// Tauri command: revoke a plugin's persistent clipboard grants.
+#[tauri::command]
+pub fn revoke_clipboard_grants(plugin_id: String) -> Result<(), String> {
+    update_grants(|g| g.revoke(&plugin_id))?;
+    log::info!("[SAFETY] Revoked clipboard grants for '{}'", plugin_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_are_per_plugin_and_direction() {
+        let mut g = ClipboardGrants::default();
+        g.grant("com.example.a", Access::Read);
+        assert!(g.is_granted("com.example.a", Access::Read));
+        assert!(!g.is_granted("com.example.a", Access::Write));
+        assert!(!g.is_granted("com.example.b", Access::Read));
+        g.revoke("com.example.a");
+        assert!(!g.is_granted("com.example.a", Access::Read));
+    }
+
+    #[test]
+    fn only_an_allow_button_grants_access() {
+        assert_eq!(decision(Some(MessageDialogResult::Yes)), Decision::Once);
+        assert_eq!(decision(Some(MessageDialogResult::Custom(ALWAYS_ALLOW.into()))), Decision::Always);
+        assert_eq!(decision(Some(MessageDialogResult::No)), Decision::Deny);
+        assert_eq!(decision(Some(MessageDialogResult::Cancel)), Decision::Deny);
+        assert_eq!(decision(Some(MessageDialogResult::Custom(DENY.into()))), Decision::Deny);
+        assert_eq!(decision(None), Decision::Deny);
+    }
+
+    #[tokio::test]
+    async fn undeclared_plugins_are_refused_before_prompting() {
+        let err = dispatch("com.example.nodecl", false, "read", &Value::Null).await.unwrap_err();
+        assert_eq!(err.0, DENIED);
+        let err = dispatch("com.example.nodecl", true, "paste", &Value::Null).await.unwrap_err();
+        assert_eq!(err.0, METHOD_NOT_FOUND);
+    }
+}
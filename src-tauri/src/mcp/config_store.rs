@@ -60,6 +60,11 @@ pub fn get_config_value(plugin_id: &str, key: &str) -> Option<Value> {
     load_config(plugin_id).get(key).cloned()
 }
 
+/// Delete a plugin's stored configuration (after uninstall).
+pub fn delete_config(plugin_id: &str) {
+    let _ = std::fs::remove_file(config_path(plugin_id));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,105 @@
+//! Plugin log streaming for the dev console.
+//!
+//! Every plugin's stderr (plus host notices such as "reloaded") lands in a
+//! bounded per-plugin buffer. While a console is subscribed to a plugin,
+//! new lines are also emitted as `plugin-log` events.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use tauri::Emitter;
+
+/// Lines kept per plugin for the console backlog.
+const MAX_LINES: usize = 500;
+
+/// Prefix for lines written by the host rather than the plugin.
+pub const HOST_PREFIX: &str = "[omni-glass]";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    pub plugin_id: String,
+    pub line: String,
+    /// Unix epoch milliseconds.
+    pub timestamp: u64,
+}
+
+static BUFFERS: LazyLock<Mutex<HashMap<String, VecDeque<LogLine>>>> = LazyLock::new(Default::default);
+static STREAMING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(Default::default);
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Give the log an app handle so lines can be emitted. Called from lib.rs.
+pub fn attach(app: tauri::AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// Append to a bounded buffer, dropping the oldest line when full.
+pub fn push_bounded<T>(buf: &mut VecDeque<T>, item: T, max: usize) {
+    if buf.len() >= max {
+        buf.pop_front();
+    }
+    buf.push_back(item);
+}
+
+/// Record a line of plugin output.
+pub fn publish(plugin_id: &str, line: &str) {
+    let entry = LogLine {
+        plugin_id: plugin_id.to_string(),
+        line: line.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+    };
+    {
+        let mut buffers = BUFFERS.lock().unwrap_or_else(|e| e.into_inner());
+        let buf = buffers.entry(plugin_id.to_string()).or_default();
+        push_bounded(buf, entry.clone(), MAX_LINES);
+    }
+    let streaming = STREAMING.lock().unwrap_or_else(|e| e.into_inner()).contains(plugin_id);
+    if streaming {
+        if let Some(app) = APP.get() {
+            let _ = app.emit("plugin-log", &entry);
+        }
+    }
+}
+
+/// Record a host notice (reload, manifest error, ...) for a plugin.
+pub fn notice(plugin_id: &str, message: &str) {
+    publish(plugin_id, &format!("{} {}", HOST_PREFIX, message));
+}
+
+/// Tauri command: start streaming a plugin's logs. Returns the backlog.
+#[tauri::command]
+pub fn stream_plugin_logs(plugin_id: String) -> Vec<LogLine> {
+    STREAMING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(plugin_id.clone());
+    BUFFERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&plugin_id)
+        .map(|b| b.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Tauri command: stop emitting `plugin-log` events for a plugin.
+#[tauri::command]
+pub fn stop_plugin_logs(plugin_id: String) {
+    STREAMING.lock().unwrap_or_else(|e| e.into_inner()).remove(&plugin_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_drops_oldest() {
+        let mut buf = VecDeque::new();
+        for i in 0..5 {
+            push_bounded(&mut buf, i, 3);
+        }
+        assert_eq!(buf.into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,191 @@
+//! Plugin developer mode — hot reload from a working copy + log console.
+//!
+//! `start_plugin_dev(path)` loads a plugin straight from the developer's
+//! source directory (not the plugins folder), then polls the directory and
+//! restarts the server whenever a save settles. The usual rules still
+//! apply: the manifest is validated, policy is checked, and changed
+//! permissions go back through the approval prompt.
+//!
+//! `open_plugin_console(plugin_id)` opens a window that streams the
+//! plugin's stderr and reload notices (`logs.rs`).
+
+pub mod logs;
+pub mod watch;
+
+use crate::error::OmniError;
+use crate::mcp::approval::{self, ApprovalStatus};
+use crate::mcp::approval_commands;
+use crate::mcp::install::verify;
+use crate::mcp::loader::{self, PendingApprovals};
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::{health, manifest};
+use crate::policy;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::Manager;
+
+/// How often the source directory is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(750);
+
+struct DevSession {
+    dir: PathBuf,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static SESSIONS: LazyLock<Mutex<HashMap<String, DevSession>>> = LazyLock::new(Default::default);
+
+/// (Re)load a dev plugin from `dir`. Errors are also sent to its console.
+async fn reload(app: &tauri::AppHandle, plugin_id: &str, dir: &Path) -> Result<bool, String> {
+    let registry = app.state::<ToolRegistry>();
+    let manifest = match manifest::load_manifest(dir) {
+        Ok(m) if m.id == plugin_id => m,
+        Ok(m) => {
+            let e = format!("manifest id changed to '{}' — restart dev mode", m.id);
+            logs::notice(plugin_id, &e);
+            return Err(e);
+        }
+        Err(e) => {
+            logs::notice(plugin_id, &format!("manifest error: {}", e));
+            return Err(e);
+        }
+    };
+
+    registry.remove_plugin(plugin_id).await;
+    let status = approval::check_approval(&approval::load_approvals(), &manifest);
+    if status == ApprovalStatus::Denied {
+        let e = format!("'{}' was denied — remove it from approvals to retry", plugin_id);
+        logs::notice(plugin_id, &e);
+        return Err(e);
+    }
+    if status != ApprovalStatus::Approved {
+        logs::notice(plugin_id, "permissions need approval — see the permission prompt");
+        let pending = app.state::<PendingApprovals>();
+        let mut queue = pending.queue.lock().await;
+        queue.retain(|(m, _, _)| m.id != plugin_id);
+        let is_update = status == ApprovalStatus::PermissionsChanged;
+        queue.push((manifest, dir.to_path_buf(), is_update));
+        drop(queue);
+        approval_commands::open_permission_prompt(app);
+        return Ok(false);
+    }
+
+    match loader::load_approved_plugin(&manifest, dir, &registry).await {
+        Ok(count) => {
+            logs::notice(plugin_id, &format!("loaded v{} with {} tools", manifest.version, count));
+            Ok(true)
+        }
+        Err(e) => {
+            logs::notice(plugin_id, &format!("load failed: {}", e));
+            Err(e)
+        }
+    }
+}
+
+/// Poll `dir` and reload once a change has settled (two equal polls).
+fn spawn_watcher(app: tauri::AppHandle, plugin_id: String, dir: PathBuf) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut loaded = watch::fingerprint(&dir);
+        let mut last_seen = loaded;
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let now = watch::fingerprint(&dir);
+            let settled = now == last_seen;
+            last_seen = now;
+            if settled && now != loaded {
+                loaded = now;
+                log::info!("[MCP] Dev plugin '{}' changed — reloading", plugin_id);
+                logs::notice(&plugin_id, "change detected — reloading");
+                let _ = reload(&app, &plugin_id, &dir).await;
+            }
+        }
+    })
+}
+
+/// Tauri command: load a plugin from a source directory with hot reload.
+///
+/// Returns the plugin ID. An installed plugin with the same ID is
+/// replaced until dev mode stops.
+#[tauri::command]
+pub async fn start_plugin_dev(app: tauri::AppHandle, path: String) -> Result<String, OmniError> {
+    let dir = std::fs::canonicalize(&path).map_err(|e| OmniError::Plugin(format!("Cannot open {}: {}", path, e)))?;
+    let manifest = manifest::load_manifest(&dir).map_err(OmniError::Plugin)?;
+    if !verify::is_safe_plugin_id(&manifest.id) {
+        return Err(OmniError::Plugin(format!("Invalid plugin id '{}'", manifest.id)));
+    }
+    if !policy::precedence::is_plugin_allowed(policy::current(), &manifest.id) {
+        return Err(OmniError::Plugin(format!("Plugin '{}' is blocked by your organization's policy", manifest.id)));
+    }
+
+    let plugin_id = manifest.id.clone();
+    if let Some(old) = SESSIONS.lock().unwrap_or_else(|e| e.into_inner()).remove(&plugin_id) {
+        old.task.abort();
+    }
+    logs::notice(&plugin_id, &format!("dev mode: watching {}", dir.display()));
+    reload(&app, &plugin_id, &dir).await.map_err(OmniError::Plugin)?;
+
+    let task = spawn_watcher(app.clone(), plugin_id.clone(), dir.clone());
+    SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(plugin_id.clone(), DevSession { dir, task });
+    log::info!("[MCP] Dev mode started for '{}'", plugin_id);
+    Ok(plugin_id)
+}
+
+/// Tauri command: stop hot reload and unload the dev copy.
+///
+/// An installed copy of the same plugin is reloaded on next startup.
+#[tauri::command]
+pub async fn stop_plugin_dev(
+    plugin_id: String,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<(), String> {
+    let session = SESSIONS.lock().unwrap_or_else(|e| e.into_inner()).remove(&plugin_id);
+    let session = session.ok_or_else(|| format!("'{}' is not in dev mode", plugin_id))?;
+    session.task.abort();
+    health::forget(&plugin_id);
+    registry.remove_plugin(&plugin_id).await;
+    logs::notice(&plugin_id, "dev mode stopped");
+    log::info!("[MCP] Dev mode stopped for '{}'", plugin_id);
+    Ok(())
+}
+
+/// Tauri command: plugins currently in dev mode, with their source paths.
+#[tauri::command]
+pub fn get_dev_plugins() -> Vec<serde_json::Value> {
+    SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(id, s)| serde_json::json!({ "id": id, "path": s.dir.to_string_lossy() }))
+        .collect()
+}
+
+/// Tauri command: open (or focus) the log console for a plugin.
+#[tauri::command]
+pub fn open_plugin_console(app: tauri::AppHandle, plugin_id: String) -> Result<(), String> {
+    let label = format!("plugin-console-{}", plugin_id.replace(|c: char| !c.is_ascii_alphanumeric(), "-"));
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    let url = format!("plugin-console.html?plugin={}", urlencode(&plugin_id));
+    tauri::WebviewWindowBuilder::new(&app, &label, tauri::WebviewUrl::App(url.into()))
+        .title(format!("Plugin Console — {}", plugin_id))
+        .inner_size(720.0, 420.0)
+        .build()
+        .map_err(|e| format!("Failed to open console: {}", e))?;
+    Ok(())
+}
+
+/// Percent-encode a plugin ID for a query string.
+fn urlencode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,74 @@
+//! Change detection for a plugin source directory.
+//!
+//! Polling rather than OS file events: plugin directories are small, and
+//! a fingerprint of (path, size, mtime) per file is cheap to recompute
+//! and behaves the same on every platform and editor save strategy.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Directories that change constantly but never need a reload.
+const IGNORED_DIRS: &[&str] = &["node_modules", ".git", "__pycache__", "target", ".venv"];
+
+/// Stop walking after this many files (guards against huge trees).
+const MAX_FILES: usize = 5_000;
+
+/// Fingerprint of all files under `dir`. Changes when any file is added,
+/// removed, resized, or touched.
+pub fn fingerprint(dir: &Path) -> u64 {
+    let mut entries = Vec::new();
+    collect(dir, dir, &mut entries);
+    entries.sort();
+    let mut hasher = DefaultHasher::new();
+    entries.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn collect(root: &Path, dir: &Path, out: &mut Vec<(String, u64, u128)>) {
+    let Ok(read) = std::fs::read_dir(dir) else { return };
+    for entry in read.flatten() {
+        if out.len() >= MAX_FILES {
+            return;
+        }
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Ok(meta) = entry.metadata() else { continue };
+        if meta.is_dir() {
+            if !IGNORED_DIRS.contains(&name.as_ref()) {
+                collect(root, &entry.path(), out);
+            }
+            continue;
+        }
+        let rel = entry.path().strip_prefix(root).map(|p| p.to_string_lossy().into_owned());
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        out.push((rel.unwrap_or_default(), meta.len(), mtime));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_edits_and_ignores_dependencies() {
+        let dir = std::env::temp_dir().join("og-devmode-watch-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("node_modules")).unwrap();
+        std::fs::write(dir.join("index.js"), "a").unwrap();
+
+        let before = fingerprint(&dir);
+        std::fs::write(dir.join("node_modules/dep.js"), "x").unwrap();
+        assert_eq!(fingerprint(&dir), before);
+
+        std::fs::write(dir.join("index.js"), "ab").unwrap();
+        assert_ne!(fingerprint(&dir), before);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,172 @@
+//! Per-plugin and per-tool enablement.
+//!
+//! Users can keep a plugin installed but hide it — or only some of its
+//! tools — from the LLM. Disabled tools are left out of the classify
+//! `plugin_tools` prompt and the text launcher, and the execute router
+//! refuses them. The plugin process keeps running; re-enabling is instant.
+//!
+//! State lives in `~/.config/omni-glass/plugin-enablement.json` and is
+//! cached in memory after first use. Built-in tools cannot be disabled.
+//! The active workspace and profile can hide further plugins; those are
+//! added on read and never written here.
+
+use crate::error::OmniError;
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Disabled plugins and tools. Everything not listed is enabled.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EnablementStore {
+    #[serde(default)]
+    pub disabled_plugins: BTreeSet<String>,
+    /// Qualified tool names ("plugin_id:tool_name").
+    #[serde(default)]
+    pub disabled_tools: BTreeSet<String>,
+}
+
+impl EnablementStore {
+    /// Whether a tool may be offered to and executed for the user.
+    pub fn is_enabled(&self, plugin_id: &str, tool_name: &str) -> bool {
+        plugin_id == "builtin"
+            || (!self.disabled_plugins.contains(plugin_id)
+                && !self.disabled_tools.contains(&qualified(plugin_id, tool_name)))
+    }
+
+    pub fn set_plugin(&mut self, plugin_id: &str, enabled: bool) {
+        if enabled {
+            self.disabled_plugins.remove(plugin_id);
+        } else {
+            self.disabled_plugins.insert(plugin_id.to_string());
+        }
+    }
+
+    pub fn set_tool(&mut self, plugin_id: &str, tool_name: &str, enabled: bool) {
+        let qname = qualified(plugin_id, tool_name);
+        if enabled {
+            self.disabled_tools.remove(&qname);
+        } else {
+            self.disabled_tools.insert(qname);
+        }
+    }
+
+    /// Drop all entries for a plugin (on uninstall).
+    pub fn forget_plugin(&mut self, plugin_id: &str) {
+        let prefix = format!("{}:", plugin_id);
+        self.disabled_plugins.remove(plugin_id);
+        self.disabled_tools.retain(|q| !q.starts_with(&prefix));
+    }
+}
+
+fn qualified(plugin_id: &str, tool_name: &str) -> String {
+    crate::mcp::registry::qualified_name(plugin_id, tool_name)
+}
+
+static STORE: JsonStore<EnablementStore> = JsonStore::new("plugin-enablement.json");
+
+/// The user's own toggles, as persisted.
+fn stored() -> EnablementStore {
+    STORE.get()
+}
+
+/// Current enablement state, including plugins hidden by the active
+/// workspace (`history::workspace`) and the active profile (`settings`).
+pub fn current() -> EnablementStore {
+    let mut store = stored();
+    store.disabled_plugins.extend(crate::history::workspace::current().active_disabled_plugins());
+    if let Some(profile) = crate::settings::active_profile() {
+        store.disabled_plugins.extend(profile.disabled_plugins);
+    }
+    store
+}
+
+/// Whether a tool is enabled under the current state.
+pub fn is_enabled(plugin_id: &str, tool_name: &str) -> bool {
+    current().is_enabled(plugin_id, tool_name)
+}
+
+/// Apply `change` to the state and persist it.
+pub fn update(change: impl FnOnce(&mut EnablementStore)) -> Result<(), String> {
+    STORE.update(change)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: get disabled plugins and tools for the settings panel.
+/// Workspace- and profile-hidden plugins are listed by `get_workspaces`
+/// and `get_settings` instead.
+#[tauri::command]
+pub fn get_plugin_enablement() -> EnablementStore {
+    stored()
+}
+
+/// Tauri command: enable or disable every tool of a plugin.
+#[tauri::command]
+pub fn set_plugin_enabled(plugin_id: String, enabled: bool) -> Result<(), OmniError> {
+    update(|s| s.set_plugin(&plugin_id, enabled)).map_err(OmniError::Plugin)?;
+    log::info!("[MCP] Plugin '{}' {}", plugin_id, if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Tauri command: enable or disable a single plugin tool.
+#[tauri::command]
+pub fn set_tool_enabled(plugin_id: String, tool_name: String, enabled: bool) -> Result<(), OmniError> {
+    update(|s| s.set_tool(&plugin_id, &tool_name, enabled)).map_err(OmniError::Plugin)?;
+    log::info!(
+        "[MCP] Tool '{}:{}' {}",
+        plugin_id,
+        tool_name,
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn everything_enabled_by_default() {
+        let s = EnablementStore::default();
+        assert!(s.is_enabled("com.test.a", "tool"));
+    }
+
+    #[test]
+    fn plugin_and_tool_toggles() {
+        let mut s = EnablementStore::default();
+        s.set_tool("com.test.a", "one", false);
+        assert!(!s.is_enabled("com.test.a", "one"));
+        assert!(s.is_enabled("com.test.a", "two"));
+
+        s.set_plugin("com.test.a", false);
+        assert!(!s.is_enabled("com.test.a", "two"));
+        s.set_plugin("com.test.a", true);
+        assert!(s.is_enabled("com.test.a", "two"));
+        assert!(!s.is_enabled("com.test.a", "one"));
+    }
+
+    #[test]
+    fn builtins_cannot_be_disabled() {
+        let mut s = EnablementStore::default();
+        s.set_plugin("builtin", false);
+        assert!(s.is_enabled("builtin", "explain_error"));
+    }
+
+    #[test]
+    fn forget_drops_only_that_plugin() {
+        let mut s = EnablementStore::default();
+        s.set_plugin("com.test.a", false);
+        s.set_tool("com.test.a", "one", false);
+        s.set_tool("com.test.ab", "one", false);
+        s.forget_plugin("com.test.a");
+        assert!(s.disabled_plugins.is_empty());
+        assert_eq!(s.disabled_tools.len(), 1);
+    }
+
+    #[test]
+    fn parses_partial_file() {
+        let s: EnablementStore = serde_json::from_str(r#"{"disabledPlugins":["x.y"]}"#).unwrap();
+        assert!(!s.is_enabled("x.y", "t"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Plugin fan-out — one action that calls several plugins concurrently.
+//!
+//! When two or more plugins offer a tool with the same name (e.g. three
+//! price trackers each exposing `check_price`), the action menu gets an
+//! extra "Compare" entry whose id lists every tool:
+//! `compare:com.a.prices:check_price,com.b.prices:check_price`.
+//!
+//! Executing it runs each tool through `execute_plugin_tool` (args bridge,
+//! safety gates) with at most `MAX_CONCURRENT` calls in flight, all bounded
+//! by one shared deadline. Failures and stragglers don't sink the action:
+//! the merged markdown result shows every plugin's answer or why it's
+//! missing, and the action only errors if no plugin answered.
+
+use crate::llm::execute::{ActionResult, ActionResultBody};
+use crate::mcp::registry::{qualified_name, RegisteredTool, ToolRegistry};
+use futures_util::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Action-id prefix for fan-out actions.
+pub const ACTION_PREFIX: &str = "compare:";
+
+/// Most tools a single fan-out may call.
+pub const MAX_TOOLS: usize = 6;
+
+/// Calls in flight at once.
+const MAX_CONCURRENT: usize = 3;
+
+/// Shared deadline for the whole fan-out (args bridge + call per tool).
+const FANOUT_DEADLINE: Duration = Duration::from_secs(45);
+
+/// How one branch of a fan-out ended.
+#[derive(Debug, Clone)]
+pub enum BranchOutcome {
+    Done(Box<ActionResult>),
+    TimedOut,
+}
+
+/// Tool ids named by a fan-out action id. `None` unless the id has the
+/// prefix and names 2..=MAX_TOOLS distinct tools.
+pub fn parse_action_id(action_id: &str) -> Option<Vec<String>> {
+    let list = action_id.strip_prefix(ACTION_PREFIX)?;
+    let mut ids: Vec<String> = Vec::new();
+    for id in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if !ids.iter().any(|seen| seen == id) {
+            ids.push(id.to_string());
+        }
+    }
+    (2..=MAX_TOOLS).contains(&ids.len()).then_some(ids)
+}
+
+/// Prompt entries for fan-out actions: one per tool name that at least two
+/// of the offered plugins share. Same line format as `tools_for_prompt`.
+pub fn prompt_entries(tools: &[&RegisteredTool]) -> String {
+    let mut groups: BTreeMap<&str, Vec<&RegisteredTool>> = BTreeMap::new();
+    for tool in tools {
+        let group = groups.entry(tool.name.as_str()).or_default();
+        if !group.iter().any(|t| t.plugin_id == tool.plugin_id) {
+            group.push(tool);
+        }
+    }
+
+    let mut out = String::new();
+    for group in groups.values_mut().filter(|g| g.len() >= 2) {
+        group.sort_by(|a, b| a.plugin_id.cmp(&b.plugin_id));
+        group.truncate(MAX_TOOLS);
+        let ids: Vec<String> = group.iter().map(|t| qualified_name(&t.plugin_id, &t.name)).collect();
+        out.push_str(&format!(
+            "- id: \"{}{}\", label: \"Compare: {}\", description: \"Run '{}' on {} plugins and compare the results\", icon: \"sparkles\", requiresExecution: true\n",
+            ACTION_PREFIX,
+            ids.join(","),
+            group[0].display_name,
+            group[0].name,
+            group.len()
+        ));
+    }
+    out
+}
+
+/// Run every tool named by a fan-out action and merge the results.
+pub async fn execute(registry: &ToolRegistry, action_id: &str, tool_ids: &[String], input_text: &str) -> ActionResult {
+    let mut tools = Vec::new();
+    for id in tool_ids {
+        let Some(qname) = registry.resolve_action(id).await else {
+            return ActionResult::error(action_id, &format!("Tool '{}' is not installed", id));
+        };
+        match registry.get_tool(&qname).await {
+            Some(tool) if tool.plugin_id != "builtin" => tools.push((qname, tool)),
+            _ => return ActionResult::error(action_id, &format!("'{}' is not a plugin tool", id)),
+        }
+    }
+
+    log::info!("[MCP] Fan-out '{}' across {} tools", action_id, tools.len());
+    let deadline = tokio::time::Instant::now() + FANOUT_DEADLINE;
+    let mut branches: Vec<(usize, String, BranchOutcome)> = stream::iter(tools.into_iter().enumerate())
+        .map(|(i, (qname, tool))| async move {
+            let call = crate::mcp::execute_plugin_tool(
+                registry,
+                &qname,
+                input_text,
+                Some(&tool.description),
+                tool.input_schema.as_ref(),
+            );
+            let outcome = match tokio::time::timeout_at(deadline, call).await {
+                Ok(result) => BranchOutcome::Done(Box::new(result)),
+                Err(_) => {
+                    log::warn!("[MCP] Fan-out branch '{}' missed the deadline", qname);
+                    BranchOutcome::TimedOut
+                }
+            };
+            (i, format!("{} ({})", tool.display_name, tool.plugin_id), outcome)
+        })
+        .buffer_unordered(MAX_CONCURRENT)
+        .collect()
+        .await;
+
+    // Present results in the order the action listed them
+    branches.sort_by_key(|(i, _, _)| *i);
+    let branches: Vec<(String, BranchOutcome)> = branches.into_iter().map(|(_, l, o)| (l, o)).collect();
+    merge(action_id, &branches)
+}
+
+/// Merge branch results into one markdown text result.
+pub fn merge(action_id: &str, branches: &[(String, BranchOutcome)]) -> ActionResult {
+    let mut sections = Vec::new();
+    let mut answered = 0;
+    for (label, outcome) in branches {
+        let body = match outcome {
+            BranchOutcome::Done(r) if r.status == "success" => {
+                answered += 1;
+                section_text(&r.result)
+            }
+            BranchOutcome::Done(r) => format!("_Failed: {}_", r.result.text.as_deref().unwrap_or("unknown error")),
+            BranchOutcome::TimedOut => format!("_No answer within {}s._", FANOUT_DEADLINE.as_secs()),
+        };
+        sections.push(format!("**{}**\n\n{}", label, body.trim()));
+    }
+    let summary = format!("_{} of {} plugins answered._", answered, branches.len());
+    let text = format!("{}\n\n---\n\n{}", sections.join("\n\n---\n\n"), summary);
+
+    if answered == 0 {
+        return ActionResult::error(action_id, &text);
+    }
+    ActionResult {
+        status: "success".to_string(),
+        action_id: action_id.to_string(),
+        result: ActionResultBody {
+            result_type: "text".to_string(),
+            text: Some(text),
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: Some("text/markdown".to_string()),
+            email: None,
+        },
+        metadata: None,
+    }
+}
+
+/// Text shown for one successful branch. Files and other non-text results
+/// can't be merged, so they are only named.
+fn section_text(body: &ActionResultBody) -> String {
+    match body.result_type.as_str() {
+        "text" => body.text.clone().unwrap_or_default(),
+        "file" => format!(
+            "_Returned a file ({}) — run this plugin on its own to save it._",
+            body.file_path.as_deref().unwrap_or("unnamed")
+        ),
+        other => format!("_Returned a {} result — run this plugin on its own to use it._", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(plugin_id: &str, name: &str) -> RegisteredTool {
+        RegisteredTool {
+            plugin_id: plugin_id.to_string(),
+            name: name.to_string(),
+            display_name: "Check Price".to_string(),
+            description: String::new(),
+            input_schema: None,
+        }
+    }
+
+    fn text_result(status: &str, text: &str) -> BranchOutcome {
+        let mut r = ActionResult::error("x", text);
+        r.status = status.to_string();
+        BranchOutcome::Done(Box::new(r))
+    }
+
+    #[test]
+    fn action_ids_need_two_to_max_distinct_tools() {
+        assert_eq!(
+            parse_action_id("compare:a.b:check,c.d:check,a.b:check").unwrap(),
+            vec!["a.b:check", "c.d:check"]
+        );
+        assert!(parse_action_id("compare:a.b:check").is_none());
+        assert!(parse_action_id("a.b:check,c.d:check").is_none());
+        let many: Vec<String> = (0..=MAX_TOOLS).map(|i| format!("p{}:t", i)).collect();
+        assert!(parse_action_id(&format!("{}{}", ACTION_PREFIX, many.join(","))).is_none());
+    }
+
+    #[test]
+    fn prompt_groups_shared_tool_names_across_plugins() {
+        let tools = [tool("com.b", "check_price"), tool("com.a", "check_price"), tool("com.a", "other")];
+        let refs: Vec<&RegisteredTool> = tools.iter().collect();
+        let out = prompt_entries(&refs);
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.contains("id: \"compare:com.a:check_price,com.b:check_price\""));
+        assert!(prompt_entries(&refs[2..]).is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_partial_results() {
+        let branches = vec![
+            ("A".to_string(), text_result("success", "$10")),
+            ("B".to_string(), text_result("error", "boom")),
+            ("C".to_string(), BranchOutcome::TimedOut),
+        ];
+        let merged = merge("compare:x", &branches);
+        assert_eq!(merged.status, "success");
+        let text = merged.result.text.unwrap();
+        assert!(text.contains("**A**\n\n$10"));
+        assert!(text.contains("_Failed: boom_"));
+        assert!(text.contains("_1 of 3 plugins answered._"));
+
+        let failed = merge("compare:x", &branches[1..]);
+        assert_eq!(failed.status, "error");
+    }
+}
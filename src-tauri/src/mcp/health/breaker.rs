@@ -0,0 +1,138 @@
+//! Circuit breaker and restart backoff — pure timing logic.
+//!
+//! A plugin that fails `FAILURE_THRESHOLD` calls in a row "trips" its
+//! circuit: its tools are hidden from prompts and calls are refused until
+//! the open period ends. Each consecutive trip doubles the open period.
+//! After it ends the circuit is half-open — the next call decides whether
+//! it closes (success) or re-opens (failure).
+
+use std::time::{Duration, Instant};
+
+/// Consecutive failures that open the circuit.
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// First open period; doubles per consecutive trip up to `MAX_OPEN`.
+const BASE_OPEN: Duration = Duration::from_secs(30);
+const MAX_OPEN: Duration = Duration::from_secs(10 * 60);
+
+/// Restart delays: 1s, 2s, 4s, ... capped at `MAX_RESTART_DELAY`.
+const BASE_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// Restarts allowed before giving up on a crashing plugin.
+pub const MAX_RESTARTS: u32 = 5;
+
+#[derive(Debug, Default, Clone)]
+pub struct Breaker {
+    consecutive_failures: u32,
+    /// Trips since the circuit last closed cleanly.
+    trips: u32,
+    open_until: Option<Instant>,
+}
+
+impl Breaker {
+    /// A successful call closes the circuit and resets the counters.
+    pub fn record_success(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Record a failed call. Returns `true` if this failure opened the circuit.
+    pub fn record_failure(&mut self, now: Instant) -> bool {
+        self.consecutive_failures += 1;
+        // Half-open probe failed, or threshold reached
+        let half_open = self.open_until.is_some_and(|t| now >= t);
+        if half_open || self.consecutive_failures >= FAILURE_THRESHOLD {
+            let open_for = BASE_OPEN
+                .saturating_mul(1 << self.trips.min(8))
+                .min(MAX_OPEN);
+            self.open_until = Some(now + open_for);
+            self.trips += 1;
+            self.consecutive_failures = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Whether calls should be refused right now.
+    pub fn is_open(&self, now: Instant) -> bool {
+        self.open_until.is_some_and(|t| now < t)
+    }
+
+    /// When the open period ends (if open).
+    pub fn open_until(&self) -> Option<Instant> {
+        self.open_until
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+}
+
+/// Delay before restart attempt `attempt` (0-based).
+pub fn restart_delay(attempt: u32) -> Duration {
+    BASE_RESTART_DELAY
+        .saturating_mul(1 << attempt.min(16))
+        .min(MAX_RESTART_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold() {
+        let now = Instant::now();
+        let mut b = Breaker::default();
+        assert!(!b.record_failure(now));
+        assert!(!b.record_failure(now));
+        assert!(b.record_failure(now));
+        assert!(b.is_open(now));
+        assert!(!b.is_open(now + BASE_OPEN));
+    }
+
+    #[test]
+    fn success_resets() {
+        let now = Instant::now();
+        let mut b = Breaker::default();
+        b.record_failure(now);
+        b.record_failure(now);
+        b.record_success();
+        assert!(!b.record_failure(now));
+        assert_eq!(b.consecutive_failures(), 1);
+    }
+
+    #[test]
+    fn half_open_failure_reopens_for_longer() {
+        let now = Instant::now();
+        let mut b = Breaker::default();
+        for _ in 0..FAILURE_THRESHOLD {
+            b.record_failure(now);
+        }
+        let probe = now + BASE_OPEN;
+        assert!(!b.is_open(probe));
+        assert!(b.record_failure(probe)); // single failure re-opens
+        assert!(b.is_open(probe + BASE_OPEN));
+        assert!(!b.is_open(probe + BASE_OPEN * 2));
+    }
+
+    #[test]
+    fn open_period_is_capped() {
+        let mut now = Instant::now();
+        let mut b = Breaker::default();
+        for _ in 0..FAILURE_THRESHOLD {
+            b.record_failure(now);
+        }
+        for _ in 0..20 {
+            now = b.open_until().unwrap();
+            b.record_failure(now);
+        }
+        assert_eq!(b.open_until().unwrap() - now, MAX_OPEN);
+    }
+
+    #[test]
+    fn restart_backoff_doubles_then_caps() {
+        assert_eq!(restart_delay(0), Duration::from_secs(1));
+        assert_eq!(restart_delay(3), Duration::from_secs(8));
+        assert_eq!(restart_delay(30), MAX_RESTART_DELAY);
+    }
+}
@@ -0,0 +1,239 @@
+//! Plugin health — crash restarts, circuit breaking, and status.
+//!
+//! - Every tool call reports success/failure here (`registry/dispatch.rs`).
+//!   Repeated failures trip a circuit breaker (`breaker.rs`) that hides the
+//!   plugin's tools from prompts until it cools down.
+//! - A supervisor task notices stdio servers that exited (crashed, or
+//!   killed after a call timeout) and reloads them with exponential backoff,
+//!   giving up after `MAX_RESTARTS` attempts in a row.
+//! - `get_plugin_health` reports per-plugin state; changes are emitted to
+//!   the frontend as `plugin-health`.
+
+pub mod breaker;
+
+use crate::mcp::manifest::PluginManifest;
+use crate::mcp::registry::ToolRegistry;
+use breaker::{Breaker, MAX_RESTARTS};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+/// How often the supervisor checks for exited servers and cooled circuits.
+const SUPERVISOR_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthState {
+    Healthy,
+    Restarting,
+    CircuitOpen,
+    /// Crashed more than `MAX_RESTARTS` times in a row; not restarted.
+    Failed,
+}
+
+/// Health snapshot sent to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginHealth {
+    pub plugin_id: String,
+    pub state: HealthState,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    /// Seconds until an open circuit is retried.
+    pub retry_in_secs: Option<u64>,
+}
+
+#[derive(Default)]
+struct Tracked {
+    breaker: Breaker,
+    restarting: bool,
+    failed: bool,
+    restarts: u32,
+    total_failures: u64,
+    last_error: Option<String>,
+    /// What to reload on crash (stdio / WASM plugins loaded from disk).
+    source: Option<(PluginManifest, PathBuf)>,
+}
+
+impl Tracked {
+    fn snapshot(&self, plugin_id: &str, now: Instant) -> PluginHealth {
+        let open = self.breaker.is_open(now);
+        PluginHealth {
+            plugin_id: plugin_id.to_string(),
+            state: if self.failed {
+                HealthState::Failed
+            } else if self.restarting {
+                HealthState::Restarting
+            } else if open {
+                HealthState::CircuitOpen
+            } else {
+                HealthState::Healthy
+            },
+            consecutive_failures: self.breaker.consecutive_failures(),
+            total_failures: self.total_failures,
+            restarts: self.restarts,
+            last_error: self.last_error.clone(),
+            retry_in_secs: self
+                .breaker
+                .open_until()
+                .filter(|_| open)
+                .map(|t| t.saturating_duration_since(now).as_secs()),
+        }
+    }
+}
+
+static TRACKED: LazyLock<Mutex<HashMap<String, Tracked>>> = LazyLock::new(Default::default);
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+fn with_tracked<R>(plugin_id: &str, f: impl FnOnce(&mut Tracked) -> R) -> R {
+    let mut map = TRACKED.lock().unwrap_or_else(|e| e.into_inner());
+    f(map.entry(plugin_id.to_string()).or_default())
+}
+
+fn emit(plugin_id: &str) {
+    let Some(app) = APP.get() else { return };
+    let snapshot = with_tracked(plugin_id, |t| t.snapshot(plugin_id, Instant::now()));
+    let _ = app.emit("plugin-health", &snapshot);
+}
+
+/// Record a successfully loaded plugin so it can be restarted on crash.
+pub fn track(manifest: &PluginManifest, plugin_dir: &Path) {
+    with_tracked(&manifest.id, |t| {
+        t.source = Some((manifest.clone(), plugin_dir.to_path_buf()));
+        t.restarting = false;
+        t.failed = false;
+    });
+    emit(&manifest.id);
+}
+
+/// Declared manifest and directory of a loaded stdio / WASM plugin.
+pub fn source(plugin_id: &str) -> Option<(PluginManifest, PathBuf)> {
+    let map = TRACKED.lock().unwrap_or_else(|e| e.into_inner());
+    map.get(plugin_id).and_then(|t| t.source.clone())
+}
+
+/// Stop tracking a plugin (uninstall).
+pub fn forget(plugin_id: &str) {
+    TRACKED.lock().unwrap_or_else(|e| e.into_inner()).remove(plugin_id);
+}
+
+pub fn is_circuit_open(plugin_id: &str) -> bool {
+    with_tracked(plugin_id, |t| t.breaker.is_open(Instant::now()))
+}
+
+pub fn record_success(plugin_id: &str) {
+    let changed = with_tracked(plugin_id, |t| {
+        let changed = t.breaker.consecutive_failures() > 0 || t.restarts > 0;
+        t.breaker.record_success();
+        t.restarts = 0;
+        changed
+    });
+    if changed {
+        emit(plugin_id);
+    }
+}
+
+/// Record a failed call. Returns `true` if the circuit just opened.
+pub fn record_failure(plugin_id: &str, error: &str) -> bool {
+    let tripped = with_tracked(plugin_id, |t| {
+        t.total_failures += 1;
+        t.last_error = Some(error.to_string());
+        t.breaker.record_failure(Instant::now())
+    });
+    if tripped {
+        log::warn!("[MCP] Circuit open for '{}' after repeated failures: {}", plugin_id, error);
+    }
+    emit(plugin_id);
+    tripped
+}
+
+/// Reload a crashed plugin after its backoff delay.
+fn schedule_restart(app: tauri::AppHandle, plugin_id: String) {
+    let plan = with_tracked(&plugin_id, |t| {
+        let source = t.source.clone()?;
+        if t.restarts >= MAX_RESTARTS {
+            t.failed = true;
+            t.restarting = false;
+            return None;
+        }
+        let attempt = t.restarts;
+        t.restarts += 1;
+        t.restarting = true;
+        Some((attempt, source))
+    });
+    let Some((attempt, (manifest, dir))) = plan else {
+        log::error!("[MCP] '{}' keeps crashing — giving up after {} restarts", plugin_id, MAX_RESTARTS);
+        emit(&plugin_id);
+        return;
+    };
+    emit(&plugin_id);
+
+    tauri::async_runtime::spawn(async move {
+        let delay = breaker::restart_delay(attempt);
+        log::info!("[MCP] Restarting '{}' in {}s (attempt {})", plugin_id, delay.as_secs(), attempt + 1);
+        tokio::time::sleep(delay).await;
+        if !dir.is_dir() {
+            return; // uninstalled meanwhile
+        }
+        let registry = app.state::<ToolRegistry>();
+        if let Err(e) = crate::mcp::loader::load_approved_plugin(&manifest, &dir, &registry).await {
+            log::warn!("[MCP] Restart of '{}' failed: {}", plugin_id, e);
+            with_tracked(&plugin_id, |t| t.last_error = Some(e));
+            registry.remove_plugin(&plugin_id).await;
+            schedule_restart(app.clone(), plugin_id);
+        }
+    });
+}
+
+/// Start the supervisor loop. Called once from lib.rs after plugins load.
+pub fn spawn_supervisor(app: tauri::AppHandle) {
+    let _ = APP.set(app.clone());
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SUPERVISOR_INTERVAL).await;
+            let registry = app.state::<ToolRegistry>();
+
+            for (id, killed) in registry.exited_servers().await {
+                // A server killed after a timeout already counted that call
+                if !killed {
+                    log::warn!("[MCP] Plugin '{}' exited unexpectedly", id);
+                    record_failure(&id, "server process exited");
+                }
+                registry.remove_plugin(&id).await;
+                schedule_restart(app.clone(), id);
+            }
+
+            // Cooled-down circuits go half-open: show tools, let one call probe
+            let now = Instant::now();
+            let cooled: Vec<String> = TRACKED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .filter(|(_, t)| t.breaker.open_until().is_some_and(|u| now >= u))
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in cooled {
+                if !registry.is_available(&id).await && !is_circuit_open(&id) {
+                    registry.set_available(&id, true).await;
+                    log::info!("[MCP] Circuit half-open for '{}' — retrying", id);
+                    emit(&id);
+                }
+            }
+        }
+    });
+}
+
+/// Tauri command: health of every plugin that has been loaded or called.
+#[tauri::command]
+pub fn get_plugin_health() -> Vec<PluginHealth> {
+    let now = Instant::now();
+    let map = TRACKED.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out: Vec<PluginHealth> = map.iter().map(|(id, t)| t.snapshot(id, now)).collect();
+    out.sort_by(|a, b| a.plugin_id.cmp(&b.plugin_id));
+    out
+}
@@ -0,0 +1,130 @@
+//! Host requests — JSON-RPC calls a stdio plugin makes back to Omni-Glass.
+//!
+//! MCP is bidirectional: while handling a `tools/call`, a plugin may write
+//! its own request to stdout. `client/framing.rs` passes those here and
+//! writes the reply to the plugin's stdin. Supported namespaces:
+//!
+//! - `omniglass/storage/*` — plugin-local storage (`storage/rpc.rs`)
+//! - `omniglass/clipboard/*` — brokered clipboard (`clipboard.rs`)
+//!
+//! Anything else gets JSON-RPC "method not found".
+//!
+//! The time spent answering is tracked per plugin (`host_time`), so call
+//! timeouts can leave it out: a clipboard prompt waits on the user.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const SERVER_ERROR: i64 = -32000;
+/// The user (or policy) refused the request.
+pub const DENIED: i64 = -32001;
+
+/// Error half of a host request: JSON-RPC code + message.
+pub type RpcError = (i64, String);
+
+/// What the connection is allowed to use, from the plugin's manifest.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostCaps {
+    pub clipboard: bool,
+}
+
+/// A request from a plugin.
+#[derive(Debug)]
+pub struct HostRequest {
+    pub id: Value,
+    pub method: String,
+    pub params: Value,
+}
+
+/// Parse a stdout line as a plugin→host request. `None` for responses,
+/// notifications, and non-JSON output.
+pub fn parse_request(line: &str) -> Option<HostRequest> {
+    let msg: Value = serde_json::from_str(line).ok()?;
+    Some(HostRequest {
+        method: msg.get("method")?.as_str()?.to_string(),
+        id: msg.get("id")?.clone(),
+        params: msg.get("params").cloned().unwrap_or(Value::Null),
+    })
+}
+
+/// Per plugin: time spent on finished requests, and when the one being
+/// answered started.
+static HOST_TIME: Mutex<BTreeMap<String, (Duration, Option<Instant>)>> = Mutex::new(BTreeMap::new());
+
+/// Total time spent answering `plugin_id`'s requests, including one still
+/// being answered.
+pub fn host_time(plugin_id: &str) -> Duration {
+    let times = HOST_TIME.lock().unwrap_or_else(|e| e.into_inner());
+    times
+        .get(plugin_id)
+        .map(|(spent, open)| *spent + open.map_or(Duration::ZERO, |started| started.elapsed()))
+        .unwrap_or_default()
+}
+
+fn set_open(plugin_id: &str, open: bool) {
+    let mut times = HOST_TIME.lock().unwrap_or_else(|e| e.into_inner());
+    let (spent, started) = times.entry(plugin_id.to_string()).or_default();
+    if let Some(started) = started.take() {
+        *spent += started.elapsed();
+    }
+    if open {
+        *started = Some(Instant::now());
+    }
+}
+
+/// Handle a request and build the JSON-RPC response to send back.
+pub async fn handle(plugin_id: &str, caps: HostCaps, req: HostRequest) -> Value {
+    set_open(plugin_id, true);
+    let outcome = answer(plugin_id, caps, &req).await;
+    set_open(plugin_id, false);
+    reply(plugin_id, &req, outcome)
+}
+
+async fn answer(plugin_id: &str, caps: HostCaps, req: &HostRequest) -> Result<Value, RpcError> {
+    if let Some(op) = req.method.strip_prefix(crate::mcp::storage::rpc::METHOD_PREFIX) {
+        crate::mcp::storage::PluginStorage::open(plugin_id)
+            .map_err(|e| (SERVER_ERROR, e))
+            .and_then(|storage| crate::mcp::storage::rpc::dispatch(&storage, op, &req.params))
+    } else if let Some(op) = req.method.strip_prefix(crate::mcp::clipboard::METHOD_PREFIX) {
+        crate::mcp::clipboard::dispatch(plugin_id, caps.clipboard, op, &req.params).await
+    } else {
+        Err((METHOD_NOT_FOUND, format!("Method '{}' not supported by host", req.method)))
+    }
+}
+
+fn reply(plugin_id: &str, req: &HostRequest, outcome: Result<Value, RpcError>) -> Value {
+    match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": req.id, "result": result }),
+        Err((code, message)) => {
+            log::debug!("[MCP] '{}' {} failed: {}", plugin_id, req.method, message);
+            json!({ "jsonrpc": "2.0", "id": req.id, "error": { "code": code, "message": message } })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_requests_are_parsed() {
+        assert!(parse_request(r#"{"jsonrpc":"2.0","id":1,"result":{}}"#).is_none());
+        assert!(parse_request(r#"{"jsonrpc":"2.0","method":"notifications/x"}"#).is_none());
+        assert!(parse_request("plain log output").is_none());
+        let req = parse_request(r#"{"jsonrpc":"2.0","id":"r1","method":"omniglass/storage/keys"}"#).unwrap();
+        assert_eq!(req.id, "r1");
+        assert_eq!(req.params, Value::Null);
+    }
+
+    #[test]
+    fn errors_echo_the_request_id() {
+        let req = parse_request(r#"{"jsonrpc":"2.0","id":7,"method":"sampling/createMessage"}"#).unwrap();
+        let out = reply("com.example.a", &req, Err((METHOD_NOT_FOUND, "nope".into())));
+        assert_eq!(out["id"], 7);
+        assert_eq!(out["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
@@ -0,0 +1,145 @@
+//! Plugin bundle unpacking (`.zip`).
+//!
+//! Bundles come from the network, so extraction is defensive: entries
+//! that escape the destination (zip-slip), symlinks, and archives that
+//! expand past a size or entry budget are all rejected.
+
+use crate::mcp::manifest::MANIFEST_FILENAME;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Maximum total uncompressed size of a bundle.
+const MAX_UNPACKED_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Maximum number of entries in a bundle.
+const MAX_ENTRIES: usize = 5_000;
+
+/// Extract a zip bundle into `dest` (which must already exist).
+pub fn extract_zip(bytes: &[u8], dest: &Path) -> Result<(), String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Not a valid plugin bundle: {}", e))?;
+    if archive.len() > MAX_ENTRIES {
+        return Err(format!("Bundle has too many entries ({})", archive.len()));
+    }
+
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = entry.name().to_string();
+        let rel = entry
+            .enclosed_name()
+            .ok_or_else(|| format!("Bundle entry '{}' escapes the plugin directory", name))?;
+        if entry.is_symlink() {
+            return Err(format!("Bundle entry '{}' is a symlink", name));
+        }
+        let out = dest.join(rel);
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&out).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        // Enforce the budget on bytes actually read, not the declared size
+        let remaining = MAX_UNPACKED_BYTES - total;
+        let mut file = std::fs::File::create(&out).map_err(|e| e.to_string())?;
+        let written = std::io::copy(&mut (&mut entry).take(remaining + 1), &mut file)
+            .map_err(|e| format!("Failed to extract '{}': {}", name, e))?;
+        if written > remaining {
+            return Err("Bundle is too large when unpacked".to_string());
+        }
+        total += written;
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            // Keep the exec bits (binary plugins); never setuid/setgid
+            let mode = (mode & 0o755) | 0o600;
+            let _ = std::fs::set_permissions(&out, std::fs::Permissions::from_mode(mode));
+        }
+    }
+    Ok(())
+}
+
+/// Locate the plugin root inside an extracted bundle: either the bundle
+/// root itself or its single top-level directory.
+pub fn find_plugin_root(extracted: &Path) -> Result<PathBuf, String> {
+    if extracted.join(MANIFEST_FILENAME).is_file() {
+        return Ok(extracted.to_path_buf());
+    }
+    let dirs: Vec<PathBuf> = std::fs::read_dir(extracted)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    match dirs.as_slice() {
+        [only] if only.join(MANIFEST_FILENAME).is_file() => Ok(only.clone()),
+        _ => Err(format!("Bundle has no {} at its root", MANIFEST_FILENAME)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn bundle(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut buf = std::io::Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buf);
+        for (name, body) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+        buf.into_inner()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("og-archive-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn extracts_and_finds_nested_root() {
+        let dir = temp_dir("nested");
+        let bytes = bundle(&[("my-plugin/omni-glass.plugin.json", "{}"), ("my-plugin/index.js", "")]);
+        extract_zip(&bytes, &dir).unwrap();
+        assert_eq!(find_plugin_root(&dir).unwrap(), dir.join("my-plugin"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn finds_manifest_at_bundle_root() {
+        let dir = temp_dir("flat");
+        extract_zip(&bundle(&[("omni-glass.plugin.json", "{}")]), &dir).unwrap();
+        assert_eq!(find_plugin_root(&dir).unwrap(), dir);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_zip_slip() {
+        let dir = temp_dir("slip");
+        let err = extract_zip(&bundle(&[("../../evil.sh", "rm -rf ~")]), &dir).unwrap_err();
+        assert!(err.contains("escapes"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_bundle_without_manifest() {
+        let dir = temp_dir("nomanifest");
+        extract_zip(&bundle(&[("index.js", "")]), &dir).unwrap();
+        assert!(find_plugin_root(&dir).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_garbage_bytes() {
+        assert!(extract_zip(b"not a zip", &temp_dir("garbage")).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! Community plugin catalog — a curated JSON index for one-click install.
+//!
+//! The index is fetched over https and cached in
+//! `~/.config/omni-glass/catalog-cache.json`. A fresh cache is served
+//! without a network call; a stale one is refreshed, and if the fetch
+//! fails (or the caller asks for offline mode) the cached copy is used.
+//!
+//! ```json
+//! { "plugins": [{ "id": "com.example.notes", "name": "Notes", "version": "1.0.0",
+//!   "description": "...", "permissions": { "network": ["api.example.com"] },
+//!   "downloadUrl": "https://.../notes-1.0.0.zip", "sha256": "<hex>" }] }
+//! ```
+//!
+//! Entries carry the digest that `install_plugin` verifies, so the UI
+//! passes `downloadUrl` + `sha256` straight through.
+
+use super::verify;
+use crate::mcp::manifest::Permissions;
+use crate::mcp::sandbox::risk::{self, RiskLevel};
+use crate::mcp::{loader, permissions};
+use crate::policy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default index location (`plugins/catalog.json` in the repository);
+/// `OMNI_GLASS_CATALOG_URL` overrides it.
+const DEFAULT_CATALOG_URL: &str =
+    "https://raw.githubusercontent.com/goshtasb/OmniGlass/main/plugins/catalog.json";
+
+/// How long a cached index is served without refetching.
+const CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+
+/// Largest index we will accept.
+const MAX_INDEX_BYTES: usize = 2 * 1024 * 1024;
+
+/// One plugin listed in the catalog.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogEntry {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub permissions: Permissions,
+    pub download_url: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogCache {
+    fetched_at: u64,
+    plugins: Vec<CatalogEntry>,
+}
+
+#[derive(Deserialize)]
+struct CatalogIndex {
+    plugins: Vec<CatalogEntry>,
+}
+
+/// Catalog entry plus local state, as shown in the Settings UI.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogListing {
+    #[serde(flatten)]
+    pub entry: CatalogEntry,
+    pub risk_level: RiskLevel,
+    pub installed: bool,
+    pub blocked: bool,
+}
+
+/// Reject entries the installer would refuse anyway.
+pub fn validate_entry(e: &CatalogEntry) -> Result<(), String> {
+    if !verify::is_safe_plugin_id(&e.id) || !e.id.contains('.') {
+        return Err(format!("invalid id '{}'", e.id));
+    }
+    if !e.download_url.starts_with("https://") {
+        return Err("downloadUrl must be https".to_string());
+    }
+    let hex = e.sha256.strip_prefix("sha256:").unwrap_or(&e.sha256);
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("sha256 is not a SHA-256 digest".to_string());
+    }
+    permissions::validate_permissions(&e.permissions)
+}
+
+/// Parse an index document, dropping invalid entries.
+pub fn parse_index(raw: &[u8]) -> Result<Vec<CatalogEntry>, String> {
+    let index: CatalogIndex =
+        serde_json::from_slice(raw).map_err(|e| format!("Invalid catalog index: {}", e))?;
+    Ok(index
+        .plugins
+        .into_iter()
+        .filter(|e| match validate_entry(e) {
+            Ok(()) => true,
+            Err(err) => {
+                log::warn!("[CATALOG] Skipping '{}': {}", e.id, err);
+                false
+            }
+        })
+        .collect())
+}
+
+/// Whether a cache written at `fetched_at` is still fresh at `now`.
+pub fn is_fresh(fetched_at: u64, now: u64) -> bool {
+    now.saturating_sub(fetched_at) < CACHE_TTL_SECS
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("catalog-cache.json"))
+}
+
+fn read_cache() -> Option<CatalogCache> {
+    let raw = std::fs::read(cache_path()?).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+fn write_cache(cache: &CatalogCache) {
+    let Some(path) = cache_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_vec_pretty(cache) {
+        if let Err(e) = std::fs::write(&path, json) {
+            log::warn!("[CATALOG] Failed to write cache: {}", e);
+        }
+    }
+}
+
+async fn fetch_index() -> Result<Vec<CatalogEntry>, String> {
+    let url = std::env::var("OMNI_GLASS_CATALOG_URL").unwrap_or_else(|_| DEFAULT_CATALOG_URL.into());
+    if !url.starts_with("https://") {
+        return Err("Catalog URL must use https".to_string());
+    }
+    let client = crate::net::client_builder()?
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut response = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Catalog fetch failed: {}", e))?;
+    // A declared size over the cap is refused before reading the body;
+    // an undeclared or wrong one is caught while reading.
+    if response.content_length().is_some_and(|len| len > MAX_INDEX_BYTES as u64) {
+        return Err("Catalog index is too large".to_string());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Catalog fetch failed: {}", e))? {
+        if bytes.len() + chunk.len() > MAX_INDEX_BYTES {
+            return Err("Catalog index is too large".to_string());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    parse_index(&bytes)
+}
+
+/// Tauri command: list catalog plugins for the Settings UI.
+///
+/// `offline` serves only the cache; `refresh` ignores the cache TTL.
+#[tauri::command]
+pub async fn list_catalog_plugins(
+    offline: Option<bool>,
+    refresh: Option<bool>,
+) -> Result<serde_json::Value, String> {
+    let cached = read_cache();
+    let cache_fresh = cached.as_ref().is_some_and(|c| is_fresh(c.fetched_at, now_secs()));
+    let use_cache = offline.unwrap_or(false) || (cache_fresh && !refresh.unwrap_or(false));
+
+    let (plugins, fetched_at, from_cache) = match cached {
+        Some(c) if use_cache => (c.plugins, c.fetched_at, true),
+        _ if offline.unwrap_or(false) => return Err("No cached catalog available offline".to_string()),
+        cached => match fetch_index().await {
+            Ok(plugins) => {
+                let cache = CatalogCache { fetched_at: now_secs(), plugins };
+                write_cache(&cache);
+                (cache.plugins, cache.fetched_at, false)
+            }
+            Err(e) => {
+                let c = cached.ok_or(e.clone())?;
+                log::warn!("[CATALOG] {}; serving cached index", e);
+                (c.plugins, c.fetched_at, true)
+            }
+        },
+    };
+
+    let plugins_dir = loader::plugins_dir();
+    let active = policy::current();
+    let listings: Vec<CatalogListing> = plugins
+        .into_iter()
+        .map(|entry| CatalogListing {
+            risk_level: risk::calculate_risk(&entry.permissions),
+            installed: plugins_dir.as_ref().is_some_and(|d| d.join(&entry.id).is_dir()),
+            blocked: !policy::precedence::is_plugin_allowed(active, &entry.id),
+            entry,
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "plugins": listings,
+        "fetchedAt": fetched_at,
+        "fromCache": from_cache,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn entry(id: &str, url: &str, sha: &str) -> CatalogEntry {
+        CatalogEntry {
+            id: id.into(),
+            name: "Test".into(),
+            version: "1.0.0".into(),
+            description: String::new(),
+            permissions: Permissions::default(),
+            download_url: url.into(),
+            sha256: sha.into(),
+        }
+    }
+
+    #[test]
+    fn validates_entries() {
+        assert!(validate_entry(&entry("com.test.a", "https://x.io/a.zip", HASH)).is_ok());
+        let prefixed = format!("sha256:{}", HASH);
+        assert!(validate_entry(&entry("com.test.a", "https://x.io/a.zip", &prefixed)).is_ok());
+        assert!(validate_entry(&entry("com.test.a", "http://x.io/a.zip", HASH)).is_err());
+        assert!(validate_entry(&entry("com.test.a", "https://x.io/a.zip", "abc")).is_err());
+        assert!(validate_entry(&entry("../evil", "https://x.io/a.zip", HASH)).is_err());
+    }
+
+    #[test]
+    fn parse_drops_invalid_entries() {
+        let raw = format!(
+            r#"{{"plugins":[
+                {{"id":"com.test.good","name":"Good","version":"1.0.0",
+                  "downloadUrl":"https://x.io/g.zip","sha256":"{h}"}},
+                {{"id":"com.test.bad","name":"Bad","version":"1.0.0",
+                  "downloadUrl":"ftp://x.io/b.zip","sha256":"{h}"}}
+            ]}}"#,
+            h = HASH
+        );
+        let plugins = parse_index(raw.as_bytes()).unwrap();
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].id, "com.test.good");
+        assert!(parse_index(b"[]").is_err());
+    }
+
+    #[test]
+    fn cache_freshness() {
+        assert!(is_fresh(1_000, 1_000 + CACHE_TTL_SECS - 1));
+        assert!(!is_fresh(1_000, 1_000 + CACHE_TTL_SECS));
+        assert!(is_fresh(2_000, 1_000)); // clock went backwards
+    }
+}
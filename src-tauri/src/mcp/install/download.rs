@@ -0,0 +1,77 @@
+//! Resumable bundle downloads.
+//!
+//! Bytes stream into `plugins/.downloads/<hash of url>.part` instead of
+//! memory, so a download cut short by a crash or restart picks up where
+//! it stopped: the next attempt asks for the rest with an HTTP `Range`
+//! request. A server that ignores the range answers 200 and the file is
+//! rewritten from the start. The part file is removed once the bundle is
+//! installed or fails its digest check.
+
+use super::verify;
+use crate::mcp::loader;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Where the partial download for `url` lives.
+pub fn part_path(url: &str) -> Option<PathBuf> {
+    let key = &verify::sha256_hex(url.as_bytes())[..16];
+    loader::plugins_dir().map(|d| d.join(".downloads").join(format!("{}.part", key)))
+}
+
+/// Forget the partial download for `url`.
+pub fn discard(url: &str) {
+    if let Some(path) = part_path(url) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Download `url`, resuming a partial file if one exists. Returns the
+/// whole bundle; at most `max_bytes`. `progress` hears the bytes so far
+/// and the total, when the server says.
+pub async fn fetch(url: &str, max_bytes: usize, mut progress: impl FnMut(u64, Option<u64>)) -> Result<Vec<u8>, String> {
+    if !url.starts_with("https://") {
+        return Err("Plugin downloads must use https".to_string());
+    }
+    let path = part_path(url).ok_or("Could not determine plugins directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create download dir: {}", e))?;
+    }
+    let have = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = crate::net::client().get(url);
+    if have > 0 {
+        log::info!("[INSTALL] Resuming download at {} bytes: {}", have, url);
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", have));
+    }
+    let response = request.send().await.map_err(|e| format!("Download failed: {}", e))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && have > 0 {
+        // Already complete before the restart
+        return std::fs::read(&path).map_err(|e| format!("Cannot read download: {}", e));
+    }
+    let mut response = response.error_for_status().map_err(|e| format!("Download failed: {}", e))?;
+    let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&path)
+        .map_err(|e| format!("Cannot write download: {}", e))?;
+    let mut written = if resumed { have as usize } else { 0 };
+    let total = response.content_length().map(|len| len + written as u64);
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Download failed: {}", e))? {
+        if written + chunk.len() > max_bytes {
+            drop(file);
+            discard(url);
+            return Err("Plugin bundle is too large".to_string());
+        }
+        file.write_all(&chunk).map_err(|e| format!("Cannot write download: {}", e))?;
+        written += chunk.len();
+        progress(written as u64, total);
+    }
+    drop(file);
+    std::fs::read(&path).map_err(|e| format!("Cannot read download: {}", e))
+}
@@ -0,0 +1,197 @@
+//! Plugin installation and removal.
+//!
+//! `install_plugin` takes a local `.zip` path or an `https://` URL:
+//! 1. Read / download the bundle (size-capped; downloads resume after a
+//!    restart — `download.rs`, tracked by `session`)
+//! 2. Verify its SHA-256 against the index digest (required for URLs)
+//! 3. Unpack into a staging directory and validate the manifest
+//! 4. Move it into the plugins directory under its plugin ID
+//! 5. Load it if already approved with these permissions, otherwise queue
+//!    it for the permission prompt
+//!
+//! `uninstall_plugin` stops the plugin and removes its files, approval
+//! record, stored configuration, keychain secrets, enablement switches,
+//! clipboard grants, scheduled-task approvals and history, and plugin
+//! storage data. `catalog.rs` lists community
+//! plugins that can be installed this way.
+
+pub mod archive;
+pub mod catalog;
+pub mod download;
+pub mod verify;
+
+use crate::error::OmniError;
+use crate::mcp::approval::{self, ApprovalStatus};
+use crate::mcp::approval_commands::{self, PendingPlugin};
+use crate::mcp::loader::{self, PendingApprovals};
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::storage::PluginStorage;
+use crate::mcp::{clipboard, config_store, enablement, health, manifest, permission_audit, schedule, secrets};
+use crate::policy;
+use std::path::{Path, PathBuf};
+
+/// Largest bundle we will download or read.
+const MAX_BUNDLE_BYTES: usize = 50 * 1024 * 1024;
+
+/// Tauri command: install a plugin bundle from a path or URL.
+///
+/// Returns the plugin summary and whether it is waiting for approval.
+#[tauri::command]
+pub async fn install_plugin(
+    app: tauri::AppHandle,
+    source: String,
+    sha256: Option<String>,
+    registry: tauri::State<'_, ToolRegistry>,
+    pending: tauri::State<'_, PendingApprovals>,
+) -> Result<serde_json::Value, OmniError> {
+    install(&app, &source, sha256.as_deref(), &registry, &pending).await.map_err(OmniError::Plugin)
+}
+
+/// Install from a path or URL (also used to resume downloads at startup).
+pub async fn install(
+    app: &tauri::AppHandle,
+    source: &str,
+    sha256: Option<&str>,
+    registry: &ToolRegistry,
+    pending: &PendingApprovals,
+) -> Result<serde_json::Value, String> {
+    let is_remote = source.starts_with("https://") || source.starts_with("http://");
+    let bytes = match (is_remote, sha256) {
+        (true, None) => return Err("Downloaded plugins need a SHA-256 digest from the plugin index".to_string()),
+        (true, Some(expected)) => {
+            crate::session::download_started(source, expected);
+            let fetched = download::fetch(source, MAX_BUNDLE_BYTES, |done, total| crate::tray::status::downloading(app, done, total)).await;
+            crate::tray::status::downloaded(app);
+            let bytes = fetched?;
+            let verified = verify::verify_sha256(&bytes, expected);
+            // A finished download either installs or is corrupt; neither resumes.
+            download::discard(source);
+            crate::session::download_finished(source);
+            verified?;
+            bytes
+        }
+        (false, expected) => {
+            let bytes = read_bundle(Path::new(source))?;
+            match expected {
+                Some(expected) => verify::verify_sha256(&bytes, expected)?,
+                None => log::warn!("[INSTALL] Installing local bundle without a digest: {}", source),
+            }
+            bytes
+        }
+    };
+
+    let plugins_dir = loader::plugins_dir().ok_or("Could not determine plugins directory")?;
+    let staging = plugins_dir.join(format!(".staging-{}", verify::sha256_hex(&bytes)));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging).map_err(|e| format!("Failed to create staging dir: {}", e))?;
+
+    let result = stage_and_move(&bytes, &staging, &plugins_dir, registry).await;
+    let _ = std::fs::remove_dir_all(&staging);
+    let (manifest, dest, was_installed) = result?;
+
+    let store = approval::load_approvals();
+    let needs_approval = approval::check_approval(&store, &manifest) != ApprovalStatus::Approved;
+    if needs_approval {
+        let mut queue = pending.queue.lock().await;
+        queue.retain(|(m, _, _)| m.id != manifest.id);
+        queue.push((manifest.clone(), dest, was_installed));
+        drop(queue);
+        approval_commands::open_permission_prompt(app);
+    } else {
+        loader::load_approved_plugin(&manifest, &dest, registry).await?;
+    }
+
+    log::info!(
+        "[INSTALL] Installed '{}' v{} ({})",
+        manifest.id,
+        manifest.version,
+        if needs_approval { "awaiting approval" } else { "loaded" }
+    );
+    Ok(serde_json::json!({
+        "plugin": PendingPlugin::from(&manifest),
+        "needsApproval": needs_approval,
+    }))
+}
+
+/// Unpack, validate, and move the bundle into `plugins/<id>/`.
+/// Returns the manifest, its final directory, and whether it replaced an install.
+async fn stage_and_move(
+    bytes: &[u8],
+    staging: &Path,
+    plugins_dir: &Path,
+    registry: &ToolRegistry,
+) -> Result<(manifest::PluginManifest, PathBuf, bool), String> {
+    archive::extract_zip(bytes, staging)?;
+    let root = archive::find_plugin_root(staging)?;
+    let manifest = manifest::load_manifest(&root)?;
+
+    if !verify::is_safe_plugin_id(&manifest.id) {
+        return Err(format!("Plugin id '{}' contains unsupported characters", manifest.id));
+    }
+    if !policy::precedence::is_plugin_allowed(policy::current(), &manifest.id) {
+        return Err(format!("Plugin '{}' is blocked by your organization's policy", manifest.id));
+    }
+
+    let dest = plugins_dir.join(&manifest.id);
+    let was_installed = dest.exists();
+    if was_installed {
+        registry.remove_plugin(&manifest.id).await;
+        std::fs::remove_dir_all(&dest).map_err(|e| format!("Failed to remove old version: {}", e))?;
+    }
+    std::fs::rename(&root, &dest).map_err(|e| format!("Failed to install plugin: {}", e))?;
+    Ok((manifest, dest, was_installed))
+}
+
+fn read_bundle(path: &Path) -> Result<Vec<u8>, String> {
+    let meta = std::fs::metadata(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    if meta.len() as usize > MAX_BUNDLE_BYTES {
+        return Err("Plugin bundle is too large".to_string());
+    }
+    std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+/// Tauri command: stop a plugin and delete its files, approval, and config.
+#[tauri::command]
+pub async fn uninstall_plugin(
+    plugin_id: String,
+    registry: tauri::State<'_, ToolRegistry>,
+    pending: tauri::State<'_, PendingApprovals>,
+) -> Result<(), OmniError> {
+    uninstall(&plugin_id, &registry, &pending).await.map_err(OmniError::Plugin)
+}
+
+/// Stop a plugin and remove everything stored for it.
+async fn uninstall(plugin_id: &str, registry: &ToolRegistry, pending: &PendingApprovals) -> Result<(), String> {
+    if !verify::is_safe_plugin_id(plugin_id) {
+        return Err(format!("Invalid plugin id '{}'", plugin_id));
+    }
+    let plugins_dir = loader::plugins_dir().ok_or("Could not determine plugins directory")?;
+    let dir = plugins_dir.join(plugin_id);
+    if !dir.is_dir() {
+        return Err(format!("Plugin '{}' is not installed", plugin_id));
+    }
+
+    if let Ok(installed) = manifest::load_manifest(&dir) {
+        secrets::forget_plugin(&installed);
+    }
+    health::forget(plugin_id);
+    registry.remove_plugin(plugin_id).await;
+    pending.queue.lock().await.retain(|(m, _, _)| m.id != plugin_id);
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove plugin files: {}", e))?;
+
+    let mut store = approval::load_approvals();
+    approval::forget_plugin(&mut store, plugin_id);
+    approval::save_approvals(&store)?;
+    config_store::delete_config(plugin_id);
+    if let Ok(storage) = PluginStorage::open(plugin_id) {
+        storage.clear();
+    }
+    enablement::update(|s| s.forget_plugin(plugin_id))?;
+    clipboard::update_grants(|g| g.revoke(plugin_id))?;
+    schedule::update_store(|s| s.forget_plugin(plugin_id))?;
+    schedule::history::forget_plugin(plugin_id);
+    permission_audit::update(|s| s.forget_plugin(plugin_id))?;
+
+    log::info!("[INSTALL] Uninstalled '{}'", plugin_id);
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Bundle integrity checks — SHA-256 digests from the plugin index.
+
+use sha2::{Digest, Sha256};
+
+/// Lowercase hex SHA-256 of `bytes`.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Check `bytes` against an expected digest. Accepts bare hex or the
+/// `sha256:<hex>` form used elsewhere in the app; case-insensitive.
+pub fn verify_sha256(bytes: &[u8], expected: &str) -> Result<(), String> {
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not a SHA-256 digest", expected));
+    }
+    let actual = sha256_hex(bytes);
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(format!(
+            "Bundle hash mismatch: expected {}, got {}",
+            expected.to_ascii_lowercase(),
+            actual
+        ));
+    }
+    Ok(())
+}
+
+/// Plugin IDs become directory names, so only allow a safe character set.
+pub fn is_safe_plugin_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && !id.contains("..")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_SHA: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn accepts_matching_digest_in_both_forms() {
+        assert!(verify_sha256(b"hello", HELLO_SHA).is_ok());
+        assert!(verify_sha256(b"hello", &format!("sha256:{}", HELLO_SHA.to_uppercase())).is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatch_and_malformed_digest() {
+        assert!(verify_sha256(b"hello!", HELLO_SHA).is_err());
+        assert!(verify_sha256(b"hello", "abc123").is_err());
+    }
+
+    #[test]
+    fn plugin_id_must_be_a_safe_directory_name() {
+        assert!(is_safe_plugin_id("com.omni-glass.github-issues"));
+        assert!(!is_safe_plugin_id("../evil.plugin"));
+        assert!(!is_safe_plugin_id("com.example/evil"));
+        assert!(!is_safe_plugin_id(".hidden.plugin"));
+    }
+}
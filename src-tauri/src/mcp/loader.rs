@@ -9,6 +9,8 @@ use crate::mcp::client::McpServer;
 use crate::mcp::manifest::{self, PluginManifest, Runtime};
 use crate::mcp::registry::ToolRegistry;
 use crate::mcp::sandbox::env_filter;
+use crate::mcp::sandbox::net_proxy::NetProxy;
+use crate::policy;
 use std::path::{Path, PathBuf};
 use tokio::sync::Mutex;
 
@@ -26,7 +28,7 @@ impl PendingApprovals {
 }
 
 /// Default plugin directory: ~/.config/omni-glass/plugins/
-fn plugins_dir() -> Option<PathBuf> {
+pub fn plugins_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|c| c.join("omni-glass").join("plugins"))
 }
 
@@ -34,10 +36,11 @@ fn plugins_dir() -> Option<PathBuf> {
 ///
 /// For each valid plugin subdirectory:
 /// 1. Parse manifest
-/// 2. Check approval status
-/// 3. Approved → spawn sandboxed, initialize, discover, register
-/// 4. Denied → skip silently
-/// 5. NeedsApproval / PermissionsChanged → queue for user prompt
+/// 2. Skip plugins blocked by enterprise policy
+/// 3. Check approval status
+/// 4. Approved → spawn sandboxed, initialize, discover, register
+/// 5. Denied → skip silently
+/// 6. NeedsApproval / PermissionsChanged → queue for user prompt
 ///
 /// Failures are logged and skipped — never fatal to the app.
 pub async fn load_plugins(registry: &ToolRegistry, pending: &PendingApprovals) {
@@ -69,7 +72,9 @@ pub async fn load_plugins(registry: &ToolRegistry, pending: &PendingApprovals) {
 
     for entry in entries.flatten() {
         let path = entry.path();
-        if !path.is_dir() {
+        // Dot-dirs are install staging areas, not plugins
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !path.is_dir() || hidden {
             continue;
         }
 
@@ -86,6 +91,12 @@ pub async fn load_plugins(registry: &ToolRegistry, pending: &PendingApprovals) {
             }
         };
 
+        // Enterprise policy overrides any user approval
+        if !policy::precedence::is_plugin_allowed(policy::current(), &manifest.id) {
+            log::info!("[MCP] Plugin '{}' blocked by policy, skipping", manifest.id);
+            continue;
+        }
+
         // Check approval status
         match approval::check_approval(&store, &manifest) {
             ApprovalStatus::Approved => {
@@ -131,10 +142,13 @@ pub async fn load_plugins(registry: &ToolRegistry, pending: &PendingApprovals) {
 /// Public because it's called from both the startup loader and the
 /// `approve_plugin` command after the user grants permission.
 pub async fn load_approved_plugin(
-    manifest: &PluginManifest,
+    declared: &PluginManifest,
     plugin_dir: &Path,
     registry: &ToolRegistry,
 ) -> Result<u32, String> {
+    // Spawn with what the user still grants; track the declared manifest
+    // so restarts re-apply the current revocations.
+    let manifest = &crate::mcp::permission_audit::effective_manifest(declared);
     log::info!(
         "[MCP] Loading plugin '{}' v{} ({})",
         manifest.name,
@@ -142,14 +156,48 @@ pub async fn load_approved_plugin(
         manifest.id
     );
 
-    // 1. Filter environment variables (all platforms)
-    let env = env_filter::filter_environment(&manifest.permissions, &manifest.id);
+    crate::mcp::triggers::register(&manifest.id, &manifest.extensions.triggers);
+    crate::mcp::slash_commands::register(&manifest.id, &manifest.extensions.commands);
+    crate::mcp::schedule::register(&manifest.id, &manifest.extensions.tasks);
+
+    // WASM plugins run in-process — no child process, env, or proxy
+    if manifest.runtime == Runtime::Wasm {
+        let count = crate::mcp::wasm::load_wasm_plugin(manifest, plugin_dir, registry).await?;
+        crate::mcp::health::track(declared, plugin_dir);
+        return Ok(count);
+    }
+    // Shell adapters spawn their CLI per call — no long-running server
+    if manifest.runtime == Runtime::Shell {
+        let count = crate::mcp::shell_adapter::load_shell_plugin(manifest, plugin_dir, registry).await?;
+        crate::mcp::health::track(declared, plugin_dir);
+        return Ok(count);
+    }
+
+    // Declared hosts and paths need an OS sandbox; refuse before spawning
+    crate::mcp::permissions::check_enforceable(&manifest.permissions, crate::mcp::sandbox::OS_SANDBOX)?;
+
+    // 1. Filter environment variables (all platforms), add keychain secrets
+    let mut env = env_filter::filter_environment(&manifest.permissions, &manifest.id);
+    env.extend(crate::mcp::secrets::secrets_for(manifest));
+    crate::mcp::permission_audit::record_env_use(manifest, &env);
+
+    // 1b. Route declared network access through the host allowlist proxy,
+    //     trusting the user's custom CA bundle if one is set
+    let proxy = match manifest.permissions.network {
+        Some(ref hosts) if !hosts.is_empty() => {
+            let proxy = NetProxy::start(&manifest.id, hosts.clone()).await?;
+            env.extend(proxy.env_vars());
+            env.extend(crate::net::upstream::plugin_ca_env());
+            Some(proxy)
+        }
+        _ => None,
+    };
 
     // 2. Determine spawn command
     let (command, args) = resolve_command(manifest, plugin_dir)?;
     let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
-    // 3. Spawn — sandboxed on macOS, filtered-env-only on other platforms
+    // 3. Spawn — sandboxed on macOS, filtered-env-only elsewhere
     let mut server = spawn_plugin(
         &manifest.id,
         manifest,
@@ -157,18 +205,26 @@ pub async fn load_approved_plugin(
         &command,
         &args_refs,
         env,
+        proxy.as_ref().map(|p| p.port),
     )?;
+    server.clipboard_declared = manifest.permissions.clipboard;
 
     // 4. Initialize handshake
     server.initialize().await?;
 
-    // 5. Discover tools
+    // 5. Discover tools, plus optional resources and prompts
     let tools = server.list_tools().await?;
     let tool_count = tools.len() as u32;
+    crate::mcp::resources::discover(&manifest.id, &mut server).await;
+    crate::mcp::prompts::discover(&manifest.id, &mut server).await;
 
     // 6. Register tools and store server
     registry.register_plugin_tools(&manifest.id, tools).await;
     registry.add_server(manifest.id.clone(), server).await;
+    if let Some(proxy) = proxy {
+        registry.add_proxy(manifest.id.clone(), proxy).await;
+    }
+    crate::mcp::health::track(declared, plugin_dir);
 
     Ok(tool_count)
 }
@@ -181,13 +237,14 @@ fn spawn_plugin(
     command: &str,
     args: &[&str],
     env: std::collections::HashMap<String, String>,
+    proxy_port: Option<u16>,
 ) -> Result<McpServer, String> {
     // Suppress unused warning on non-macOS platforms (manifest used in sandbox profile gen)
-    let _ = &manifest;
+    let _ = (&manifest, proxy_port);
     #[cfg(target_os = "macos")]
     {
         use crate::mcp::sandbox::macos;
-        match macos::generate_profile(manifest, plugin_dir) {
+        match macos::generate_profile(manifest, plugin_dir, proxy_port) {
             Ok(profile) => {
                 let profile_path = macos::write_profile(plugin_id, &profile)?;
                 log::info!("[SANDBOX] Profile written for '{}': {}", plugin_id, profile_path.display());
@@ -197,7 +254,7 @@ fn spawn_plugin(
             }
             Err(e) => {
                 log::warn!(
-                    "[SANDBOX] Failed to generate profile for '{}': {} — loading without sandbox",
+                    "[SANDBOX] Failed to generate profile for '{}': {} — trying without sandbox",
                     plugin_id,
                     e
                 );
@@ -205,7 +262,8 @@ fn spawn_plugin(
         }
     }
 
-    // Fallback: spawn with filtered environment only (all platforms)
+    // Fallback: spawn with filtered environment only, if nothing declared needs the sandbox
+    crate::mcp::permissions::check_enforceable(&manifest.permissions, false)?;
     McpServer::spawn(plugin_id, command, args, env, Some(plugin_dir))
 }
 
@@ -236,5 +294,6 @@ fn resolve_command(
             }
             Ok((entry_str, vec![]))
         }
+        Runtime::Wasm | Runtime::Shell => Err("WASM and shell adapter plugins have no server process".to_string()),
     }
 }
@@ -0,0 +1,33 @@
+//! Manifest sections owned by other modules: snip triggers
+//! (`triggers.rs`), text-launcher slash commands (`slash_commands.rs`),
+//! and scheduled tasks (`schedule/`). They sit at the top level of the
+//! manifest JSON and are checked here, together with the runtime's and
+//! the permissions' own rules.
+
+use super::PluginManifest;
+use crate::mcp::schedule::ScheduledTask;
+use crate::mcp::slash_commands::SlashCommand;
+use crate::mcp::triggers::Triggers;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Extensions {
+    /// When to offer the plugin's tools on a snip.
+    #[serde(default)]
+    pub triggers: Option<Triggers>,
+    /// Text-launcher slash commands.
+    #[serde(default)]
+    pub commands: Vec<SlashCommand>,
+    /// Periodic tool calls.
+    #[serde(default)]
+    pub tasks: Vec<ScheduledTask>,
+}
+
+/// Checks beyond identity and entry point (called from `load.rs`).
+pub(super) fn validate(m: &PluginManifest) -> Result<(), String> {
+    crate::mcp::wasm::abi::validate_manifest(m)?;
+    crate::mcp::triggers::validate(&m.extensions.triggers)?;
+    crate::mcp::slash_commands::validate(&m.extensions.commands)?;
+    crate::mcp::schedule::validate(&m.extensions.tasks)?;
+    crate::mcp::permissions::validate_permissions(&m.permissions)
+}
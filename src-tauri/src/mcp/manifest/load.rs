@@ -1,93 +1,9 @@
-//! Plugin manifest parser and validator.
-//!
-//! Each plugin directory must contain an `omni-glass.plugin.json` file
-//! describing the plugin's identity, runtime, entry point, and permissions.
+//! Reading `omni-glass.plugin.json` from a plugin directory and checking
+//! its fields.
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use super::{PluginManifest, MANIFEST_FILENAME};
 use std::path::Path;
 
-/// The filename expected in every plugin directory.
-pub const MANIFEST_FILENAME: &str = "omni-glass.plugin.json";
-
-/// A user-configurable field declared in a plugin's manifest.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-pub struct ConfigField {
-    /// Field type: "string", "number", or "boolean".
-    #[serde(rename = "type")]
-    pub field_type: String,
-    /// Human-readable label for the settings UI.
-    pub label: String,
-    /// Placeholder text for text inputs.
-    #[serde(default)]
-    pub placeholder: Option<String>,
-    /// Help text shown below the input.
-    #[serde(default)]
-    pub description: Option<String>,
-}
-
-/// Parsed and validated plugin manifest.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct PluginManifest {
-    pub id: String,
-    pub name: String,
-    pub version: String,
-    #[serde(default)]
-    pub description: String,
-    pub runtime: Runtime,
-    pub entry: String,
-    #[serde(default)]
-    pub permissions: Permissions,
-    /// Optional user-configurable fields (e.g., default_repo, target_language).
-    #[serde(default)]
-    pub configuration: Option<HashMap<String, ConfigField>>,
-}
-
-/// Plugin runtime environment.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
-pub enum Runtime {
-    Node,
-    Python,
-    Binary,
-}
-
-/// Filesystem access declaration: a path and its access level.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-pub struct FsPerm {
-    pub path: String,
-    pub access: String, // "read" | "write" | "read-write"
-}
-
-/// Shell access declaration: list of allowed commands.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-pub struct ShellPerm {
-    pub commands: Vec<String>,
-}
-
-/// Plugin permission declarations.
-///
-/// Each field is optional (except `clipboard`). Omitting a field means the
-/// plugin does NOT request that capability. The sandbox denies everything
-/// not explicitly declared here.
-#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
-pub struct Permissions {
-    #[serde(default)]
-    pub clipboard: bool,
-    /// Network domains the plugin may contact. `None` = no network access.
-    #[serde(default)]
-    pub network: Option<Vec<String>>,
-    /// Filesystem paths + access levels.
-    #[serde(default)]
-    pub filesystem: Option<Vec<FsPerm>>,
-    /// Environment variables the plugin may read (by name).
-    #[serde(default)]
-    pub environment: Option<Vec<String>>,
-    /// Shell commands the plugin may spawn.
-    #[serde(default)]
-    pub shell: Option<ShellPerm>,
-}
-
 /// Load and validate a plugin manifest from a directory.
 pub fn load_manifest(plugin_dir: &Path) -> Result<PluginManifest, String> {
     let manifest_path = plugin_dir.join(MANIFEST_FILENAME);
@@ -147,12 +63,13 @@ fn validate(m: &PluginManifest, plugin_dir: &Path) -> Result<(), String> {
         ));
     }
 
-    Ok(())
+    super::extensions::validate(m)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mcp::manifest::Runtime;
     use std::fs;
 
     fn setup_test_plugin(dir: &Path, manifest_json: &str, entry: &str) {
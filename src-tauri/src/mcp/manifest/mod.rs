@@ -0,0 +1,103 @@
+//! Plugin manifest parser and validator.
+//!
+//! Each plugin directory must contain an `omni-glass.plugin.json` file
+//! describing the plugin's identity, runtime, entry point, and permissions.
+//! Loading and validation are in `load.rs`; triggers, slash commands, and
+//! scheduled tasks in `extensions.rs`.
+
+mod extensions;
+mod load;
+
+pub use extensions::Extensions;
+pub use load::load_manifest;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The filename expected in every plugin directory.
+pub const MANIFEST_FILENAME: &str = "omni-glass.plugin.json";
+
+/// A user-configurable field declared in a plugin's manifest.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ConfigField {
+    /// Field type: "string", "number", or "boolean".
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Human-readable label for the settings UI.
+    pub label: String,
+    /// Placeholder text for text inputs.
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    /// Help text shown below the input.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Parsed and validated plugin manifest.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub runtime: Runtime,
+    pub entry: String,
+    #[serde(default)]
+    pub permissions: Permissions,
+    /// Optional user-configurable fields (e.g., default_repo, target_language).
+    #[serde(default)]
+    pub configuration: Option<HashMap<String, ConfigField>>,
+    /// Triggers, slash commands, and scheduled tasks — see `extensions.rs`.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+/// Plugin runtime environment.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    Node,
+    Python,
+    Binary,
+    /// In-process wasmtime module — see `mcp/wasm/`.
+    Wasm,
+    /// Existing CLI wrapped by a TOML adapter — see `mcp/shell_adapter/`.
+    Shell,
+}
+
+/// Filesystem access declaration: a path and its access level.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FsPerm {
+    pub path: String,
+    pub access: String, // "read" | "write" | "read-write"
+}
+
+/// Shell access declaration: list of allowed commands.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ShellPerm {
+    pub commands: Vec<String>,
+}
+
+/// Plugin permission declarations.
+///
+/// Each field is optional (except `clipboard`). Omitting a field means the
+/// plugin does NOT request that capability. The sandbox denies everything
+/// not explicitly declared here.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Permissions {
+    #[serde(default)]
+    pub clipboard: bool,
+    /// Network domains the plugin may contact. `None` = no network access.
+    #[serde(default)]
+    pub network: Option<Vec<String>>,
+    /// Filesystem paths + access levels.
+    #[serde(default)]
+    pub filesystem: Option<Vec<FsPerm>>,
+    /// Environment variables the plugin may read (by name).
+    #[serde(default)]
+    pub environment: Option<Vec<String>>,
+    /// Shell commands the plugin may spawn.
+    #[serde(default)]
+    pub shell: Option<ShellPerm>,
+}
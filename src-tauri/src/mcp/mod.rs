@@ -5,27 +5,69 @@
 //!
 //! - **types**: MCP protocol types (JSON-RPC framing, tool definitions)
 //! - **client**: McpServer — spawn child process, handshake, call tools
+//! - **host_rpc**: Requests plugins send back to the host (storage, clipboard)
 //! - **manifest**: Parse and validate `omni-glass.plugin.json` files
 //! - **registry**: ToolRegistry — central store for built-in + plugin tools
 //! - **loader**: Scan plugins directory, spawn servers, discover tools
+//! - **remote**: HTTP/SSE transport for remote MCP servers + health checks
+//! - **fanout**: One action calling several plugins concurrently, merged result
+//! - **resources**: Plugin MCP resources the LLM can read during execute
+//! - **prompts**: Plugin MCP prompt templates offered as snip actions
+//! - **schedule**: User-approved periodic plugin tasks, notifications, run history
+//! - **slash_commands**: Plugin-declared text-launcher commands routed without the LLM
+//! - **render**: Typed plugin results (table, link, file, ...) → ActionResult
 //! - **builtins**: Register the 6 built-in actions as internal tools
 //! - **sandbox**: OS-level process sandboxing (env filtering, macOS sandbox-exec)
 //! - **approval**: Plugin approval state management (user consent)
+//! - **wasm**: In-process wasmtime runtime for capability-sandboxed `.wasm` plugins
+//! - **shell_adapter**: TOML-declared tools that run an existing CLI (`runtime: "shell"`)
+//! - **permissions**: Manifest permission validation + runtime allow checks
+//! - **permission_audit**: Per-permission last use + user revocation after install
+//! - **violations**: Log + event stream of denied plugin access attempts
+//! - **health**: Call timeouts, crash restarts with backoff, circuit breaker
+//! - **enablement**: Persisted per-plugin / per-tool on-off switches
+//! - **secrets**: Keychain-stored values for declared plugin env vars
+//! - **storage**: Per-plugin key-value/file data area with a size quota
+//! - **clipboard**: Host-brokered clipboard access with prompts + grants
+//! - **triggers**: Manifest conditions for offering a plugin's tools on a snip
+//! - **install**: Install/uninstall plugin bundles with digest verification
+//! - **devmode**: Hot reload from a source directory + plugin log console
 
 pub mod approval;
 pub mod approval_commands;
 pub mod builtins;
 pub mod client;
+pub mod clipboard;
 pub mod config_store;
+pub mod devmode;
+pub mod enablement;
+pub mod fanout;
+pub mod health;
+pub mod host_rpc;
+pub mod install;
 pub mod loader;
 pub mod manifest;
+pub mod permission_audit;
+pub mod permissions;
+pub mod prompts;
 pub mod registry;
+pub mod remote;
+pub mod render;
+pub mod resources;
 pub mod sandbox;
+pub mod schedule;
+pub mod secrets;
+pub mod shell_adapter;
+pub mod slash_commands;
+pub mod storage;
+pub mod triggers;
 pub mod types;
+pub mod violations;
+pub mod wasm;
 
 pub use registry::ToolRegistry;
 
-use crate::llm::execute::{ActionResult, ActionResultBody};
+use crate::llm::execute::ActionResult;
 use crate::safety::{command_check, redact};
 
 /// Execute a plugin tool call, converting the MCP result to our ActionResult type.
@@ -40,7 +82,20 @@ pub async fn execute_plugin_tool(
     tool_description: Option<&str>,
     input_schema: Option<&serde_json::Value>,
 ) -> ActionResult {
-    // Generate structured args for non-trivial schemas, fallback to {text} otherwise
+    // Disabled tools are hidden from prompts; refuse them if named anyway
+    let resolved = match registry.resolve_action(action_id).await {
+        Some(qname) => registry.get_tool(&qname).await,
+        None => None,
+    };
+    if let Some(tool) = resolved {
+        if !enablement::is_enabled(&tool.plugin_id, &tool.name) {
+            log::warn!("[MCP] Refusing disabled tool '{}'", action_id);
+            return ActionResult::error(action_id, "This tool is disabled in Settings");
+        }
+    }
+
+    // Generate structured args for non-trivial schemas, {text} otherwise.
+    // Arguments that don't match the schema never reach the tool.
     let arguments = match input_schema {
         Some(schema) if !crate::llm::plugin_args::is_trivial_schema(schema) => {
             match crate::llm::plugin_args::generate_plugin_args(
@@ -53,21 +108,44 @@ pub async fn execute_plugin_tool(
             {
                 Ok(args) => args,
                 Err(e) => {
-                    log::warn!("[MCP] Args bridge failed, falling back to {{text}}: {}", e);
-                    serde_json::json!({ "text": input_text })
+                    log::warn!("[MCP] Args bridge failed for '{}': {}", action_id, e);
+                    return ActionResult::error(action_id, &format!("Could not build arguments for this tool: {}", e));
                 }
             }
         }
-        _ => serde_json::json!({ "text": input_text }),
+        _ => match crate::llm::plugin_args::text_args(input_text, input_schema) {
+            Ok(args) => args,
+            Err(errors) => {
+                let errors = errors.join("; ");
+                log::warn!("[MCP] Text arguments rejected by '{}' schema: {}", action_id, errors);
+                return ActionResult::error(action_id, &format!("This tool's input schema rejects the text: {}", errors));
+            }
+        },
     };
 
+    run_plugin_tool(registry, action_id, arguments).await
+}
+
+/// Call a plugin tool with ready-made arguments and apply the output
+/// safety gates. Used directly by launcher slash commands, which build
+/// their own arguments (`slash_commands.rs`).
+pub async fn run_plugin_tool(registry: &ToolRegistry, action_id: &str, arguments: serde_json::Value) -> ActionResult {
     match registry.call_plugin_tool(action_id, arguments).await {
         Ok(result) => {
-            let raw_text = result.text();
             if result.is_error {
-                return ActionResult::error(action_id, &format!("Plugin error: {}", raw_text));
+                return ActionResult::error(action_id, &format!("Plugin error: {}", result.text()));
             }
 
+            // Validate typed output and convert it for the result window
+            let mut body = match render::from_tool_result(&result) {
+                Ok(body) => body,
+                Err(e) => {
+                    log::warn!("[MCP] Plugin '{}' returned an invalid result: {}", action_id, e);
+                    return ActionResult::error(action_id, &format!("Plugin returned an invalid result: {}", e));
+                }
+            };
+            let raw_text = body.text.take().unwrap_or_default();
+
             // Safety gate 1: block dangerous commands in plugin output
             let cmd_check = command_check::is_command_safe(&raw_text);
             if !cmd_check.safe {
@@ -96,18 +174,12 @@ pub async fn execute_plugin_tool(
                     labels
                 );
             }
+            body.text = Some(redaction.cleaned_text);
 
             ActionResult {
                 status: "success".to_string(),
                 action_id: action_id.to_string(),
-                result: ActionResultBody {
-                    result_type: "text".to_string(),
-                    text: Some(redaction.cleaned_text),
-                    file_path: None,
-                    command: None,
-                    clipboard_content: None,
-                    mime_type: None,
-                },
+                result: body,
                 metadata: None,
             }
         }
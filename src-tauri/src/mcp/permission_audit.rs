@@ -0,0 +1,281 @@
+//! Plugin permission audit — see what a plugin uses, take back what it
+//! doesn't need.
+//!
+//! Each declared permission is addressed as a string:
+//! `clipboard`, `network:<host>`, `filesystem:<path>`,
+//! `environment:<VAR>`, `shell:<command>`.
+//!
+//! A revoked permission is removed from the manifest before the plugin is
+//! spawned (`effective_manifest`), and the live checks (network proxy,
+//! clipboard broker) consult `is_revoked` on every access, so revocation
+//! applies immediately; spawn-time grants (environment, filesystem, shell)
+//! take effect through a restart done by `revoke_plugin_permission`.
+//!
+//! Last use is recorded where the host can observe it: network requests
+//! through the proxy, brokered clipboard access, and environment variables
+//! handed over at spawn, and commands run by shell adapters. Filesystem
+//! use, and shell use by other plugins, happen inside the OS sandbox and
+//! are not observable. State lives in
+//! `~/.config/omni-glass/plugin-permissions.json`.
+
+use crate::error::OmniError;
+use crate::mcp::manifest::{self, Permissions, PluginManifest, Runtime};
+use crate::mcp::registry::ToolRegistry;
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// Usage is written to disk at most this often (new permissions at once).
+const USAGE_SAVE_INTERVAL_SECS: u64 = 60;
+
+/// Revocations and last-use times, per plugin.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditStore {
+    #[serde(default)]
+    pub revoked: BTreeMap<String, BTreeSet<String>>,
+    /// plugin_id → permission → unix epoch seconds.
+    #[serde(default)]
+    pub last_used: BTreeMap<String, BTreeMap<String, u64>>,
+    /// When usage was last worth saving; in memory only.
+    #[serde(skip)]
+    usage_saved_at: u64,
+}
+
+impl AuditStore {
+    pub fn is_revoked(&self, plugin_id: &str, permission: &str) -> bool {
+        self.revoked.get(plugin_id).is_some_and(|r| r.contains(permission))
+    }
+
+    pub fn revoke(&mut self, plugin_id: &str, permission: &str) {
+        self.revoked.entry(plugin_id.to_string()).or_default().insert(permission.to_string());
+    }
+
+    /// Record a use. Returns whether the change is worth saving: a first
+    /// use, or the last save is `USAGE_SAVE_INTERVAL_SECS` old. Measured
+    /// from the save, not the previous use, so steady use is still saved.
+    pub fn record_use(&mut self, plugin_id: &str, permission: &str, now: u64) -> bool {
+        let uses = self.last_used.entry(plugin_id.to_string()).or_default();
+        let first = uses.insert(permission.to_string(), now).is_none();
+        let save = first || now >= self.usage_saved_at + USAGE_SAVE_INTERVAL_SECS;
+        if save {
+            self.usage_saved_at = now;
+        }
+        save
+    }
+
+    /// Drop everything for a plugin (on uninstall).
+    pub fn forget_plugin(&mut self, plugin_id: &str) {
+        self.revoked.remove(plugin_id);
+        self.last_used.remove(plugin_id);
+    }
+}
+
+/// Every permission a manifest declares, as audit strings.
+pub fn permission_ids(perms: &Permissions) -> Vec<String> {
+    let mut ids = Vec::new();
+    if perms.clipboard {
+        ids.push("clipboard".to_string());
+    }
+    ids.extend(perms.network.iter().flatten().map(|h| format!("network:{}", h)));
+    ids.extend(perms.filesystem.iter().flatten().map(|f| format!("filesystem:{}", f.path)));
+    ids.extend(perms.environment.iter().flatten().map(|v| format!("environment:{}", v)));
+    ids.extend(perms.shell.iter().flat_map(|s| &s.commands).map(|c| format!("shell:{}", c)));
+    ids
+}
+
+/// Declared permissions minus the revoked ones.
+pub fn without_revoked(perms: &Permissions, revoked: &BTreeSet<String>) -> Permissions {
+    let keep = |id: String| !revoked.contains(&id);
+    let mut out = perms.clone();
+    out.clipboard = perms.clipboard && keep("clipboard".to_string());
+    if let Some(hosts) = out.network.as_mut() {
+        hosts.retain(|h| keep(format!("network:{}", h)));
+    }
+    if let Some(paths) = out.filesystem.as_mut() {
+        paths.retain(|f| keep(format!("filesystem:{}", f.path)));
+    }
+    if let Some(vars) = out.environment.as_mut() {
+        vars.retain(|v| keep(format!("environment:{}", v)));
+    }
+    if let Some(shell) = out.shell.as_mut() {
+        shell.commands.retain(|c| keep(format!("shell:{}", c)));
+    }
+    out
+}
+
+static STORE: JsonStore<AuditStore> = JsonStore::new("plugin-permissions.json");
+
+/// Apply `change` to the audit state and persist it.
+pub fn update(change: impl FnOnce(&mut AuditStore)) -> Result<(), String> {
+    STORE.update(change)
+}
+
+fn current() -> AuditStore {
+    STORE.get()
+}
+
+pub fn is_revoked(plugin_id: &str, permission: &str) -> bool {
+    STORE.read(|s| s.is_revoked(plugin_id, permission))
+}
+
+/// Note that a plugin just exercised a permission.
+pub fn record_use(plugin_id: &str, permission: &str) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    if let Err(e) = STORE.update_if(|s| s.record_use(plugin_id, permission, now)) {
+        log::warn!("[SANDBOX] Failed to save permission usage: {}", e);
+    }
+}
+
+/// Record the declared environment variables a plugin was spawned with.
+pub fn record_env_use(manifest: &PluginManifest, env: &HashMap<String, String>) {
+    for var in manifest.permissions.environment.iter().flatten().filter(|v| env.contains_key(*v)) {
+        record_use(&manifest.id, &format!("environment:{}", var));
+    }
+}
+
+/// The manifest a plugin is actually granted: revoked permissions removed.
+pub fn effective_manifest(manifest: &PluginManifest) -> PluginManifest {
+    let store = current();
+    let mut granted = manifest.clone();
+    if let Some(revoked) = store.revoked.get(&manifest.id) {
+        granted.permissions = without_revoked(&manifest.permissions, revoked);
+    }
+    granted
+}
+
+/// One declared permission in the audit view.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionAudit {
+    pub permission: String,
+    pub revoked: bool,
+    /// Unix epoch seconds; `None` if never seen.
+    pub last_used: Option<u64>,
+    /// Whether the host can observe this permission being used.
+    pub usage_tracked: bool,
+}
+
+/// Declared manifest and directory: the running copy, else the installed one.
+fn declared_source(plugin_id: &str) -> Result<(PluginManifest, PathBuf), String> {
+    if let Some(source) = crate::mcp::health::source(plugin_id) {
+        return Ok(source);
+    }
+    let dir = crate::mcp::loader::plugins_dir()
+        .ok_or("Could not determine plugins directory")?
+        .join(plugin_id);
+    Ok((manifest::load_manifest(&dir)?, dir))
+}
+
+/// Tauri command: a plugin's declared permissions, revocations, last use.
+#[tauri::command]
+pub fn get_plugin_permissions(plugin_id: String) -> Result<Vec<PermissionAudit>, String> {
+    let (manifest, _) = declared_source(&plugin_id)?;
+    let store = current();
+    let used = store.last_used.get(&plugin_id);
+    Ok(permission_ids(&manifest.permissions)
+        .into_iter()
+        .map(|permission| PermissionAudit {
+            revoked: store.is_revoked(&plugin_id, &permission),
+            last_used: used.and_then(|u| u.get(&permission).copied()),
+            usage_tracked: !permission.starts_with("filesystem:")
+                && (!permission.starts_with("shell:") || manifest.runtime == Runtime::Shell),
+            permission,
+        })
+        .collect())
+}
+
+/// Tauri command: revoke one permission. Live checks see it at once;
+/// a running plugin is restarted so spawn-time grants are dropped too.
+#[tauri::command]
+pub async fn revoke_plugin_permission(
+    plugin_id: String,
+    permission: String,
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<(), OmniError> {
+    let (manifest, dir) = declared_source(&plugin_id).map_err(OmniError::Plugin)?;
+    if !permission_ids(&manifest.permissions).contains(&permission) {
+        return Err(OmniError::Plugin(format!("Plugin '{}' does not declare '{}'", plugin_id, permission)));
+    }
+    update(|s| s.revoke(&plugin_id, &permission)).map_err(OmniError::Plugin)?;
+    log::info!("[SANDBOX] Revoked '{}' from plugin '{}'", permission, plugin_id);
+
+    let live = permission == "clipboard" || permission.starts_with("network:");
+    let running = crate::mcp::health::source(&plugin_id).is_some();
+    if running && !live {
+        registry.remove_plugin(&plugin_id).await;
+        crate::mcp::loader::load_approved_plugin(&manifest, &dir, &registry).await.map_err(OmniError::Plugin)?;
+        log::info!("[SANDBOX] Restarted '{}' without '{}'", plugin_id, permission);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn perms() -> Permissions {
+        serde_json::from_value(serde_json::json!({
+            "clipboard": true,
+            "network": ["api.example.com", "cdn.example.com"],
+            "filesystem": [{"path": "~/Documents", "access": "read"}],
+            "environment": ["API_KEY"],
+            "shell": {"commands": ["git"]}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_every_declared_permission() {
+        assert_eq!(
+            permission_ids(&perms()),
+            vec![
+                "clipboard",
+                "network:api.example.com",
+                "network:cdn.example.com",
+                "filesystem:~/Documents",
+                "environment:API_KEY",
+                "shell:git"
+            ]
+        );
+        assert!(permission_ids(&Permissions::default()).is_empty());
+    }
+
+    #[test]
+    fn revoked_permissions_are_removed() {
+        let revoked: BTreeSet<String> =
+            ["clipboard", "network:cdn.example.com", "shell:git"].iter().map(|s| s.to_string()).collect();
+        let granted = without_revoked(&perms(), &revoked);
+        assert!(!granted.clipboard);
+        assert_eq!(granted.network.unwrap(), vec!["api.example.com"]);
+        assert_eq!(granted.environment.unwrap(), vec!["API_KEY"]);
+        assert!(granted.shell.unwrap().commands.is_empty());
+    }
+
+    #[test]
+    fn usage_saves_are_throttled() {
+        let mut store = AuditStore::default();
+        assert!(store.record_use("com.example.a", "clipboard", 1000));
+        assert!(!store.record_use("com.example.a", "clipboard", 1030));
+        assert_eq!(store.last_used["com.example.a"]["clipboard"], 1030);
+        assert!(store.record_use("com.example.a", "network:api.example.com", 1031));
+        store.revoke("com.example.a", "clipboard");
+        assert!(store.is_revoked("com.example.a", "clipboard"));
+        store.forget_plugin("com.example.a");
+        assert!(store.revoked.is_empty() && store.last_used.is_empty());
+    }
+
+    #[test]
+    fn steady_use_below_the_interval_is_still_saved() {
+        let mut store = AuditStore::default();
+        let saved: Vec<u64> = (0..10)
+            .map(|i| 1000 + i * 30)
+            .filter(|&now| store.record_use("com.example.a", "clipboard", now))
+            .collect();
+        assert_eq!(saved, vec![1000, 1060, 1120, 1180, 1240]);
+    }
+}
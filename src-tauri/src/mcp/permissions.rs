@@ -0,0 +1,203 @@
+//! Plugin permission model — schema validation and runtime checks.
+//!
+//! `manifest.rs` parses the `permissions` block; this module decides
+//! whether the declarations are well-formed and answers the runtime
+//! questions the host asks while a plugin runs:
+//! - does host X match a declared pattern? (network proxy)
+//! - can this OS enforce what the plugin declares? (loader)
+//!
+//! Pure functions only — enforcement lives in `sandbox/`.
+
+use crate::mcp::manifest::Permissions;
+use std::path::{Component, Path};
+
+/// Valid values for `filesystem[].access`.
+const ACCESS_LEVELS: [&str; 3] = ["read", "write", "read-write"];
+
+/// Validate the permission declarations in a manifest.
+///
+/// Rejects declarations that are malformed or so broad they defeat the
+/// sandbox (e.g. the whole home directory, or a bare `*` host).
+pub fn validate_permissions(perms: &Permissions) -> Result<(), String> {
+    for host in perms.network.iter().flatten() {
+        if !is_valid_host_pattern(host) {
+            return Err(format!(
+                "Invalid network host '{}' — use a hostname like api.example.com or *.example.com",
+                host
+            ));
+        }
+    }
+
+    for fs in perms.filesystem.iter().flatten() {
+        if !ACCESS_LEVELS.contains(&fs.access.as_str()) {
+            return Err(format!(
+                "Invalid filesystem access '{}' for '{}' — use read, write, or read-write",
+                fs.access, fs.path
+            ));
+        }
+        validate_fs_path(&fs.path)?;
+    }
+
+    for var in perms.environment.iter().flatten() {
+        if !is_valid_env_name(var) {
+            return Err(format!("Invalid environment variable name '{}'", var));
+        }
+    }
+
+    if let Some(ref shell) = perms.shell {
+        for cmd in &shell.commands {
+            if cmd.is_empty() || cmd.contains(['/', '\\', ' ']) {
+                return Err(format!("Shell command '{}' must be a bare program name", cmd));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Hostname, optionally prefixed with `*.` for subdomains. No scheme, port, or path.
+fn is_valid_host_pattern(pattern: &str) -> bool {
+    let host = pattern.strip_prefix("*.").unwrap_or(pattern);
+    !host.is_empty()
+        && host.contains('.')
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+/// Declared paths must be absolute (or `~`-relative), free of `..`, and
+/// narrower than the filesystem root or the whole home directory.
+fn validate_fs_path(path: &str) -> Result<(), String> {
+    if !(path.starts_with('/') || path.starts_with("~/")) {
+        return Err(format!("Filesystem path '{}' must be absolute or start with ~/", path));
+    }
+    if Path::new(path).components().any(|c| c == Component::ParentDir) {
+        return Err(format!("Filesystem path '{}' must not contain ..", path));
+    }
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() || trimmed == "~" {
+        return Err(format!("Filesystem path '{}' is too broad", path));
+    }
+    Ok(())
+}
+
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Does `host` match a declared pattern? `*.example.com` matches
+/// `api.example.com` but not `example.com` itself.
+pub fn host_matches(pattern: &str, host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    let pattern = pattern.to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host.ends_with(&format!(".{}", suffix)),
+        None => host == pattern,
+    }
+}
+
+/// `Err` when a plugin declares network hosts or filesystem paths but
+/// will run without an OS sandbox (`sandboxed` false). Only the sandbox
+/// holds a native process to those lists — a plugin can ignore its proxy
+/// and open any file — so running it would grant more than was approved.
+pub fn check_enforceable(perms: &Permissions, sandboxed: bool) -> Result<(), String> {
+    if sandboxed {
+        return Ok(());
+    }
+    let mut declared = Vec::new();
+    if perms.network.as_ref().is_some_and(|hosts| !hosts.is_empty()) {
+        declared.push("network hosts");
+    }
+    if perms.filesystem.as_ref().is_some_and(|paths| !paths.is_empty()) {
+        declared.push("filesystem paths");
+    }
+    if declared.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Plugins can't be sandboxed on this system, so the declared {} can't be enforced; the plugin won't start",
+        declared.join(" and ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::manifest::{FsPerm, ShellPerm};
+
+    fn fs(path: &str, access: &str) -> FsPerm {
+        FsPerm { path: path.into(), access: access.into() }
+    }
+
+    #[test]
+    fn accepts_well_formed_permissions() {
+        let perms = Permissions {
+            clipboard: true,
+            network: Some(vec!["api.github.com".into(), "*.example.com".into()]),
+            filesystem: Some(vec![fs("~/Documents/notes", "read")]),
+            environment: Some(vec!["GITHUB_TOKEN".into()]),
+            shell: Some(ShellPerm { commands: vec!["git".into()] }),
+        };
+        assert!(validate_permissions(&perms).is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_hosts() {
+        for host in ["*", "https://api.example.com", "localhost", "api.example.com/path"] {
+            let perms = Permissions { network: Some(vec![host.into()]), ..Default::default() };
+            assert!(validate_permissions(&perms).is_err(), "{} should be rejected", host);
+        }
+    }
+
+    #[test]
+    fn rejects_broad_or_relative_paths() {
+        for path in ["~", "~/", "/", "relative/dir", "~/Documents/../.ssh"] {
+            let perms = Permissions { filesystem: Some(vec![fs(path, "read")]), ..Default::default() };
+            assert!(validate_permissions(&perms).is_err(), "{} should be rejected", path);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_access_level() {
+        let perms = Permissions { filesystem: Some(vec![fs("/tmp/x", "all")]), ..Default::default() };
+        assert!(validate_permissions(&perms).is_err());
+    }
+
+    #[test]
+    fn rejects_shell_paths_and_bad_env_names() {
+        let shell = Permissions {
+            shell: Some(ShellPerm { commands: vec!["/bin/rm".into()] }),
+            ..Default::default()
+        };
+        assert!(validate_permissions(&shell).is_err());
+        let env = Permissions { environment: Some(vec!["1BAD".into()]), ..Default::default() };
+        assert!(validate_permissions(&env).is_err());
+    }
+
+    #[test]
+    fn wildcard_host_matches_subdomains_only() {
+        assert!(host_matches("*.example.com", "api.example.com"));
+        assert!(host_matches("*.example.com", "a.b.EXAMPLE.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+        assert!(!host_matches("*.example.com", "evil-example.com"));
+        assert!(host_matches("api.example.com", "API.example.com"));
+    }
+
+    #[test]
+    fn allowlists_need_a_sandbox() {
+        let perms = Permissions {
+            network: Some(vec!["api.example.com".into()]),
+            filesystem: Some(vec![fs("~/notes", "read")]),
+            ..Default::default()
+        };
+        assert!(check_enforceable(&perms, true).is_ok());
+        let err = check_enforceable(&perms, false).unwrap_err();
+        assert!(err.contains("network hosts and filesystem paths"));
+        let env_only = Permissions { environment: Some(vec!["TOKEN".into()]), ..Default::default() };
+        assert!(check_enforceable(&env_only, false).is_ok());
+    }
+}
@@ -0,0 +1,220 @@
+//! Plugin prompts — MCP prompt templates offered as snip actions.
+//!
+//! A stdio plugin whose server advertises the MCP `prompts` capability has
+//! its `prompts/list` read at load time. Each prompt is offered in the
+//! classify menu as `prompt:<plugin_id>/<name>`. Running it fills the
+//! template with the snipped text (`prompts/get`) and sends the resulting
+//! messages to the LLM (`llm::agent::run_prompt`), which may read plugin
+//! resources along the way.
+//!
+//! The snip goes into the argument named `text`, else the first argument.
+//! Prompts with any other required argument can't be filled from a snip
+//! and are not offered. A prompt without arguments gets the snip as a
+//! trailing user message. Prompts follow the per-tool enablement switch
+//! under their own name.
+
+use crate::llm::{self, ActionResult};
+use crate::mcp::client::McpServer;
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::types::McpPrompt;
+use crate::safety::{injection, redact};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+/// Action id prefix routed here by `pipeline_execute.rs`.
+pub const ACTION_PREFIX: &str = "prompt:";
+
+/// Prompts kept per plugin.
+const MAX_PROMPTS: usize = 20;
+
+/// Offerable prompts of loaded plugins, keyed by plugin_id.
+static PROMPTS: LazyLock<Mutex<BTreeMap<String, Vec<McpPrompt>>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// The argument the snipped text fills, or `None` for a prompt without
+/// arguments. `Err` when another argument is required.
+fn input_argument(prompt: &McpPrompt) -> Result<Option<&str>, String> {
+    let input = prompt
+        .arguments
+        .iter()
+        .find(|a| a.name == "text")
+        .or_else(|| prompt.arguments.first());
+    let other_required = prompt.arguments.iter().filter(|a| a.required).find(|a| Some(*a) != input);
+    match other_required {
+        Some(a) => Err(format!("prompt '{}' requires '{}', which a snip can't fill", prompt.name, a.name)),
+        None => Ok(input.map(|a| a.name.as_str())),
+    }
+}
+
+/// List a freshly initialized server's prompts, if it has any.
+/// Failures are logged — prompts are optional.
+pub async fn discover(plugin_id: &str, server: &mut McpServer) {
+    if server.capabilities.prompts.is_none() {
+        forget(plugin_id);
+        return;
+    }
+    let list = match server.list_prompts().await {
+        Ok(list) => list,
+        Err(e) => {
+            log::warn!("[MCP] '{}' prompts/list failed: {}", plugin_id, e);
+            forget(plugin_id);
+            return;
+        }
+    };
+    let offered: Vec<McpPrompt> = list
+        .into_iter()
+        .filter(|p| match input_argument(p) {
+            Ok(_) => true,
+            Err(e) => {
+                log::warn!("[MCP] '{}' {} — not offered", plugin_id, e);
+                false
+            }
+        })
+        .take(MAX_PROMPTS)
+        .collect();
+    log::info!("[MCP] '{}' offers {} prompts", plugin_id, offered.len());
+    PROMPTS.lock().unwrap_or_else(|e| e.into_inner()).insert(plugin_id.to_string(), offered);
+}
+
+/// Drop a plugin's prompts (unload).
+pub fn forget(plugin_id: &str) {
+    PROMPTS.lock().unwrap_or_else(|e| e.into_inner()).remove(plugin_id);
+}
+
+/// Classify-menu entries for enabled prompts of reachable plugins.
+/// Same line format as `ToolRegistry::tools_for_prompt`.
+pub fn prompt_entries(unavailable: &HashSet<String>) -> String {
+    let enablement = crate::mcp::enablement::current();
+    let map = PROMPTS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = String::new();
+    for (plugin_id, prompts) in map.iter().filter(|(id, _)| !unavailable.contains(*id)) {
+        for p in prompts.iter().filter(|p| enablement.is_enabled(plugin_id, &p.name)) {
+            out.push_str(&format!(
+                "- id: \"{}{}/{}\", label: \"{}\", description: \"{}\", icon: \"sparkles\", requiresExecution: true\n",
+                ACTION_PREFIX,
+                plugin_id,
+                p.name,
+                p.name.replace(['_', '-'], " "),
+                p.description.as_deref().unwrap_or("Plugin prompt")
+            ));
+        }
+    }
+    out
+}
+
+/// Split `prompt:<plugin_id>/<name>` into its parts.
+pub fn parse_action_id(action_id: &str) -> Option<(String, String)> {
+    let (plugin_id, name) = action_id.strip_prefix(ACTION_PREFIX)?.split_once('/')?;
+    (!plugin_id.is_empty() && !name.is_empty()).then(|| (plugin_id.to_string(), name.to_string()))
+}
+
+/// Convert MCP prompt messages to Anthropic messages. Text and embedded
+/// text resources are kept; images and blobs are dropped.
+fn to_llm_messages(messages: &[Value]) -> Vec<Value> {
+    messages
+        .iter()
+        .filter_map(|m| {
+            let role = match m.get("role").and_then(Value::as_str) {
+                Some("assistant") => "assistant",
+                _ => "user",
+            };
+            let content = m.get("content")?;
+            let text = match content.get("type").and_then(Value::as_str) {
+                Some("text") => content.get("text")?.as_str()?,
+                Some("resource") => content.get("resource")?.get("text")?.as_str()?,
+                _ => return None,
+            };
+            Some(json!({ "role": role, "content": text }))
+        })
+        .collect()
+}
+
+/// Run a prompt action on the snipped text.
+pub async fn execute(registry: &ToolRegistry, action_id: &str, input_text: &str) -> ActionResult {
+    let Some((plugin_id, name)) = parse_action_id(action_id) else {
+        return ActionResult::error(action_id, "Malformed prompt action");
+    };
+    let prompt = PROMPTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&plugin_id)
+        .and_then(|list| list.iter().find(|p| p.name == name).cloned());
+    let Some(prompt) = prompt else {
+        return ActionResult::error(action_id, &format!("Plugin '{}' has no prompt '{}'", plugin_id, name));
+    };
+    if !crate::mcp::enablement::is_enabled(&plugin_id, &name) {
+        return ActionResult::error(action_id, "This prompt is disabled in Settings");
+    }
+
+    let clean = redact::redact_sensitive_data(input_text).cleaned_text;
+    let snip = injection::wrap_untrusted("snipped_text", &clean);
+    let mut arguments = Map::new();
+    let input = input_argument(&prompt).ok().flatten();
+    if let Some(arg) = input {
+        arguments.insert(arg.to_string(), Value::String(snip.clone()));
+    }
+
+    let fetched = match registry.stdio_server(&plugin_id).await {
+        Ok(server) => server.lock().await.get_prompt(&name, Value::Object(arguments)).await,
+        Err(e) => Err(e),
+    };
+    let mut messages = match fetched {
+        Ok(messages) => to_llm_messages(&messages),
+        Err(e) => return ActionResult::error(action_id, &format!("Prompt failed: {}", e)),
+    };
+    if input.is_none() {
+        messages.push(json!({ "role": "user", "content": snip }));
+    }
+    if messages.is_empty() {
+        return ActionResult::error(action_id, "The plugin's prompt was empty");
+    }
+    log::info!("[EXECUTE] Running prompt '{}' from '{}' ({} messages)", name, plugin_id, messages.len());
+    llm::agent::run_prompt(action_id, messages, registry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(args: Value) -> McpPrompt {
+        serde_json::from_value(json!({"name": "review", "arguments": args})).unwrap()
+    }
+
+    #[test]
+    fn snip_fills_text_or_first_argument() {
+        assert_eq!(input_argument(&prompt(json!([]))).unwrap(), None);
+        let text_arg = prompt(json!([{"name": "lang"}, {"name": "text", "required": true}]));
+        assert_eq!(input_argument(&text_arg).unwrap(), Some("text"));
+        let first = prompt(json!([{"name": "code", "required": true}, {"name": "style"}]));
+        assert_eq!(input_argument(&first).unwrap(), Some("code"));
+        let unfillable = prompt(json!([{"name": "code"}, {"name": "repo", "required": true}]));
+        assert!(input_argument(&unfillable).is_err());
+    }
+
+    #[test]
+    fn action_ids_round_trip() {
+        assert_eq!(
+            parse_action_id("prompt:com.example.a/review"),
+            Some(("com.example.a".to_string(), "review".to_string()))
+        );
+        assert_eq!(parse_action_id("prompt:com.example.a"), None);
+        assert_eq!(parse_action_id("com.example.a:review"), None);
+    }
+
+    #[test]
+    fn messages_keep_text_only() {
+        let messages = vec![
+            json!({"role": "user", "content": {"type": "text", "text": "Review this"}}),
+            json!({"role": "user", "content": {"type": "image", "data": "AAAA", "mimeType": "image/png"}}),
+            json!({"role": "assistant", "content": {"type": "resource", "resource": {"uri": "a", "text": "rules"}}}),
+        ];
+        assert_eq!(
+            to_llm_messages(&messages),
+            vec![
+                json!({"role": "user", "content": "Review this"}),
+                json!({"role": "assistant", "content": "rules"}),
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Server availability — which plugins' tools can be offered now.
+//!
+//! Remote servers are pinged by the health loop (`remote/mod.rs`); stdio
+//! servers are checked for an exited process by the health supervisor
+//! (`health/`). A plugin marked unavailable keeps its tools registered
+//! but hidden from menus and prompts until it recovers.
+
+use super::ToolRegistry;
+use futures_util::future::join_all;
+
+impl ToolRegistry {
+    /// Ping every remote server at once and record which ones are reachable.
+    /// Returns `(plugin_id, reachable)` for servers whose state flipped.
+    pub async fn check_remote_health(&self) -> Vec<(String, bool)> {
+        // Snapshot the list: a ping may take its whole timeout, and calls
+        // and installs need the map meanwhile
+        let remotes: Vec<_> = {
+            let map = self.remotes.lock().await;
+            map.iter().map(|(id, s)| (id.clone(), s.clone())).collect()
+        };
+        let pings = remotes.into_iter().map(|(id, remote)| async move {
+            // A server busy with a call is answering; skip it this round
+            let mut server = remote.try_lock().ok()?;
+            Some((id, server.ping().await.is_ok()))
+        });
+        let results: Vec<_> = join_all(pings).await.into_iter().flatten().collect();
+
+        let mut unavailable = self.unavailable.lock().await;
+        results
+            .into_iter()
+            .filter(|(id, reachable)| {
+                // remove/insert return true only when the state actually flips
+                if *reachable {
+                    unavailable.remove(id)
+                } else {
+                    unavailable.insert(id.clone())
+                }
+            })
+            .collect()
+    }
+
+    /// Hide or restore a plugin's tools (circuit breaker).
+    pub async fn set_available(&self, plugin_id: &str, available: bool) {
+        let mut unavailable = self.unavailable.lock().await;
+        if available {
+            unavailable.remove(plugin_id);
+        } else {
+            unavailable.insert(plugin_id.to_string());
+        }
+    }
+
+    /// Plugin IDs whose stdio server process has exited, each with whether
+    /// it was killed after a call timed out.
+    pub async fn exited_servers(&self) -> Vec<(String, bool)> {
+        let servers: Vec<_> = {
+            let map = self.servers.lock().await;
+            map.iter().map(|(id, s)| (id.clone(), s.clone())).collect()
+        };
+        let mut exited = Vec::new();
+        for (id, server) in servers {
+            // A server busy with a call is alive; skip it this round
+            if let Ok(mut s) = server.try_lock() {
+                if s.has_exited() {
+                    exited.push((id, s.was_killed()));
+                }
+            }
+        }
+        exited
+    }
+
+    /// Is this plugin's server currently reachable? Builtins always are.
+    pub async fn is_available(&self, plugin_id: &str) -> bool {
+        !self.unavailable.lock().await.contains(plugin_id)
+    }
+}
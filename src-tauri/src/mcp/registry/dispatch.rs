@@ -0,0 +1,134 @@
+//! Tool call dispatch — route a call to the owning plugin's transport.
+//!
+//! Every call is bounded by a timeout and its outcome is reported to the
+//! health tracker. Time the host spends answering the plugin's own
+//! requests (a clipboard prompt waiting on the user) doesn't count. When a plugin's circuit trips, its tools are hidden
+//! (marked unavailable) until the health supervisor half-opens it.
+
+use super::{RegisteredTool, ToolRegistry};
+use crate::mcp::client::{McpServer, TOOL_CALL_TIMEOUT_SECS};
+use crate::mcp::health;
+use crate::mcp::host_rpc;
+use crate::mcp::types::ToolResult;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Outer bound for any transport (stdio enforces its own, tighter limit).
+const DISPATCH_TIMEOUT: Duration = Duration::from_secs(TOOL_CALL_TIMEOUT_SECS + 5);
+
+impl ToolRegistry {
+    /// Call a tool on a plugin's MCP server.
+    /// Resolves the tool, finds the server, and dispatches the call.
+    pub async fn call_plugin_tool(
+        &self,
+        action_id: &str,
+        arguments: serde_json::Value,
+    ) -> Result<ToolResult, String> {
+        // Resolve tool info
+        let tool = {
+            let tools = self.tools.lock().await;
+            let found = tools
+                .iter()
+                .find(|(qname, t)| *qname == action_id || t.name == action_id);
+            match found {
+                Some((_, t)) => t.clone(),
+                None => return Err(format!("Tool '{}' not found in registry", action_id)),
+            }
+        };
+
+        if health::is_circuit_open(&tool.plugin_id) {
+            return Err(format!(
+                "Plugin '{}' is failing repeatedly and is paused — try again shortly",
+                tool.plugin_id
+            ));
+        }
+        if !self.is_available(&tool.plugin_id).await {
+            return Err(format!("Plugin server '{}' is unreachable", tool.plugin_id));
+        }
+
+        let plugin_id = tool.plugin_id.clone();
+        let result = bounded(&plugin_id, self.dispatch(tool, arguments)).await;
+
+        // Tool-level errors (isError) are the plugin working as designed;
+        // only transport failures count against its health
+        match &result {
+            Ok(_) => health::record_success(&plugin_id),
+            Err(e) => {
+                if health::record_failure(&plugin_id, e) {
+                    self.set_available(&plugin_id, false).await;
+                }
+            }
+        }
+        result
+    }
+
+    /// A stdio plugin's connection, for resource and prompt requests.
+    /// Same pause / reachability checks as a tool call.
+    pub async fn stdio_server(&self, plugin_id: &str) -> Result<Arc<Mutex<McpServer>>, String> {
+        if health::is_circuit_open(plugin_id) || !self.is_available(plugin_id).await {
+            return Err(format!("Plugin '{}' is paused or unreachable", plugin_id));
+        }
+        self.servers
+            .lock()
+            .await
+            .get(plugin_id)
+            .cloned()
+            .ok_or_else(|| format!("No running server for plugin '{}'", plugin_id))
+    }
+
+    async fn dispatch(&self, tool: RegisteredTool, arguments: serde_json::Value) -> Result<ToolResult, String> {
+        // WASM plugins are CPU-bound — run off the async executor
+        let wasm = self.wasm.lock().await.get(&tool.plugin_id).cloned();
+        if let Some(plugin) = wasm {
+            return tauri::async_runtime::spawn_blocking(move || {
+                // A runaway guest outlives the dispatch timeout; the watchdog reports it
+                let _watch = crate::crash::watch(format!("plugin {}", tool.plugin_id), DISPATCH_TIMEOUT);
+                plugin.call_tool(&tool.name, &arguments)
+            })
+                .await
+                .map_err(|e| format!("WASM task failed: {}", e))?;
+        }
+
+        // Shell adapters spawn one CLI process per call
+        let shell = self.shell.lock().await.get(&tool.plugin_id).cloned();
+        if let Some(adapter) = shell {
+            return adapter.call_tool(&tool.name, &arguments).await;
+        }
+
+        // Stdio server: lock only this plugin's connection
+        let server = self.servers.lock().await.get(&tool.plugin_id).cloned();
+        if let Some(server) = server {
+            return server.lock().await.call_tool(&tool.name, arguments).await;
+        }
+
+        let remote = self.remotes.lock().await.get(&tool.plugin_id).cloned();
+        let remote = remote.ok_or_else(|| {
+            format!("No running server for plugin '{}'", tool.plugin_id)
+        })?;
+        remote.lock().await.call_tool(&tool.name, arguments).await
+    }
+}
+
+/// Run a call under `DISPATCH_TIMEOUT`. When it runs out, the deadline
+/// moves by the time the host spent answering the plugin's requests
+/// since the last look, and again while a prompt is still open.
+async fn bounded(plugin_id: &str, call: impl Future<Output = Result<ToolResult, String>>) -> Result<ToolResult, String> {
+    let mut call = std::pin::pin!(call);
+    let mut host_seen = host_rpc::host_time(plugin_id);
+    let mut deadline = Instant::now() + DISPATCH_TIMEOUT;
+    loop {
+        if let Ok(result) = tokio::time::timeout_at(deadline, &mut call).await {
+            return result;
+        }
+        let host_now = host_rpc::host_time(plugin_id);
+        let paused = host_now.saturating_sub(host_seen);
+        if paused.is_zero() {
+            return Err(format!("Plugin '{}' timed out after {}s", plugin_id, DISPATCH_TIMEOUT.as_secs()));
+        }
+        host_seen = host_now;
+        deadline += paused;
+    }
+}
@@ -0,0 +1,266 @@
+//! Tool registry — central store for all discovered tools.
+//!
+//! Holds both built-in tools (dispatched to internal Rust functions)
+//! and plugin tools (dispatched via MCP stdio to child processes, or via
+//! HTTP to remote MCP servers).
+//! Registered as Tauri managed state so all commands can query it.
+//! Tool call dispatch lives in `dispatch.rs`, server availability in
+//! `availability.rs`.
+
+mod availability;
+mod dispatch;
+
+use crate::mcp::client::McpServer;
+use crate::mcp::remote::client::RemoteMcpServer;
+use crate::mcp::sandbox::net_proxy::NetProxy;
+use crate::mcp::shell_adapter::ShellAdapter;
+use crate::mcp::triggers::{self, SnipContext};
+use crate::mcp::types::McpTool;
+use crate::mcp::wasm::WasmPlugin;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// A tool registered in the system, whether built-in or from a plugin.
+#[derive(Debug, Clone)]
+pub struct RegisteredTool {
+    /// Plugin that owns this tool ("builtin" for internal tools).
+    pub plugin_id: String,
+    /// Tool name as exposed by the MCP server.
+    pub name: String,
+    /// Human-readable label for the action menu.
+    pub display_name: String,
+    /// What this tool does (shown in menu or injected into LLM prompt).
+    pub description: String,
+    /// JSON Schema for the tool's input (optional).
+    pub input_schema: Option<serde_json::Value>,
+}
+
+/// Qualified name format: "plugin_id:tool_name".
+pub fn qualified_name(plugin_id: &str, tool_name: &str) -> String {
+    format!("{}:{}", plugin_id, tool_name)
+}
+
+/// "create_issue" → "Create Issue".
+pub(crate) fn display_name(tool_name: &str) -> String {
+    tool_name
+        .replace('_', " ")
+        .split_whitespace()
+        .map(|w| {
+            let mut c = w.chars();
+            c.next().map(|f| f.to_uppercase().collect::<String>() + c.as_str()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Central registry for all tools and their MCP server handles.
+pub struct ToolRegistry {
+    /// Running MCP server processes, keyed by plugin_id. Each has its own
+    /// lock so a slow plugin never blocks calls to the others.
+    servers: Mutex<HashMap<String, Arc<Mutex<McpServer>>>>,
+    /// Network allowlist proxies, keyed by plugin_id. Dropping one stops it.
+    proxies: Mutex<HashMap<String, NetProxy>>,
+    /// Remote (HTTP) MCP servers, keyed by plugin_id. Locked one by one
+    /// like `servers`, so a slow ping or call holds up only its own plugin.
+    remotes: Mutex<HashMap<String, Arc<Mutex<RemoteMcpServer>>>>,
+    /// In-process WASM plugins, keyed by plugin_id.
+    wasm: Mutex<HashMap<String, WasmPlugin>>,
+    /// CLI-wrapping shell adapters, keyed by plugin_id.
+    shell: Mutex<HashMap<String, Arc<ShellAdapter>>>,
+    /// Plugin IDs whose server failed its last health check or whose
+    /// circuit breaker is open.
+    unavailable: Mutex<HashSet<String>>,
+    /// All registered tools, keyed by qualified name ("plugin_id:tool_name").
+    tools: Mutex<HashMap<String, RegisteredTool>>,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self {
+            servers: Mutex::new(HashMap::new()),
+            proxies: Mutex::new(HashMap::new()),
+            remotes: Mutex::new(HashMap::new()),
+            wasm: Mutex::new(HashMap::new()),
+            shell: Mutex::new(HashMap::new()),
+            unavailable: Mutex::new(HashSet::new()),
+            tools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register tools discovered from an MCP server.
+    pub async fn register_plugin_tools(&self, plugin_id: &str, tools: Vec<McpTool>) {
+        let mut map = self.tools.lock().await;
+        for tool in tools {
+            let qname = qualified_name(plugin_id, &tool.name);
+            let display = display_name(&tool.name);
+
+            map.insert(
+                qname,
+                RegisteredTool {
+                    plugin_id: plugin_id.to_string(),
+                    name: tool.name.clone(),
+                    display_name: display,
+                    description: tool.description.unwrap_or_default(),
+                    input_schema: tool.input_schema,
+                },
+            );
+        }
+    }
+
+    /// Register a single built-in tool (no MCP server needed).
+    pub async fn register_builtin(&self, tool: RegisteredTool) {
+        let qname = qualified_name(&tool.plugin_id, &tool.name);
+        self.tools.lock().await.insert(qname, tool);
+    }
+
+    /// Store a running MCP server handle.
+    pub async fn add_server(&self, plugin_id: String, server: McpServer) {
+        self.servers.lock().await.insert(plugin_id, Arc::new(Mutex::new(server)));
+    }
+
+    /// Keep a plugin's network proxy alive for as long as the plugin runs.
+    pub async fn add_proxy(&self, plugin_id: String, proxy: NetProxy) {
+        self.proxies.lock().await.insert(plugin_id, proxy);
+    }
+
+    /// Store a connected remote MCP server.
+    pub async fn add_remote(&self, plugin_id: String, server: RemoteMcpServer) {
+        self.remotes.lock().await.insert(plugin_id, Arc::new(Mutex::new(server)));
+    }
+
+    /// Store a compiled WASM plugin.
+    pub async fn add_wasm(&self, plugin_id: String, plugin: WasmPlugin) {
+        self.wasm.lock().await.insert(plugin_id, plugin);
+    }
+
+    /// Store a loaded shell adapter.
+    pub async fn add_shell(&self, plugin_id: String, adapter: ShellAdapter) {
+        self.shell.lock().await.insert(plugin_id, Arc::new(adapter));
+    }
+
+    /// Look up a tool by its qualified name.
+    pub async fn get_tool(&self, qualified: &str) -> Option<RegisteredTool> {
+        self.tools.lock().await.get(qualified).cloned()
+    }
+
+    /// Check if an action ID belongs to a plugin (non-builtin) tool.
+    pub async fn is_plugin_action(&self, action_id: &str) -> bool {
+        let tools = self.tools.lock().await;
+        // Action ID from LLM may be just the tool name — search all entries
+        for (qname, tool) in tools.iter() {
+            if (qname == action_id || tool.name == action_id) && tool.plugin_id != "builtin" {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Find the qualified name for an action ID (handles both qualified and bare names).
+    pub async fn resolve_action(&self, action_id: &str) -> Option<String> {
+        let tools = self.tools.lock().await;
+        // Direct match on qualified name
+        if tools.contains_key(action_id) {
+            return Some(action_id.to_string());
+        }
+        // Search by bare tool name
+        for (qname, tool) in tools.iter() {
+            if tool.name == action_id {
+                return Some(qname.clone());
+            }
+        }
+        None
+    }
+
+    /// Format plugin tools as ActionMenu-compatible entries for LLM prompt injection.
+    ///
+    /// Uses the same field names (id, label, description, icon, requiresExecution)
+    /// that the CLASSIFY prompt expects, so the LLM can include them directly
+    /// in its actions array response. Tools whose manifest triggers don't
+    /// match the snip are left out. Tool names shared by several plugins
+    /// also get a fan-out "Compare" entry (`fanout.rs`), and plugin prompt
+    /// templates are offered as `prompt:` actions (`prompts.rs`).
+    pub async fn tools_for_prompt(&self, snip: &SnipContext) -> String {
+        let enablement = crate::mcp::enablement::current();
+        let tools = self.tools.lock().await;
+        let unavailable = self.unavailable.lock().await;
+        let plugin_tools: Vec<_> = tools
+            .values()
+            .filter(|t| t.plugin_id != "builtin" && !unavailable.contains(&t.plugin_id))
+            .filter(|t| enablement.is_enabled(&t.plugin_id, &t.name))
+            .filter(|t| triggers::is_offered(&t.plugin_id, &t.name, snip))
+            .collect();
+
+        let mut out = String::new();
+        for tool in &plugin_tools {
+            let qname = qualified_name(&tool.plugin_id, &tool.name);
+            out.push_str(&format!(
+                "- id: \"{}\", label: \"{}\", description: \"{}\", icon: \"sparkles\", requiresExecution: true\n",
+                qname, tool.display_name, tool.description
+            ));
+        }
+        out.push_str(&crate::mcp::fanout::prompt_entries(&plugin_tools));
+        out.push_str(&crate::mcp::prompts::prompt_entries(&unavailable));
+        out
+    }
+
+    /// Get all registered tools (for debugging / settings UI).
+    pub async fn all_tools(&self) -> Vec<RegisteredTool> {
+        self.tools.lock().await.values().cloned().collect()
+    }
+
+    /// Enabled tools whose server is currently reachable (for LLM prompts).
+    pub async fn available_tools(&self) -> Vec<RegisteredTool> {
+        let enablement = crate::mcp::enablement::current();
+        let tools = self.tools.lock().await;
+        let unavailable = self.unavailable.lock().await;
+        tools
+            .values()
+            .filter(|t| !unavailable.contains(&t.plugin_id))
+            .filter(|t| enablement.is_enabled(&t.plugin_id, &t.name))
+            .cloned()
+            .collect()
+    }
+
+    /// Shutdown all running MCP servers.
+    pub async fn shutdown_all(&self) {
+        let mut servers = self.servers.lock().await;
+        for (id, server) in servers.drain() {
+            log::info!("[MCP] Shutting down plugin '{}'", id);
+            server.lock().await.shutdown().await;
+        }
+        self.proxies.lock().await.clear();
+        for (_, remote) in self.remotes.lock().await.drain() {
+            remote.lock().await.shutdown().await;
+        }
+    }
+
+    /// Stop a single plugin and forget its tools (uninstall / reinstall).
+    pub async fn remove_plugin(&self, plugin_id: &str) {
+        let server = self.servers.lock().await.remove(plugin_id);
+        if let Some(server) = server {
+            log::info!("[MCP] Shutting down plugin '{}'", plugin_id);
+            server.lock().await.shutdown().await;
+        }
+        let remote = self.remotes.lock().await.remove(plugin_id);
+        if let Some(remote) = remote {
+            remote.lock().await.shutdown().await;
+        }
+        self.proxies.lock().await.remove(plugin_id);
+        self.wasm.lock().await.remove(plugin_id);
+        self.shell.lock().await.remove(plugin_id);
+        self.unavailable.lock().await.remove(plugin_id);
+        self.tools.lock().await.retain(|_, t| t.plugin_id != plugin_id);
+        triggers::forget(plugin_id);
+        crate::mcp::slash_commands::forget(plugin_id);
+        crate::mcp::schedule::forget(plugin_id);
+        crate::mcp::resources::forget(plugin_id);
+        crate::mcp::prompts::forget(plugin_id);
+    }
+}
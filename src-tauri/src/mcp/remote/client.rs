@@ -0,0 +1,222 @@
+//! Remote MCP client — JSON-RPC 2.0 over HTTP (Streamable HTTP transport).
+//!
+//! Every request is a POST to the server URL. The server answers either
+//! with a JSON body or with a `text/event-stream` body whose `message`
+//! events carry the response. The `Mcp-Session-Id` header returned by
+//! `initialize` is echoed on every later request.
+
+use super::sse;
+use crate::mcp::types::*;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default timeout for any single JSON-RPC request (seconds).
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Session header defined by the MCP Streamable HTTP transport.
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// An active connection to a remote MCP server.
+pub struct RemoteMcpServer {
+    pub plugin_id: String,
+    url: String,
+    headers: HeaderMap,
+    session_id: Option<String>,
+    http: reqwest::Client,
+    next_id: AtomicU64,
+}
+
+impl RemoteMcpServer {
+    /// Prepare a connection. `auth_headers` are sent on every request.
+    ///
+    /// Does NOT contact the server — call `initialize()` after.
+    pub fn connect(
+        plugin_id: &str,
+        url: &str,
+        auth_headers: Vec<(String, String)>,
+    ) -> Result<Self, String> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in auth_headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid header name '{}': {}", name, e))?;
+            let mut value = HeaderValue::from_str(&value)
+                .map_err(|e| format!("Invalid value for header '{}': {}", name, e))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let http = crate::net::client_builder()?
+            .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| format!("HTTP client error: {}", e))?;
+
+        Ok(Self {
+            plugin_id: plugin_id.to_string(),
+            url: url.to_string(),
+            headers,
+            session_id: None,
+            http,
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// Send the initialize handshake and notifications/initialized notification.
+    pub async fn initialize(&mut self) -> Result<ServerInfo, String> {
+        let params = InitializeParams {
+            protocol_version: "2025-03-26".to_string(),
+            capabilities: ClientCapabilities {},
+            client_info: ClientInfo {
+                name: "omni-glass".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+        };
+        let resp = self
+            .request("initialize", Some(serde_json::to_value(&params).unwrap()))
+            .await?;
+
+        let server_info: ServerInfo = resp
+            .get("serverInfo")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(ServerInfo { name: None, version: None });
+
+        self.notify("notifications/initialized").await?;
+
+        log::info!(
+            "[MCP] Initialized remote '{}' at {} — server: {} v{}",
+            self.plugin_id,
+            self.url,
+            server_info.name.as_deref().unwrap_or("unknown"),
+            server_info.version.as_deref().unwrap_or("?")
+        );
+        Ok(server_info)
+    }
+
+    /// Discover tools via tools/list.
+    pub async fn list_tools(&mut self) -> Result<Vec<McpTool>, String> {
+        let resp = self.request("tools/list", None).await?;
+        let tools_obj = resp
+            .get("tools")
+            .cloned()
+            .unwrap_or(serde_json::Value::Array(vec![]));
+        serde_json::from_value(tools_obj).map_err(|e| format!("Bad tools/list: {}", e))
+    }
+
+    /// Execute a tool by name with the given arguments.
+    pub async fn call_tool(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> Result<ToolResult, String> {
+        let params = serde_json::json!({ "name": name, "arguments": arguments });
+        let resp = self.request("tools/call", Some(params)).await?;
+        serde_json::from_value(resp).map_err(|e| format!("Bad tools/call result: {}", e))
+    }
+
+    /// Health check via the MCP `ping` method.
+    pub async fn ping(&mut self) -> Result<(), String> {
+        self.request("ping", None).await.map(|_| ())
+    }
+
+    /// End the session. Servers that don't support DELETE just ignore it.
+    pub async fn shutdown(&mut self) {
+        if self.session_id.is_some() {
+            let _ = self.http.delete(&self.url).headers(self.request_headers()).send().await;
+        }
+        log::info!("[MCP] Disconnected remote '{}'", self.plugin_id);
+    }
+
+    // ── Internal: HTTP framing ──────────────────────────────────────
+
+    fn request_headers(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json, text/event-stream"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        if let Some(value) = self.session_id.as_deref().and_then(|s| HeaderValue::from_str(s).ok()) {
+            headers.insert(SESSION_HEADER, value);
+        }
+        headers
+    }
+
+    /// POST a JSON-RPC request and wait for the matching response.
+    async fn request(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let req = JsonRpcRequest::new(id, method, params);
+
+        let mut response = self
+            .http
+            .post(&self.url)
+            .headers(self.request_headers())
+            .json(&req)
+            .send()
+            .await
+            .map_err(|e| format!("[MCP] '{}' {} failed: {}", self.plugin_id, method, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "[MCP] '{}' {}: HTTP {}",
+                self.plugin_id,
+                method,
+                response.status()
+            ));
+        }
+        if let Some(session) = response.headers().get(SESSION_HEADER) {
+            self.session_id = session.to_str().ok().map(str::to_string);
+        }
+
+        let is_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+
+        let resp: JsonRpcResponse = if is_stream {
+            read_sse_response(&mut response, id).await
+        } else {
+            response.json().await.map_err(|e| e.to_string())
+        }
+        .map_err(|e| format!("[MCP] '{}' {}: {}", self.plugin_id, method, e))?;
+
+        if let Some(err) = resp.error {
+            return Err(format!("[MCP] '{}' {}: {}", self.plugin_id, method, err));
+        }
+        resp.result.ok_or_else(|| {
+            format!("[MCP] '{}' {}: response had neither result nor error", self.plugin_id, method)
+        })
+    }
+
+    /// POST a JSON-RPC notification (server replies 202 with no body).
+    async fn notify(&mut self, method: &str) -> Result<(), String> {
+        let notif = JsonRpcNotification::new(method, None);
+        self.http
+            .post(&self.url)
+            .headers(self.request_headers())
+            .json(&notif)
+            .send()
+            .await
+            .map_err(|e| format!("[MCP] '{}' {} failed: {}", self.plugin_id, method, e))?;
+        Ok(())
+    }
+}
+
+/// Read SSE chunks until the response with `id` arrives or the stream ends.
+async fn read_sse_response(
+    response: &mut reqwest::Response,
+    id: u64,
+) -> Result<JsonRpcResponse, String> {
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        buf.extend_from_slice(&chunk);
+        // Decode whole events only: a chunk may end inside a UTF-8 sequence
+        let end = sse::complete_len(&buf);
+        let text = String::from_utf8_lossy(&buf[..end]).replace("\r\n", "\n");
+        let (events, _) = sse::parse_events(&text);
+        if let Some(resp) = sse::find_response(&events, id) {
+            return Ok(resp);
+        }
+        buf.drain(..end);
+    }
+    Err("event stream ended without a response".to_string())
+}
@@ -0,0 +1,167 @@
+//! Remote MCP server configuration.
+//!
+//! Servers are listed in `~/.config/omni-glass/remote-servers.json`:
+//!
+//! ```json
+//! [{ "id": "com.team.tools", "name": "Team Tools",
+//!    "url": "https://tools.team.internal/mcp", "authHeaders": ["Authorization"] }]
+//! ```
+//!
+//! Only header NAMES live in the file. Header values (tokens) are stored
+//! in the OS keychain under the `omni-glass-mcp` service.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Keychain service name for remote server auth headers.
+const KEYCHAIN_SERVICE: &str = "omni-glass-mcp";
+
+/// One configured remote MCP server.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteServerConfig {
+    /// Reverse-domain ID, same namespace as local plugins.
+    pub id: String,
+    pub name: String,
+    /// Streamable HTTP endpoint.
+    pub url: String,
+    /// Header names whose values are read from the keychain.
+    #[serde(default)]
+    pub auth_headers: Vec<String>,
+}
+
+fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("remote-servers.json"))
+}
+
+/// Load all valid remote server entries. Invalid entries are logged and skipped.
+pub fn load_configs() -> Vec<RemoteServerConfig> {
+    let Some(path) = config_path() else {
+        return Vec::new();
+    };
+    let Ok(raw) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let configs: Vec<RemoteServerConfig> = match serde_json::from_str(&raw) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("[MCP] Invalid {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+    configs
+        .into_iter()
+        .filter(|c| match validate_config(c) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("[MCP] Skipping remote server '{}': {}", c.id, e);
+                false
+            }
+        })
+        .collect()
+}
+
+/// Validate a remote server entry.
+///
+/// Plain `http://` is only allowed for loopback daemons — auth headers
+/// must never cross the network unencrypted.
+pub fn validate_config(c: &RemoteServerConfig) -> Result<(), String> {
+    if c.id.is_empty() || !c.id.contains('.') {
+        return Err(format!(
+            "id '{}' must be reverse-domain format (e.g. com.example.tools)",
+            c.id
+        ));
+    }
+    let url = reqwest::Url::parse(&c.url).map_err(|e| format!("invalid url: {}", e))?;
+    let loopback = matches!(
+        url.host_str(),
+        Some("localhost") | Some("127.0.0.1") | Some("[::1]")
+    );
+    match url.scheme() {
+        "https" => {}
+        "http" if loopback => {}
+        _ => return Err(format!("url '{}' must use https (http only for localhost)", c.url)),
+    }
+    for header in &c.auth_headers {
+        if reqwest::header::HeaderName::from_bytes(header.as_bytes()).is_err() {
+            return Err(format!("invalid header name '{}'", header));
+        }
+    }
+    Ok(())
+}
+
+fn keychain_account(server_id: &str, header: &str) -> String {
+    format!("{}/{}", server_id, header.to_ascii_lowercase())
+}
+
+/// Read the auth header values for a server from the keychain.
+/// Headers without a stored value are skipped with a warning.
+pub fn auth_header_values(c: &RemoteServerConfig) -> Vec<(String, String)> {
+    c.auth_headers
+        .iter()
+        .filter_map(|header| {
+            let value = keyring::Entry::new(KEYCHAIN_SERVICE, &keychain_account(&c.id, header))
+                .and_then(|entry| entry.get_password());
+            match value {
+                Ok(v) if !v.is_empty() => Some((header.clone(), v)),
+                _ => {
+                    log::warn!("[MCP] No keychain value for '{}' header '{}'", c.id, header);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Store an auth header value for a server in the keychain.
+pub fn save_auth_header(server_id: &str, header: &str, value: &str) -> Result<(), String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &keychain_account(server_id, header))
+        .map_err(|e| format!("Keyring error: {}", e))?
+        .set_password(value)
+        .map_err(|e| format!("Failed to save header: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(url: &str) -> RemoteServerConfig {
+        RemoteServerConfig {
+            id: "com.team.tools".into(),
+            name: "Team Tools".into(),
+            url: url.into(),
+            auth_headers: vec!["Authorization".into()],
+        }
+    }
+
+    #[test]
+    fn accepts_https_and_loopback_http() {
+        assert!(validate_config(&config("https://tools.example.com/mcp")).is_ok());
+        assert!(validate_config(&config("http://localhost:8931/mcp")).is_ok());
+        assert!(validate_config(&config("http://127.0.0.1:8931/mcp")).is_ok());
+    }
+
+    #[test]
+    fn rejects_plain_http_to_remote_hosts() {
+        assert!(validate_config(&config("http://tools.example.com/mcp")).is_err());
+        assert!(validate_config(&config("ftp://tools.example.com")).is_err());
+        assert!(validate_config(&config("not a url")).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_id_and_header_names() {
+        let mut c = config("https://tools.example.com/mcp");
+        c.id = "tools".into();
+        assert!(validate_config(&c).is_err());
+        let mut c = config("https://tools.example.com/mcp");
+        c.auth_headers = vec!["Bad Header".into()];
+        assert!(validate_config(&c).is_err());
+    }
+
+    #[test]
+    fn parses_camel_case_config() {
+        let raw = r#"[{"id":"com.a.b","name":"A","url":"https://a.b/mcp","authHeaders":["X-Api-Key"]}]"#;
+        let parsed: Vec<RemoteServerConfig> = serde_json::from_str(raw).unwrap();
+        assert_eq!(parsed[0].auth_headers, vec!["X-Api-Key".to_string()]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Remote MCP servers — plugins reached over HTTP instead of stdio.
+//!
+//! Local daemons and team-hosted tool servers are listed in
+//! `remote-servers.json` (see `config.rs`). At startup each one is
+//! connected, initialized, and its tools registered alongside stdio
+//! plugins. A background health check pings every server; unreachable
+//! servers' tools are dropped from classify prompts until they recover.
+//! A server that is down at startup is marked unavailable and connected
+//! again on each health check until it answers.
+
+pub mod client;
+pub mod config;
+pub mod sse;
+
+use crate::mcp::registry::ToolRegistry;
+use crate::policy;
+use client::RemoteMcpServer;
+use config::RemoteServerConfig;
+use tauri::{Emitter, Manager};
+
+/// Seconds between health checks.
+const HEALTH_CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Connect to every configured remote server and register its tools.
+///
+/// Failures are logged, never fatal to the app. Servers that failed are
+/// marked unavailable and returned, for `spawn_health_checks` to retry.
+pub async fn load_remote_servers(registry: &ToolRegistry) -> Vec<RemoteServerConfig> {
+    let configs = config::load_configs();
+    if configs.is_empty() {
+        return Vec::new();
+    }
+
+    let mut loaded = 0u32;
+    let mut failed = Vec::new();
+    for c in &configs {
+        if !policy::precedence::is_plugin_allowed(policy::current(), &c.id) {
+            log::info!("[MCP] Remote server '{}' blocked by policy, skipping", c.id);
+            continue;
+        }
+        match connect(c, registry).await {
+            Ok(tool_count) => {
+                loaded += 1;
+                log::info!("[MCP] Remote '{}' registered {} tools", c.id, tool_count);
+            }
+            Err(e) => {
+                log::warn!("[MCP] Failed to connect remote '{}', will retry: {}", c.id, e);
+                registry.set_available(&c.id, false).await;
+                failed.push(c.clone());
+            }
+        }
+    }
+    log::info!("[MCP] {} of {} remote servers connected", loaded, configs.len());
+    failed
+}
+
+async fn connect(c: &RemoteServerConfig, registry: &ToolRegistry) -> Result<u32, String> {
+    let headers = config::auth_header_values(c);
+    let mut server = RemoteMcpServer::connect(&c.id, &c.url, headers)?;
+    server.initialize().await?;
+    let tools = server.list_tools().await?;
+    let tool_count = tools.len() as u32;
+    registry.register_plugin_tools(&c.id, tools).await;
+    registry.add_remote(c.id.clone(), server).await;
+    Ok(tool_count)
+}
+
+/// Ping remote servers forever, emitting `remote-server-health` on changes.
+/// Each round also retries the servers in `unconnected` (see
+/// `load_remote_servers`) until they connect.
+pub fn spawn_health_checks(app: tauri::AppHandle, mut unconnected: Vec<RemoteServerConfig>) {
+    tauri::async_runtime::spawn(async move {
+        let interval = std::time::Duration::from_secs(HEALTH_CHECK_INTERVAL_SECS);
+        loop {
+            tokio::time::sleep(interval).await;
+            let registry = app.state::<ToolRegistry>();
+            let mut changes = registry.check_remote_health().await;
+            changes.extend(retry_unconnected(&registry, &mut unconnected).await);
+            for (id, reachable) in changes {
+                if reachable {
+                    log::info!("[MCP] Remote '{}' is reachable again", id);
+                } else {
+                    log::warn!("[MCP] Remote '{}' is unreachable — tools hidden", id);
+                }
+                let _ = app.emit(
+                    "remote-server-health",
+                    serde_json::json!({ "id": id, "reachable": reachable }),
+                );
+            }
+        }
+    });
+}
+
+/// Try each server that never connected again. Returns `(id, true)` for
+/// the ones that did; they move to the regular pings.
+async fn retry_unconnected(
+    registry: &ToolRegistry,
+    unconnected: &mut Vec<RemoteServerConfig>,
+) -> Vec<(String, bool)> {
+    let mut connected = Vec::new();
+    for c in std::mem::take(unconnected) {
+        match connect(&c, registry).await {
+            Ok(tool_count) => {
+                log::info!("[MCP] Remote '{}' connected, registered {} tools", c.id, tool_count);
+                registry.set_available(&c.id, true).await;
+                connected.push((c.id, true));
+            }
+            Err(e) => {
+                log::debug!("[MCP] Remote '{}' still unreachable: {}", c.id, e);
+                unconnected.push(c);
+            }
+        }
+    }
+    connected
+}
+
+/// Tauri command: configured remote servers and their current health.
+#[tauri::command]
+pub async fn get_remote_servers(
+    registry: tauri::State<'_, ToolRegistry>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut out = Vec::new();
+    for c in config::load_configs() {
+        let reachable = registry.is_available(&c.id).await;
+        out.push(serde_json::json!({
+            "id": c.id,
+            "name": c.name,
+            "url": c.url,
+            "authHeaders": c.auth_headers,
+            "reachable": reachable,
+        }));
+    }
+    Ok(out)
+}
+
+/// Tauri command: store an auth header value for a remote server in the keychain.
+/// Takes effect on the next launch.
+#[tauri::command]
+pub fn save_remote_auth_header(server_id: String, header: String, value: String) -> Result<(), String> {
+    config::save_auth_header(&server_id, &header, &value)?;
+    log::info!("[MCP] Saved '{}' header for remote '{}'", header, server_id);
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! Minimal Server-Sent Events parser for MCP HTTP responses.
+//!
+//! Streamable HTTP servers may answer a JSON-RPC POST with a
+//! `text/event-stream` body instead of plain JSON. Each `message` event
+//! carries one JSON-RPC message in its `data` field.
+
+use crate::mcp::types::JsonRpcResponse;
+
+/// One parsed SSE event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SseEvent {
+    /// Event name. Defaults to "message" when the server omits it.
+    pub event: String,
+    /// Concatenated `data:` lines, joined with `\n`.
+    pub data: String,
+}
+
+/// Length of the complete events at the start of raw stream bytes: up to
+/// and including the last blank line (`\n\n` or `\r\n\r\n`). Only that
+/// much is decoded; the rest waits for the next chunk, so a character
+/// split across chunks is never decoded in halves.
+pub fn complete_len(buf: &[u8]) -> usize {
+    (1..=buf.len())
+        .rev()
+        .find(|&end| buf[..end].ends_with(b"\n\n") || buf[..end].ends_with(b"\r\n\r\n"))
+        .unwrap_or(0)
+}
+
+/// Parse every complete event in `buf` (events end with a blank line).
+///
+/// `buf` must use `\n` line endings — callers normalize `\r\n` after
+/// decoding. Returns the events and the number of bytes consumed, so the
+/// caller can keep the unfinished tail and append the next chunk to it.
+pub fn parse_events(buf: &str) -> (Vec<SseEvent>, usize) {
+    let mut events = Vec::new();
+    let mut consumed = 0;
+    for block in buf.split_inclusive("\n\n") {
+        if !block.ends_with("\n\n") {
+            break;
+        }
+        consumed += block.len();
+        if let Some(event) = parse_block(block) {
+            events.push(event);
+        }
+    }
+    (events, consumed)
+}
+
+fn parse_block(block: &str) -> Option<SseEvent> {
+    let mut event = String::from("message");
+    let mut data: Vec<&str> = Vec::new();
+    for line in block.lines() {
+        if line.starts_with(':') {
+            continue; // comment / keep-alive
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event = value.to_string(),
+            "data" => data.push(value),
+            _ => {}
+        }
+    }
+    if data.is_empty() {
+        return None;
+    }
+    Some(SseEvent { event, data: data.join("\n") })
+}
+
+/// Find the JSON-RPC response with `id` among parsed events.
+pub fn find_response(events: &[SseEvent], id: u64) -> Option<JsonRpcResponse> {
+    events
+        .iter()
+        .filter(|e| e.event == "message")
+        .filter_map(|e| serde_json::from_str::<JsonRpcResponse>(&e.data).ok())
+        .find(|resp| resp.id == Some(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_complete_events_and_keeps_tail() {
+        let buf = "event: message\ndata: {\"a\":1}\n\ndata: partial";
+        let (events, consumed) = parse_events(buf);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "{\"a\":1}");
+        assert_eq!(&buf[consumed..], "data: partial");
+    }
+
+    #[test]
+    fn complete_len_stops_before_a_split_character() {
+        let mut buf = "data: caf\u{e9}\r\n\r\ndata: caf".as_bytes().to_vec();
+        buf.push(0xC3); // first byte of "é"; the second is in the next chunk
+        let end = complete_len(&buf);
+        assert_eq!(&buf[..end], "data: caf\u{e9}\r\n\r\n".as_bytes());
+        assert_eq!(complete_len(b"data: partial\n"), 0);
+    }
+
+    #[test]
+    fn joins_multiline_data() {
+        let buf = "data: line1\ndata: line2\n\n";
+        let (events, consumed) = parse_events(buf);
+        assert_eq!(events[0].data, "line1\nline2");
+        assert_eq!(events[0].event, "message");
+        assert_eq!(consumed, buf.len());
+    }
+
+    #[test]
+    fn skips_comments_and_empty_events() {
+        let (events, _) = parse_events(": keep-alive\n\n");
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn finds_matching_response() {
+        let buf = "data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n\
+                   data: {\"jsonrpc\":\"2.0\",\"id\":7,\"result\":{\"ok\":true}}\n\n";
+        let (events, _) = parse_events(buf);
+        let resp = find_response(&events, 7).unwrap();
+        assert!(resp.result.is_some());
+        assert!(find_response(&events, 8).is_none());
+    }
+}
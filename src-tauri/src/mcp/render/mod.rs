@@ -0,0 +1,265 @@
+//! Plugin result rendering contract.
+//!
+//! Plugins may return a typed result under the `omniglass/result` key of
+//! the MCP result's `_meta`, next to their plain text blocks. The host
+//! validates it and converts it into the `ActionResultBody` shape the
+//! result window already renders:
+//!
+//! | `type` | Fields | Rendered as |
+//! |---|---|---|
+//! | `text` | `text` | text result |
+//! | `markdown` | `markdown` | text result (markdown) |
+//! | `table` | `columns`, `rows`, `title?` | text result with an aligned table block |
+//! | `link` | `url`, `title?` | text result (`text/uri-list`, URL last) with an Open Link button |
+//! | `file` | `filename`, `content`, `mimeType?` | save-file dialog |
+//! | `image` | `data` (base64), `mimeType` | saved to a temp file, path shown |
+//!
+//! `structuredContent` is left alone: under MCP it follows the tool's own
+//! output schema, not this contract. Native MCP `image` content blocks are
+//! handled like the `image` type. Results with neither fall back to their
+//! concatenated text blocks. Saved images are removed after a day.
+
+mod saved_image;
+
+use crate::llm::execute::ActionResultBody;
+use crate::mcp::types::{ToolResult, ToolResultContent};
+use saved_image::save_image;
+use serde::Deserialize;
+
+const MAX_TEXT_BYTES: usize = 100 * 1024;
+const MAX_TABLE_ROWS: usize = 500;
+const MAX_FILE_BYTES: usize = 5 * 1024 * 1024;
+/// `_meta` key of a typed result.
+pub const RESULT_META_KEY: &str = "omniglass/result";
+
+/// A typed plugin result (`_meta["omniglass/result"]`).
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PluginOutput {
+    Text { text: String },
+    Markdown { markdown: String },
+    Table {
+        columns: Vec<String>,
+        rows: Vec<Vec<serde_json::Value>>,
+        #[serde(default)]
+        title: Option<String>,
+    },
+    Link {
+        url: String,
+        #[serde(default)]
+        title: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    File {
+        filename: String,
+        content: String,
+        #[serde(default)]
+        mime_type: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Image { data: String, mime_type: String },
+}
+
+fn body(result_type: &str, text: String, mime_type: Option<&str>) -> ActionResultBody {
+    ActionResultBody {
+        result_type: result_type.to_string(),
+        text: Some(text),
+        file_path: None,
+        command: None,
+        clipboard_content: None,
+        mime_type: mime_type.map(str::to_string),
+        email: None,
+    }
+}
+
+fn check_len(label: &str, s: &str, max: usize) -> Result<(), String> {
+    if s.len() > max {
+        return Err(format!("{} is larger than {} KB", label, max / 1024));
+    }
+    Ok(())
+}
+
+/// Validate a typed result and convert it for the result window.
+pub fn to_body(output: PluginOutput) -> Result<ActionResultBody, String> {
+    match output {
+        PluginOutput::Text { text } => {
+            check_len("text", &text, MAX_TEXT_BYTES)?;
+            Ok(body("text", text, None))
+        }
+        PluginOutput::Markdown { markdown } => {
+            check_len("markdown", &markdown, MAX_TEXT_BYTES)?;
+            Ok(body("text", markdown, Some("text/markdown")))
+        }
+        PluginOutput::Table { columns, rows, title } => {
+            let table = render_table(&columns, &rows)?;
+            let text = match title {
+                Some(t) => format!("**{}**\n```\n{}```", t, table),
+                None => format!("```\n{}```", table),
+            };
+            Ok(body("text", text, Some("text/markdown")))
+        }
+        PluginOutput::Link { url, title } => {
+            let parsed = reqwest::Url::parse(&url).map_err(|e| format!("invalid link: {}", e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!("link scheme '{}' is not allowed", parsed.scheme()));
+            }
+            let text = match title {
+                Some(t) => format!("{}\n{}", t, parsed),
+                None => parsed.to_string(),
+            };
+            Ok(body("text", text, Some("text/uri-list")))
+        }
+        PluginOutput::File { filename, content, mime_type } => {
+            if !is_plain_filename(&filename) {
+                return Err(format!("file name '{}' must not contain a path", filename));
+            }
+            check_len("file", &content, MAX_FILE_BYTES)?;
+            let mut b = body("file", content, mime_type.as_deref());
+            b.file_path = Some(filename);
+            Ok(b)
+        }
+        PluginOutput::Image { data, mime_type } => save_image(&data, &mime_type),
+    }
+}
+
+/// Convert a tool result: a typed `_meta` result first, then image
+/// blocks, then plain text.
+pub fn from_tool_result(result: &ToolResult) -> Result<ActionResultBody, String> {
+    if let Some(typed) = result.meta.as_ref().and_then(|m| m.get(RESULT_META_KEY)) {
+        let output: PluginOutput = serde_json::from_value(typed.clone())
+            .map_err(|e| format!("invalid {}: {}", RESULT_META_KEY, e))?;
+        return to_body(output);
+    }
+    let image = result.content.iter().find_map(|c| match c {
+        ToolResultContent::Image { data, mime_type } => Some((data, mime_type)),
+        _ => None,
+    });
+    if let Some((data, mime_type)) = image {
+        return save_image(data, mime_type);
+    }
+    Ok(body("text", result.text(), None))
+}
+
+/// Render rows as a fixed-width table (shown in a code block).
+pub fn render_table(columns: &[String], rows: &[Vec<serde_json::Value>]) -> Result<String, String> {
+    if columns.is_empty() {
+        return Err("table has no columns".to_string());
+    }
+    if rows.len() > MAX_TABLE_ROWS {
+        return Err(format!("table has more than {} rows", MAX_TABLE_ROWS));
+    }
+    let mut cells: Vec<Vec<String>> = vec![columns.to_vec()];
+    for (i, row) in rows.iter().enumerate() {
+        if row.len() != columns.len() {
+            return Err(format!("table row {} has {} cells, expected {}", i, row.len(), columns.len()));
+        }
+        cells.push(row.iter().map(cell_text).collect::<Result<_, _>>()?);
+    }
+
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|c| cells.iter().map(|r| r[c].chars().count()).max().unwrap_or(0))
+        .collect();
+    let mut out = String::new();
+    for (i, row) in cells.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{:<w$}", cell, w = *w))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            out.push_str(&rule.join("  "));
+            out.push('\n');
+        }
+    }
+    check_len("table", &out, MAX_TEXT_BYTES)?;
+    Ok(out)
+}
+
+fn cell_text(v: &serde_json::Value) -> Result<String, String> {
+    match v {
+        serde_json::Value::String(s) => Ok(s.replace(['\n', '\r'], " ")),
+        serde_json::Value::Null => Ok(String::new()),
+        serde_json::Value::Bool(_) | serde_json::Value::Number(_) => Ok(v.to_string()),
+        _ => Err("table cells must be strings, numbers, booleans, or null".to_string()),
+    }
+}
+
+fn is_plain_filename(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', '\0'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(v: serde_json::Value) -> Result<ActionResultBody, String> {
+        to_body(serde_json::from_value(v).map_err(|e| e.to_string())?)
+    }
+
+    #[test]
+    fn markdown_and_text_map_to_text_results() {
+        let b = parse(json!({"type": "markdown", "markdown": "**hi**"})).unwrap();
+        assert_eq!(b.result_type, "text");
+        assert_eq!(b.mime_type.as_deref(), Some("text/markdown"));
+        assert_eq!(parse(json!({"type": "text", "text": "ok"})).unwrap().text.as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn table_renders_aligned_and_checks_shape() {
+        let cols = vec!["Name".to_string(), "Qty".to_string()];
+        let rows = vec![vec![json!("apple"), json!(3)], vec![json!("kiwi"), json!(null)]];
+        let t = render_table(&cols, &rows).unwrap();
+        assert_eq!(t, "Name   Qty\n-----  ---\napple  3\nkiwi\n");
+        assert!(render_table(&cols, &[vec![json!("x")]]).is_err());
+        assert!(render_table(&cols, &[vec![json!("x"), json!({"a": 1})]]).is_err());
+        assert!(render_table(&[], &[]).is_err());
+    }
+
+    #[test]
+    fn links_must_be_http() {
+        let b = parse(json!({"type": "link", "url": "https://example.com/x", "title": "Issue"})).unwrap();
+        assert_eq!(b.text.as_deref(), Some("Issue\nhttps://example.com/x"));
+        assert!(parse(json!({"type": "link", "url": "javascript:alert(1)"})).is_err());
+        assert!(parse(json!({"type": "link", "url": "file:///etc/passwd"})).is_err());
+    }
+
+    #[test]
+    fn file_names_cannot_be_paths() {
+        let b = parse(json!({"type": "file", "filename": "out.csv", "content": "a,b"})).unwrap();
+        assert_eq!(b.result_type, "file");
+        assert_eq!(b.file_path.as_deref(), Some("out.csv"));
+        assert!(parse(json!({"type": "file", "filename": "../x.sh", "content": ""})).is_err());
+        assert!(parse(json!({"type": "file", "filename": "..", "content": ""})).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_images_and_unknown_types() {
+        assert!(parse(json!({"type": "image", "data": "AAAA", "mimeType": "image/svg+xml"})).is_err());
+        assert!(parse(json!({"type": "image", "data": "%%%", "mimeType": "image/png"})).is_err());
+        assert!(parse(json!({"type": "html", "html": "<b>x</b>"})).is_err());
+    }
+
+    #[test]
+    fn plain_text_results_fall_back() {
+        let r: ToolResult = serde_json::from_value(json!({"content": [{"type": "text", "text": "hi"}]})).unwrap();
+        assert_eq!(from_tool_result(&r).unwrap().text.as_deref(), Some("hi"));
+        // A tool's own structuredContent is not this contract
+        let r: ToolResult = serde_json::from_value(json!({
+            "content": [{"type": "text", "text": "hi"}], "structuredContent": {"type": "link", "url": "ftp://x"}
+        }))
+        .unwrap();
+        assert_eq!(from_tool_result(&r).unwrap().text.as_deref(), Some("hi"));
+        let r: ToolResult = serde_json::from_value(json!({
+            "content": [], "_meta": {"omniglass/result": {"type": "link", "url": "ftp://x"}}
+        }))
+        .unwrap();
+        assert!(from_tool_result(&r).is_err());
+    }
+}
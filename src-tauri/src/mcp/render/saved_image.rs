@@ -0,0 +1,64 @@
+//! Images from plugin results — decoded, checked, and saved to a temp
+//! directory so the result can show their path. Images older than a day
+//! are removed when the next one is saved.
+
+use super::body;
+use crate::llm::execute::ActionResultBody;
+use base64::Engine;
+use std::path::Path;
+use std::time::Duration;
+
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+/// Temp directory for images from plugin results.
+const RESULTS_DIR: &str = "omni-glass-results";
+/// Saved images older than this are removed when the next one is saved.
+const IMAGE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const IMAGE_TYPES: &[(&str, &str)] = &[
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+];
+
+/// Decode an image and write it to the per-session results directory.
+pub(super) fn save_image(data: &str, mime_type: &str) -> Result<ActionResultBody, String> {
+    let ext = IMAGE_TYPES
+        .iter()
+        .find(|(m, _)| *m == mime_type)
+        .map(|(_, e)| *e)
+        .ok_or_else(|| format!("image type '{}' is not supported", mime_type))?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| format!("image data is not valid base64: {}", e))?;
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!("image is larger than {} MB", MAX_IMAGE_BYTES / (1024 * 1024)));
+    }
+
+    let dir = std::env::temp_dir().join(RESULTS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to save image: {}", e))?;
+    remove_stale_images(&dir);
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let path = dir.join(format!("plugin-image-{}.{}", stamp, ext));
+    std::fs::write(&path, &bytes).map_err(|e| format!("Failed to save image: {}", e))?;
+
+    let mut b = body("text", format!("Image saved to {}", path.display()), Some(mime_type));
+    b.file_path = Some(path.to_string_lossy().into_owned());
+    Ok(b)
+}
+
+/// Remove images saved more than `IMAGE_TTL` ago. Best effort.
+fn remove_stale_images(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age > IMAGE_TTL));
+        if stale && entry.file_name().to_string_lossy().starts_with("plugin-image-") {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Plugin resources — reference data the LLM can fetch while it works.
+//!
+//! A stdio plugin whose server advertises the MCP `resources` capability
+//! has its `resources/list` read at load time. During the execute step the
+//! LLM is offered a `read_resource` tool listing those URIs
+//! (`llm/agent.rs`); each call is answered here by `resources/read` on the
+//! owning plugin. Only listed URIs can be read, contents are redacted and
+//! marked untrusted before they reach the LLM, and disabled plugins are
+//! left out. Remote and WASM plugins don't expose resources.
+
+use crate::mcp::client::McpServer;
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::types::McpResource;
+use crate::safety::{injection, redact};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+
+/// Name of the tool the LLM calls to read a resource.
+pub const READ_TOOL: &str = "read_resource";
+
+/// Resources kept per plugin.
+const MAX_RESOURCES: usize = 50;
+/// Text returned per read.
+const MAX_RESOURCE_CHARS: usize = 20_000;
+
+/// Resources of loaded plugins, keyed by plugin_id.
+static RESOURCES: LazyLock<Mutex<BTreeMap<String, Vec<McpResource>>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// List a freshly initialized server's resources, if it has any.
+/// Failures are logged — resources are optional.
+pub async fn discover(plugin_id: &str, server: &mut McpServer) {
+    if server.capabilities.resources.is_none() {
+        forget(plugin_id);
+        return;
+    }
+    match server.list_resources().await {
+        Ok(mut list) => {
+            list.truncate(MAX_RESOURCES);
+            log::info!("[MCP] '{}' exposes {} resources", plugin_id, list.len());
+            RESOURCES.lock().unwrap_or_else(|e| e.into_inner()).insert(plugin_id.to_string(), list);
+        }
+        Err(e) => {
+            log::warn!("[MCP] '{}' resources/list failed: {}", plugin_id, e);
+            forget(plugin_id);
+        }
+    }
+}
+
+/// Drop a plugin's resources (unload).
+pub fn forget(plugin_id: &str) {
+    RESOURCES.lock().unwrap_or_else(|e| e.into_inner()).remove(plugin_id);
+}
+
+/// Resources of enabled plugins as `(plugin_id, resource)`.
+fn catalog() -> Vec<(String, McpResource)> {
+    let enablement = crate::mcp::enablement::current();
+    let map = RESOURCES.lock().unwrap_or_else(|e| e.into_inner());
+    map.iter()
+        .filter(|(plugin_id, _)| !enablement.disabled_plugins.contains(*plugin_id))
+        .flat_map(|(plugin_id, list)| list.iter().map(|r| (plugin_id.clone(), r.clone())))
+        .collect()
+}
+
+/// Anthropic tool definition for `read_resource`, or `None` when no
+/// plugin exposes resources.
+pub fn tool_definition() -> Option<Value> {
+    build_tool(&catalog())
+}
+
+fn build_tool(catalog: &[(String, McpResource)]) -> Option<Value> {
+    if catalog.is_empty() {
+        return None;
+    }
+    let mut description = String::from(
+        "Read reference data exposed by an installed plugin. Call only when the data helps with the requested action. Available resources:",
+    );
+    for (plugin_id, r) in catalog {
+        description.push_str(&format!("\n- {} ({}): {}", r.uri, plugin_id, r.name));
+        if let Some(d) = r.description.as_deref().filter(|d| !d.is_empty()) {
+            description.push_str(&format!(" — {}", d));
+        }
+    }
+    let uris: Vec<&str> = catalog.iter().map(|(_, r)| r.uri.as_str()).collect();
+    Some(serde_json::json!({
+        "name": READ_TOOL,
+        "description": description,
+        "input_schema": {
+            "type": "object",
+            "properties": { "uri": { "type": "string", "enum": uris } },
+            "required": ["uri"]
+        }
+    }))
+}
+
+/// Join the text parts of a `resources/read` result, capped.
+fn contents_text(contents: &[Value]) -> String {
+    let parts: Vec<&str> = contents
+        .iter()
+        .map(|c| c.get("text").and_then(Value::as_str).unwrap_or("[binary content omitted]"))
+        .collect();
+    let text = parts.join("\n");
+    if text.chars().count() > MAX_RESOURCE_CHARS {
+        return text.chars().take(MAX_RESOURCE_CHARS).collect::<String>() + "\n[truncated]";
+    }
+    text
+}
+
+/// Read a listed resource for the LLM: redacted and marked untrusted.
+pub async fn read_for_llm(registry: &ToolRegistry, uri: &str) -> Result<String, String> {
+    let plugin_id = catalog()
+        .into_iter()
+        .find(|(_, r)| r.uri == uri)
+        .map(|(plugin_id, _)| plugin_id)
+        .ok_or_else(|| format!("Unknown resource '{}'", uri))?;
+    let server = registry.stdio_server(&plugin_id).await?;
+    let contents = server.lock().await.read_resource(uri).await?;
+    log::info!("[EXECUTE] Read resource '{}' from '{}'", uri, plugin_id);
+    let clean = redact::redact_sensitive_data(&contents_text(&contents)).cleaned_text;
+    Ok(injection::wrap_untrusted("plugin_resource", &clean))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(uri: &str, description: Option<&str>) -> McpResource {
+        McpResource {
+            uri: uri.into(),
+            name: "Glossary".into(),
+            description: description.map(Into::into),
+            mime_type: None,
+        }
+    }
+
+    #[test]
+    fn tool_lists_every_uri() {
+        assert!(build_tool(&[]).is_none());
+        let catalog = vec![
+            ("com.example.a".to_string(), resource("docs://glossary", Some("Team terms"))),
+            ("com.example.b".to_string(), resource("db://schema", None)),
+        ];
+        let tool = build_tool(&catalog).unwrap();
+        assert_eq!(tool["name"], READ_TOOL);
+        assert_eq!(tool["input_schema"]["properties"]["uri"]["enum"], serde_json::json!(["docs://glossary", "db://schema"]));
+        let description = tool["description"].as_str().unwrap();
+        assert!(description.contains("- docs://glossary (com.example.a): Glossary — Team terms"));
+        assert!(description.contains("- db://schema (com.example.b): Glossary"));
+    }
+
+    #[test]
+    fn contents_are_joined_and_capped() {
+        let contents = vec![
+            serde_json::json!({"uri": "a", "text": "first"}),
+            serde_json::json!({"uri": "a", "blob": "AAAA"}),
+        ];
+        assert_eq!(contents_text(&contents), "first\n[binary content omitted]");
+        let long = vec![serde_json::json!({"text": "x".repeat(MAX_RESOURCE_CHARS + 10)})];
+        assert!(contents_text(&long).ends_with("\n[truncated]"));
+    }
+}
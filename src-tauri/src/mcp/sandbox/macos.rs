@@ -11,21 +11,20 @@
 //! etc. are mathematically inaccessible. Plugin stdout → LLM cloud API
 //! = exfiltration path, so user files must be default-deny.
 
-use crate::mcp::manifest::{PluginManifest, Runtime};
+use crate::mcp::manifest::PluginManifest;
 use std::path::{Path, PathBuf};
 
-/// Paths for a runtime binary and its installation prefix.
-pub struct RuntimePaths {
-    pub binary: PathBuf,
-    pub prefix: PathBuf,
-}
+pub use super::runtime::{find_runtime_paths, RuntimePaths};
 
 /// Build a sandbox-exec `.sb` profile from manifest permissions.
 /// Default-deny, broad system reads, wall off /Users, then selectively
-/// re-allow runtime prefix, plugin dir, and declared paths.
+/// re-allow runtime prefix, plugin dir, and declared paths. With
+/// `proxy_port` set the plugin may only connect to the host allowlist
+/// proxy (`net_proxy.rs`) on that port.
 pub fn generate_profile(
     manifest: &PluginManifest,
     plugin_dir: &Path,
+    proxy_port: Option<u16>,
 ) -> Result<String, String> {
     let runtime_paths = find_runtime_paths(&manifest.runtime)?;
     let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
@@ -106,12 +105,18 @@ pub fn generate_profile(
 
     // ── Network (if declared) ──
     if let Some(ref domains) = manifest.permissions.network {
-        if !domains.is_empty() {
-            profile.push_str(
+        match proxy_port {
+            _ if domains.is_empty() => {}
+            Some(port) => profile.push_str(&format!(
+                ";; Network: only via the host allowlist proxy\n\
+                 (allow network-outbound (remote ip \"localhost:{}\"))\n\n",
+                port
+            )),
+            None => profile.push_str(
                 ";; Network (coarse: domain filtering not possible)\n\
                  (allow network-outbound)\n(allow network-inbound)\n\
                  (allow network* (local ip \"localhost:*\"))\n\n"
-            );
+            ),
         }
     }
 
@@ -170,43 +175,10 @@ pub fn write_profile(plugin_id: &str, profile: &str) -> Result<PathBuf, String>
     Ok(path)
 }
 
-/// Find the runtime binary and its installation prefix.
-/// The prefix is the parent of `bin/` — e.g., for
-/// `~/.nvm/versions/node/v24/bin/node`, prefix = `~/.nvm/versions/node/v24`.
-pub fn find_runtime_paths(runtime: &Runtime) -> Result<RuntimePaths, String> {
-    match runtime {
-        Runtime::Node => {
-            let binary = which::which("node")
-                .map_err(|_| "Node.js not found in PATH".to_string())?;
-            let prefix = binary.parent()
-                .and_then(|bin| bin.parent())
-                .unwrap_or(binary.parent().unwrap_or(Path::new("")))
-                .to_path_buf();
-            Ok(RuntimePaths { binary, prefix })
-        }
-        Runtime::Python => {
-            let binary = which::which("python3")
-                .or_else(|_| which::which("python"))
-                .map_err(|_| "Python not found in PATH".to_string())?;
-            let prefix = binary.parent()
-                .and_then(|bin| bin.parent())
-                .unwrap_or(binary.parent().unwrap_or(Path::new("")))
-                .to_path_buf();
-            Ok(RuntimePaths { binary, prefix })
-        }
-        Runtime::Binary => {
-            Ok(RuntimePaths {
-                binary: PathBuf::new(),
-                prefix: PathBuf::new(),
-            })
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mcp::manifest::{FsPerm, Permissions};
+    use crate::mcp::manifest::{FsPerm, Permissions, Runtime};
 
     fn test_manifest(perms: Permissions) -> PluginManifest {
         PluginManifest {
@@ -218,6 +190,7 @@ mod tests {
             entry: "index.js".to_string(),
             permissions: perms,
             configuration: None,
+            extensions: Default::default(),
         }
     }
 
@@ -226,7 +199,7 @@ mod tests {
         let manifest = test_manifest(Permissions::default());
         let dir = std::env::temp_dir().join("og-sandbox-test");
         let _ = std::fs::create_dir_all(&dir);
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         assert!(profile.contains("(deny default)"));
         assert!(profile.contains("(allow file-read* (subpath \"/\"))"));
         assert!(profile.contains("(deny file-read* (subpath \"/Users\"))"));
@@ -237,7 +210,7 @@ mod tests {
         let manifest = test_manifest(Permissions::default());
         let dir = std::env::temp_dir().join("og-sandbox-test");
         let _ = std::fs::create_dir_all(&dir);
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         // Runtime prefix should appear after the /Users deny
         let users_deny_pos = profile.find("deny file-read* (subpath \"/Users\")").unwrap();
         let re_allow_pos = profile.find("Re-allow: runtime prefix").unwrap();
@@ -248,7 +221,7 @@ mod tests {
     fn profile_includes_plugin_dir() {
         let manifest = test_manifest(Permissions::default());
         let dir = PathBuf::from("/tmp/og-test-plugin");
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         assert!(profile.contains("/tmp/og-test-plugin"));
     }
 
@@ -256,7 +229,7 @@ mod tests {
     fn no_network_no_network_rule() {
         let manifest = test_manifest(Permissions::default());
         let dir = std::env::temp_dir();
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         assert!(!profile.contains("network-outbound"));
     }
 
@@ -267,10 +240,22 @@ mod tests {
             ..Default::default()
         });
         let dir = std::env::temp_dir();
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         assert!(profile.contains("network-outbound"));
     }
 
+    #[test]
+    fn with_proxy_only_allows_proxy_port() {
+        let manifest = test_manifest(Permissions {
+            network: Some(vec!["api.example.com".into()]),
+            ..Default::default()
+        });
+        let dir = std::env::temp_dir();
+        let profile = generate_profile(&manifest, &dir, Some(40123)).unwrap();
+        assert!(profile.contains("(remote ip \"localhost:40123\")"));
+        assert!(!profile.contains("(allow network-outbound)\n"));
+    }
+
     #[test]
     fn declared_fs_overrides_users_deny() {
         let manifest = test_manifest(Permissions {
@@ -281,7 +266,7 @@ mod tests {
             ..Default::default()
         });
         let dir = std::env::temp_dir();
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         let home = dirs::home_dir().unwrap();
         let home_str = home.to_string_lossy();
         // Read-only path gets re-allow after /Users deny
@@ -301,7 +286,7 @@ mod tests {
             ..Default::default()
         });
         let dir = std::env::temp_dir();
-        let profile = generate_profile(&manifest, &dir).unwrap();
+        let profile = generate_profile(&manifest, &dir, None).unwrap();
         assert!(!profile.contains("\"~/"));
         let home = dirs::home_dir().unwrap();
         assert!(profile.contains(&format!("{}/Documents", home.to_string_lossy())));
@@ -2,16 +2,27 @@
 //!
 //! Submodules:
 //! - env_filter: Environment variable filtering (all platforms)
+//! - net_proxy: Per-plugin network allowlist proxy (all platforms)
 //! - macos: sandbox-exec profile generation (macOS only)
 //! - risk: Permission risk level calculation
+//! - stderr_watch: Report sandbox file denials from plugin stderr
 //! - windows/linux: stubs with env filtering only
 
+/// Whether this OS has a process sandbox for plugins (sandbox-exec on
+/// macOS). Elsewhere plugins run with a filtered environment only.
+pub const OS_SANDBOX: bool = cfg!(target_os = "macos");
+
 pub mod env_filter;
+pub mod net_proxy;
 pub mod risk;
+pub mod stderr_watch;
 
 #[cfg(target_os = "macos")]
 pub mod macos;
 
+#[cfg(target_os = "macos")]
+mod runtime;
+
 #[cfg(target_os = "windows")]
 pub mod windows;
 
@@ -0,0 +1,241 @@
+//! Per-plugin network allowlist proxy.
+//!
+//! Each plugin that declares `permissions.network` gets its own HTTP proxy
+//! on 127.0.0.1. The loader points the plugin at it via `HTTP(S)_PROXY`,
+//! and on macOS the sandbox profile only allows outbound connections to
+//! the proxy port, so the proxy is the plugin's only way out. Where no
+//! sandbox holds a plugin to its proxy, a plugin that declares hosts
+//! doesn't start (`permissions::check_enforceable`).
+//!
+//! Requests to declared hosts are tunnelled (CONNECT) or forwarded
+//! (absolute-form HTTP). Anything else gets a 403 and is reported as a
+//! permission violation. Hosts the user has revoked since the plugin
+//! started (`permission_audit.rs`) are refused the same way. Allowed
+//! requests leave through the app's own upstream proxy, if one is set
+//! (`net::upstream`).
+
+use crate::mcp::permission_audit;
+use crate::mcp::permissions::host_matches;
+use crate::mcp::violations::{self, ViolationKind};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head we accept before giving up.
+const MAX_HEAD_BYTES: usize = 8192;
+
+/// Proxy environment variables set on the plugin process.
+const PROXY_ENV_VARS: &[&str] = &[
+    "HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY",
+    "http_proxy", "https_proxy", "all_proxy",
+];
+
+/// Where a proxied request wants to go.
+#[derive(Debug, PartialEq)]
+pub struct ProxyTarget {
+    pub host: String,
+    pub port: u16,
+    /// `true` for CONNECT tunnels (HTTPS), `false` for plain HTTP forwarding.
+    pub tunnel: bool,
+}
+
+/// A running proxy. Dropping it stops the listener.
+pub struct NetProxy {
+    pub port: u16,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl NetProxy {
+    /// Bind a loopback listener on an ephemeral port and start serving.
+    pub async fn start(plugin_id: &str, allowed_hosts: Vec<String>) -> Result<Self, String> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .map_err(|e| format!("Failed to bind network proxy: {}", e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read proxy address: {}", e))?
+            .port();
+
+        let plugin_id = plugin_id.to_string();
+        let allowed = Arc::new(allowed_hosts);
+        log::info!(
+            "[SANDBOX] Network proxy for '{}' on 127.0.0.1:{} (allow: {:?})",
+            plugin_id,
+            port,
+            allowed
+        );
+
+        let task = tauri::async_runtime::spawn(async move {
+            loop {
+                let (stream, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        log::warn!("[SANDBOX] Proxy accept failed for '{}': {}", plugin_id, e);
+                        continue;
+                    }
+                };
+                let id = plugin_id.clone();
+                let allowed = allowed.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &id, &allowed).await {
+                        log::debug!("[SANDBOX] Proxy connection for '{}' ended: {}", id, e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { port, task })
+    }
+
+    /// Environment variables that route the plugin's HTTP clients through the proxy.
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let url = format!("http://127.0.0.1:{}", self.port);
+        let mut vars: Vec<(String, String)> = PROXY_ENV_VARS
+            .iter()
+            .map(|key| (key.to_string(), url.clone()))
+            .collect();
+        vars.push(("NO_PROXY".to_string(), String::new()));
+        vars
+    }
+}
+
+impl Drop for NetProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Serve one client connection: read the request head, check the host,
+/// then splice the client and upstream sockets together.
+async fn handle_connection(
+    mut client: TcpStream,
+    plugin_id: &str,
+    allowed: &[String],
+) -> Result<(), String> {
+    let (buf, head_end) = read_head(&mut client).await?;
+    let Some(target) = parse_request_target(&buf[..head_end]) else {
+        let _ = client.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n").await;
+        return Err("unparseable request".to_string());
+    };
+
+    let granted = allowed.iter().find(|pattern| {
+        host_matches(pattern, &target.host)
+            && !permission_audit::is_revoked(plugin_id, &format!("network:{}", pattern))
+    });
+    let Some(pattern) = granted else {
+        violations::report(plugin_id, ViolationKind::Network, &target.host);
+        let _ = client
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await;
+        return Ok(());
+    };
+    permission_audit::record_use(plugin_id, &format!("network:{}", pattern));
+
+    let upstream = match crate::net::upstream::connect(&target.host, target.port, target.tunnel).await {
+        Ok(u) => u,
+        Err(e) => {
+            let _ = client.write_all(b"HTTP/1.1 502 Bad Gateway\r\nConnection: close\r\n\r\n").await;
+            return Err(e);
+        }
+    };
+
+    let io = |e: std::io::Error| e.to_string();
+    let forwarded = upstream.forward(&buf);
+    let mut stream = upstream.stream;
+    if target.tunnel {
+        client.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n").await.map_err(io)?;
+        stream.write_all(&buf[head_end..]).await.map_err(io)?;
+    } else {
+        stream.write_all(&forwarded).await.map_err(io)?;
+    }
+    tokio::io::copy_bidirectional(&mut client, &mut stream).await.map_err(io)?;
+    Ok(())
+}
+
+/// Read until the end of the request head. Returns the bytes read so far
+/// and the offset just past the blank line.
+async fn read_head(client: &mut TcpStream) -> Result<(Vec<u8>, usize), String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let n = client.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err("client closed before sending a request".to_string());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            return Ok((buf, pos + 4));
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err("request head too large".to_string());
+        }
+    }
+}
+
+/// Parse the request line of a proxy request.
+///
+/// Accepts `CONNECT host:port` and absolute-form `GET http://host[:port]/path`.
+pub fn parse_request_target(head: &[u8]) -> Option<ProxyTarget> {
+    let head = std::str::from_utf8(head).ok()?;
+    let mut parts = head.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_authority(target, None)?;
+        return Some(ProxyTarget { host, port, tunnel: true });
+    }
+
+    let rest = target.strip_prefix("http://")?;
+    let authority = rest.split('/').next()?;
+    let (host, port) = split_authority(authority, Some(80))?;
+    Some(ProxyTarget { host, port, tunnel: false })
+}
+
+/// Split `host[:port]` (IPv6 hosts in brackets). Strips userinfo.
+fn split_authority(authority: &str, default_port: Option<u16>) -> Option<(String, u16)> {
+    let authority = authority.rsplit('@').next()?;
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((h, p)) if !p.contains(']') => (h, p.parse().ok()?),
+        _ => (authority, default_port?),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_ascii_lowercase(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_connect_tunnel() {
+        let t = parse_request_target(b"CONNECT api.github.com:443 HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(t, ProxyTarget { host: "api.github.com".into(), port: 443, tunnel: true });
+    }
+
+    #[test]
+    fn parses_absolute_form_http() {
+        let t = parse_request_target(b"GET http://Example.com/a/b?c=1 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(t, ProxyTarget { host: "example.com".into(), port: 80, tunnel: false });
+        let t = parse_request_target(b"POST http://example.com:8080/ HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(t.port, 8080);
+    }
+
+    #[test]
+    fn strips_userinfo_and_ipv6_brackets() {
+        let t = parse_request_target(b"GET http://user:pw@example.com/ HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(t.host, "example.com");
+        let t = parse_request_target(b"CONNECT [::1]:443 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(t.host, "::1");
+    }
+
+    #[test]
+    fn rejects_origin_form_and_missing_port() {
+        assert!(parse_request_target(b"GET /index.html HTTP/1.1\r\n\r\n").is_none());
+        assert!(parse_request_target(b"CONNECT api.github.com HTTP/1.1\r\n\r\n").is_none());
+        assert!(parse_request_target(b"").is_none());
+    }
+}
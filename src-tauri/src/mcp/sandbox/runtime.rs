@@ -0,0 +1,48 @@
+//! Runtime binary discovery for sandbox profiles.
+//!
+//! The macOS profile must re-allow the runtime's install prefix (which
+//! often lives under /Users, e.g. nvm or pyenv) and exec of its binary.
+
+use crate::mcp::manifest::Runtime;
+use std::path::{Path, PathBuf};
+
+/// Paths for a runtime binary and its installation prefix.
+pub struct RuntimePaths {
+    pub binary: PathBuf,
+    pub prefix: PathBuf,
+}
+
+/// Find the runtime binary and its installation prefix.
+/// The prefix is the parent of `bin/` — e.g., for
+/// `~/.nvm/versions/node/v24/bin/node`, prefix = `~/.nvm/versions/node/v24`.
+pub fn find_runtime_paths(runtime: &Runtime) -> Result<RuntimePaths, String> {
+    match runtime {
+        Runtime::Node => {
+            let binary = which::which("node")
+                .map_err(|_| "Node.js not found in PATH".to_string())?;
+            let prefix = binary.parent()
+                .and_then(|bin| bin.parent())
+                .unwrap_or(binary.parent().unwrap_or(Path::new("")))
+                .to_path_buf();
+            Ok(RuntimePaths { binary, prefix })
+        }
+        Runtime::Python => {
+            let binary = which::which("python3")
+                .or_else(|_| which::which("python"))
+                .map_err(|_| "Python not found in PATH".to_string())?;
+            let prefix = binary.parent()
+                .and_then(|bin| bin.parent())
+                .unwrap_or(binary.parent().unwrap_or(Path::new("")))
+                .to_path_buf();
+            Ok(RuntimePaths { binary, prefix })
+        }
+        // Binaries need no prefix; WASM plugins never spawn a process;
+        // shell adapters exec only their declared commands
+        Runtime::Binary | Runtime::Wasm | Runtime::Shell => {
+            Ok(RuntimePaths {
+                binary: PathBuf::new(),
+                prefix: PathBuf::new(),
+            })
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Plugin stderr watcher — turns sandbox file denials into violations.
+//!
+//! When the sandbox blocks a file access, the plugin's runtime prints an
+//! error such as `EPERM: operation not permitted, open '/Users/me/.ssh/id_rsa'`
+//! (Node) or `PermissionError: [Errno 1] Operation not permitted: '...'`
+//! (Python). We tail stderr, forward every line to the debug log and the
+//! dev console (`devmode::logs`), and report the denied path to the
+//! violation log.
+
+use crate::mcp::violations::{self, ViolationKind};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::ChildStderr;
+
+/// Markers that identify a permission denial in runtime error output.
+const DENIAL_MARKERS: &[&str] = &[
+    "operation not permitted",
+    "permission denied",
+    "eperm",
+    "eacces",
+];
+
+/// Spawn a task that drains the plugin's stderr until it closes.
+pub fn watch(plugin_id: &str, stderr: ChildStderr) {
+    let plugin_id = plugin_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            log::debug!("[MCP] '{}' stderr: {}", plugin_id, line);
+            crate::mcp::devmode::logs::publish(&plugin_id, &line);
+            if let Some(path) = parse_fs_denial(&line) {
+                violations::report(&plugin_id, ViolationKind::Filesystem, &path);
+            }
+        }
+    });
+}
+
+/// Extract the denied path from a runtime error line, if it is one.
+/// The path is the last single-quoted absolute path on the line.
+pub fn parse_fs_denial(line: &str) -> Option<String> {
+    let lower = line.to_ascii_lowercase();
+    if !DENIAL_MARKERS.iter().any(|m| lower.contains(m)) {
+        return None;
+    }
+    line.split('\'')
+        .skip(1)
+        .step_by(2)
+        .filter(|s| s.starts_with('/'))
+        .last()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_node_eperm() {
+        let line = "Error: EPERM: operation not permitted, open '/Users/me/.ssh/id_rsa'";
+        assert_eq!(parse_fs_denial(line).as_deref(), Some("/Users/me/.ssh/id_rsa"));
+    }
+
+    #[test]
+    fn parses_python_permission_error() {
+        let line = "PermissionError: [Errno 1] Operation not permitted: '/Users/me/Desktop/a.txt'";
+        assert_eq!(parse_fs_denial(line).as_deref(), Some("/Users/me/Desktop/a.txt"));
+    }
+
+    #[test]
+    fn ignores_unrelated_lines() {
+        assert_eq!(parse_fs_denial("Loaded config from '/tmp/x.json'"), None);
+        assert_eq!(parse_fs_denial("EACCES without a quoted path"), None);
+    }
+}
@@ -3,6 +3,8 @@
 //! Full AppContainer implementation is planned for Phase 3.
 //! For now, plugins run with filtered environment variables but
 //! without OS-level process sandboxing.
+//! Plugins that declare network hosts or filesystem paths don't start
+//! here (`permissions::check_enforceable`): nothing would hold them to it.
 
 use std::collections::HashMap;
 use std::path::Path;
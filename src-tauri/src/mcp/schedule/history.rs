@@ -0,0 +1,75 @@
+//! Scheduled task history — what each run returned.
+//!
+//! Bounded list persisted to `~/.config/omni-glass/task-history.json`
+//! so results survive restarts (a notification is easy to miss).
+
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Entries kept (oldest dropped first).
+const MAX_ENTRIES: usize = 200;
+/// Result text kept per entry.
+const MAX_TEXT_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub plugin_id: String,
+    pub task_id: String,
+    pub success: bool,
+    /// Result text, or the error message.
+    pub text: String,
+    /// Unix epoch seconds.
+    pub timestamp: u64,
+}
+
+static HISTORY: JsonStore<VecDeque<HistoryEntry>> = JsonStore::compact("task-history.json");
+
+/// Append a run, truncating long results.
+pub fn record(mut entry: HistoryEntry) {
+    if entry.text.chars().count() > MAX_TEXT_CHARS {
+        entry.text = entry.text.chars().take(MAX_TEXT_CHARS).collect::<String>() + "…";
+    }
+    HISTORY.update_logged(|entries| push_bounded(entries, entry));
+}
+
+fn push_bounded(entries: &mut VecDeque<HistoryEntry>, entry: HistoryEntry) {
+    while entries.len() >= MAX_ENTRIES {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+/// Drop a plugin's runs (on uninstall).
+pub fn forget_plugin(plugin_id: &str) {
+    HISTORY.update_logged(|entries| entries.retain(|e| e.plugin_id != plugin_id));
+}
+
+/// Tauri command: task runs, newest last.
+#[tauri::command]
+pub fn get_task_history() -> Vec<HistoryEntry> {
+    HISTORY.read(|entries| entries.iter().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_is_bounded() {
+        let mut entries = VecDeque::new();
+        for i in 0..(MAX_ENTRIES as u64 + 5) {
+            let entry = HistoryEntry {
+                plugin_id: "com.example.a".into(),
+                task_id: "t".into(),
+                success: true,
+                text: String::new(),
+                timestamp: i,
+            };
+            push_bounded(&mut entries, entry);
+        }
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries.front().unwrap().timestamp, 5);
+    }
+}
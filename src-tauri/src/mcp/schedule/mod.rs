@@ -0,0 +1,191 @@
+//! Scheduled plugin tasks — periodic tool calls without a snip.
+//!
+//! A manifest may declare tasks that call one of its tools on an interval:
+//!
+//! ```json
+//! "tasks": [{
+//!   "id": "watch-price", "tool": "check_price", "every": "1h",
+//!   "arguments": { "url": "https://shop.example.com/item/42" },
+//!   "description": "Check the watched item's price"
+//! }]
+//! ```
+//!
+//! Frequency is bounded: `every` is `<n>m`, `<n>h`, or `<n>d`, between 15
+//! minutes and 7 days, and a plugin may declare at most 5 tasks. No task
+//! runs until the user approves it in Settings. The approval records the
+//! tool, arguments, and interval, so an update that changes what the task
+//! does or asks to run more often needs a new one. Approvals and last-run
+//! times are kept by `store.rs`, the loop and notifications are in
+//! `runner.rs`, run records in `history.rs`.
+
+pub mod history;
+mod runner;
+mod store;
+
+pub use runner::spawn_scheduler;
+pub use store::{load_store, update_store, ScheduleStore, TaskState};
+
+use store::task_key;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+
+const MAX_TASKS: usize = 5;
+const MIN_INTERVAL_SECS: u64 = 15 * 60;
+const MAX_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// A task as declared in the manifest.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTask {
+    /// Lowercase letters, digits, `-` and `_`; unique within the plugin.
+    pub id: String,
+    /// Tool (by name) the task calls.
+    pub tool: String,
+    /// Interval such as `"30m"`, `"6h"`, `"1d"`.
+    pub every: String,
+    /// Arguments passed to the tool on every run.
+    #[serde(default = "empty_object")]
+    pub arguments: Value,
+    #[serde(default)]
+    pub description: String,
+}
+
+fn empty_object() -> Value {
+    Value::Object(Default::default())
+}
+
+/// Parse an interval (`"15m"`, `"2h"`, `"1d"`) into seconds.
+pub fn parse_interval(every: &str) -> Result<u64, String> {
+    let every = every.trim();
+    let unit = match every.chars().last() {
+        Some('m') => 60,
+        Some('h') => 60 * 60,
+        Some('d') => 24 * 60 * 60,
+        _ => return Err(format!("interval '{}' must end in m, h, or d", every)),
+    };
+    let n: u64 = every[..every.len() - 1]
+        .parse()
+        .map_err(|_| format!("interval '{}' must start with a whole number", every))?;
+    let secs = n.saturating_mul(unit);
+    if !(MIN_INTERVAL_SECS..=MAX_INTERVAL_SECS).contains(&secs) {
+        return Err(format!("interval '{}' must be between 15m and 7d", every));
+    }
+    Ok(secs)
+}
+
+/// Validate manifest tasks (called from `manifest::validate`).
+pub fn validate(tasks: &[ScheduledTask]) -> Result<(), String> {
+    if tasks.len() > MAX_TASKS {
+        return Err(format!("At most {} scheduled tasks are allowed", MAX_TASKS));
+    }
+    for (i, t) in tasks.iter().enumerate() {
+        let id_ok = t.id.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_');
+        if t.id.is_empty() || !id_ok {
+            return Err(format!("tasks[{}]: invalid id '{}'", i, t.id));
+        }
+        if tasks[..i].iter().any(|other| other.id == t.id) {
+            return Err(format!("tasks[{}]: duplicate id '{}'", i, t.id));
+        }
+        if t.tool.trim().is_empty() || !t.arguments.is_object() {
+            return Err(format!("tasks.{}: needs a 'tool' and object 'arguments'", t.id));
+        }
+        parse_interval(&t.every).map_err(|e| format!("tasks.{}: {}", t.id, e))?;
+    }
+    Ok(())
+}
+
+/// Tasks of loaded plugins, keyed by plugin_id.
+static REGISTERED: LazyLock<Mutex<BTreeMap<String, Vec<ScheduledTask>>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Store a plugin's tasks when it loads.
+pub fn register(plugin_id: &str, tasks: &[ScheduledTask]) {
+    let mut map = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+    if tasks.is_empty() {
+        map.remove(plugin_id);
+    } else {
+        map.insert(plugin_id.to_string(), tasks.to_vec());
+    }
+}
+
+/// Drop a plugin's tasks (unload). Approvals are kept for its next load.
+pub fn forget(plugin_id: &str) {
+    REGISTERED.lock().unwrap_or_else(|e| e.into_inner()).remove(plugin_id);
+}
+
+fn registered() -> Vec<(String, ScheduledTask)> {
+    let map = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+    map.iter().flat_map(|(id, tasks)| tasks.iter().map(|t| (id.clone(), t.clone()))).collect()
+}
+
+/// A task as shown in Settings.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    pub plugin_id: String,
+    #[serde(flatten)]
+    pub task: ScheduledTask,
+    pub approved: bool,
+    pub last_run: u64,
+}
+
+/// Tauri command: tasks of loaded plugins with their approval state.
+#[tauri::command]
+pub fn get_scheduled_tasks() -> Vec<TaskInfo> {
+    let store = load_store();
+    registered()
+        .into_iter()
+        .map(|(plugin_id, task)| TaskInfo {
+            approved: store.is_approved(&plugin_id, &task),
+            last_run: store.tasks.get(&task_key(&plugin_id, &task.id)).map_or(0, |s| s.last_run),
+            plugin_id,
+            task,
+        })
+        .collect()
+}
+
+/// Tauri command: approve or stop a scheduled task.
+#[tauri::command]
+pub fn set_task_approved(plugin_id: String, task_id: String, approved: bool) -> Result<(), String> {
+    let task = registered()
+        .into_iter()
+        .find(|(p, t)| *p == plugin_id && t.id == task_id)
+        .map(|(_, t)| t)
+        .ok_or_else(|| format!("No task '{}' for plugin '{}'", task_id, plugin_id))?;
+    update_store(|s| s.set_approved(&plugin_id, &task, approved))?;
+    log::info!("[MCP] Task '{}/{}' {}", plugin_id, task_id, if approved { "approved" } else { "stopped" });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(every: &str) -> ScheduledTask {
+        serde_json::from_value(serde_json::json!({"id": "watch", "tool": "check", "every": every})).unwrap()
+    }
+
+    #[test]
+    fn intervals_are_bounded() {
+        assert_eq!(parse_interval("15m").unwrap(), 900);
+        assert_eq!(parse_interval("2h").unwrap(), 7200);
+        assert_eq!(parse_interval("7d").unwrap(), 604_800);
+        assert!(parse_interval("5m").is_err());
+        assert!(parse_interval("8d").is_err());
+        assert!(parse_interval("1w").is_err());
+        assert!(parse_interval("h").is_err());
+    }
+
+    #[test]
+    fn validation_rejects_bad_tasks() {
+        assert!(validate(&[task("1h")]).is_ok());
+        assert!(validate(&[task("1h"), task("2h")]).is_err());
+        assert!(validate(&[task("1m")]).is_err());
+        let mut bad = task("1h");
+        bad.arguments = serde_json::json!("x");
+        assert!(validate(&[bad]).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Scheduler loop — runs due tasks and reports their results.
+//!
+//! Every minute, each approved task whose interval has elapsed is called
+//! through `run_plugin_tool` (same output safety gates as a snip action).
+//! Tasks run one at a time. The last-run time is saved before the call,
+//! so a failing task waits a full interval before trying again. Successes
+//! become system notifications (Copy / Open, `notifications/`); every
+//! run is added to the history.
+
+use super::history::{self, HistoryEntry};
+use crate::mcp::registry::qualified_name;
+use crate::mcp::{enablement, ToolRegistry};
+use crate::notifications::{self, notice::Notice, Reopen};
+use std::time::Duration;
+use tauri::Manager;
+
+const TICK: Duration = Duration::from_secs(60);
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Start the scheduler loop. Called once from lib.rs after plugins load.
+pub fn spawn_scheduler(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            run_due(&app).await;
+        }
+    });
+}
+
+async fn run_due(app: &tauri::AppHandle) {
+    let now = unix_now();
+    let store = super::load_store();
+    let due: Vec<_> = super::registered()
+        .into_iter()
+        .filter(|(plugin_id, task)| store.is_due(plugin_id, task, now))
+        .filter(|(plugin_id, task)| enablement::is_enabled(plugin_id, &task.tool))
+        .collect();
+
+    for (plugin_id, task) in due {
+        if let Err(e) = super::update_store(|s| s.mark_run(&plugin_id, &task.id, now)) {
+            log::warn!("[MCP] Task '{}/{}' skipped: {}", plugin_id, task.id, e);
+            continue;
+        }
+        log::info!("[MCP] Running scheduled task '{}/{}'", plugin_id, task.id);
+        let registry = app.state::<ToolRegistry>();
+        let qname = qualified_name(&plugin_id, &task.tool);
+        let result = crate::mcp::run_plugin_tool(&registry, &qname, task.arguments.clone()).await;
+        let success = result.status == "success";
+        let text = result.result.text.clone().unwrap_or_default();
+
+        if success {
+            let title = if task.description.is_empty() { task.id.clone() } else { task.description.clone() };
+            let notice = Notice::for_result(title.clone(), &result);
+            notifications::notify(app, notice, Reopen { snip_id: None, snip_text: title, result });
+        } else {
+            log::warn!("[MCP] Task '{}/{}' failed: {}", plugin_id, task.id, text);
+        }
+        history::record(HistoryEntry {
+            plugin_id,
+            task_id: task.id,
+            success,
+            text,
+            timestamp: now,
+        });
+    }
+}
@@ -0,0 +1,122 @@
+//! Scheduled task approvals and last runs, persisted to
+//! `~/.config/omni-glass/scheduled-tasks.json`.
+
+use super::{parse_interval, ScheduledTask};
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Approval and last run of one task.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskState {
+    /// Tool, arguments, and interval the user approved.
+    #[serde(default)]
+    pub tool: String,
+    #[serde(default)]
+    pub arguments: Value,
+    pub every: String,
+    /// Unix epoch seconds; 0 = never ran.
+    #[serde(default)]
+    pub last_run: u64,
+}
+
+/// Approved tasks, keyed by `<plugin_id>/<task_id>`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ScheduleStore {
+    #[serde(default)]
+    pub tasks: BTreeMap<String, TaskState>,
+}
+
+pub(super) fn task_key(plugin_id: &str, task_id: &str) -> String {
+    format!("{}/{}", plugin_id, task_id)
+}
+
+impl ScheduleStore {
+    /// Approved as it is now declared: same tool, arguments, and interval?
+    pub fn is_approved(&self, plugin_id: &str, task: &ScheduledTask) -> bool {
+        self.tasks
+            .get(&task_key(plugin_id, &task.id))
+            .is_some_and(|s| s.tool == task.tool && s.arguments == task.arguments && s.every == task.every)
+    }
+
+    /// Approved and at least one interval since the last run.
+    pub fn is_due(&self, plugin_id: &str, task: &ScheduledTask, now: u64) -> bool {
+        let Ok(interval) = parse_interval(&task.every) else { return false };
+        self.is_approved(plugin_id, task)
+            && self.tasks.get(&task_key(plugin_id, &task.id)).is_some_and(|s| now >= s.last_run + interval)
+    }
+
+    pub fn set_approved(&mut self, plugin_id: &str, task: &ScheduledTask, approved: bool) {
+        let key = task_key(plugin_id, &task.id);
+        if approved {
+            let state = TaskState {
+                tool: task.tool.clone(),
+                arguments: task.arguments.clone(),
+                every: task.every.clone(),
+                last_run: 0,
+            };
+            self.tasks.insert(key, state);
+        } else {
+            self.tasks.remove(&key);
+        }
+    }
+
+    pub fn mark_run(&mut self, plugin_id: &str, task_id: &str, now: u64) {
+        if let Some(state) = self.tasks.get_mut(&task_key(plugin_id, task_id)) {
+            state.last_run = now;
+        }
+    }
+
+    /// Drop all approvals for a plugin (on uninstall).
+    pub fn forget_plugin(&mut self, plugin_id: &str) {
+        let prefix = format!("{}/", plugin_id);
+        self.tasks.retain(|k, _| !k.starts_with(&prefix));
+    }
+}
+
+static STORE: JsonStore<ScheduleStore> = JsonStore::new("scheduled-tasks.json");
+
+pub fn load_store() -> ScheduleStore {
+    STORE.get()
+}
+
+/// Apply `change` to the stored approvals and persist them.
+pub fn update_store(change: impl FnOnce(&mut ScheduleStore)) -> Result<(), String> {
+    STORE.update(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(every: &str) -> ScheduledTask {
+        serde_json::from_value(serde_json::json!({"id": "watch", "tool": "check", "every": every})).unwrap()
+    }
+
+    #[test]
+    fn runs_only_when_approved_for_the_declared_interval() {
+        let mut store = ScheduleStore::default();
+        let hourly = task("1h");
+        assert!(!store.is_due("com.example.a", &hourly, 10_000));
+
+        store.set_approved("com.example.a", &hourly, true);
+        assert!(store.is_due("com.example.a", &hourly, 10_000));
+        store.mark_run("com.example.a", "watch", 10_000);
+        assert!(!store.is_due("com.example.a", &hourly, 10_000 + 3599));
+        assert!(store.is_due("com.example.a", &hourly, 10_000 + 3600));
+
+        // An update asking for a shorter interval or other arguments needs re-approval
+        assert!(!store.is_approved("com.example.a", &task("15m")));
+        let mut changed = task("1h");
+        changed.arguments = serde_json::json!({"url": "https://evil.example"});
+        assert!(!store.is_approved("com.example.a", &changed));
+        changed = task("1h");
+        changed.tool = "delete_all".into();
+        assert!(!store.is_approved("com.example.a", &changed));
+
+        store.forget_plugin("com.example.a");
+        assert!(store.tasks.is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+//! Plugin secrets in the OS keychain.
+//!
+//! Plugins declare the environment variables they need (e.g.
+//! `JIRA_API_TOKEN`). Rather than exporting tokens in the user's shell,
+//! values can be saved here — macOS Keychain, Windows Credential Manager,
+//! or Secret Service on Linux — under the `omni-glass-plugin` service.
+//!
+//! Secrets are read only when a plugin is spawned and go straight into
+//! that child's environment; they are never set on the host process and
+//! never returned to the frontend. A keychain value overrides a shell
+//! variable of the same name.
+
+use crate::error::OmniError;
+use crate::mcp::loader;
+use crate::mcp::manifest::{self, PluginManifest};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Keychain service name for plugin secrets.
+const KEYCHAIN_SERVICE: &str = "omni-glass-plugin";
+
+/// Keychain account for one secret: `<plugin_id>/<KEY>`.
+fn keychain_account(plugin_id: &str, key: &str) -> String {
+    format!("{}/{}", plugin_id, key)
+}
+
+fn entry(plugin_id: &str, key: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &keychain_account(plugin_id, key))
+        .map_err(|e| format!("Keyring error: {}", e))
+}
+
+/// Secrets may only be stored for keys the manifest declares.
+pub fn check_declared(manifest: &PluginManifest, key: &str) -> Result<(), String> {
+    let declared = manifest.permissions.environment.iter().flatten().any(|k| k == key);
+    if declared {
+        Ok(())
+    } else {
+        Err(format!(
+            "Plugin '{}' does not declare environment variable '{}'",
+            manifest.id, key
+        ))
+    }
+}
+
+/// Keychain values for the plugin's declared environment variables.
+/// Missing entries are skipped silently — most keys are not secrets.
+pub fn secrets_for(manifest: &PluginManifest) -> HashMap<String, String> {
+    manifest
+        .permissions
+        .environment
+        .iter()
+        .flatten()
+        .filter_map(|key| {
+            let value = entry(&manifest.id, key).ok()?.get_password().ok()?;
+            (!value.is_empty()).then(|| (key.clone(), value))
+        })
+        .collect()
+}
+
+/// Delete every stored secret for a plugin (on uninstall).
+pub fn forget_plugin(manifest: &PluginManifest) {
+    for key in manifest.permissions.environment.iter().flatten() {
+        if let Ok(e) = entry(&manifest.id, key) {
+            let _ = e.delete_credential();
+        }
+    }
+}
+
+fn installed_manifest(plugin_id: &str) -> Result<PluginManifest, String> {
+    let dir = loader::plugins_dir()
+        .ok_or("Could not determine plugins directory")?
+        .join(plugin_id);
+    manifest::load_manifest(&dir)
+}
+
+/// Whether a declared key has a stored value (values are never exposed).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretStatus {
+    pub key: String,
+    pub is_set: bool,
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: store a plugin secret. An empty value deletes it.
+///
+/// Takes effect the next time the plugin is spawned.
+#[tauri::command]
+pub fn set_plugin_secret(plugin_id: String, key: String, value: String) -> Result<(), OmniError> {
+    let manifest = installed_manifest(&plugin_id).map_err(OmniError::Plugin)?;
+    check_declared(&manifest, &key).map_err(OmniError::Plugin)?;
+    let e = entry(&plugin_id, &key).map_err(OmniError::Plugin)?;
+    if value.is_empty() {
+        match e.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(err) => return Err(OmniError::Plugin(format!("Failed to delete secret: {}", err))),
+        }
+        log::info!("[MCP] Deleted secret '{}' for plugin '{}'", key, plugin_id);
+    } else {
+        e.set_password(&value)
+            .map_err(|err| OmniError::Plugin(format!("Failed to save secret: {}", err)))?;
+        log::info!("[MCP] Saved secret '{}' for plugin '{}'", key, plugin_id);
+    }
+    Ok(())
+}
+
+/// Tauri command: which declared keys have a stored secret.
+#[tauri::command]
+pub fn get_plugin_secret_status(plugin_id: String) -> Result<Vec<SecretStatus>, String> {
+    let manifest = installed_manifest(&plugin_id)?;
+    let stored = secrets_for(&manifest);
+    Ok(manifest
+        .permissions
+        .environment
+        .iter()
+        .flatten()
+        .map(|key| SecretStatus {
+            key: key.clone(),
+            is_set: stored.contains_key(key),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::manifest::{Permissions, Runtime};
+
+    fn manifest(env: Option<Vec<String>>) -> PluginManifest {
+        PluginManifest {
+            id: "com.test.jira".into(),
+            name: "Jira".into(),
+            version: "1.0.0".into(),
+            description: String::new(),
+            runtime: Runtime::Node,
+            entry: "index.js".into(),
+            permissions: Permissions { environment: env, ..Default::default() },
+            configuration: None,
+            extensions: Default::default(),
+        }
+    }
+
+    #[test]
+    fn only_declared_keys_accepted() {
+        let m = manifest(Some(vec!["JIRA_API_TOKEN".into()]));
+        assert!(check_declared(&m, "JIRA_API_TOKEN").is_ok());
+        assert!(check_declared(&m, "AWS_SECRET_ACCESS_KEY").is_err());
+        assert!(check_declared(&manifest(None), "JIRA_API_TOKEN").is_err());
+    }
+
+    #[test]
+    fn account_is_scoped_per_plugin() {
+        assert_eq!(keychain_account("com.test.jira", "TOKEN"), "com.test.jira/TOKEN");
+    }
+}
@@ -0,0 +1,192 @@
+//! Shell adapter — wrap an existing CLI (`jq`, `pandoc`, `gh`) as plugin
+//! tools without writing an MCP server.
+//!
+//! A plugin with `"runtime": "shell"` points `entry` at a TOML file that
+//! maps each tool onto one program invocation (`spec.rs`, filled in by
+//! `template.rs`). The plugin goes
+//! through the same approval, policy, and enablement as any other; each
+//! call spawns the program directly (no shell) with the filtered
+//! environment, under the plugin's sandbox profile on macOS, behind its
+//! network proxy if it declares hosts (which, like paths, needs the
+//! sandbox: without one such an adapter doesn't load). The program must be listed in
+//! `permissions.shell.commands`, and every rendered command line passes
+//! `safety::command_check` before it runs. A non-zero exit is a tool error
+//! carrying the end of stderr.
+
+pub mod spec;
+pub mod template;
+
+use crate::mcp::manifest::PluginManifest;
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::sandbox::{env_filter, net_proxy::NetProxy};
+use crate::mcp::types::{McpTool, ToolResult, ToolResultContent};
+use crate::safety::command_check;
+use spec::{AdapterSpec, ShellTool};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Result text kept per call.
+const MAX_OUTPUT_CHARS: usize = 100_000;
+/// Stderr kept in an error result.
+const MAX_STDERR_CHARS: usize = 2_000;
+
+/// A loaded adapter: parsed spec plus what every invocation needs.
+pub struct ShellAdapter {
+    pub plugin_id: String,
+    spec: AdapterSpec,
+    plugin_dir: PathBuf,
+    env: HashMap<String, String>,
+    /// macOS sandbox profile written at load.
+    profile: Option<PathBuf>,
+}
+
+impl ShellAdapter {
+    /// Parse the adapter TOML and prepare the invocation environment.
+    pub fn load(manifest: &PluginManifest, plugin_dir: &Path, proxy: Option<&NetProxy>) -> Result<Self, String> {
+        let path = plugin_dir.join(&manifest.entry);
+        let raw = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let allowed = manifest.permissions.shell.as_ref().map(|s| s.commands.clone()).unwrap_or_default();
+        let spec = spec::parse(&raw, &allowed)?;
+
+        let mut env = env_filter::filter_environment(&manifest.permissions, &manifest.id);
+        env.extend(crate::mcp::secrets::secrets_for(manifest));
+        crate::mcp::permission_audit::record_env_use(manifest, &env);
+        env.extend(proxy.map(NetProxy::env_vars).unwrap_or_default());
+
+        let profile = sandbox_profile(manifest, plugin_dir, proxy.map(|p| p.port));
+        crate::mcp::permissions::check_enforceable(&manifest.permissions, profile.is_some())?;
+
+        Ok(Self {
+            plugin_id: manifest.id.clone(),
+            spec,
+            plugin_dir: plugin_dir.to_path_buf(),
+            env,
+            profile,
+        })
+    }
+
+    pub fn list_tools(&self) -> Vec<McpTool> {
+        self.spec
+            .tools
+            .iter()
+            .map(|t| McpTool {
+                name: t.name.clone(),
+                description: Some(t.description.clone()),
+                input_schema: Some(spec::input_schema(t)),
+            })
+            .collect()
+    }
+
+    /// Run one tool: render argv, check it, spawn, parse stdout.
+    pub async fn call_tool(&self, name: &str, arguments: &serde_json::Value) -> Result<ToolResult, String> {
+        let tool = self.spec.tools.iter().find(|t| t.name == name).ok_or_else(|| format!("Unknown tool '{}'", name))?;
+        let (argv, stdin) = match template::render_call(tool, arguments) {
+            Ok(rendered) => rendered,
+            Err(e) => return Ok(text_result(e, true)),
+        };
+        let line = template::command_line(&tool.command, &argv);
+        let check = command_check::is_command_safe(&line);
+        if !check.safe {
+            let reason = check.reason.unwrap_or_else(|| "Unknown safety concern".to_string());
+            return Ok(text_result(format!("Command blocked: {}", reason), true));
+        }
+        log::info!("[SANDBOX] '{}' running: {}", self.plugin_id, line);
+        crate::mcp::permission_audit::record_use(&self.plugin_id, &format!("shell:{}", tool.command));
+
+        let output = self.run(tool, &argv, stdin).await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let tail: String = stderr.chars().rev().take(MAX_STDERR_CHARS).collect::<Vec<_>>().into_iter().rev().collect();
+            return Ok(text_result(format!("'{}' failed ({}): {}", tool.command, output.status, tail.trim()), true));
+        }
+        match spec::parse_output(tool, &String::from_utf8_lossy(&output.stdout)) {
+            Ok(text) if text.chars().count() > MAX_OUTPUT_CHARS => {
+                Ok(text_result(text.chars().take(MAX_OUTPUT_CHARS).collect::<String>() + "\n[truncated]", false))
+            }
+            Ok(text) => Ok(text_result(text, false)),
+            Err(e) => Ok(text_result(e, true)),
+        }
+    }
+
+    async fn run(&self, tool: &ShellTool, argv: &[String], stdin: Option<String>) -> Result<std::process::Output, String> {
+        let mut cmd = match self.profile.as_deref() {
+            Some(profile) => {
+                let mut cmd = tokio::process::Command::new("sandbox-exec");
+                cmd.arg("-f").arg(profile).arg(&tool.command);
+                cmd
+            }
+            None => tokio::process::Command::new(&tool.command),
+        };
+        cmd.args(argv)
+            .env_clear()
+            .envs(&self.env)
+            .current_dir(&self.plugin_dir)
+            .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let mut child = cmd.spawn().map_err(|e| format!("Failed to run '{}': {}", tool.command, e))?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            // Write concurrently with reading so large inputs can't deadlock
+            tauri::async_runtime::spawn(async move {
+                let _ = pipe.write_all(input.as_bytes()).await;
+            });
+        }
+        tokio::time::timeout(Duration::from_secs(tool.timeout_secs), child.wait_with_output())
+            .await
+            .map_err(|_| format!("'{}' timed out after {}s", tool.command, tool.timeout_secs))?
+            .map_err(|e| format!("'{}' failed: {}", tool.command, e))
+    }
+}
+
+/// Write the plugin's sandbox profile (macOS). `None` if generation
+/// fails — the calls then run with the filtered env only, unless the
+/// plugin declares hosts or paths (then it doesn't load).
+#[cfg(target_os = "macos")]
+fn sandbox_profile(manifest: &PluginManifest, plugin_dir: &Path, proxy_port: Option<u16>) -> Option<PathBuf> {
+    use crate::mcp::sandbox::macos;
+    match macos::generate_profile(manifest, plugin_dir, proxy_port)
+        .and_then(|profile| macos::write_profile(&manifest.id, &profile))
+    {
+        Ok(path) => Some(path),
+        Err(e) => {
+            log::warn!("[SANDBOX] No profile for adapter '{}': {} — running without sandbox", manifest.id, e);
+            None
+        }
+    }
+}
+
+/// No sandbox profiles off macOS.
+#[cfg(not(target_os = "macos"))]
+fn sandbox_profile(_manifest: &PluginManifest, _plugin_dir: &Path, _proxy_port: Option<u16>) -> Option<PathBuf> {
+    None
+}
+
+fn text_result(text: String, is_error: bool) -> ToolResult {
+    ToolResult {
+        content: vec![ToolResultContent::Text { text }],
+        is_error,
+        meta: None,
+    }
+}
+
+/// Load a `runtime: "shell"` plugin into the registry.
+pub async fn load_shell_plugin(manifest: &PluginManifest, plugin_dir: &Path, registry: &ToolRegistry) -> Result<u32, String> {
+    let proxy = match manifest.permissions.network {
+        Some(ref hosts) if !hosts.is_empty() => Some(NetProxy::start(&manifest.id, hosts.clone()).await?),
+        _ => None,
+    };
+    let adapter = ShellAdapter::load(manifest, plugin_dir, proxy.as_ref())?;
+    let tools = adapter.list_tools();
+    let tool_count = tools.len() as u32;
+    registry.register_plugin_tools(&manifest.id, tools).await;
+    registry.add_shell(manifest.id.clone(), adapter).await;
+    if let Some(proxy) = proxy {
+        registry.add_proxy(manifest.id.clone(), proxy).await;
+    }
+    log::info!("[MCP] Loaded shell adapter '{}' with {} tools", manifest.id, tool_count);
+    Ok(tool_count)
+}
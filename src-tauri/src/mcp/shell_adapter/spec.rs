@@ -0,0 +1,206 @@
+//! Shell adapter spec — the TOML that maps tool calls onto a CLI.
+//!
+//! ```toml
+//! [[tool]]
+//! name = "jq_filter"
+//! description = "Run a jq filter over JSON text"
+//! command = "jq"
+//! args = ["-r{{raw?}}", "{{filter}}"]
+//! stdin = "{{json}}"
+//! output = "text"
+//!
+//! [tool.params.filter]
+//! type = "string"
+//! description = "jq filter, e.g. .items[].name"
+//!
+//! [tool.params.json]
+//! type = "string"
+//!
+//! [tool.params.raw]
+//! type = "boolean"
+//! required = false
+//! ```
+//!
+//! Each `args` element is exactly one argv entry, filled in by
+//! `template.rs`: nothing goes through a shell, and a value can't become
+//! an option of the program. `output` is `text` (trimmed stdout) or `json`
+//! (pretty-printed, optionally narrowed by `json_pointer`).
+
+use super::template::placeholders;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+const MAX_TOOLS: usize = 20;
+const DEFAULT_TIMEOUT_SECS: u64 = 20;
+/// Tool calls are killed after this anyway (`client::TOOL_CALL_TIMEOUT_SECS`).
+const MAX_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdapterSpec {
+    #[serde(default, rename = "tool")]
+    pub tools: Vec<ShellTool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShellTool {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Bare program name; must be declared in `permissions.shell.commands`.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Template written to the program's stdin.
+    #[serde(default)]
+    pub stdin: Option<String>,
+    #[serde(default = "default_output")]
+    pub output: String,
+    /// JSON pointer into parsed `json` output, e.g. `/items`.
+    #[serde(default)]
+    pub json_pointer: Option<String>,
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub params: BTreeMap<String, ShellParam>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShellParam {
+    /// `string` (default), `integer`, `number`, or `boolean`.
+    #[serde(default = "default_param_type", rename = "type")]
+    pub param_type: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_true")]
+    pub required: bool,
+}
+
+fn default_output() -> String {
+    "text".to_string()
+}
+
+fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+fn default_param_type() -> String {
+    "string".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Parse and validate an adapter file. Tools whose command the manifest
+/// doesn't (or no longer) allow are left out with a warning.
+pub fn parse(raw: &str, allowed_commands: &[String]) -> Result<AdapterSpec, String> {
+    let mut spec: AdapterSpec = toml::from_str(raw).map_err(|e| format!("Invalid adapter TOML: {}", e))?;
+    if spec.tools.is_empty() || spec.tools.len() > MAX_TOOLS {
+        return Err(format!("An adapter must define 1 to {} [[tool]] entries", MAX_TOOLS));
+    }
+    for (i, t) in spec.tools.iter().enumerate() {
+        let name_ok = t.name.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_');
+        if t.name.is_empty() || !name_ok {
+            return Err(format!("tool[{}]: invalid name '{}'", i, t.name));
+        }
+        if spec.tools[..i].iter().any(|other| other.name == t.name) {
+            return Err(format!("tool[{}]: duplicate name '{}'", i, t.name));
+        }
+        if !matches!(t.output.as_str(), "text" | "json") || !(1..=MAX_TIMEOUT_SECS).contains(&t.timeout_secs) {
+            return Err(format!("tool.{}: output must be text or json, timeout_secs 1-{}", t.name, MAX_TIMEOUT_SECS));
+        }
+        for (name, p) in &t.params {
+            if !matches!(p.param_type.as_str(), "string" | "integer" | "number" | "boolean") {
+                return Err(format!("tool.{}.params.{}: unknown type '{}'", t.name, name, p.param_type));
+            }
+        }
+        for template in t.args.iter().chain(&t.stdin) {
+            if let Some((name, _)) = placeholders(template).into_iter().find(|(n, _)| !t.params.contains_key(*n)) {
+                return Err(format!("tool.{}: '{{{{{}}}}}' is not a declared param", t.name, name));
+            }
+        }
+    }
+    spec.tools.retain(|t| {
+        let allowed = allowed_commands.contains(&t.command);
+        if !allowed {
+            log::warn!("[SANDBOX] Adapter tool '{}' skipped: '{}' not in permissions.shell.commands", t.name, t.command);
+        }
+        allowed
+    });
+    Ok(spec)
+}
+
+/// JSON Schema advertised for a tool.
+pub fn input_schema(t: &ShellTool) -> Value {
+    let mut properties = Map::new();
+    for (name, p) in &t.params {
+        properties.insert(name.clone(), serde_json::json!({ "type": p.param_type, "description": p.description }));
+    }
+    let required: Vec<&String> = t.params.iter().filter(|(_, p)| p.required).map(|(n, _)| n).collect();
+    serde_json::json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Turn stdout into the tool's text result.
+pub fn parse_output(t: &ShellTool, stdout: &str) -> Result<String, String> {
+    if t.output != "json" {
+        return Ok(stdout.trim().to_string());
+    }
+    let parsed: Value = serde_json::from_str(stdout).map_err(|e| format!("'{}' did not print valid JSON: {}", t.command, e))?;
+    let value = match t.json_pointer.as_deref() {
+        Some(pointer) => parsed.pointer(pointer).ok_or_else(|| format!("No '{}' in the output", pointer))?,
+        None => &parsed,
+    };
+    Ok(match value {
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JQ: &str = r#"
+        [[tool]]
+        name = "jq_filter"
+        command = "jq"
+        args = ["-r{{raw?}}", "{{filter}}"]
+        stdin = "{{json}}"
+
+        [tool.params.filter]
+        [tool.params.json]
+        [tool.params.raw]
+        type = "boolean"
+        required = false
+    "#;
+
+    fn jq() -> ShellTool {
+        parse(JQ, &["jq".to_string()]).unwrap().tools.remove(0)
+    }
+
+    #[test]
+    fn validation_and_permissions() {
+        assert!(parse(JQ, &[]).unwrap().tools.is_empty());
+        let undeclared = "[[tool]]\nname = \"x\"\ncommand = \"jq\"\nargs = [\"{{nope}}\"]";
+        assert!(parse(undeclared, &["jq".to_string()]).unwrap_err().contains("{{nope}}"));
+        assert!(parse("[[tool]]\nname = \"X\"\ncommand = \"jq\"", &[]).is_err());
+        assert!(parse("[[tool]]\nname = \"x\"\ncommand = \"jq\"\ntimeout_secs = 90", &[]).is_err());
+        let schema = input_schema(&jq());
+        assert_eq!(schema["required"], serde_json::json!(["filter", "json"]));
+        assert_eq!(schema["properties"]["raw"]["type"], "boolean");
+    }
+
+    #[test]
+    fn json_output_and_pointer() {
+        let mut t = jq();
+        assert_eq!(parse_output(&t, "  hi\n").unwrap(), "hi");
+        t.output = "json".to_string();
+        t.json_pointer = Some("/items/0".to_string());
+        assert_eq!(parse_output(&t, r#"{"items": ["first"]}"#).unwrap(), "first");
+        assert!(parse_output(&t, r#"{"items": []}"#).is_err());
+        assert!(parse_output(&t, "not json").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! Shell adapter templates — tool arguments into argv and stdin.
+//!
+//! `{{name}}` is replaced by the argument's value. An element is dropped
+//! when it refers to an argument that is missing or `false`; `{{name?}}`
+//! adds no text and only decides whether its element is kept (conditional
+//! flags). A template is filled in one pass, so a value containing
+//! `{{other}}` stays literal text.
+//!
+//! No shell is involved, so values can't inject commands, but a value at
+//! the start of an argv element could still be read as an option
+//! (`-o ~/.zshrc`, `--lua-filter=…`). Such a string value is refused,
+//! unless the spec puts a `--` element before it.
+
+use super::spec::ShellTool;
+use serde_json::Value;
+
+/// A piece of a template: literal text, or a placeholder
+/// `(name, conditional)`.
+enum Piece<'a> {
+    Text(&'a str),
+    Param(&'a str, bool),
+}
+
+fn pieces(template: &str) -> Vec<Piece<'_>> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else { break };
+        out.push(Piece::Text(&rest[..start]));
+        let inner = rest[start + 2..start + 2 + len].trim();
+        out.push(match inner.strip_suffix('?') {
+            Some(name) => Piece::Param(name, true),
+            None => Piece::Param(inner, false),
+        });
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push(Piece::Text(rest));
+    out
+}
+
+/// Placeholders in a template: `(name, conditional)`.
+pub fn placeholders(template: &str) -> Vec<(&str, bool)> {
+    pieces(template)
+        .into_iter()
+        .filter_map(|p| match p {
+            Piece::Param(name, conditional) => Some((name, conditional)),
+            Piece::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Fill one template. `Ok(None)` drops the element. With `guard`, a
+/// string value that would start the element can't start with `-`.
+fn fill(template: &str, arguments: &Value, guard: bool) -> Result<Option<String>, String> {
+    let mut out = String::new();
+    for piece in pieces(template) {
+        let (name, conditional) = match piece {
+            Piece::Text(text) => {
+                out.push_str(text);
+                continue;
+            }
+            Piece::Param(name, conditional) => (name, conditional),
+        };
+        let value = match arguments.get(name) {
+            None | Some(Value::Null) | Some(Value::Bool(false)) => return Ok(None),
+            Some(Value::String(s)) => {
+                if guard && !conditional && out.is_empty() && s.starts_with('-') {
+                    return Err(format!("Argument '{}' can't start with '-': it would be read as an option", name));
+                }
+                s.clone()
+            }
+            Some(other) => other.to_string(),
+        };
+        if !conditional {
+            out.push_str(&value);
+        }
+    }
+    Ok(Some(out))
+}
+
+/// Build argv and stdin for a call. Missing required params, and values
+/// that would become options, are errors.
+pub fn render_call(t: &ShellTool, arguments: &Value) -> Result<(Vec<String>, Option<String>), String> {
+    if let Some((name, _)) = t.params.iter().find(|(n, p)| p.required && arguments.get(n.as_str()).is_none_or(Value::is_null)) {
+        return Err(format!("Missing required argument '{}'", name));
+    }
+    let mut argv = Vec::new();
+    // After `--` the program reads everything as operands
+    let mut after_separator = false;
+    for template in &t.args {
+        if let Some(arg) = fill(template, arguments, !after_separator)? {
+            argv.push(arg);
+        }
+        after_separator |= template == "--";
+    }
+    let stdin = match t.stdin.as_deref() {
+        Some(template) => fill(template, arguments, false)?,
+        None => None,
+    };
+    Ok((argv, stdin))
+}
+
+/// The command as one line, for the safety check and logs.
+pub fn command_line(program: &str, argv: &[String]) -> String {
+    let quote = |a: &String| {
+        if !a.is_empty() && a.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:@%+,".contains(c)) {
+            a.clone()
+        } else {
+            format!("'{}'", a.replace('\'', "'\\''"))
+        }
+    };
+    std::iter::once(program.to_string()).chain(argv.iter().map(quote)).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(args: &str) -> ShellTool {
+        let raw = format!(
+            "[[tool]]\nname = \"t\"\ncommand = \"jq\"\nargs = {}\nstdin = \"{{{{json}}}}\"\n\
+             [tool.params.filter]\n[tool.params.json]\n[tool.params.raw]\ntype = \"boolean\"\nrequired = false",
+            args
+        );
+        super::super::spec::parse(&raw, &["jq".to_string()]).unwrap().tools.remove(0)
+    }
+
+    #[test]
+    fn values_fill_single_argv_entries_once() {
+        let jq = tool(r#"["-r{{raw?}}", "{{filter}}"]"#);
+        let args = json!({"filter": ".a; rm -rf ~", "json": "{\"a\":1}", "raw": true});
+        let (argv, stdin) = render_call(&jq, &args).unwrap();
+        assert_eq!(argv, vec!["-r", ".a; rm -rf ~"]);
+        assert_eq!(stdin.as_deref(), Some("{\"a\":1}"));
+        assert_eq!(command_line("jq", &argv), "jq -r '.a; rm -rf ~'");
+
+        let (argv, stdin) = render_call(&jq, &json!({"filter": "{{json}}", "json": "{{filter}}"})).unwrap();
+        assert_eq!((argv, stdin.as_deref()), (vec!["{{json}}".to_string()], Some("{{filter}}")));
+        assert!(render_call(&jq, &json!({"json": "1"})).unwrap_err().contains("'filter'"));
+    }
+
+    #[test]
+    fn values_cannot_become_options() {
+        let args = json!({"filter": "-o/Users/x/.zshrc", "json": "-1"});
+        assert!(render_call(&tool(r#"["{{filter}}"]"#), &args).unwrap_err().contains("'filter'"));
+        let (argv, _) = render_call(&tool(r#"["--", "{{filter}}"]"#), &args).unwrap();
+        assert_eq!(argv, vec!["--", "-o/Users/x/.zshrc"]);
+        let (argv, _) = render_call(&tool(r#"["--arg={{filter}}"]"#), &args).unwrap();
+        assert_eq!(argv, vec!["--arg=-o/Users/x/.zshrc"]);
+    }
+}
@@ -0,0 +1,300 @@
+//! Plugin launcher commands — `/jira PROJ-123`, `/translate fr hello`.
+//!
+//! A manifest may declare slash commands for the text launcher, each bound
+//! to one of its tools:
+//!
+//! ```json
+//! "commands": [{
+//!   "name": "translate", "tool": "translate", "description": "Translate text",
+//!   "args": [
+//!     { "name": "target", "values": ["fr", "de", "es"] },
+//!     { "name": "text", "rest": true }
+//!   ]
+//! }]
+//! ```
+//!
+//! Words after the command fill `args` in order; a `rest` argument takes
+//! the remainder of the line. A matching prefix skips the LLM router
+//! (`pipeline_text.rs`), but the arguments still get the tool's
+//! `inputSchema` check (`llm/args_schema.rs`). A command with no `args`
+//! passes `{ "text": <remainder> }`. `values` are both the allowed choices
+//! and completion hints for the launcher UI.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+
+const MAX_COMMANDS: usize = 20;
+const MAX_ARGS: usize = 8;
+const MAX_NAME_LEN: usize = 32;
+
+/// A launcher command as declared in the manifest.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommand {
+    /// Typed after `/`; lowercase letters, digits, `-` and `_`.
+    pub name: String,
+    /// Tool (by name) the command calls.
+    pub tool: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub args: Vec<CommandArg>,
+}
+
+/// One positional argument.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandArg {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// `string` (default), `integer`, `number`, or `boolean`.
+    #[serde(default = "default_arg_type", rename = "type")]
+    pub arg_type: String,
+    #[serde(default = "default_true")]
+    pub required: bool,
+    /// Takes the rest of the line (last argument only).
+    #[serde(default)]
+    pub rest: bool,
+    /// Allowed values, also offered as completions.
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+fn default_arg_type() -> String {
+    "string".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Completion metadata for the launcher UI.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandInfo {
+    pub plugin_id: String,
+    pub usage: String,
+    #[serde(flatten)]
+    pub command: SlashCommand,
+}
+
+/// Registered commands by name, with the owning plugin.
+static COMMANDS: LazyLock<Mutex<BTreeMap<String, (String, SlashCommand)>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Validate manifest commands (called from `manifest::validate`).
+pub fn validate(commands: &[SlashCommand]) -> Result<(), String> {
+    if commands.len() > MAX_COMMANDS {
+        return Err(format!("At most {} launcher commands are allowed", MAX_COMMANDS));
+    }
+    for (i, c) in commands.iter().enumerate() {
+        let name_ok = c.name.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_');
+        if c.name.is_empty() || c.name.len() > MAX_NAME_LEN || !name_ok {
+            return Err(format!("commands[{}]: invalid name '{}'", i, c.name));
+        }
+        if commands[..i].iter().any(|other| other.name == c.name) {
+            return Err(format!("commands[{}]: duplicate name '{}'", i, c.name));
+        }
+        if c.tool.trim().is_empty() || c.args.len() > MAX_ARGS {
+            return Err(format!("commands.{}: needs a 'tool' and at most {} args", c.name, MAX_ARGS));
+        }
+        for (j, a) in c.args.iter().enumerate() {
+            let at = format!("commands.{}.args.{}", c.name, a.name);
+            if a.name.trim().is_empty() {
+                return Err(format!("commands.{}.args[{}]: name must not be empty", c.name, j));
+            }
+            if !matches!(a.arg_type.as_str(), "string" | "integer" | "number" | "boolean") {
+                return Err(format!("{}: unknown type '{}'", at, a.arg_type));
+            }
+            if a.rest && j + 1 != c.args.len() {
+                return Err(format!("{}: only the last argument may be 'rest'", at));
+            }
+            if a.required && c.args[..j].iter().any(|prev| !prev.required) {
+                return Err(format!("{}: required arguments must come before optional ones", at));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `/translate <target> <text...>` — shown in completions and errors.
+pub fn usage(c: &SlashCommand) -> String {
+    let mut out = format!("/{}", c.name);
+    for a in &c.args {
+        let dots = if a.rest { "..." } else { "" };
+        let (open, close) = if a.required { ('<', '>') } else { ('[', ']') };
+        out.push_str(&format!(" {}{}{}{}", open, a.name, dots, close));
+    }
+    out
+}
+
+/// Split `/name rest of line` into the name and the (trimmed) remainder.
+fn split_invocation(text: &str) -> Option<(&str, &str)> {
+    let line = text.trim().strip_prefix('/')?;
+    let (name, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    Some((name, rest.trim()))
+}
+
+/// Build the tool arguments from the words after the command.
+pub fn parse_args(c: &SlashCommand, rest: &str) -> Result<Value, String> {
+    if c.args.is_empty() {
+        return Ok(serde_json::json!({ "text": rest }));
+    }
+    let mut out = Map::new();
+    let mut remaining = rest;
+    for a in &c.args {
+        remaining = remaining.trim_start();
+        if remaining.is_empty() {
+            if a.required {
+                return Err(format!("Missing <{}> — usage: {}", a.name, usage(c)));
+            }
+            break;
+        }
+        let word = if a.rest {
+            std::mem::take(&mut remaining)
+        } else {
+            let (word, tail) = remaining.split_once(char::is_whitespace).unwrap_or((remaining, ""));
+            remaining = tail;
+            word
+        };
+        if !a.values.is_empty() && !a.values.iter().any(|v| v == word) {
+            return Err(format!("<{}> must be one of: {}", a.name, a.values.join(", ")));
+        }
+        out.insert(a.name.clone(), typed_value(&a.arg_type, word).map_err(|e| format!("<{}> {}", a.name, e))?);
+    }
+    if !remaining.trim().is_empty() {
+        return Err(format!("Too many arguments — usage: {}", usage(c)));
+    }
+    Ok(Value::Object(out))
+}
+
+fn typed_value(arg_type: &str, word: &str) -> Result<Value, String> {
+    match arg_type {
+        "integer" => word.parse::<i64>().map(Value::from).map_err(|_| "must be a whole number".to_string()),
+        "number" => word.parse::<f64>().map(Value::from).map_err(|_| "must be a number".to_string()),
+        "boolean" => match word {
+            "true" | "yes" | "on" => Ok(Value::Bool(true)),
+            "false" | "no" | "off" => Ok(Value::Bool(false)),
+            _ => Err("must be true or false".to_string()),
+        },
+        _ => Ok(Value::String(word.to_string())),
+    }
+}
+
+/// Register a plugin's commands when it loads. A name already taken by
+/// another plugin stays with the first one.
+pub fn register(plugin_id: &str, commands: &[SlashCommand]) {
+    let mut map = COMMANDS.lock().unwrap_or_else(|e| e.into_inner());
+    map.retain(|_, (owner, _)| owner != plugin_id);
+    for c in commands {
+        if let Some((owner, _)) = map.get(&c.name) {
+            log::warn!("[MCP] '{}' command /{} already registered by '{}' — skipped", plugin_id, c.name, owner);
+            continue;
+        }
+        map.insert(c.name.clone(), (plugin_id.to_string(), c.clone()));
+    }
+}
+
+/// Drop a plugin's commands (unload).
+pub fn forget(plugin_id: &str) {
+    COMMANDS.lock().unwrap_or_else(|e| e.into_inner()).retain(|_, (owner, _)| owner != plugin_id);
+}
+
+/// A launcher line routed straight to a plugin tool.
+#[derive(Debug, PartialEq)]
+pub struct Routed {
+    pub plugin_id: String,
+    pub tool: String,
+    pub arguments: Value,
+    pub usage: String,
+}
+
+/// Match launcher input against registered commands. `None` when the line
+/// isn't a known slash command (the LLM router handles it); `Some(Err)`
+/// when it is one but the arguments don't fit.
+pub fn route(text: &str) -> Option<Result<Routed, String>> {
+    let (name, rest) = split_invocation(text)?;
+    let (plugin_id, command) = COMMANDS.lock().unwrap_or_else(|e| e.into_inner()).get(name).cloned()?;
+    Some(parse_args(&command, rest).map(|arguments| Routed { plugin_id, usage: usage(&command), tool: command.tool, arguments }))
+}
+
+/// Tauri command: launcher commands of enabled plugin tools, for completion.
+#[tauri::command]
+pub fn get_launcher_commands() -> Vec<CommandInfo> {
+    let enablement = crate::mcp::enablement::current();
+    let map = COMMANDS.lock().unwrap_or_else(|e| e.into_inner());
+    map.values()
+        .filter(|(plugin_id, c)| enablement.is_enabled(plugin_id, &c.tool))
+        .map(|(plugin_id, c)| CommandInfo { plugin_id: plugin_id.clone(), usage: usage(c), command: c.clone() })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(json: &str) -> SlashCommand {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn translate() -> SlashCommand {
+        command(
+            r#"{"name": "translate", "tool": "translate", "args": [
+                {"name": "target", "values": ["fr", "de"]},
+                {"name": "text", "rest": true}]}"#,
+        )
+    }
+
+    #[test]
+    fn positional_and_rest_args() {
+        let c = translate();
+        assert_eq!(usage(&c), "/translate <target> <text...>");
+        let args = parse_args(&c, "fr  good  morning").unwrap();
+        assert_eq!(args, serde_json::json!({"target": "fr", "text": "good  morning"}));
+        assert!(parse_args(&c, "it ciao").unwrap_err().contains("one of: fr, de"));
+        assert!(parse_args(&c, "fr").unwrap_err().contains("Missing <text>"));
+    }
+
+    #[test]
+    fn typed_and_optional_args() {
+        let c = command(
+            r#"{"name": "top", "tool": "top", "args": [
+                {"name": "count", "type": "integer", "required": false}]}"#,
+        );
+        assert_eq!(parse_args(&c, "").unwrap(), serde_json::json!({}));
+        assert_eq!(parse_args(&c, "5").unwrap(), serde_json::json!({"count": 5}));
+        assert!(parse_args(&c, "five").is_err());
+        assert!(parse_args(&c, "5 6").unwrap_err().contains("Too many"));
+        let bare = command(r#"{"name": "jira", "tool": "get_issue"}"#);
+        assert_eq!(parse_args(&bare, "PROJ-1").unwrap(), serde_json::json!({"text": "PROJ-1"}));
+    }
+
+    #[test]
+    fn validation_rejects_bad_commands() {
+        assert!(validate(&[command(r#"{"name": "Jira", "tool": "t"}"#)]).is_err());
+        assert!(validate(&[command(r#"{"name": "j", "tool": ""}"#)]).is_err());
+        let early_rest = r#"{"name": "x", "tool": "t", "args": [{"name": "a", "rest": true}, {"name": "b"}]}"#;
+        assert!(validate(&[command(early_rest)]).is_err());
+        let bad_order = r#"{"name": "x", "tool": "t", "args": [{"name": "a", "required": false}, {"name": "b"}]}"#;
+        assert!(validate(&[command(bad_order)]).is_err());
+        assert!(validate(&[translate(), translate()]).is_err());
+        assert!(validate(&[translate()]).is_ok());
+    }
+
+    #[test]
+    fn routes_registered_prefixes_only() {
+        register("com.example.slash", &[translate()]);
+        register("com.example.other", &[translate()]);
+        let routed = route("/translate de hallo").unwrap().unwrap();
+        assert_eq!(routed.plugin_id, "com.example.slash");
+        assert!(route("/translate").unwrap().is_err());
+        assert!(route("/unknown x").is_none());
+        assert!(route("translate de x").is_none());
+        forget("com.example.slash");
+        assert!(route("/translate de x").is_none());
+        forget("com.example.other");
+    }
+}
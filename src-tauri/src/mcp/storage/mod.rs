@@ -0,0 +1,249 @@
+//! Plugin-local storage — a host-managed data area per plugin.
+//!
+//! Each plugin gets `~/.config/omni-glass/plugin-data/<plugin_id>/` with a
+//! JSON key-value store (`kv.json`) and a `files/` folder, capped at
+//! `QUOTA_BYTES` in total. Plugins never touch the directory directly:
+//! stdio plugins send `omniglass/storage/*` requests over their MCP
+//! connection (`rpc.rs`), WASM plugins use the `storage_get` /
+//! `storage_set` host imports. History-style plugins therefore need no
+//! filesystem permission at all.
+
+pub mod rpc;
+
+use serde_json::{Map, Value};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Total bytes a plugin may keep (key-value store + files).
+pub const QUOTA_BYTES: u64 = 10 * 1024 * 1024;
+
+const KV_FILE: &str = "kv.json";
+const FILES_DIR: &str = "files";
+const MAX_KEY_LEN: usize = 256;
+
+/// Serializes read-modify-write cycles (WASM calls run on worker threads).
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Root of all plugin data directories.
+pub fn data_root() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("omni-glass").join("plugin-data"))
+}
+
+/// A plugin's storage area. Cheap; does not touch the disk until used.
+pub struct PluginStorage {
+    dir: PathBuf,
+}
+
+impl PluginStorage {
+    pub fn open(plugin_id: &str) -> Result<Self, String> {
+        if !crate::mcp::install::verify::is_safe_plugin_id(plugin_id) {
+            return Err(format!("Invalid plugin id '{}'", plugin_id));
+        }
+        let root = data_root().ok_or("Could not determine data directory")?;
+        Ok(Self::at(root.join(plugin_id)))
+    }
+
+    /// Storage rooted at an explicit directory (tests).
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Bytes currently used.
+    pub fn usage(&self) -> u64 {
+        dir_size(&self.dir)
+    }
+
+    // ── Key-value ───────────────────────────────────────────────────
+
+    fn load_kv(&self) -> Map<String, Value> {
+        std::fs::read_to_string(self.dir.join(KV_FILE))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_kv(&self, kv: &Map<String, Value>) -> Result<(), String> {
+        let raw = serde_json::to_string(kv).map_err(|e| e.to_string())?;
+        self.write_within_quota(&self.dir.join(KV_FILE), raw.as_bytes())
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.load_kv().get(key).cloned()
+    }
+
+    pub fn set(&self, key: &str, value: Value) -> Result<(), String> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(format!("Key must be 1-{} bytes", MAX_KEY_LEN));
+        }
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut kv = self.load_kv();
+        kv.insert(key.to_string(), value);
+        self.save_kv(&kv)
+    }
+
+    /// Returns whether the key existed.
+    pub fn delete(&self, key: &str) -> Result<bool, String> {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut kv = self.load_kv();
+        let existed = kv.remove(key).is_some();
+        if existed {
+            self.save_kv(&kv)?;
+        }
+        Ok(existed)
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.load_kv().keys().cloned().collect()
+    }
+
+    // ── Files ───────────────────────────────────────────────────────
+
+    fn file_path(&self, name: &str) -> Result<PathBuf, String> {
+        Ok(self.dir.join(FILES_DIR).join(safe_relative(name)?))
+    }
+
+    pub fn read_file(&self, name: &str) -> Result<Vec<u8>, String> {
+        let path = self.file_path(name)?;
+        std::fs::read(&path).map_err(|_| format!("No stored file '{}'", name))
+    }
+
+    pub fn write_file(&self, name: &str, bytes: &[u8]) -> Result<(), String> {
+        let path = self.file_path(name)?;
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.write_within_quota(&path, bytes)
+    }
+
+    /// Returns whether the file existed.
+    pub fn delete_file(&self, name: &str) -> Result<bool, String> {
+        let path = self.file_path(name)?;
+        Ok(std::fs::remove_file(path).is_ok())
+    }
+
+    /// Stored file names (relative, `/`-separated), sorted.
+    pub fn list_files(&self) -> Vec<String> {
+        let root = self.dir.join(FILES_DIR);
+        let mut out = Vec::new();
+        collect_files(&root, &root, &mut out);
+        out.sort();
+        out
+    }
+
+    /// Delete everything the plugin stored.
+    pub fn clear(&self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+
+    fn write_within_quota(&self, path: &Path, bytes: &[u8]) -> Result<(), String> {
+        let existing = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        check_quota(self.usage(), existing, bytes.len() as u64, QUOTA_BYTES)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Storage error: {}", e))?;
+        }
+        std::fs::write(path, bytes).map_err(|e| format!("Storage error: {}", e))
+    }
+}
+
+/// Would replacing `existing` bytes with `incoming` bytes exceed `quota`?
+pub fn check_quota(used: u64, existing: u64, incoming: u64, quota: u64) -> Result<(), String> {
+    let after = used.saturating_sub(existing) + incoming;
+    if after > quota {
+        return Err(format!(
+            "Storage quota exceeded: {} of {} bytes would be used",
+            after, quota
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a plugin-supplied file name: relative, no `..`, no root.
+pub fn safe_relative(name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(name);
+    let ok = !name.is_empty()
+        && name.len() <= MAX_KEY_LEN
+        && path.components().all(|c| matches!(c, Component::Normal(_)));
+    if ok {
+        Ok(path.to_path_buf())
+    } else {
+        Err(format!("Invalid file name '{}'", name))
+    }
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(read) = std::fs::read_dir(dir) else { return 0 };
+    read.flatten()
+        .map(|e| match e.metadata() {
+            Ok(m) if m.is_dir() => dir_size(&e.path()),
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) {
+    let Ok(read) = std::fs::read_dir(dir) else { return };
+    for entry in read.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, out);
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            let parts: Vec<_> = rel.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+            out.push(parts.join("/"));
+        }
+    }
+}
+
+/// Tauri command: bytes used and the quota, for the settings panel.
+#[tauri::command]
+pub fn get_plugin_storage_usage(plugin_id: String) -> Result<serde_json::Value, String> {
+    let storage = PluginStorage::open(&plugin_id)?;
+    Ok(serde_json::json!({ "usedBytes": storage.usage(), "quotaBytes": QUOTA_BYTES }))
+}
+
+/// Tauri command: wipe a plugin's stored data.
+#[tauri::command]
+pub fn clear_plugin_storage(plugin_id: String) -> Result<(), String> {
+    PluginStorage::open(&plugin_id)?.clear();
+    log::info!("[MCP] Cleared storage for '{}'", plugin_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_storage(name: &str) -> PluginStorage {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        PluginStorage::at(dir)
+    }
+
+    #[test]
+    fn kv_round_trip() {
+        let s = temp_storage("og-storage-kv");
+        s.set("history", serde_json::json!(["a", "b"])).unwrap();
+        assert_eq!(s.get("history"), Some(serde_json::json!(["a", "b"])));
+        assert_eq!(s.keys(), vec!["history"]);
+        assert!(s.delete("history").unwrap());
+        assert_eq!(s.get("history"), None);
+        s.clear();
+    }
+
+    #[test]
+    fn files_stay_inside_storage() {
+        let s = temp_storage("og-storage-files");
+        s.write_file("notes/today.md", b"hello").unwrap();
+        assert_eq!(s.read_file("notes/today.md").unwrap(), b"hello");
+        assert_eq!(s.list_files(), vec!["notes/today.md"]);
+        assert!(s.write_file("../escape", b"x").is_err());
+        assert!(s.write_file("/etc/passwd", b"x").is_err());
+        s.clear();
+    }
+
+    #[test]
+    fn quota_counts_replaced_bytes() {
+        assert!(check_quota(90, 0, 10, 100).is_ok());
+        assert!(check_quota(90, 0, 11, 100).is_err());
+        // Overwriting a 50-byte file with 60 bytes only adds 10
+        assert!(check_quota(90, 50, 60, 100).is_ok());
+    }
+}
@@ -0,0 +1,95 @@
+//! Storage requests from stdio plugins (routed by `mcp/host_rpc.rs`).
+//!
+//! Methods (params are objects):
+//!
+//! | Method | Params | Result |
+//! |---|---|---|
+//! | `omniglass/storage/get` | `key` | `{value}` (`null` if missing) |
+//! | `omniglass/storage/set` | `key`, `value` (any JSON) | `{}` |
+//! | `omniglass/storage/delete` | `key` | `{existed}` |
+//! | `omniglass/storage/keys` | — | `{keys}` |
+//! | `omniglass/storage/readFile` | `name` | `{content, encoding}` |
+//! | `omniglass/storage/writeFile` | `name`, `content`, `encoding?` | `{}` |
+//! | `omniglass/storage/deleteFile` | `name` | `{existed}` |
+//! | `omniglass/storage/listFiles` | — | `{files}` |
+//! | `omniglass/storage/usage` | — | `{usedBytes, quotaBytes}` |
+//!
+//! `encoding` is `"utf8"` (default) or `"base64"`.
+
+use super::{PluginStorage, QUOTA_BYTES};
+use crate::mcp::host_rpc::{RpcError, INVALID_PARAMS, METHOD_NOT_FOUND, SERVER_ERROR};
+use base64::Engine;
+use serde_json::{json, Value};
+
+/// Method prefix routed here by `host_rpc`.
+pub const METHOD_PREFIX: &str = "omniglass/storage/";
+
+fn str_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Missing string param '{}'", name)))
+}
+
+/// Run one storage operation. Errors carry a JSON-RPC error code.
+pub fn dispatch(storage: &PluginStorage, op: &str, params: &Value) -> Result<Value, RpcError> {
+    let server = |e: String| (SERVER_ERROR, e);
+    Ok(match op {
+        "get" => json!({ "value": storage.get(str_param(params, "key")?) }),
+        "set" => {
+            let value = params.get("value").cloned().unwrap_or(Value::Null);
+            storage.set(str_param(params, "key")?, value).map_err(server)?;
+            json!({})
+        }
+        "delete" => json!({ "existed": storage.delete(str_param(params, "key")?).map_err(server)? }),
+        "keys" => json!({ "keys": storage.keys() }),
+        "readFile" => {
+            let bytes = storage.read_file(str_param(params, "name")?).map_err(server)?;
+            match String::from_utf8(bytes) {
+                Ok(text) => json!({ "content": text, "encoding": "utf8" }),
+                Err(e) => {
+                    let b64 = base64::engine::general_purpose::STANDARD.encode(e.into_bytes());
+                    json!({ "content": b64, "encoding": "base64" })
+                }
+            }
+        }
+        "writeFile" => {
+            let content = str_param(params, "content")?;
+            let bytes = match params.get("encoding").and_then(Value::as_str).unwrap_or("utf8") {
+                "utf8" => content.as_bytes().to_vec(),
+                "base64" => base64::engine::general_purpose::STANDARD
+                    .decode(content)
+                    .map_err(|e| (INVALID_PARAMS, format!("Bad base64 content: {}", e)))?,
+                other => return Err((INVALID_PARAMS, format!("Unknown encoding '{}'", other))),
+            };
+            storage.write_file(str_param(params, "name")?, &bytes).map_err(server)?;
+            json!({})
+        }
+        "deleteFile" => {
+            json!({ "existed": storage.delete_file(str_param(params, "name")?).map_err(server)? })
+        }
+        "listFiles" => json!({ "files": storage.list_files() }),
+        "usage" => json!({ "usedBytes": storage.usage(), "quotaBytes": QUOTA_BYTES }),
+        other => {
+            return Err((METHOD_NOT_FOUND, format!("Unknown storage method '{}'", other)));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatch_round_trips_binary_files() {
+        let dir = std::env::temp_dir().join("og-storage-rpc");
+        let _ = std::fs::remove_dir_all(&dir);
+        let storage = PluginStorage::at(dir);
+        let params = json!({ "name": "blob.bin", "content": "/w==", "encoding": "base64" });
+        dispatch(&storage, "writeFile", &params).unwrap();
+        let read = dispatch(&storage, "readFile", &json!({ "name": "blob.bin" })).unwrap();
+        assert_eq!(read, json!({ "content": "/w==", "encoding": "base64" }));
+        assert_eq!(dispatch(&storage, "get", &json!({})).unwrap_err().0, INVALID_PARAMS);
+        storage.clear();
+    }
+}
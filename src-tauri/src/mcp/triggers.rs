@@ -0,0 +1,272 @@
+//! Plugin triggers — only advertise a tool when the snip looks relevant.
+//!
+//! A manifest may declare `triggers` at plugin level and/or per tool:
+//!
+//! ```json
+//! "triggers": {
+//!   "contentTypes": ["table"],
+//!   "patterns": ["\\b1Z[0-9A-Z]{16}\\b"],
+//!   "tools": { "lookup_isbn": { "patterns": ["\\b97[89][0-9]{10}\\b"] } }
+//! }
+//! ```
+//!
+//! A tool is offered when any listed content type is detected OR any
+//! pattern matches the OCR text. A per-tool entry replaces the plugin-level
+//! one. No triggers = always offered. Content types are detected locally
+//! (before the classify call) so the prompt only carries relevant tools.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+
+/// Content types a trigger may name (same set as the CLASSIFY prompt).
+pub const CONTENT_TYPES: &[&str] = &[
+    "error", "code", "table", "prose", "list", "kv_pairs", "math", "url", "datetime", "mixed",
+    "unknown",
+];
+
+const MAX_PATTERNS: usize = 20;
+const MAX_PATTERN_LEN: usize = 500;
+/// Compiled-size cap so a manifest pattern can't blow up memory.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Trigger conditions as declared in the manifest.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Triggers {
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    /// Regexes matched against the OCR text.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Per-tool overrides, keyed by tool name.
+    #[serde(default)]
+    pub tools: BTreeMap<String, Triggers>,
+}
+
+/// Validate manifest triggers (called from `manifest::validate`).
+pub fn validate(triggers: &Option<Triggers>) -> Result<(), String> {
+    let Some(t) = triggers else { return Ok(()) };
+    validate_rule(t, "triggers")?;
+    for (tool, rule) in &t.tools {
+        if !rule.tools.is_empty() {
+            return Err(format!("triggers.tools.{} must not nest 'tools'", tool));
+        }
+        validate_rule(rule, &format!("triggers.tools.{}", tool))?;
+    }
+    Ok(())
+}
+
+fn validate_rule(t: &Triggers, at: &str) -> Result<(), String> {
+    if let Some(ct) = t.content_types.iter().find(|c| !CONTENT_TYPES.contains(&c.as_str())) {
+        return Err(format!("{}: unknown content type '{}'", at, ct));
+    }
+    if t.patterns.len() > MAX_PATTERNS {
+        return Err(format!("{}: at most {} patterns", at, MAX_PATTERNS));
+    }
+    for p in &t.patterns {
+        if p.len() > MAX_PATTERN_LEN {
+            return Err(format!("{}: pattern longer than {} chars", at, MAX_PATTERN_LEN));
+        }
+        compile(p).map_err(|e| format!("{}: invalid pattern '{}': {}", at, p, e))?;
+    }
+    Ok(())
+}
+
+fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).size_limit(REGEX_SIZE_LIMIT).build()
+}
+
+/// What is known about a snip before classification.
+#[derive(Debug, Clone, Default)]
+pub struct SnipContext {
+    pub text: String,
+    pub content_types: Vec<&'static str>,
+}
+
+impl SnipContext {
+    /// `has_table` / `has_code` come from the OCR heuristics.
+    pub fn new(text: &str, has_table: bool, has_code: bool) -> Self {
+        Self {
+            text: text.to_string(),
+            content_types: detect_content_types(text, has_table, has_code),
+        }
+    }
+}
+
+static ERROR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(error|exception|traceback|panic(ked)?|fatal|failed|segmentation fault)\b|\b[45]\d\d (bad|not|internal|unauthorized|forbidden)").unwrap()
+});
+static URL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bhttps?://\S+|\bwww\.\S+\.\S+").unwrap());
+static DATETIME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"(?i)\b(jan|feb|mar|apr|may|jun|jul|aug|sept?|oct|nov|dec)[a-z]*\.?\s+\d{1,2}(st|nd|rd|th)?\b",
+        r"|\b\d{1,2}(st|nd|rd|th)?\s+(jan|feb|mar|apr|may|jun|jul|aug|sept?|oct|nov|dec)[a-z]*\b",
+        r"|\b\d{4}-\d{2}-\d{2}\b|\b\d{1,2}/\d{1,2}/\d{2,4}\b",
+        r"|\b(mon|tue|wed|thu|fri|sat|sun)[a-z]*\b.{0,20}\b\d{1,2}(:\d{2})?\s?[ap]\.?m\b",
+    ))
+    .unwrap()
+});
+static LIST_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*([-*•◦▪]|\d{1,3}[.)]|\[[ xX]\])\s+\S").unwrap());
+static KV_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*[\p{L}][\p{L}\d /#&().-]{0,30}:\s+\S").unwrap());
+static MATH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d\s*[+*/^×÷=]\s*[\d(]|\b\w\s*=\s*[\d(]|[∑∫√π≤≥≠]").unwrap());
+
+/// Cheap local guess at the content types present in `text`.
+///
+/// Unlike the CLASSIFY result this may return several types; a trigger
+/// matches if any of them is listed.
+pub fn detect_content_types(text: &str, has_table: bool, has_code: bool) -> Vec<&'static str> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return vec!["unknown"];
+    }
+    let lines: Vec<&str> = trimmed.lines().filter(|l| !l.trim().is_empty()).collect();
+    let count = |re: &Regex| lines.iter().filter(|l| re.is_match(l)).count();
+
+    let mut found = Vec::new();
+    if ERROR_RE.is_match(trimmed) {
+        found.push("error");
+    }
+    if has_code {
+        found.push("code");
+    }
+    if has_table {
+        found.push("table");
+    }
+    if count(&LIST_RE) >= 2 {
+        found.push("list");
+    }
+    if count(&KV_RE) >= 2 {
+        found.push("kv_pairs");
+    }
+    if MATH_RE.is_match(trimmed) && !has_code {
+        found.push("math");
+    }
+    if URL_RE.is_match(trimmed) {
+        found.push("url");
+    }
+    if DATETIME_RE.is_match(trimmed) {
+        found.push("datetime");
+    }
+    if found.is_empty() && trimmed.split_whitespace().count() >= 5 {
+        found.push("prose");
+    }
+    if found.len() > 1 {
+        found.push("mixed");
+    }
+    found
+}
+
+/// A trigger rule with its patterns compiled.
+#[derive(Default)]
+struct Rule {
+    content_types: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl Rule {
+    fn new(t: &Triggers) -> Self {
+        Self {
+            content_types: t.content_types.clone(),
+            // Validated at manifest load; anything that fails here is skipped
+            patterns: t.patterns.iter().filter_map(|p| compile(p).ok()).collect(),
+        }
+    }
+
+    fn matches(&self, snip: &SnipContext) -> bool {
+        if self.content_types.is_empty() && self.patterns.is_empty() {
+            return true;
+        }
+        self.content_types.iter().any(|c| snip.content_types.contains(&c.as_str()))
+            || self.patterns.iter().any(|re| re.is_match(&snip.text))
+    }
+}
+
+#[derive(Default)]
+struct PluginRules {
+    plugin: Rule,
+    tools: HashMap<String, Rule>,
+}
+
+static RULES: LazyLock<Mutex<HashMap<String, PluginRules>>> = LazyLock::new(Default::default);
+
+/// Compile and store a plugin's triggers (called when it loads).
+pub fn register(plugin_id: &str, triggers: &Option<Triggers>) {
+    let mut rules = RULES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(t) = triggers else {
+        rules.remove(plugin_id);
+        return;
+    };
+    let compiled = PluginRules {
+        plugin: Rule::new(t),
+        tools: t.tools.iter().map(|(name, r)| (name.clone(), Rule::new(r))).collect(),
+    };
+    rules.insert(plugin_id.to_string(), compiled);
+}
+
+/// Drop a plugin's triggers (unload).
+pub fn forget(plugin_id: &str) {
+    RULES.lock().unwrap_or_else(|e| e.into_inner()).remove(plugin_id);
+}
+
+/// Should this tool be advertised for this snip?
+pub fn is_offered(plugin_id: &str, tool: &str, snip: &SnipContext) -> bool {
+    let rules = RULES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(plugin) = rules.get(plugin_id) else { return true };
+    plugin.tools.get(tool).unwrap_or(&plugin.plugin).matches(snip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triggers(json: &str) -> Option<Triggers> {
+        Some(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn detects_common_types() {
+        let err = "Traceback (most recent call last):\n  File \"a.py\", line 1";
+        assert!(detect_content_types(err, false, false).contains(&"error"));
+        assert!(detect_content_types("see https://example.com/x", false, false).contains(&"url"));
+        let invite = "Design review — Tuesday, March 3rd at 2pm PST";
+        assert!(detect_content_types(invite, false, false).contains(&"datetime"));
+        assert!(detect_content_types("Due 2026-03-03", false, false).contains(&"datetime"));
+        assert!(!detect_content_types("The quick brown fox may jump", false, false).contains(&"datetime"));
+        assert!(detect_content_types("- milk\n- eggs\n- bread", false, false).contains(&"list"));
+        assert!(detect_content_types("Name: Ada\nEmail: a@b.c", false, false).contains(&"kv_pairs"));
+        assert_eq!(detect_content_types("  ", false, false), vec!["unknown"]);
+        let prose = "The quick brown fox jumps over the lazy dog.";
+        assert_eq!(detect_content_types(prose, false, false), vec!["prose"]);
+    }
+
+    #[test]
+    fn validation_rejects_bad_triggers() {
+        assert!(validate(&triggers(r#"{"contentTypes": ["spreadsheet"]}"#)).is_err());
+        assert!(validate(&triggers(r#"{"patterns": ["(unclosed"]}"#)).is_err());
+        assert!(validate(&triggers(r#"{"tools": {"a": {"tools": {"b": {}}}}}"#)).is_err());
+        assert!(validate(&triggers(r#"{"contentTypes": ["url"], "patterns": ["\\d+"]}"#)).is_ok());
+        assert!(validate(&None).is_ok());
+    }
+
+    #[test]
+    fn matches_any_condition_with_tool_override() {
+        let t = triggers(
+            r#"{"contentTypes": ["table"],
+                "tools": {"lookup_isbn": {"patterns": ["\\b97[89]\\d{10}\\b"]}}}"#,
+        );
+        register("com.example.triggers", &t);
+        let isbn = SnipContext::new("ISBN 9780262033848", false, false);
+        let table = SnipContext::new("a | b\n1 | 2", true, false);
+
+        assert!(is_offered("com.example.triggers", "lookup_isbn", &isbn));
+        assert!(!is_offered("com.example.triggers", "lookup_isbn", &table));
+        assert!(is_offered("com.example.triggers", "export", &table));
+        assert!(!is_offered("com.example.triggers", "export", &isbn));
+        assert!(is_offered("com.example.other", "anything", &isbn));
+
+        forget("com.example.triggers");
+        assert!(is_offered("com.example.triggers", "export", &isbn));
+    }
+}
@@ -5,6 +5,7 @@
 //! - JSON-RPC request/response/notification framing
 //! - Tool discovery (tools/list)
 //! - Tool execution (tools/call)
+//! - Resources (resources/list, resources/read) and prompts (prompts/list)
 //! - Initialize handshake
 
 use serde::{Deserialize, Serialize};
@@ -82,9 +83,22 @@ pub struct ClientInfo {
     pub version: String,
 }
 
-/// Capabilities the client declares (currently empty — we're a basic client).
+/// Capabilities the client declares. The only extension is the host
+/// storage API (`mcp/storage/rpc.rs`), advertised under `experimental`.
 #[derive(Debug, Serialize)]
-pub struct ClientCapabilities {}
+pub struct ClientCapabilities {
+    pub experimental: serde_json::Value,
+}
+
+impl Default for ClientCapabilities {
+    fn default() -> Self {
+        Self {
+            experimental: serde_json::json!({
+                "omniglass/storage": { "quotaBytes": crate::mcp::storage::QUOTA_BYTES }
+            }),
+        }
+    }
+}
 
 /// Parameters for the `initialize` request.
 #[derive(Debug, Serialize)]
@@ -102,6 +116,49 @@ pub struct ServerInfo {
     pub version: Option<String>,
 }
 
+/// Server capabilities from the initialize result. Only the presence of
+/// `resources` / `prompts` matters to the host.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ServerCapabilities {
+    #[serde(default)]
+    pub resources: Option<serde_json::Value>,
+    #[serde(default)]
+    pub prompts: Option<serde_json::Value>,
+}
+
+// ── MCP resources + prompts ─────────────────────────────────────────
+
+/// A resource exposed by an MCP server (from resources/list).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResource {
+    pub uri: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// A prompt template exposed by an MCP server (from prompts/list).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpPrompt {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpPromptArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
 // ── MCP tools ───────────────────────────────────────────────────────
 
 /// A tool exposed by an MCP server (from tools/list response).
@@ -122,6 +179,10 @@ pub struct ToolResult {
     pub content: Vec<ToolResultContent>,
     #[serde(default)]
     pub is_error: bool,
+    /// Result metadata; `omniglass/result` holds a typed result for the
+    /// rendering contract (see `render.rs`).
+    #[serde(default, rename = "_meta")]
+    pub meta: Option<serde_json::Value>,
 }
 
 /// A single content block in a tool result.
@@ -130,6 +191,12 @@ pub struct ToolResult {
 pub enum ToolResultContent {
     #[serde(rename = "text")]
     Text { text: String },
+    #[serde(rename = "image")]
+    Image {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
     #[serde(other)]
     Unknown,
 }
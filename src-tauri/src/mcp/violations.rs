@@ -0,0 +1,80 @@
+//! Permission violation log — surfaces sandbox denials to the user.
+//!
+//! The network proxy and the stderr watcher report here whenever a plugin
+//! tries to reach an undeclared host or touch an undeclared path, and the
+//! clipboard broker reports plugins that never declared `clipboard`. Each
+//! violation is logged, kept in a bounded in-memory list for the settings
+//! panel, and emitted to the frontend as `plugin-permission-violation`.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+/// Maximum violations kept in memory (oldest dropped first).
+const MAX_VIOLATIONS: usize = 200;
+
+/// Which permission the plugin exceeded.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationKind {
+    Network,
+    Filesystem,
+    Clipboard,
+}
+
+/// A single denied access attempt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionViolation {
+    pub plugin_id: String,
+    pub kind: ViolationKind,
+    /// Host or path the plugin tried to reach.
+    pub target: String,
+    /// Unix epoch seconds.
+    pub timestamp: u64,
+}
+
+static LOG: Mutex<VecDeque<PermissionViolation>> = Mutex::new(VecDeque::new());
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Give the log an app handle so violations can be emitted as events.
+/// Called once from lib.rs `.setup()`.
+pub fn attach(app: tauri::AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// Record a violation and notify the frontend.
+pub fn report(plugin_id: &str, kind: ViolationKind, target: &str) {
+    let violation = PermissionViolation {
+        plugin_id: plugin_id.to_string(),
+        kind,
+        target: target.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    log::warn!(
+        "[SANDBOX] Plugin '{}' denied {:?} access to '{}'",
+        plugin_id,
+        kind,
+        target
+    );
+
+    if let Ok(mut log) = LOG.lock() {
+        if log.len() >= MAX_VIOLATIONS {
+            log.pop_front();
+        }
+        log.push_back(violation.clone());
+    }
+    if let Some(app) = APP.get() {
+        let _ = app.emit("plugin-permission-violation", &violation);
+    }
+}
+
+/// Tauri command: recent permission violations, newest last.
+#[tauri::command]
+pub fn get_permission_violations() -> Vec<PermissionViolation> {
+    LOG.lock().map(|log| log.iter().cloned().collect()).unwrap_or_default()
+}
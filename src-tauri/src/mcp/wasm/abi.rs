@@ -0,0 +1,165 @@
+//! WASM plugin ABI — the contract between host and guest module.
+//!
+//! Guest exports:
+//! - `memory` — linear memory
+//! - `alloc(len: i32) -> i32` — reserve `len` bytes for host input
+//! - `tools() -> i64` — JSON array of MCP tools
+//! - `call(name_ptr, name_len, args_ptr, args_len) -> i64` — JSON MCP tool
+//!   result (or plain text)
+//!
+//! Host imports (module `omni_glass`) — the ONLY capabilities a guest has:
+//! - `log(ptr: i32, len: i32)` — write a line to the host log
+//! - `env_get(ptr: i32, len: i32) -> i64` — read a DECLARED environment variable
+//! - `storage_get(key_ptr, key_len) -> i64` — JSON value from the plugin's
+//!   storage area (`mcp/storage`)
+//! - `storage_set(key_ptr, key_len, val_ptr, val_len) -> i32` — store a JSON
+//!   value (empty deletes); `0` ok, `-1` invalid JSON or quota exceeded
+//!
+//! Strings are UTF-8. An `i64` return packs a guest pointer and length as
+//! `(ptr << 32) | len`; `0` means "no value". Every pointer and length a
+//! guest hands over is checked against its memory and a size cap
+//! (`guest_range`) before the host copies anything.
+
+use crate::mcp::manifest::{PluginManifest, Runtime};
+use crate::mcp::types::{McpTool, ToolResult, ToolResultContent};
+use std::ops::Range;
+
+/// Import module name for host functions.
+pub const HOST_MODULE: &str = "omni_glass";
+
+/// Largest argument a host import reads (log line, key, storage value).
+pub const MAX_IMPORT_BYTES: usize = 1024 * 1024;
+
+/// Largest `tools()` or `call()` output the host reads.
+pub const MAX_OUTPUT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Pack a guest pointer and length into one `i64`.
+pub fn pack(ptr: u32, len: u32) -> i64 {
+    (((ptr as u64) << 32) | len as u64) as i64
+}
+
+/// Unpack an `i64` into `(ptr, len)`. Returns `None` for the `0` sentinel.
+pub fn unpack(packed: i64) -> Option<(usize, usize)> {
+    if packed == 0 {
+        return None;
+    }
+    let raw = packed as u64;
+    Some(((raw >> 32) as usize, (raw & 0xFFFF_FFFF) as usize))
+}
+
+/// The range `ptr..ptr + len` of a guest memory of `memory_size` bytes,
+/// if it lies inside it and is at most `cap` long. Both numbers come from
+/// the guest, so this runs before any buffer is allocated.
+pub fn guest_range(ptr: i64, len: i64, memory_size: usize, cap: usize) -> Option<Range<usize>> {
+    let (start, len) = (usize::try_from(ptr).ok()?, usize::try_from(len).ok()?);
+    let end = start.checked_add(len)?;
+    (len <= cap && end <= memory_size).then_some(start..end)
+}
+
+/// Parse the guest's `tools()` output.
+pub fn parse_tools(json: &[u8]) -> Result<Vec<McpTool>, String> {
+    serde_json::from_slice(json).map_err(|e| format!("Bad tools() output: {}", e))
+}
+
+/// Parse the guest's `call()` output. A JSON tool result is used as-is;
+/// anything else is treated as a plain-text success.
+pub fn parse_call_output(bytes: &[u8]) -> ToolResult {
+    if let Ok(result) = serde_json::from_slice::<ToolResult>(bytes) {
+        return result;
+    }
+    ToolResult {
+        content: vec![ToolResultContent::Text {
+            text: String::from_utf8_lossy(bytes).into_owned(),
+        }],
+        is_error: false,
+        meta: None,
+    }
+}
+
+/// WASM guests only get the imports above, so manifests must not ask
+/// for capabilities the host cannot provide.
+pub fn validate_manifest(m: &PluginManifest) -> Result<(), String> {
+    if m.runtime != Runtime::Wasm {
+        return Ok(());
+    }
+    if !m.entry.ends_with(".wasm") {
+        return Err(format!("WASM plugin entry '{}' must be a .wasm file", m.entry));
+    }
+    let p = &m.permissions;
+    let wants_network = p.network.as_ref().is_some_and(|n| !n.is_empty());
+    let wants_fs = p.filesystem.as_ref().is_some_and(|f| !f.is_empty());
+    if p.clipboard || wants_network || wants_fs || p.shell.is_some() {
+        return Err(
+            "WASM plugins may only declare environment permissions — \
+             no clipboard, network, filesystem, or shell access is available"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::manifest::Permissions;
+
+    fn manifest(entry: &str, permissions: Permissions) -> PluginManifest {
+        PluginManifest {
+            id: "com.test.wasm".into(),
+            name: "Wasm".into(),
+            version: "1.0.0".into(),
+            description: String::new(),
+            runtime: Runtime::Wasm,
+            entry: entry.into(),
+            permissions,
+            configuration: None,
+            extensions: Default::default(),
+        }
+    }
+
+    #[test]
+    fn pack_round_trips() {
+        assert_eq!(unpack(pack(1024, 17)), Some((1024, 17)));
+        assert_eq!(unpack(pack(u32::MAX, u32::MAX)), Some((u32::MAX as usize, u32::MAX as usize)));
+        assert_eq!(unpack(0), None);
+    }
+
+    #[test]
+    fn guest_ranges_stay_inside_memory_and_the_cap() {
+        assert_eq!(guest_range(16, 4, 64, 8), Some(16..20));
+        assert_eq!(guest_range(60, 4, 64, 8), Some(60..64));
+        assert_eq!(guest_range(62, 4, 64, 8), None);
+        assert_eq!(guest_range(0, 9, 64, 8), None);
+        assert_eq!(guest_range(-1, 4, 64, 8), None);
+        assert_eq!(guest_range(0, -4, 64, 8), None);
+        assert_eq!(guest_range(i64::MAX, 1, 64, 8), None);
+    }
+
+    #[test]
+    fn parses_json_and_plain_text_results() {
+        let json = br#"{"content":[{"type":"text","text":"hi"}],"isError":true}"#;
+        let r = parse_call_output(json);
+        assert!(r.is_error);
+        assert_eq!(r.text(), "hi");
+        let r = parse_call_output(b"UPPERCASED");
+        assert!(!r.is_error);
+        assert_eq!(r.text(), "UPPERCASED");
+    }
+
+    #[test]
+    fn parses_tool_list() {
+        let tools = parse_tools(br#"[{"name":"upper","description":"Uppercase text"}]"#).unwrap();
+        assert_eq!(tools[0].name, "upper");
+        assert!(parse_tools(b"nope").is_err());
+    }
+
+    #[test]
+    fn rejects_ambient_capabilities() {
+        assert!(validate_manifest(&manifest("plugin.wasm", Permissions::default())).is_ok());
+        let env_only = Permissions { environment: Some(vec!["DICT_PATH".into()]), ..Default::default() };
+        assert!(validate_manifest(&manifest("plugin.wasm", env_only)).is_ok());
+        let net = Permissions { network: Some(vec!["api.example.com".into()]), ..Default::default() };
+        assert!(validate_manifest(&manifest("plugin.wasm", net)).is_err());
+        assert!(validate_manifest(&manifest("plugin.js", Permissions::default())).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! WASM plugin runtime — in-process, capability-sandboxed plugins.
+//!
+//! Simple plugins (text transforms, local lookups) can ship as a single
+//! `.wasm` module instead of a Node/Python/binary process. The module runs
+//! inside wasmtime with no WASI: it has no filesystem, network, clock, or
+//! process access at all — only the host functions listed in `abi.rs`
+//! (logging, declared env vars, and its own `mcp/storage` key-value area).
+//! Any other import fails instantiation.
+//!
+//! Each tool call gets a fresh `Store`, so guests keep no in-memory state
+//! between calls (persistent state goes through `storage_set`), and
+//! fuel + memory limits stop runaway loops and allocations.
+
+pub mod abi;
+
+use crate::mcp::manifest::PluginManifest;
+use crate::mcp::registry::ToolRegistry;
+use crate::mcp::storage::PluginStorage;
+use crate::mcp::types::{McpTool, ToolResult};
+use std::collections::HashMap;
+use std::path::Path;
+use wasmtime::{Caller, Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Instruction budget per call (roughly a second or two of guest work).
+const FUEL_PER_CALL: u64 = 2_000_000_000;
+
+/// Maximum linear memory per instance.
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Per-call host state visible to imports.
+struct HostState {
+    plugin_id: String,
+    /// Only the environment variables the manifest declared.
+    env: HashMap<String, String>,
+    limits: StoreLimits,
+}
+
+/// A compiled WASM plugin. Cheap to clone (engine + module are shared).
+#[derive(Clone)]
+pub struct WasmPlugin {
+    pub plugin_id: String,
+    engine: Engine,
+    module: Module,
+    env: HashMap<String, String>,
+}
+
+impl WasmPlugin {
+    /// Compile the plugin's `.wasm` entry.
+    pub fn load(manifest: &PluginManifest, plugin_dir: &Path) -> Result<Self, String> {
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| e.to_string())?;
+        let module = Module::from_file(&engine, plugin_dir.join(&manifest.entry))
+            .map_err(|e| format!("Failed to compile '{}': {}", manifest.entry, e))?;
+
+        let mut env: HashMap<String, String> = manifest
+            .permissions
+            .environment
+            .iter()
+            .flatten()
+            .filter_map(|key| std::env::var(key).ok().map(|v| (key.clone(), v)))
+            .collect();
+        env.extend(crate::mcp::secrets::secrets_for(manifest));
+
+        Ok(Self {
+            plugin_id: manifest.id.clone(),
+            engine,
+            module,
+            env,
+        })
+    }
+
+    /// Discover tools via the guest's `tools()` export.
+    pub fn list_tools(&self) -> Result<Vec<McpTool>, String> {
+        let (mut store, instance) = self.instantiate()?;
+        let tools = instance
+            .get_typed_func::<(), i64>(&mut store, "tools")
+            .map_err(|e| e.to_string())?;
+        let packed = tools.call(&mut store, ()).map_err(|e| e.to_string())?;
+        abi::parse_tools(&read_packed(&mut store, &instance, packed)?)
+    }
+
+    /// Execute a tool. CPU-bound — callers should run it on a blocking thread.
+    pub fn call_tool(&self, name: &str, arguments: &serde_json::Value) -> Result<ToolResult, String> {
+        let (mut store, instance) = self.instantiate()?;
+        let name_ptr = write_guest(&mut store, &instance, name.as_bytes())?;
+        let args = arguments.to_string();
+        let args_ptr = write_guest(&mut store, &instance, args.as_bytes())?;
+
+        let call = instance
+            .get_typed_func::<(i32, i32, i32, i32), i64>(&mut store, "call")
+            .map_err(|e| e.to_string())?;
+        let packed = call
+            .call(&mut store, (name_ptr, name.len() as i32, args_ptr, args.len() as i32))
+            .map_err(|e| format!("WASM plugin '{}' trapped: {}", self.plugin_id, e))?;
+        Ok(abi::parse_call_output(&read_packed(&mut store, &instance, packed)?))
+    }
+
+    /// Fresh store + instance with only the host imports linked.
+    fn instantiate(&self) -> Result<(Store<HostState>, Instance), String> {
+        let state = HostState {
+            plugin_id: self.plugin_id.clone(),
+            env: self.env.clone(),
+            limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY_BYTES).build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|s| &mut s.limits);
+        store.set_fuel(FUEL_PER_CALL).map_err(|e| e.to_string())?;
+
+        let mut linker: Linker<HostState> = Linker::new(&self.engine);
+        linker
+            .func_wrap(abi::HOST_MODULE, "log", host_log)
+            .map_err(|e| e.to_string())?;
+        linker
+            .func_wrap(abi::HOST_MODULE, "env_get", host_env_get)
+            .map_err(|e| e.to_string())?;
+        linker
+            .func_wrap(abi::HOST_MODULE, "storage_get", host_storage_get)
+            .map_err(|e| e.to_string())?;
+        linker
+            .func_wrap(abi::HOST_MODULE, "storage_set", host_storage_set)
+            .map_err(|e| e.to_string())?;
+
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .map_err(|e| format!("WASM plugin '{}' failed to link: {}", self.plugin_id, e))?;
+        Ok((store, instance))
+    }
+}
+
+/// `log` import: write a guest message to the host log.
+fn host_log(mut caller: Caller<'_, HostState>, ptr: i32, len: i32) {
+    let line = read_caller(&mut caller, ptr, len).unwrap_or_default();
+    log::info!("[WASM] '{}': {}", caller.data().plugin_id, String::from_utf8_lossy(&line));
+}
+
+/// `env_get` import: return a declared env var, or 0 if undeclared/unset.
+fn host_env_get(mut caller: Caller<'_, HostState>, ptr: i32, len: i32) -> i64 {
+    let Some(key) = read_caller(&mut caller, ptr, len) else {
+        return 0;
+    };
+    let key = String::from_utf8_lossy(&key).into_owned();
+    let Some(value) = caller.data().env.get(&key).cloned() else {
+        return 0;
+    };
+    return_to_guest(&mut caller, value.as_bytes())
+}
+
+/// `storage_get` import: the JSON value stored under a key, or 0.
+fn host_storage_get(mut caller: Caller<'_, HostState>, ptr: i32, len: i32) -> i64 {
+    let Some(key) = read_caller(&mut caller, ptr, len) else {
+        return 0;
+    };
+    let key = String::from_utf8_lossy(&key).into_owned();
+    let value = PluginStorage::open(&caller.data().plugin_id).ok().and_then(|s| s.get(&key));
+    match value {
+        Some(v) => return_to_guest(&mut caller, v.to_string().as_bytes()),
+        None => 0,
+    }
+}
+
+/// `storage_set` import: store a JSON value (empty = delete). 0 on
+/// success, -1 on bad input or quota exceeded.
+fn host_storage_set(mut caller: Caller<'_, HostState>, kptr: i32, klen: i32, vptr: i32, vlen: i32) -> i32 {
+    let Some(key) = read_caller(&mut caller, kptr, klen) else {
+        return -1;
+    };
+    let Some(value) = read_caller(&mut caller, vptr, vlen) else {
+        return -1;
+    };
+    let key = String::from_utf8_lossy(&key).into_owned();
+    let Ok(storage) = PluginStorage::open(&caller.data().plugin_id) else {
+        return -1;
+    };
+    let result = if value.is_empty() {
+        storage.delete(&key).map(|_| ())
+    } else {
+        serde_json::from_slice(&value)
+            .map_err(|e| e.to_string())
+            .and_then(|v| storage.set(&key, v))
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            log::warn!("[WASM] '{}' storage_set failed: {}", caller.data().plugin_id, e);
+            -1
+        }
+    }
+}
+
+/// Copy host bytes into guest memory via `alloc`; packed `(ptr, len)` or 0.
+fn return_to_guest(caller: &mut Caller<'_, HostState>, bytes: &[u8]) -> i64 {
+    let Some(alloc) = caller.get_export("alloc").and_then(|e| e.into_func()) else {
+        return 0;
+    };
+    let Ok(alloc) = alloc.typed::<i32, i32>(&*caller) else {
+        return 0;
+    };
+    let Ok(dst) = alloc.call(&mut *caller, bytes.len() as i32) else {
+        return 0;
+    };
+    match caller.get_export("memory").and_then(|e| e.into_memory()) {
+        Some(mem) if mem.write(&mut *caller, dst as usize, bytes).is_ok() => {
+            abi::pack(dst as u32, bytes.len() as u32)
+        }
+        _ => 0,
+    }
+}
+
+/// Copy an import argument out of guest memory; `None` when it is out
+/// of bounds or over `abi::MAX_IMPORT_BYTES`.
+fn read_caller(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    let mem = caller.get_export("memory")?.into_memory()?;
+    let data = mem.data(&*caller);
+    let range = abi::guest_range(ptr.into(), len.into(), data.len(), abi::MAX_IMPORT_BYTES)?;
+    Some(data[range].to_vec())
+}
+
+/// Copy `bytes` into guest memory via its `alloc` export.
+fn write_guest(store: &mut Store<HostState>, instance: &Instance, bytes: &[u8]) -> Result<i32, String> {
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut *store, "alloc")
+        .map_err(|e| e.to_string())?;
+    let ptr = alloc.call(&mut *store, bytes.len() as i32).map_err(|e| e.to_string())?;
+    let memory = instance.get_memory(&mut *store, "memory").ok_or("WASM plugin has no memory export")?;
+    memory.write(&mut *store, ptr as usize, bytes).map_err(|e| e.to_string())?;
+    Ok(ptr)
+}
+
+/// Read a packed `(ptr, len)` result out of guest memory.
+fn read_packed(store: &mut Store<HostState>, instance: &Instance, packed: i64) -> Result<Vec<u8>, String> {
+    let Some((ptr, len)) = abi::unpack(packed) else {
+        return Ok(Vec::new());
+    };
+    let memory = instance.get_memory(&mut *store, "memory").ok_or("WASM plugin has no memory export")?;
+    let data = memory.data(&*store);
+    let range = abi::guest_range(ptr as i64, len as i64, data.len(), abi::MAX_OUTPUT_BYTES)
+        .ok_or("WASM plugin returned an out-of-bounds or oversized result")?;
+    Ok(data[range].to_vec())
+}
+
+/// Compile an approved WASM plugin, discover its tools, and register it.
+pub async fn load_wasm_plugin(
+    manifest: &PluginManifest,
+    plugin_dir: &Path,
+    registry: &ToolRegistry,
+) -> Result<u32, String> {
+    let plugin = WasmPlugin::load(manifest, plugin_dir)?;
+    let tools = plugin.list_tools()?;
+    let tool_count = tools.len() as u32;
+    registry.register_plugin_tools(&manifest.id, tools).await;
+    registry.add_wasm(manifest.id.clone(), plugin).await;
+    log::info!("[WASM] Loaded '{}' with {} tools", manifest.id, tool_count);
+    Ok(tool_count)
+}
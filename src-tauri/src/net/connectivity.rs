@@ -0,0 +1,118 @@
+//! Online / offline state.
+//!
+//! A background monitor opens a TCP connection to the active provider's
+//! API host (or to the proxy, when one applies) every 30 seconds, every
+//! 10 while offline. The result is kept in memory, so the snip pipeline
+//! can tell at once that a cloud call would fail and show a local menu
+//! instead of waiting out an HTTP timeout. The `offlineMode` setting
+//! forces offline regardless of the network.
+//!
+//! Changes are broadcast as `connectivity-changed` with a
+//! `Connectivity` payload.
+
+use super::config;
+use crate::error::{Llm, OmniError};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::Emitter;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+const ONLINE_INTERVAL: Duration = Duration::from_secs(30);
+const OFFLINE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Last probe result. Starts optimistic so the first snip isn't blocked.
+static REACHABLE: AtomicBool = AtomicBool::new(true);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connectivity {
+    /// Cloud calls may be attempted.
+    pub online: bool,
+    /// The network answered the last probe.
+    pub reachable: bool,
+    /// Offline mode is switched on in settings.
+    pub offline_mode: bool,
+}
+
+pub fn status() -> Connectivity {
+    let reachable = REACHABLE.load(Ordering::Relaxed);
+    let offline_mode = crate::settings::current().offline_mode;
+    Connectivity { online: reachable && !offline_mode, reachable, offline_mode }
+}
+
+/// `Err` with the reason (`Llm::OfflineMode` / `Llm::Offline`) when cloud
+/// calls should not be tried.
+pub fn check() -> Result<(), OmniError> {
+    let status = status();
+    if status.offline_mode {
+        return Err(Llm::OfflineMode.into());
+    }
+    if !status.reachable {
+        return Err(Llm::Offline.into());
+    }
+    Ok(())
+}
+
+/// Host the active provider's API lives on.
+fn probe_host() -> &'static str {
+    match crate::settings::current().provider.as_deref() {
+        Some("gemini") => "generativelanguage.googleapis.com",
+        _ => "api.anthropic.com",
+    }
+}
+
+async fn probe() -> bool {
+    let host = probe_host();
+    let (proxy, bypass) = super::proxy();
+    let (host, port) = match proxy.filter(|_| !config::bypassed(host, &bypass)) {
+        Some(p) => (p.host, p.port),
+        None => (host.to_string(), 443),
+    };
+    matches!(
+        tokio::time::timeout(PROBE_TIMEOUT, tokio::net::TcpStream::connect((host.as_str(), port))).await,
+        Ok(Ok(_))
+    )
+}
+
+/// Tell every window the current state.
+pub fn announce(app: &tauri::AppHandle) {
+    let status = status();
+    log::info!("[NET] Connectivity: online={} (reachable={}, offline mode={})", status.online, status.reachable, status.offline_mode);
+    let _ = app.emit("connectivity-changed", &status);
+}
+
+/// Probe now; announce if reachability changed.
+async fn refresh(app: &tauri::AppHandle) {
+    let reachable = probe().await;
+    if REACHABLE.swap(reachable, Ordering::Relaxed) != reachable {
+        announce(app);
+    }
+}
+
+/// Keep probing in the background. Called once from `setup`.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh(&app).await;
+            let interval = if REACHABLE.load(Ordering::Relaxed) { ONLINE_INTERVAL } else { OFFLINE_INTERVAL };
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: current connectivity, re-probed first.
+#[tauri::command]
+pub async fn get_connectivity(app: tauri::AppHandle) -> Connectivity {
+    refresh(&app).await;
+    status()
+}
+
+/// Tauri command: turn offline mode on or off.
+#[tauri::command]
+pub fn set_offline_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    crate::settings::update(&app, serde_json::json!({ "offlineMode": enabled }))?;
+    Ok(())
+}
@@ -0,0 +1,237 @@
+//! Outbound HTTP — proxy and custom CA support for every client.
+//!
+//! All app traffic (LLM providers, plugin downloads and catalog, remote
+//! MCP servers, update checks) goes through clients built here, so the
+//! network preferences in settings (`network`, see `config.rs`) apply
+//! everywhere at once. Sandboxed plugins reach the network through
+//! their allowlist proxy, which chains to the same upstream proxy
+//! (`upstream.rs`).
+//!
+//! The client is built once and rebuilt when network settings change.
+//! The manual proxy's password is kept in the OS keychain. Whether the
+//! network is reachable at all is tracked in `connectivity.rs`.
+
+pub mod config;
+pub mod connectivity;
+pub mod upstream;
+
+use crate::error::{Network, OmniError};
+use config::{NetworkPrefs, ProxyEndpoint, SystemProxy};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const KEYCHAIN_SERVICE: &str = "omni-glass-proxy";
+/// Checked by `test_connectivity` when no URL is given.
+const DEFAULT_TEST_URL: &str = "https://api.anthropic.com";
+const TEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+type ProxyRoute = (Option<ProxyEndpoint>, Vec<String>);
+
+/// Shared client; `None` until first use or after a settings change.
+static CLIENT: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+/// Resolved proxy and bypass list, cached with the client (system
+/// detection runs a subprocess on macOS and Windows).
+static ROUTE: Mutex<Option<ProxyRoute>> = Mutex::new(None);
+/// Bumped by every `reset`, so clients built elsewhere from
+/// `client_builder()` know when to rebuild.
+static EPOCH: AtomicU64 = AtomicU64::new(0);
+
+fn prefs() -> NetworkPrefs {
+    crate::settings::current().network
+}
+
+fn proxy_password(username: Option<&str>) -> Option<String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, username?).ok()?.get_password().ok()
+}
+
+/// Proxy settings from the OS, falling back to `HTTPS_PROXY` / `ALL_PROXY`.
+pub fn system_proxy() -> SystemProxy {
+    let mut system = platform_proxy();
+    let env = |keys: &[&str]| keys.iter().find_map(|k| std::env::var(k).ok().filter(|v| !v.trim().is_empty()));
+    if system.endpoint.is_none() {
+        system.endpoint = env(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"])
+            .and_then(|v| config::parse_proxy_url(&v));
+    }
+    if let Some(list) = env(&["NO_PROXY", "no_proxy"]) {
+        system.no_proxy.extend(list.split(',').map(str::trim).filter(|h| !h.is_empty()).map(String::from));
+    }
+    system
+}
+
+#[cfg(target_os = "macos")]
+fn platform_proxy() -> SystemProxy {
+    std::process::Command::new("scutil")
+        .arg("--proxy")
+        .output()
+        .map(|out| config::parse_scutil(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+fn platform_proxy() -> SystemProxy {
+    let key = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings";
+    let query = |name: &str| {
+        let out = std::process::Command::new("reg").args(["query", key, "/v", name]).output().ok()?;
+        let text = String::from_utf8_lossy(&out.stdout).to_string();
+        text.lines().find_map(|l| l.trim().strip_prefix(name).map(|v| v.split_whitespace().skip(1).collect::<Vec<_>>().join(" ")))
+    };
+    if query("ProxyEnable").as_deref() != Some("0x1") {
+        return SystemProxy::default();
+    }
+    config::parse_windows_proxy(&query("ProxyServer").unwrap_or_default(), &query("ProxyOverride").unwrap_or_default())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_proxy() -> SystemProxy {
+    SystemProxy::default()
+}
+
+/// The upstream proxy for everything not bypassed, and the bypass list.
+pub fn proxy() -> ProxyRoute {
+    let mut guard = ROUTE.lock().unwrap_or_else(|e| e.into_inner());
+    guard
+        .get_or_insert_with(|| {
+            let prefs = prefs();
+            let system = if prefs.proxy_mode == "system" { system_proxy() } else { SystemProxy::default() };
+            prefs.resolve(&system, proxy_password(prefs.proxy_username.as_deref()))
+        })
+        .clone()
+}
+
+/// Certificates in a PEM bundle; errors name the file.
+pub fn load_ca_bundle(path: &str) -> Result<Vec<reqwest::Certificate>, String> {
+    let pem = std::fs::read(path).map_err(|e| format!("Can't read CA bundle {}: {}", path, e))?;
+    let certs = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| format!("Invalid CA bundle {}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates in CA bundle {}", path));
+    }
+    Ok(certs)
+}
+
+/// A client builder with the proxy and CA settings applied, for callers
+/// that need their own timeouts or headers.
+pub fn client_builder() -> Result<reqwest::ClientBuilder, String> {
+    let prefs = prefs();
+    let mut builder = reqwest::Client::builder().no_proxy();
+    if let (Some(endpoint), bypass) = proxy() {
+        let mut proxy = reqwest::Proxy::all(endpoint.url()).map_err(|e| format!("Invalid proxy: {}", e))?;
+        if let Some(user) = &endpoint.username {
+            proxy = proxy.basic_auth(user, endpoint.password.as_deref().unwrap_or(""));
+        }
+        builder = builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_string(&bypass.join(","))));
+    }
+    if let Some(path) = &prefs.ca_bundle {
+        for cert in load_ca_bundle(path)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    Ok(builder)
+}
+
+/// The shared client. If the settings can't be applied (say the CA file
+/// was deleted), logs why and returns a plain client.
+pub fn client() -> reqwest::Client {
+    let mut guard = CLIENT.lock().unwrap_or_else(|e| e.into_inner());
+    guard
+        .get_or_insert_with(|| {
+            client_builder().and_then(|b| b.build().map_err(|e| e.to_string())).unwrap_or_else(|e| {
+                log::error!("[NET] {} — using a direct connection", e);
+                reqwest::Client::new()
+            })
+        })
+        .clone()
+}
+
+/// Drop the shared client and proxy route so the next request picks up
+/// new settings (and re-detects the system proxy).
+pub fn reset() {
+    *CLIENT.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    *ROUTE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    EPOCH.fetch_add(1, Ordering::Relaxed);
+}
+
+/// How many times the network settings have been reset.
+pub fn epoch() -> u64 {
+    EPOCH.load(Ordering::Relaxed)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityReport {
+    pub url: String,
+    /// Proxy used, without credentials; `None` = direct.
+    pub proxy: Option<String>,
+    pub ca_bundle: Option<String>,
+    pub ok: bool,
+    pub status: Option<u16>,
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Tauri command: save (or with `null`, remove) the manual proxy's password.
+#[tauri::command]
+pub fn set_proxy_password(password: Option<String>) -> Result<(), OmniError> {
+    let username = prefs().proxy_username.ok_or_else(|| Network::Settings("set a proxy user name first".into()))?;
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, &username).map_err(|e| Network::Keychain(e.to_string()))?;
+    let password_set = password.is_some();
+    match password {
+        Some(p) => entry.set_password(&p).map_err(|e| Network::Keychain(format!("couldn't save the proxy password: {}", e)))?,
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(Network::Keychain(format!("couldn't remove the proxy password: {}", e)).into()),
+        },
+    }
+    reset();
+    log::info!("[NET] Proxy password {}", if password_set { "saved" } else { "removed" });
+    Ok(())
+}
+
+/// Tauri command: request `url` (default: the Anthropic API) with the
+/// current network settings and report how it went. Any HTTP response
+/// counts as connected; errors carry the proxy or TLS failure.
+#[tauri::command]
+pub async fn test_connectivity(url: Option<String>) -> Result<ConnectivityReport, OmniError> {
+    reset();
+    let url = url.unwrap_or_else(|| DEFAULT_TEST_URL.to_string());
+    let builder = client_builder().map_err(Network::Settings)?;
+    let client = builder.timeout(TEST_TIMEOUT).build().map_err(|e| Network::Settings(e.to_string()))?;
+    let (endpoint, bypass) = proxy();
+    let host = reqwest::Url::parse(&url).map_err(|e| Network::InvalidUrl(e.to_string()))?.host_str().unwrap_or("").to_string();
+    let mut report = ConnectivityReport {
+        proxy: endpoint.filter(|_| !config::bypassed(&host, &bypass)).map(|p| p.url()),
+        ca_bundle: prefs().ca_bundle,
+        url,
+        ok: false,
+        status: None,
+        elapsed_ms: 0,
+        error: None,
+    };
+    let start = Instant::now();
+    match client.head(&report.url).send().await {
+        Ok(resp) => {
+            report.ok = true;
+            report.status = Some(resp.status().as_u16());
+        }
+        Err(e) => report.error = Some(error_chain(&e)),
+    }
+    report.elapsed_ms = start.elapsed().as_millis() as u64;
+    log::info!("[NET] Connectivity to {} via {:?}: ok={} {:?}", report.url, report.proxy, report.ok, report.error);
+    Ok(report)
+}
+
+/// An error with its causes ("error sending request: …: certificate
+/// verify failed"), which is where proxy and TLS problems show up.
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut text = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        text.push_str(": ");
+        text.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    text
+}
@@ -0,0 +1,135 @@
+//! Upstream connections for the plugin network proxy.
+//!
+//! A plugin's allowlist proxy (`mcp/sandbox/net_proxy.rs`) opens its
+//! upstream connection here. Without a corporate proxy that is a plain
+//! TCP connection to the target. With one, HTTPS tunnels are opened with
+//! a CONNECT to it (Basic auth when a user is set), and plain HTTP
+//! requests are handed to it as-is with a `Proxy-Authorization` header.
+//! TLS inside a tunnel stays between the plugin and the server; plugins
+//! learn about a custom CA bundle through `plugin_ca_env`.
+
+use super::config::{self, ProxyEndpoint};
+use base64::Engine;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Largest proxy response head we read before giving up.
+const MAX_RESPONSE_HEAD: usize = 8192;
+
+/// An open upstream connection.
+pub struct Upstream {
+    pub stream: TcpStream,
+    /// Set when plain HTTP is forwarded to an authenticating proxy.
+    auth_header: Option<String>,
+}
+
+/// `Proxy-Authorization` header line for `proxy`, if it has a user.
+fn auth_header(proxy: &ProxyEndpoint) -> Option<String> {
+    let user = proxy.username.as_deref()?;
+    let pair = format!("{}:{}", user, proxy.password.as_deref().unwrap_or(""));
+    Some(format!(
+        "Proxy-Authorization: Basic {}\r\n",
+        base64::engine::general_purpose::STANDARD.encode(pair)
+    ))
+}
+
+fn connect_request(host: &str, port: u16, auth: Option<&str>) -> String {
+    let authority = if host.contains(':') { format!("[{}]:{}", host, port) } else { format!("{}:{}", host, port) };
+    format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n{1}\r\n", authority, auth.unwrap_or(""))
+}
+
+/// Status code of an HTTP response head.
+fn status(head: &[u8]) -> Option<u16> {
+    let line = std::str::from_utf8(head).ok()?.lines().next()?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// `head` with `header` inserted after the request line.
+fn insert_header(head: &[u8], header: &str) -> Vec<u8> {
+    let Some(eol) = head.windows(2).position(|w| w == b"\r\n") else { return head.to_vec() };
+    let mut out = Vec::with_capacity(head.len() + header.len());
+    out.extend_from_slice(&head[..eol + 2]);
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(&head[eol + 2..]);
+    out
+}
+
+/// Connect towards `host:port`, through the configured proxy unless the
+/// host is bypassed. For a `tunnel`, the proxy's CONNECT has succeeded
+/// by the time this returns.
+pub async fn connect(host: &str, port: u16, tunnel: bool) -> Result<Upstream, String> {
+    let (proxy, bypass) = super::proxy();
+    let Some(proxy) = proxy.filter(|_| !config::bypassed(host, &bypass)) else {
+        let stream = TcpStream::connect((host, port))
+            .await
+            .map_err(|e| format!("connect {}:{} failed: {}", host, port, e))?;
+        return Ok(Upstream { stream, auth_header: None });
+    };
+
+    let mut stream = TcpStream::connect((proxy.host.as_str(), proxy.port))
+        .await
+        .map_err(|e| format!("connect to proxy {} failed: {}", proxy.url(), e))?;
+    let auth = auth_header(&proxy);
+    if !tunnel {
+        return Ok(Upstream { stream, auth_header: auth });
+    }
+
+    let io = |e: std::io::Error| e.to_string();
+    stream.write_all(connect_request(host, port, auth.as_deref()).as_bytes()).await.map_err(io)?;
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    // Byte by byte, so nothing after the head (the server's TLS hello) is consumed.
+    while !head.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut byte).await.map_err(io)? == 0 || head.len() > MAX_RESPONSE_HEAD {
+            return Err(format!("proxy {} closed the CONNECT", proxy.url()));
+        }
+        head.push(byte[0]);
+    }
+    match status(&head) {
+        Some(200) => Ok(Upstream { stream, auth_header: None }),
+        Some(407) => Err(format!("proxy {} wants authentication (check user and password)", proxy.url())),
+        other => Err(format!("proxy {} refused CONNECT {}:{} ({:?})", proxy.url(), host, port, other)),
+    }
+}
+
+impl Upstream {
+    /// The bytes to send for a forwarded plain HTTP request `head`.
+    pub fn forward(&self, head: &[u8]) -> Vec<u8> {
+        match &self.auth_header {
+            Some(header) => insert_header(head, header),
+            None => head.to_vec(),
+        }
+    }
+}
+
+/// Environment variables that make common plugin runtimes (OpenSSL,
+/// Node, Python requests) trust the custom CA bundle, if one is set.
+pub fn plugin_ca_env() -> Vec<(String, String)> {
+    let Some(path) = crate::settings::current().network.ca_bundle else { return Vec::new() };
+    ["SSL_CERT_FILE", "NODE_EXTRA_CA_CERTS", "REQUESTS_CA_BUNDLE"]
+        .iter()
+        .map(|key| (key.to_string(), path.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_and_forward_carry_proxy_auth() {
+        let proxy = config::parse_proxy_url("http://alice:pw@proxy.corp:3128").unwrap();
+        let auth = auth_header(&proxy).unwrap();
+        assert_eq!(auth, "Proxy-Authorization: Basic YWxpY2U6cHc=\r\n");
+        assert_eq!(
+            connect_request("api.github.com", 443, Some(&auth)),
+            "CONNECT api.github.com:443 HTTP/1.1\r\nHost: api.github.com:443\r\nProxy-Authorization: Basic YWxpY2U6cHc=\r\n\r\n"
+        );
+        assert!(connect_request("::1", 443, None).starts_with("CONNECT [::1]:443 "));
+        assert_eq!(
+            insert_header(b"GET http://a/ HTTP/1.1\r\nHost: a\r\n\r\n", &auth),
+            b"GET http://a/ HTTP/1.1\r\nProxy-Authorization: Basic YWxpY2U6cHc=\r\nHost: a\r\n\r\n".to_vec()
+        );
+        assert_eq!(status(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n"), Some(407));
+    }
+}
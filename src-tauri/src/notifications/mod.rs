@@ -0,0 +1,86 @@
+//! Notifications domain — results that finish in the background.
+//!
+//! An action whose result window was closed (or replaced by a newer snip)
+//! before it finished, and a scheduled plugin task, report through a
+//! system notification instead. It offers Copy and Open buttons
+//! (`notice.rs` decides which) through a native backend per platform
+//! (`native.rs`); without one, the notification plugin shows the same
+//! text without buttons. Open, or a click on the notification, reopens
+//! the action menu on the result, read back from the snip's history
+//! record when it has one.
+
+pub mod native;
+pub mod notice;
+
+use crate::clipboard::formats::Flavors;
+use crate::i18n::tf;
+use crate::llm::execute::ActionResult;
+use crate::llm::ActionMenuState;
+use notice::{Choice, Notice};
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+
+/// What Open shows: a result and the text it was run on.
+pub struct Reopen {
+    /// History record to read the result from, when it was recorded.
+    pub snip_id: Option<u64>,
+    pub snip_text: String,
+    pub result: ActionResult,
+}
+
+/// A snip's action finished while its result window was closed.
+pub fn result_ready(app: &tauri::AppHandle, snip_text: &str, snip_id: Option<u64>, result: &ActionResult) {
+    let label = app
+        .state::<ActionMenuState>()
+        .menu
+        .lock()
+        .ok()
+        .and_then(|menu| menu.as_ref()?.actions.iter().find(|a| a.id == result.action_id).map(|a| a.label.clone()))
+        .unwrap_or_else(|| {
+            let tool = result.action_id.rsplit(':').next().unwrap_or(&result.action_id);
+            crate::mcp::registry::display_name(tool)
+        });
+    let key = if result.status == "success" { "notify.ready" } else { "notify.failed" };
+    let title = tf(key, &[("action", label.as_str())]);
+    let reopen = Reopen { snip_id, snip_text: snip_text.to_string(), result: result.clone() };
+    notify(app, Notice::for_result(title, result), reopen);
+}
+
+/// Show `notice`; its buttons act on `reopen.result`.
+pub fn notify(app: &tauri::AppHandle, notice: Notice, reopen: Reopen) {
+    let handle = app.clone();
+    let buttons = notice.buttons.clone();
+    let shown = native::show(app, &notice, move |choice| {
+        if buttons.contains(&choice) {
+            respond(&handle, choice, reopen);
+        }
+    });
+    match shown {
+        Ok(()) => log::info!("[NOTIFY] Shown: {} ({} buttons)", notice.title, notice.buttons.len()),
+        Err(e) => {
+            log::warn!("[NOTIFY] Native notification unavailable ({}); showing it without buttons", e);
+            if let Err(e) = app.notification().builder().title(&notice.title).body(&notice.body).show() {
+                log::warn!("[NOTIFY] Failed to show notification: {}", e);
+            }
+        }
+    }
+}
+
+fn respond(app: &tauri::AppHandle, choice: Choice, reopen: Reopen) {
+    log::info!("[NOTIFY] {:?} on '{}' result", choice, reopen.result.action_id);
+    match choice {
+        Choice::Copy => {
+            let Some(text) = notice::copy_text(&reopen.result) else { return };
+            if let Err(e) = crate::clipboard::write(&Flavors::plain(text)) {
+                log::warn!("[NOTIFY] Copy failed: {}", e);
+            }
+        }
+        Choice::Open => {
+            let stored = reopen.snip_id.and_then(|id| crate::history::stored_result(id, &reopen.result.action_id));
+            let (snip_text, result) = stored.unwrap_or((reopen.snip_text, reopen.result));
+            if let Err(e) = crate::session::show_result(app, snip_text, result) {
+                log::warn!("[NOTIFY] {}", e);
+            }
+        }
+    }
+}
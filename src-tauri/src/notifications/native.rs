@@ -0,0 +1,109 @@
+//! Platform backends for notifications with buttons.
+//!
+//! The notification plugin can't show buttons or report a click on the
+//! desktop, so each platform gets its own backend:
+//!   - Linux: D-Bus actions (`notify-rust`); the body click is `default`
+//!   - Windows: a toast with buttons (`tauri-winrt-notification`)
+//!   - macOS: `mac-notification-sys`, buttons in one dropdown
+//!
+//! `show` returns once the notification is up; `on_choice` runs later on
+//! a background thread, at most once. Elsewhere `show` fails and the
+//! caller falls back to the plugin (no buttons).
+
+use super::notice::{Choice, Notice};
+use crate::i18n::t;
+
+fn label(choice: Choice) -> String {
+    t(&format!("notify.{}", choice.id()))
+}
+
+#[cfg(target_os = "linux")]
+pub fn show(app: &tauri::AppHandle, notice: &Notice, on_choice: impl FnOnce(Choice) + Send + 'static) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification.appname(&app.package_info().name).summary(&notice.title).body(&notice.body);
+    if notice.buttons.contains(&Choice::Open) {
+        notification.action("default", &label(Choice::Open));
+    }
+    for choice in &notice.buttons {
+        notification.action(choice.id(), &label(*choice));
+    }
+    let (shown, result) = std::sync::mpsc::channel();
+    std::thread::spawn(move || match notification.show() {
+        Ok(handle) => {
+            let _ = shown.send(Ok(()));
+            handle.wait_for_action(|id| {
+                if let Some(choice) = Choice::from_id(id) {
+                    on_choice(choice);
+                }
+            });
+        }
+        Err(e) => {
+            let _ = shown.send(Err(e.to_string()));
+        }
+    });
+    result.recv().map_err(|e| e.to_string())?
+}
+
+#[cfg(target_os = "windows")]
+pub fn show(app: &tauri::AppHandle, notice: &Notice, on_choice: impl FnOnce(Choice) + Send + 'static) -> Result<(), String> {
+    use tauri_winrt_notification::Toast;
+    let app_id = if tauri::is_dev() { Toast::POWERSHELL_APP_ID.to_string() } else { app.config().identifier.clone() };
+    let mut toast = Toast::new(&app_id).title(&notice.title).text1(&notice.body);
+    for choice in &notice.buttons {
+        toast = toast.add_button(&label(*choice), choice.id());
+    }
+    let mut on_choice = Some(on_choice);
+    toast
+        .on_activated(move |action| {
+            // No action id: the toast body was clicked
+            let choice = action.as_deref().map_or(Some(Choice::Open), Choice::from_id);
+            if let (Some(choice), Some(on_choice)) = (choice, on_choice.take()) {
+                on_choice(choice);
+            }
+            Ok(())
+        })
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "macos")]
+pub fn show(app: &tauri::AppHandle, notice: &Notice, on_choice: impl FnOnce(Choice) + Send + 'static) -> Result<(), String> {
+    use mac_notification_sys::{MainButton, Notification, NotificationResponse};
+    let _ = mac_notification_sys::set_application(&app.config().identifier);
+    let buttons: Vec<(Choice, String)> = notice.buttons.iter().map(|c| (*c, label(*c))).collect();
+    let (title, body) = (notice.title.clone(), notice.body.clone());
+    let actions = t("notify.actions");
+    // Waiting for the click blocks, so the whole exchange runs on its own thread
+    std::thread::spawn(move || {
+        let names: Vec<&str> = buttons.iter().map(|(_, name)| name.as_str()).collect();
+        let mut options = Notification::new();
+        options.wait_for_click(true);
+        match names.as_slice() {
+            [] => {}
+            [one] => {
+                options.main_button(MainButton::SingleAction(one));
+            }
+            many => {
+                options.main_button(MainButton::DropdownActions(&actions, many));
+            }
+        }
+        let choice = match mac_notification_sys::send_notification(&title, None, &body, Some(&options)) {
+            Ok(NotificationResponse::ActionButton(name)) => buttons.iter().find(|(_, n)| *n == name).map(|(c, _)| *c),
+            Ok(NotificationResponse::Click) => Some(Choice::Open),
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!("[NOTIFY] Notification failed: {}", e);
+                None
+            }
+        };
+        if let Some(choice) = choice {
+            on_choice(choice);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+pub fn show(_app: &tauri::AppHandle, _notice: &Notice, _on_choice: impl FnOnce(Choice) + Send + 'static) -> Result<(), String> {
+    Err("No notification backend with buttons on this platform".to_string())
+}
@@ -0,0 +1,98 @@
+//! What a result notification says and which buttons it offers (pure,
+//! no I/O).
+
+use crate::llm::execute::ActionResult;
+
+/// Notification body length.
+pub const MAX_BODY_CHARS: usize = 200;
+
+/// A button on the notification, or a click on the notification itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Choice {
+    Copy,
+    Open,
+}
+
+impl Choice {
+    /// Action id given to the platform backend.
+    pub fn id(self) -> &'static str {
+        match self {
+            Choice::Copy => "copy",
+            Choice::Open => "open",
+        }
+    }
+
+    /// The choice behind an action id a backend reported. A click on the
+    /// notification body (`default` on Linux) counts as Open.
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "copy" => Some(Choice::Copy),
+            "open" | "default" => Some(Choice::Open),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notice {
+    pub title: String,
+    pub body: String,
+    pub buttons: Vec<Choice>,
+}
+
+impl Notice {
+    /// Notice for `result`, titled by the caller. A failure has no buttons;
+    /// a file can't be copied as text, only opened (to save it).
+    pub fn for_result(title: String, result: &ActionResult) -> Self {
+        let buttons = match () {
+            _ if result.status != "success" => vec![],
+            _ if copy_text(result).is_none() || result.result.result_type == "file" => vec![Choice::Open],
+            _ => vec![Choice::Copy, Choice::Open],
+        };
+        Notice { title, body: preview(result.result.text.as_deref().unwrap_or_default()), buttons }
+    }
+}
+
+/// What Copy puts on the clipboard: the clipboard payload, the command,
+/// or the text, in that order.
+pub fn copy_text(result: &ActionResult) -> Option<&str> {
+    let body = &result.result;
+    body.clipboard_content.as_deref().or(body.command.as_deref()).or(body.text.as_deref()).filter(|t| !t.trim().is_empty())
+}
+
+/// The start of `text` on one line, cut at `MAX_BODY_CHARS`.
+pub fn preview(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut body: String = flat.chars().take(MAX_BODY_CHARS).collect();
+    if body.len() < flat.len() {
+        body.push('…');
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buttons_follow_the_result() {
+        let mut result = ActionResult::error("explain_error", "offline");
+        assert!(Notice::for_result("t".into(), &result).buttons.is_empty());
+        result.status = "success".to_string();
+        result.result.result_type = "text".to_string();
+        result.result.text = Some("The   map\nis undefined".to_string());
+        let notice = Notice::for_result("Explain error".into(), &result);
+        assert_eq!((notice.buttons, notice.body.as_str()), (vec![Choice::Copy, Choice::Open], "The map is undefined"));
+        result.result.result_type = "file".to_string();
+        assert_eq!(Notice::for_result("t".into(), &result).buttons, vec![Choice::Open]);
+        result.result.command = Some("npm ci".to_string());
+        assert_eq!(copy_text(&result), Some("npm ci"));
+    }
+
+    #[test]
+    fn text_helpers() {
+        assert_eq!(preview(&"é".repeat(MAX_BODY_CHARS + 5)).chars().count(), MAX_BODY_CHARS + 1);
+        assert_eq!(preview("short"), "short");
+        assert_eq!((Choice::from_id("default"), Choice::from_id("__closed")), (Some(Choice::Open), None));
+    }
+}
@@ -3,7 +3,8 @@
 //! This module is only compiled on macOS. It uses swift-bridge to call
 //! into Swift code that wraps VNRecognizeTextRequest.
 
-use super::{OcrOutput, RecognitionLevel};
+use super::spans::{self, TextSpan};
+use super::{OcrOutput, RecognitionLevel, RgbaView};
 
 #[swift_bridge::bridge]
 mod ffi {
@@ -19,14 +20,13 @@ mod ffi {
     extern "Swift" {
         fn run_ocr_on_path(path: String, level: i32) -> OcrResult;
         fn run_ocr_on_png_data(data: Vec<u8>, level: i32) -> OcrResult;
+        fn run_ocr_on_rgba(data: &[u8], width: u32, height: u32, bytes_per_row: u32, level: i32) -> OcrResult;
+        fn run_ocr_spans_on_rgba(data: &[u8], width: u32, height: u32, bytes_per_row: u32, level: i32) -> String;
         fn warm_up_vision();
     }
 }
 
-/// Run OCR on an image file path via Apple Vision.
-#[allow(dead_code)] // path-based API reserved for future use
-pub fn recognize_text(image_path: &str, level: RecognitionLevel) -> OcrOutput {
-    let result = ffi::run_ocr_on_path(image_path.to_string(), level as i32);
+fn output(result: ffi::OcrResult) -> OcrOutput {
     OcrOutput {
         text: result.text,
         char_count: result.char_count,
@@ -36,16 +36,27 @@ pub fn recognize_text(image_path: &str, level: RecognitionLevel) -> OcrOutput {
     }
 }
 
+/// Run OCR on an image file path via Apple Vision.
+#[allow(dead_code)] // path-based API reserved for future use
+pub fn recognize_text(image_path: &str, level: RecognitionLevel) -> OcrOutput {
+    output(ffi::run_ocr_on_path(image_path.to_string(), level as i32))
+}
+
 /// Run OCR on in-memory PNG bytes via Apple Vision.
 pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) -> OcrOutput {
-    let result = ffi::run_ocr_on_png_data(png_bytes, level as i32);
-    OcrOutput {
-        text: result.text,
-        char_count: result.char_count,
-        latency_ms: result.latency_ms,
-        confidence: result.confidence,
-        recognition_level: result.recognition_level,
-    }
+    output(ffi::run_ocr_on_png_data(png_bytes, level as i32))
+}
+
+/// Run OCR on borrowed RGBA pixels via Apple Vision. Swift wraps the
+/// buffer in a CGImage without copying; it is only read during the call.
+pub fn recognize_text_from_rgba(view: &RgbaView, level: RecognitionLevel) -> OcrOutput {
+    output(ffi::run_ocr_on_rgba(view.data, view.width, view.height, view.stride as u32, level as i32))
+}
+
+/// Recognized words with their boxes, in the view's pixels.
+pub fn recognize_spans(view: &RgbaView, level: RecognitionLevel) -> Vec<TextSpan> {
+    let report = ffi::run_ocr_spans_on_rgba(view.data, view.width, view.height, view.stride as u32, level as i32);
+    spans::from_vision(&report, view.width, view.height)
 }
 
 /// Warm up Vision Framework with a throwaway recognition request.
@@ -0,0 +1,172 @@
+//! Cancellable OCR jobs — one engine, the newest request wins.
+//!
+//! Speculative OCR (`pipeline_prefetch.rs`) submits a job each time the
+//! selection settles. Jobs run one at a time, each on its own thread; a
+//! job superseded before its turn never starts, and one superseded while
+//! running can see it through its `Cancel` and has its result dropped.
+//! The engine calls themselves can't be interrupted, so a superseded job
+//! costs at most the OCR already in flight. `take` hands over the result
+//! for a key, waiting while that exact job is still running.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+enum Slot<K, V> {
+    Empty,
+    Pending(K, u64),
+    Done(K, V),
+}
+
+struct Inner<K, V> {
+    latest: Arc<AtomicU64>,
+    slot: Mutex<Slot<K, V>>,
+    changed: Condvar,
+    /// Held while a job runs, so jobs never overlap.
+    engine: Mutex<()>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<K, V> Inner<K, V> {
+    /// Store the result of job `generation` if it is still the current one.
+    fn finish(&self, generation: u64, value: Option<V>) {
+        let mut slot = lock(&self.slot);
+        if !matches!(&*slot, Slot::Pending(_, g) if *g == generation) {
+            return;
+        }
+        *slot = match (std::mem::replace(&mut *slot, Slot::Empty), value) {
+            (Slot::Pending(key, _), Some(value)) => Slot::Done(key, value),
+            _ => Slot::Empty,
+        };
+        self.changed.notify_all();
+    }
+}
+
+/// Told to a running job; set once a newer job or `cancel` replaced it.
+pub struct Cancel {
+    latest: Arc<AtomicU64>,
+    generation: u64,
+}
+
+impl Cancel {
+    pub fn is_cancelled(&self) -> bool {
+        self.latest.load(Ordering::SeqCst) != self.generation
+    }
+}
+
+/// Holds at most one job: the newest, running or finished.
+pub struct JobManager<K, V> {
+    inner: Arc<Inner<K, V>>,
+}
+
+impl<K: PartialEq + Send + 'static, V: Send + 'static> JobManager<K, V> {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                latest: Arc::new(AtomicU64::new(0)),
+                slot: Mutex::new(Slot::Empty),
+                changed: Condvar::new(),
+                engine: Mutex::new(()),
+            }),
+        }
+    }
+
+    /// Run `work` for `key`, superseding any earlier job. `work` returns
+    /// `None` when it noticed it was cancelled.
+    pub fn submit<F>(&self, key: K, work: F)
+    where
+        F: FnOnce(&Cancel) -> Option<V> + Send + 'static,
+    {
+        let generation = {
+            let mut slot = lock(&self.inner.slot);
+            let generation = self.inner.latest.fetch_add(1, Ordering::SeqCst) + 1;
+            *slot = Slot::Pending(key, generation);
+            self.inner.changed.notify_all();
+            generation
+        };
+        let inner = Arc::clone(&self.inner);
+        std::thread::spawn(move || {
+            let _engine = lock(&inner.engine);
+            let cancel = Cancel { latest: Arc::clone(&inner.latest), generation };
+            let value = if cancel.is_cancelled() { None } else { work(&cancel) };
+            inner.finish(generation, value);
+        });
+    }
+
+    /// Drop the current job and its result.
+    pub fn cancel(&self) {
+        let mut slot = lock(&self.inner.slot);
+        self.inner.latest.fetch_add(1, Ordering::SeqCst);
+        *slot = Slot::Empty;
+        self.inner.changed.notify_all();
+    }
+
+    /// The result of the job for `key`, waiting up to `wait` if it is
+    /// still running; `None` if the current job is for another key.
+    pub fn take(&self, key: &K, wait: Duration) -> Option<V> {
+        let deadline = Instant::now() + wait;
+        let mut slot = lock(&self.inner.slot);
+        loop {
+            match &*slot {
+                Slot::Done(k, _) if k == key => break,
+                Slot::Pending(k, _) if k == key => {
+                    let left = deadline.checked_duration_since(Instant::now())?;
+                    slot = self.inner.changed.wait_timeout(slot, left).unwrap_or_else(PoisonError::into_inner).0;
+                }
+                _ => return None,
+            }
+        }
+        match std::mem::replace(&mut *slot, Slot::Empty) {
+            Slot::Done(_, value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn newest_job_wins() {
+        let jobs = JobManager::new();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        jobs.submit(1, move |cancel: &Cancel| {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            (!cancel.is_cancelled()).then_some("first")
+        });
+        started_rx.recv().unwrap();
+        jobs.submit(2, |_: &Cancel| Some("second"));
+        jobs.submit(3, |_: &Cancel| Some("third"));
+        release_tx.send(()).unwrap();
+        assert_eq!(jobs.take(&1, WAIT), None);
+        assert_eq!(jobs.take(&2, WAIT), None);
+        assert_eq!(jobs.take(&3, WAIT), Some("third"));
+        assert_eq!(jobs.take(&3, WAIT), None);
+    }
+
+    #[test]
+    fn take_waits_for_the_running_job() {
+        let jobs = JobManager::new();
+        jobs.submit("a", |_: &Cancel| {
+            std::thread::sleep(Duration::from_millis(50));
+            Some(7)
+        });
+        assert_eq!(jobs.take(&"a", WAIT), Some(7));
+
+        jobs.submit("b", |_: &Cancel| {
+            std::thread::sleep(Duration::from_millis(50));
+            Some(8)
+        });
+        jobs.cancel();
+        assert_eq!(jobs.take(&"b", WAIT), None);
+    }
+}
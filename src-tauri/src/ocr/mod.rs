@@ -3,11 +3,19 @@
 //! Dispatches to the appropriate platform backend:
 //! - macOS: Apple Vision Framework via swift-bridge FFI
 //! - Windows: Windows.Media.Ocr via windows-rs (WinRT)
+//! - Linux: the Tesseract command-line tool, when installed
 //!
 //! External code uses the public functions here — the platform
 //! backend is selected at compile time via #[cfg(target_os)].
 
 pub mod heuristics;
+pub mod jobs;
+pub mod rgba;
+pub mod spans;
+pub mod table_detect;
+
+pub use rgba::RgbaView;
+pub use spans::TextSpan;
 
 #[cfg(target_os = "macos")]
 mod apple_vision;
@@ -15,12 +23,15 @@ mod apple_vision;
 #[cfg(target_os = "windows")]
 mod windows_ocr;
 
+#[cfg(target_os = "linux")]
+mod tesseract;
+
 /// Recognition level for text recognition.
 ///
 /// Maps to VNRequestTextRecognitionLevel on macOS.
 /// On Windows, both levels use the same WinRT engine
 /// (Windows.Media.Ocr doesn't expose accuracy levels).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RecognitionLevel {
     Accurate = 0,
     Fast = 1,
@@ -46,7 +57,8 @@ pub struct OcrOutput {
 
 /// Run OCR on in-memory PNG bytes. Eliminates disk I/O from the pipeline.
 ///
-/// Dispatches to Apple Vision (macOS) or Windows.Media.Ocr (Windows).
+/// Dispatches to Apple Vision (macOS), Windows.Media.Ocr (Windows), or
+/// Tesseract (Linux).
 pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) -> OcrOutput {
     #[cfg(target_os = "macos")]
     {
@@ -57,6 +69,54 @@ pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) ->
     {
         windows_ocr::recognize_text_from_bytes(png_bytes, level)
     }
+
+    #[cfg(target_os = "linux")]
+    {
+        tesseract::recognize_text_from_bytes(png_bytes, level)
+    }
+}
+
+/// Run OCR on raw pixels read in place — no PNG encode or decode.
+///
+/// The snip pipeline's path: the view is a region of the capture itself,
+/// so OCR can start before (and alongside) the crop's PNG encode.
+pub fn recognize_text_from_rgba(view: &RgbaView, level: RecognitionLevel) -> OcrOutput {
+    #[cfg(target_os = "macos")]
+    {
+        apple_vision::recognize_text_from_rgba(view, level)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        windows_ocr::recognize_text_from_rgba(view, level)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        tesseract::recognize_text_from_rgba(view, level)
+    }
+}
+
+/// Recognized words with their boxes in the view's pixels (origin top
+/// left), for laying selectable text over the snip. Windows.Media.Ocr and
+/// Tesseract have one level; `level` applies to Apple Vision.
+pub fn recognize_spans_from_rgba(view: &RgbaView, level: RecognitionLevel) -> Vec<TextSpan> {
+    #[cfg(target_os = "macos")]
+    {
+        apple_vision::recognize_spans(view, level)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = level;
+        windows_ocr::recognize_spans(view)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let _ = level;
+        tesseract::recognize_spans(view)
+    }
 }
 
 /// Run OCR on an image file and return extracted text with metadata.
@@ -69,6 +129,26 @@ pub fn recognize_text(image_path: &str, level: RecognitionLevel) -> OcrOutput {
     apple_vision::recognize_text(image_path, level)
 }
 
+/// Whether the platform engine can run at all. Windows needs an OCR
+/// language pack for one of the user's languages, Linux the `tesseract`
+/// binary; Apple Vision always can.
+pub fn available() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        true
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        windows_ocr::available()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        tesseract::available()
+    }
+}
+
 /// Warm up the OCR engine to avoid cold-start penalty on first snip.
 /// Call once at startup.
 pub fn warm_up() {
@@ -77,4 +157,7 @@ pub fn warm_up() {
 
     #[cfg(target_os = "windows")]
     windows_ocr::warm_up();
+
+    #[cfg(target_os = "linux")]
+    tesseract::warm_up();
 }
@@ -0,0 +1,70 @@
+//! Borrowed RGBA regions — OCR input without a PNG round-trip (pure).
+//!
+//! A snip is a rectangle of the full-screen capture. `RgbaView` points
+//! at that rectangle inside the capture's own buffer: `data` starts at
+//! the region's top-left pixel and each row is `stride` bytes after the
+//! previous one, so describing the crop copies nothing. Apple Vision
+//! reads it in place; Windows packs the rows into one WinRT buffer.
+
+use image::RgbaImage;
+
+/// A `width`×`height` region of an RGBA8 buffer, `stride` bytes per row.
+#[derive(Debug, Clone, Copy)]
+pub struct RgbaView<'a> {
+    pub data: &'a [u8],
+    pub width: u32,
+    pub height: u32,
+    pub stride: usize,
+}
+
+impl<'a> RgbaView<'a> {
+    /// The region of `image` at `x, y`, clamped to the image like
+    /// `DynamicImage::crop_imm`; `None` when nothing is left of it.
+    pub fn crop(image: &'a RgbaImage, x: u32, y: u32, width: u32, height: u32) -> Option<Self> {
+        let (x, y) = (x.min(image.width()), y.min(image.height()));
+        let width = width.min(image.width() - x);
+        let height = height.min(image.height() - y);
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let stride = image.width() as usize * 4;
+        let start = y as usize * stride + x as usize * 4;
+        let end = start + (height as usize - 1) * stride + width as usize * 4;
+        Some(Self { data: &image.as_raw()[start..end], width, height, stride })
+    }
+
+    /// Each row's pixels, without the bytes between rows.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        let row_len = self.width as usize * 4;
+        (0..self.height as usize).map(move |row| &self.data[row * self.stride..row * self.stride + row_len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgba};
+
+    fn sample() -> RgbaImage {
+        RgbaImage::from_fn(50, 40, |x, y| Rgba([x as u8, y as u8, (x + y) as u8, 255]))
+    }
+
+    #[test]
+    fn rows_match_a_copied_crop() {
+        let image = sample();
+        let view = RgbaView::crop(&image, 7, 11, 20, 9).unwrap();
+        let copied = DynamicImage::ImageRgba8(image.clone()).crop_imm(7, 11, 20, 9).to_rgba8();
+        assert_eq!((view.width, view.height, view.stride), (20, 9, 200));
+        assert_eq!(view.rows().flatten().copied().collect::<Vec<u8>>(), copied.into_raw());
+    }
+
+    #[test]
+    fn clamps_to_the_image() {
+        let image = sample();
+        let view = RgbaView::crop(&image, 40, 30, 100, 100).unwrap();
+        assert_eq!((view.width, view.height), (10, 10));
+        assert_eq!(view.rows().last().unwrap().len(), 40);
+        assert!(RgbaView::crop(&image, 50, 0, 10, 10).is_none());
+        assert!(RgbaView::crop(&image, 0, 0, 0, 10).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Recognized words with where they are in the image (pure, no I/O).
+//!
+//! Each engine reports word boxes its own way: Vision normalized with the
+//! origin at the bottom left (passed over FFI as tab-separated lines),
+//! Tesseract as its TSV output, WinRT as pixel rectangles. All become
+//! `TextSpan`s in image pixels, origin at the top left, so the result
+//! window can lay the text over the snip where it was read.
+
+use serde::Serialize;
+
+/// One recognized word and its box in image pixels.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextSpan {
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Index of the line the word is on, in reading order.
+    pub line: u32,
+}
+
+/// From the Swift bridge's report: one word per line,
+/// `line⇥x⇥y⇥width⇥height⇥text`, the box normalized to a
+/// `width`×`height` image with the origin at the bottom left.
+pub fn from_vision(report: &str, width: u32, height: u32) -> Vec<TextSpan> {
+    let (w, h) = (f64::from(width), f64::from(height));
+    report
+        .lines()
+        .filter_map(|row| {
+            let mut fields = row.splitn(6, '\t');
+            let line = fields.next()?.parse().ok()?;
+            let mut number = || fields.next()?.parse::<f64>().ok();
+            let (x, y, bw, bh) = (number()?, number()?, number()?, number()?);
+            let text = fields.next()?.trim();
+            (!text.is_empty()).then(|| TextSpan {
+                text: text.to_string(),
+                x: x * w,
+                y: (1.0 - y - bh) * h,
+                width: bw * w,
+                height: bh * h,
+                line,
+            })
+        })
+        .collect()
+}
+
+/// From `tesseract … tsv` output: word rows (level 5) with text, lines
+/// numbered in the order they first appear.
+pub fn from_tesseract_tsv(tsv: &str) -> Vec<TextSpan> {
+    let mut lines: Vec<(&str, &str, &str)> = Vec::new();
+    tsv.lines()
+        .skip(1)
+        .filter_map(|row| {
+            let fields: Vec<&str> = row.split('\t').collect();
+            if fields.len() < 12 || fields[0] != "5" || fields[11].trim().is_empty() {
+                return None;
+            }
+            let key = (fields[2], fields[3], fields[4]);
+            let line = lines.iter().position(|k| *k == key).unwrap_or_else(|| {
+                lines.push(key);
+                lines.len() - 1
+            });
+            let number = |i: usize| fields[i].parse::<f64>().ok();
+            Some(TextSpan {
+                text: fields[11].trim().to_string(),
+                x: number(6)?,
+                y: number(7)?,
+                width: number(8)?,
+                height: number(9)?,
+                line: line as u32,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vision_boxes_flip_to_top_left_pixels() {
+        let report = "0\t0.25\t0.5\t0.25\t0.25\tHello\n0\t0.5\t0.5\t0.25\t0.25\tworld\n1\tbad\nbroken\n1\t0\t0\t0.5\t0.25\t  \n";
+        let spans = from_vision(report, 400, 200);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0], TextSpan { text: "Hello".into(), x: 100.0, y: 50.0, width: 100.0, height: 50.0, line: 0 });
+        assert_eq!((spans[1].x, spans[1].text.as_str()), (200.0, "world"));
+    }
+
+    #[test]
+    fn tesseract_words_are_numbered_by_line() {
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+                   1\t1\t0\t0\t0\t0\t0\t0\t640\t480\t-1\t\n\
+                   4\t1\t1\t1\t1\t0\t10\t20\t200\t30\t-1\t\n\
+                   5\t1\t1\t1\t1\t1\t10\t20\t90\t30\t96.5\tTotal\n\
+                   5\t1\t1\t1\t1\t2\t110\t20\t100\t30\t95.1\t$42.00\n\
+                   5\t1\t1\t1\t2\t1\t10\t60\t80\t30\t91.0\tThanks\n\
+                   5\t1\t1\t1\t2\t2\t95\t60\t5\t30\t12.0\t \n";
+        let spans = from_tesseract_tsv(tsv);
+        let words: Vec<(&str, u32)> = spans.iter().map(|s| (s.text.as_str(), s.line)).collect();
+        assert_eq!(words, vec![("Total", 0), ("$42.00", 0), ("Thanks", 1)]);
+        assert_eq!((spans[1].x, spans[1].y, spans[1].width, spans[1].height), (110.0, 20.0, 100.0, 30.0));
+    }
+}
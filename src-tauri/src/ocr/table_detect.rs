@@ -0,0 +1,123 @@
+//! Table structure from OCR text (pure, no I/O).
+//!
+//! `heuristics::detect_table_structure` only says whether text looks
+//! tabular; this splits it into rows and cells so it can be exported
+//! without an LLM. Three layouts are recognized, tried in order:
+//!   - tab-separated cells
+//!   - pipe-separated cells (Markdown tables; `---` rule rows dropped)
+//!   - columns separated by runs of two or more spaces
+//!
+//! A table needs at least two rows and two columns, and most rows must
+//! agree on the column count. Anything else returns `None` and the
+//! caller falls back to the LLM.
+
+/// Rows of cells; the first row is the header. Every row has the same
+/// number of cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn columns(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+}
+
+/// Share of rows that must have the most common column count.
+const MIN_AGREEMENT: f64 = 0.6;
+
+pub fn detect(text: &str) -> Option<Table> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() < 2 {
+        return None;
+    }
+    let layouts: [fn(&str) -> Vec<String>; 3] = [split_tabs, split_pipes, split_spaces];
+    let guards: [fn(&str) -> bool; 3] = [|l| l.contains('\t'), |l| l.contains('|'), |_| true];
+    layouts.iter().zip(guards).find_map(|(split, guard)| {
+        if lines.iter().filter(|l| guard(l)).count() * 2 <= lines.len() {
+            return None;
+        }
+        let rows = lines.iter().filter(|l| !is_rule(l)).map(|l| split(l)).collect();
+        normalize(rows)
+    })
+}
+
+fn split_tabs(line: &str) -> Vec<String> {
+    line.split('\t').map(|c| c.trim().to_string()).collect()
+}
+
+fn split_pipes(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(|c| c.trim().to_string()).collect()
+}
+
+/// Cells separated by two or more spaces (single spaces stay in a cell).
+fn split_spaces(line: &str) -> Vec<String> {
+    line.trim()
+        .split("  ")
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A Markdown rule row such as `|---|:---:|`.
+fn is_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('-') && line.chars().all(|c| matches!(c, '-' | ':' | '|' | '+' | ' '))
+}
+
+/// Keep rows with the common column count, padding short rows when they
+/// are only missing trailing cells; `None` unless enough rows agree.
+fn normalize(rows: Vec<Vec<String>>) -> Option<Table> {
+    let mut counts = std::collections::BTreeMap::new();
+    for row in &rows {
+        *counts.entry(row.len()).or_insert(0usize) += 1;
+    }
+    let (&columns, &agreeing) = counts.iter().max_by_key(|(len, n)| (**n, **len))?;
+    if columns < 2 || (agreeing as f64) < rows.len() as f64 * MIN_AGREEMENT {
+        return None;
+    }
+    let rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .filter(|row| row.len() <= columns && row.len() * 2 > columns)
+        .map(|mut row| {
+            row.resize(columns, String::new());
+            row
+        })
+        .collect();
+    (rows.len() >= 2).then_some(Table { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(table: &Table, row: usize) -> Vec<&str> {
+        table.rows[row].iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn tab_pipe_and_space_layouts_are_split() {
+        let tabs = detect("Name\tQty\tPrice\nApple\t3\t1,20\nPear\t\t0,90").unwrap();
+        assert_eq!(cells(&tabs, 2), vec!["Pear", "", "0,90"]);
+
+        let pipes = detect("| Region | Sales |\n|---|---:|\n| North | 1,200 |\n| South | 950 |").unwrap();
+        assert_eq!(pipes.rows.len(), 3);
+        assert_eq!(cells(&pipes, 1), vec!["North", "1,200"]);
+
+        let spaces = detect("Region     Q1 Sales    Q2 Sales\nNorth      1,200       1,400\nSouth East  950         1,010").unwrap();
+        assert_eq!(spaces.columns(), 3);
+        assert_eq!(cells(&spaces, 0), vec!["Region", "Q1 Sales", "Q2 Sales"]);
+    }
+
+    #[test]
+    fn prose_and_single_lines_are_not_tables() {
+        assert_eq!(detect("Just one line  with  gaps"), None);
+        assert_eq!(detect("A sentence of prose.\nAnother sentence follows it."), None);
+        assert_eq!(detect("TypeError: x is undefined\n    at foo (app.js:3:9)\n    at bar (app.js:9:1)"), None);
+    }
+}
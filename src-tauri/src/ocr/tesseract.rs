@@ -0,0 +1,100 @@
+//! Linux OCR via the Tesseract command-line tool.
+//!
+//! This module is only compiled on Linux, which has no OCR engine of its
+//! own. The `tesseract` binary (packaged by every major distribution)
+//! reads a PNG on stdin and writes the text to stdout; it is found on
+//! `PATH` when needed, so the app still runs without it and a snip just
+//! yields no text. As on Windows, both recognition levels run the same
+//! engine.
+
+use super::spans::{self, TextSpan};
+use super::{OcrOutput, RecognitionLevel, RgbaView};
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use std::io::{Cursor, Write};
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+/// Run OCR on in-memory PNG bytes via `tesseract stdin stdout`.
+pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) -> OcrOutput {
+    let start = Instant::now();
+    output(run(&png_bytes, &[]), start, level)
+}
+
+/// Run OCR on RGBA pixels; Tesseract only reads image files, so the
+/// rows are packed and encoded to PNG first.
+pub fn recognize_text_from_rgba(view: &RgbaView, level: RecognitionLevel) -> OcrOutput {
+    let start = Instant::now();
+    output(png_of(view).and_then(|png| run(&png, &[])), start, level)
+}
+
+/// Words with their boxes, from Tesseract's TSV output.
+pub fn recognize_spans(view: &RgbaView) -> Vec<TextSpan> {
+    match png_of(view).and_then(|png| run(&png, &["tsv"])) {
+        Ok(tsv) => spans::from_tesseract_tsv(&tsv),
+        Err(e) => {
+            log::error!("[OCR] Tesseract OCR failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn png_of(view: &RgbaView) -> Result<Vec<u8>, String> {
+    let pixels: Vec<u8> = view.rows().flatten().copied().collect();
+    let image = RgbaImage::from_raw(view.width, view.height, pixels).ok_or_else(|| "Pixel buffer doesn't match its size".to_string())?;
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(image).write_to(&mut Cursor::new(&mut png), ImageFormat::Png).map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+/// `tesseract stdin stdout` with `configs` (e.g. `tsv`) on `png`.
+fn run(png: &[u8], configs: &[&str]) -> Result<String, String> {
+    let mut child = Command::new("tesseract")
+        .args(["stdin", "stdout"])
+        .args(configs)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("tesseract not available: {}", e))?;
+    child.stdin.take().ok_or("tesseract has no stdin")?.write_all(png).map_err(|e| e.to_string())?;
+    let out = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(format!("tesseract exited with {}", out.status));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn output(recognized: Result<String, String>, start: Instant, level: RecognitionLevel) -> OcrOutput {
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let text = recognized.unwrap_or_else(|e| {
+        log::error!("[OCR] Tesseract OCR failed: {}", e);
+        String::new()
+    });
+    // The plain-text output has no confidences; same default as Windows.
+    let confidence = if text.is_empty() { 0.0 } else { 0.85 };
+    OcrOutput {
+        char_count: text.len() as i64,
+        text,
+        latency_ms,
+        confidence,
+        recognition_level: match level {
+            RecognitionLevel::Fast => "fast",
+            RecognitionLevel::Accurate => "accurate",
+        }
+        .to_string(),
+    }
+}
+
+/// Whether the `tesseract` binary is on `PATH`.
+pub fn available() -> bool {
+    which::which("tesseract").is_ok()
+}
+
+/// Tesseract starts fresh for each snip; just report whether it's there.
+pub fn warm_up() {
+    if available() {
+        log::info!("[OCR] Tesseract found");
+    } else {
+        log::warn!("[OCR] tesseract not found on PATH; snips will have no text");
+    }
+}
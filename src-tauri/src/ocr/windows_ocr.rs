@@ -3,15 +3,16 @@
 //! This module is only compiled on Windows. It uses the `windows` crate
 //! to access the WinRT OcrEngine API, which ships with Windows 10+.
 //!
-//! NOTE: This code has been written on macOS and needs testing on Windows.
-//! The WinRT API surface has been verified against Microsoft docs, but
-//! the exact `windows` crate bindings may need minor adjustments.
+//! Exercised end to end on Windows by `tests/windows_ocr.rs` (PNG and
+//! in-place pixel paths), which the Windows CI job runs with the rest of
+//! `cargo test`.
 
-use super::{OcrOutput, RecognitionLevel};
+use super::spans::TextSpan;
+use super::{OcrOutput, RecognitionLevel, RgbaView};
 use std::time::Instant;
 
 use windows::{
-    Graphics::Imaging::BitmapDecoder,
+    Graphics::Imaging::{BitmapDecoder, BitmapPixelFormat, SoftwareBitmap},
     Media::Ocr::OcrEngine,
     Storage::Streams::{DataWriter, InMemoryRandomAccessStream},
 };
@@ -22,8 +23,17 @@ use windows::{
 /// language from installed Windows language packs).
 pub fn recognize_text_from_bytes(png_bytes: Vec<u8>, level: RecognitionLevel) -> OcrOutput {
     let start = Instant::now();
+    output(recognize_inner(&png_bytes), start, level)
+}
 
-    match recognize_inner(&png_bytes) {
+/// Run OCR on raw RGBA pixels via Windows.Media.Ocr — no PNG decode.
+pub fn recognize_text_from_rgba(view: &RgbaView, level: RecognitionLevel) -> OcrOutput {
+    let start = Instant::now();
+    output(recognize_rgba_inner(view), start, level)
+}
+
+fn output(recognized: windows::core::Result<String>, start: Instant, level: RecognitionLevel) -> OcrOutput {
+    match recognized {
         Ok(text) => {
             let char_count = text.len() as i64;
             let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
@@ -77,12 +87,66 @@ fn recognize_inner(png_bytes: &[u8]) -> windows::core::Result<String> {
     let decoder = BitmapDecoder::CreateAsync(&stream)?.get()?;
     let bitmap = decoder.GetSoftwareBitmapAsync()?.get()?;
 
+    recognize_bitmap(&bitmap)
+}
+
+/// OCR of pixels that are already decoded.
+///
+/// Flow: rows → one WinRT buffer (dropping the crop's stride)
+///       → Rgba8 SoftwareBitmap → Bgra8 → OcrEngine::RecognizeAsync → text
+fn recognize_rgba_inner(view: &RgbaView) -> windows::core::Result<String> {
+    recognize_bitmap(&bitmap_of(view)?)
+}
+
+/// Words with their boxes: WinRT reports each word's rectangle in pixels.
+pub fn recognize_spans(view: &RgbaView) -> Vec<TextSpan> {
+    let spans = || -> windows::core::Result<Vec<TextSpan>> {
+        let engine = OcrEngine::TryCreateFromUserProfileLanguages()?;
+        let result = engine.RecognizeAsync(&bitmap_of(view)?)?.get()?;
+        let mut spans = Vec::new();
+        for (line, ocr_line) in result.Lines()?.into_iter().enumerate() {
+            for word in ocr_line.Words()? {
+                let rect = word.BoundingRect()?;
+                spans.push(TextSpan {
+                    text: word.Text()?.to_string(),
+                    x: f64::from(rect.X),
+                    y: f64::from(rect.Y),
+                    width: f64::from(rect.Width),
+                    height: f64::from(rect.Height),
+                    line: line as u32,
+                });
+            }
+        }
+        Ok(spans)
+    };
+    spans().unwrap_or_else(|e| {
+        log::error!("[OCR] Windows OCR failed: {}", e);
+        Vec::new()
+    })
+}
+
+fn bitmap_of(view: &RgbaView) -> windows::core::Result<SoftwareBitmap> {
+    let writer = DataWriter::new()?;
+    for row in view.rows() {
+        writer.WriteBytes(row)?;
+    }
+    let buffer = writer.DetachBuffer()?;
+    let rgba = SoftwareBitmap::CreateCopyFromBuffer(
+        &buffer,
+        BitmapPixelFormat::Rgba8,
+        view.width as i32,
+        view.height as i32,
+    )?;
+    SoftwareBitmap::Convert(&rgba, BitmapPixelFormat::Bgra8)
+}
+
+fn recognize_bitmap(bitmap: &SoftwareBitmap) -> windows::core::Result<String> {
     // Step 4: Create OCR engine from user's installed language packs
     // TryCreateFromUserProfileLanguages auto-selects the best language
     let engine = OcrEngine::TryCreateFromUserProfileLanguages()?;
 
     // Step 5: Run OCR recognition
-    let result = engine.RecognizeAsync(&bitmap)?.get()?;
+    let result = engine.RecognizeAsync(bitmap)?.get()?;
 
     // Step 6: Extract text — OcrResult.Text() returns all lines joined
     let text = result.Text()?.to_string();
@@ -90,6 +154,11 @@ fn recognize_inner(png_bytes: &[u8]) -> windows::core::Result<String> {
     Ok(text)
 }
 
+/// Whether an engine can be created for the user's languages.
+pub fn available() -> bool {
+    OcrEngine::TryCreateFromUserProfileLanguages().is_ok()
+}
+
 /// Warm up Windows OCR engine by pre-loading the engine.
 ///
 /// This loads the OCR DLLs and language data on first call,
@@ -0,0 +1,133 @@
+//! First-run onboarding — guided setup with live permission checks.
+//!
+//! `get_onboarding_status` probes the machine every time it is called
+//! (screen capture permission, API keys) so the setup screen reflects
+//! changes the user just made in System Settings or the Settings panel.
+//! `run_onboarding_self_test` does a real capture + crop + OCR of a strip
+//! of the screen — no LLM call — and remembers the outcome.
+//!
+//! Until setup is finished or skipped, `open_if_needed` shows the setup
+//! window (`onboarding.html`) at launch. Completion and the last
+//! self-test live in `~/.config/omni-glass/onboarding.json`.
+
+pub mod steps;
+
+use crate::capture::{self, permission};
+use crate::llm;
+use crate::ocr;
+use crate::persist::JsonStore;
+use crate::windows::{self, spec::Window};
+use serde::{Deserialize, Serialize};
+use steps::{OnboardingStatus, Probes, SelfTest};
+
+/// Size of the strip the self-test recognizes (clamped to the screen).
+const TEST_REGION: (u32, u32) = (800, 240);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OnboardingRecord {
+    #[serde(default)]
+    completed: bool,
+    #[serde(default)]
+    self_test: Option<SelfTest>,
+}
+
+static RECORD: JsonStore<OnboardingRecord> = JsonStore::new("onboarding.json");
+
+fn current() -> OnboardingRecord {
+    RECORD.get()
+}
+
+fn update(change: impl FnOnce(&mut OnboardingRecord)) -> Result<(), String> {
+    RECORD.update(change)
+}
+
+fn probe() -> Probes {
+    Probes {
+        macos: cfg!(target_os = "macos"),
+        screen_capture: permission::screen_capture_allowed(),
+        configured_providers: llm::provider::all_providers()
+            .into_iter()
+            .filter(|p| llm::provider::is_provider_configured(&p.id))
+            .map(|p| p.id)
+            .collect(),
+        active_provider: crate::settings_commands::resolve_provider(),
+    }
+}
+
+/// Capture the primary monitor, crop a centered strip, and OCR it.
+fn self_test() -> Result<(u64, u64, usize), String> {
+    if !permission::screen_capture_allowed() {
+        return Err("Screen capture is not permitted".to_string());
+    }
+    let start = std::time::Instant::now();
+    let screenshot = capture::capture_primary_monitor().map_err(|e| e.to_string())?;
+    let width = TEST_REGION.0.min(screenshot.width());
+    let height = TEST_REGION.1.min(screenshot.height());
+    let x = (screenshot.width() - width) / 2;
+    let y = (screenshot.height() - height) / 2;
+    let png = capture::crop_to_png_bytes(&screenshot, x, y, width, height).map_err(|e| e.to_string())?;
+    let capture_ms = start.elapsed().as_millis() as u64;
+
+    let start = std::time::Instant::now();
+    let output = ocr::recognize_text_from_bytes(png, ocr::RecognitionLevel::Fast);
+    let ocr_ms = start.elapsed().as_millis() as u64;
+    Ok((capture_ms, ocr_ms, output.text.chars().count()))
+}
+
+/// Show the setup window unless setup was finished or skipped before.
+/// Called once from `setup`.
+pub fn open_if_needed(app: &tauri::AppHandle) {
+    if current().completed {
+        return;
+    }
+    log::info!("[ONBOARDING] Setup not finished; opening the setup window");
+    if let Err(e) = windows::show(app, Window::Onboarding) {
+        log::warn!("[ONBOARDING] {}", e);
+    }
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: each setup step with its state and next action.
+#[tauri::command]
+pub fn get_onboarding_status() -> OnboardingStatus {
+    let record = current();
+    steps::build_status(&probe(), record.self_test.as_ref(), record.completed)
+}
+
+/// Tauri command: ask the OS for Screen Recording access. Returns
+/// whether it is granted now (macOS usually needs a relaunch).
+#[tauri::command]
+pub fn request_screen_capture_permission() -> bool {
+    let granted = permission::request_screen_capture();
+    log::info!("[ONBOARDING] Screen capture permission requested — granted: {}", granted);
+    granted
+}
+
+/// Tauri command: run the self-test snip and remember the outcome.
+#[tauri::command]
+pub async fn run_onboarding_self_test() -> Result<SelfTest, String> {
+    let outcome = tauri::async_runtime::spawn_blocking(self_test)
+        .await
+        .map_err(|e| format!("Self-test crashed: {}", e))?;
+    let at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let test = match outcome {
+        Ok((capture_ms, ocr_ms, chars)) => SelfTest { at, ok: true, capture_ms, ocr_ms, chars, error: None },
+        Err(e) => SelfTest { at, ok: false, capture_ms: 0, ocr_ms: 0, chars: 0, error: Some(e) },
+    };
+    log::info!("[ONBOARDING] Self-test {}", if test.ok { "passed" } else { "failed" });
+    update(|r| r.self_test = Some(test.clone()))?;
+    Ok(test)
+}
+
+/// Tauri command: the user finished or skipped setup. Closes the setup
+/// window.
+#[tauri::command]
+pub fn complete_onboarding(app: tauri::AppHandle) -> Result<(), String> {
+    update(|r| r.completed = true)?;
+    windows::close(&app, Window::Onboarding)
+}
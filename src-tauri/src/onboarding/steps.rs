@@ -0,0 +1,164 @@
+//! Onboarding checklist (pure, no I/O).
+//!
+//! `mod.rs` probes the system; this file turns the probes into the
+//! ordered steps the setup screen shows. Each step says what state it is
+//! in and, when it isn't done, what the user should do about it. The app
+//! is ready once every step is `Ok`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StepState {
+    Ok,
+    /// The user has to do something (grant, configure).
+    NeedsAction,
+    /// Tried and failed.
+    Failed,
+    /// Not tried yet.
+    Pending,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Step {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub state: StepState,
+    pub detail: String,
+    /// What to do next, when not `Ok`.
+    pub remedy: Option<String>,
+}
+
+/// Outcome of the last self-test snip.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTest {
+    /// Unix seconds.
+    pub at: u64,
+    pub ok: bool,
+    pub capture_ms: u64,
+    pub ocr_ms: u64,
+    /// Characters recognized in the test region.
+    pub chars: usize,
+    pub error: Option<String>,
+}
+
+/// What `mod.rs` found on this machine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Probes {
+    pub macos: bool,
+    pub screen_capture: bool,
+    /// Providers with an API key.
+    pub configured_providers: Vec<String>,
+    /// Provider that will be used.
+    pub active_provider: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStatus {
+    pub steps: Vec<Step>,
+    /// Every step is `Ok`.
+    pub ready: bool,
+    /// The user finished (or skipped) setup before.
+    pub completed: bool,
+}
+
+fn screen_step(p: &Probes) -> Step {
+    let (state, detail, remedy) = match (p.screen_capture, p.macos) {
+        (true, _) => (StepState::Ok, "Omni-Glass can capture the screen.".to_string(), None),
+        (false, true) => (
+            StepState::NeedsAction,
+            "Screen Recording permission has not been granted.".to_string(),
+            Some("Allow Omni-Glass in System Settings → Privacy & Security → Screen Recording, then relaunch.".to_string()),
+        ),
+        (false, false) => (
+            StepState::Failed,
+            "No monitor could be captured.".to_string(),
+            Some("Check that a display is connected and that screen capture isn't blocked by policy.".to_string()),
+        ),
+    };
+    Step { id: "screen_capture", title: "Screen capture", state, detail, remedy }
+}
+
+fn provider_step(p: &Probes) -> Step {
+    let (state, detail, remedy) = if p.configured_providers.is_empty() {
+        (
+            StepState::NeedsAction,
+            "No AI provider has an API key.".to_string(),
+            Some("Add an Anthropic or Gemini API key in Settings → AI Provider.".to_string()),
+        )
+    } else if !p.configured_providers.contains(&p.active_provider) {
+        (
+            StepState::NeedsAction,
+            format!("The active provider ({}) has no API key.", p.active_provider),
+            Some(format!("Add a key for {} or switch to {} in Settings.", p.active_provider, p.configured_providers.join(" / "))),
+        )
+    } else {
+        (StepState::Ok, format!("Using {}.", p.active_provider), None)
+    };
+    Step { id: "provider", title: "AI provider", state, detail, remedy }
+}
+
+fn self_test_step(test: Option<&SelfTest>) -> Step {
+    let (state, detail, remedy) = match test {
+        None => (StepState::Pending, "Not run yet.".to_string(), Some("Run the self-test.".to_string())),
+        Some(t) if t.ok => (
+            StepState::Ok,
+            format!("Captured in {}ms, recognized {} characters in {}ms.", t.capture_ms, t.chars, t.ocr_ms),
+            None,
+        ),
+        Some(t) => (
+            StepState::Failed,
+            t.error.clone().unwrap_or_else(|| "Self-test failed.".to_string()),
+            Some("Fix the steps above, then run the self-test again.".to_string()),
+        ),
+    };
+    Step { id: "self_test", title: "Self-test snip", state, detail, remedy }
+}
+
+pub fn build_status(probes: &Probes, self_test: Option<&SelfTest>, completed: bool) -> OnboardingStatus {
+    let steps = vec![screen_step(probes), provider_step(probes), self_test_step(self_test)];
+    let ready = steps.iter().all(|s| s.state == StepState::Ok);
+    OnboardingStatus { steps, ready, completed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probes(screen_capture: bool, configured: &[&str]) -> Probes {
+        Probes {
+            macos: true,
+            screen_capture,
+            configured_providers: configured.iter().map(|s| s.to_string()).collect(),
+            active_provider: "anthropic".to_string(),
+        }
+    }
+
+    #[test]
+    fn steps_report_what_is_missing() {
+        let status = build_status(&probes(false, &[]), None, false);
+        let states: Vec<StepState> = status.steps.iter().map(|s| s.state).collect();
+        assert_eq!(states, vec![StepState::NeedsAction, StepState::NeedsAction, StepState::Pending]);
+        assert!(!status.ready);
+        assert!(status.steps.iter().all(|s| s.remedy.is_some()));
+
+        let status = build_status(&probes(true, &["gemini"]), None, false);
+        assert!(status.steps[1].detail.contains("anthropic"));
+    }
+
+    #[test]
+    fn ready_once_the_self_test_passes() {
+        let pass = SelfTest { at: 1, ok: true, capture_ms: 40, ocr_ms: 30, chars: 12, error: None };
+        let status = build_status(&probes(true, &["anthropic"]), Some(&pass), true);
+        assert!(status.ready);
+        assert!(status.completed);
+
+        let fail = SelfTest { ok: false, error: Some("Capture failed".into()), ..pass };
+        let status = build_status(&probes(true, &["anthropic"]), Some(&fail), true);
+        assert_eq!(status.steps[2].state, StepState::Failed);
+        assert_eq!(status.steps[2].detail, "Capture failed");
+    }
+}
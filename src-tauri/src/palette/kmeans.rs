@@ -0,0 +1,149 @@
+//! Dominant colors of a set of pixels by k-means (pure, no I/O).
+//!
+//! Seeds come from a 4-bit-per-channel histogram — the most common bins
+//! that are visibly different from each other — so the result is the
+//! same for the same image, and a small accent color that fills its own
+//! bin still gets a swatch. Clusters that end up nearly the same color
+//! are merged, and swatches are ordered by how much of the image they
+//! cover.
+
+/// Most swatches returned.
+pub const MAX_COLORS: usize = 6;
+
+const ITERATIONS: usize = 12;
+/// Squared RGB distance under which two colors count as one swatch.
+const MERGE_DISTANCE: f32 = 28.0 * 28.0;
+
+/// One dominant color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Swatch {
+    pub rgb: [u8; 3],
+    /// Fraction of the pixels, 0–1.
+    pub share: f32,
+}
+
+impl Swatch {
+    /// `#1A2B3C`.
+    pub fn hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.rgb[0], self.rgb[1], self.rgb[2])
+    }
+}
+
+fn distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+fn nearest(centers: &[[f32; 3]], point: &[f32; 3]) -> usize {
+    (0..centers.len())
+        .min_by(|&a, &b| distance(&centers[a], point).total_cmp(&distance(&centers[b], point)))
+        .unwrap_or(0)
+}
+
+/// Centers of the most common histogram bins, at least `MERGE_DISTANCE` apart.
+fn seeds(points: &[[f32; 3]], k: usize) -> Vec<[f32; 3]> {
+    let mut bins = vec![(0u32, [0f32; 3]); 4096];
+    for p in points {
+        let bin = (p[0] as usize >> 4) << 8 | (p[1] as usize >> 4) << 4 | p[2] as usize >> 4;
+        bins[bin].0 += 1;
+        (0..3).for_each(|i| bins[bin].1[i] += p[i]);
+    }
+    bins.retain(|(count, _)| *count > 0);
+    bins.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+    let mut seeds: Vec<[f32; 3]> = Vec::with_capacity(k);
+    for (count, sum) in bins {
+        let mean = sum.map(|s| s / count as f32);
+        if seeds.iter().all(|s| distance(s, &mean) >= MERGE_DISTANCE) {
+            seeds.push(mean);
+            if seeds.len() == k {
+                break;
+            }
+        }
+    }
+    seeds
+}
+
+/// Up to `k` dominant colors of `pixels`, most common first.
+pub fn dominant(pixels: &[[u8; 3]], k: usize) -> Vec<Swatch> {
+    let points: Vec<[f32; 3]> = pixels.iter().map(|p| p.map(f32::from)).collect();
+    let mut centers = seeds(&points, k.min(MAX_COLORS));
+    if centers.is_empty() {
+        return Vec::new();
+    }
+    let mut labels = vec![usize::MAX; points.len()];
+    let mut counts = vec![0usize; centers.len()];
+    for _ in 0..ITERATIONS {
+        let mut changed = false;
+        for (label, point) in labels.iter_mut().zip(&points) {
+            let closest = nearest(&centers, point);
+            changed |= closest != *label;
+            *label = closest;
+        }
+        let mut sums = vec![[0f64; 3]; centers.len()];
+        counts.fill(0);
+        for (&label, point) in labels.iter().zip(&points) {
+            counts[label] += 1;
+            (0..3).for_each(|i| sums[label][i] += f64::from(point[i]));
+        }
+        for (c, center) in centers.iter_mut().enumerate() {
+            if counts[c] > 0 {
+                *center = sums[c].map(|s| (s / counts[c] as f64) as f32);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Fold each cluster into a bigger one of nearly the same color
+    let mut clusters: Vec<([f32; 3], usize)> = centers.into_iter().zip(counts).filter(|(_, n)| *n > 0).collect();
+    clusters.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let mut merged: Vec<([f32; 3], usize)> = Vec::new();
+    for (center, count) in clusters {
+        match merged.iter_mut().find(|(c, _)| distance(c, &center) < MERGE_DISTANCE) {
+            Some((c, n)) => {
+                let total = (*n + count) as f32;
+                *c = [0, 1, 2].map(|i| (c[i] * *n as f32 + center[i] * count as f32) / total);
+                *n += count;
+            }
+            None => merged.push((center, count)),
+        }
+    }
+    merged.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    merged
+        .into_iter()
+        .map(|(center, count)| Swatch {
+            rgb: center.map(|v| v.round().clamp(0.0, 255.0) as u8),
+            share: count as f32 / points.len() as f32,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_dominant_colors_in_order() {
+        let mut pixels = vec![[250, 250, 250]; 600];
+        pixels.extend(vec![[20, 60, 200]; 300]);
+        pixels.extend(vec![[230, 40, 40]; 100]);
+        // Slight noise around the blue stays one swatch
+        pixels.extend(vec![[24, 64, 196]; 50]);
+        let swatches = dominant(&pixels, MAX_COLORS);
+        let hexes: Vec<String> = swatches.iter().map(Swatch::hex).collect();
+        assert_eq!(hexes.len(), 3);
+        assert_eq!(hexes[0], "#FAFAFA");
+        assert_eq!(swatches[1].rgb, [21, 61, 199]);
+        assert_eq!(hexes[2], "#E62828");
+        assert!((swatches.iter().map(|s| s.share).sum::<f32>() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert!(dominant(&[], MAX_COLORS).is_empty());
+        assert_eq!(dominant(&[[0, 0, 0]; 10], MAX_COLORS), vec![Swatch { rgb: [0, 0, 0], share: 1.0 }]);
+        let gradient: Vec<[u8; 3]> = (0..=255).map(|v| [v, v, v]).collect();
+        assert!(dominant(&gradient, 3).len() <= 3);
+        assert_eq!(Swatch { rgb: [255, 8, 160], share: 0.5 }.hex(), "#FF08A0");
+    }
+}
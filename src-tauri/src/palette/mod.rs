@@ -0,0 +1,111 @@
+//! Palette domain — "Extract Color Palette" for mockups and screenshots.
+//!
+//! A snip with little text is likely a picture, so it gets an
+//! `extract_palette` action. `kmeans.rs` finds the dominant colors of the
+//! stored crop; this file downsamples the crop, renders the colors as a
+//! swatch strip, and returns hex codes with the PNG. Runs offline; the
+//! action menu calls it directly (`extract_palette`).
+
+pub mod kmeans;
+
+use crate::i18n::t;
+use crate::llm;
+use crate::llm::types::{Action, ActionMenu};
+use image::{Rgb, RgbImage};
+use serde::Serialize;
+
+/// Action id, handled by the action menu through `extract_palette`.
+pub const PALETTE_ACTION: &str = "extract_palette";
+
+/// Snips with at most this many non-space characters are offered the action.
+const MAX_TEXT_CHARS: usize = 40;
+/// The crop is shrunk to fit this square before clustering.
+const SAMPLE_PIXELS: u32 = 128;
+/// Side of one swatch in the rendered strip.
+const SWATCH_PIXELS: u32 = 64;
+
+/// One color of the palette.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteColor {
+    /// `#1A2B3C`.
+    pub hex: String,
+    pub rgb: [u8; 3],
+    /// Share of the snip, 0–1.
+    pub share: f32,
+}
+
+/// Dominant colors of a snip, most common first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Palette {
+    pub colors: Vec<PaletteColor>,
+    /// Base64-encoded PNG of the swatches side by side.
+    pub png: String,
+}
+
+/// Append the palette action when `text` is short enough that the snip is
+/// mostly picture. Returns whether it was added.
+pub fn augment(menu: &mut ActionMenu, text: &str) -> bool {
+    let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    if chars > MAX_TEXT_CHARS || menu.actions.iter().any(|a| a.id == PALETTE_ACTION) {
+        return false;
+    }
+    let priority = menu.actions.iter().map(|a| a.priority).max().unwrap_or(0).saturating_add(1);
+    menu.actions.push(Action {
+        id: PALETTE_ACTION.to_string(),
+        label: t("menu.extractPalette"),
+        icon: "palette".to_string(),
+        priority,
+        description: t("menu.extractPaletteDescription"),
+        requires_execution: false,
+    });
+    true
+}
+
+fn sample(png: &[u8]) -> Result<Vec<[u8; 3]>, String> {
+    let image = image::load_from_memory(png).map_err(|e| format!("Could not read the snip: {}", e))?;
+    let pixels = image
+        .thumbnail(SAMPLE_PIXELS, SAMPLE_PIXELS)
+        .to_rgba8()
+        .pixels()
+        .filter(|p| p[3] >= 128)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    Ok(pixels)
+}
+
+fn render(swatches: &[kmeans::Swatch]) -> Result<Vec<u8>, String> {
+    let width = SWATCH_PIXELS * swatches.len() as u32;
+    let strip = RgbImage::from_fn(width, SWATCH_PIXELS, |x, _| Rgb(swatches[(x / SWATCH_PIXELS) as usize].rgb));
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgb8(strip)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the dominant colors of the current snip and a swatch PNG.
+#[tauri::command]
+pub fn extract_palette(state: tauri::State<'_, llm::ActionMenuState>) -> Result<Palette, String> {
+    let png = {
+        let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
+        guard.clone().ok_or("No snip image available — snip first")?
+    };
+    let pixels = sample(&png)?;
+    let swatches = kmeans::dominant(&pixels, kmeans::MAX_COLORS);
+    if swatches.is_empty() {
+        return Err("The snip has no visible pixels".to_string());
+    }
+    let strip = render(&swatches)?;
+    log::info!("[PALETTE] {} colors from {} pixels", swatches.len(), pixels.len());
+    Ok(Palette {
+        colors: swatches
+            .iter()
+            .map(|s| PaletteColor { hex: s.hex(), rgb: s.rgb, share: s.share })
+            .collect(),
+        png: base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &strip),
+    })
+}
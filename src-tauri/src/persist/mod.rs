@@ -0,0 +1,197 @@
+//! Persist domain — small JSON files under `~/.config/omni-glass/`.
+//!
+//! Settings, approvals, grants, logs: each is one serde value kept in
+//! memory and rewritten whole on every change. `JsonStore` is that
+//! pattern once — the value is read on first use (a missing or unreadable
+//! file gives the default), `update` changes it and writes it back, and
+//! `erase` shreds the old file before writing what is left (history
+//! purge). Each store is a `static`, so its lock also orders the writes.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One JSON file in the config directory, cached in memory.
+pub struct JsonStore<T> {
+    file: &'static str,
+    /// Indented, for files people open; compact for large logs.
+    pretty: bool,
+    /// Builds the value from the file; used once, on first access.
+    load: fn(&Path) -> T,
+    /// In-memory copy; `None` until first read from disk.
+    value: Mutex<Option<T>>,
+}
+
+/// The file at `path` as a `T`; the default if it is missing or invalid.
+pub fn read_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Write `value` to `path`, creating the config directory if needed.
+pub fn write_json<T: Serialize>(path: &Path, value: &T, pretty: bool) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = if pretty { serde_json::to_string_pretty(value) } else { serde_json::to_string(value) };
+    let json = json.map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Overwrite a file with zeros, flush it to disk, then delete it.
+/// A missing file is not an error.
+pub fn shred(path: &Path) -> Result<(), String> {
+    use std::io::Write;
+    let len = match std::fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let zeros = vec![0u8; 64 * 1024];
+    let mut left = len;
+    while left > 0 {
+        let n = left.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..n]).map_err(|e| format!("Failed to overwrite {}: {}", path.display(), e))?;
+        left -= n as u64;
+    }
+    file.sync_all().map_err(|e| format!("Failed to flush {}: {}", path.display(), e))?;
+    drop(file);
+    std::fs::remove_file(path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+}
+
+impl<T: DeserializeOwned + Default> JsonStore<T> {
+    /// A store for `file`, written indented.
+    pub const fn new(file: &'static str) -> Self {
+        Self::with_loader(file, read_json::<T>)
+    }
+
+    /// A store for `file`, written without indentation.
+    pub const fn compact(file: &'static str) -> Self {
+        JsonStore { file, pretty: false, load: read_json::<T>, value: Mutex::new(None) }
+    }
+}
+
+impl<T: Default> JsonStore<T> {
+    /// A store for `file` whose value is built by `load` (defaults filled
+    /// in, migrations), written indented.
+    pub const fn with_loader(file: &'static str, load: fn(&Path) -> T) -> Self {
+        JsonStore { file, pretty: true, load, value: Mutex::new(None) }
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        dirs::config_dir().map(|c| c.join("omni-glass").join(self.file))
+    }
+
+    /// Run `f` on the value under the lock, reading the file first if
+    /// this is the first use.
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.value.lock().unwrap_or_else(|e| e.into_inner());
+        f(guard.get_or_insert_with(|| self.path().map(|p| (self.load)(&p)).unwrap_or_default()))
+    }
+
+    /// Read the value under the lock.
+    pub fn read<R>(&self, read: impl FnOnce(&T) -> R) -> R {
+        self.with(|value| read(value))
+    }
+}
+
+impl<T: Default + Clone> JsonStore<T> {
+    /// A copy of the value.
+    pub fn get(&self) -> T {
+        self.read(T::clone)
+    }
+}
+
+impl<T: Default + Serialize> JsonStore<T> {
+    fn save(&self, value: &T) -> Result<(), String> {
+        let path = self.path().ok_or("Could not determine config directory")?;
+        write_json(&path, value, self.pretty)
+    }
+
+    /// Apply `change` and write the value out. The change stays in
+    /// memory even if the write fails.
+    pub fn update<R>(&self, change: impl FnOnce(&mut T) -> R) -> Result<R, String> {
+        self.with(|value| {
+            let out = change(value);
+            self.save(value)?;
+            Ok(out)
+        })
+    }
+
+    /// Like `update`, for bookkeeping that goes on when the disk is full
+    /// or read-only: a failed write is logged, not returned.
+    pub fn update_logged<R>(&self, change: impl FnOnce(&mut T) -> R) -> R {
+        self.with(|value| {
+            let out = change(value);
+            if let Err(e) = self.save(value) {
+                log::warn!("[PERSIST] {}", e);
+            }
+            out
+        })
+    }
+
+    /// Like `update`, but only written when `change` returns `true`
+    /// (throttled bookkeeping).
+    pub fn update_if(&self, change: impl FnOnce(&mut T) -> bool) -> Result<(), String> {
+        self.with(|value| if change(value) { self.save(value) } else { Ok(()) })
+    }
+
+    /// Apply `change` and write the value out, to disk and memory alike,
+    /// only if it succeeds.
+    pub fn try_update<R>(&self, change: impl FnOnce(&mut T) -> Result<R, String>) -> Result<R, String>
+    where
+        T: Clone,
+    {
+        self.with(|value| {
+            let mut next = value.clone();
+            let out = change(&mut next)?;
+            self.save(&next)?;
+            *value = next;
+            Ok(out)
+        })
+    }
+
+    /// Apply `change`, shred the old file, then write what is left: what
+    /// the file held before can't be read back from the disk (purges).
+    pub fn erase(&self, change: impl FnOnce(&mut T)) -> Result<(), String> {
+        self.with(|value| {
+            change(value);
+            if let Some(path) = self.path() {
+                shred(&path)?;
+            }
+            self.save(value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shred_removes_the_file() {
+        let path = std::env::temp_dir().join(format!("og-shred-{}", std::process::id()));
+        std::fs::write(&path, vec![7u8; 100_000]).unwrap();
+        shred(&path).unwrap();
+        assert!(!path.exists());
+        assert!(shred(&path).is_ok());
+    }
+
+    #[test]
+    fn files_round_trip_and_bad_ones_read_as_default() {
+        let path = std::env::temp_dir().join(format!("og-persist-{}", std::process::id())).join("store.json");
+        write_json(&path, &vec![1u32, 2, 3], false).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1,2,3]");
+        assert_eq!(read_json::<Vec<u32>>(&path), vec![1, 2, 3]);
+        std::fs::write(&path, "{not json").unwrap();
+        assert_eq!(read_json::<Vec<u32>>(&path), Vec::<u32>::new());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
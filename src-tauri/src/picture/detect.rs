@@ -0,0 +1,52 @@
+//! Is a snip a picture rather than text? (pure, no I/O)
+//!
+//! Decided from the OCR result alone, before any LLM call: classify
+//! on a handful of stray characters guesses badly, so a snip with no
+//! text, or with a little text spread thin over a large image (a
+//! watermark on a photo, a label on a chart), or with a little text OCR
+//! itself doubts, gets the image menu instead.
+
+/// Snips with more non-space characters than this are text.
+const MAX_CHARS: usize = 60;
+/// Characters per 10,000 pixels below which the text is incidental.
+const MIN_DENSITY: f64 = 1.0;
+/// OCR confidence below which a short read is taken for noise.
+const MIN_CONFIDENCE: f64 = 0.4;
+
+/// Whether a `width`×`height` snip OCR read as `text` with `confidence`
+/// is a picture.
+pub fn is_picture(text: &str, confidence: f64, width: u32, height: u32) -> bool {
+    let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+    if chars == 0 {
+        return true;
+    }
+    if chars > MAX_CHARS {
+        return false;
+    }
+    let area = f64::from(width) * f64::from(height) / 10_000.0;
+    confidence < MIN_CONFIDENCE || (area > 0.0 && chars as f64 / area < MIN_DENSITY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_or_sparse_text_is_a_picture() {
+        assert!(is_picture("", 0.0, 40, 40));
+        assert!(is_picture(" \n ", 0.0, 800, 600));
+        // A watermark across a photo
+        assert!(is_picture("Shutterstock", 0.85, 1200, 800));
+        // A short read OCR doubts
+        assert!(is_picture("~ii ,l", 0.2, 200, 100));
+    }
+
+    #[test]
+    fn short_dense_or_long_text_is_text() {
+        // A button
+        assert!(!is_picture("Submit", 0.85, 120, 40));
+        // A paragraph, however large the snip
+        let paragraph = "The quick brown fox jumps over the lazy dog, then keeps on running until it is far away";
+        assert!(!is_picture(paragraph, 0.85, 4000, 3000));
+    }
+}
@@ -0,0 +1,166 @@
+//! Picture domain — actions for snips that are images, not text.
+//!
+//! A snip of a photo, icon, chart, or mockup has little or no text, and
+//! classify guesses badly from a few stray characters. `detect.rs`
+//! decides from the OCR result alone, before any LLM call, whether a
+//! snip is a picture; the pipeline then skips classify and shows
+//! `menu()`: Describe Image (a vision model, `llm::vision`), Copy Image,
+//! Save Image, Extract Color Palette (`palette/`), and Find Similar on
+//! Screen, which captures the screen again, looks for other places the
+//! snip appears (`similar.rs`), and can snip any of them. All but
+//! Describe Image run offline.
+
+pub mod detect;
+pub mod similar;
+
+use crate::capture;
+use crate::deeplink::parse::Region;
+use crate::i18n::t;
+use crate::llm;
+use crate::llm::types::{Action, ActionMenu};
+use image::DynamicImage;
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// Action ids. Describe runs through `execute_action`; the others are
+/// handled by the action menu.
+pub const DESCRIBE_ACTION: &str = "describe_image";
+pub const COPY_ACTION: &str = "copy_image";
+pub const SAVE_ACTION: &str = "save_image";
+pub const SIMILAR_ACTION: &str = "find_similar";
+
+/// Content type of a picture snip's menu (for `actions.defaults`).
+pub const CONTENT_TYPE: &str = "image";
+
+/// Most places reported by Find Similar on Screen.
+const MAX_MATCHES: usize = 8;
+/// Long edge of a match's thumbnail.
+const THUMBNAIL_EDGE: u32 = 96;
+/// A match covering this share of the snipped region is the snip itself.
+const SELF_OVERLAP: f64 = 0.5;
+
+/// The screen of the last search and what was found on it, so a match
+/// can be snipped from the same pixels the user saw.
+static SEARCH: Mutex<Option<(DynamicImage, Vec<similar::Match>)>> = Mutex::new(None);
+
+/// One place on screen that looks like the snip.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarMatch {
+    /// Screen pixels.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Correlation, up to 1.
+    pub score: f64,
+    /// Base64-encoded PNG thumbnail.
+    pub png: String,
+}
+
+fn action(id: &str, label: &str, icon: &str, priority: u8, requires_execution: bool) -> Action {
+    Action {
+        id: id.to_string(),
+        label: t(label),
+        icon: icon.to_string(),
+        priority,
+        description: t(&format!("{}Description", label)),
+        requires_execution,
+    }
+}
+
+/// The menu for a picture snip. Describe Image is left out when cloud
+/// calls are off; Copy Text is added when OCR found some text.
+pub fn menu(text: &str) -> ActionMenu {
+    let mut entries = Vec::new();
+    if crate::settings::cloud_allowed().is_ok() {
+        entries.push((DESCRIBE_ACTION, "menu.describeImage", "eye", true));
+    }
+    entries.push((COPY_ACTION, "menu.copyImage", "clipboard", false));
+    entries.push((SAVE_ACTION, "menu.saveImage", "download", false));
+    entries.push((crate::palette::PALETTE_ACTION, "menu.extractPalette", "palette", false));
+    entries.push((SIMILAR_ACTION, "menu.findSimilar", "search", false));
+    if !text.trim().is_empty() {
+        entries.push(("copy_text", "menu.copyText", "clipboard", false));
+    }
+    let actions = entries
+        .into_iter()
+        .zip(1u8..)
+        .map(|((id, label, icon, execute), priority)| action(id, label, icon, priority, execute))
+        .collect();
+    ActionMenu {
+        content_type: CONTENT_TYPE.to_string(),
+        confidence: 1.0,
+        summary: t("menu.pictureSummary"),
+        detected_language: None,
+        actions,
+    }
+}
+
+/// Describe the snip `png` with a vision model.
+pub async fn describe(png: &[u8], registry: &crate::mcp::ToolRegistry) -> llm::ActionResult {
+    match image::load_from_memory(png) {
+        Ok(image) => llm::vision::describe_image(DESCRIBE_ACTION, &image, png, registry).await,
+        Err(e) => llm::ActionResult::error(DESCRIBE_ACTION, &format!("Could not read the snip: {}", e)),
+    }
+}
+
+/// Capture the screen and find the snip `png` on it, leaving out the
+/// snipped region itself.
+fn search(app: &tauri::AppHandle, png: &[u8]) -> Result<Vec<SimilarMatch>, String> {
+    let template = image::load_from_memory(png).map_err(|e| format!("Could not read the snip: {}", e))?.to_luma8();
+    let screen = crate::tray::checked_capture(app, capture::capture_for_snip(&crate::settings::current().capture))
+        .map_err(|e| e.to_string())?;
+    let own = crate::triggers::last_region();
+    let matches: Vec<similar::Match> = similar::find(&screen.to_luma8(), &template, MAX_MATCHES + 1)
+        .into_iter()
+        .filter(|m| own.is_none_or(|r| m.overlap(r.x, r.y, r.width, r.height) < SELF_OVERLAP))
+        .take(MAX_MATCHES)
+        .collect();
+    log::info!("[PICTURE] {} similar place(s) on a {}x{} screen", matches.len(), screen.width(), screen.height());
+    let found = matches
+        .iter()
+        .map(|m| {
+            let thumbnail = screen.crop_imm(m.x, m.y, m.width, m.height).thumbnail(THUMBNAIL_EDGE, THUMBNAIL_EDGE);
+            let png = capture::encode::png_fast(&thumbnail)?;
+            Ok(SimilarMatch {
+                x: m.x,
+                y: m.y,
+                width: m.width,
+                height: m.height,
+                score: m.score,
+                png: base64::Engine::encode(&base64::engine::general_purpose::STANDARD, png),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    *SEARCH.lock().unwrap_or_else(|e| e.into_inner()) = Some((screen, matches));
+    Ok(found)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: other places on screen that look like the current snip.
+#[tauri::command]
+pub async fn find_similar_on_screen(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, llm::ActionMenuState>,
+) -> Result<Vec<SimilarMatch>, String> {
+    let png = {
+        let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
+        guard.clone().ok_or("No snip image available — snip first")?
+    };
+    tauri::async_runtime::spawn_blocking(move || search(&app, &png)).await.map_err(|e| e.to_string())?
+}
+
+/// Tauri command: snip match `index` of the last search, from the screen
+/// it was found on. The new snip's menu opens under it.
+#[tauri::command]
+pub fn snip_similar(app: tauri::AppHandle, index: usize) -> Result<(), String> {
+    let (screen, found) = SEARCH.lock().unwrap_or_else(|e| e.into_inner()).take().ok_or("Search the screen first")?;
+    let m = found.get(index).ok_or_else(|| format!("No match {}", index))?;
+    let region = Region { x: m.x, y: m.y, width: m.width, height: m.height };
+    let scale = app.primary_monitor().ok().flatten().map_or(1.0, |m| m.scale_factor());
+    let menu = (f64::from(m.x) / scale, f64::from(m.y + m.height) / scale);
+    crate::deeplink::run_snip(&app, screen, region, menu);
+    Ok(())
+}
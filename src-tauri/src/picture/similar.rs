@@ -0,0 +1,182 @@
+//! Find where a snip appears on screen (pure, no I/O).
+//!
+//! Template matching by normalized cross-correlation on grayscale
+//! pixels, so a brighter or dimmer copy still matches. Both images are
+//! shrunk by the same factor first, until a full scan stays within
+//! `MAX_WORK` pixel comparisons; window sums come from integral images,
+//! so only the correlation itself costs a pass over the template.
+
+use image::imageops::{self, FilterType};
+use image::GrayImage;
+
+/// Score (−1 to 1) a match needs.
+const MIN_SCORE: f64 = 0.85;
+/// The template is shrunk to about this long edge before matching.
+const TEMPLATE_EDGE: f64 = 32.0;
+/// Template side below which matching isn't meaningful.
+const MIN_SIDE: u32 = 6;
+/// Pixel comparisons allowed for one scan.
+const MAX_WORK: f64 = 1.5e8;
+
+/// A place on screen that looks like the snip, in screen pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub score: f64,
+}
+
+impl Match {
+    /// Share of the smaller of the two rectangles they have in common.
+    pub fn overlap(&self, x: u32, y: u32, width: u32, height: u32) -> f64 {
+        let w = (self.x + self.width).min(x + width).saturating_sub(self.x.max(x));
+        let h = (self.y + self.height).min(y + height).saturating_sub(self.y.max(y));
+        let smaller = (self.width * self.height).min(width * height).max(1);
+        f64::from(w * h) / f64::from(smaller)
+    }
+}
+
+/// Up to `max` places in `screen` that look like `template`, best first,
+/// none overlapping another. Empty when the template is too small, larger
+/// than the screen, or a flat color (which would match everywhere).
+pub fn find(screen: &GrayImage, template: &GrayImage, max: usize) -> Vec<Match> {
+    let (tw, th) = template.dimensions();
+    let (sw, sh) = screen.dimensions();
+    if tw < MIN_SIDE || th < MIN_SIDE || tw > sw || th > sh {
+        return Vec::new();
+    }
+    let mut factor = (f64::from(tw.max(th)) / TEMPLATE_EDGE).max(1.0);
+    let shrunk = |side: u32, factor: f64| ((f64::from(side) / factor).round() as u32).max(1);
+    let work = |factor: f64| {
+        let (t, s) = ((shrunk(tw, factor), shrunk(th, factor)), (shrunk(sw, factor), shrunk(sh, factor)));
+        f64::from(t.0 * t.1) * f64::from((s.0 + 1).saturating_sub(t.0)) * f64::from((s.1 + 1).saturating_sub(t.1))
+    };
+    while work(factor) > MAX_WORK {
+        factor *= 1.25;
+    }
+    if shrunk(tw, factor) < MIN_SIDE || shrunk(th, factor) < MIN_SIDE {
+        return Vec::new();
+    }
+    let small = |image: &GrayImage| {
+        let (w, h) = image.dimensions();
+        if factor == 1.0 { image.clone() } else { imageops::resize(image, shrunk(w, factor), shrunk(h, factor), FilterType::Triangle) }
+    };
+    let (screen, template) = (small(screen), small(template));
+
+    let mut candidates = scan(&screen, &template);
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+    let mut found: Vec<Match> = Vec::new();
+    for (x, y, score) in candidates {
+        if found.len() == max {
+            break;
+        }
+        let at = |v: u32| (f64::from(v) * factor).round() as u32;
+        let candidate = Match { x: at(x), y: at(y), width: tw, height: th, score };
+        if found.iter().all(|m| m.overlap(candidate.x, candidate.y, tw, th) == 0.0) {
+            found.push(candidate);
+        }
+    }
+    found
+}
+
+/// Every position scoring at least `MIN_SCORE`: (x, y, score).
+fn scan(screen: &GrayImage, template: &GrayImage) -> Vec<(u32, u32, f64)> {
+    let (tw, th) = template.dimensions();
+    let (sw, sh) = screen.dimensions();
+    let n = f64::from(tw * th);
+    let mean = template.pixels().map(|p| f64::from(p[0])).sum::<f64>() / n;
+    let centered: Vec<f64> = template.pixels().map(|p| f64::from(p[0]) - mean).collect();
+    let norm = centered.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm < 1e-6 * n {
+        return Vec::new();
+    }
+    let (sum, squares) = integrals(screen);
+    let stride = sw as usize + 1;
+    let window = |table: &[f64], x: usize, y: usize| {
+        let (x1, y1) = (x + tw as usize, y + th as usize);
+        table[y1 * stride + x1] - table[y * stride + x1] - table[y1 * stride + x] + table[y * stride + x]
+    };
+    let raw = screen.as_raw();
+    let mut hits = Vec::new();
+    for y in 0..=(sh - th) as usize {
+        for x in 0..=(sw - tw) as usize {
+            let s = window(&sum, x, y);
+            let variance = window(&squares, x, y) - s * s / n;
+            if variance < 1e-6 {
+                continue;
+            }
+            let mut cross = 0.0;
+            for (row, weights) in centered.chunks(tw as usize).enumerate() {
+                let start = (y + row) * sw as usize + x;
+                cross += raw[start..start + tw as usize].iter().zip(weights).map(|(p, w)| f64::from(*p) * w).sum::<f64>();
+            }
+            let score = cross / (variance.sqrt() * norm);
+            if score >= MIN_SCORE {
+                hits.push((x as u32, y as u32, score));
+            }
+        }
+    }
+    hits
+}
+
+/// Summed-area tables of pixel values and their squares, one row and
+/// column larger than the image.
+fn integrals(image: &GrayImage) -> (Vec<f64>, Vec<f64>) {
+    let (w, h) = (image.width() as usize, image.height() as usize);
+    let mut sum = vec![0.0; (w + 1) * (h + 1)];
+    let mut squares = sum.clone();
+    for (y, row) in image.as_raw().chunks(w).enumerate() {
+        let (mut line, mut line_squares) = (0.0, 0.0);
+        for (x, p) in row.iter().enumerate() {
+            let v = f64::from(*p);
+            line += v;
+            line_squares += v * v;
+            let i = (y + 1) * (w + 1) + x + 1;
+            sum[i] = sum[i - w - 1] + line;
+            squares[i] = squares[i - w - 1] + line_squares;
+        }
+    }
+    (sum, squares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    /// A 12×10 glyph: a frame with a diagonal.
+    fn glyph(x: u32, y: u32) -> u8 {
+        if x == 0 || y == 0 || x == 11 || y == 9 || x == y { 230 } else { 30 }
+    }
+
+    #[test]
+    fn finds_each_copy_once_best_first() {
+        let mut screen = GrayImage::from_pixel(120, 80, Luma([90]));
+        for (ox, oy) in [(10, 10), (70, 50)] {
+            for y in 0..10 {
+                for x in 0..12 {
+                    // The second copy is dimmer; correlation doesn't mind
+                    let v = if ox == 70 { glyph(x, y) / 2 + 20 } else { glyph(x, y) };
+                    screen.put_pixel(ox + x, oy + y, Luma([v]));
+                }
+            }
+        }
+        let template = GrayImage::from_fn(12, 10, |x, y| Luma([glyph(x, y)]));
+        let found = find(&screen, &template, 5);
+        let places: Vec<(u32, u32)> = found.iter().map(|m| (m.x, m.y)).collect();
+        assert_eq!(places.len(), 2, "{:?}", found);
+        assert!(places.contains(&(10, 10)) && places.contains(&(70, 50)));
+        assert!(found[0].score >= found[1].score && found[1].score > 0.99);
+        assert_eq!(found[0].overlap(10, 10, 12, 10), 1.0);
+    }
+
+    #[test]
+    fn nothing_for_flat_tiny_or_oversized_templates() {
+        let screen = GrayImage::from_fn(64, 48, |x, y| Luma([((x * 7 + y * 13) % 255) as u8]));
+        assert!(find(&screen, &GrayImage::from_pixel(16, 16, Luma([128])), 5).is_empty());
+        assert!(find(&screen, &GrayImage::from_pixel(4, 4, Luma([10])), 5).is_empty());
+        assert!(find(&screen, &GrayImage::from_fn(80, 10, |x, _| Luma([x as u8])), 5).is_empty());
+    }
+}
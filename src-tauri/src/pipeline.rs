@@ -1,21 +1,51 @@
 //! Core snip-to-action pipeline commands.
 //!
-//! These are the multi-step orchestration commands:
-//! - process_snip: crop → OCR → open skeleton menu → stream LLM classify
-//! - execute_action: OCR text + chosen action → LLM execute → ActionResult
+//! process_snip: crop → OCR → open skeleton menu → stream LLM classify
+//! (or reuse the classification of a recent duplicate snip), then
+//! auto-save the crop if enabled (capture/autosave.rs). Configured
+//! webhooks hear about each classified snip (webhooks/mod.rs). The
+//! region is remembered for the "snip last region" trigger, and the snip
+//! is recorded in memory for replay (replay/mod.rs). Currency,
+//! unit, and time zone conversions are added to any menu whose snip has
+//! something to convert (convert/mod.rs), and a photographed document
+//! can be straightened (document/mod.rs). A snip with little or no text
+//! skips classify for the image menu (picture/mod.rs).
+//!
+//! Crop and OCR are usually already done by the time process_snip runs,
+//! by a speculative job started while the selection was being adjusted
+//! (pipeline_prefetch.rs). The second step (execute_action) lives in
+//! pipeline_execute.rs.
 
-use crate::capture::CaptureState;
+use crate::capture::{self, autosave, CaptureState};
+use crate::convert;
+use crate::document;
+use crate::error::{Capture, OmniError};
+use crate::history;
 use crate::llm;
 use crate::mcp;
 use crate::ocr;
+use crate::palette;
+use crate::picture;
+use crate::pipeline_prefetch;
+use crate::replay;
+use crate::safety;
+use crate::session;
+use crate::settings;
 use crate::settings_commands::resolve_provider;
-use tauri::Manager;
+use crate::stats;
+use crate::structured;
+use crate::triggers;
+use crate::webhooks;
+use crate::windows::{self, WindowEvent};
+use tauri::{Emitter, Manager};
+use tracing::Instrument;
 
 /// Tauri command: process a snip through the full pipeline (streaming).
 ///
 /// crop → OCR → open skeleton menu → stream LLM classify → populate actions.
 /// The action menu window opens immediately with a skeleton UI,
 /// then fills in as the streaming response arrives (~300ms TTFT).
+/// The tray icon spins while it runs and shows a failed snip.
 #[tauri::command]
 pub async fn process_snip(
     app: tauri::AppHandle,
@@ -25,8 +55,17 @@ pub async fn process_snip(
     height: u32,
     menu_x: f64,
     menu_y: f64,
-) -> Result<(), String> {
+) -> Result<(), OmniError> {
+    crate::tray::status::capturing(&app, false);
+    windows::overlay::disarm();
+    let snip = run(app.clone(), (x, y, width, height), (menu_x, menu_y));
+    crate::tray::status::track(&app, snip).await
+}
+
+/// The pipeline itself; `process_snip` adds the tray status.
+async fn run(app: tauri::AppHandle, (x, y, width, height): (u32, u32, u32, u32), (menu_x, menu_y): (f64, f64)) -> Result<(), OmniError> {
     let pipeline_start = std::time::Instant::now();
+    triggers::remember_region(x, y, width, height);
 
     // Write diagnostics to Desktop for debugging — appends each stage.
     let diag_path = dirs::desktop_dir()
@@ -42,51 +81,39 @@ pub async fn process_snip(
     let _ = std::fs::write(&diag_path, "");
     diag_write(&diag_path, &format!("=== SNIP: {}x{} at ({},{}) ===", width, height, x, y));
 
-    // Stage 2a: Crop the stored screenshot
-    let cropped = {
+    // Stage 2a–2c: Crop the stored screenshot, then drop it — only the
+    // crop is needed from here on. Perceptual hash (duplicate detection)
+    // alongside the lossless PNG encode, then OCR, all in memory. Usually
+    // done already by the speculative job for this rectangle.
+    let ocr_level = pipeline_prefetch::ocr_level();
+    let prepared = {
         let state = app.state::<CaptureState>();
-        let guard = state.screenshot.lock().map_err(|e| e.to_string())?;
-        let screenshot = guard
-            .as_ref()
-            .ok_or("No screenshot available — capture first")?;
-        screenshot.crop_imm(x, y, width, height)
+        let screenshot = state.screenshot().ok_or(Capture::NoScreenshot)?;
+        state.release();
+        // Rounding on a scaled display can put an edge one pixel past the image
+        let rect = capture::dpi::fit((x, y, width, height), (screenshot.width(), screenshot.height()))
+            .ok_or_else(|| OmniError::Ocr("The selection is outside the screenshot".to_string()))?;
+        pipeline_prefetch::take_or_prepare(&screenshot, rect, ocr_level).map_err(OmniError::Ocr)?
     };
-    let crop_ms = pipeline_start.elapsed().as_millis();
-    diag_write(&diag_path, &format!("crop: {}ms", crop_ms));
+    let pipeline_prefetch::Prepared { cropped, png, image_hash, ocr: ocr_result, crop_ms, encode_ms, ocr_ms, speculative } = prepared;
+    diag_write(&diag_path, &format!("crop: {}ms (speculative: {})", crop_ms, speculative));
     log::info!(
         "[CAPTURE] Bounding box received: {{x: {}, y: {}, w: {}, h: {}}}",
         x, y, width, height
     );
     log::info!("[CAPTURE] Region crop: {}ms", crop_ms);
+    log::info!("[CAPTURE] PNG encode: {}ms ({} bytes)", encode_ms, png.len());
 
-    // Stage 2b: Encode crop to PNG bytes in memory — no disk I/O.
-    let encode_start = std::time::Instant::now();
-    let mut png_bytes = Vec::new();
-    cropped
-        .write_to(
-            &mut std::io::Cursor::new(&mut png_bytes),
-            image::ImageFormat::Png,
-        )
-        .map_err(|e| format!("PNG encode failed: {}", e))?;
-    let encode_ms = encode_start.elapsed().as_millis();
-    log::info!("[CAPTURE] PNG encode: {}ms ({} bytes)", encode_ms, png_bytes.len());
+    let autosave_png = (autosave::current().enabled && history::privacy::recording()).then(|| png.clone());
 
-    // Stage 2c: OCR — bytes passed directly, no temp file
-    let ocr_start = std::time::Instant::now();
-    let ocr_level = match std::env::var("OCR_MODE").unwrap_or_default().as_str() {
-        "accurate" => ocr::RecognitionLevel::Accurate,
-        _ => ocr::RecognitionLevel::Fast,
-    };
-    let png_bytes_for_reocr = png_bytes.clone();
-    let ocr_result = ocr::recognize_text_from_bytes(png_bytes, ocr_level);
-    let ocr_ms = ocr_start.elapsed().as_millis();
+    stats::record_ocr_latency(ocr_ms as u64);
     diag_write(&diag_path, &format!("ocr: {} chars in {}ms, confidence={:.2}", ocr_result.char_count, ocr_ms, ocr_result.confidence));
     if ocr_result.char_count == 0 {
         diag_write(&diag_path, "WARNING: OCR returned ZERO characters!");
     } else {
         diag_write(&diag_path, &format!("ocr_preview: {:?}", &ocr_result.text[..ocr_result.text.len().min(200)]));
     }
-    eprintln!("[PIPELINE] OCR: {} chars in {}ms, confidence={:.2}", ocr_result.char_count, ocr_ms, ocr_result.confidence);
+    crate::selection::ocr_done(&app, &ocr_result.text);
     log::info!("[OCR] Recognition level: {:?}", ocr_level);
     log::info!(
         "[OCR] Extracted {} chars in {}ms",
@@ -99,91 +126,116 @@ pub async fn process_snip(
     log::info!("[OCR] has_table_structure: {}", has_table);
     log::info!("[OCR] has_code_structure: {}", has_code);
 
+    // Stage 2e: Prompt-injection scan — flag only; prompts wrap the text as untrusted.
+    let injection = tracing::info_span!("injection_scan").in_scope(|| safety::injection::scan_for_injection(&ocr_result.text));
+    if injection.is_suspicious {
+        log::warn!(
+            "[SAFETY] Possible prompt injection in snip (score={:.2}, signals={:?})",
+            injection.score,
+            injection.signals
+        );
+        diag_write(&diag_path, &format!("injection_suspected: {:?}", injection.signals));
+    }
+
     // Store OCR text early — action menu needs it for Copy Text (available in skeleton)
     // Clear previous menu so the poll doesn't render stale data from a prior snip.
     let menu_state = app.state::<llm::ActionMenuState>();
     *menu_state.menu.lock().unwrap() = None;
     *menu_state.ocr_text.lock().unwrap() = Some(ocr_result.text.clone());
-    *menu_state.crop_png.lock().unwrap() = Some(png_bytes_for_reocr);
-
-    // Stage 3a: Close overlay
-    if let Some(window) = app.get_webview_window("overlay") {
-        let _ = window.destroy();
-    }
+    replay::begin(&ocr_result.text, ocr_result.confidence, &png);
+    *menu_state.crop_png.lock().unwrap() = Some(png);
+    *menu_state.auto_action.lock().unwrap() = None;
+    *menu_state.injection_warning.lock().unwrap() = injection.is_suspicious.then(|| injection.clone());
 
-    // Stage 3b: Open action menu window BEFORE LLM call.
+    // Stage 3: Swap the overlay for the action menu BEFORE LLM call.
     // Shows skeleton immediately — Copy Text is clickable, summary shimmer visible.
-    let render_start = std::time::Instant::now();
+    tracing::info_span!("menu_window").in_scope(|| windows::open_action_menu(&app, Some((menu_x, menu_y))))?;
 
-    if let Some(existing) = app.get_webview_window("action-menu") {
-        let _ = existing.destroy();
+    // The menu may not be listening yet; it also takes the warning on load
+    if injection.is_suspicious {
+        let _ = app.emit("snip-injection-warning", &injection);
     }
 
-    let _menu_window = tauri::WebviewWindowBuilder::new(
-        &app,
-        "action-menu",
-        tauri::WebviewUrl::App("action-menu.html".into()),
-    )
-    .title("Omni-Glass Actions")
-    .inner_size(300.0, 280.0)
-    .position(menu_x, menu_y + 8.0)
-    .decorations(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .resizable(true)
-    .build()
-    .map_err(|e| format!("Failed to create action menu window: {}", e))?;
-
-    let render_ms = render_start.elapsed().as_millis();
+    // Per-stage timing is in the snip's trace (get_last_snip_trace)
     let local_ms = pipeline_start.elapsed().as_millis();
-    log::info!("[RENDER] Skeleton menu window created in {}ms", render_ms);
-    log::info!(
-        "[PIPELINE] Local processing: {}ms (crop={} + encode={} + ocr={} + window={})",
-        local_ms, crop_ms, encode_ms, ocr_ms, render_ms
-    );
+    log::info!("[PIPELINE] Local processing: {}ms", local_ms);
+
+    // From here until the menu is final, a restart reopens this snip.
+    session::snip_started(&ocr_result.text, menu_x, menu_y);
+
+    // Stage 3c: Repeat of a recent snip — reuse its classification, skip the LLM.
+    if let Some(menu) = history::reusable_menu(&ocr_result.text, image_hash) {
+        diag_write(&diag_path, "duplicate: reusing previous classification");
+        replay::record_classify(&serde_json::to_string(&menu).unwrap_or_default());
+        if let Some(png) = autosave_png {
+            autosave::save_snip(png, &ocr_result.text, &menu);
+        }
+        let _ = app.emit("action-menu-complete", &menu);
+        queue_default_action(&app, &menu);
+        *menu_state.menu.lock().unwrap() = Some(menu);
+        session::snip_finished();
+        log::info!("[PIPELINE] Duplicate snip served in {}ms", pipeline_start.elapsed().as_millis());
+        return Ok(());
+    }
+
+    // Stage 3d: A picture, not text — the image menu, no LLM call.
+    if picture::detect::is_picture(&ocr_result.text, ocr_result.confidence, cropped.width(), cropped.height()) {
+        diag_write(&diag_path, "picture: little or no text, image menu");
+        let menu = picture::menu(&ocr_result.text);
+        let _ = app.emit("action-menu-complete", &menu);
+        settle(&app, &ocr_result.text, menu, image_hash, &cropped, autosave_png);
+        log::info!("[PIPELINE] Picture snip served in {}ms", pipeline_start.elapsed().as_millis());
+        return Ok(());
+    }
 
     // Stage 4: Stream LLM classify — emits events to the action menu window.
     // Get plugin tool descriptions so the LLM knows about installed plugins.
     let registry = app.state::<mcp::ToolRegistry>();
     let all_tools = registry.all_tools().await;
     let plugin_count = all_tools.iter().filter(|t| t.plugin_id != "builtin").count();
-    let plugin_tools = registry.tools_for_prompt().await;
+    let snip = mcp::triggers::SnipContext::new(&ocr_result.text, has_table, has_code);
+    let plugin_tools = registry.tools_for_prompt(&snip).await;
     diag_write(&diag_path, &format!("registry: {} total tools, {} plugin tools", all_tools.len(), plugin_count));
+    diag_write(&diag_path, &format!("snip content types: {:?}", snip.content_types));
+
+    // Outbound gate — local-only and offline stop here with a heuristic menu;
+    // unusual volume pauses for re-confirmation.
+    if let Err(e) = safety::outbound_guard::guard_outbound(&app, "snip", ocr_result.text.chars().count()).await {
+        let local = e.cloud_unavailable();
+        let mut menu = if local { llm::ActionMenu::local(&e.to_string(), &snip.content_types) } else { llm::ActionMenu::fallback() };
+        convert::augment(&mut menu, &ocr_result.text);
+        structured::augment(&mut menu, &ocr_result.text);
+        palette::augment(&mut menu, &ocr_result.text);
+        document::augment(&mut menu, &cropped);
+        let _ = app.emit("action-menu-complete", &menu);
+        *menu_state.menu.lock().unwrap() = Some(menu);
+        session::snip_finished();
+        return if local { Ok(()) } else { Err(e) };
+    }
 
     let provider = resolve_provider();
     diag_write(&diag_path, &format!("provider: {}", provider));
     diag_write(&diag_path, &format!("ANTHROPIC_API_KEY present: {}", std::env::var("ANTHROPIC_API_KEY").map(|k| !k.is_empty()).unwrap_or(false)));
-    diag_write(&diag_path, &format!("LLM_PROVIDER env: {:?}", std::env::var("LLM_PROVIDER").ok()));
+    diag_write(&diag_path, &format!("provider setting: {:?}", settings::current().provider));
     if !plugin_tools.is_empty() {
         diag_write(&diag_path, &format!("plugin_tools_for_prompt:\n{}", plugin_tools.trim()));
     } else {
         diag_write(&diag_path, "plugin_tools_for_prompt: EMPTY (no plugins or not loaded yet)");
     }
-    eprintln!("[PIPELINE] LLM provider: {}", provider);
-    let action_menu = match provider.as_str() {
-        "gemini" => {
-            llm::classify_streaming_gemini(
-                &app,
-                &ocr_result.text,
-                has_table,
-                has_code,
-                ocr_result.confidence,
-                &plugin_tools,
-            )
-            .await
-        }
-        _ => {
-            llm::classify_streaming(
-                &app,
-                &ocr_result.text,
-                has_table,
-                has_code,
-                ocr_result.confidence,
-                &plugin_tools,
-            )
-            .await
-        }
+    let classify_span = tracing::info_span!("classify", provider = %provider);
+    let mut action_menu = match provider.as_str() {
+        "gemini" => llm::classify_streaming_gemini(&app, &ocr_result.text, has_table, has_code, ocr_result.confidence, &plugin_tools).instrument(classify_span).await,
+        _ => llm::classify_streaming(&app, &ocr_result.text, has_table, has_code, ocr_result.confidence, &plugin_tools).instrument(classify_span).await,
     };
+    // Local tools are offered whatever the classifier chose; re-send the menu if any were added
+    let text = &ocr_result.text;
+    if convert::augment(&mut action_menu, text)
+        | structured::augment(&mut action_menu, text)
+        | palette::augment(&mut action_menu, text)
+        | document::augment(&mut action_menu, &cropped)
+    {
+        let _ = app.emit("action-menu-complete", &action_menu);
+    }
 
     // Log classify result to diagnostics
     diag_write(&diag_path, &format!("classify_result: content_type={}, summary={}", action_menu.content_type, action_menu.summary));
@@ -195,8 +247,7 @@ pub async fn process_snip(
     diag_write(&diag_path, &format!("total_pipeline: {}ms", diag_ms));
     eprintln!("[PIPELINE] Diagnostics written to {}", diag_path.display());
 
-    // Store final ActionMenu in state (fallback for get_action_menu command)
-    *menu_state.menu.lock().unwrap() = Some(action_menu);
+    settle(&app, &ocr_result.text, action_menu, image_hash, &cropped, autosave_png);
 
     let total_ms = pipeline_start.elapsed().as_millis();
     log::info!(
@@ -211,86 +262,33 @@ pub async fn process_snip(
     Ok(())
 }
 
-/// Tauri command: execute an action on the stored OCR text.
-///
-/// Called by the action menu when the user clicks an action that
-/// requires LLM execution (explain_error, suggest_fix, export_csv, etc.).
-/// Returns an ActionResult JSON to the frontend.
-#[tauri::command]
-pub async fn execute_action(
-    state: tauri::State<'_, llm::ActionMenuState>,
-    registry: tauri::State<'_, mcp::ToolRegistry>,
-    action_id: String,
-) -> Result<llm::ActionResult, String> {
-    let fast_text = {
-        let guard = state.ocr_text.lock().map_err(|e| e.to_string())?;
-        guard
-            .clone()
-            .ok_or("No OCR text available — snip first".to_string())?
-    };
-
-    // Check if this action belongs to a plugin (non-builtin MCP tool).
-    // If so, route to the plugin's MCP server with LLM-generated args.
-    if registry.is_plugin_action(&action_id).await {
-        log::info!("[EXECUTE] Routing to plugin: {}", action_id);
-        let resolved = registry.resolve_action(&action_id).await;
-        let tool_meta = match &resolved {
-            Some(qname) => registry.get_tool(qname).await,
-            None => None,
-        };
-        let result = mcp::execute_plugin_tool(
-            &registry,
-            &action_id,
-            &fast_text,
-            tool_meta.as_ref().map(|t| t.description.as_str()),
-            tool_meta.as_ref().and_then(|t| t.input_schema.as_ref()),
-        )
-        .await;
-        return Ok(result);
+/// Keep a finished menu: history, webhooks, auto-save, the default
+/// action, and the state `get_action_menu` falls back to.
+fn settle(
+    app: &tauri::AppHandle,
+    text: &str,
+    menu: llm::ActionMenu,
+    image_hash: u64,
+    cropped: &image::DynamicImage,
+    autosave_png: Option<Vec<u8>>,
+) {
+    history::record_snip(app, text, &menu, image_hash, cropped);
+    webhooks::snip_completed(text, &menu);
+    if let Some(png) = autosave_png {
+        autosave::save_snip(png, text, &menu);
     }
+    queue_default_action(app, &menu);
+    *app.state::<llm::ActionMenuState>().menu.lock().unwrap() = Some(menu);
+    session::snip_finished();
+}
 
-    // For code-fix actions, re-OCR with .accurate for higher fidelity text.
-    // The classify step used .fast (~30ms) which is good enough for action detection,
-    // but code fixes need every bracket and quote to be correct.
-    let needs_accurate = matches!(
-        action_id.as_str(),
-        "suggest_fix" | "fix_error" | "fix_syntax" | "fix_code" | "format_code"
-    );
-    let ocr_text = if needs_accurate {
-        let crop_png = {
-            let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
-            guard.clone()
-        };
-        match crop_png {
-            Some(png_bytes) => {
-                let start = std::time::Instant::now();
-                let result = ocr::recognize_text_from_bytes(
-                    png_bytes,
-                    ocr::RecognitionLevel::Accurate,
-                );
-                let ms = start.elapsed().as_millis();
-                eprintln!(
-                    "[EXECUTE] Re-OCR (.accurate): {} chars in {}ms (was {} chars with .fast)",
-                    result.char_count, ms, fast_text.len()
-                );
-                result.text
-            }
-            None => {
-                eprintln!("[EXECUTE] No crop PNG available, using .fast OCR text");
-                fast_text
-            }
-        }
-    } else {
-        fast_text
-    };
-
-    log::info!("[EXECUTE] Starting action: {}", action_id);
-    let result = llm::execute_action_anthropic(&action_id, &ocr_text).await;
-    log::info!(
-        "[EXECUTE] Complete: status={}, type={}",
-        result.status,
-        result.result.result_type
-    );
-
-    Ok(result)
+/// Queue the user's default action for this content type, if the menu
+/// offers it. The action menu takes it with `take_auto_action`, either on
+/// this event or after it finds the menu by polling.
+fn queue_default_action(app: &tauri::AppHandle, menu: &llm::ActionMenu) {
+    let offered = menu.actions.iter().map(|a| a.id.as_str());
+    let Some(action_id) = settings::current().actions.default_action(&menu.content_type, offered) else { return };
+    log::info!("[PIPELINE] Default action for '{}': {}", menu.content_type, action_id);
+    *app.state::<llm::ActionMenuState>().auto_action.lock().unwrap() = Some(action_id.clone());
+    windows::send(app, WindowEvent::AutoExecute { action_id });
 }
@@ -0,0 +1,243 @@
+//! Execute step of the snip pipeline.
+//!
+//! execute_action: OCR text + chosen action → LLM execute (or plugin
+//! MCP call) → ActionResult. Runs after process_snip has stored the
+//! OCR text and crop in ActionMenuState. Successful results are
+//! attached to the snip's history record, and built-in actions are
+//! answered from that record when run again (unless regenerating). A
+//! result whose menu was closed meanwhile becomes a notification.
+//! Every result, cached or not, is reported to configured webhooks.
+//! Refusals before any call (local-only, offline, paused, no API key)
+//! are returned as `OmniError` so the menu can tell them apart. Describe
+//! Image sends the crop rather than the text (picture/mod.rs).
+
+use crate::calendar;
+use crate::convert;
+use crate::editor;
+use crate::email;
+use crate::error::{Llm, OmniError};
+use crate::export;
+use crate::history;
+use crate::llm;
+use crate::mcp;
+use crate::notifications;
+use crate::picture;
+use crate::ocr;
+use crate::safety;
+use crate::structured;
+use crate::webhooks;
+use tauri::Manager;
+use tracing::Instrument;
+
+/// Tauri command: execute an action on the stored OCR text.
+///
+/// Called by the action menu when the user clicks an action that
+/// requires LLM execution (explain_error, suggest_fix, export_csv, etc.).
+/// Returns an ActionResult JSON to the frontend. A cached result has
+/// `metadata.cachedAt` set; `regenerate` skips and replaces it.
+#[tauri::command]
+pub async fn execute_action(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, llm::ActionMenuState>,
+    registry: tauri::State<'_, mcp::ToolRegistry>,
+    action_id: String,
+    regenerate: Option<bool>,
+) -> Result<llm::ActionResult, OmniError> {
+    let fast_text = {
+        let guard = state.ocr_text.lock().map_err(|e| e.to_string())?;
+        guard
+            .clone()
+            .ok_or("No OCR text available — snip first".to_string())?
+    };
+    // Opens a local file only — nothing to cache or keep in history
+    if action_id == editor::OPEN_ACTION {
+        return Ok(editor::open_snip(&state, &fast_text));
+    }
+    // A replayed snip (replay/) leaves history, webhooks, and the cache alone
+    let replaying = crate::replay::active();
+    if !replaying && is_cacheable(&registry, &action_id).await {
+        if regenerate.unwrap_or(false) {
+            history::invalidate_results(&fast_text, &action_id);
+        } else if let Some(cached) = history::cached_result(&fast_text, &action_id) {
+            log::info!("[EXECUTE] Serving cached result for {}", action_id);
+            webhooks::action_executed(&fast_text, &cached);
+            remember(&state, &fast_text, &cached);
+            return Ok(cached);
+        }
+    }
+    let running = run_action(&app, &state, &registry, &action_id, fast_text.clone());
+    let result = crate::tray::status::track(&app, running.instrument(tracing::info_span!("execute", action = %action_id))).await?;
+    if replaying {
+        remember(&state, &fast_text, &result);
+        return Ok(result);
+    }
+    let snip_id = history::record_answer(&fast_text, &result);
+    crate::session::result_ready(&fast_text, &result);
+    webhooks::action_executed(&fast_text, &result);
+    if !menu_shows(&app, &state, &fast_text) {
+        notifications::result_ready(&app, &fast_text, snip_id, &result);
+    }
+    remember(&state, &fast_text, &result);
+    Ok(result)
+}
+
+/// Whether the action menu is still open on this snip to show the result;
+/// it may have been closed, or replaced by a newer snip, meanwhile.
+fn menu_shows(app: &tauri::AppHandle, state: &llm::ActionMenuState, snip_text: &str) -> bool {
+    app.get_webview_window("action-menu").is_some()
+        && state.ocr_text.lock().is_ok_and(|text| text.as_deref() == Some(snip_text))
+}
+
+/// Keep the result for `copy_result`, which copies it in rich formats.
+fn remember(state: &llm::ActionMenuState, snip_text: &str, result: &llm::ActionResult) {
+    if let Ok(mut last) = state.last_result.lock() {
+        *last = Some((snip_text.to_string(), result.clone()));
+    }
+}
+
+/// Only built-in LLM actions are cached. Plugin tools, prompts, and
+/// fan-outs may have side effects or depend on plugin state; conversions
+/// are cheap and should follow today's rates and zone offsets. The cache
+/// is keyed by text, which says nothing about a picture.
+async fn is_cacheable(registry: &mcp::ToolRegistry, action_id: &str) -> bool {
+    action_id != history::REUSE_ACTION
+        && action_id != picture::DESCRIBE_ACTION
+        && !convert::is_action(action_id)
+        && mcp::fanout::parse_action_id(action_id).is_none()
+        && !action_id.starts_with(mcp::prompts::ACTION_PREFIX)
+        && !registry.is_plugin_action(action_id).await
+}
+
+/// Route the action to a fan-out, prompt, plugin, or built-in LLM execute.
+async fn run_action(
+    app: &tauri::AppHandle,
+    state: &llm::ActionMenuState,
+    registry: &mcp::ToolRegistry,
+    action_id: &str,
+    fast_text: String,
+) -> Result<llm::ActionResult, OmniError> {
+
+    // A duplicate snip's earlier answer — no LLM call, nothing leaves the machine
+    if action_id == history::REUSE_ACTION {
+        return Ok(history::reuse_result(&fast_text));
+    }
+
+    // A table OCR could split is exported in Rust; the LLM is the fallback
+    if let Some(result) = export::local_result(action_id, &fast_text) {
+        return Ok(result);
+    }
+
+    // JSON and YAML are validated and pretty-printed locally
+    if let Some(result) = structured::local_result(action_id, &fast_text) {
+        return Ok(result);
+    }
+
+    // Units and zones convert offline; only exchange rates are fetched, and no snip text is sent
+    if let Some(result) = convert::execute(action_id, &fast_text).await {
+        return Ok(result);
+    }
+
+    // A fan-out action sends the text to every plugin it names
+    let fanout = mcp::fanout::parse_action_id(action_id);
+    let copies = fanout.as_ref().map_or(1, Vec::len);
+    safety::outbound_guard::guard_outbound(app, "execute", fast_text.chars().count() * copies).await?;
+
+    // A picture is described from its pixels; OCR has little to go on
+    if action_id == picture::DESCRIBE_ACTION {
+        if !crate::settings_commands::has_api_key("anthropic") {
+            return Err(Llm::NoApiKey.into());
+        }
+        let png = state.crop_png.lock().map_err(|e| e.to_string())?.clone().ok_or("No snip image available — snip first")?;
+        return Ok(picture::describe(&png, registry).await);
+    }
+
+    // Event fields come from the LLM; the .ics is validated and written in Rust
+    if action_id == calendar::EVENT_ACTION {
+        return Ok(calendar::create_event(&fast_text).await);
+    }
+
+    // Draft fields come from the LLM; the mail client is opened by the frontend
+    if action_id == email::DRAFT_ACTION {
+        return Ok(email::compose(&fast_text).await);
+    }
+
+    if let Some(tool_ids) = fanout {
+        log::info!("[EXECUTE] Fanning out to {} plugin tools", tool_ids.len());
+        return Ok(mcp::fanout::execute(registry, action_id, &tool_ids, &fast_text).await);
+    }
+
+    // A plugin prompt template, filled with the text and run by the LLM
+    if action_id.starts_with(mcp::prompts::ACTION_PREFIX) {
+        log::info!("[EXECUTE] Running plugin prompt: {}", action_id);
+        return Ok(mcp::prompts::execute(registry, action_id, &fast_text).await);
+    }
+
+    // Check if this action belongs to a plugin (non-builtin MCP tool).
+    // If so, route to the plugin's MCP server with LLM-generated args.
+    if registry.is_plugin_action(action_id).await {
+        log::info!("[EXECUTE] Routing to plugin: {}", action_id);
+        let resolved = registry.resolve_action(action_id).await;
+        let tool_meta = match &resolved {
+            Some(qname) => registry.get_tool(qname).await,
+            None => None,
+        };
+        let result = mcp::execute_plugin_tool(
+            registry,
+            action_id,
+            &fast_text,
+            tool_meta.as_ref().map(|t| t.description.as_str()),
+            tool_meta.as_ref().and_then(|t| t.input_schema.as_ref()),
+        )
+        .await;
+        return Ok(result);
+    }
+
+    // For code-fix actions, re-OCR with .accurate for higher fidelity text.
+    // The classify step used .fast (~30ms) which is good enough for action detection,
+    // but code fixes need every bracket and quote to be correct.
+    // Skipped in power-saver mode (see power/mod.rs).
+    let needs_accurate = matches!(
+        action_id,
+        "suggest_fix" | "fix_error" | "fix_syntax" | "fix_code" | "format_code"
+    ) && !crate::power::saver();
+    let ocr_text = if let Some(recorded) = crate::replay::accurate_text().filter(|_| needs_accurate) {
+        recorded
+    } else if needs_accurate {
+        let crop_png = {
+            let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
+            guard.clone()
+        };
+        match crop_png {
+            Some(png_bytes) => {
+                let result = tracing::info_span!("ocr", level = "accurate")
+                    .in_scope(|| ocr::recognize_text_from_bytes(png_bytes, ocr::RecognitionLevel::Accurate));
+                log::info!(
+                    "[EXECUTE] Re-OCR (.accurate): {} chars (was {} chars with .fast)",
+                    result.char_count, fast_text.len()
+                );
+                crate::replay::record_accurate_text(&result.text);
+                result.text
+            }
+            None => {
+                eprintln!("[EXECUTE] No crop PNG available, using .fast OCR text");
+                fast_text
+            }
+        }
+    } else {
+        fast_text
+    };
+
+    // A replay answers from its recording; otherwise built-in actions need a key
+    if !crate::replay::active() && !crate::settings_commands::has_api_key("anthropic") {
+        return Err(Llm::NoApiKey.into());
+    }
+    log::info!("[EXECUTE] Starting action: {}", action_id);
+    let result = llm::execute_action_anthropic(action_id, &ocr_text, registry).await;
+    log::info!(
+        "[EXECUTE] Complete: status={}, type={}",
+        result.status,
+        result.result.result_type
+    );
+
+    Ok(result)
+}
@@ -0,0 +1,162 @@
+//! Speculative OCR while the user is still adjusting the selection.
+//!
+//! Whenever the pointer rests mid-drag (debounced in overlay.ts), the
+//! overlay calls prefetch_ocr with the current rectangle. That runs the
+//! first stages of process_snip — crop, perceptual hash + PNG encode,
+//! and OCR of the region straight from the capture's pixels — as a job
+//! in `ocr::jobs`; a newer rectangle cancels it. When the mouse is
+//! released on the same rectangle, process_snip takes the finished (or
+//! nearly finished) result instead of starting over, so OCR is usually
+//! done by the time the menu opens.
+
+use crate::capture::{self, CaptureState};
+use crate::history;
+use crate::ocr::{self, jobs::JobManager};
+use crate::settings;
+use image::DynamicImage;
+use std::sync::{Arc, LazyLock, Weak};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+/// How long process_snip waits for a running job on its exact rectangle.
+const TAKE_WAIT: Duration = Duration::from_secs(3);
+/// Crop, encode, and OCR taking longer than this is reported as hung.
+const PREPARE_LIMIT: Duration = Duration::from_secs(20);
+
+/// x, y, width, height in screenshot pixels.
+pub type Rect = (u32, u32, u32, u32);
+
+/// What a job was for: one rectangle of one capture at one OCR level.
+/// The capture is held weakly, so a queued result never keeps a
+/// released screenshot alive.
+struct Key {
+    screenshot: Weak<DynamicImage>,
+    rect: Rect,
+    level: ocr::RecognitionLevel,
+}
+
+impl Key {
+    fn new(screenshot: &Arc<DynamicImage>, rect: Rect, level: ocr::RecognitionLevel) -> Self {
+        Self { screenshot: Arc::downgrade(screenshot), rect, level }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        Weak::ptr_eq(&self.screenshot, &other.screenshot) && self.rect == other.rect && self.level == other.level
+    }
+}
+
+/// Stages 2a–2c of process_snip for one rectangle.
+pub struct Prepared {
+    pub cropped: DynamicImage,
+    pub png: Vec<u8>,
+    pub image_hash: u64,
+    pub ocr: ocr::OcrOutput,
+    pub crop_ms: u128,
+    pub encode_ms: u128,
+    pub ocr_ms: u128,
+    /// Computed by a speculative job before the mouse was released.
+    pub speculative: bool,
+}
+
+static JOBS: LazyLock<JobManager<Key, Result<Prepared, String>>> = LazyLock::new(JobManager::new);
+
+/// OCR level from settings (`ocrMode`); always fast in power-saver mode.
+pub fn ocr_level() -> ocr::RecognitionLevel {
+    if crate::power::saver() {
+        return ocr::RecognitionLevel::Fast;
+    }
+    match settings::current().ocr_mode.as_str() {
+        "accurate" => ocr::RecognitionLevel::Accurate,
+        _ => ocr::RecognitionLevel::Fast,
+    }
+}
+
+/// Run `work` in `span` (a stage of the snip's trace), returning its time too.
+fn timed<T>(span: tracing::Span, work: impl FnOnce() -> T) -> (T, u128) {
+    let start = Instant::now();
+    let out = span.in_scope(work);
+    (out, start.elapsed().as_millis())
+}
+
+/// Crop, hash and encode — with OCR alongside, reading the region in
+/// place in the capture — unless `cancelled` says the result is no
+/// longer wanted before OCR starts. Captures that aren't RGBA8 fall back
+/// to OCR of the encoded PNG.
+fn prepare(
+    screenshot: &DynamicImage,
+    (x, y, width, height): Rect,
+    level: ocr::RecognitionLevel,
+    cancelled: impl Fn() -> bool + Sync,
+) -> Result<Prepared, String> {
+    let _watch = crate::crash::watch("ocr", PREPARE_LIMIT);
+    // Rayon threads don't inherit the current span, so stages name their parent
+    let parent = tracing::Span::current();
+    let (cropped, crop_ms) = timed(tracing::info_span!("crop"), || screenshot.crop_imm(x, y, width, height));
+    let view = screenshot.as_rgba8().and_then(|rgba| ocr::RgbaView::crop(rgba, x, y, width, height));
+
+    let (((image_hash, png), encode_ms), direct) = rayon::join(
+        || {
+            timed(tracing::info_span!(parent: &parent, "encode"), || {
+                rayon::join(|| history::dedupe::image_hash(&cropped), || capture::encode::png_fast(&cropped))
+            })
+        },
+        || {
+            view.filter(|_| !cancelled()).map(|view| {
+                let span = tracing::info_span!(parent: &parent, "ocr", source = "pixels", ?level);
+                timed(span, || ocr::recognize_text_from_rgba(&view, level))
+            })
+        },
+    );
+    let png = png.map_err(|e| format!("PNG encode failed: {}", e))?;
+    if cancelled() {
+        return Err("Superseded by a newer selection".to_string());
+    }
+
+    let (ocr, ocr_ms) = direct.unwrap_or_else(|| {
+        timed(tracing::info_span!("ocr", source = "png", ?level), || ocr::recognize_text_from_bytes(png.clone(), level))
+    });
+    Ok(Prepared { cropped, png, image_hash, ocr, crop_ms, encode_ms, ocr_ms, speculative: false })
+}
+
+/// The crop and OCR of `rect`: the speculative job's when it was for
+/// this exact rectangle, otherwise computed now.
+pub fn take_or_prepare(screenshot: &Arc<DynamicImage>, rect: Rect, level: ocr::RecognitionLevel) -> Result<Prepared, String> {
+    if let Some(prepared) = JOBS.take(&Key::new(screenshot, rect, level), TAKE_WAIT) {
+        log::info!("[OCR] Using speculative result for the released selection");
+        return prepared;
+    }
+    JOBS.cancel();
+    prepare(screenshot, rect, level, || false)
+}
+
+/// Drop any speculative job and its result (overlay closed).
+pub fn cancel() {
+    JOBS.cancel();
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: start OCR of the rectangle the user is adjusting,
+/// superseding the previous one. Coordinates are in screenshot pixels,
+/// as for process_snip.
+#[tauri::command]
+pub fn prefetch_ocr(app: tauri::AppHandle, x: u32, y: u32, width: u32, height: u32) {
+    // In power-saver mode only the released selection is OCR'd.
+    if crate::power::saver() {
+        return;
+    }
+    let Some(screenshot) = app.state::<CaptureState>().screenshot() else { return };
+    let Some(rect) = capture::dpi::fit((x, y, width, height), (screenshot.width(), screenshot.height())) else { return };
+    let level = ocr_level();
+    JOBS.submit(Key::new(&screenshot, rect, level), move |cancel| {
+        let prepared = tracing::info_span!("prefetch", width, height)
+            .in_scope(|| prepare(&screenshot, rect, level, || cancel.is_cancelled()));
+        if cancel.is_cancelled() {
+            log::debug!("[OCR] Speculative job for {:?} superseded", rect);
+            return None;
+        }
+        Some(prepared.map(|p| Prepared { speculative: true, ..p }))
+    });
+}
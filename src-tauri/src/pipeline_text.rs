@@ -3,10 +3,13 @@
 //! This is a separate pipeline from the snip pipeline (pipeline.rs).
 //! The user types text in the launcher (Cmd+Shift+Space) instead of
 //! snipping a screen region. The LLM decides: respond directly or
-//! route to a tool (built-in or plugin).
+//! route to a tool (built-in or plugin). Plugin slash commands
+//! (`/jira PROJ-123`) go straight to their tool without the LLM.
 
+use crate::error::{Llm, OmniError};
 use crate::llm;
 use crate::mcp;
+use crate::safety;
 use serde::{Deserialize, Serialize};
 
 use llm::prompts_text_command::{self, TEXT_COMMAND_MAX_TOKENS, TEXT_COMMAND_SYSTEM_PROMPT};
@@ -45,13 +48,35 @@ struct RouteDecision {
 /// Routes through LLM to decide: direct response or tool dispatch.
 #[tauri::command]
 pub async fn execute_text_command(
+    app: tauri::AppHandle,
     text: String,
     registry: tauri::State<'_, mcp::ToolRegistry>,
-) -> Result<TextCommandResult, String> {
+) -> Result<TextCommandResult, OmniError> {
     log::info!("[TEXT_CMD] Input: {} chars", text.len());
 
-    // Get all available tools for the LLM prompt
-    let all_tools = registry.all_tools().await;
+    // Plugin slash command — local routing, nothing goes to the LLM
+    if let Some(routed) = mcp::slash_commands::route(&text) {
+        let routed = routed.map_err(OmniError::Plugin)?;
+        if !mcp::enablement::is_enabled(&routed.plugin_id, &routed.tool) {
+            return Err(OmniError::Plugin("This command's tool is disabled in Settings".to_string()));
+        }
+        let qname = mcp::registry::qualified_name(&routed.plugin_id, &routed.tool);
+        // Same schema check as LLM-built arguments, minus the repair retry
+        if let Some(schema) = registry.get_tool(&qname).await.and_then(|t| t.input_schema) {
+            llm::args_schema::validate_args(&routed.arguments, &schema).map_err(|errors| {
+                OmniError::Plugin(format!("{} — usage: {}", errors.join("; "), routed.usage))
+            })?;
+        }
+        log::info!("[TEXT_CMD] Slash command → {}", qname);
+        let result = mcp::run_plugin_tool(&registry, &qname, routed.arguments).await;
+        return Ok(to_text_result(result, &routed.tool));
+    }
+
+    safety::outbound_guard::guard_outbound(&app, "text_command", text.chars().count()).await?;
+    let _working = crate::tray::status::working(&app);
+
+    // Get all reachable tools for the LLM prompt
+    let all_tools = registry.available_tools().await;
     let tool_descriptions: Vec<String> = all_tools
         .iter()
         .map(|t| {
@@ -64,40 +89,39 @@ pub async fn execute_text_command(
     // Build the LLM request
     let user_message = prompts_text_command::build_text_command_message(&text, &tools_prompt);
 
-    let api_key = std::env::var("ANTHROPIC_API_KEY")
-        .map_err(|_| "No API key configured".to_string())?;
+    let api_key = std::env::var("ANTHROPIC_API_KEY").map_err(|_| Llm::NoApiKey)?;
     if api_key.is_empty() {
-        return Err("No API key configured".to_string());
+        return Err(Llm::NoApiKey.into());
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::llm::http::client("anthropic");
     let resp = client
         .post("https://api.anthropic.com/v1/messages")
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
         .json(&serde_json::json!({
-            "model": llm::prompts::MODEL,
+            "model": llm::provider::model_for("anthropic"),
             "max_tokens": TEXT_COMMAND_MAX_TOKENS,
             "system": TEXT_COMMAND_SYSTEM_PROMPT,
             "messages": [{"role": "user", "content": user_message}]
         }))
         .send()
         .await
-        .map_err(|e| format!("API call failed: {}", e))?;
+        .map_err(|e| Llm::Request(format!("API call failed: {}", e)))?;
 
     if !resp.status().is_success() {
         let body = resp.text().await.unwrap_or_default();
-        return Err(format!("API error: {}", &body[..200.min(body.len())]));
+        return Err(Llm::Request(format!("API error: {}", &body[..200.min(body.len())])).into());
     }
 
-    let body = resp.text().await.map_err(|e| e.to_string())?;
-    let response_text = extract_text(&body)?;
+    let body = resp.text().await.map_err(|e| Llm::Request(e.to_string()))?;
+    let response_text = extract_text(&body).map_err(Llm::Request)?;
     eprintln!("[TEXT_CMD] Raw router response: {}", &response_text[..300.min(response_text.len())]);
     let json_text = streaming::strip_code_fences(&response_text);
 
     let decision: RouteDecision = serde_json::from_str(&json_text)
-        .map_err(|e| format!("Failed to parse LLM routing decision: {}", e))?;
+        .map_err(|e| Llm::Request(format!("Failed to parse LLM routing decision: {}", e)))?;
 
     eprintln!(
         "[TEXT_CMD] Router decision: type={}, tool_id={:?}, text={:?}",
@@ -126,7 +150,7 @@ pub async fn execute_text_command(
             log::info!("[TEXT_CMD] Routing to tool: {}", tool_id);
             route_to_tool(&registry, &tool_id, &input).await
         }
-        other => Err(format!("Unknown route type: {}", other)),
+        other => Err(Llm::Request(format!("Unknown route type: {}", other)).into()),
     }
 }
 
@@ -135,7 +159,7 @@ async fn route_to_tool(
     registry: &mcp::ToolRegistry,
     tool_id: &str,
     input_text: &str,
-) -> Result<TextCommandResult, String> {
+) -> Result<TextCommandResult, OmniError> {
     // Strip plugin prefix (e.g. "builtin::run_command" or "builtin:run_command" → "run_command")
     let bare_id = tool_id
         .rsplit_once("::")
@@ -160,19 +184,23 @@ async fn route_to_tool(
         .await
     } else {
         // Built-in tool — use the execute pipeline
-        llm::execute_action_anthropic(bare_id, input_text).await
+        llm::execute_action_anthropic(bare_id, input_text, registry).await
     };
 
-    Ok(TextCommandResult {
+    Ok(to_text_result(result, bare_id))
+}
+
+fn to_text_result(result: llm::ActionResult, action_id: &str) -> TextCommandResult {
+    TextCommandResult {
         status: result.status,
         text: result.result.text.unwrap_or_default(),
-        action_id: Some(bare_id.to_string()),
+        action_id: Some(action_id.to_string()),
         result_type: result.result.result_type,
         command: result.result.command,
         file_path: result.result.file_path,
         file_content: None, // File content handled via file_path
         clipboard_content: result.result.clipboard_content,
-    })
+    }
 }
 
 /// Extract text content from an Anthropic Messages API response.
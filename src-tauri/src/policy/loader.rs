@@ -0,0 +1,146 @@
+//! Policy file discovery and parsing.
+//!
+//! Locations (first match wins):
+//! 1. macOS:   `/Library/Application Support/omni-glass/policy.json`
+//!    Windows: `%ProgramData%\omni-glass\policy.json`
+//!    Linux:   `/etc/omni-glass/policy.json`
+//! 2. Debug builds only, with no system policy installed:
+//!    `OMNI_GLASS_POLICY` env var — explicit path, for testing policies
+//!
+//! The system location is admin-writable only and always wins, and
+//! release builds ignore the env var, so a user cannot loosen the policy
+//! by pointing the app at a file of their own.
+//!
+//! A file that is present but can't be read or parsed fails closed: the
+//! app runs under `Policy::locked_down()` rather than unrestricted.
+
+use super::Policy;
+use std::path::{Path, PathBuf};
+
+const POLICY_FILENAME: &str = "policy.json";
+
+/// Providers a policy may force or pin. Mirrors `llm::provider::all_providers`.
+const KNOWN_PROVIDERS: [&str; 2] = ["anthropic", "gemini"];
+
+/// System-wide policy path for the current platform.
+fn system_policy_path() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        Some(PathBuf::from("/Library/Application Support/omni-glass").join(POLICY_FILENAME))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var("ProgramData")
+            .ok()
+            .map(|d| PathBuf::from(d).join("omni-glass").join(POLICY_FILENAME))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Some(PathBuf::from("/etc/omni-glass").join(POLICY_FILENAME))
+    }
+}
+
+/// `OMNI_GLASS_POLICY`, honored in debug builds only.
+fn test_policy_path() -> Option<PathBuf> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    std::env::var("OMNI_GLASS_POLICY").ok().filter(|p| !p.is_empty()).map(PathBuf::from)
+}
+
+/// Find and parse the policy file. Returns `None` when no policy is installed.
+pub fn load() -> Option<(Result<Policy, String>, PathBuf)> {
+    let path = system_policy_path()
+        .filter(|p| p.exists())
+        .or_else(test_policy_path)
+        .filter(|p| p.exists())?;
+    Some((load_file(&path), path))
+}
+
+/// Read and parse the policy file at `path`. On error the app must still
+/// start so the user can report the problem, but locked down
+/// (`Policy::locked_down`), never unrestricted.
+fn load_file(path: &Path) -> Result<Policy, String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_policy(&raw)
+}
+
+/// Parse and validate policy JSON.
+pub fn parse_policy(raw: &str) -> Result<Policy, String> {
+    let mut policy: Policy =
+        serde_json::from_str(raw).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    if let Some(ref forced) = policy.forced_provider {
+        let forced = forced.to_lowercase();
+        if !KNOWN_PROVIDERS.contains(&forced.as_str()) {
+            return Err(format!("Unknown forcedProvider '{}'", forced));
+        }
+        policy.forced_provider = Some(forced);
+    }
+
+    for provider in policy.pinned_models.keys() {
+        if !KNOWN_PROVIDERS.contains(&provider.as_str()) {
+            return Err(format!("Unknown provider '{}' in pinnedModels", provider));
+        }
+    }
+
+    Ok(policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_object_is_unrestricted() {
+        let policy = parse_policy("{}").unwrap();
+        assert_eq!(policy, Policy::default());
+    }
+
+    #[test]
+    fn full_policy_parses() {
+        let policy = parse_policy(
+            r#"{
+                "forcedProvider": "Gemini",
+                "disablePlugins": true,
+                "blockedPlugins": ["com.example.bad"],
+                "disableCommandExecution": true,
+                "redactionRules": [{"label": "employee_id", "pattern": "EMP-\\d{6}"}],
+                "pinnedModels": {"anthropic": "claude-haiku-4-5-20251001"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(policy.forced_provider.as_deref(), Some("gemini"));
+        assert!(policy.disable_plugins);
+        assert!(policy.disable_command_execution);
+        assert_eq!(policy.blocked_plugins, vec!["com.example.bad"]);
+        assert_eq!(policy.redaction_rules[0].label, "employee_id");
+        assert_eq!(policy.pinned_models["anthropic"], "claude-haiku-4-5-20251001");
+    }
+
+    #[test]
+    fn rejects_unknown_forced_provider() {
+        let err = parse_policy(r#"{"forcedProvider": "openai"}"#).unwrap_err();
+        assert!(err.contains("openai"));
+    }
+
+    #[test]
+    fn rejects_unknown_pinned_provider() {
+        let err = parse_policy(r#"{"pinnedModels": {"mystery": "m-1"}}"#).unwrap_err();
+        assert!(err.contains("mystery"));
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert!(parse_policy("{ not json").is_err());
+    }
+
+    #[test]
+    fn malformed_file_is_an_error_not_an_empty_policy() {
+        let path = std::env::temp_dir().join(format!("og-policy-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"disablePlugins": true,}"#).unwrap();
+        assert!(load_file(&path).unwrap_err().contains("Invalid JSON"));
+        std::fs::remove_file(&path).unwrap();
+        assert!(load_file(&path).unwrap_err().contains("Failed to read"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Enterprise policy layer — read-only, admin-managed configuration.
+//!
+//! A policy file (installed by IT or pushed via MDM) is loaded once at
+//! startup and sits ABOVE all user settings. It can:
+//! - force a specific LLM provider
+//! - disable third-party plugins (all, or by ID)
+//! - mandate additional redaction rules
+//! - disable execution of LLM-suggested shell commands
+//! - pin model versions per provider
+//! - set outbound data thresholds for the anomaly monitor
+//! - restrict where exported files may be saved (`paths.rs`)
+//!
+//! The app never writes the policy file. Precedence rules live in
+//! `precedence.rs` as pure functions.
+
+mod loader;
+pub mod paths;
+pub mod precedence;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// A redaction rule mandated by policy, applied after the built-in patterns.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RedactionRule {
+    /// Label used in the `[REDACTED:<label>]` placeholder.
+    pub label: String,
+    /// Regular expression to match.
+    pub pattern: String,
+}
+
+/// Hourly thresholds for text sent to cloud providers. Exceeding either
+/// one pauses outbound calls until the user re-confirms.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutboundLimits {
+    pub max_chars_per_hour: usize,
+    pub max_requests_per_hour: usize,
+}
+
+impl Default for OutboundLimits {
+    fn default() -> Self {
+        Self {
+            max_chars_per_hour: 200_000,
+            max_requests_per_hour: 300,
+        }
+    }
+}
+
+/// Parsed policy file. Every field is optional and an empty `{}` policy is
+/// equivalent to no policy: a missing field means "no restriction", except
+/// `outbound_limits`, where it keeps the built-in thresholds.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Policy {
+    /// Force this provider ("anthropic" | "gemini"). Users cannot switch.
+    pub forced_provider: Option<String>,
+    /// Disable all third-party MCP plugins. Built-in actions stay available.
+    pub disable_plugins: bool,
+    /// Plugin IDs that must never load, even if the user approved them.
+    pub blocked_plugins: Vec<String>,
+    /// Block running LLM-suggested shell commands.
+    pub disable_command_execution: bool,
+    /// Extra redaction rules applied to all text leaving the machine.
+    pub redaction_rules: Vec<RedactionRule>,
+    /// Pinned model IDs keyed by provider ID.
+    pub pinned_models: HashMap<String, String>,
+    /// Outbound data thresholds. `None` = built-in defaults; the monitor
+    /// itself can't be turned off.
+    pub outbound_limits: Option<OutboundLimits>,
+    /// Folders files may be saved to (`~` allowed). Empty = anywhere safe.
+    pub allowed_save_dirs: Vec<String>,
+    /// Folders files must never be saved to, on top of the built-in list.
+    pub blocked_save_dirs: Vec<String>,
+}
+
+impl Policy {
+    /// What applies while an installed policy file can't be read or
+    /// parsed: the restrictions that don't depend on the organization's
+    /// choices. No plugins, no shell commands; the outbound monitor keeps
+    /// its defaults.
+    pub fn locked_down() -> Self {
+        Self { disable_plugins: true, disable_command_execution: true, ..Self::default() }
+    }
+}
+
+/// The loaded policy plus its pre-compiled redaction patterns.
+struct ActivePolicy {
+    policy: Policy,
+    source: Option<String>,
+    /// Why the installed file was rejected; `policy` is then locked down.
+    error: Option<String>,
+    redaction_patterns: Vec<(Regex, String)>,
+}
+
+static ACTIVE: OnceLock<ActivePolicy> = OnceLock::new();
+static EMPTY: OnceLock<ActivePolicy> = OnceLock::new();
+
+/// Load the policy file from its platform location. Call once at startup,
+/// before any settings are read. Subsequent calls are no-ops.
+pub fn init() {
+    let (policy, source, error) = match loader::load() {
+        Some((Ok(policy), path)) => (policy, Some(path.display().to_string()), None),
+        Some((Err(e), path)) => {
+            log::error!("[POLICY] Rejected {}: {} — running locked down", path.display(), e);
+            (Policy::locked_down(), Some(path.display().to_string()), Some(e))
+        }
+        None => (Policy::default(), None, None),
+    };
+    let redaction_patterns = compile_rules(&policy.redaction_rules);
+    if let Some(path) = source.as_ref().filter(|_| error.is_none()) {
+        log::info!(
+            "[POLICY] Loaded {} (provider={:?}, plugins_disabled={}, commands_disabled={}, {} redaction rules)",
+            path,
+            policy.forced_provider,
+            policy.disable_plugins,
+            policy.disable_command_execution,
+            redaction_patterns.len()
+        );
+    }
+    let _ = ACTIVE.set(ActivePolicy { policy, source, error, redaction_patterns });
+}
+
+fn active() -> &'static ActivePolicy {
+    ACTIVE.get().unwrap_or_else(|| {
+        EMPTY.get_or_init(|| ActivePolicy {
+            policy: Policy::default(),
+            source: None,
+            error: None,
+            redaction_patterns: Vec::new(),
+        })
+    })
+}
+
+/// The active policy. Returns an empty (unrestricted) policy if none is
+/// installed, and `Policy::locked_down()` if the installed one was rejected.
+pub fn current() -> &'static Policy {
+    &active().policy
+}
+
+/// Path of the loaded policy file, if any. Used by the settings panel to
+/// explain why a control is locked.
+pub fn source() -> Option<&'static str> {
+    active().source.as_deref()
+}
+
+/// Why the installed policy file was rejected, if it was.
+pub fn error() -> Option<&'static str> {
+    active().error.as_deref()
+}
+
+/// Tell the user the policy file was rejected and the app is locked
+/// down. Called once the app is up; nothing to say otherwise.
+pub fn warn_if_rejected(app: &tauri::AppHandle) {
+    use crate::i18n::{t, tf};
+    use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+    let (Some(path), Some(error)) = (source(), error()) else { return };
+    app.dialog()
+        .message(tf("policy.rejectedMessage", &[("path", path), ("error", error)]))
+        .title(t("policy.rejectedTitle"))
+        .kind(MessageDialogKind::Error)
+        .show(|_| {});
+}
+
+/// Compiled policy redaction patterns as `(regex, label)` pairs.
+pub fn redaction_patterns() -> &'static [(Regex, String)] {
+    &active().redaction_patterns
+}
+
+/// Check a save-dialog path against the built-in and policy folder rules
+/// (`paths.rs`); returns the path to write.
+pub fn check_save_path(path: &str) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().map(|h| h.to_string_lossy().into_owned());
+    paths::check_save_path(path, current(), home.as_deref())
+}
+
+/// Compile redaction rules. Invalid patterns are logged and skipped.
+fn compile_rules(rules: &[RedactionRule]) -> Vec<(Regex, String)> {
+    rules
+        .iter()
+        .filter_map(|rule| match Regex::new(&rule.pattern) {
+            Ok(re) => Some((re, rule.label.clone())),
+            Err(e) => {
+                log::error!("[POLICY] Invalid redaction pattern '{}': {}", rule.label, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Tauri command: summarize the active policy for the settings panel.
+#[tauri::command]
+pub fn get_policy() -> serde_json::Value {
+    serde_json::json!({
+        "active": source().is_some(),
+        "source": source(),
+        "error": error(),
+        "policy": current(),
+    })
+}
@@ -0,0 +1,123 @@
+//! Where files may be written — save-path rules (pure, no I/O).
+//!
+//! Paths come from the native save dialog, so they must be absolute. A
+//! path is refused when it climbs with `..`, lands in a system folder, or
+//! lands in a folder that holds credentials (`~/.ssh`, `~/.aws`, …) or the
+//! app's own config. Policy narrows this further:
+//!   - `blockedSaveDirs`: more folders to refuse
+//!   - `allowedSaveDirs`: when non-empty, everything outside them is refused
+//!
+//! Folders may start with `~`. Matching ignores case and `/` vs `\`, so
+//! `C:\Windows` also catches `c:/windows/temp`.
+
+use super::Policy;
+use std::path::PathBuf;
+
+/// System folders nothing is saved into.
+const SYSTEM_DIRS: &[&str] = &[
+    "/System",
+    "/Library",
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/etc",
+    "/private/etc",
+    "/boot",
+    "/dev",
+    "/proc",
+    "C:\\Windows",
+    "C:\\Program Files",
+    "C:\\Program Files (x86)",
+];
+
+/// Folders under the home directory that hold secrets or app config.
+const HOME_SECRET_DIRS: &[&str] = &["~/.ssh", "~/.gnupg", "~/.aws", "~/.kube", "~/.config/omni-glass"];
+
+/// `path` if it may be written, or why not. `home` expands `~` in the
+/// folder lists; without it, `~` folders are skipped.
+pub fn check_save_path(path: &str, policy: &Policy, home: Option<&str>) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if !is_absolute(path) {
+        return Err("Save path must be absolute".to_string());
+    }
+    if path.split(['/', '\\']).any(|part| part == "..") {
+        return Err("Save path must not contain '..'".to_string());
+    }
+    let target = key(path);
+    if inside(&target, SYSTEM_DIRS, home) || inside(&target, HOME_SECRET_DIRS, home) {
+        return Err("Files can't be saved in system or credential folders".to_string());
+    }
+    if inside(&target, &policy.blocked_save_dirs, home) {
+        return Err("Saving to this folder is blocked by your organization's policy".to_string());
+    }
+    if !policy.allowed_save_dirs.is_empty() && !inside(&target, &policy.allowed_save_dirs, home) {
+        return Err("Your organization's policy only allows saving to approved folders".to_string());
+    }
+    Ok(PathBuf::from(path))
+}
+
+/// Whether `target` (from `key`) is one of `dirs` or inside one.
+fn inside(target: &str, dirs: &[impl AsRef<str>], home: Option<&str>) -> bool {
+    dirs.iter().filter_map(|dir| expand(dir.as_ref(), home)).any(|dir| target == dir || target.starts_with(&format!("{}/", dir)))
+}
+
+/// `/…` or a Windows drive path (`C:\…`, `C:/…`) or UNC share (`\\host\…`).
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || path.starts_with("\\\\")
+        || (bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'/' | b'\\'))
+}
+
+/// Comparable form: `/` separators, lowercase, no trailing separator.
+fn key(path: &str) -> String {
+    path.replace('\\', "/").to_lowercase().trim_end_matches('/').to_string()
+}
+
+/// A folder from a list in comparable form, with `~` expanded; `None`
+/// when it needs a home directory we don't have.
+fn expand(dir: &str, home: Option<&str>) -> Option<String> {
+    let dir = dir.trim();
+    let full = match dir.strip_prefix('~') {
+        Some(rest) => format!("{}{}", home?.trim_end_matches(['/', '\\']), rest),
+        None => dir.to_string(),
+    };
+    Some(key(&full)).filter(|k| !k.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOME: Option<&str> = Some("/Users/ana");
+
+    #[test]
+    fn built_in_rules() {
+        let policy = Policy::default();
+        assert!(check_save_path("/Users/ana/Desktop/snip.png", &policy, HOME).is_ok());
+        assert!(check_save_path("C:\\Users\\ana\\Documents\\table.xlsx", &policy, HOME).is_ok());
+        assert!(check_save_path("snip.png", &policy, HOME).is_err());
+        assert!(check_save_path("/Users/ana/Desktop/../.ssh/id_rsa", &policy, HOME).is_err());
+        assert!(check_save_path("/etc/hosts", &policy, HOME).is_err());
+        assert!(check_save_path("c:/windows/system32/x.txt", &policy, HOME).is_err());
+        assert!(check_save_path("/Users/ana/.SSH/authorized_keys", &policy, HOME).is_err());
+        assert!(check_save_path("/Users/ana/.config/omni-glass/settings.json", &policy, HOME).is_err());
+        assert!(check_save_path("/Users/ana/Library/notes.txt", &policy, HOME).is_ok());
+        assert!(check_save_path("/etcetera/notes.txt", &policy, HOME).is_ok());
+    }
+
+    #[test]
+    fn policy_folders() {
+        let policy = Policy {
+            allowed_save_dirs: vec!["~/Documents".into(), "/Volumes/Share/".into()],
+            blocked_save_dirs: vec!["~/Documents/Private".into()],
+            ..Policy::default()
+        };
+        assert!(check_save_path("/Users/ana/Documents/a.csv", &policy, HOME).is_ok());
+        assert!(check_save_path("/Volumes/Share/team/a.csv", &policy, HOME).is_ok());
+        assert!(check_save_path("/Users/ana/Desktop/a.csv", &policy, HOME).is_err());
+        assert!(check_save_path("/Users/ana/Documents/private/a.csv", &policy, HOME).is_err());
+        // Without a home directory, `~` folders can't match anything.
+        assert!(check_save_path("/Users/ana/Documents/a.csv", &policy, None).is_err());
+    }
+}
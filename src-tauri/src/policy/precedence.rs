@@ -0,0 +1,131 @@
+//! Precedence rules — how policy values override user settings.
+//!
+//! Order (highest wins):
+//! 1. Enterprise policy
+//! 2. Explicit user choice (settings panel, stored in settings.json)
+//! 3. Auto-detection / built-in defaults
+//!
+//! Pure functions only: callers pass the policy and the user-level values
+//! in, and get the effective value back.
+
+use super::Policy;
+
+/// Resolve the effective provider.
+///
+/// `auto_detect` is only invoked when neither the policy nor the user
+/// picked a provider, since detection may touch the OS keychain.
+pub fn effective_provider(
+    policy: &Policy,
+    user_choice: Option<&str>,
+    auto_detect: impl FnOnce() -> String,
+) -> String {
+    if let Some(forced) = policy.forced_provider.as_deref() {
+        return forced.to_string();
+    }
+    match user_choice {
+        Some(choice) => choice.to_string(),
+        None => auto_detect(),
+    }
+}
+
+/// Check whether the user may switch to `provider_id`.
+pub fn check_provider_selection(policy: &Policy, provider_id: &str) -> Result<(), String> {
+    match policy.forced_provider.as_deref() {
+        Some(forced) if forced != provider_id => Err(format!(
+            "Provider is locked to '{}' by your organization's policy",
+            forced
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Resolve the model ID for a provider: a pinned model beats the default.
+pub fn effective_model<'a>(policy: &'a Policy, provider_id: &str, default: &'a str) -> &'a str {
+    policy
+        .pinned_models
+        .get(provider_id)
+        .map(|m| m.as_str())
+        .filter(|m| !m.is_empty())
+        .unwrap_or(default)
+}
+
+/// Whether a third-party plugin may load. Built-in tools are never affected.
+pub fn is_plugin_allowed(policy: &Policy, plugin_id: &str) -> bool {
+    !policy.disable_plugins && !policy.blocked_plugins.iter().any(|id| id == plugin_id)
+}
+
+/// Whether LLM-suggested shell commands may run.
+pub fn is_command_execution_allowed(policy: &Policy) -> bool {
+    !policy.disable_command_execution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forced(provider: &str) -> Policy {
+        Policy {
+            forced_provider: Some(provider.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn policy_provider_beats_user_choice() {
+        let p = effective_provider(&forced("gemini"), Some("anthropic"), || "anthropic".into());
+        assert_eq!(p, "gemini");
+    }
+
+    #[test]
+    fn user_choice_beats_auto_detect() {
+        let p = effective_provider(&Policy::default(), Some("gemini"), || "anthropic".into());
+        assert_eq!(p, "gemini");
+    }
+
+    #[test]
+    fn auto_detect_used_without_policy_or_choice() {
+        let p = effective_provider(&Policy::default(), None, || "anthropic".into());
+        assert_eq!(p, "anthropic");
+    }
+
+    #[test]
+    fn auto_detect_skipped_when_forced() {
+        let p = effective_provider(&forced("anthropic"), None, || panic!("should not detect"));
+        assert_eq!(p, "anthropic");
+    }
+
+    #[test]
+    fn provider_selection_locked_by_policy() {
+        assert!(check_provider_selection(&forced("gemini"), "anthropic").is_err());
+        assert!(check_provider_selection(&forced("gemini"), "gemini").is_ok());
+        assert!(check_provider_selection(&Policy::default(), "anthropic").is_ok());
+    }
+
+    #[test]
+    fn pinned_model_overrides_default() {
+        let mut policy = Policy::default();
+        policy.pinned_models.insert("anthropic".into(), "claude-pinned".into());
+        assert_eq!(effective_model(&policy, "anthropic", "claude-default"), "claude-pinned");
+        assert_eq!(effective_model(&policy, "gemini", "gemini-default"), "gemini-default");
+    }
+
+    #[test]
+    fn plugins_disabled_globally_or_by_id() {
+        let blocked = Policy {
+            blocked_plugins: vec!["com.example.bad".into()],
+            ..Default::default()
+        };
+        assert!(!is_plugin_allowed(&blocked, "com.example.bad"));
+        assert!(is_plugin_allowed(&blocked, "com.example.good"));
+
+        let all_off = Policy { disable_plugins: true, ..Default::default() };
+        assert!(!is_plugin_allowed(&all_off, "com.example.good"));
+    }
+
+    #[test]
+    fn command_execution_toggle() {
+        assert!(is_command_execution_allowed(&Policy::default()));
+        let locked = Policy { disable_command_execution: true, ..Default::default() };
+        assert!(!is_command_execution_allowed(&locked));
+    }
+}
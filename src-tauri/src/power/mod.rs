@@ -0,0 +1,140 @@
+//! Adaptive quality — less work per snip on low battery or under heat.
+//!
+//! A background monitor reads the power state every minute:
+//!   - macOS: `pmset -g batt`, `pmset -g` (Low Power Mode), `pmset -g therm`
+//!   - Windows: `GetSystemPowerStatus` (AC line, charge, battery saver)
+//!   - Linux: `/sys/class/power_supply` and the ACPI platform profile
+//!
+//! With `qualityMode` at `"auto"`, thermal pressure, a low-power mode, or
+//! an unplugged battery at or below 20% puts the app in saver mode; the
+//! setting can also pin `"full"` or `"saver"`. In saver mode the pipeline
+//! uses fast OCR everywhere (no accurate re-OCR for code, no speculative
+//! OCR while the menu opens) and encodes the overlay preview at a lower
+//! JPEG quality. `saver()` is what pipeline code asks.
+//!
+//! Changes are broadcast as `quality-mode-changed` with a
+//! `QualityStatus` payload.
+
+pub mod state;
+
+use state::{PowerState, QualityStatus};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+
+const INTERVAL: Duration = Duration::from_secs(60);
+
+/// Last power reading. `None` until the monitor's first read.
+static POWER: Mutex<Option<PowerState>> = Mutex::new(None);
+
+#[cfg(target_os = "macos")]
+fn read_power() -> PowerState {
+    let pmset = |args: &[&str]| {
+        std::process::Command::new("pmset")
+            .args(args)
+            .output()
+            .map(|out| String::from_utf8_lossy(&out.stdout).to_string())
+            .unwrap_or_default()
+    };
+    let (on_battery, battery_percent) = state::parse_pmset_batt(&pmset(&["-g", "batt"]));
+    PowerState {
+        on_battery,
+        battery_percent,
+        low_power: state::parse_pmset_low_power(&pmset(&["-g"])),
+        thermal_pressure: state::parse_pmset_therm(&pmset(&["-g", "therm"])),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read_power() -> PowerState {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    if unsafe { GetSystemPowerStatus(&mut status) }.is_err() {
+        return PowerState::default();
+    }
+    PowerState {
+        // ACLineStatus: 0 offline, 1 online, 255 unknown.
+        on_battery: status.ACLineStatus == 0,
+        battery_percent: (status.BatteryLifePercent <= 100).then_some(status.BatteryLifePercent),
+        // SystemStatusFlag 1: battery saver is on.
+        low_power: status.SystemStatusFlag == 1,
+        thermal_pressure: false,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_power() -> PowerState {
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).map(|s| s.trim().to_string()).ok();
+    let battery = std::fs::read_dir("/sys/class/power_supply")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| read(path.join("type")).as_deref() == Some("Battery"));
+    let (on_battery, battery_percent) = match battery {
+        Some(path) => (
+            read(path.join("status")).as_deref() == Some("Discharging"),
+            read(path.join("capacity")).and_then(|c| c.parse().ok()),
+        ),
+        None => (false, None),
+    };
+    let profile = read("/sys/firmware/acpi/platform_profile".into());
+    PowerState { on_battery, battery_percent, low_power: profile.as_deref() == Some("low-power"), thermal_pressure: false }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn read_power() -> PowerState {
+    PowerState::default()
+}
+
+pub fn status() -> QualityStatus {
+    let power = POWER.lock().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default();
+    state::decide(&crate::settings::current().quality_mode, power)
+}
+
+/// Whether the pipeline should cut back (see module docs).
+pub fn saver() -> bool {
+    status().saver()
+}
+
+/// Tell every window the current mode.
+pub fn announce(app: &tauri::AppHandle) {
+    let status = status();
+    log::info!("[POWER] Quality mode: {} (setting={}, reason={:?})", status.mode, status.setting, status.reason);
+    let _ = app.emit("quality-mode-changed", &status);
+}
+
+/// Read the power state now; announce if the mode changed.
+fn refresh(app: &tauri::AppHandle) {
+    let before = status();
+    let power = read_power();
+    log::debug!("[POWER] {:?}", power);
+    *POWER.lock().unwrap_or_else(|e| e.into_inner()) = Some(power);
+    if status().mode != before.mode {
+        announce(app);
+    }
+}
+
+/// Keep reading the power state in the background. Called once from `setup`.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        refresh(&app);
+        std::thread::sleep(INTERVAL);
+    });
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the active quality mode and why.
+#[tauri::command]
+pub fn get_quality_mode() -> QualityStatus {
+    status()
+}
+
+/// Tauri command: set the `qualityMode` override (`auto`, `full`, `saver`).
+#[tauri::command]
+pub fn set_quality_mode(app: tauri::AppHandle, mode: String) -> Result<QualityStatus, String> {
+    crate::settings::update(&app, serde_json::json!({ "qualityMode": mode }))?;
+    Ok(status())
+}
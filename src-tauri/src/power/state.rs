@@ -0,0 +1,132 @@
+//! Power state and the quality decision (pure, no I/O).
+//!
+//! Platform readers in `mod.rs` fill a `PowerState`; on macOS that is
+//! parsed from `pmset` output here. `decide` turns it and the
+//! `qualityMode` setting into the mode the pipeline should run in.
+
+use serde::Serialize;
+
+/// Values of the `qualityMode` setting; `auto` follows the power state.
+pub const QUALITY_MODES: &[&str] = &["auto", "full", "saver"];
+/// Battery level at or below which an unplugged machine saves power.
+pub const LOW_BATTERY_PERCENT: u8 = 20;
+/// `CPU_Speed_Limit` below this means the CPU is being throttled.
+const THROTTLED_BELOW: u32 = 100;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerState {
+    pub on_battery: bool,
+    pub battery_percent: Option<u8>,
+    /// macOS Low Power Mode / Windows battery saver / Linux power-saver profile.
+    pub low_power: bool,
+    /// The OS reports thermal throttling.
+    pub thermal_pressure: bool,
+}
+
+impl PowerState {
+    /// Why work should be cut back, if it should.
+    pub fn pressure(&self) -> Option<&'static str> {
+        if self.thermal_pressure {
+            Some("thermal")
+        } else if self.low_power {
+            Some("lowPower")
+        } else if self.on_battery && self.battery_percent.is_some_and(|p| p <= LOW_BATTERY_PERCENT) {
+            Some("lowBattery")
+        } else {
+            None
+        }
+    }
+}
+
+/// Payload of `quality-mode-changed` and `get_quality_mode`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityStatus {
+    /// `"full"` or `"saver"`.
+    pub mode: &'static str,
+    /// The `qualityMode` setting: `"auto"`, `"full"`, or `"saver"`.
+    pub setting: String,
+    /// What put the app in saver mode: `setting`, `thermal`, `lowPower`, `lowBattery`.
+    pub reason: Option<&'static str>,
+    pub power: PowerState,
+}
+
+impl QualityStatus {
+    pub fn saver(&self) -> bool {
+        self.mode == "saver"
+    }
+}
+
+/// The mode for `setting` under `power`. Only `"auto"` follows the power state.
+pub fn decide(setting: &str, power: PowerState) -> QualityStatus {
+    let reason = match setting {
+        "full" => None,
+        "saver" => Some("setting"),
+        _ => power.pressure(),
+    };
+    QualityStatus { mode: if reason.is_some() { "saver" } else { "full" }, setting: setting.to_string(), reason, power }
+}
+
+/// `pmset -g batt`: on battery, and the charge of the internal battery.
+pub fn parse_pmset_batt(text: &str) -> (bool, Option<u8>) {
+    let on_battery = text.contains("'Battery Power'");
+    let percent = text
+        .lines()
+        .filter(|l| l.contains("InternalBattery"))
+        .find_map(|l| l.split_whitespace().find_map(|w| w.strip_suffix("%;")?.parse().ok()));
+    (on_battery, percent)
+}
+
+/// `pmset -g`: Low Power Mode (`lowpowermode 1`).
+pub fn parse_pmset_low_power(text: &str) -> bool {
+    text.lines().any(|l| {
+        let mut words = l.split_whitespace();
+        words.next() == Some("lowpowermode") && words.next() == Some("1")
+    })
+}
+
+/// `pmset -g therm`: a recorded thermal warning or a CPU speed limit.
+pub fn parse_pmset_therm(text: &str) -> bool {
+    text.lines().any(|l| {
+        let l = l.trim();
+        let limited = l
+            .strip_prefix("CPU_Speed_Limit")
+            .and_then(|v| v.trim().trim_start_matches('=').trim().parse::<u32>().ok())
+            .is_some_and(|limit| limit < THROTTLED_BELOW);
+        limited || (l.contains("warning level") && !l.contains("No "))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pmset_output() {
+        let batt = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=4653155)\t18%; discharging; 1:02 remaining present: true\n";
+        assert_eq!(parse_pmset_batt(batt), (true, Some(18)));
+        assert_eq!(parse_pmset_batt("Now drawing from 'AC Power'\n"), (false, None));
+
+        assert!(parse_pmset_low_power("System-wide power settings:\n lowpowermode         1\n standby 1\n"));
+        assert!(!parse_pmset_low_power(" lowpowermode         0\n"));
+
+        let cool = "Note: No thermal warning level has been recorded\n\tCPU_Scheduler_Limit \t= 100\n\tCPU_Speed_Limit \t= 100\n";
+        assert!(!parse_pmset_therm(cool));
+        assert!(parse_pmset_therm("\tCPU_Speed_Limit \t= 64\n"));
+        assert!(parse_pmset_therm("Thermal warning level set to 2.\n"));
+    }
+
+    #[test]
+    fn only_auto_follows_the_power_state() {
+        let low = PowerState { on_battery: true, battery_percent: Some(15), ..PowerState::default() };
+        assert_eq!(decide("auto", low.clone()).reason, Some("lowBattery"));
+        assert!(!decide("full", low.clone()).saver());
+        assert!(!decide("auto", PowerState { on_battery: false, ..low.clone() }).saver());
+
+        let hot = PowerState { thermal_pressure: true, ..PowerState::default() };
+        assert_eq!(decide("auto", hot).reason, Some("thermal"));
+        assert_eq!(decide("saver", PowerState::default()).reason, Some("setting"));
+        assert_eq!(decide("auto", PowerState::default()).mode, "full");
+    }
+}
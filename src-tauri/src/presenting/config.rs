@@ -0,0 +1,71 @@
+//! What a snip does while the screen is shared (`capture.presenting`),
+//! with validation (pure, no I/O).
+//!
+//! `mode` is `warn` (the default: ask before a snip opens), `pause`
+//! (snips don't open until the share ends), or `off` (shares aren't
+//! looked for). `indicators` adds window titles, or parts of them, that
+//! mean a share is on, for meeting apps the built-in list doesn't know.
+
+use serde::{Deserialize, Serialize};
+
+pub const PRESENTING_MODES: &[&str] = &["warn", "pause", "off"];
+/// Extra indicator titles allowed in settings.
+const MAX_INDICATORS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentingPrefs {
+    /// `"warn"`, `"pause"`, or `"off"`.
+    #[serde(default = "default_mode")]
+    pub mode: String,
+    /// Parts of window titles shown only while a share is on.
+    #[serde(default)]
+    pub indicators: Vec<String>,
+}
+
+fn default_mode() -> String {
+    "warn".to_string()
+}
+
+impl Default for PresentingPrefs {
+    fn default() -> Self {
+        Self { mode: default_mode(), indicators: Vec::new() }
+    }
+}
+
+impl PresentingPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if !PRESENTING_MODES.contains(&self.mode.as_str()) {
+            return Err(format!("Unknown screen-sharing mode: {}", self.mode));
+        }
+        if self.indicators.len() > MAX_INDICATORS {
+            return Err(format!("Up to {} screen-sharing indicators are allowed", MAX_INDICATORS));
+        }
+        if self.indicators.iter().any(|i| i.trim().is_empty()) {
+            return Err("A screen-sharing indicator is empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether shares are looked for at all.
+    pub fn watching(&self) -> bool {
+        self.mode != "off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modes_and_indicators_are_checked() {
+        let prefs: PresentingPrefs = serde_json::from_str("{}").unwrap();
+        assert_eq!(prefs, PresentingPrefs::default());
+        assert!(prefs.watching() && prefs.validate().is_ok());
+        let off = PresentingPrefs { mode: "off".into(), ..prefs.clone() };
+        assert!(!off.watching() && off.validate().is_ok());
+        assert!(PresentingPrefs { mode: "hide".into(), ..prefs.clone() }.validate().is_err());
+        assert!(PresentingPrefs { indicators: vec![" ".into()], ..prefs.clone() }.validate().is_err());
+        assert!(PresentingPrefs { indicators: vec!["x".into(); 51], ..prefs }.validate().is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Is the screen being shared? Guessed from window titles (pure, no I/O).
+//!
+//! No OS API says that another app is capturing the screen, but meeting
+//! apps and browsers show a toolbar or indicator window while they share
+//! it, with a title that gives it away. The window list (CGWindowList on
+//! macOS, top-level windows on Windows and X11) is checked for those.
+//! Titles are matched case-insensitively, as parts of the whole title.
+
+/// A window as the detector sees it: its owning app and its title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitledWindow {
+    pub app: String,
+    pub title: String,
+}
+
+/// (part of the app name, part of the title), lowercase; an empty app
+/// part matches any app.
+const INDICATORS: &[(&str, &str)] = &[
+    // Zoom's floating share toolbar ("zoom share toolbar window")
+    ("zoom", "share toolbar"),
+    ("zoom", "you are screen sharing"),
+    // Microsoft Teams' bar over a shared screen
+    ("teams", "sharing control bar"),
+    ("webex", "sharing"),
+    ("slack", "sharing your screen"),
+    // Chromium browsers, for Meet and other web meetings:
+    // "meet.google.com is sharing your screen."
+    ("", "is sharing your screen"),
+    ("", "is sharing a window"),
+    ("", "is sharing this tab"),
+    ("", "is sharing a tab"),
+    ("firefox", "sharing indicator"),
+];
+
+/// The app sharing the screen, if one of `windows` is a share indicator:
+/// a built-in one, or one whose title contains one of `extra`.
+pub fn sharing_app(windows: &[TitledWindow], extra: &[String]) -> Option<String> {
+    let extra: Vec<String> = extra.iter().map(|e| e.trim().to_lowercase()).filter(|e| !e.is_empty()).collect();
+    windows
+        .iter()
+        .find(|w| {
+            let (app, title) = (w.app.to_lowercase(), w.title.to_lowercase());
+            INDICATORS.iter().any(|(a, t)| app.contains(a) && title.contains(t)) || extra.iter().any(|e| title.contains(e))
+        })
+        .map(|w| if w.app.trim().is_empty() { w.title.clone() } else { w.app.clone() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(app: &str, title: &str) -> TitledWindow {
+        TitledWindow { app: app.into(), title: title.into() }
+    }
+
+    #[test]
+    fn meeting_and_browser_indicators_are_found() {
+        let zoom = [window("Safari", "Docs"), window("zoom.us", "zoom share toolbar window")];
+        assert_eq!(sharing_app(&zoom, &[]), Some("zoom.us".into()));
+        let meet = [window("Google Chrome", "meet.google.com is sharing your screen.")];
+        assert_eq!(sharing_app(&meet, &[]), Some("Google Chrome".into()));
+        let teams = [window("Microsoft Teams", "Sharing control bar | Microsoft Teams")];
+        assert_eq!(sharing_app(&teams, &[]), Some("Microsoft Teams".into()));
+    }
+
+    #[test]
+    fn ordinary_windows_are_not_shares() {
+        // Watching someone else's share, or reading about sharing
+        let windows = [
+            window("zoom.us", "Zoom Meeting"),
+            window("Safari", "How to stop sharing in Webex - Help"),
+            window("Code", "sharing.rs — omni-glass"),
+        ];
+        assert_eq!(sharing_app(&windows, &[]), None);
+        assert_eq!(sharing_app(&windows, &[" ".into()]), None);
+        let whereby = [window("", "Whereby — Presenting")];
+        assert_eq!(sharing_app(&whereby, &["presenting".into()]), Some("Whereby — Presenting".into()));
+    }
+}
@@ -0,0 +1,122 @@
+//! Presenting domain — keep snips off a shared screen.
+//!
+//! A snip taken while presenting shows the overlay, the action menu, and
+//! the answer to everyone watching. A background monitor reads the window
+//! list every few seconds and looks for a meeting app's or browser's
+//! share indicator (`detect.rs`). While one is up, every snip (hotkey,
+//! tray, launcher, triggers, links) goes through `gate`: with
+//! `capture.presenting.mode` at `warn` a dialog asks first; at `pause`
+//! the snip doesn't open and a notification says why. A share starting
+//! or ending is logged, and notified in `pause` mode.
+//!
+//! On Wayland other apps' windows can't be listed, so no share is found.
+
+pub mod config;
+pub mod detect;
+
+use crate::desktop::session::Display;
+use crate::i18n::{t, tf};
+use detect::TitledWindow;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+const INTERVAL: Duration = Duration::from_secs(2);
+
+/// The app sharing the screen at the last look; `None` while nothing is.
+static SHARING: Mutex<Option<String>> = Mutex::new(None);
+
+/// The app sharing the screen, as of the monitor's last look.
+pub fn sharing() -> Option<String> {
+    SHARING.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Look for a share indicator among other apps' windows now.
+fn look(prefs: &config::PresentingPrefs) -> Option<String> {
+    if !prefs.watching() || crate::desktop::display() == Display::Wayland {
+        return None;
+    }
+    let windows: Vec<TitledWindow> = match crate::capture::window_titles() {
+        Ok(all) => all.into_iter().map(|(app, title)| TitledWindow { app, title }).collect(),
+        Err(e) => {
+            log::debug!("[PRESENTING] {}", e);
+            return None;
+        }
+    };
+    detect::sharing_app(&windows, &prefs.indicators)
+}
+
+fn notify(app: &AppHandle, title: String, body: String) {
+    use tauri_plugin_notification::NotificationExt;
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("[PRESENTING] Failed to show notification: {}", e);
+    }
+}
+
+/// Look now; log and, when pausing, notify if the share started or ended.
+fn refresh(app: &AppHandle) {
+    let prefs = crate::settings::current().capture.presenting;
+    let now = look(&prefs);
+    let before = std::mem::replace(&mut *SHARING.lock().unwrap_or_else(|e| e.into_inner()), now.clone());
+    if before == now {
+        return;
+    }
+    let pausing = prefs.mode == "pause";
+    match (now, before) {
+        (Some(sharer), _) => {
+            log::info!("[PRESENTING] {} is sharing the screen", sharer);
+            if pausing {
+                notify(app, t("presenting.pausedTitle"), tf("presenting.pausedBody", &[("app", &sharer)]));
+            }
+        }
+        (None, Some(sharer)) => {
+            log::info!("[PRESENTING] {} stopped sharing the screen", sharer);
+            if pausing {
+                notify(app, t("presenting.resumedTitle"), t("presenting.resumedBody"));
+            }
+        }
+        (None, None) => {}
+    }
+}
+
+/// Keep looking for screen shares in the background. Called once from `setup`.
+pub fn spawn_monitor(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        refresh(&app);
+        std::thread::sleep(INTERVAL);
+    });
+}
+
+/// Run `snip` now unless the screen is shared. Then, in `warn` mode, a
+/// dialog asks first and a confirmed snip starts on the main thread, as
+/// from the tray; in `pause` mode it is dropped with a notification.
+pub fn gate(app: &AppHandle, snip: impl FnOnce(&AppHandle) + Send + 'static) {
+    let prefs = crate::settings::current().capture.presenting;
+    let Some(sharer) = sharing().filter(|_| prefs.watching()) else {
+        snip(app);
+        return;
+    };
+    if prefs.mode == "pause" {
+        log::info!("[PRESENTING] Snip skipped: {} is sharing the screen", sharer);
+        notify(app, t("presenting.skippedTitle"), tf("presenting.pausedBody", &[("app", &sharer)]));
+        return;
+    }
+    log::info!("[PRESENTING] {} is sharing the screen; asking before the snip", sharer);
+    let handle = app.clone();
+    app.dialog()
+        .message(tf("presenting.warnMessage", &[("app", &sharer)]))
+        .title(t("presenting.warnTitle"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(t("presenting.snipAnyway"), t("presenting.cancel")))
+        .show(move |confirmed| {
+            if !confirmed {
+                log::info!("[PRESENTING] Snip cancelled while sharing");
+                return;
+            }
+            let app = handle.clone();
+            if let Err(e) = handle.run_on_main_thread(move || snip(&app)) {
+                log::warn!("[PRESENTING] Could not start the snip: {}", e);
+            }
+        });
+}
@@ -0,0 +1,53 @@
+//! QR domain — "Show as QR" to move a link, Wi-Fi login, or short text
+//! to a phone.
+//!
+//! `payload.rs` decides what is encoded; this file renders it with the
+//! `qrcode` crate at error-correction level M and returns a PNG. Runs
+//! offline; the action menu calls it directly (`show_qr`).
+
+pub mod payload;
+
+use qrcode::{EcLevel, QrCode};
+use serde::Serialize;
+
+/// Action id, handled by the action menu through `generate_qr`.
+pub const SHOW_ACTION: &str = "show_qr";
+
+/// Smallest rendered size in pixels, quiet zone included.
+const MIN_PIXELS: u32 = 320;
+
+/// A rendered QR code.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QrImage {
+    /// Base64-encoded PNG.
+    pub png: String,
+    /// `url`, `wifi`, or `text`.
+    pub kind: String,
+    pub caption: String,
+}
+
+fn render(data: &str) -> Result<Vec<u8>, String> {
+    let code = QrCode::with_error_correction_level(data.as_bytes(), EcLevel::M).map_err(|e| e.to_string())?;
+    let image = code.render::<image::Luma<u8>>().quiet_zone(true).min_dimensions(MIN_PIXELS, MIN_PIXELS).build();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageLuma8(image)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: `text` (a snip or a result) as a QR code PNG.
+#[tauri::command]
+pub fn generate_qr(text: String) -> Result<QrImage, String> {
+    let payload = payload::payload(&text)?;
+    let png = render(&payload.data)?;
+    log::info!("[QR] Rendered {} payload: {} bytes → {} byte PNG", payload.kind.as_str(), payload.data.len(), png.len());
+    Ok(QrImage {
+        png: base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &png),
+        kind: payload.kind.as_str().to_string(),
+        caption: payload.caption,
+    })
+}
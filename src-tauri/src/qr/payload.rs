@@ -0,0 +1,115 @@
+//! What goes into a QR code for a piece of text (pure, no I/O).
+//!
+//! Phones act on a QR code by its content, so the text is turned into the
+//! form their camera apps recognize:
+//!   - a single link → the normalized URL (`links::extract`), so
+//!     `goog1e.c0m/x` scans as `https://google.com/x`
+//!   - Wi-Fi credentials (`SSID: Home` / `Password: …` lines) → the
+//!     `WIFI:T:WPA;S:…;P:…;;` form that joins the network on scan
+//!   - anything else → the text itself
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Most bytes a QR code holds at error-correction level M (version 40).
+pub const MAX_BYTES: usize = 2331;
+
+static SSID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)^\s*(?:ssid|network(?: name)?|wi-?fi(?: name| network)?|wlan)\s*[:=]\s*(\S.*?)\s*$").unwrap()
+});
+static PASSWORD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)^\s*(?:password|passphrase|passwort|contraseña|wpa key|key|pw)\s*[:=]\s*(\S.*?)\s*$").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Url,
+    Wifi,
+    Text,
+}
+
+impl Kind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Kind::Url => "url",
+            Kind::Wifi => "wifi",
+            Kind::Text => "text",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payload {
+    pub kind: Kind,
+    /// The exact string encoded.
+    pub data: String,
+    /// Shown under the code; a Wi-Fi password is left out.
+    pub caption: String,
+}
+
+/// The payload for `text`, or why it can't be a QR code.
+pub fn payload(text: &str) -> Result<Payload, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("There is no text to encode".to_string());
+    }
+    let payload = if let Some(found) = crate::links::extract::parse(text).filter(|_| !text.contains(char::is_whitespace)) {
+        Payload { kind: Kind::Url, caption: found.url.clone(), data: found.url }
+    } else if let Some(ssid) = SSID_RE.captures(text).map(|c| c[1].to_string()) {
+        let password = PASSWORD_RE.captures(text).map(|c| c[1].to_string());
+        let security = match &password {
+            None => "nopass",
+            Some(_) if text.to_ascii_uppercase().contains("WEP") => "WEP",
+            Some(_) => "WPA",
+        };
+        let password = password.map_or(String::new(), |p| format!("P:{};", escape(&p)));
+        Payload {
+            kind: Kind::Wifi,
+            data: format!("WIFI:T:{};S:{};{};", security, escape(&ssid), password),
+            caption: format!("Wi-Fi: {}", ssid),
+        }
+    } else {
+        Payload { kind: Kind::Text, data: text.to_string(), caption: text.chars().take(80).collect() }
+    };
+    if payload.data.len() > MAX_BYTES {
+        return Err(format!("Too long for a QR code ({} bytes, at most {})", payload.data.len(), MAX_BYTES));
+    }
+    Ok(payload)
+}
+
+/// Backslash-escape the characters the `WIFI:` format reserves.
+fn escape(value: &str) -> String {
+    let mut out = String::new();
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_and_text() {
+        let p = payload("  goog1e.c0m/search \n").unwrap();
+        assert_eq!((p.kind, p.data.as_str()), (Kind::Url, "https://google.com/search"));
+        let p = payload("See https://example.com for details").unwrap();
+        assert_eq!((p.kind, p.data.as_str()), (Kind::Text, "See https://example.com for details"));
+        assert!(payload("   ").is_err());
+        assert!(payload(&"x".repeat(MAX_BYTES + 1)).unwrap_err().contains("Too long"));
+    }
+
+    #[test]
+    fn wifi_credentials() {
+        let p = payload("Guest Wi-Fi\nNetwork: Cafe;Guest\nPassword: p@ss:word\n").unwrap();
+        assert_eq!(p.kind, Kind::Wifi);
+        assert_eq!(p.data, r"WIFI:T:WPA;S:Cafe\;Guest;P:p@ss\:word;;");
+        assert_eq!(p.caption, "Wi-Fi: Cafe;Guest");
+        assert_eq!(payload("SSID = Lobby").unwrap().data, "WIFI:T:nopass;S:Lobby;;");
+        assert!(payload("SSID: Old\nSecurity: WEP\nKey: 12345").unwrap().data.starts_with("WIFI:T:WEP;"));
+    }
+}
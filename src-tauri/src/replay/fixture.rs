@@ -0,0 +1,145 @@
+//! Replay fixtures — one snip's inputs and provider responses.
+//!
+//! Layout, as a directory or under `snip/` in a diagnostics zip:
+//!   ocr.json                 `{"text", "confidence", "accurateText"?}`
+//!   snip.png                 the crop (optional)
+//!   classify.txt             the model's classify text, as streamed (optional)
+//!   execute/<action>.json    Messages API response body per action
+//!
+//! Pure apart from `load`; `entries` / `from_entries` convert to and from
+//! the files.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Directory of a fixture inside a diagnostics bundle.
+pub const BUNDLE_DIR: &str = "snip/";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrRecord {
+    pub text: String,
+    pub confidence: f64,
+    /// Text of the `.accurate` re-OCR, if an action asked for one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accurate_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Fixture {
+    pub ocr: OcrRecord,
+    pub image: Option<Vec<u8>>,
+    pub classify: Option<String>,
+    /// Response body by action id.
+    pub execute: BTreeMap<String, String>,
+}
+
+impl Fixture {
+    /// The fixture as (relative path, contents) pairs.
+    pub fn entries(&self) -> Vec<(String, Vec<u8>)> {
+        let ocr = serde_json::to_vec_pretty(&self.ocr).unwrap_or_default();
+        let mut entries = vec![("ocr.json".to_string(), ocr)];
+        if let Some(png) = &self.image {
+            entries.push(("snip.png".to_string(), png.clone()));
+        }
+        if let Some(text) = &self.classify {
+            entries.push(("classify.txt".to_string(), text.clone().into_bytes()));
+        }
+        for (action, body) in &self.execute {
+            entries.push((format!("execute/{}.json", action), body.clone().into_bytes()));
+        }
+        entries
+    }
+
+    /// Rebuild a fixture from `entries`; paths are relative to the fixture root.
+    pub fn from_entries(entries: BTreeMap<String, Vec<u8>>) -> Result<Self, String> {
+        let ocr = entries.get("ocr.json").ok_or("Fixture has no ocr.json")?;
+        let ocr: OcrRecord = serde_json::from_slice(ocr).map_err(|e| format!("Invalid ocr.json: {}", e))?;
+        let text = |bytes: &Vec<u8>| String::from_utf8_lossy(bytes).into_owned();
+        let execute = entries
+            .iter()
+            .filter_map(|(path, body)| {
+                let action = path.strip_prefix("execute/")?.strip_suffix(".json")?;
+                Some((action.to_string(), text(body)))
+            })
+            .collect();
+        Ok(Self {
+            ocr,
+            image: entries.get("snip.png").cloned(),
+            classify: entries.get("classify.txt").map(text),
+            execute,
+        })
+    }
+}
+
+/// Load a fixture from a directory, or from the `snip/` entries of a
+/// diagnostics zip.
+pub fn load(path: &Path) -> Result<Fixture, String> {
+    let mut entries = BTreeMap::new();
+    if path.is_dir() {
+        let mut files = vec![path.join("ocr.json"), path.join("snip.png"), path.join("classify.txt")];
+        if let Ok(dir) = std::fs::read_dir(path.join("execute")) {
+            files.extend(dir.flatten().map(|e| e.path()));
+        }
+        for file in files {
+            let Ok(bytes) = std::fs::read(&file) else { continue };
+            let relative = file.strip_prefix(path).unwrap_or(&file);
+            entries.insert(relative.to_string_lossy().replace('\\', "/"), bytes);
+        }
+    } else {
+        let file = std::fs::File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+            let Some(relative) = entry.name().strip_prefix(BUNDLE_DIR).map(str::to_string) else { continue };
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+            entries.insert(relative, bytes);
+        }
+        if entries.is_empty() {
+            return Err("This diagnostics bundle was exported without the snip".to_string());
+        }
+    }
+    Fixture::from_entries(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Fixture {
+        Fixture {
+            ocr: OcrRecord { text: "ModuleNotFoundError".into(), confidence: 0.9, accurate_text: None },
+            image: Some(vec![0x89, b'P', b'N', b'G']),
+            classify: Some("{\"contentType\": \"error\"}".into()),
+            execute: BTreeMap::from([("explain_error".into(), "{\"content\": []}".into())]),
+        }
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let entries: BTreeMap<_, _> = fixture().entries().into_iter().collect();
+        assert!(entries.contains_key("execute/explain_error.json"));
+        assert_eq!(Fixture::from_entries(entries).unwrap(), fixture());
+
+        let only_ocr = BTreeMap::from([("ocr.json".to_string(), b"{\"text\": \"hi\", \"confidence\": 1}".to_vec())]);
+        let loaded = Fixture::from_entries(only_ocr).unwrap();
+        assert_eq!((loaded.ocr.text.as_str(), loaded.image, loaded.classify), ("hi", None, None));
+    }
+
+    #[test]
+    fn loads_a_directory() {
+        let dir = std::env::temp_dir().join(format!("omni-glass-replay-{}", std::process::id()));
+        for (path, bytes) in fixture().entries() {
+            let file = dir.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, bytes).unwrap();
+        }
+        let loaded = load(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(loaded.unwrap(), fixture());
+        assert!(Fixture::from_entries(BTreeMap::new()).unwrap_err().contains("ocr.json"));
+    }
+}
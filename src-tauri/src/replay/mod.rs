@@ -0,0 +1,141 @@
+//! Recorded-snip replay — reproduce a user's snip locally.
+//!
+//! The last snip is kept in memory as a `Fixture` while it runs: the crop
+//! and OCR text (`begin`), the model's classify text (`record_classify`),
+//! the `.accurate` re-OCR, and each execute response body
+//! (`record_execute`). `export_diagnostics` writes it under `snip/` only
+//! when the user chooses to include the snip.
+//!
+//! `replay_snip` loads such a bundle (or a fixture directory) and drives
+//! the menu from it instead of the screen and the provider: the action menu
+//! opens, the recorded classify text streams through the same skeleton and
+//! item events, and built-in LLM actions get their recorded response, so
+//! parsing, salvage, and the safety checks see exactly what the user's
+//! snip did. No provider is called for those, and nothing is written to
+//! history or sent to webhooks. The replay lasts until the next real snip.
+
+pub mod fixture;
+
+use crate::llm::{self, ActionMenu};
+use fixture::{Fixture, OcrRecord};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use tauri::{Emitter, Manager};
+
+/// The latest real snip.
+static LAST: Mutex<Option<Fixture>> = Mutex::new(None);
+/// The fixture being replayed, if any.
+static REPLAYING: Mutex<Option<Fixture>> = Mutex::new(None);
+
+fn lock(fixture: &Mutex<Option<Fixture>>) -> MutexGuard<'_, Option<Fixture>> {
+    fixture.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A real snip finished OCR: start recording it, and end any replay.
+pub fn begin(text: &str, confidence: f64, png: &[u8]) {
+    *lock(&REPLAYING) = None;
+    *lock(&LAST) = Some(Fixture {
+        ocr: OcrRecord { text: text.to_string(), confidence, accurate_text: None },
+        image: Some(png.to_vec()),
+        ..Fixture::default()
+    });
+}
+
+/// Add to the latest snip's recording (not while replaying one).
+fn update(change: impl FnOnce(&mut Fixture)) {
+    if active() {
+        return;
+    }
+    if let Some(fixture) = lock(&LAST).as_mut() {
+        change(fixture);
+    }
+}
+
+/// The classify text as the model streamed it, before parsing.
+pub fn record_classify(text: &str) {
+    update(|f| f.classify = Some(text.to_string()));
+}
+
+/// The Messages API response body of an execute call.
+pub fn record_execute(action_id: &str, body: &str) {
+    update(|f| {
+        f.execute.insert(action_id.to_string(), body.to_string());
+    });
+}
+
+pub fn record_accurate_text(text: &str) {
+    update(|f| f.ocr.accurate_text = Some(text.to_string()));
+}
+
+/// The latest real snip, for the diagnostics bundle.
+pub fn last() -> Option<Fixture> {
+    lock(&LAST).clone()
+}
+
+pub fn active() -> bool {
+    lock(&REPLAYING).is_some()
+}
+
+/// While replaying, the recorded response body for `action_id` — or an
+/// error if the snip never ran it; the provider is not called either way.
+/// `None` when not replaying.
+pub fn execute_body(action_id: &str) -> Option<Result<String, String>> {
+    let replaying = lock(&REPLAYING);
+    let fixture = replaying.as_ref()?;
+    Some(
+        fixture
+            .execute
+            .get(action_id)
+            .cloned()
+            .ok_or_else(|| format!("No recorded response for {} in this replay", action_id)),
+    )
+}
+
+/// While replaying, the recorded `.accurate` text (the `.fast` text if the
+/// snip never re-OCRed), so no OCR runs.
+pub fn accurate_text() -> Option<String> {
+    lock(&REPLAYING).as_ref().map(|f| f.ocr.accurate_text.clone().unwrap_or_else(|| f.ocr.text.clone()))
+}
+
+// ── Tauri commands ──
+
+/// Tauri command: replay the snip in `fixture_path` (a diagnostics zip
+/// exported with the snip, or a fixture directory). Returns the final menu.
+#[tauri::command]
+pub async fn replay_snip(app: tauri::AppHandle, fixture_path: String) -> Result<ActionMenu, String> {
+    let fixture = fixture::load(Path::new(&fixture_path))?;
+    log::info!(
+        "[REPLAY] {}: {} chars, classify {}, {} recorded actions",
+        fixture_path,
+        fixture.ocr.text.len(),
+        if fixture.classify.is_some() { "recorded" } else { "missing" },
+        fixture.execute.len()
+    );
+
+    let menu_state = app.state::<llm::ActionMenuState>();
+    *menu_state.menu.lock().unwrap() = None;
+    *menu_state.ocr_text.lock().unwrap() = Some(fixture.ocr.text.clone());
+    *menu_state.crop_png.lock().unwrap() = fixture.image.clone();
+    *menu_state.auto_action.lock().unwrap() = None;
+    *menu_state.injection_warning.lock().unwrap() = None;
+    *menu_state.last_result.lock().unwrap() = None;
+    *lock(&REPLAYING) = Some(fixture.clone());
+
+    crate::windows::open_action_menu(&app, None)?;
+    let text = &fixture.ocr.text;
+    let mut menu = match &fixture.classify {
+        Some(raw) => llm::replay_classify(&app, raw).await,
+        None => {
+            let menu = ActionMenu::fallback();
+            let _ = app.emit("action-menu-complete", &menu);
+            menu
+        }
+    };
+    // The same local actions a live snip gets
+    if crate::convert::augment(&mut menu, text) | crate::structured::augment(&mut menu, text) | crate::palette::augment(&mut menu, text) {
+        let _ = app.emit("action-menu-complete", &menu);
+    }
+    *menu_state.menu.lock().unwrap() = Some(menu.clone());
+    log::info!("[REPLAY] Menu: type={}, {} actions", menu.content_type, menu.actions.len());
+    Ok(menu)
+}
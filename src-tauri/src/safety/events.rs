@@ -0,0 +1,82 @@
+//! Safety event log — an audit trail of sensitive host decisions.
+//!
+//! Records brokered clipboard access by plugins and outbound-volume
+//! anomalies, whether allowed or refused. Events are logged, kept in a
+//! bounded in-memory list for the settings panel, and emitted to the
+//! frontend as `safety-event`.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+/// Maximum events kept in memory (oldest dropped first).
+const MAX_EVENTS: usize = 500;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SafetyEventKind {
+    ClipboardRead,
+    ClipboardWrite,
+    OutboundAnomaly,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafetyEvent {
+    pub kind: SafetyEventKind,
+    /// Plugin ID, or the pipeline name for outbound events.
+    pub source: String,
+    /// Whether the action went ahead.
+    pub allowed: bool,
+    /// How it was decided, e.g. "allowed once", "persistent grant", "denied by user".
+    pub detail: String,
+    /// Unix epoch seconds.
+    pub timestamp: u64,
+}
+
+static EVENTS: Mutex<VecDeque<SafetyEvent>> = Mutex::new(VecDeque::new());
+static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Give the log an app handle so events can be emitted.
+/// Called once from lib.rs `.setup()`.
+pub fn attach(app: tauri::AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// Record an event and notify the frontend.
+pub fn record(kind: SafetyEventKind, source: &str, allowed: bool, detail: &str) {
+    let event = SafetyEvent {
+        kind,
+        source: source.to_string(),
+        allowed,
+        detail: detail.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    log::info!(
+        "[SAFETY] {:?} from '{}': {} ({})",
+        kind,
+        source,
+        if allowed { "allowed" } else { "refused" },
+        detail
+    );
+
+    if let Ok(mut events) = EVENTS.lock() {
+        if events.len() >= MAX_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(event.clone());
+    }
+    if let Some(app) = APP.get() {
+        let _ = app.emit("safety-event", &event);
+    }
+}
+
+/// Tauri command: recent safety events, newest last.
+#[tauri::command]
+pub fn get_safety_events() -> Vec<SafetyEvent> {
+    EVENTS.lock().map(|e| e.iter().cloned().collect()).unwrap_or_default()
+}
@@ -1,8 +1,16 @@
-//! Safety layer — sensitive data redaction and command validation.
+//! Safety layer — sensitive data redaction, command validation, and
+//! prompt-injection hardening.
 //!
 //! All OCR text passes through redaction before reaching cloud LLMs.
 //! All LLM-suggested commands pass through the blocklist before
-//! being shown to the user.
+//! being shown to the user. All OCR text is wrapped as untrusted
+//! data before it is placed in a prompt. Outbound volume is monitored
+//! so an automation can't silently exfiltrate large amounts of text.
+//! Sensitive host decisions are kept in an audit trail (`events`).
 
 pub mod command_check;
+pub mod events;
+pub mod injection;
+pub mod outbound;
+pub mod outbound_guard;
 pub mod redact;
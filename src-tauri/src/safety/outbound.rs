@@ -0,0 +1,153 @@
+//! Outbound data monitor — rolling one-hour window of text sent to cloud LLMs.
+//!
+//! A sudden spike (e.g. an automation snipping in a loop) is treated as an
+//! anomaly: further outbound calls are paused until the user re-confirms.
+//! Thresholds come from the enterprise policy or built-in defaults.
+//!
+//! Pure logic: callers pass the current time in, so tests control the clock.
+
+use crate::policy::OutboundLimits;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Length of the rolling window, in seconds.
+pub const WINDOW_SECS: u64 = 3600;
+
+/// Totals for the current window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboundStats {
+    pub chars_last_hour: usize,
+    pub requests_last_hour: usize,
+}
+
+/// Outcome of checking a pending outbound request against the limits.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutboundDecision {
+    Allowed,
+    /// The request would exceed a limit. `reason` is shown to the user.
+    Anomalous { reason: String },
+}
+
+/// Rolling record of outbound requests as `(epoch_secs, chars)` pairs.
+#[derive(Debug, Default)]
+pub struct OutboundWindow {
+    events: VecDeque<(u64, usize)>,
+}
+
+impl OutboundWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop events older than the window.
+    fn prune(&mut self, now: u64) {
+        while let Some(&(ts, _)) = self.events.front() {
+            if now.saturating_sub(ts) < WINDOW_SECS {
+                break;
+            }
+            self.events.pop_front();
+        }
+    }
+
+    /// Totals for the last hour.
+    pub fn stats(&mut self, now: u64) -> OutboundStats {
+        self.prune(now);
+        OutboundStats {
+            chars_last_hour: self.events.iter().map(|(_, c)| c).sum(),
+            requests_last_hour: self.events.len(),
+        }
+    }
+
+    /// Would sending `chars` more right now exceed either limit?
+    pub fn check(&mut self, now: u64, chars: usize, limits: &OutboundLimits) -> OutboundDecision {
+        let stats = self.stats(now);
+        let projected_chars = stats.chars_last_hour + chars;
+        let projected_requests = stats.requests_last_hour + 1;
+
+        if projected_chars > limits.max_chars_per_hour {
+            return OutboundDecision::Anomalous {
+                reason: format!(
+                    "{} characters sent to the cloud in the last hour (limit {})",
+                    projected_chars, limits.max_chars_per_hour
+                ),
+            };
+        }
+        if projected_requests > limits.max_requests_per_hour {
+            return OutboundDecision::Anomalous {
+                reason: format!(
+                    "{} cloud requests in the last hour (limit {})",
+                    projected_requests, limits.max_requests_per_hour
+                ),
+            };
+        }
+        OutboundDecision::Allowed
+    }
+
+    /// Record a request that was allowed (or confirmed by the user).
+    pub fn record(&mut self, now: u64, chars: usize) {
+        self.events.push_back((now, chars));
+    }
+
+    /// Start a fresh window after the user re-confirms, so the next
+    /// prompt only appears after another full limit's worth of data.
+    pub fn reset(&mut self) {
+        self.events.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(chars: usize, requests: usize) -> OutboundLimits {
+        OutboundLimits {
+            max_chars_per_hour: chars,
+            max_requests_per_hour: requests,
+        }
+    }
+
+    #[test]
+    fn allows_under_limits() {
+        let mut w = OutboundWindow::new();
+        w.record(0, 500);
+        assert_eq!(w.check(10, 400, &limits(1000, 10)), OutboundDecision::Allowed);
+    }
+
+    #[test]
+    fn flags_char_volume_spike() {
+        let mut w = OutboundWindow::new();
+        w.record(0, 900);
+        let decision = w.check(10, 200, &limits(1000, 10));
+        assert!(matches!(decision, OutboundDecision::Anomalous { .. }));
+    }
+
+    #[test]
+    fn flags_request_rate_spike() {
+        let mut w = OutboundWindow::new();
+        for t in 0..3 {
+            w.record(t, 1);
+        }
+        let decision = w.check(5, 1, &limits(1000, 3));
+        assert!(matches!(decision, OutboundDecision::Anomalous { .. }));
+    }
+
+    #[test]
+    fn old_events_expire() {
+        let mut w = OutboundWindow::new();
+        w.record(0, 900);
+        assert_eq!(
+            w.check(WINDOW_SECS, 200, &limits(1000, 10)),
+            OutboundDecision::Allowed
+        );
+        assert_eq!(w.stats(WINDOW_SECS).requests_last_hour, 0);
+    }
+
+    #[test]
+    fn reset_clears_window() {
+        let mut w = OutboundWindow::new();
+        w.record(0, 900);
+        w.reset();
+        assert_eq!(w.stats(1), OutboundStats::default());
+    }
+}
@@ -0,0 +1,117 @@
+//! Outbound guard — imperative shell around `outbound.rs`.
+//!
+//! Every pipeline that sends OCR or typed text to a cloud provider calls
+//! `guard_outbound` first. In local-only mode (a setting, or the active
+//! profile) or while offline it refuses outright, without waiting on
+//! the network. When the rolling window is anomalous,
+//! the user gets a native warning dialog and must re-confirm before data
+//! leaves.
+
+use super::events::{self, SafetyEventKind};
+use super::outbound::{OutboundDecision, OutboundStats, OutboundWindow};
+use crate::error::{OmniError, Safety};
+use crate::i18n::{t, tf};
+use crate::policy;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// Rolling outbound window. Managed as Tauri state.
+pub struct OutboundMonitor {
+    pub window: Mutex<OutboundWindow>,
+}
+
+impl OutboundMonitor {
+    pub fn new() -> Self {
+        Self {
+            window: Mutex::new(OutboundWindow::new()),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Check `chars` of outbound text against the limits and record it.
+/// Callers count characters (`chars().count()`), not bytes, as the
+/// limits are in characters.
+///
+/// Returns `Err` if local-only mode is on (setting or active profile), if
+/// the app is offline, or if the volume is anomalous and the user
+/// declined to continue (`Safety::OutboundPaused`).
+pub async fn guard_outbound(
+    app: &tauri::AppHandle,
+    source: &str,
+    chars: usize,
+) -> Result<(), OmniError> {
+    crate::settings::cloud_allowed()?;
+    crate::net::connectivity::check()?;
+    let limits = policy::current().outbound_limits.unwrap_or_default();
+    let monitor = app.state::<OutboundMonitor>();
+    let now = now_secs();
+
+    let decision = {
+        let mut window = monitor.window.lock().map_err(|e| e.to_string())?;
+        let decision = window.check(now, chars, &limits);
+        if decision == OutboundDecision::Allowed {
+            window.record(now, chars);
+        }
+        decision
+    };
+
+    let reason = match decision {
+        OutboundDecision::Allowed => return Ok(()),
+        OutboundDecision::Anomalous { reason } => reason,
+    };
+
+    log::warn!("[SAFETY] Outbound anomaly from {}: {}", source, reason);
+    let _ = app.emit("outbound-anomaly", &reason);
+
+    let confirmed = confirm_with_user(app, &reason).await;
+    let decision = if confirmed { "re-confirmed by user" } else { "paused by user" };
+    events::record(SafetyEventKind::OutboundAnomaly, source, confirmed, &format!("{}: {}", decision, reason));
+    if !confirmed {
+        log::warn!("[SAFETY] User declined outbound request from {}", source);
+        return Err(Safety::OutboundPaused { reason }.into());
+    }
+
+    let mut window = monitor.window.lock().map_err(|e| e.to_string())?;
+    window.reset();
+    window.record(now, chars);
+    log::info!("[SAFETY] User re-confirmed outbound data from {}", source);
+    Ok(())
+}
+
+/// Show a native warning dialog and wait for the user's answer.
+async fn confirm_with_user(app: &tauri::AppHandle, reason: &str) -> bool {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(tf("outbound.dialogMessage", &[("reason", reason)]))
+        .title(t("outbound.dialogTitle"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(t("outbound.continue"), t("outbound.pause")))
+        .show(move |confirmed| {
+            let _ = tx.send(confirmed);
+        });
+    rx.await.unwrap_or(false)
+}
+
+/// Tauri command: current outbound totals and limits for the settings panel.
+#[tauri::command]
+pub fn get_outbound_stats(
+    monitor: tauri::State<'_, OutboundMonitor>,
+) -> Result<serde_json::Value, String> {
+    let stats: OutboundStats = monitor
+        .window
+        .lock()
+        .map_err(|e| e.to_string())?
+        .stats(now_secs());
+    Ok(serde_json::json!({
+        "stats": stats,
+        "limits": policy::current().outbound_limits.unwrap_or_default(),
+    }))
+}
@@ -44,12 +44,24 @@ static SENSITIVE_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(
 /// Scan text for sensitive data patterns and replace matches with
 /// `[REDACTED:<label>]` placeholders.
 ///
+/// Built-in patterns run first (minus any the user turned off in
+/// settings), then any rules mandated by enterprise policy.
+///
 /// Returns the cleaned text and a summary of what was redacted.
 pub fn redact_sensitive_data(text: &str) -> RedactionResult {
     let mut cleaned = text.to_string();
     let mut redactions = Vec::new();
 
-    for (pattern, label) in SENSITIVE_PATTERNS.iter() {
+    let settings = crate::settings::current();
+    let builtin = SENSITIVE_PATTERNS
+        .iter()
+        .filter(|(_, label)| settings.redacts(label))
+        .map(|(re, label)| (re, *label));
+    let mandated = crate::policy::redaction_patterns()
+        .iter()
+        .map(|(re, label)| (re, label.as_str()));
+
+    for (pattern, label) in builtin.chain(mandated) {
         let matches: Vec<_> = pattern.find_iter(&cleaned).collect();
         if !matches.is_empty() {
             redactions.push(Redaction {
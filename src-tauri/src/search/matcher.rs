@@ -0,0 +1,111 @@
+//! Where typed text appears among recognized words (pure, no I/O).
+//!
+//! The words of each OCR line are joined with single spaces and searched
+//! case-insensitively, so a query of several words matches them side by
+//! side on one line. A match's box is the union of the words it touches;
+//! a query matching part of a word ("port" in "Export") boxes that word.
+
+use crate::ocr::TextSpan;
+use std::collections::BTreeMap;
+
+/// What the launcher command starts with; the text to find follows.
+pub const PREFIX: &str = "find on screen:";
+
+/// One place the query was found, in the OCR'd image's pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// The recognized words the match covers.
+    pub text: String,
+    /// The whole line it is on.
+    pub line: String,
+}
+
+/// The text to find in a launcher command, if `input` is one.
+/// `Some("")` while nothing is typed after the prefix.
+pub fn query_of(input: &str) -> Option<&str> {
+    let input = input.trim_start();
+    let head = input.get(..PREFIX.len())?;
+    head.eq_ignore_ascii_case(PREFIX).then(|| input[PREFIX.len()..].trim())
+}
+
+/// Every place `query` appears in `spans`, in reading order.
+pub fn find(spans: &[TextSpan], query: &str) -> Vec<Hit> {
+    let needle = query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let mut lines: BTreeMap<u32, Vec<&TextSpan>> = BTreeMap::new();
+    for span in spans {
+        lines.entry(span.line).or_default().push(span);
+    }
+    let mut hits = Vec::new();
+    for words in lines.values_mut() {
+        words.sort_by(|a, b| a.x.total_cmp(&b.x));
+        // Each word's byte range in the lowercased line
+        let (mut haystack, mut ranges) = (String::new(), Vec::new());
+        for word in words.iter() {
+            if !haystack.is_empty() {
+                haystack.push(' ');
+            }
+            let start = haystack.len();
+            haystack.push_str(&word.text.to_lowercase());
+            ranges.push(start..haystack.len());
+        }
+        let line = words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+        for (start, found) in haystack.match_indices(&needle) {
+            let end = start + found.len();
+            let covered: Vec<&TextSpan> =
+                words.iter().zip(&ranges).filter(|(_, r)| r.start < end && start < r.end).map(|(w, _)| *w).collect();
+            let (Some(first), Some(last)) = (covered.first(), covered.last()) else { continue };
+            let top = covered.iter().map(|w| w.y).fold(f64::INFINITY, f64::min);
+            let bottom = covered.iter().map(|w| w.y + w.height).fold(f64::NEG_INFINITY, f64::max);
+            hits.push(Hit {
+                x: first.x,
+                y: top,
+                width: last.x + last.width - first.x,
+                height: bottom - top,
+                text: covered.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" "),
+                line: line.clone(),
+            });
+        }
+    }
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, x: f64, line: u32) -> TextSpan {
+        TextSpan { text: text.to_string(), x, y: f64::from(line) * 20.0, width: 10.0 * text.len() as f64, height: 14.0, line }
+    }
+
+    #[test]
+    fn finds_phrases_on_a_line_and_parts_of_words() {
+        // Out of order within a line, as engines may report them
+        let spans = [word("Settings", 60.0, 0), word("Open", 0.0, 0), word("Export", 0.0, 1), word("report", 80.0, 1)];
+        let hits = find(&spans, "  open   SETTINGS ");
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].x, hits[0].y, hits[0].width, hits[0].height), (0.0, 0.0, 140.0, 14.0));
+        assert_eq!(hits[0].line, "Open Settings");
+
+        let parts: Vec<String> = find(&spans, "port").into_iter().map(|h| h.text).collect();
+        assert_eq!(parts, ["Export", "report"]);
+        // Words on different lines don't make a phrase
+        assert!(find(&spans, "settings export").is_empty());
+        assert!(find(&spans, " ").is_empty());
+    }
+
+    #[test]
+    fn launcher_commands_name_the_text_to_find() {
+        assert_eq!(query_of("find on screen: Save as"), Some("Save as"));
+        assert_eq!(query_of("  Find On Screen:invoice "), Some("invoice"));
+        assert_eq!(query_of("find on screen:"), Some(""));
+        assert_eq!(query_of("find on scr"), None);
+        assert_eq!(query_of("what is on screen: now"), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! Search domain — find text anywhere on screen.
+//!
+//! The launcher command `find on screen: <text>` captures every monitor
+//! (with the capture rules applied, and the launcher hidden so it doesn't
+//! find its own input), reads each one with word boxes
+//! (`ocr::recognize_spans_from_rgba`), and lists where the text appears
+//! (`matcher.rs`). Picking a place rings it on screen for a moment and
+//! can bring the mouse pointer there (`windows::highlight`). All of it
+//! runs locally; what was found is kept only until the next search.
+
+pub mod matcher;
+
+use crate::capture::{self, rules::Rect};
+use crate::error::OmniError;
+use crate::i18n::t;
+use crate::ocr::{self, RecognitionLevel, RgbaView};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// Most places listed.
+const MAX_MATCHES: usize = 20;
+/// Space between the found words and the ring, in logical pixels.
+const RING_PADDING: f64 = 6.0;
+
+/// A listed place: its monitor's frame and captured size, and where it
+/// is in the captured pixels.
+struct Placed {
+    frame: Rect,
+    image: (u32, u32),
+    hit: matcher::Hit,
+}
+
+/// What the last search found, in the order listed.
+static FOUND: Mutex<Vec<Placed>> = Mutex::new(Vec::new());
+
+/// One place the text was found.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenMatch {
+    /// 1-based, in the order the OS lists monitors.
+    pub monitor: usize,
+    /// Screen coordinates, in the units the OS gives monitor frames.
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// The recognized words found.
+    pub text: String,
+    /// The line they are on.
+    pub line: String,
+}
+
+impl Placed {
+    fn to_match(&self, monitor: usize) -> ScreenMatch {
+        let scale = if self.frame.width > 0 { f64::from(self.image.0) / f64::from(self.frame.width) } else { 1.0 };
+        let hit = &self.hit;
+        ScreenMatch {
+            monitor,
+            x: self.frame.x + (hit.x / scale).round() as i32,
+            y: self.frame.y + (hit.y / scale).round() as i32,
+            width: (hit.width / scale).round() as u32,
+            height: (hit.height / scale).round() as u32,
+            text: hit.text.clone(),
+            line: hit.line.clone(),
+        }
+    }
+
+    /// The ring around this place in physical screen pixels, on the
+    /// monitor whose frame (physical or logical) matches the captured one.
+    fn ring(&self, app: &AppHandle) -> Option<(f64, f64, f64, f64)> {
+        let (fx, fy) = (f64::from(self.frame.x), f64::from(self.frame.y));
+        let monitors = app.available_monitors().ok()?;
+        let monitor = monitors.iter().find(|m| {
+            let (physical, logical) = (m.position(), m.position().to_logical::<f64>(m.scale_factor()));
+            let same_logical = (logical.x - fx).abs() < 1.0 && (logical.y - fy).abs() < 1.0;
+            (physical.x, physical.y) == (self.frame.x, self.frame.y) || same_logical
+        })?;
+        let ratio = f64::from(monitor.size().width) / f64::from(self.image.0.max(1));
+        let pad = RING_PADDING * monitor.scale_factor();
+        let (origin, hit) = (monitor.position(), &self.hit);
+        Some((
+            f64::from(origin.x) + hit.x * ratio - pad,
+            f64::from(origin.y) + hit.y * ratio - pad,
+            hit.width * ratio + 2.0 * pad,
+            hit.height * ratio + 2.0 * pad,
+        ))
+    }
+}
+
+/// Capture and read every monitor, and list where `query` appears.
+fn search(app: &AppHandle, query: &str) -> Result<Vec<ScreenMatch>, OmniError> {
+    let prefs = crate::settings::current().capture;
+    let captured = crate::windows::highlight::out_of_capture(app, || capture::capture_all_monitors(&prefs));
+    let monitors = match captured {
+        Ok(all) => all,
+        Err(e) => return crate::tray::checked_capture(app, Err(e)).map(|_| Vec::new()),
+    };
+    let mut found = Vec::new();
+    for (index, (image, frame)) in monitors.into_iter().enumerate() {
+        let image = crate::tray::checked_capture(app, Ok(image))?.into_rgba8();
+        let Some(view) = RgbaView::crop(&image, 0, 0, image.width(), image.height()) else { continue };
+        let spans = ocr::recognize_spans_from_rgba(&view, RecognitionLevel::Accurate);
+        let hits = matcher::find(&spans, query);
+        log::info!("[SEARCH] Monitor {}: {} word(s) read, {} match(es)", index + 1, spans.len(), hits.len());
+        found.extend(hits.into_iter().map(|hit| (index + 1, Placed { frame, image: image.dimensions(), hit })));
+    }
+    found.truncate(MAX_MATCHES);
+    let listed = found.iter().map(|(monitor, placed)| placed.to_match(*monitor)).collect();
+    *FOUND.lock().unwrap_or_else(|e| e.into_inner()) = found.into_iter().map(|(_, placed)| placed).collect();
+    Ok(listed)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: every place `query` appears on screen, up to
+/// `MAX_MATCHES`, monitor by monitor in reading order.
+#[tauri::command]
+pub async fn find_on_screen(app: AppHandle, query: String) -> Result<Vec<ScreenMatch>, OmniError> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !ocr::available() {
+        return Err(OmniError::Other(t("error.noOcrEngine")));
+    }
+    log::info!("[SEARCH] Finding {} character(s) on screen", query.chars().count());
+    tauri::async_runtime::spawn_blocking(move || search(&app, &query))
+        .await
+        .map_err(|e| OmniError::Other(e.to_string()))?
+}
+
+/// Tauri command: ring place `index` of the last search on screen; with
+/// `point`, move the mouse pointer to it too.
+#[tauri::command]
+pub fn show_screen_match(app: AppHandle, index: usize, point: bool) -> Result<(), String> {
+    let ring = {
+        let found = FOUND.lock().unwrap_or_else(|e| e.into_inner());
+        let placed = found.get(index).ok_or_else(|| format!("No match {}", index))?;
+        placed.ring(&app).ok_or("That monitor is no longer connected")?
+    };
+    crate::windows::highlight::show(&app, ring, point)
+}
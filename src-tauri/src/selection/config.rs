@@ -0,0 +1,66 @@
+//! Keyboard selection steps (`capture.keyboard`), with validation (pure,
+//! no I/O).
+
+use serde::{Deserialize, Serialize};
+
+/// Largest step allowed, in CSS pixels.
+const MAX_STEP: u32 = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardPrefs {
+    /// Pixels an arrow key moves or resizes the selection.
+    #[serde(default = "default_step")]
+    pub step: u32,
+    /// Pixels with Shift held.
+    #[serde(default = "default_large_step")]
+    pub large_step: u32,
+}
+
+fn default_step() -> u32 {
+    10
+}
+
+fn default_large_step() -> u32 {
+    50
+}
+
+impl Default for KeyboardPrefs {
+    fn default() -> Self {
+        Self { step: default_step(), large_step: default_large_step() }
+    }
+}
+
+impl KeyboardPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, step) in [("Keyboard step", self.step), ("Large keyboard step", self.large_step)] {
+            if !(1..=MAX_STEP).contains(&step) {
+                return Err(format!("{} must be between 1 and {} pixels", name, MAX_STEP));
+            }
+        }
+        if self.large_step < self.step {
+            return Err("The large keyboard step can't be smaller than the step".to_string());
+        }
+        Ok(())
+    }
+
+    /// Pixels for one key press.
+    pub fn step(&self, large: bool) -> f64 {
+        f64::from(if large { self.large_step } else { self.step })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_are_bounded_and_ordered() {
+        let prefs: KeyboardPrefs = serde_json::from_str(r#"{"step": 5}"#).unwrap();
+        assert_eq!((prefs.step(false), prefs.step(true)), (5.0, 50.0));
+        assert!(prefs.validate().is_ok());
+        assert!(KeyboardPrefs { step: 0, ..prefs.clone() }.validate().is_err());
+        assert!(KeyboardPrefs { large_step: 2000, ..prefs.clone() }.validate().is_err());
+        assert!(KeyboardPrefs { step: 60, large_step: 50 }.validate().is_err());
+    }
+}
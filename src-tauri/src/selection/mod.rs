@@ -0,0 +1,111 @@
+//! Keyboard snip — choosing the region without a mouse.
+//!
+//! In the overlay, an arrow key (or Enter before anything is selected)
+//! starts a keyboard selection: a rectangle the backend keeps and moves.
+//! Arrows nudge it, a modifier moves one edge out or in, steps come from
+//! `capture.keyboard`, and Enter confirms it into `process_snip` like a
+//! drag would. Each change is announced for screen readers: the backend
+//! emits `a11y-announce` and pages speak it through an `aria-live`
+//! region; OCR finishing is announced the same way (`ocr_done`). The
+//! newest announcement is also kept for a window that opens after it
+//! (`take_announcement`), such as the action menu.
+
+pub mod config;
+pub mod rect;
+
+use crate::capture::{dpi, CaptureState};
+use crate::error::{Capture, OmniError};
+use crate::i18n::tf;
+use rect::{Direction, Selection};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// The selection, and the overlay size it stays inside (CSS pixels).
+static ACTIVE: Mutex<Option<(Selection, (f64, f64))>> = Mutex::new(None);
+/// The newest announcement, until a window takes it.
+static PENDING: Mutex<Option<String>> = Mutex::new(None);
+
+/// Speak `text` in whichever window has focus.
+pub fn announce(app: &AppHandle, text: String) {
+    if let Err(e) = app.emit("a11y-announce", &text) {
+        log::warn!("[SELECTION] Failed to announce: {}", e);
+    }
+    *PENDING.lock().unwrap_or_else(|e| e.into_inner()) = Some(text);
+}
+
+/// OCR of a snip finished with `text`.
+pub fn ocr_done(app: &AppHandle, text: &str) {
+    let words = text.split_whitespace().count();
+    let message = if words == 0 { crate::i18n::t("a11y.ocrEmpty") } else { tf("a11y.ocrDone", &[("words", &words.to_string())]) };
+    announce(app, message);
+}
+
+/// The overlay closed; a later one starts over.
+pub fn clear() {
+    ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+fn describe(key: &str, s: &Selection) -> String {
+    let px = |v: f64| (v.round() as i64).to_string();
+    tf(key, &[("width", &px(s.width)), ("height", &px(s.height)), ("x", &px(s.x)), ("y", &px(s.y))])
+}
+
+/// Apply `change` to the active selection and announce the result.
+fn change(app: &AppHandle, change: impl FnOnce(Selection, (f64, f64), f64) -> Selection, large: bool) -> Result<Selection, String> {
+    let step = crate::settings::current().capture.keyboard.step(large);
+    let (before, after) = {
+        let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+        let (selection, bounds) = active.as_mut().ok_or("No keyboard selection")?;
+        let before = *selection;
+        *selection = change(before, *bounds, step);
+        (before, *selection)
+    };
+    let key = if after == before { "a11y.selectionEdge" } else { "a11y.selection" };
+    announce(app, describe(key, &after));
+    Ok(after)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: start a keyboard selection in an overlay of `width` ×
+/// `height` CSS pixels, centered.
+#[tauri::command]
+pub fn start_keyboard_selection(app: AppHandle, width: f64, height: f64) -> Selection {
+    let selection = Selection::centered((width, height));
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some((selection, (width, height)));
+    log::info!("[SELECTION] Keyboard selection in {}x{}", width, height);
+    announce(&app, describe("a11y.selectionStart", &selection));
+    selection
+}
+
+/// Tauri command: move the selection one step (`large`: the Shift step).
+#[tauri::command]
+pub fn nudge_selection(app: AppHandle, direction: Direction, large: bool) -> Result<Selection, String> {
+    change(&app, |s, bounds, step| s.nudge(direction, step, bounds), large)
+}
+
+/// Tauri command: move the `direction` edge one step out, or in with
+/// `shrink`.
+#[tauri::command]
+pub fn expand_selection(app: AppHandle, direction: Direction, large: bool, shrink: bool) -> Result<Selection, String> {
+    change(&app, |s, bounds, step| s.resize(direction, if shrink { -step } else { step }, bounds), large)
+}
+
+/// Tauri command: snip the selection, as releasing a drag would.
+#[tauri::command]
+pub async fn confirm_selection(app: AppHandle) -> Result<(), OmniError> {
+    let (selection, (width, height)) = ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).take().ok_or("No keyboard selection")?;
+    let screenshot = app.state::<CaptureState>().screenshot().ok_or(Capture::NoScreenshot)?;
+    let Selection { x, y, width: w, height: h } = selection;
+    let (px, py, pw, ph) = dpi::to_screenshot((x, y, w, h), (width, height), (screenshot.width(), screenshot.height()))
+        .ok_or("The selection is off the screen")?;
+    announce(&app, crate::i18n::t("a11y.reading"));
+    crate::pipeline::process_snip(app, px, py, pw, ph, x, y + h).await
+}
+
+/// Tauri command: the newest announcement, for a window that just
+/// opened. Returned once.
+#[tauri::command]
+pub fn take_announcement() -> Option<String> {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
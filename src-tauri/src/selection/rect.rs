@@ -0,0 +1,107 @@
+//! Keyboard selection geometry (pure, no I/O).
+//!
+//! The selection is a rectangle in the overlay's CSS pixels that never
+//! leaves the overlay. `nudge` moves it; `resize` moves one edge out or
+//! in. Each stops at the overlay's edge, and a side never gets shorter
+//! than `MIN_SIDE`, the overlay's smallest snip.
+
+use serde::{Deserialize, Serialize};
+
+/// Shortest side, in CSS pixels (smaller drags close the overlay).
+pub const MIN_SIDE: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Unit step along x and y.
+    fn unit(self) -> (f64, f64) {
+        match self {
+            Direction::Up => (0.0, -1.0),
+            Direction::Down => (0.0, 1.0),
+            Direction::Left => (-1.0, 0.0),
+            Direction::Right => (1.0, 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Selection {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Selection {
+    /// The starting selection: a third of the overlay, centered.
+    pub fn centered((width, height): (f64, f64)) -> Selection {
+        let (w, h) = ((width / 3.0).max(MIN_SIDE), (height / 3.0).max(MIN_SIDE));
+        Selection { x: ((width - w) / 2.0).round(), y: ((height - h) / 2.0).round(), width: w.round(), height: h.round() }
+    }
+
+    /// Moved `by` pixels towards `direction`, stopping at the edge.
+    pub fn nudge(self, direction: Direction, by: f64, (width, height): (f64, f64)) -> Selection {
+        let (dx, dy) = direction.unit();
+        Selection {
+            x: (self.x + dx * by).clamp(0.0, (width - self.width).max(0.0)),
+            y: (self.y + dy * by).clamp(0.0, (height - self.height).max(0.0)),
+            ..self
+        }
+    }
+
+    /// The `side` edge moved `by` pixels outwards (negative: inwards),
+    /// stopping at the overlay's edge and at `MIN_SIDE`.
+    pub fn resize(self, side: Direction, by: f64, (width, height): (f64, f64)) -> Selection {
+        let (mut left, mut top) = (self.x, self.y);
+        let (mut right, mut bottom) = (self.x + self.width, self.y + self.height);
+        match side {
+            Direction::Left => left = (left - by).min(right - MIN_SIDE).max(0.0),
+            Direction::Up => top = (top - by).min(bottom - MIN_SIDE).max(0.0),
+            Direction::Right => right = (right + by).min(width).max(left + MIN_SIDE),
+            Direction::Down => bottom = (bottom + by).min(height).max(top + MIN_SIDE),
+        }
+        Selection { x: left, y: top, width: right - left, height: bottom - top }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: (f64, f64) = (1440.0, 900.0);
+
+    #[test]
+    fn nudges_stop_at_the_edge() {
+        let start = Selection::centered(SCREEN);
+        assert_eq!(start, Selection { x: 480.0, y: 300.0, width: 480.0, height: 300.0 });
+        let moved = start.nudge(Direction::Right, 10.0, SCREEN).nudge(Direction::Up, 50.0, SCREEN);
+        assert_eq!((moved.x, moved.y, moved.width), (490.0, 250.0, 480.0));
+        // Against the right edge: no further
+        let edge = moved.nudge(Direction::Right, 1000.0, SCREEN);
+        assert_eq!(edge.x, 960.0);
+        assert_eq!(edge.nudge(Direction::Right, 10.0, SCREEN), edge);
+    }
+
+    #[test]
+    fn resizing_moves_one_edge_within_limits() {
+        let start = Selection::centered(SCREEN);
+        let wider = start.resize(Direction::Left, 30.0, SCREEN);
+        assert_eq!((wider.x, wider.width), (450.0, 510.0));
+        let taller = start.resize(Direction::Down, 50.0, SCREEN);
+        assert_eq!((taller.y, taller.height), (300.0, 350.0));
+        // Grown past the screen: clamped; shrunk past the minimum: clamped
+        assert_eq!(start.resize(Direction::Up, 1000.0, SCREEN).y, 0.0);
+        assert_eq!(start.resize(Direction::Up, 1000.0, SCREEN).height, 600.0);
+        assert_eq!(start.resize(Direction::Right, -1000.0, SCREEN).width, MIN_SIDE);
+        let thin = start.resize(Direction::Left, -1000.0, SCREEN);
+        assert_eq!((thin.x, thin.width), (950.0, MIN_SIDE));
+    }
+}
@@ -0,0 +1,190 @@
+//! Session restore — recover gracefully when the app restarts mid-work.
+//!
+//! The pipeline notes what is in flight here (`state.rs`), and
+//! `~/.config/omni-glass/session.json` is rewritten on every change. At
+//! startup `restore` reopens the action menu for a snip whose classify
+//! never finished, shows a text result that arrived but was never seen
+//! in the result window, and recreates pinned windows;
+//! `resume_downloads` finishes plugin downloads once the registry is up.
+//! `show_result` also shows clicked result notifications and history
+//! answers. Nothing about snips is kept while history is paused
+//! (incognito), and a history purge shreds what was kept (`purge`).
+
+pub mod state;
+
+use crate::llm::execute::ActionResult;
+use crate::llm::{self, ActionMenu};
+use crate::mcp::{self, loader::PendingApprovals, ToolRegistry};
+use crate::persist::JsonStore;
+use crate::safety::redact;
+use state::{InflightSnip, Recovery, SessionState, UnviewedResult};
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Startup attempts to finish a download before giving up on it.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+static STATE: JsonStore<SessionState> = JsonStore::compact("session.json");
+
+/// Result handed to the reopened action menu.
+static RESTORED: Mutex<Option<ActionResult>> = Mutex::new(None);
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Apply `change` and write the state out.
+fn update<T>(change: impl FnOnce(&mut SessionState) -> T) -> T {
+    STATE.update_logged(change)
+}
+
+// ── Pipeline hooks ───────────────────────────────────────────────────
+
+/// A snip's menu is open and classify is about to run.
+pub fn snip_started(text: &str, menu_x: f64, menu_y: f64) {
+    if !crate::history::privacy::recording() {
+        return;
+    }
+    let text = redact::redact_sensitive_data(text).cleaned_text;
+    update(|s| {
+        s.inflight = Some(InflightSnip { started_at: now_secs(), text, menu_x, menu_y });
+        s.unviewed = None;
+    });
+}
+
+/// The snip's menu is final.
+pub fn snip_finished() {
+    update(|s| s.inflight = None);
+}
+
+/// A text result is on its way to the action menu.
+pub fn result_ready(snip_text: &str, result: &ActionResult) {
+    if result.status != "success" || result.result.result_type != "text" || !crate::history::privacy::recording() {
+        return;
+    }
+    let snip_text = redact::redact_sensitive_data(snip_text).cleaned_text;
+    let mut result = result.clone();
+    if let Some(text) = result.result.text.as_mut() {
+        *text = redact::redact_sensitive_data(text).cleaned_text;
+    }
+    update(|s| s.unviewed = Some(UnviewedResult { created_at: now_secs(), snip_text, result }));
+}
+
+/// Forget the interrupted snip and unviewed result, and shred the file
+/// that held their text (history purge). Pins and downloads are written
+/// back.
+pub fn purge() -> Result<(), String> {
+    STATE.erase(|s| {
+        s.inflight = None;
+        s.unviewed = None;
+    })?;
+    RESTORED.lock().unwrap_or_else(|e| e.into_inner()).take();
+    Ok(())
+}
+
+pub fn download_started(url: &str, sha256: &str) {
+    update(|s| s.download_started(url, sha256));
+}
+
+pub fn download_finished(url: &str) {
+    update(|s| s.download_finished(url));
+}
+
+// ── Startup ──────────────────────────────────────────────────────────
+
+/// Reopen an interrupted snip or unviewed result, and pinned windows.
+pub fn restore(app: &tauri::AppHandle) {
+    let (recovery, pinned) = update(|s| (s.take_recovery(now_secs()), s.pinned.clone()));
+    let reopened = match recovery {
+        Recovery::Nothing => Ok(()),
+        Recovery::Snip(snip) => {
+            log::info!("[SESSION] Reopening snip interrupted during classify");
+            let mut menu = ActionMenu::fallback();
+            menu.summary = "Restored after restart — analysis was interrupted".to_string();
+            reopen_menu(app, snip.text, menu, Some((snip.menu_x, snip.menu_y)))
+        }
+        Recovery::Result(unviewed) => {
+            let UnviewedResult { snip_text, result, .. } = *unviewed;
+            log::info!("[SESSION] Reopening unviewed '{}' result", result.action_id);
+            show_result(app, snip_text, result)
+        }
+    };
+    if let Err(e) = reopened {
+        log::warn!("[SESSION] {}", e);
+    }
+
+    for window in &pinned {
+        if let Err(e) = crate::windows::reopen_pinned(app, window) {
+            log::warn!("[SESSION] {}", e);
+        }
+    }
+}
+
+/// Show `result` for `snip_text` (an unviewed result, a clicked result
+/// notification, a history answer): text in the result window, anything
+/// else in the action menu, whose handlers it needs.
+pub fn show_result(app: &tauri::AppHandle, snip_text: String, result: ActionResult) -> Result<(), String> {
+    let menu_state = app.state::<llm::ActionMenuState>();
+    *menu_state.last_result.lock().unwrap_or_else(|e| e.into_inner()) = Some((snip_text.clone(), result.clone()));
+    if result.result.result_type == "text" {
+        return crate::windows::result::open(app, result, None);
+    }
+    *RESTORED.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+    reopen_menu(app, snip_text, ActionMenu::fallback(), None)
+}
+
+/// Reopen the action menu on `text` with `menu` (also for history deep links).
+pub fn reopen_menu(app: &tauri::AppHandle, text: String, menu: ActionMenu, position: Option<(f64, f64)>) -> Result<(), String> {
+    let menu_state = app.state::<llm::ActionMenuState>();
+    *menu_state.ocr_text.lock().unwrap() = Some(text);
+    *menu_state.menu.lock().unwrap() = Some(menu);
+    crate::windows::open_action_menu(app, position)
+}
+
+/// Finish plugin downloads a restart interrupted. Run after plugins load.
+pub async fn resume_downloads(app: &tauri::AppHandle) {
+    let downloads = update(|s| {
+        s.downloads.retain(|d| d.attempts < MAX_DOWNLOAD_ATTEMPTS);
+        s.downloads.iter_mut().for_each(|d| d.attempts += 1);
+        s.downloads.clone()
+    });
+    let registry = app.state::<ToolRegistry>();
+    let pending = app.state::<PendingApprovals>();
+    for download in downloads {
+        log::info!("[SESSION] Resuming download: {}", download.url);
+        if let Err(e) = mcp::install::install(app, &download.url, Some(&download.sha256), &registry, &pending).await {
+            log::warn!("[SESSION] Resumed download failed (attempt {}): {}", download.attempts, e);
+        }
+    }
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the result the reopened action menu should show.
+/// Returned once.
+#[tauri::command]
+pub fn take_restored_result() -> Option<ActionResult> {
+    RESTORED.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+/// Tauri command: the action menu or result window displayed its result.
+#[tauri::command]
+pub fn mark_result_viewed() {
+    update(|s| s.unviewed = None);
+}
+
+/// Tauri command: pin the calling window so it reopens after a restart
+/// (at its current position and size), or unpin it.
+#[tauri::command]
+pub fn set_window_pinned(window: tauri::WebviewWindow, pinned: bool) -> Result<(), String> {
+    if !pinned {
+        update(|s| s.unpin(window.label()));
+        return Ok(());
+    }
+    let pin = crate::windows::pin_of(&window)?;
+    update(|s| s.pin(pin));
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Runtime state kept across restarts (pure, no I/O).
+//!
+//! Only what recovery needs: the snip whose classify was in flight, the
+//! last text result nobody looked at, windows the user pinned, and
+//! plugin downloads that hadn't finished. Entries older than
+//! `MAX_AGE_SECS` are not restored — after a day, reopening a stale
+//! snip would be more confusing than helpful.
+
+use crate::llm::execute::ActionResult;
+use serde::{Deserialize, Serialize};
+
+/// How old a snip or result can be and still be restored.
+pub const MAX_AGE_SECS: u64 = 24 * 60 * 60;
+/// Pinned windows kept.
+const MAX_PINNED: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InflightSnip {
+    /// Unix seconds.
+    pub started_at: u64,
+    /// Redacted OCR text.
+    pub text: String,
+    pub menu_x: f64,
+    pub menu_y: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnviewedResult {
+    /// Unix seconds.
+    pub created_at: u64,
+    /// Redacted OCR text of the snip it answered.
+    pub snip_text: String,
+    pub result: ActionResult,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedWindow {
+    pub label: String,
+    /// App-relative page, with query (e.g. `plugin-console.html?plugin=x`).
+    pub url: String,
+    pub title: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDownload {
+    pub url: String,
+    pub sha256: String,
+    /// Restarts that tried to finish it.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionState {
+    #[serde(default)]
+    pub inflight: Option<InflightSnip>,
+    #[serde(default)]
+    pub unviewed: Option<UnviewedResult>,
+    #[serde(default)]
+    pub pinned: Vec<PinnedWindow>,
+    #[serde(default)]
+    pub downloads: Vec<PendingDownload>,
+}
+
+/// What to bring back at startup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recovery {
+    Nothing,
+    /// Classify never finished: reopen the menu with the OCR text.
+    Snip(InflightSnip),
+    /// An answer arrived but was never shown.
+    Result(Box<UnviewedResult>),
+}
+
+impl SessionState {
+    /// Take the snip or result to reopen, newest first; stale entries
+    /// are dropped. Leaves neither behind.
+    pub fn take_recovery(&mut self, now: u64) -> Recovery {
+        let fresh = |at: u64| now.saturating_sub(at) <= MAX_AGE_SECS;
+        let snip = self.inflight.take().filter(|s| fresh(s.started_at));
+        let result = self.unviewed.take().filter(|r| fresh(r.created_at));
+        match (snip, result) {
+            (Some(s), Some(r)) if r.created_at > s.started_at => Recovery::Result(Box::new(r)),
+            (Some(s), _) => Recovery::Snip(s),
+            (None, Some(r)) => Recovery::Result(Box::new(r)),
+            (None, None) => Recovery::Nothing,
+        }
+    }
+
+    /// Add or update a pinned window (newest kept when over the cap).
+    pub fn pin(&mut self, window: PinnedWindow) {
+        self.pinned.retain(|w| w.label != window.label);
+        self.pinned.push(window);
+        if self.pinned.len() > MAX_PINNED {
+            self.pinned.remove(0);
+        }
+    }
+
+    pub fn unpin(&mut self, label: &str) {
+        self.pinned.retain(|w| w.label != label);
+    }
+
+    pub fn download_started(&mut self, url: &str, sha256: &str) {
+        if !self.downloads.iter().any(|d| d.url == url) {
+            self.downloads.push(PendingDownload { url: url.to_string(), sha256: sha256.to_string(), attempts: 0 });
+        }
+    }
+
+    pub fn download_finished(&mut self, url: &str) {
+        self.downloads.retain(|d| d.url != url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snip(at: u64) -> InflightSnip {
+        InflightSnip { started_at: at, text: "text".into(), menu_x: 0.0, menu_y: 0.0 }
+    }
+
+    fn unviewed(at: u64) -> UnviewedResult {
+        UnviewedResult { created_at: at, snip_text: "text".into(), result: ActionResult::error("explain", "x") }
+    }
+
+    #[test]
+    fn newest_fresh_entry_is_recovered_once() {
+        let mut s = SessionState { inflight: Some(snip(100)), unviewed: Some(unviewed(50)), ..Default::default() };
+        assert_eq!(s.take_recovery(200), Recovery::Snip(snip(100)));
+        assert_eq!(s.take_recovery(200), Recovery::Nothing);
+
+        let mut s = SessionState { inflight: Some(snip(100)), unviewed: Some(unviewed(150)), ..Default::default() };
+        assert_eq!(s.take_recovery(200), Recovery::Result(Box::new(unviewed(150))));
+
+        let mut s = SessionState { inflight: Some(snip(100)), ..Default::default() };
+        assert_eq!(s.take_recovery(100 + MAX_AGE_SECS + 1), Recovery::Nothing);
+        assert!(s.inflight.is_none());
+    }
+
+    #[test]
+    fn pins_and_downloads_are_deduplicated() {
+        let mut s = SessionState::default();
+        let window = |label: &str, x: f64| PinnedWindow {
+            label: label.into(), url: "settings.html".into(), title: "Settings".into(), x, y: 0.0, width: 1.0, height: 1.0,
+        };
+        s.pin(window("settings", 1.0));
+        s.pin(window("settings", 2.0));
+        assert_eq!(s.pinned, vec![window("settings", 2.0)]);
+        for i in 0..MAX_PINNED + 1 {
+            s.pin(window(&format!("w{}", i), 0.0));
+        }
+        assert_eq!(s.pinned.len(), MAX_PINNED);
+        assert!(s.pinned.iter().all(|w| w.label != "settings"));
+        s.unpin("w3");
+        assert_eq!(s.pinned.len(), MAX_PINNED - 1);
+
+        s.download_started("https://x/a.zip", "ab");
+        s.download_started("https://x/a.zip", "ab");
+        assert_eq!(s.downloads.len(), 1);
+        s.download_finished("https://x/a.zip");
+        assert!(s.downloads.is_empty());
+    }
+}
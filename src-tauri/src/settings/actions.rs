@@ -0,0 +1,126 @@
+//! Action key bindings and default actions (pure, no I/O).
+//!
+//! `keys` maps a key pressed in the action menu to an action id, or to
+//! `@top` for whatever the classifier ranked first. `defaults` maps a
+//! content type to an action that runs by itself as soon as the menu
+//! for such a snip is final — but only if the classifier actually
+//! offered it, so a default can never run something out of context.
+//! `result_on_top` keeps the result window above other windows.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Binding target meaning "the highest-priority action in the menu".
+pub const TOP_ACTION: &str = "@top";
+/// Entries allowed in each map.
+const MAX_ENTRIES: usize = 30;
+/// Named keys that can be bound besides single characters.
+const NAMED_KEYS: &[&str] = &["Enter", "Space", "Tab", "Backspace", "Delete"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPrefs {
+    /// Key (`"c"`, `"Enter"`, `"F2"`) → action id or `@top`.
+    #[serde(default = "default_keys")]
+    pub keys: BTreeMap<String, String>,
+    /// Content type (`"table"`) → action id to auto-execute.
+    #[serde(default)]
+    pub defaults: BTreeMap<String, String>,
+    /// Open the result window above other windows (see windows/result.rs).
+    #[serde(default)]
+    pub result_on_top: bool,
+}
+
+fn default_keys() -> BTreeMap<String, String> {
+    [("Enter", TOP_ACTION), ("c", "copy_text")]
+        .into_iter()
+        .map(|(k, a)| (k.to_string(), a.to_string()))
+        .collect()
+}
+
+impl Default for ActionPrefs {
+    fn default() -> Self {
+        Self { keys: default_keys(), defaults: BTreeMap::new(), result_on_top: false }
+    }
+}
+
+fn valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    let single = matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_graphic());
+    let function = key
+        .strip_prefix('F')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| (1..=12).contains(&n));
+    single || function || NAMED_KEYS.contains(&key)
+}
+
+fn valid_action_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 100 && !id.contains(char::is_whitespace)
+}
+
+impl ActionPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.keys.len() > MAX_ENTRIES || self.defaults.len() > MAX_ENTRIES {
+            return Err(format!("At most {} key bindings and {} default actions", MAX_ENTRIES, MAX_ENTRIES));
+        }
+        for (key, action) in &self.keys {
+            if !valid_key(key) {
+                return Err(format!("Key '{}' can't be bound (use one character, F1–F12, or {})", key, NAMED_KEYS.join(", ")));
+            }
+            if key.chars().any(|c| c.is_ascii_uppercase()) && key.len() == 1 {
+                return Err(format!("Bind lowercase '{}' instead of '{}'", key.to_ascii_lowercase(), key));
+            }
+            if !valid_action_id(action) {
+                return Err(format!("Invalid action for key '{}': '{}'", key, action));
+            }
+        }
+        for (content_type, action) in &self.defaults {
+            if content_type.is_empty() || content_type.len() > 40 || content_type != &content_type.to_lowercase() {
+                return Err(format!("Invalid content type: '{}'", content_type));
+            }
+            if !valid_action_id(action) || action == TOP_ACTION {
+                return Err(format!("Invalid default action for '{}': '{}'", content_type, action));
+            }
+        }
+        Ok(())
+    }
+
+    /// The action to auto-run for a snip of `content_type`, if one is set
+    /// and the menu offers it.
+    pub fn default_action<'a>(&self, content_type: &str, offered: impl IntoIterator<Item = &'a str>) -> Option<String> {
+        let wanted = self.defaults.get(content_type)?;
+        offered.into_iter().any(|id| id == wanted).then(|| wanted.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_are_validated() {
+        let mut prefs = ActionPrefs::default();
+        assert!(prefs.validate().is_ok());
+        for (key, ok) in [("f", true), ("F5", true), ("Enter", true), ("F13", false), ("Escape", false), ("C", false), ("ab", false)] {
+            prefs.keys = BTreeMap::from([(key.to_string(), "suggest_fix".to_string())]);
+            assert_eq!(prefs.validate().is_ok(), ok, "{}", key);
+        }
+        prefs.keys.clear();
+        prefs.defaults = BTreeMap::from([("table".to_string(), TOP_ACTION.to_string())]);
+        assert!(prefs.validate().is_err());
+        prefs.defaults = BTreeMap::from([("Table".to_string(), "export_csv".to_string())]);
+        assert!(prefs.validate().is_err());
+    }
+
+    #[test]
+    fn default_action_runs_only_when_offered() {
+        let prefs = ActionPrefs {
+            keys: BTreeMap::new(),
+            defaults: BTreeMap::from([("table".to_string(), "export_csv".to_string())]),
+            result_on_top: false,
+        };
+        assert_eq!(prefs.default_action("table", ["copy_text", "export_csv"]), Some("export_csv".to_string()));
+        assert_eq!(prefs.default_action("table", ["copy_text"]), None);
+        assert_eq!(prefs.default_action("code", ["export_csv"]), None);
+    }
+}
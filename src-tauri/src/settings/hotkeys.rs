@@ -0,0 +1,80 @@
+//! Global hotkey binding.
+//!
+//! The tray stays the primary entry point; the snip and launcher hotkeys
+//! are opt-in and bound only when the user sets them — except on
+//! Windows, where the snip hotkey falls back to the first free of
+//! `shortcut::WINDOWS_ALTERNATIVES` (the plugin registers with
+//! `RegisterHotKey`, which refuses keys Windows or another app holds).
+//! Rebinding drops every shortcut this app holds and registers the
+//! current set; the snip key that won is kept for `get_snip_hotkey`.
+
+use super::schema::Hotkeys;
+use super::shortcut;
+use std::sync::Mutex;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+/// The snip shortcut currently registered.
+static SNIP_BOUND: Mutex<Option<String>> = Mutex::new(None);
+
+/// Reject shortcuts the plugin can't parse before they are saved.
+pub fn check(hotkeys: &Hotkeys) -> Result<(), String> {
+    for key in [&hotkeys.snip, &hotkeys.launcher].into_iter().flatten() {
+        key.parse::<Shortcut>().map_err(|e| format!("Invalid hotkey '{}': {}", key, e))?;
+    }
+    Ok(())
+}
+
+fn snip(app: &tauri::AppHandle) {
+    let click_epoch_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as f64)
+        .unwrap_or(0.0);
+    crate::tray::start_snip_mode(app, click_epoch_ms);
+}
+
+fn launcher(app: &tauri::AppHandle) {
+    if let Err(e) = crate::windows::show(app, crate::windows::spec::Window::Launcher) {
+        log::warn!("[SETTINGS] Launcher hotkey: {}", e);
+    }
+}
+
+/// Register `key` to `run`; false if it can't be bound.
+fn bind(app: &tauri::AppHandle, name: &str, key: &str, run: fn(&tauri::AppHandle)) -> bool {
+    let bound = app.global_shortcut().on_shortcut(key, move |app, _shortcut, event| {
+        if event.state() == ShortcutState::Pressed {
+            crate::crash::guard(|| run(app));
+        }
+    });
+    match bound {
+        Ok(()) => log::info!("[SETTINGS] {} hotkey bound: {}", name, key),
+        Err(e) => log::warn!("[SETTINGS] Could not bind {} hotkey '{}': {}", name.to_lowercase(), key, e),
+    }
+    bound.is_ok()
+}
+
+/// Register `hotkeys`, replacing whatever was bound before.
+pub fn apply(app: &tauri::AppHandle, hotkeys: &Hotkeys) {
+    if let Err(e) = app.global_shortcut().unregister_all() {
+        log::warn!("[SETTINGS] Failed to clear hotkeys: {}", e);
+    }
+    if let Some(key) = &hotkeys.launcher {
+        bind(app, "Launcher", key, launcher);
+    }
+    let windows = cfg!(target_os = "windows");
+    if let Some(key) = hotkeys.snip.as_deref().filter(|k| windows && shortcut::reserved_on_windows(k)) {
+        log::warn!("[SETTINGS] Snip hotkey '{}' is reserved by Windows", key);
+    }
+    let candidates = shortcut::snip_candidates(hotkeys.snip.as_deref(), hotkeys.windows_fallback, windows, hotkeys.launcher.as_deref());
+    let bound = candidates.into_iter().find(|key| bind(app, "Snip", key, snip));
+    if bound.is_some() && bound != hotkeys.snip {
+        log::info!("[SETTINGS] Snip hotkey falls back to {:?}", bound);
+    }
+    *SNIP_BOUND.lock().unwrap_or_else(|e| e.into_inner()) = bound;
+}
+
+/// Tauri command: the snip shortcut in effect, which on Windows may be
+/// a fallback rather than `hotkeys.snip`.
+#[tauri::command]
+pub fn get_snip_hotkey() -> Option<String> {
+    SNIP_BOUND.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
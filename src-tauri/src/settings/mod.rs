@@ -0,0 +1,206 @@
+//! User settings — one typed, versioned config for every subsystem.
+//!
+//! Provider choice, model overrides, OCR mode, hotkeys, redaction
+//! toggles, startup behavior, and profiles live in
+//! `~/.config/omni-glass/settings.json` (schema and migrations in
+//! `schema.rs`). Subsystems read `current()` — the stored settings with
+//! the active profile layered on top (`profiles.rs`). Changes go through
+//! `update`, which validates, writes the file, emits `settings-changed`
+//! (plus `profile-changed` on a switch), and applies hotkeys, the login
+//! item, background mode, and network settings. API keys stay in the OS
+//! keychain and never enter this file.
+//!
+//! Until the file is first written, the old `LLM_PROVIDER` / `OCR_MODE`
+//! environment variables seed the defaults. A file that can't be parsed
+//! is moved aside to `settings.invalid.json`; one written by a newer
+//! build is left alone and settings are read-only until it is replaced.
+
+pub mod actions;
+pub mod hotkeys;
+pub mod prefs;
+pub mod profiles;
+pub mod schema;
+pub mod shortcut;
+pub mod startup;
+
+pub use schema::Settings;
+use crate::error::{OmniError, Safety};
+use crate::persist::{self, JsonStore};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Emitter;
+
+static STORE: JsonStore<Settings> = JsonStore::with_loader("settings.json", load);
+/// Set when the file on disk is from a newer build and must not be overwritten.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// No file yet: adopt what the environment used to configure.
+fn seed_from_env() -> Settings {
+    let mut settings = Settings::default();
+    if let Ok(p) = std::env::var("LLM_PROVIDER") {
+        let p = p.to_lowercase();
+        if schema::PROVIDERS.contains(&p.as_str()) {
+            settings.provider = Some(p);
+        }
+    }
+    if let Ok(mode) = std::env::var("OCR_MODE") {
+        let mode = mode.to_lowercase();
+        if schema::OCR_MODES.contains(&mode.as_str()) {
+            settings.ocr_mode = mode;
+        }
+    }
+    settings
+}
+
+fn load(path: &Path) -> Settings {
+    let Ok(raw) = std::fs::read_to_string(path) else { return seed_from_env() };
+
+    let parsed = serde_json::from_str::<Value>(&raw).map_err(|e| format!("Invalid settings file: {}", e));
+    match parsed.and_then(schema::migrate) {
+        Ok((settings, migrated)) => {
+            if migrated {
+                log::info!("[SETTINGS] Migrated settings to version {}", schema::CURRENT_VERSION);
+                if let Err(e) = persist::write_json(path, &settings, true) {
+                    log::warn!("[SETTINGS] {}", e);
+                }
+            }
+            settings
+        }
+        Err(e) if e.contains("newer than this build") => {
+            log::warn!("[SETTINGS] {} — using defaults, settings are read-only", e);
+            READ_ONLY.store(true, Ordering::Relaxed);
+            Settings::default()
+        }
+        Err(e) => {
+            log::warn!("[SETTINGS] {} — moved aside, using defaults", e);
+            let _ = std::fs::rename(path, path.with_file_name("settings.invalid.json"));
+            Settings::default()
+        }
+    }
+}
+
+/// Settings as stored, without the active profile — what the settings
+/// window edits.
+pub fn stored() -> Settings {
+    STORE.get()
+}
+
+/// Current settings, with the active profile applied.
+pub fn current() -> Settings {
+    profiles::layer(&stored())
+}
+
+/// The active profile, if any.
+pub fn active_profile() -> Option<profiles::Profile> {
+    let settings = stored();
+    settings.profiles.get(settings.active_profile.as_deref()?).cloned()
+}
+
+/// `Err(Safety::LocalOnly)` when nothing may go to a cloud LLM.
+pub fn cloud_allowed() -> Result<(), OmniError> {
+    if !current().local_only {
+        return Ok(());
+    }
+    Err(Safety::LocalOnly { profile: stored().active_profile }.into())
+}
+
+/// Apply a partial JSON update, persist it, and tell the app.
+pub fn update(app: &tauri::AppHandle, patch: Value) -> Result<Settings, String> {
+    if READ_ONLY.load(Ordering::Relaxed) {
+        return Err("settings.json was written by a newer version of Omni-Glass".to_string());
+    }
+    let (previous, next) = STORE.try_update(|stored| {
+        let next = schema::apply_patch(stored, patch)?;
+        hotkeys::check(&next.hotkeys)?;
+        let new_ca = next.network.ca_bundle.as_deref().filter(|_| next.network.ca_bundle != stored.network.ca_bundle);
+        if let Some(path) = new_ca {
+            crate::net::load_ca_bundle(path)?;
+        }
+        if next.launch_at_login != stored.launch_at_login {
+            startup::set_launch_at_login(app, next.launch_at_login)?;
+        }
+        Ok((std::mem::replace(stored, next.clone()), next))
+    })?;
+
+    if previous.hotkeys != next.hotkeys {
+        hotkeys::apply(app, &next.hotkeys);
+    }
+    if previous.network != next.network {
+        crate::net::reset();
+    }
+    if previous.locale != next.locale {
+        crate::i18n::reload(app);
+    }
+    if previous.offline_mode != next.offline_mode {
+        crate::net::connectivity::announce(app);
+    }
+    if previous.quality_mode != next.quality_mode {
+        crate::power::announce(app);
+    }
+    if previous.triggers != next.triggers {
+        crate::triggers::apply(app);
+    }
+    if previous.background_mode != next.background_mode {
+        startup::apply_background_mode(app, next.background_mode);
+    }
+    if previous.active_profile != next.active_profile || previous.profiles != next.profiles {
+        log::info!("[SETTINGS] Profile now {:?}", next.active_profile);
+        let _ = app.emit("profile-changed", &next.active_profile);
+    }
+    let _ = app.emit("settings-changed", &next);
+    log::info!("[SETTINGS] Settings updated");
+    Ok(next)
+}
+
+/// Make the profile matching `name` (ignoring case) active; `None`
+/// turns profiles off.
+pub fn set_active_profile(app: &tauri::AppHandle, name: Option<&str>) -> Result<Option<String>, String> {
+    let name = match name {
+        Some(raw) => Some(profiles::find(&stored().profiles, raw).ok_or_else(|| format!("No profile '{}'", raw.trim()))?),
+        None => None,
+    };
+    update(app, serde_json::json!({ "activeProfile": name }))?;
+    Ok(name)
+}
+
+/// Apply stored settings that live outside the app: hotkeys, Dock
+/// icon, and the login item. Called once in `setup`.
+pub fn attach(app: &tauri::AppHandle) {
+    let settings = current();
+    hotkeys::apply(app, &settings.hotkeys);
+    startup::apply_background_mode(app, settings.background_mode);
+    match startup::launch_at_login(app) {
+        Some(enabled) if enabled != settings.launch_at_login => {
+            log::info!("[SETTINGS] Login item changed outside the app; now {}", enabled);
+            if let Err(e) = update(app, serde_json::json!({ "launchAtLogin": enabled })) {
+                log::warn!("[SETTINGS] {}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: all user settings as stored (the active profile's
+/// overrides are not applied).
+#[tauri::command]
+pub fn get_settings() -> Settings {
+    stored()
+}
+
+/// Tauri command: change some settings. `patch` is a partial object,
+/// e.g. `{ "models": { "gemini": "gemini-2.5-pro" } }`; `null` resets a
+/// field or removes a map entry. Returns the full settings after the change.
+#[tauri::command]
+pub fn update_settings(app: tauri::AppHandle, patch: Value) -> Result<Settings, String> {
+    update(&app, patch)
+}
+
+/// Tauri command: switch profile by name, or turn profiles off with
+/// `null`. Returns the active profile's name.
+#[tauri::command]
+pub fn switch_profile(app: tauri::AppHandle, name: Option<String>) -> Result<Option<String>, String> {
+    set_active_profile(&app, name.as_deref())
+}
@@ -0,0 +1,95 @@
+//! Smaller settings groups: model overrides, hotkeys, redaction, and
+//! updates, with their validation (pure, no I/O). Re-exported from
+//! `schema.rs`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPrefs {
+    /// Model id override; `None` uses the built-in default.
+    #[serde(default)]
+    pub anthropic: Option<String>,
+    #[serde(default)]
+    pub gemini: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Hotkeys {
+    /// Global shortcut that starts a snip, e.g. `CmdOrCtrl+Shift+S`.
+    #[serde(default)]
+    pub snip: Option<String>,
+    /// Global shortcut that opens the launcher palette, e.g. `Alt+Space`.
+    #[serde(default)]
+    pub launcher: Option<String>,
+    /// On Windows, bind an alternative snip key when `snip` is unset,
+    /// reserved by Windows, or taken (`shortcut.rs`).
+    #[serde(default = "default_true")]
+    pub windows_fallback: bool,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self { snip: None, launcher: None, windows_fallback: true }
+    }
+}
+
+impl Hotkeys {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, key) in [("Snip", &self.snip), ("Launcher", &self.launcher)] {
+            if let Some(key) = key.as_ref().filter(|k| k.trim().is_empty() || k.len() > 60) {
+                return Err(format!("{} hotkey must be a non-empty shortcut (got '{}')", name, key));
+            }
+        }
+        if self.snip.is_some() && self.snip == self.launcher {
+            return Err("Snip and launcher hotkeys must differ".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RedactionPrefs {
+    /// Built-in rules turned off, by label.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePrefs {
+    /// `"stable"` or `"beta"` (see updater/channel.rs).
+    #[serde(default = "default_channel")]
+    pub channel: String,
+    /// Check in the background and download what is found.
+    #[serde(default = "default_true")]
+    pub auto_check: bool,
+    /// A version the user chose not to install.
+    #[serde(default)]
+    pub skipped_version: Option<String>,
+}
+
+impl Default for UpdatePrefs {
+    fn default() -> Self {
+        Self { channel: default_channel(), auto_check: true, skipped_version: None }
+    }
+}
+
+impl UpdatePrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if !crate::updater::channel::CHANNELS.contains(&self.channel.as_str()) {
+            return Err(format!("Unknown update channel: {}", self.channel));
+        }
+        Ok(())
+    }
+}
+
+fn default_channel() -> String {
+    "stable".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
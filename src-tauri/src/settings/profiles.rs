@@ -0,0 +1,142 @@
+//! Profiles — named layers over the user's settings (pure, no I/O).
+//!
+//! A profile bundles the choices that differ between, say, work and
+//! personal use: provider, redaction strictness, local-only mode,
+//! hidden plugins, and history retention. At most one is active. The
+//! stored settings stay the base layer that the settings window edits;
+//! `layer` puts the active profile on top to give what every subsystem
+//! reads through `settings::current()`. Fields a profile leaves unset
+//! fall through to the base.
+
+use super::schema::{Settings, PROVIDERS};
+use crate::history::privacy::Retention;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+const MAX_PROFILES: usize = 10;
+const MAX_NAME_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    /// Provider while active; `None` keeps the base choice.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Every built-in redaction rule on, whatever the base turns off.
+    #[serde(default)]
+    pub strict_redaction: bool,
+    /// Nothing is sent to a cloud LLM while active.
+    #[serde(default)]
+    pub local_only: bool,
+    /// Plugins hidden while active, on top of the user's own toggles.
+    #[serde(default)]
+    pub disabled_plugins: BTreeSet<String>,
+    /// History retention while active; `None` keeps the user's rule.
+    #[serde(default)]
+    pub history: Option<Retention>,
+}
+
+/// Profiles a fresh install starts with; both can be edited or removed.
+pub fn default_profiles() -> BTreeMap<String, Profile> {
+    BTreeMap::from([
+        ("Personal".to_string(), Profile::default()),
+        (
+            "Work".to_string(),
+            Profile {
+                strict_redaction: true,
+                local_only: true,
+                ..Profile::default()
+            },
+        ),
+    ])
+}
+
+impl Profile {
+    fn validate(&self, name: &str) -> Result<(), String> {
+        if name.trim() != name || name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(format!("Profile names are 1-{} characters without outer spaces", MAX_NAME_CHARS));
+        }
+        if let Some(p) = self.provider.as_deref().filter(|p| !PROVIDERS.contains(p)) {
+            return Err(format!("Unknown provider in profile '{}': {}", name, p));
+        }
+        if let Some(retention) = self.history {
+            retention.validate().map_err(|e| format!("Profile '{}': {}", name, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the profile list and the active name (part of `Settings::validate`).
+pub fn validate(profiles: &BTreeMap<String, Profile>, active: Option<&str>) -> Result<(), String> {
+    if profiles.len() > MAX_PROFILES {
+        return Err(format!("At most {} profiles", MAX_PROFILES));
+    }
+    for (name, profile) in profiles {
+        profile.validate(name)?;
+    }
+    match active {
+        Some(name) if !profiles.contains_key(name) => Err(format!("No profile '{}'", name)),
+        _ => Ok(()),
+    }
+}
+
+/// Existing profile matching `raw`, ignoring case.
+pub fn find(profiles: &BTreeMap<String, Profile>, raw: &str) -> Option<String> {
+    profiles.keys().find(|k| k.eq_ignore_ascii_case(raw.trim())).cloned()
+}
+
+/// The settings subsystems see: `base` with its active profile on top.
+pub fn layer(base: &Settings) -> Settings {
+    let mut settings = base.clone();
+    let Some(profile) = base.active_profile.as_ref().and_then(|name| base.profiles.get(name)) else {
+        return settings;
+    };
+    if let Some(provider) = &profile.provider {
+        settings.provider = Some(provider.clone());
+    }
+    if profile.strict_redaction {
+        settings.redaction.disabled.clear();
+    }
+    settings.local_only |= profile.local_only;
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_profile_layers_over_base() {
+        let mut base = Settings { provider: Some("anthropic".to_string()), ..Settings::default() };
+        base.redaction.disabled = vec!["ssn".to_string()];
+        assert_eq!(layer(&base), base);
+
+        base.active_profile = Some("Work".to_string());
+        base.profiles.get_mut("Work").unwrap().provider = Some("gemini".to_string());
+        let work = layer(&base);
+        assert_eq!(work.provider.as_deref(), Some("gemini"));
+        assert!(work.redacts("ssn") && work.local_only);
+
+        base.active_profile = Some("Personal".to_string());
+        let personal = layer(&base);
+        assert_eq!(personal.provider.as_deref(), Some("anthropic"));
+        assert!(!personal.redacts("ssn") && !personal.local_only);
+    }
+
+    #[test]
+    fn profiles_are_validated() {
+        let mut profiles = default_profiles();
+        assert!(validate(&profiles, Some("Work")).is_ok());
+        assert!(validate(&profiles, Some("Gaming")).is_err());
+        assert_eq!(find(&profiles, " work ").as_deref(), Some("Work"));
+
+        profiles.insert(" Side ".to_string(), Profile::default());
+        assert!(validate(&profiles, None).is_err());
+        profiles.remove(" Side ");
+        profiles.get_mut("Personal").unwrap().history = Some(Retention::Days { days: 0 });
+        assert!(validate(&profiles, None).is_err());
+        profiles.get_mut("Personal").unwrap().history = None;
+        profiles.get_mut("Personal").unwrap().provider = Some("openai".to_string());
+        assert!(validate(&profiles, None).is_err());
+    }
+}
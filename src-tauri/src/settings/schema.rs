@@ -0,0 +1,297 @@
+//! Settings schema, validation, and migration (pure, no I/O).
+//!
+//! The file carries a `version`. Older files are walked forward one
+//! migration at a time (`MIGRATIONS[n]` lifts version `n` to `n + 1`)
+//! on the raw JSON, before it is parsed into `Settings`, so a migration
+//! can rename or reshape keys freely. Files written by a newer build are
+//! refused rather than guessed at.
+//!
+//! Updates arrive as partial JSON patches: objects merge key by key,
+//! anything else replaces the stored value, and `null` removes the key —
+//! resetting a field to its default or dropping a map entry.
+
+use super::actions::ActionPrefs;
+pub use super::prefs::{Hotkeys, ModelPrefs, RedactionPrefs, UpdatePrefs};
+use super::profiles::{self, Profile};
+use crate::capture::rules::CapturePrefs;
+use crate::convert::config::ConvertPrefs;
+use crate::editor::detect::EditorPrefs;
+use crate::export::markdown::ExportPrefs;
+use crate::extract::config::ExtractPrefs;
+use crate::net::config::NetworkPrefs;
+use crate::triggers::config::TriggerPrefs;
+use crate::webhooks::config::WebhookPrefs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Schema version written by this build.
+pub const CURRENT_VERSION: u32 = 1;
+
+pub const PROVIDERS: &[&str] = &["anthropic", "gemini"];
+pub const OCR_MODES: &[&str] = &["fast", "accurate"];
+/// Built-in redaction rules a user may turn off (see safety/redact.rs).
+/// Rules mandated by enterprise policy always apply.
+pub const REDACTION_RULES: &[&str] = &["credit_card", "ssn", "api_key", "aws_key", "private_key"];
+
+/// One step of the upgrade path; `MIGRATIONS[n]` turns version `n` into `n + 1`.
+type Migration = fn(&mut serde_json::Map<String, Value>);
+
+const MIGRATIONS: &[Migration] = &[v0_to_v1];
+
+/// Version 0 is the unversioned layout: flat `llmProvider` / `ocrMode`
+/// keys named after the old environment variables.
+fn v0_to_v1(raw: &mut serde_json::Map<String, Value>) {
+    if let Some(provider) = raw.remove("llmProvider") {
+        raw.entry("provider").or_insert(provider);
+    }
+    if let Some(Value::String(mode)) = raw.get_mut("ocrMode") {
+        *mode = mode.to_lowercase();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    pub version: u32,
+    /// Chosen LLM provider; `None` picks the first one with a key.
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub models: ModelPrefs,
+    #[serde(default = "default_ocr_mode")]
+    pub ocr_mode: String,
+    /// Full quality, power saver, or `auto` to switch on battery / heat.
+    #[serde(default = "default_quality_mode")]
+    pub quality_mode: String,
+    #[serde(default)]
+    pub hotkeys: Hotkeys,
+    #[serde(default)]
+    pub redaction: RedactionPrefs,
+    /// Start at login (mirrors the OS login item).
+    #[serde(default)]
+    pub launch_at_login: bool,
+    /// macOS: menu bar only, no Dock icon.
+    #[serde(default = "default_true")]
+    pub background_mode: bool,
+    /// Launching the app again brings the running one forward.
+    #[serde(default = "default_true")]
+    pub focus_existing: bool,
+    #[serde(default)]
+    pub updates: UpdatePrefs,
+    /// Action-menu key bindings and per-content-type default actions.
+    #[serde(default)]
+    pub actions: ActionPrefs,
+    /// Never send snips to a cloud LLM (a profile can also turn this on).
+    #[serde(default)]
+    pub local_only: bool,
+    /// Named layers over these settings (see profiles.rs).
+    #[serde(default = "profiles::default_profiles")]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Proxy and custom CA for all outbound HTTP (see net/config.rs).
+    #[serde(default)]
+    pub network: NetworkPrefs,
+    /// Treat the network as down: local menus only, no cloud calls.
+    #[serde(default)]
+    pub offline_mode: bool,
+    /// UI language (`de`, `pt-BR`); `None` follows the system.
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Monitor to snip and apps never to capture (see capture/rules.rs).
+    #[serde(default)]
+    pub capture: CapturePrefs,
+    /// Markdown export template (see export/markdown.rs).
+    #[serde(default)]
+    pub export: ExportPrefs,
+    /// Editor for "Open in Editor" (see editor/detect.rs).
+    #[serde(default)]
+    pub editor: EditorPrefs,
+    /// Outbound webhooks on snip events (see webhooks/config.rs).
+    #[serde(default)]
+    pub webhooks: WebhookPrefs,
+    /// Stream Deck / MIDI inputs mapped to snip actions (see triggers/config.rs).
+    #[serde(default)]
+    pub triggers: TriggerPrefs,
+    /// Currency, unit, and time zone conversion targets (see convert/config.rs).
+    #[serde(default)]
+    pub convert: ConvertPrefs,
+    /// Saved regex recipes for the extraction workbench (see extract/config.rs).
+    #[serde(default)]
+    pub extract: ExtractPrefs,
+}
+
+fn default_ocr_mode() -> String {
+    "fast".to_string()
+}
+
+fn default_quality_mode() -> String {
+    "auto".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            provider: None,
+            models: ModelPrefs::default(),
+            ocr_mode: default_ocr_mode(),
+            quality_mode: default_quality_mode(),
+            hotkeys: Hotkeys::default(),
+            redaction: RedactionPrefs::default(),
+            launch_at_login: false,
+            background_mode: true,
+            focus_existing: true,
+            updates: UpdatePrefs::default(),
+            actions: ActionPrefs::default(),
+            local_only: false,
+            profiles: profiles::default_profiles(),
+            active_profile: None,
+            network: NetworkPrefs::default(),
+            offline_mode: false,
+            locale: None,
+            capture: CapturePrefs::default(),
+            export: ExportPrefs::default(),
+            editor: EditorPrefs::default(),
+            webhooks: WebhookPrefs::default(),
+            triggers: TriggerPrefs::default(),
+            convert: ConvertPrefs::default(),
+            extract: ExtractPrefs::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(p) = &self.provider {
+            if !PROVIDERS.contains(&p.as_str()) {
+                return Err(format!("Unknown provider: {}", p));
+            }
+        }
+        for model in [&self.models.anthropic, &self.models.gemini].into_iter().flatten() {
+            if model.trim().is_empty() || model.len() > 100 || model.contains(char::is_whitespace) {
+                return Err(format!("Invalid model id: '{}'", model));
+            }
+        }
+        if !OCR_MODES.contains(&self.ocr_mode.as_str()) {
+            return Err(format!("Invalid OCR mode: {}. Use 'fast' or 'accurate'.", self.ocr_mode));
+        }
+        if !crate::power::state::QUALITY_MODES.contains(&self.quality_mode.as_str()) {
+            return Err(format!("Invalid quality mode: {}. Use 'auto', 'full' or 'saver'.", self.quality_mode));
+        }
+        self.hotkeys.validate()?;
+        if let Some(rule) = self.redaction.disabled.iter().find(|r| !REDACTION_RULES.contains(&r.as_str())) {
+            return Err(format!("Unknown redaction rule: {}", rule));
+        }
+        self.actions.validate()?;
+        profiles::validate(&self.profiles, self.active_profile.as_deref())?;
+        self.network.validate()?;
+        self.capture.validate()?;
+        self.export.validate()?;
+        self.editor.validate()?;
+        self.webhooks.validate()?;
+        self.triggers.validate()?;
+        self.convert.validate()?;
+        self.extract.validate()?;
+        if let Some(locale) = self.locale.as_deref().filter(|l| crate::i18n::catalog::normalize(l).as_deref() != Some(l)) {
+            return Err(format!("Invalid locale: {}", locale));
+        }
+        self.updates.validate()
+    }
+
+    /// Whether the built-in redaction rule `label` is on.
+    pub fn redacts(&self, label: &str) -> bool {
+        !self.redaction.disabled.iter().any(|r| r == label)
+    }
+}
+
+/// Parse a stored file, migrating it to `CURRENT_VERSION`. Returns the
+/// settings and whether a migration ran (so the caller can rewrite it).
+pub fn migrate(mut raw: Value) -> Result<(Settings, bool), String> {
+    let map = raw.as_object_mut().ok_or("Settings file is not a JSON object")?;
+    let from = map.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if from > CURRENT_VERSION {
+        return Err(format!("Settings file is version {}, newer than this build ({})", from, CURRENT_VERSION));
+    }
+    for step in &MIGRATIONS[from as usize..] {
+        step(map);
+    }
+    map.insert("version".into(), Value::from(CURRENT_VERSION));
+    let settings: Settings = serde_json::from_value(raw).map_err(|e| format!("Invalid settings file: {}", e))?;
+    settings.validate()?;
+    Ok((settings, from < CURRENT_VERSION))
+}
+
+fn merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(t), Value::Object(p)) => {
+            for (key, value) in p {
+                if value.is_null() {
+                    t.remove(&key);
+                } else {
+                    merge(t.entry(key).or_insert(Value::Null), value);
+                }
+            }
+        }
+        (t, p) => *t = p,
+    }
+}
+
+/// Apply a partial update. The version can't be patched.
+pub fn apply_patch(current: &Settings, mut patch: Value) -> Result<Settings, String> {
+    if let Some(map) = patch.as_object_mut() {
+        map.remove("version");
+    } else {
+        return Err("Settings update must be a JSON object".to_string());
+    }
+    let mut value = serde_json::to_value(current).map_err(|e| e.to_string())?;
+    merge(&mut value, patch);
+    let next: Settings = serde_json::from_value(value).map_err(|e| format!("Invalid settings: {}", e))?;
+    next.validate()?;
+    Ok(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unversioned_files_are_migrated() {
+        let (settings, migrated) = migrate(json!({ "llmProvider": "gemini", "ocrMode": "ACCURATE" })).unwrap();
+        assert!(migrated);
+        assert_eq!(settings.version, CURRENT_VERSION);
+        assert_eq!(settings.provider.as_deref(), Some("gemini"));
+        assert_eq!(settings.ocr_mode, "accurate");
+
+        let current = serde_json::to_value(Settings::default()).unwrap();
+        assert_eq!(migrate(current).unwrap(), (Settings::default(), false));
+        assert!(migrate(json!({ "version": CURRENT_VERSION + 1 })).is_err());
+        assert!(migrate(json!({ "version": 1, "ocrMode": "slow" })).is_err());
+    }
+
+    #[test]
+    fn patches_merge_and_validate() {
+        let base = Settings::default();
+        let next = apply_patch(&base, json!({ "provider": "anthropic", "models": { "gemini": "gemini-2.5-pro" }, "version": 9 })).unwrap();
+        assert_eq!(next.provider.as_deref(), Some("anthropic"));
+        assert_eq!(next.models.gemini.as_deref(), Some("gemini-2.5-pro"));
+        assert_eq!(next.models.anthropic, None);
+        assert_eq!(next.version, CURRENT_VERSION);
+
+        let reset = apply_patch(&next, json!({ "provider": null, "actions": { "keys": { "c": null } } })).unwrap();
+        assert_eq!(reset.provider, None);
+        assert!(!reset.actions.keys.contains_key("c"));
+        assert!(apply_patch(&base, json!({ "provider": "openai" })).is_err());
+        assert!(apply_patch(&base, json!({ "redaction": { "disabled": ["ssn", "nope"] } })).is_err());
+        assert!(!apply_patch(&base, json!({ "redaction": { "disabled": ["ssn"] } })).unwrap().redacts("ssn"));
+        assert!(apply_patch(&base, json!({ "updates": { "channel": "nightly" } })).is_err());
+        assert!(apply_patch(&base, json!({ "capture": { "appRules": [{ "app": "1Password", "action": "hide" }] } })).is_err());
+        assert!(apply_patch(&base, json!([1])).is_err());
+    }
+}
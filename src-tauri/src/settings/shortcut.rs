@@ -0,0 +1,89 @@
+//! Which shortcuts to try for the snip hotkey (pure, no I/O).
+//!
+//! Windows keeps Win+Shift+S for its own Snipping Tool, so a snip hotkey
+//! set to it can never be registered, and with none set a Windows user
+//! had no keyboard way in. There the configured key is tried first
+//! (unless the OS reserves it), then `WINDOWS_ALTERNATIVES` in order
+//! unless `hotkeys.windowsFallback` is off; the first that registers wins.
+
+/// Tried in order on Windows when the configured snip key is unset,
+/// reserved, or held by another app.
+pub const WINDOWS_ALTERNATIVES: [&str; 3] = ["Ctrl+Alt+S", "Ctrl+Shift+Alt+S", "Ctrl+Alt+PrintScreen"];
+
+/// Taken by Windows itself; `RegisterHotKey` always refuses them.
+const WINDOWS_RESERVED: [&str; 3] = ["Super+Shift+S", "Super+PrintScreen", "Super+Shift+R"];
+
+/// `key` with modifier aliases unified and sorted, lowercased, so
+/// `Win+Shift+S` and `shift+super+s` compare equal. `CmdOrCtrl` is Ctrl,
+/// as it is on Windows.
+pub fn normalize(key: &str) -> String {
+    let mut parts: Vec<String> = key
+        .split('+')
+        .map(|part| match part.trim().to_ascii_lowercase().as_str() {
+            "win" | "windows" | "meta" | "cmd" | "command" => "super".to_string(),
+            "control" | "cmdorctrl" | "commandorcontrol" => "ctrl".to_string(),
+            "option" => "alt".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    let key = parts.pop().unwrap_or_default();
+    parts.sort();
+    parts.dedup();
+    parts.push(key);
+    parts.join("+")
+}
+
+/// Whether Windows keeps `key` for itself.
+pub fn reserved_on_windows(key: &str) -> bool {
+    let key = normalize(key);
+    WINDOWS_RESERVED.iter().any(|r| normalize(r) == key)
+}
+
+/// Snip shortcuts to try, in order. Elsewhere only the configured one;
+/// on `windows` the configured one unless reserved, then the
+/// alternatives if `fallback`. `taken` (the launcher key) is skipped.
+pub fn snip_candidates(configured: Option<&str>, fallback: bool, windows: bool, taken: Option<&str>) -> Vec<String> {
+    let mut candidates: Vec<String> = configured.filter(|k| !(windows && reserved_on_windows(k))).map(str::to_string).into_iter().collect();
+    if windows && fallback {
+        candidates.extend(WINDOWS_ALTERNATIVES.iter().map(|k| k.to_string()));
+    }
+    let taken = taken.map(normalize);
+    let mut seen = Vec::new();
+    candidates.retain(|k| {
+        let key = normalize(k);
+        let keep = Some(&key) != taken.as_ref() && !seen.contains(&key);
+        seen.push(key);
+        keep
+    });
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_keys_are_recognized_under_any_spelling() {
+        assert_eq!(normalize("Win+Shift+S"), "shift+super+s");
+        assert_eq!(normalize(" shift + super + s"), "shift+super+s");
+        assert!(reserved_on_windows("Win+Shift+S"));
+        assert!(reserved_on_windows("Shift+Meta+S"));
+        assert!(!reserved_on_windows("CmdOrCtrl+Shift+S"));
+    }
+
+    #[test]
+    fn windows_falls_back_to_alternatives() {
+        // Elsewhere: just the configured key
+        assert_eq!(snip_candidates(Some("Cmd+Shift+2"), true, false, None), vec!["Cmd+Shift+2"]);
+        assert!(snip_candidates(None, true, false, None).is_empty());
+        // Windows, reserved key and the launcher on the first alternative
+        assert_eq!(
+            snip_candidates(Some("Win+Shift+S"), true, true, Some("ctrl+alt+s")),
+            vec!["Ctrl+Shift+Alt+S", "Ctrl+Alt+PrintScreen"]
+        );
+        // Windows, own key first; no fallback when turned off
+        assert_eq!(snip_candidates(Some("Ctrl+Alt+S"), true, true, None), WINDOWS_ALTERNATIVES.to_vec());
+        assert_eq!(snip_candidates(Some("Alt+Q"), false, true, None), vec!["Alt+Q"]);
+        assert!(snip_candidates(Some("Win+Shift+S"), false, true, None).is_empty());
+    }
+}
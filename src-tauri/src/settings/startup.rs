@@ -0,0 +1,58 @@
+//! Launch at login and background (menu-bar) mode.
+//!
+//! Login items go through `tauri-plugin-autostart` (a LaunchAgent on
+//! macOS, the `Run` registry key on Windows, an XDG autostart entry on
+//! Linux). The OS entry is the source of truth: if the user removes it
+//! in System Settings, `attach` adopts that instead of re-adding it.
+//!
+//! Background mode hides the Dock icon on macOS so Omni-Glass lives only
+//! in the menu bar. Other platforms already keep it out of the taskbar.
+
+use tauri_plugin_autostart::ManagerExt;
+
+/// Whether the OS will start Omni-Glass at login; `None` if unknown.
+pub fn launch_at_login(app: &tauri::AppHandle) -> Option<bool> {
+    match app.autolaunch().is_enabled() {
+        Ok(enabled) => Some(enabled),
+        Err(e) => {
+            log::warn!("[SETTINGS] Could not read login item: {}", e);
+            None
+        }
+    }
+}
+
+/// Add or remove the login item.
+pub fn set_launch_at_login(app: &tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let autostart = app.autolaunch();
+    let result = if enabled { autostart.enable() } else { autostart.disable() };
+    result.map_err(|e| format!("Failed to update login item: {}", e))?;
+    log::info!("[SETTINGS] Launch at login {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Show or hide the Dock icon (macOS only).
+pub fn apply_background_mode(app: &tauri::AppHandle, background: bool) {
+    #[cfg(target_os = "macos")]
+    {
+        let policy = if background { tauri::ActivationPolicy::Accessory } else { tauri::ActivationPolicy::Regular };
+        if let Err(e) = app.set_activation_policy(policy) {
+            log::warn!("[SETTINGS] Failed to set activation policy: {}", e);
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = (app, background);
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: start Omni-Glass at login, or stop doing so.
+#[tauri::command]
+pub fn set_autostart(app: tauri::AppHandle, enabled: bool) -> Result<super::Settings, String> {
+    super::update(&app, serde_json::json!({ "launchAtLogin": enabled }))
+}
+
+/// Tauri command: run as a menu-bar-only utility (no Dock icon), or not.
+#[tauri::command]
+pub fn set_background_mode(app: tauri::AppHandle, enabled: bool) -> Result<super::Settings, String> {
+    super::update(&app, serde_json::json!({ "backgroundMode": enabled }))
+}
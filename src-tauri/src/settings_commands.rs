@@ -1,48 +1,50 @@
 //! Settings panel Tauri commands and provider resolution.
 //!
 //! Handles:
-//! - Provider configuration (get/set active provider, save API keys)
+//! - Provider configuration (get/set active provider, save API keys),
+//!   subject to the enterprise policy in policy/
 //! - API key storage (OS keychain via keyring crate + env var)
 //! - Provider connection testing
 //! - OCR mode get/set
 //! - Settings window lifecycle
+//!
+//! Provider and OCR mode are stored in the settings file (settings/).
 
 use crate::llm;
-use tauri::Manager;
+use crate::policy;
+use crate::settings;
 
 // ── Provider resolution ──────────────────────────────────────────────
 
 /// Determine which LLM provider to use.
 ///
 /// Priority:
-/// 1. LLM_PROVIDER env var (explicit override: "anthropic" or "gemini")
-/// 2. First provider with an API key set (env var or keychain)
-/// 3. "anthropic" as final default
+/// 1. Enterprise policy `forcedProvider` (see policy/precedence.rs)
+/// 2. The provider chosen in settings ("anthropic" or "gemini")
+/// 3. First provider with an API key set (env var or keychain)
+/// 4. "anthropic" as final default
 pub fn resolve_provider() -> String {
-    // Explicit override
-    if let Ok(p) = std::env::var("LLM_PROVIDER") {
-        let p = p.to_lowercase();
-        if p == "gemini" || p == "anthropic" {
-            log::info!("[LLM] Provider override: {}", p);
-            return p;
-        }
+    let user_choice = settings::current().provider;
+    if let Some(ref p) = user_choice {
+        log::info!("[LLM] Provider from settings: {}", p);
     }
 
-    // Auto-detect: first configured key wins
-    if has_api_key("anthropic") {
-        return "anthropic".to_string();
-    }
-    if has_api_key("gemini") {
-        return "gemini".to_string();
-    }
-
-    // Default (will trigger fallback menu since no key is set)
-    "anthropic".to_string()
+    policy::precedence::effective_provider(policy::current(), user_choice.as_deref(), || {
+        // Auto-detect: first configured key wins
+        if has_api_key("anthropic") {
+            return "anthropic".to_string();
+        }
+        if has_api_key("gemini") {
+            return "gemini".to_string();
+        }
+        // Default (will trigger fallback menu since no key is set)
+        "anthropic".to_string()
+    })
 }
 
 /// Check if a provider has an API key available (env var or keychain).
 /// If found in keychain but not in env, loads it into env for the provider to use.
-fn has_api_key(provider_id: &str) -> bool {
+pub(crate) fn has_api_key(provider_id: &str) -> bool {
     let env_key = match provider_id {
         "anthropic" => "ANTHROPIC_API_KEY",
         "gemini" => "GEMINI_API_KEY",
@@ -86,13 +88,15 @@ pub fn get_provider_config() -> Result<serde_json::Value, String> {
         "activeProvider": active,
         "providers": providers,
         "configuredProviders": configured,
+        "providerLocked": policy::current().forced_provider.is_some(),
     }))
 }
 
 /// Tauri command: set the active LLM provider.
 #[tauri::command]
-pub fn set_active_provider(provider_id: String) -> Result<(), String> {
-    std::env::set_var("LLM_PROVIDER", &provider_id);
+pub fn set_active_provider(app: tauri::AppHandle, provider_id: String) -> Result<(), String> {
+    policy::precedence::check_provider_selection(policy::current(), &provider_id)?;
+    settings::update(&app, serde_json::json!({ "provider": provider_id }))?;
     log::info!("[SETTINGS] Active provider set to: {}", provider_id);
     Ok(())
 }
@@ -136,7 +140,7 @@ pub async fn test_provider(provider_id: String) -> Result<bool, String> {
                     ("content-type".to_string(), "application/json".to_string()),
                 ],
                 serde_json::json!({
-                    "model": "claude-haiku-4-5-20251001",
+                    "model": llm::provider::model_for("anthropic"),
                     "max_tokens": 50,
                     "messages": [{"role": "user", "content": "Reply with just: ok"}]
                 }),
@@ -147,7 +151,8 @@ pub async fn test_provider(provider_id: String) -> Result<bool, String> {
                 .map_err(|_| "No GEMINI_API_KEY set".to_string())?;
             (
                 format!(
-                    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent?key={}",
+                    "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+                    llm::provider::model_for("gemini"),
                     key
                 ),
                 vec![("content-type".to_string(), "application/json".to_string())],
@@ -160,7 +165,7 @@ pub async fn test_provider(provider_id: String) -> Result<bool, String> {
         _ => return Err(format!("Unknown provider: {}", provider_id)),
     };
 
-    let client = reqwest::Client::new();
+    let client = crate::llm::http::client(&provider_id);
     let mut req = client.post(&url);
     for (k, v) in headers {
         req = req.header(&k, &v);
@@ -179,10 +184,7 @@ pub async fn test_provider(provider_id: String) -> Result<bool, String> {
 /// Tauri command: close the settings window.
 #[tauri::command]
 pub fn close_settings(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("settings") {
-        window.close().map_err(|e| e.to_string())?;
-    }
-    Ok(())
+    crate::windows::close(&app, crate::windows::spec::Window::Settings)
 }
 
 /// Tauri command: open the settings window.
@@ -190,41 +192,20 @@ pub fn close_settings(app: tauri::AppHandle) -> Result<(), String> {
 /// Called from the tray context menu.
 #[tauri::command]
 pub fn open_settings(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("settings") {
-        let _ = window.set_focus();
-        return Ok(());
-    }
-
-    tauri::WebviewWindowBuilder::new(
-        &app,
-        "settings",
-        tauri::WebviewUrl::App("settings.html".into()),
-    )
-    .title("Omni-Glass Settings")
-    .inner_size(520.0, 500.0)
-    .resizable(true)
-    .build()
-    .map_err(|e| format!("Failed to create settings window: {}", e))?;
-
-    Ok(())
+    crate::windows::show(&app, crate::windows::spec::Window::Settings)
 }
 
 /// Tauri command: get the current OCR recognition mode.
 #[tauri::command]
 pub fn get_ocr_mode() -> String {
-    std::env::var("OCR_MODE")
-        .unwrap_or_else(|_| "fast".to_string())
-        .to_lowercase()
+    settings::current().ocr_mode
 }
 
 /// Tauri command: set the OCR recognition mode.
 #[tauri::command]
-pub fn set_ocr_mode(mode: String) -> Result<(), String> {
+pub fn set_ocr_mode(app: tauri::AppHandle, mode: String) -> Result<(), String> {
     let mode = mode.to_lowercase();
-    if mode != "fast" && mode != "accurate" {
-        return Err(format!("Invalid OCR mode: {}. Use 'fast' or 'accurate'.", mode));
-    }
-    std::env::set_var("OCR_MODE", &mode);
+    settings::update(&app, serde_json::json!({ "ocrMode": mode }))?;
     log::info!("[SETTINGS] OCR mode set to: {}", mode);
     Ok(())
 }
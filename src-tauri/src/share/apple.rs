@@ -0,0 +1,21 @@
+//! macOS share sheet — `NSSharingServicePicker` via swift-bridge.
+//!
+//! `swift-src/share_bridge.swift` shows the picker under the mouse, over
+//! the key window (the action menu) or a tiny transparent panel when no
+//! window has focus. Must be called on the main thread.
+
+use super::content::ShareContent;
+use std::path::Path;
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Swift" {
+        /// `image_path` is empty when only text is shared.
+        fn show_share_picker(title: String, text: String, image_path: String);
+    }
+}
+
+pub fn show(shared: &ShareContent, image: Option<&Path>) {
+    let image_path = image.map(|p| p.display().to_string()).unwrap_or_default();
+    ffi::show_share_picker(shared.title.clone(), shared.text.clone(), image_path);
+}
@@ -0,0 +1,123 @@
+//! What a share carries: a title, the text, and whether the crop goes
+//! along (pure, no I/O).
+//!
+//! A result is shared as its text (a command as the command line); the
+//! snip itself as its OCR text. The crop is attached when sharing the
+//! snip or a text answer — not for commands or files, where an image of
+//! the source would only get in the way.
+
+use crate::history::store::SnipRecord;
+use crate::llm::ActionResult;
+
+/// Longest text handed to the share sheet; Messages and Teams cut far
+/// shorter anyway.
+const MAX_TEXT_CHARS: usize = 20_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareContent {
+    pub title: String,
+    pub text: String,
+    pub with_image: bool,
+}
+
+/// The text of a successful result, if it has any.
+pub fn result_text(result: &ActionResult) -> Option<(String, bool)> {
+    if result.status != "success" {
+        return None;
+    }
+    let body = &result.result;
+    let (text, with_image) = match body.result_type.as_str() {
+        "command" => (body.command.as_ref(), false),
+        "clipboard" => (body.clipboard_content.as_ref().or(body.text.as_ref()), true),
+        "file" => (body.text.as_ref(), false),
+        _ => (body.text.as_ref(), true),
+    };
+    text.filter(|t| !t.trim().is_empty()).map(|t| (t.clone(), with_image))
+}
+
+/// Share content for the snip's OCR text, or for `result` when given.
+pub fn build(title: &str, snip_text: &str, result: Option<&ActionResult>) -> Option<ShareContent> {
+    let (text, with_image) = match result {
+        Some(result) => result_text(result)?,
+        None => (snip_text.to_string(), true),
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(ShareContent {
+        title: title.to_string(),
+        text: text.chars().take(MAX_TEXT_CHARS).collect(),
+        with_image,
+    })
+}
+
+/// Share content for a history record: its newest answer to `action_id`,
+/// or with no `action_id` its OCR text.
+pub fn from_record(record: &SnipRecord, action_id: Option<&str>) -> Option<ShareContent> {
+    let title = match record.summary.trim() {
+        "" => format!("Snip #{}", record.id),
+        summary => summary.to_string(),
+    };
+    let Some(action_id) = action_id else {
+        return build(&title, &record.text, None);
+    };
+    let answer = record.answers.iter().rev().find(|a| a.action_id == action_id)?;
+    match &answer.result {
+        Some(result) => build(&title, &record.text, Some(result)),
+        // Invalidated result: only its stored text is left
+        None => build(&title, &answer.text, None).map(|c| ShareContent { with_image: false, ..c }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::store::SnipAnswer;
+
+    fn result(result_type: &str, text: Option<&str>, command: Option<&str>) -> ActionResult {
+        let mut result = ActionResult::error("explain_this", "");
+        result.status = "success".to_string();
+        result.result.result_type = result_type.to_string();
+        result.result.text = text.map(String::from);
+        result.result.command = command.map(String::from);
+        result
+    }
+
+    #[test]
+    fn picks_text_by_result_type() {
+        let snip = build("Chart", "  Q3 revenue  ", None).unwrap();
+        assert_eq!((snip.text.as_str(), snip.with_image), ("Q3 revenue", true));
+        let answer = build("Chart", "Q3", Some(&result("text", Some("It grew"), None))).unwrap();
+        assert_eq!((answer.text.as_str(), answer.with_image), ("It grew", true));
+        let command = build("Chart", "Q3", Some(&result("command", None, Some("ls -la")))).unwrap();
+        assert_eq!((command.text.as_str(), command.with_image), ("ls -la", false));
+        assert_eq!(build("Chart", "Q3", Some(&result("text", Some("  "), None))), None);
+        let mut failed = result("text", Some("It grew"), None);
+        failed.status = "error".to_string();
+        assert_eq!(build("Chart", "Q3", Some(&failed)), None);
+    }
+
+    #[test]
+    fn history_records_use_newest_answer() {
+        let mut store = crate::history::store::HistoryStore::default();
+        let id = store.add_snip("Q3 revenue", None, None, "default", 100);
+        let mut record = store.records().iter().find(|r| r.id == id).unwrap().clone();
+        record.answers.push(SnipAnswer {
+            action_id: "explain_this".into(),
+            text: "old".into(),
+            created_at: 100,
+            result: Some(result("text", Some("old"), None)),
+        });
+        record.answers.push(SnipAnswer {
+            action_id: "explain_this".into(),
+            text: "new".into(),
+            created_at: 200,
+            result: None,
+        });
+        let shared = from_record(&record, Some("explain_this")).unwrap();
+        assert_eq!((shared.title.as_str(), shared.text.as_str(), shared.with_image), (&*format!("Snip #{}", id), "new", false));
+        assert_eq!(from_record(&record, None).unwrap().text, "Q3 revenue");
+        assert_eq!(from_record(&record, Some("translate")), None);
+    }
+}
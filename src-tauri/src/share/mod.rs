@@ -0,0 +1,129 @@
+//! Share domain — send a snip or result to Messages, AirDrop, Mail,
+//! Teams, … through the OS share sheet.
+//!
+//! `share_result` gathers the text (`content.rs`) and, where it helps,
+//! the crop, writes the crop to a temp PNG named like an auto-save, and
+//! opens the platform picker on the main thread: `NSSharingServicePicker`
+//! on macOS (`apple.rs`), the Share UI (`DataTransferManager`) on
+//! Windows (`windows.rs`). Linux has no system share sheet.
+
+pub mod content;
+
+#[cfg(target_os = "macos")]
+mod apple;
+#[cfg(target_os = "windows")]
+mod windows;
+
+use crate::capture::autosave;
+use crate::history;
+use crate::i18n::t;
+use crate::llm::ActionMenuState;
+use content::ShareContent;
+use std::path::PathBuf;
+
+/// Whether this platform has a share sheet.
+const SUPPORTED: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+/// Temp folder for shared crops. Cleared at the next share, since the
+/// target app may read the file after the picker closes.
+const SHARE_DIR: &str = "omni-glass-share";
+
+/// Content and crop of a history snip.
+fn from_history(state: &ActionMenuState, snip_id: u64, action_id: Option<&str>) -> Result<(ShareContent, Option<Vec<u8>>, String), String> {
+    let record = history::with_records(|records| records.iter().find(|r| r.id == snip_id).cloned())
+        .ok_or_else(|| t("error.snipNotFound"))?;
+    let shared = content::from_record(&record, action_id).ok_or_else(|| t("error.nothingToShare"))?;
+    let png = if shared.with_image { crate::export::current_crop(state, record.image_hash) } else { None };
+    let stem = autosave::render_stem(&autosave::current().template, record.created_at, &record.content_type, &record.summary);
+    Ok((shared, png, stem))
+}
+
+/// Content and crop of the snip in the action menu.
+fn from_current(state: &ActionMenuState, action_id: Option<&str>) -> Result<(ShareContent, Option<Vec<u8>>, String), String> {
+    let text = state.ocr_text.lock().map_err(|e| e.to_string())?.clone().ok_or_else(|| t("error.nothingToShare"))?;
+    let menu = state.menu.lock().map_err(|e| e.to_string())?.clone();
+    let (content_type, summary) = menu.map_or((String::new(), String::new()), |m| (m.content_type, m.summary));
+    let title = if summary.trim().is_empty() { "Omni-Glass snip".to_string() } else { summary.clone() };
+    let result = match action_id {
+        Some(action_id) => {
+            let last = state.last_result.lock().map_err(|e| e.to_string())?.clone();
+            let result = last
+                .filter(|(snip, r)| *snip == text && r.action_id == action_id)
+                .map(|(_, r)| r)
+                .or_else(|| history::cached_result(&text, action_id))
+                .ok_or_else(|| t("error.nothingToShare"))?;
+            Some(result)
+        }
+        None => None,
+    };
+    let shared = content::build(&title, &text, result.as_ref()).ok_or_else(|| t("error.nothingToShare"))?;
+    let png = if shared.with_image { state.crop_png.lock().map_err(|e| e.to_string())?.clone() } else { None };
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+    let stem = autosave::render_stem(&autosave::current().template, now, &content_type, &summary);
+    Ok((shared, png, stem))
+}
+
+/// Write the crop for the share target, replacing the last share's.
+fn write_crop(png: &[u8], stem: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join(SHARE_DIR);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create share folder: {}", e))?;
+    let path = dir.join(format!("{}.png", stem));
+    std::fs::write(&path, png).map_err(|e| format!("Failed to write image: {}", e))?;
+    Ok(path)
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: open the share sheet for a snip (its OCR text and
+/// crop) or, with `action_id`, its latest answer to that action.
+/// `snip_id` picks a history record; without it the current snip is used.
+/// The crop only goes along while it is still in memory.
+#[tauri::command]
+pub fn share_result(
+    window: tauri::WebviewWindow,
+    state: tauri::State<'_, ActionMenuState>,
+    snip_id: Option<u64>,
+    action_id: Option<String>,
+) -> Result<(), String> {
+    if !SUPPORTED {
+        return Err(t("error.shareUnavailable"));
+    }
+    let (shared, png, stem) = match snip_id {
+        Some(id) => from_history(&state, id, action_id.as_deref())?,
+        None => from_current(&state, action_id.as_deref())?,
+    };
+    let image = png.map(|png| write_crop(&png, &stem)).transpose()?;
+    log::info!(
+        "[SHARE] Sharing '{}': {} chars{} ({})",
+        shared.title,
+        shared.text.chars().count(),
+        if image.is_some() { " and the crop" } else { "" },
+        action_id.as_deref().unwrap_or("snip")
+    );
+    show(&window, shared, image)
+}
+
+/// Open the platform picker next to `window`, on the main thread.
+#[cfg(target_os = "macos")]
+fn show(window: &tauri::WebviewWindow, shared: ShareContent, image: Option<PathBuf>) -> Result<(), String> {
+    window
+        .run_on_main_thread(move || apple::show(&shared, image.as_deref()))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn show(window: &tauri::WebviewWindow, shared: ShareContent, image: Option<PathBuf>) -> Result<(), String> {
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?.0 as isize;
+    window
+        .run_on_main_thread(move || {
+            if let Err(e) = windows::show(hwnd, &shared, image.as_deref()) {
+                log::warn!("[SHARE] Share UI failed: {}", e);
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn show(_window: &tauri::WebviewWindow, _shared: ShareContent, _image: Option<PathBuf>) -> Result<(), String> {
+    unreachable!("checked by SUPPORTED")
+}
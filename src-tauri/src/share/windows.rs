@@ -0,0 +1,59 @@
+//! Windows Share UI — `DataTransferManager` for the calling window.
+//!
+//! The content is supplied from the `DataRequested` handler, which runs
+//! when the Share UI opens. Each share replaces the previous handler so
+//! an old snip is never offered again. Must be called on the main thread.
+//!
+//! NOTE: Like ocr/windows_ocr.rs, written against the Microsoft docs and
+//! the `windows` crate bindings; needs testing on Windows.
+
+use super::content::ShareContent;
+use std::cell::RefCell;
+use std::path::Path;
+use windows::{
+    core::{factory, HSTRING},
+    ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager},
+    Foundation::TypedEventHandler,
+    Storage::{StorageFile, Streams::RandomAccessStreamReference},
+    Win32::{Foundation::HWND, UI::Shell::IDataTransferManagerInterop},
+};
+
+thread_local! {
+    /// The registered handler, removed before the next share.
+    static HANDLER: RefCell<Option<(DataTransferManager, i64)>> = const { RefCell::new(None) };
+}
+
+pub fn show(hwnd: isize, shared: &ShareContent, image: Option<&Path>) -> windows::core::Result<()> {
+    let hwnd = HWND(hwnd as *mut core::ffi::c_void);
+    let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+    let bitmap = match image {
+        Some(path) => {
+            let file = StorageFile::GetFileFromPathAsync(&HSTRING::from(path.as_os_str()))?.get()?;
+            Some(RandomAccessStreamReference::CreateFromFile(&file)?)
+        }
+        None => None,
+    };
+    let (title, text) = (HSTRING::from(&shared.title), HSTRING::from(&shared.text));
+    let handler = TypedEventHandler::new(move |_, args: &Option<DataRequestedEventArgs>| {
+        let Some(args) = args else { return Ok(()) };
+        let data = args.Request()?.Data()?;
+        data.Properties()?.SetTitle(&title)?;
+        data.SetText(&text)?;
+        if let Some(bitmap) = &bitmap {
+            data.SetBitmap(bitmap)?;
+        }
+        Ok(())
+    });
+
+    HANDLER.with(|slot| -> windows::core::Result<()> {
+        if let Some((previous, token)) = slot.borrow_mut().take() {
+            let _ = previous.RemoveDataRequested(token);
+        }
+        let token = manager.DataRequested(&handler)?;
+        *slot.borrow_mut() = Some((manager, token));
+        Ok(())
+    })?;
+    unsafe { interop.ShowShareUIForWindow(hwnd) }
+}
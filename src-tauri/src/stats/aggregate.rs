@@ -0,0 +1,242 @@
+//! Statistics over history and usage samples (pure, no I/O).
+//!
+//! Everything is limited to a window of the last N days. Snips per day
+//! lists every day in the window, zeros included, so a chart needs no
+//! gap filling. Top actions count the results history kept, so failed
+//! runs don't appear. Percentiles use the nearest-rank method.
+
+use super::usage::UsageLog;
+use crate::capture::autosave::utc_parts;
+use crate::history::store::SnipRecord;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+/// Entries in each "top" list.
+const TOP_N: usize = 10;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayCount {
+    /// YYYY-MM-DD (UTC).
+    pub date: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NamedCount {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Latency {
+    pub samples: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCost {
+    pub provider: String,
+    pub calls: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub days: u32,
+    pub total_snips: usize,
+    pub snips_per_day: Vec<DayCount>,
+    pub top_content_types: Vec<NamedCount>,
+    pub top_actions: Vec<NamedCount>,
+    /// `None` until an OCR pass has been timed in the window.
+    pub ocr_latency: Option<Latency>,
+    /// Classify calls, all providers; `None` until one has been timed.
+    pub llm_latency: Option<Latency>,
+    pub llm_cost: Vec<ProviderCost>,
+    pub total_cost_usd: f64,
+}
+
+fn date(secs: u64) -> String {
+    let (y, m, d, ..) = utc_parts(secs);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Most frequent first, ties by name.
+fn top(counts: BTreeMap<String, usize>) -> Vec<NamedCount> {
+    let mut list: Vec<NamedCount> = counts.into_iter().map(|(name, count)| NamedCount { name, count }).collect();
+    list.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    list.truncate(TOP_N);
+    list
+}
+
+/// Nearest-rank percentile of sorted, non-empty samples.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Percentiles of unsorted samples; `None` when there are none.
+fn latency(mut samples: Vec<u64>) -> Option<Latency> {
+    samples.sort_unstable();
+    (!samples.is_empty()).then(|| Latency {
+        samples: samples.len(),
+        p50_ms: percentile(&samples, 50),
+        p90_ms: percentile(&samples, 90),
+        p99_ms: percentile(&samples, 99),
+    })
+}
+
+/// Aggregate the `days` days up to and including `now`'s day.
+pub fn compute(records: &[SnipRecord], usage: &UsageLog, now: u64, days: u32) -> Stats {
+    let today = now / SECS_PER_DAY;
+    let first_day = today.saturating_sub(u64::from(days.max(1)) - 1);
+    let since = first_day * SECS_PER_DAY;
+
+    let mut per_day = vec![0usize; (today - first_day + 1) as usize];
+    let mut content_types = BTreeMap::new();
+    let mut actions = BTreeMap::new();
+    let mut total_snips = 0;
+    for record in records.iter().filter(|r| r.created_at >= since && r.created_at <= now) {
+        total_snips += 1;
+        per_day[(record.created_at / SECS_PER_DAY - first_day) as usize] += 1;
+        if !record.content_type.is_empty() {
+            *content_types.entry(record.content_type.clone()).or_insert(0) += 1;
+        }
+        for answer in record.answers.iter().filter(|a| a.created_at >= since) {
+            *actions.entry(answer.action_id.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let ocr_latency = latency(usage.ocr.iter().filter(|s| s.at >= since).map(|s| s.ms).collect());
+    let llm_latency = latency(usage.llm.iter().filter(|c| c.at >= since && c.ms > 0).map(|c| c.ms).collect());
+
+    let mut providers: BTreeMap<String, ProviderCost> = BTreeMap::new();
+    for call in usage.llm.iter().filter(|c| c.at >= since) {
+        let entry = providers.entry(call.provider.clone()).or_insert_with(|| ProviderCost {
+            provider: call.provider.clone(),
+            ..ProviderCost::default()
+        });
+        entry.calls += 1;
+        entry.input_tokens += call.input_tokens;
+        entry.output_tokens += call.output_tokens;
+        entry.cost_usd += call.cost_usd;
+    }
+    let llm_cost: Vec<ProviderCost> = providers.into_values().collect();
+
+    Stats {
+        days: days.max(1),
+        total_snips,
+        snips_per_day: per_day
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| DayCount { date: date((first_day + i as u64) * SECS_PER_DAY), count })
+            .collect(),
+        top_content_types: top(content_types),
+        top_actions: top(actions),
+        ocr_latency,
+        llm_latency,
+        total_cost_usd: llm_cost.iter().fold(0.0, |sum, p| sum + p.cost_usd),
+        llm_cost,
+    }
+}
+
+/// Quote a CSV field when it needs it.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One `section,name,value` row per figure.
+pub fn to_csv(stats: &Stats) -> String {
+    let mut rows = vec!["section,name,value".to_string()];
+    let mut row = |section: &str, name: &str, value: String| {
+        rows.push(format!("{},{},{}", section, field(name), value));
+    };
+    for d in &stats.snips_per_day {
+        row("snips_per_day", &d.date, d.count.to_string());
+    }
+    for c in &stats.top_content_types {
+        row("content_type", &c.name, c.count.to_string());
+    }
+    for a in &stats.top_actions {
+        row("action", &a.name, a.count.to_string());
+    }
+    for (section, latency) in [("ocr_latency_ms", &stats.ocr_latency), ("llm_latency_ms", &stats.llm_latency)] {
+        if let Some(l) = latency {
+            row(section, "p50", l.p50_ms.to_string());
+            row(section, "p90", l.p90_ms.to_string());
+            row(section, "p99", l.p99_ms.to_string());
+            row(section, "samples", l.samples.to_string());
+        }
+    }
+    for p in &stats.llm_cost {
+        row("llm_calls", &p.provider, p.calls.to_string());
+        row("llm_input_tokens", &p.provider, p.input_tokens.to_string());
+        row("llm_output_tokens", &p.provider, p.output_tokens.to_string());
+        row("llm_cost_usd", &p.provider, format!("{:.6}", p.cost_usd));
+    }
+    row("llm_cost_usd", "total", format!("{:.6}", stats.total_cost_usd));
+    rows.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::store::HistoryStore;
+    use crate::llm::types::ActionMenu;
+    use super::super::usage::{LlmCall, OcrSample};
+
+    #[test]
+    fn aggregates_within_the_window() {
+        let day = SECS_PER_DAY;
+        let mut store = HistoryStore::default();
+        let mut menu = ActionMenu::fallback();
+        menu.content_type = "error".to_string();
+        store.add_snip("old", Some(&menu), None, "default", day);
+        store.add_snip("a", Some(&menu), None, "default", 10 * day + 5);
+        store.add_snip("b", None, None, "default", 12 * day);
+        store.add_answer("a", "explain_error", "…", None, 10 * day + 9).unwrap();
+
+        let mut usage = UsageLog::default();
+        for ms in 1..=100 {
+            usage.push_ocr(OcrSample { at: 11 * day, ms });
+        }
+        usage.push_ocr(OcrSample { at: day, ms: 9_999 });
+        for provider in ["anthropic", "anthropic", "gemini"] {
+            usage.push_llm(LlmCall { at: 11 * day, provider: provider.into(), input_tokens: 1000, output_tokens: 100, cost_usd: 0.5, ms: 800 });
+        }
+
+        let stats = compute(store.records(), &usage, 12 * day + 60, 3);
+        assert_eq!(stats.total_snips, 2);
+        let counts: Vec<usize> = stats.snips_per_day.iter().map(|d| d.count).collect();
+        assert_eq!(counts, vec![1, 0, 1]);
+        assert_eq!(stats.snips_per_day[0].date, "1970-01-11");
+        assert_eq!(stats.top_content_types, vec![NamedCount { name: "error".into(), count: 1 }]);
+        assert_eq!(stats.top_actions[0].name, "explain_error");
+        let latency = stats.ocr_latency.unwrap();
+        assert_eq!((latency.samples, latency.p50_ms, latency.p90_ms, latency.p99_ms), (100, 50, 90, 99));
+        assert_eq!(stats.llm_latency.unwrap().p99_ms, 800);
+        assert_eq!(stats.llm_cost[0].calls, 2);
+        assert_eq!(stats.total_cost_usd, 1.5);
+    }
+
+    #[test]
+    fn csv_has_one_row_per_figure() {
+        let stats = compute(&[], &UsageLog::default(), 0, 1);
+        assert!(stats.ocr_latency.is_none());
+        assert_eq!(to_csv(&stats), "section,name,value\nsnips_per_day,1970-01-01,0\nllm_cost_usd,total,0.000000\n");
+        assert_eq!(field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,39 @@
+//! Usage statistics — the data behind the Settings "Insights" panel.
+//!
+//! Combines snip history (snips per day, content types, actions) with
+//! usage samples history doesn't keep (`usage.rs`: OCR and LLM latency, LLM
+//! tokens and cost). The aggregation itself is pure (`aggregate.rs`).
+
+pub mod aggregate;
+pub mod usage;
+
+pub use usage::{record_llm_call, record_ocr_latency};
+
+use aggregate::Stats;
+
+/// Window used when the frontend doesn't pass one.
+const DEFAULT_DAYS: u32 = 30;
+/// Longest window.
+const MAX_DAYS: u32 = 365;
+
+fn collect(days: Option<u32>) -> Stats {
+    let days = days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    usage::with_log(|log| crate::history::with_records(|records| aggregate::compute(records, log, now, days)))
+}
+
+/// Tauri command: statistics for the last `days` days (default 30).
+#[tauri::command]
+pub fn get_stats(days: Option<u32>) -> Stats {
+    collect(days)
+}
+
+/// Tauri command: the same statistics as CSV, for saving via
+/// `write_file_to_path`.
+#[tauri::command]
+pub fn get_stats_csv(days: Option<u32>) -> String {
+    aggregate::to_csv(&collect(days))
+}
@@ -0,0 +1,100 @@
+//! Usage samples that history doesn't keep: OCR latency, and LLM latency
+//! and token spend per provider.
+//!
+//! Only numbers are recorded — never snip text. Samples are capped and
+//! persisted to `~/.config/omni-glass/usage-stats.json`. Nothing is
+//! recorded while history is paused (incognito), and a history purge
+//! erases them.
+
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Samples kept per kind (oldest dropped first).
+const MAX_SAMPLES: usize = 5000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrSample {
+    /// Unix seconds.
+    pub at: u64,
+    pub ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmCall {
+    /// Unix seconds.
+    pub at: u64,
+    pub provider: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated from the provider's list price.
+    pub cost_usd: f64,
+    /// Request start to end of stream; 0 in samples recorded before it was kept.
+    #[serde(default)]
+    pub ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageLog {
+    #[serde(default)]
+    pub ocr: VecDeque<OcrSample>,
+    #[serde(default)]
+    pub llm: VecDeque<LlmCall>,
+}
+
+impl UsageLog {
+    pub fn push_ocr(&mut self, sample: OcrSample) {
+        self.ocr.push_back(sample);
+        while self.ocr.len() > MAX_SAMPLES {
+            self.ocr.pop_front();
+        }
+    }
+
+    pub fn push_llm(&mut self, call: LlmCall) {
+        self.llm.push_back(call);
+        while self.llm.len() > MAX_SAMPLES {
+            self.llm.pop_front();
+        }
+    }
+}
+
+static LOG: JsonStore<UsageLog> = JsonStore::compact("usage-stats.json");
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Read the log under the lock.
+pub fn with_log<T>(read: impl FnOnce(&UsageLog) -> T) -> T {
+    LOG.read(read)
+}
+
+fn record(change: impl FnOnce(&mut UsageLog, u64)) {
+    if crate::history::privacy::current().paused {
+        return;
+    }
+    LOG.update_logged(|log| change(log, now_secs()));
+}
+
+/// Drop every sample and shred the file (history purge).
+pub fn purge() -> Result<(), String> {
+    LOG.erase(|log| *log = UsageLog::default())
+}
+
+/// Record how long one OCR pass took.
+pub fn record_ocr_latency(ms: u64) {
+    record(|log, at| log.push_ocr(OcrSample { at, ms }));
+}
+
+/// Record the token usage, estimated cost, and latency of one LLM call.
+pub fn record_llm_call(provider: &str, input_tokens: u64, output_tokens: u64, cost_usd: f64, ms: u64) {
+    record(|log, at| {
+        log.push_llm(LlmCall { at, provider: provider.to_string(), input_tokens, output_tokens, cost_usd, ms })
+    });
+}
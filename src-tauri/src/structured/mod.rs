@@ -0,0 +1,135 @@
+//! Structured-data domain — "Format JSON/YAML" for snipped documents.
+//!
+//! A snip that looks like JSON or YAML (`syntax::detect`) gets a
+//! `format_data` action. It parses the text locally, reports a syntax
+//! error with its line and column, or returns the document pretty-printed
+//! in a code block the result view offers to copy. JSON is re-indented
+//! without reordering anything (`syntax.rs`); YAML goes through
+//! `serde_yaml`, which keeps key order but drops comments. No LLM call.
+
+pub mod syntax;
+
+use crate::i18n::{t, tf};
+use crate::llm::execute::{ActionResult, ActionResultBody, ActionResultMetadata};
+use crate::llm::types::{Action, ActionMenu};
+use serde::Deserialize;
+use syntax::{Kind, SyntaxError};
+
+/// Action id, answered here before the outbound guard.
+pub const FORMAT_ACTION: &str = "format_data";
+
+/// Append the format action when `text` is a JSON or YAML document and
+/// `menu` lacks it. Returns whether it was added.
+pub fn augment(menu: &mut ActionMenu, text: &str) -> bool {
+    if menu.actions.iter().any(|a| a.id == FORMAT_ACTION) {
+        return false;
+    }
+    let Some(kind) = syntax::detect(text) else { return false };
+    let priority = menu.actions.iter().map(|a| a.priority).max().unwrap_or(0).saturating_add(1);
+    menu.actions.push(Action {
+        id: FORMAT_ACTION.to_string(),
+        label: tf("menu.formatData", &[("format", kind.name())]),
+        icon: "braces".to_string(),
+        priority,
+        description: t("menu.formatDataDescription"),
+        requires_execution: true,
+    });
+    true
+}
+
+/// Drop serde's own " at line L column C" suffix; the position is
+/// reported separately.
+fn message(error: &impl std::fmt::Display) -> String {
+    let text = error.to_string();
+    match text.rfind(" at line ") {
+        Some(i) => text[..i].to_string(),
+        None => text,
+    }
+}
+
+/// "object with 3 keys", "array of 5 items", or "single value".
+fn shape(container: Option<(&str, usize)>) -> String {
+    match container {
+        Some((id, count)) => tf(id, &[("count", count.to_string().as_str())]),
+        None => t("structured.scalar"),
+    }
+}
+
+fn format_json(text: &str) -> Result<(String, String), SyntaxError> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| SyntaxError { line: e.line(), column: e.column(), message: message(&e) })?;
+    let container = match &value {
+        serde_json::Value::Object(map) => Some(("structured.object", map.len())),
+        serde_json::Value::Array(items) => Some(("structured.array", items.len())),
+        _ => None,
+    };
+    Ok((syntax::reindent_json(text), shape(container)))
+}
+
+/// Every document of a multi-document stream, each re-serialized.
+fn format_yaml(text: &str) -> Result<(String, String), SyntaxError> {
+    let to_error = |e: serde_yaml::Error| {
+        let (line, column) = e.location().map_or((0, 0), |l| (l.line(), l.column()));
+        SyntaxError { line, column, message: message(&e) }
+    };
+    let mut documents = Vec::new();
+    let mut container = None;
+    for document in serde_yaml::Deserializer::from_str(text) {
+        let value = serde_yaml::Value::deserialize(document).map_err(to_error)?;
+        container = match &value {
+            serde_yaml::Value::Mapping(map) => Some(("structured.object", map.len())),
+            serde_yaml::Value::Sequence(items) => Some(("structured.array", items.len())),
+            _ => None,
+        };
+        documents.push(serde_yaml::to_string(&value).map_err(to_error)?);
+    }
+    if documents.len() > 1 {
+        container = Some(("structured.documents", documents.len()));
+    }
+    Ok((documents.join("---\n").trim_end().to_string(), shape(container)))
+}
+
+/// The formatted document or its syntax error, or `None` when the action
+/// isn't `format_data` or the text is neither JSON nor YAML.
+pub fn local_result(action_id: &str, text: &str) -> Option<ActionResult> {
+    if action_id != FORMAT_ACTION {
+        return None;
+    }
+    let text = syntax::straighten_quotes(text.trim());
+    let kind = syntax::detect(&text)?;
+    let formatted = match kind {
+        Kind::Json => format_json(&text),
+        Kind::Yaml => format_yaml(&text),
+    };
+    let (body, note) = match formatted {
+        Ok((document, shape)) => {
+            log::info!("[STRUCTURED] Formatted {} ({} chars)", kind.name(), document.len());
+            let summary = tf("structured.valid", &[("format", kind.name()), ("shape", shape.as_str())]);
+            (format!("{}\n\n```{}\n{}\n```", summary, kind.name().to_lowercase(), document), summary)
+        }
+        Err(error) => {
+            log::info!("[STRUCTURED] Invalid {} at {}:{}", kind.name(), error.line, error.column);
+            let (line, column) = (error.line.to_string(), error.column.to_string());
+            let summary = tf(
+                "structured.invalid",
+                &[("format", kind.name()), ("line", line.as_str()), ("column", column.as_str()), ("error", error.message.as_str())],
+            );
+            let context = syntax::error_line(&text, &error).map(|line| format!("\n\n`{}`", line.replace('`', "'")));
+            (format!("{}{}", summary, context.unwrap_or_default()), summary)
+        }
+    };
+    Some(ActionResult {
+        status: "success".to_string(),
+        action_id: action_id.to_string(),
+        result: ActionResultBody {
+            result_type: "text".to_string(),
+            text: Some(body),
+            file_path: None,
+            command: None,
+            clipboard_content: None,
+            mime_type: None,
+            email: None,
+        },
+        metadata: Some(ActionResultMetadata { processing_note: Some(note), ..ActionResultMetadata::default() }),
+    })
+}
@@ -0,0 +1,166 @@
+//! JSON and YAML recognition, JSON re-indenting, and error positions
+//! (pure, no I/O).
+//!
+//! JSON is re-indented token by token rather than through
+//! `serde_json::Value`, so key order, number spelling (`1.0`, `1e3`,
+//! 20-digit ids), and escapes stay exactly as snipped. YAML is only
+//! recognized here; `mod.rs` formats it through `serde_yaml`.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Json,
+    Yaml,
+}
+
+impl Kind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Json => "JSON",
+            Kind::Yaml => "YAML",
+        }
+    }
+}
+
+/// Where parsing failed, 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+const INDENT: &str = "  ";
+
+/// `key:`, `- item`, or a comment — the lines a YAML document is made of.
+static YAML_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*(?:-(?:\s|$)|#|---|\.\.\.|[\w"'./$-][^:#]*:(?:\s|$))"#).unwrap());
+/// A key that opens a nested block (`spec:` with nothing after it).
+static YAML_BLOCK_KEY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^(\s*)(?:- )?[\w"'./$-][^:#]*:\s*$"#).unwrap());
+
+/// OCR turns straight quotes into typographic ones.
+pub fn straighten_quotes(text: &str) -> String {
+    text.replace(['“', '”', '„', '″'], "\"").replace(['‘', '’', '′'], "'")
+}
+
+/// Whether `text` looks like a JSON or YAML document (valid or not, so
+/// broken documents still get their errors reported).
+pub fn detect(text: &str) -> Option<Kind> {
+    let trimmed = text.trim();
+    let wrapped = |open: char, close: char| trimmed.starts_with(open) && trimmed.ends_with(close);
+    if wrapped('{', '}') || (wrapped('[', ']') && (trimmed.contains('"') || trimmed.contains(','))) {
+        return Some(Kind::Json);
+    }
+    let lines: Vec<&str> = trimmed.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() < 2 {
+        return None;
+    }
+    let yaml_lines = lines.iter().filter(|l| YAML_LINE.is_match(l)).count();
+    let nested = lines.windows(2).any(|pair| {
+        YAML_BLOCK_KEY.captures(pair[0]).is_some_and(|caps| {
+            let indent = caps[1].len();
+            let next = pair[1].len() - pair[1].trim_start().len();
+            next > indent || (next == indent && pair[1].trim_start().starts_with("- "))
+        })
+    });
+    let ok = (nested || lines[0].trim() == "---") && yaml_lines * 5 >= lines.len() * 4;
+    ok.then_some(Kind::Yaml)
+}
+
+/// `text` (already validated as JSON) with two-space indentation.
+pub fn reindent_json(text: &str) -> String {
+    let chars: Vec<char> = text.trim().chars().collect();
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&INDENT.repeat(depth));
+    };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                let close = if c == '{' { '}' } else { ']' };
+                let next = chars[i..].iter().position(|c| !c.is_whitespace()).map(|p| i + p);
+                if let Some(next) = next.filter(|n| chars[*n] == close) {
+                    out.push(c);
+                    out.push(close);
+                    i = next + 1;
+                } else {
+                    out.push(c);
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// The snipped line an error points at, trimmed for display.
+pub fn error_line(text: &str, error: &SyntaxError) -> Option<String> {
+    let line = text.lines().nth(error.line.checked_sub(1)?)?.trim();
+    (!line.is_empty()).then(|| line.chars().take(120).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_documents() {
+        assert_eq!(detect(r#"{"a": 1, "b": [1, 2]}"#), Some(Kind::Json));
+        assert_eq!(detect(r#"{"a": 1 "b": 2}"#), Some(Kind::Json));
+        assert_eq!(detect("[1, 2, 3]"), Some(Kind::Json));
+        assert_eq!(detect("[1] footnote"), None);
+        assert_eq!(detect("server:\n  port: 8080\n  hosts:\n    - a\n    - b"), Some(Kind::Yaml));
+        assert_eq!(detect("steps:\n- run: make\n- run: test"), Some(Kind::Yaml));
+        assert_eq!(detect("---\nname: x\nversion: 2"), Some(Kind::Yaml));
+        assert_eq!(detect("Name: Jane\nTotal: $5"), None);
+        assert_eq!(detect("Note:\n  This is a paragraph of prose that goes on.\n  And on, without keys at all here."), None);
+        assert_eq!(straighten_quotes("{“a”: ‘b’}"), "{\"a\": 'b'}");
+    }
+
+    #[test]
+    fn reindents_json_exactly() {
+        let json = r#"{"id":12345678901234567890,"tags":[],"nested":{"s":"a,b:{c}\"]","n":1.50},"list":[1,{}]}"#;
+        assert_eq!(
+            reindent_json(json),
+            "{\n  \"id\": 12345678901234567890,\n  \"tags\": [],\n  \"nested\": {\n    \"s\": \"a,b:{c}\\\"]\",\n    \"n\": 1.50\n  },\n  \"list\": [\n    1,\n    {}\n  ]\n}"
+        );
+        let error = SyntaxError { line: 2, column: 3, message: String::new() };
+        assert_eq!(error_line("{\n  \"a\" 1\n}", &error).as_deref(), Some("\"a\" 1"));
+    }
+}
@@ -0,0 +1,157 @@
+//! Thumbnail service — small previews of snips for history, the tray's
+//! recent snips, and pins.
+//!
+//! The full-screen capture is dropped as soon as a snip is cropped
+//! (`capture::CaptureState::release`); what is kept of the image is a
+//! set of lossless WebP thumbnails of the crop in
+//! `~/.config/omni-glass/thumbnails/`, one file per size
+//! (`variant.rs`). `queue` hands the crop to a background thread, so
+//! `process_snip` never waits on encoding. `load` serves a size, making
+//! it from the `Large` source on first request; cached sizes beyond the
+//! cache budget are evicted least recently used first. Files of records
+//! that are gone are swept after each recorded snip and shredded with
+//! the rest of history on purge. Webviews load them from
+//! `omni-image://localhost/thumbnail-<id>-<size>`.
+
+pub mod variant;
+
+use image::DynamicImage;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+use variant::{Cached, File, Size};
+
+/// Disk space for cached `Small` / `Medium` thumbnails.
+const CACHE_BUDGET: u64 = 16 * 1024 * 1024;
+/// Sizes made as soon as a snip is recorded; the rest wait for a request.
+const EAGER: [Size; 2] = [Size::Large, Size::Medium];
+
+/// A snip to make thumbnails of, and what to do once they are made.
+type Job = (u64, DynamicImage, Box<dyn FnOnce() + Send>);
+
+static QUEUE: OnceLock<Sender<Job>> = OnceLock::new();
+
+fn dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("omni-glass").join("thumbnails"))
+}
+
+fn write(id: u64, size: Size, bytes: &[u8]) -> Result<(), String> {
+    let dir = dir().ok_or("Could not determine config directory")?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(variant::file_name(id, size)), bytes).map_err(|e| e.to_string())
+}
+
+fn generate(id: u64, image: &DynamicImage) {
+    let start = std::time::Instant::now();
+    for size in EAGER {
+        if let Err(e) = variant::encode(image, size).and_then(|bytes| write(id, size, &bytes)) {
+            log::warn!("[THUMBS] Failed to save {} thumbnail of snip #{}: {}", size.name(), id, e);
+            return;
+        }
+    }
+    log::debug!("[THUMBS] Snip #{} thumbnails in {}ms", id, start.elapsed().as_millis());
+    evict();
+}
+
+/// Make the thumbnails of snip `id` in the background, then call `done`
+/// (also when they failed). Failures are logged, never surfaced.
+pub fn queue(id: u64, image: DynamicImage, done: impl FnOnce() + Send + 'static) {
+    let sender = QUEUE.get_or_init(|| {
+        let (sender, jobs) = mpsc::channel::<Job>();
+        let spawned = std::thread::Builder::new().name("thumbnails".to_string()).spawn(move || {
+            for (id, image, done) in jobs {
+                generate(id, &image);
+                done();
+            }
+        });
+        if let Err(e) = spawned {
+            log::warn!("[THUMBS] Failed to start thumbnail thread: {}", e);
+        }
+        sender
+    });
+    if sender.send((id, image, Box::new(done))).is_err() {
+        log::warn!("[THUMBS] Thumbnail thread is gone; snip #{} has no thumbnail", id);
+    }
+}
+
+/// Mark a cached file as just used, for eviction.
+fn touch(path: &Path) {
+    let _ = std::fs::File::options().write(true).open(path).and_then(|f| f.set_modified(SystemTime::now()));
+}
+
+/// The source image of snip `id`: its `Large` thumbnail, or the JPEG of
+/// an older version.
+fn source(id: u64) -> Option<DynamicImage> {
+    let dir = dir()?;
+    [variant::file_name(id, Size::Large), variant::legacy_name(id)]
+        .iter()
+        .find_map(|name| std::fs::read(dir.join(name)).ok())
+        .and_then(|bytes| image::load_from_memory(&bytes).ok())
+}
+
+/// The WebP thumbnail of snip `id` at `size`, made from its source if it
+/// isn't cached. `None` if the snip has no thumbnail (yet).
+pub fn load(id: u64, size: Size) -> Option<Vec<u8>> {
+    let path = dir()?.join(variant::file_name(id, size));
+    if let Ok(bytes) = std::fs::read(&path) {
+        touch(&path);
+        return Some(bytes);
+    }
+    let bytes = variant::encode(&source(id)?, size).ok()?;
+    if let Err(e) = write(id, size, &bytes) {
+        log::warn!("[THUMBS] Failed to cache {} thumbnail of snip #{}: {}", size.name(), id, e);
+    }
+    evict();
+    Some(bytes)
+}
+
+fn files() -> Vec<(PathBuf, Option<File>)> {
+    let Some(entries) = dir().and_then(|d| std::fs::read_dir(d).ok()) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|entry| (entry.path(), variant::parse_file_name(&entry.file_name().to_string_lossy())))
+        .collect()
+}
+
+/// Delete cached sizes over `CACHE_BUDGET`, least recently used first.
+fn evict() {
+    let Some(dir) = dir() else { return };
+    let cached: Vec<Cached> = files()
+        .into_iter()
+        .filter_map(|(path, file)| {
+            let Some(File::Variant(id, size)) = file else { return None };
+            let meta = std::fs::metadata(path).ok()?;
+            Some(Cached { id, size, bytes: meta.len(), used: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH) })
+        })
+        .collect();
+    let evicted = variant::evict(&cached, CACHE_BUDGET);
+    for (id, size) in &evicted {
+        let _ = std::fs::remove_file(dir.join(variant::file_name(*id, *size)));
+    }
+    if !evicted.is_empty() {
+        log::debug!("[THUMBS] Evicted {} cached thumbnails", evicted.len());
+    }
+}
+
+/// Delete thumbnails of records no longer in `kept`; returns how many files.
+pub fn sweep(kept: &HashSet<u64>) -> usize {
+    let stale: Vec<PathBuf> = files()
+        .into_iter()
+        .filter(|(_, file)| file.is_some_and(|f| !kept.contains(&f.id())))
+        .map(|(path, _)| path)
+        .collect();
+    for path in &stale {
+        let _ = std::fs::remove_file(path);
+    }
+    stale.len()
+}
+
+/// Overwrite and delete every thumbnail (history purge).
+pub fn purge() -> Result<(), String> {
+    for (path, _) in files() {
+        crate::persist::shred(&path)?;
+    }
+    Ok(())
+}
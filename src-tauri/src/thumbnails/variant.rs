@@ -0,0 +1,149 @@
+//! Thumbnail sizes, file names, encoding, and eviction (pure, no I/O).
+//!
+//! Each snip has a `Large` thumbnail, the source the other sizes are
+//! derived from, kept as long as its history record. `Medium` and
+//! `Small` are a cache: made on first request, and the least recently
+//! used go once they add up to more than the cache budget.
+
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Size {
+    /// Tray menu icons.
+    Small,
+    /// History list.
+    Medium,
+    /// Pins and hover previews; the source of the other sizes.
+    Large,
+}
+
+impl Size {
+    /// Longest side, in pixels.
+    pub fn pixels(self) -> u32 {
+        match self {
+            Size::Small => 64,
+            Size::Medium => 240,
+            Size::Large => 480,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Size::Small => "small",
+            Size::Medium => "medium",
+            Size::Large => "large",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        [Size::Small, Size::Medium, Size::Large].into_iter().find(|s| s.name() == name)
+    }
+}
+
+/// What a file in the thumbnails directory holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum File {
+    Variant(u64, Size),
+    /// `<id>.jpg` from before size variants; used as the source until the
+    /// record goes.
+    Legacy(u64),
+}
+
+impl File {
+    pub fn id(self) -> u64 {
+        match self {
+            File::Variant(id, _) | File::Legacy(id) => id,
+        }
+    }
+}
+
+pub fn file_name(id: u64, size: Size) -> String {
+    format!("{}-{}.webp", id, size.name())
+}
+
+pub fn legacy_name(id: u64) -> String {
+    format!("{}.jpg", id)
+}
+
+pub fn parse_file_name(name: &str) -> Option<File> {
+    if let Some(id) = name.strip_suffix(".jpg") {
+        return id.parse().ok().map(File::Legacy);
+    }
+    let (id, size) = name.strip_suffix(".webp")?.split_once('-')?;
+    Some(File::Variant(id.parse().ok()?, Size::parse(size)?))
+}
+
+/// `image` shrunk to fit `size` square, as lossless WebP. Screen content
+/// is flat color and text, which lossless WebP keeps crisp and small.
+pub fn encode(image: &DynamicImage, size: Size) -> Result<Vec<u8>, String> {
+    let rgb = image.thumbnail(size.pixels(), size.pixels()).to_rgb8();
+    let mut out = Vec::new();
+    WebPEncoder::new_lossless(&mut out)
+        .encode(rgb.as_raw(), rgb.width(), rgb.height(), ExtendedColorType::Rgb8)
+        .map_err(|e| format!("WebP encode failed: {}", e))?;
+    Ok(out)
+}
+
+/// A cached thumbnail on disk.
+#[derive(Debug, Clone)]
+pub struct Cached {
+    pub id: u64,
+    pub size: Size,
+    pub bytes: u64,
+    /// Last served (the file's modification time).
+    pub used: SystemTime,
+}
+
+/// Cached `Small` / `Medium` thumbnails to delete, least recently used
+/// first, until the rest fit in `budget` bytes. `Large` is never evicted.
+pub fn evict(files: &[Cached], budget: u64) -> Vec<(u64, Size)> {
+    let mut cache: Vec<&Cached> = files.iter().filter(|f| f.size != Size::Large).collect();
+    cache.sort_by_key(|f| f.used);
+    let mut total: u64 = cache.iter().map(|f| f.bytes).sum();
+    cache
+        .into_iter()
+        .take_while(|f| {
+            let over = total > budget;
+            total = total.saturating_sub(f.bytes);
+            over
+        })
+        .map(|f| (f.id, f.size))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn names_round_trip_and_sizes_fit() {
+        assert_eq!(parse_file_name(&file_name(42, Size::Small)), Some(File::Variant(42, Size::Small)));
+        assert_eq!(parse_file_name(&legacy_name(7)), Some(File::Legacy(7)));
+        assert_eq!(parse_file_name("42-huge.webp"), None);
+        assert_eq!(parse_file_name(".DS_Store"), None);
+
+        let image = DynamicImage::ImageRgba8(image::RgbaImage::new(1200, 300));
+        for (size, dims) in [(Size::Small, (64, 16)), (Size::Medium, (240, 60)), (Size::Large, (480, 120))] {
+            let decoded = image::load_from_memory(&encode(&image, size).unwrap()).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), dims);
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_cache_entries_only() {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let files = vec![
+            Cached { id: 1, size: Size::Large, bytes: 500, used: at(1) },
+            Cached { id: 1, size: Size::Medium, bytes: 100, used: at(5) },
+            Cached { id: 2, size: Size::Small, bytes: 40, used: at(2) },
+            Cached { id: 3, size: Size::Medium, bytes: 100, used: at(9) },
+        ];
+        assert_eq!(evict(&files, 240), vec![]);
+        assert_eq!(evict(&files, 200), vec![(2, Size::Small)]);
+        assert_eq!(evict(&files, 150), vec![(2, Size::Small), (1, Size::Medium)]);
+        assert_eq!(evict(&files, 0).len(), 3);
+    }
+}
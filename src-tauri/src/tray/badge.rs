@@ -0,0 +1,157 @@
+//! Tray icon badges (pure, no I/O).
+//!
+//! `Status` is what the app is doing; `Status::badge` picks the one badge
+//! that shows it, and `render` paints that badge into the bottom-right
+//! corner of the base icon: a dot while capturing or after an error, a
+//! turning arc while a snip or command is being read and answered, and a
+//! ring that fills up during a download.
+
+use image::{Rgba, RgbaImage};
+use std::f64::consts::TAU;
+use std::time::Instant;
+
+/// Frames of the spinner; one full turn.
+pub const SPINNER_FRAMES: usize = 8;
+/// Badge radius, as a fraction of the icon size.
+const RADIUS: f64 = 0.25;
+/// Inner edge of the spinner and progress ring, as a fraction of the radius.
+const RING_INNER: f64 = 0.45;
+/// Share of a turn the spinner's arc covers.
+const SPINNER_ARC: f64 = 0.3;
+
+const OUTLINE: Rgba<u8> = Rgba([15, 23, 42, 255]);
+const DARK: Rgba<u8> = Rgba([30, 41, 59, 255]);
+const TRACK: Rgba<u8> = Rgba([71, 85, 105, 255]);
+const BLUE: Rgba<u8> = Rgba([59, 130, 246, 255]);
+const AMBER: Rgba<u8> = Rgba([245, 158, 11, 255]);
+const GREEN: Rgba<u8> = Rgba([34, 197, 94, 255]);
+const RED: Rgba<u8> = Rgba([239, 68, 68, 255]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Badge {
+    /// Snip mode: the screen is captured, a region being chosen.
+    Capturing,
+    /// OCR or an LLM call is running; `frame` turns the arc.
+    Busy { frame: usize },
+    /// A download, percent done.
+    Progress(u8),
+    /// The last snip or action failed.
+    Error,
+}
+
+/// What the app is doing, as far as the tray icon shows it.
+#[derive(Debug, Default)]
+pub struct Status {
+    pub capturing: bool,
+    /// Snips and commands in flight.
+    pub jobs: usize,
+    /// Percent of the running download, if any.
+    pub download: Option<u8>,
+    /// The error badge shows until then.
+    pub failed_until: Option<Instant>,
+    /// Spinner frame; advanced while `animating`.
+    pub frame: usize,
+}
+
+impl Status {
+    pub const fn new() -> Self {
+        Self { capturing: false, jobs: 0, download: None, failed_until: None, frame: 0 }
+    }
+
+    /// The badge to show at `now`, most urgent first: an error, then
+    /// capturing, work in flight, and a download. `None` is the plain icon.
+    pub fn badge(&self, now: Instant) -> Option<Badge> {
+        if self.failed_until.is_some_and(|until| now < until) {
+            Some(Badge::Error)
+        } else if self.capturing {
+            Some(Badge::Capturing)
+        } else if self.jobs > 0 {
+            Some(Badge::Busy { frame: self.frame % SPINNER_FRAMES })
+        } else {
+            self.download.map(Badge::Progress)
+        }
+    }
+
+    /// Whether the spinner has to keep turning.
+    pub fn animating(&self, now: Instant) -> bool {
+        matches!(self.badge(now), Some(Badge::Busy { .. }))
+    }
+}
+
+/// Percent of a download done; 0 while the size is unknown.
+pub fn percent(done: u64, total: Option<u64>) -> u8 {
+    match total {
+        Some(total) if total > 0 => (done.min(total) * 100 / total) as u8,
+        _ => 0,
+    }
+}
+
+/// `base` with `badge` painted over its bottom-right corner.
+pub fn render(base: &RgbaImage, badge: Option<Badge>) -> RgbaImage {
+    let mut icon = base.clone();
+    let Some(badge) = badge else { return icon };
+    let radius = f64::from(icon.width().min(icon.height())) * RADIUS;
+    let (cx, cy) = (f64::from(icon.width()) - radius, f64::from(icon.height()) - radius);
+    for (x, y, pixel) in icon.enumerate_pixels_mut() {
+        let (dx, dy) = (f64::from(x) + 0.5 - cx, f64::from(y) + 0.5 - cy);
+        let distance = dx.hypot(dy);
+        if distance > radius {
+            continue;
+        }
+        // Share of a turn, clockwise from 12 o'clock
+        let turn = (dx.atan2(-dy) / TAU).rem_euclid(1.0);
+        *pixel = if distance > radius - 1.0 { OUTLINE } else { shade(badge, distance >= radius * RING_INNER, turn) };
+    }
+    icon
+}
+
+fn shade(badge: Badge, on_ring: bool, turn: f64) -> Rgba<u8> {
+    match badge {
+        Badge::Capturing => BLUE,
+        Badge::Error => RED,
+        Badge::Busy { frame } => {
+            let start = frame as f64 / SPINNER_FRAMES as f64;
+            if on_ring && (turn - start).rem_euclid(1.0) < SPINNER_ARC { AMBER } else { DARK }
+        }
+        Badge::Progress(percent) if on_ring => {
+            if turn < f64::from(percent) / 100.0 { GREEN } else { TRACK }
+        }
+        Badge::Progress(_) => DARK,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn the_most_urgent_state_wins() {
+        let now = Instant::now();
+        let mut status = Status { jobs: 1, download: Some(40), frame: 9, ..Status::new() };
+        assert_eq!(status.badge(now), Some(Badge::Busy { frame: 1 }));
+        assert!(status.animating(now));
+        status.capturing = true;
+        assert_eq!(status.badge(now), Some(Badge::Capturing));
+        status.failed_until = Some(now + Duration::from_secs(1));
+        assert_eq!(status.badge(now), Some(Badge::Error));
+        // The error fades; work done, the download shows again
+        status = Status { failed_until: Some(now), download: Some(40), ..Status::new() };
+        assert_eq!(status.badge(now), Some(Badge::Progress(40)));
+        assert_eq!(Status::new().badge(now), None);
+        assert_eq!((percent(50, Some(200)), percent(300, Some(200)), percent(5, None)), (25, 100, 0));
+    }
+
+    #[test]
+    fn badges_paint_only_the_corner() {
+        let base = RgbaImage::from_pixel(32, 32, Rgba([255, 255, 255, 255]));
+        assert_eq!(render(&base, None), base);
+        let error = render(&base, Some(Badge::Error));
+        assert_eq!(error.get_pixel(0, 0), base.get_pixel(0, 0));
+        assert_eq!(*error.get_pixel(24, 24), RED);
+        // Half done: the right side of the ring is filled, the left is not
+        let half = render(&base, Some(Badge::Progress(50)));
+        assert_eq!(*half.get_pixel(30, 24), GREEN);
+        assert_eq!(*half.get_pixel(18, 24), TRACK);
+    }
+}
@@ -0,0 +1,117 @@
+//! Tray menu item ids and labels (pure, no I/O).
+//!
+//! Every clickable item has an `Entry`; its id is what the menu event
+//! carries back, so `Entry::parse(Entry::id())` round-trips. Ids with a
+//! value (a workspace, a snip) put it after a `prefix:`.
+
+/// Characters of a snip's summary shown in Recent Snips.
+const RECENT_LABEL_CHARS: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    /// Region snip with the overlay.
+    Snip,
+    /// Snip the focused window of another app.
+    SnipWindow,
+    /// Snip a whole monitor, by 1-based index.
+    SnipMonitor(usize),
+    /// Reopen a history snip.
+    Recent(u64),
+    /// Show the last result again.
+    LastResult,
+    TypeCommand,
+    Workspace(String),
+    /// `None` is the "None" profile.
+    Profile(Option<String>),
+    PauseHistory,
+    Settings,
+    InstallUpdate,
+    Quit,
+}
+
+impl Entry {
+    pub fn id(&self) -> String {
+        match self {
+            Entry::Snip => "snip".to_string(),
+            Entry::SnipWindow => "snip_window".to_string(),
+            Entry::SnipMonitor(n) => format!("monitor:{}", n),
+            Entry::Recent(id) => format!("recent:{}", id),
+            Entry::LastResult => "last_result".to_string(),
+            Entry::TypeCommand => "type_command".to_string(),
+            Entry::Workspace(name) => format!("workspace:{}", name),
+            Entry::Profile(name) => format!("profile:{}", name.as_deref().unwrap_or_default()),
+            Entry::PauseHistory => "pause_history".to_string(),
+            Entry::Settings => "settings".to_string(),
+            Entry::InstallUpdate => "install_update".to_string(),
+            Entry::Quit => "quit".to_string(),
+        }
+    }
+
+    pub fn parse(id: &str) -> Option<Entry> {
+        if let Some((prefix, value)) = id.split_once(':') {
+            return match prefix {
+                "monitor" => value.parse().ok().filter(|n| *n >= 1).map(Entry::SnipMonitor),
+                "recent" => value.parse().ok().map(Entry::Recent),
+                "workspace" => Some(Entry::Workspace(value.to_string())),
+                "profile" => Some(Entry::Profile(Some(value.to_string()).filter(|n| !n.is_empty()))),
+                _ => None,
+            };
+        }
+        Some(match id {
+            "snip" => Entry::Snip,
+            "snip_window" => Entry::SnipWindow,
+            "last_result" => Entry::LastResult,
+            "type_command" => Entry::TypeCommand,
+            "pause_history" => Entry::PauseHistory,
+            "settings" => Entry::Settings,
+            "install_update" => Entry::InstallUpdate,
+            "quit" => Entry::Quit,
+            _ => return None,
+        })
+    }
+}
+
+/// A Recent Snips label: the summary, or else the first line of the
+/// text, shortened.
+pub fn recent_label(summary: &str, text: &str) -> String {
+    let source = if summary.trim().is_empty() { text } else { summary };
+    let line = source.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default();
+    match line.char_indices().nth(RECENT_LABEL_CHARS) {
+        Some((cut, _)) => format!("{}…", line[..cut].trim_end()),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_round_trip() {
+        let entries = [
+            Entry::Snip,
+            Entry::SnipWindow,
+            Entry::SnipMonitor(2),
+            Entry::Recent(42),
+            Entry::LastResult,
+            Entry::Workspace("Client: Acme".to_string()),
+            Entry::Profile(Some("Work".to_string())),
+            Entry::Profile(None),
+            Entry::InstallUpdate,
+        ];
+        for entry in entries {
+            assert_eq!(Entry::parse(&entry.id()), Some(entry));
+        }
+        assert_eq!(Entry::parse("monitor:0"), None);
+        assert_eq!(Entry::parse("recent:abc"), None);
+        assert_eq!(Entry::parse("unknown"), None);
+    }
+
+    #[test]
+    fn recent_labels_prefer_the_summary_and_are_shortened() {
+        assert_eq!(recent_label("Python stack trace", "Traceback..."), "Python stack trace");
+        assert_eq!(recent_label("  ", "\n  Invoice #42\nTotal"), "Invoice #42");
+        let long = recent_label("", &"word ".repeat(20));
+        assert!(long.ends_with('…') && long.chars().count() <= RECENT_LABEL_CHARS + 1);
+    }
+}
@@ -0,0 +1,126 @@
+//! The tray menu, built from current state.
+//!
+//! Rebuilt whole (`super::refresh_menu`) whenever something it shows
+//! changes: a recorded snip, the workspace, profile, provider,
+//! connectivity, pause state, locale, or a downloaded update.
+
+use super::entries::{recent_label, Entry};
+use crate::i18n::{t, tf};
+use crate::thumbnails::variant::Size;
+use tauri::image::Image as TauriImage;
+use tauri::menu::{CheckMenuItemBuilder, IconMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, Submenu, SubmenuBuilder};
+use tauri::{AppHandle, Wry};
+
+/// Snips listed under Recent Snips.
+const RECENT_SNIPS: usize = 5;
+
+fn item(app: &AppHandle, entry: Entry, label: impl AsRef<str>) -> tauri::Result<tauri::menu::MenuItem<Wry>> {
+    MenuItemBuilder::with_id(entry.id(), label).build(app)
+}
+
+fn check(app: &AppHandle, entry: Entry, label: impl AsRef<str>, checked: bool) -> tauri::Result<tauri::menu::CheckMenuItem<Wry>> {
+    CheckMenuItemBuilder::with_id(entry.id(), label).checked(checked).build(app)
+}
+
+/// Region, focused window, or one of the monitors.
+fn snip_submenu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let mut submenu = SubmenuBuilder::new(app, t("tray.snipMode"))
+        .item(&item(app, Entry::Snip, t("tray.snipRegion"))?)
+        .item(&item(app, Entry::SnipWindow, t("tray.snipWindow"))?)
+        .separator();
+    for (i, name) in crate::capture::list_monitors().iter().enumerate() {
+        let label = tf("tray.snipMonitor", &[("n", &(i + 1).to_string()), ("name", name)]);
+        submenu = submenu.item(&item(app, Entry::SnipMonitor(i + 1), label)?);
+    }
+    submenu.build()
+}
+
+/// A snip's small thumbnail as a menu icon, if it has one.
+fn thumbnail(id: u64) -> Option<TauriImage<'static>> {
+    let bytes = crate::thumbnails::load(id, Size::Small)?;
+    let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
+    let (w, h) = (rgba.width(), rgba.height());
+    Some(TauriImage::new_owned(rgba.into_raw(), w, h))
+}
+
+/// The newest snips of the active workspace, each with its thumbnail.
+fn recent_submenu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let workspace = crate::history::workspace::active();
+    let recent: Vec<(u64, String)> = crate::history::with_records(|records| {
+        records
+            .iter()
+            .rev()
+            .filter(|r| r.in_scope(Some(&workspace)))
+            .take(RECENT_SNIPS)
+            .map(|r| (r.id, recent_label(&r.summary, &r.text)))
+            .collect()
+    });
+    let mut submenu = SubmenuBuilder::new(app, t("tray.recent"));
+    if recent.is_empty() {
+        let none = MenuItemBuilder::new(t("tray.recentNone")).enabled(false).build(app)?;
+        return submenu.item(&none).build();
+    }
+    for (id, label) in recent {
+        let entry = IconMenuItemBuilder::with_id(Entry::Recent(id).id(), label);
+        let entry = match thumbnail(id) {
+            Some(icon) => entry.icon(icon),
+            None => entry,
+        };
+        submenu = submenu.item(&entry.build(app)?);
+    }
+    submenu.build()
+}
+
+/// "Provider: Claude Haiku", or why the cloud isn't used.
+fn provider_label() -> String {
+    if crate::settings::cloud_allowed().is_err() {
+        return t("tray.providerLocal");
+    }
+    let id = crate::settings_commands::resolve_provider();
+    let name = crate::llm::provider::all_providers()
+        .into_iter()
+        .find(|p| p.id == id)
+        .and_then(|p| p.name.split(" — ").next().map(str::to_string))
+        .unwrap_or(id);
+    let key = if crate::net::connectivity::check().is_err() { "tray.providerOffline" } else { "tray.provider" };
+    tf(key, &[("name", &name)])
+}
+
+/// Build the tray menu, with one checked entry per workspace and profile.
+pub fn build(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let workspaces = crate::history::workspace::current();
+    let mut submenu = SubmenuBuilder::new(app, t("tray.workspace"));
+    for name in workspaces.workspaces.keys() {
+        submenu = submenu.item(&check(app, Entry::Workspace(name.clone()), name, *name == workspaces.active)?);
+    }
+    let submenu = submenu.build()?;
+
+    let settings = crate::settings::stored();
+    let none_item = check(app, Entry::Profile(None), t("tray.profileNone"), settings.active_profile.is_none())?;
+    let mut profiles = SubmenuBuilder::new(app, t("tray.profile")).item(&none_item).separator();
+    for name in settings.profiles.keys() {
+        let active = settings.active_profile.as_ref() == Some(name);
+        profiles = profiles.item(&check(app, Entry::Profile(Some(name.clone())), name, active)?);
+    }
+    let profiles = profiles.build()?;
+
+    let provider = MenuItemBuilder::new(provider_label()).enabled(false).build(app)?;
+    let paused = crate::history::privacy::current().paused;
+    let mut menu = MenuBuilder::new(app)
+        .item(&item(app, Entry::Snip, t("tray.snip"))?)
+        .item(&snip_submenu(app)?)
+        .item(&item(app, Entry::TypeCommand, t("tray.typeCommand"))?)
+        .item(&recent_submenu(app)?)
+        .item(&item(app, Entry::LastResult, t("tray.lastResult"))?)
+        .separator()
+        .item(&submenu)
+        .item(&profiles)
+        .item(&provider)
+        .item(&check(app, Entry::PauseHistory, t("tray.pauseHistory"), paused)?)
+        .item(&item(app, Entry::Settings, t("tray.settings"))?)
+        .separator();
+    if let Some(version) = crate::updater::ready_version() {
+        menu = menu.item(&item(app, Entry::InstallUpdate, tf("tray.installUpdate", &[("version", &version)]))?);
+    }
+    menu.item(&item(app, Entry::Quit, t("tray.quit"))?).build()
+}
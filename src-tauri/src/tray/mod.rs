@@ -0,0 +1,285 @@
+//! System tray setup and click handler.
+//!
+//! The tray icon is the primary entry point for Omni-Glass.
+//! Left/right-click opens a native menu (`menu.rs`) with Snip Screen, a
+//! Snip submenu (region, focused window, each monitor), Type Command,
+//! Recent Snips, Workspace and Profile switchers, the provider in use,
+//! Pause History, Settings, Install Update (when one is downloaded), and
+//! Quit. Labels follow the app locale. The menu is rebuilt whenever an
+//! event says something it shows changed (`MENU_EVENTS`).
+
+mod badge;
+pub mod entries;
+mod menu;
+pub mod status;
+
+use crate::capture::{self, CaptureError};
+use crate::deeplink::parse::Region;
+use crate::error::OmniError;
+use crate::i18n::{t, tf};
+use crate::windows::{self, spec::Window, WindowEvent};
+use entries::Entry;
+use image::DynamicImage;
+use tauri::{tray::TrayIconBuilder, AppHandle, Listener, Manager};
+
+const TRAY_ID: &str = "main";
+/// Full-screen capture file earlier versions left in the temp dir; the
+/// overlay now loads captures from memory. Shredded on history purge.
+pub const CAPTURE_FILE: &str = "omni-glass-capture.png";
+/// A screen capture taking longer than this is reported as hung.
+const CAPTURE_LIMIT: std::time::Duration = std::time::Duration::from_secs(15);
+/// Events after which the menu is rebuilt: recent snips, workspace,
+/// pause state, profile, provider, locale, and connectivity.
+const MENU_EVENTS: [&str; 7] = [
+    "history-changed",
+    "history-purged",
+    "history-paused-changed",
+    "workspace-changed",
+    "settings-changed",
+    "locale-changed",
+    "connectivity-changed",
+];
+
+/// Rebuild the tray menu. Runs on each of `MENU_EVENTS`; called directly
+/// when a workspace is created or an update is downloaded.
+pub fn refresh_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    match menu::build(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => log::warn!("[TRAY] Failed to rebuild menu: {}", e),
+    }
+}
+
+/// Sets up the system tray icon with a native menu.
+///
+/// Both left-click and right-click open the same menu:
+///   - Snip Screen  → capture flow
+///   - Snip ▸       → region, focused window, or a whole monitor
+///   - Type Command → text launcher
+///   - Recent Snips ▸ → reopen one of the last five snips
+///   - Workspace ▸  → switch the active workspace
+///   - Profile ▸    → switch profile (provider, redaction, plugins, history)
+///   - Provider     → the LLM provider in use (disabled, informational)
+///   - Pause History → incognito: record nothing until unchecked
+///   - Settings...  → settings window
+///   - Install Update → install a downloaded update and restart
+///   - Quit         → exit
+pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = menu::build(app)?;
+
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(status::icon(None))
+        .tooltip(t("tray.tooltip"))
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| crate::crash::guard(|| on_menu_event(app, event.id().as_ref())))
+        .build(app)?;
+
+    for event in MENU_EVENTS {
+        let handle = app.clone();
+        app.listen_any(event, move |_| refresh_menu(&handle));
+    }
+    status::reapply(app);
+    Ok(())
+}
+
+/// Tray menu clicks.
+fn on_menu_event(app: &AppHandle, id: &str) {
+    let Some(entry) = Entry::parse(id) else { return };
+    let done = match entry {
+        Entry::Snip => {
+            log::info!("[TRAY] Snip Screen selected");
+            let click_epoch_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as f64;
+            start_snip_mode(app, click_epoch_ms);
+            Ok(())
+        }
+        Entry::SnipWindow => {
+            log::info!("[TRAY] Snip Window selected");
+            snip_off_thread(app, snip_window);
+            Ok(())
+        }
+        Entry::SnipMonitor(n) => {
+            log::info!("[TRAY] Snip Monitor {} selected", n);
+            snip_off_thread(app, move |app| snip_monitor(app, n));
+            Ok(())
+        }
+        Entry::Recent(id) => crate::deeplink::open_history(app, id),
+        Entry::LastResult => crate::windows::result::reopen_last_result(app.clone()),
+        Entry::TypeCommand => {
+            log::info!("[TRAY] Type Command selected");
+            windows::show(app, Window::Launcher)
+        }
+        Entry::Settings => {
+            log::info!("[TRAY] Settings selected");
+            windows::show(app, Window::Settings)
+        }
+        Entry::PauseHistory => crate::history::privacy::set_paused(app, !crate::history::privacy::current().paused),
+        Entry::InstallUpdate => {
+            log::info!("[TRAY] Install Update selected");
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::updater::install(&app).await {
+                    log::error!("[TRAY] Update failed: {}", e);
+                }
+            });
+            Ok(())
+        }
+        Entry::Quit => {
+            log::info!("[TRAY] Quit selected");
+            app.exit(0);
+            Ok(())
+        }
+        Entry::Workspace(name) => crate::history::workspace::switch_to(app, &name).map(|_| ()),
+        Entry::Profile(name) => crate::settings::set_active_profile(app, name.as_deref()).map(|_| ()),
+    };
+    if let Err(e) = done {
+        log::error!("[TRAY] {}: {}", id, e);
+    }
+}
+
+/// Rebuild the tray after a crash; recreate it if it is gone.
+pub fn restart(app: &AppHandle) {
+    if app.tray_by_id(TRAY_ID).is_some() {
+        refresh_menu(app);
+    } else if let Err(e) = setup_tray(app) {
+        log::error!("[TRAY] Failed to recreate tray: {}", e);
+    }
+}
+
+/// Tell the user why nothing happened: a `refuse` capture rule matched.
+pub(crate) fn warn_refused(app: &AppHandle, blocked: &str) {
+    use tauri_plugin_notification::NotificationExt;
+    let body = tf("capture.refusedBody", &[("app", blocked)]);
+    if let Err(e) = app.notification().builder().title(t("capture.refusedTitle")).body(body).show() {
+        log::warn!("[TRAY] Failed to show capture warning: {}", e);
+    }
+}
+
+/// A snip capture, or why there is none: a refusal is explained in a
+/// notification, a capture the OS withheld in a dialog.
+pub(crate) fn checked_capture(app: &AppHandle, capture: Result<DynamicImage, CaptureError>) -> Result<DynamicImage, OmniError> {
+    if let Err(CaptureError::Refused(blocked)) = &capture {
+        warn_refused(app, blocked);
+    }
+    capture::permission::checked(app, capture)
+}
+
+/// Run a window or monitor snip on a blocking task; capturing can take
+/// a while and must not hold up the menu. Asked first (or skipped) while
+/// the screen is shared.
+fn snip_off_thread(app: &AppHandle, snip: impl FnOnce(&AppHandle) -> Result<(), String> + Send + 'static) {
+    crate::presenting::gate(app, move |app| {
+        crate::diagnostics::spans::begin();
+        crate::llm::http::preconnect();
+        crate::boot::ensure_plugins(app);
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let _watch = crate::crash::watch("capture", CAPTURE_LIMIT);
+            if let Err(e) = snip(&app) {
+                log::warn!("[TRAY] Snip failed: {}", e);
+                status::failed(&app);
+            }
+        });
+    });
+}
+
+/// Snip the focused window of another app; the menu opens under it.
+fn snip_window(app: &AppHandle) -> Result<(), String> {
+    let captured = capture::capture_focused_window(&crate::settings::current().capture);
+    let frame = captured.as_ref().ok().map(|(_, frame)| *frame);
+    let screenshot = checked_capture(app, captured.map(|(image, _)| image))?;
+    let region = Region { x: 0, y: 0, width: screenshot.width(), height: screenshot.height() };
+    // Frames are global; the primary monitor is at the origin. Windows
+    // reports them in physical pixels, the menu takes logical ones.
+    let scale = match cfg!(target_os = "windows") {
+        true => app.primary_monitor().ok().flatten().map_or(1.0, |m| m.scale_factor()),
+        false => 1.0,
+    };
+    let menu = frame.map_or((0.0, 0.0), |f| (f64::from(f.x) / scale, (f64::from(f.y) + f64::from(f.height)) / scale));
+    crate::deeplink::run_snip(app, screenshot, region, menu);
+    Ok(())
+}
+
+/// Snip the whole of monitor `n` (1-based); the menu opens mid-screen.
+fn snip_monitor(app: &AppHandle, n: usize) -> Result<(), String> {
+    let mut prefs = crate::settings::current().capture;
+    prefs.monitor = Some(n.to_string());
+    let screenshot = checked_capture(app, capture::capture_for_snip(&prefs))?;
+    let region = Region { x: 0, y: 0, width: screenshot.width(), height: screenshot.height() };
+    let middle = app.primary_monitor().ok().flatten().map(|m| m.size().to_logical::<f64>(m.scale_factor()));
+    let menu = middle.map_or((0.0, 0.0), |size| (size.width / 2.0, size.height / 2.0));
+    crate::deeplink::run_snip(app, screenshot, region, menu);
+    Ok(())
+}
+
+/// Initiates snip mode without blocking the calling (tray, shortcut, or
+/// IPC) thread.
+///
+/// The overlay window is built hidden while the screen is captured on a
+/// blocking task — hidden, so it is not in the capture. Once shown, the
+/// transparent overlay dims the live desktop as a placeholder until the
+/// screenshot arrives over `omni-image://` (`capture/protocol.rs`); it
+/// waits for `capture-ready` if it loads before the capture is stored.
+///
+/// While the screen is shared, the overlay opens only once the user
+/// confirms, or not at all in pause mode (`presenting::gate`).
+pub fn start_snip_mode(app: &AppHandle, click_epoch_ms: f64) {
+    crate::presenting::gate(app, move |app| open_overlay(app, click_epoch_ms));
+}
+
+fn open_overlay(app: &AppHandle, click_epoch_ms: f64) {
+    use crate::capture::CaptureState;
+
+    // A previous snip's capture info must not be shown for this one
+    app.state::<CaptureState>().begin();
+    status::capturing(app, true);
+    // Stages from here on make up this snip's trace (get_last_snip_trace)
+    crate::diagnostics::spans::begin();
+    // Warm the provider connection while the user selects
+    crate::llm::http::preconnect();
+    // The menu offers plugin actions; don't wait for the post-launch delay
+    crate::boot::ensure_plugins(app);
+
+    // A leftover overlay from an earlier snip is closed first
+    let overlay = match tracing::info_span!("overlay_window").in_scope(|| windows::replace(app, Window::Overlay)) {
+        Ok(window) => window,
+        Err(e) => {
+            log::error!("Failed to start snip mode: {}", e);
+            status::capturing(app, false);
+            status::failed(app);
+            return;
+        }
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _watch = crate::crash::watch("capture", CAPTURE_LIMIT);
+        if let Err(e) = capture_into_overlay(&app, &overlay, click_epoch_ms) {
+            log::error!("Failed to start snip mode: {}", e);
+            // Destroying it frees the capture and ends the capturing badge
+            let _ = overlay.destroy();
+            status::failed(&app);
+        }
+    });
+}
+
+/// Capture the chosen monitor, store it, and reveal the overlay.
+fn capture_into_overlay(app: &AppHandle, overlay: &tauri::WebviewWindow, click_epoch_ms: f64) -> Result<(), String> {
+    use crate::capture::{protocol, CaptureInfo, CaptureState};
+
+    let captured = tracing::info_span!("capture").in_scope(|| capture::capture_for_snip(&crate::settings::current().capture));
+    let screenshot = checked_capture(app, captured)?;
+
+    let info = CaptureInfo { image_url: protocol::url(protocol::Resource::Screenshot), click_epoch_ms };
+    app.state::<CaptureState>().store(screenshot, Some(info));
+    let _show = tracing::info_span!("overlay_show").entered();
+    overlay.show().map_err(|e| e.to_string())?;
+    let _ = overlay.set_focus();
+    windows::send(app, WindowEvent::CaptureReady);
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Tray icon state — the icon shows what the app is doing.
+//!
+//! Snip mode marks the icon as capturing; a snip, action, or typed
+//! command in flight turns a spinner; a plugin or update download fills
+//! a progress ring; a failed snip or action shows an error dot for a few
+//! seconds. `badge.rs` decides which badge wins and paints it; this file
+//! keeps the state and swaps the icon when the badge changes.
+
+use super::badge::{self, Badge, Status};
+use image::RgbaImage;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::image::Image as TauriImage;
+use tauri::AppHandle;
+
+/// How long the error badge stays.
+const ERROR_SHOWN: Duration = Duration::from_secs(5);
+/// Time between spinner frames.
+const SPIN_INTERVAL: Duration = Duration::from_millis(120);
+
+static STATUS: Mutex<Status> = Mutex::new(Status::new());
+/// Badge on the icon now; `None` until the first change.
+static SHOWN: Mutex<Option<Option<Badge>>> = Mutex::new(None);
+/// A spinner task is running.
+static SPINNING: AtomicBool = AtomicBool::new(false);
+static BASE: OnceLock<RgbaImage> = OnceLock::new();
+
+/// The plain tray icon, decoded once.
+pub fn base_icon() -> &'static RgbaImage {
+    BASE.get_or_init(|| {
+        let bytes = include_bytes!("../../icons/32x32.png");
+        image::load_from_memory(bytes).map(|img| img.to_rgba8()).unwrap_or_else(|e| {
+            log::error!("[TRAY] Failed to decode tray icon: {}", e);
+            RgbaImage::new(32, 32)
+        })
+    })
+}
+
+/// `base_icon` with `badge`, as a Tauri image.
+pub fn icon(badge: Option<Badge>) -> TauriImage<'static> {
+    let rgba = badge::render(base_icon(), badge);
+    let (w, h) = (rgba.width(), rgba.height());
+    TauriImage::new_owned(rgba.into_raw(), w, h)
+}
+
+/// Change the status, then show the badge it calls for.
+fn update(app: &AppHandle, change: impl FnOnce(&mut Status)) {
+    let now = Instant::now();
+    let (badge, animating) = {
+        let mut status = STATUS.lock().unwrap_or_else(|e| e.into_inner());
+        change(&mut status);
+        (status.badge(now), status.animating(now))
+    };
+    show(app, badge);
+    if animating && !SPINNING.swap(true, Ordering::SeqCst) {
+        spin(app.clone());
+    }
+}
+
+fn show(app: &AppHandle, badge: Option<Badge>) {
+    {
+        let mut shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+        if *shown == Some(badge) {
+            return;
+        }
+        *shown = Some(badge);
+    }
+    let Some(tray) = app.tray_by_id(super::TRAY_ID) else { return };
+    if let Err(e) = tray.set_icon(Some(icon(badge))) {
+        log::warn!("[TRAY] Failed to set icon: {}", e);
+    }
+}
+
+/// Put the current badge on a new tray icon (after a crash recreated it).
+pub(super) fn reapply(app: &AppHandle) {
+    *SHOWN.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    update(app, |_| {});
+}
+
+/// Turn the spinner until nothing is in flight.
+fn spin(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SPIN_INTERVAL).await;
+            let now = Instant::now();
+            let animating = STATUS.lock().unwrap_or_else(|e| e.into_inner()).animating(now);
+            if !animating {
+                SPINNING.store(false, Ordering::SeqCst);
+                // Work may have started between the check and the store
+                update(&app, |_| {});
+                return;
+            }
+            update(&app, |s| s.frame = s.frame.wrapping_add(1));
+        }
+    });
+}
+
+/// Snip mode started (`true`), or the overlay went away (`false`).
+pub fn capturing(app: &AppHandle, on: bool) {
+    update(app, |s| s.capturing = on);
+}
+
+/// `done` bytes of a download of `total` have arrived.
+pub fn downloading(app: &AppHandle, done: u64, total: Option<u64>) {
+    update(app, |s| s.download = Some(badge::percent(done, total)));
+}
+
+/// The download is over, finished or not.
+pub fn downloaded(app: &AppHandle) {
+    update(app, |s| s.download = None);
+}
+
+/// Show the error badge for a few seconds.
+pub fn failed(app: &AppHandle) {
+    update(app, |s| s.failed_until = Some(Instant::now() + ERROR_SHOWN));
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(ERROR_SHOWN).await;
+        update(&app, |_| {});
+    });
+}
+
+/// A snip or command in flight; the spinner turns until it is dropped.
+pub struct Working(AppHandle);
+
+pub fn working(app: &AppHandle) -> Working {
+    update(app, |s| s.jobs += 1);
+    Working(app.clone())
+}
+
+impl Drop for Working {
+    fn drop(&mut self) {
+        update(&self.0, |s| s.jobs = s.jobs.saturating_sub(1));
+    }
+}
+
+/// Run `job` with the spinner turning; an error shows the error badge.
+pub async fn track<T, E>(app: &AppHandle, job: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+    let result = {
+        let _working = working(app);
+        job.await
+    };
+    if result.is_err() {
+        failed(app);
+    }
+    result
+}
@@ -0,0 +1,145 @@
+//! Local trigger API — a loopback HTTP endpoint for the Stream Deck
+//! plugin and scripts.
+//!
+//! `POST /trigger/<name>` fires the input `api:<name>`; `GET /ping`
+//! checks the connection and token. Every request needs
+//! `Authorization: Bearer <token>` (kept in the keychain, shown in
+//! settings). The server listens on 127.0.0.1 only and refuses requests
+//! with an `Origin` header, so web pages cannot reach it.
+
+use super::config::valid_name;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head read; triggers carry no body.
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Runs an input; `Err` when nothing is mapped to it.
+pub type OnTrigger = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+#[derive(Debug, PartialEq)]
+pub enum Route {
+    Ping,
+    Trigger(String),
+}
+
+/// What a request head asks for, or the status and message to refuse it with.
+pub fn route(head: &str, token: &str) -> Result<Route, (u16, &'static str)> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (method, path) = (request_line.next().unwrap_or_default(), request_line.next().unwrap_or_default());
+    let mut authorized = false;
+    for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "origin" => return Err((403, "Browser requests are not accepted")),
+            "authorization" => {
+                let given = value.trim().strip_prefix("Bearer ").unwrap_or_default().trim();
+                authorized = !token.is_empty() && same(given.as_bytes(), token.as_bytes());
+            }
+            _ => {}
+        }
+    }
+    if !authorized {
+        return Err((401, "Missing or wrong token"));
+    }
+    match (method, path) {
+        ("GET", "/ping") => Ok(Route::Ping),
+        ("POST", path) => match path.strip_prefix("/trigger/") {
+            Some(name) if valid_name(name) => Ok(Route::Trigger(name.to_string())),
+            _ => Err((404, "Not found")),
+        },
+        _ => Err((404, "Not found")),
+    }
+}
+
+/// Compare without returning early, so timing does not leak the token.
+fn same(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Accept connections on `127.0.0.1:port` until the task is aborted.
+pub async fn serve(port: u16, token: String, on_trigger: OnTrigger) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Trigger API could not listen on port {}: {}", port, e))?;
+    log::info!("[TRIGGERS] API listening on 127.0.0.1:{}", port);
+    let token = Arc::new(token);
+    loop {
+        let Ok((stream, _)) = listener.accept().await else { continue };
+        let (token, on_trigger) = (token.clone(), on_trigger.clone());
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, &token, &on_trigger).await {
+                log::warn!("[TRIGGERS] API request failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream, token: &str, on_trigger: &OnTrigger) -> std::io::Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_HEAD_BYTES {
+        let n = match tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buf)).await {
+            Ok(read) => read?,
+            Err(_) => return Ok(()),
+        };
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    let (status, body) = match route(&String::from_utf8_lossy(&head), token) {
+        Ok(Route::Ping) => (200, serde_json::json!({ "ok": true })),
+        Ok(Route::Trigger(name)) => match on_trigger(&format!("api:{}", name)) {
+            Ok(()) => (200, serde_json::json!({ "ok": true })),
+            Err(e) => (404, serde_json::json!({ "ok": false, "error": e })),
+        },
+        Err((status, message)) => (status, serde_json::json!({ "ok": false, "error": message })),
+    };
+    let body = body.to_string();
+    let reason = match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        _ => "Not Found",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_authorized_requests() {
+        let head = |line: &str, auth: &str| format!("{}\r\nHost: 127.0.0.1\r\nAuthorization: {}\r\n\r\n", line, auth);
+        assert_eq!(route(&head("GET /ping HTTP/1.1", "Bearer s3cret"), "s3cret"), Ok(Route::Ping));
+        assert_eq!(
+            route(&head("POST /trigger/deck-1 HTTP/1.1", "Bearer s3cret"), "s3cret"),
+            Ok(Route::Trigger("deck-1".to_string()))
+        );
+        assert_eq!(route(&head("POST /trigger/Deck HTTP/1.1", "Bearer s3cret"), "s3cret"), Err((404, "Not found")));
+        assert_eq!(route(&head("GET /trigger/deck-1 HTTP/1.1", "Bearer s3cret"), "s3cret"), Err((404, "Not found")));
+    }
+
+    #[test]
+    fn refuses_bad_tokens_and_browsers() {
+        let ping = "GET /ping HTTP/1.1\r\nAuthorization: Bearer wrong\r\n\r\n";
+        assert_eq!(route(ping, "s3cret").unwrap_err().0, 401);
+        assert_eq!(route("GET /ping HTTP/1.1\r\n\r\n", "s3cret").unwrap_err().0, 401);
+        assert_eq!(route("GET /ping HTTP/1.1\r\nAuthorization: Bearer \r\n\r\n", "").unwrap_err().0, 401);
+        let browser = "POST /trigger/a HTTP/1.1\r\nOrigin: https://evil.example\r\nAuthorization: Bearer s3cret\r\n\r\n";
+        assert_eq!(route(browser, "s3cret").unwrap_err().0, 403);
+    }
+}
@@ -0,0 +1,191 @@
+//! Trigger settings (`triggers`), inputs, and actions (pure, no I/O).
+//!
+//! An input is `api:<name>` (a Stream Deck button or script calling the
+//! local API) or `midi:<channel>:<note>` (a MIDI note-on). `mappings`
+//! maps inputs to actions; region presets are listed in `presets` and
+//! picked by their 1-based position.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_API_PORT: u16 = 47615;
+const MAX_PRESETS: usize = 20;
+const MAX_MAPPINGS: usize = 128;
+const MAX_NAME_CHARS: usize = 40;
+/// Smallest preset side, in pixels (the overlay's minimum drag).
+const MIN_REGION_SIDE: u32 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerPrefs {
+    /// Serve the local trigger API on 127.0.0.1.
+    #[serde(default)]
+    pub api_enabled: bool,
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+    /// MIDI input port to listen on, by name; `None` listens to none.
+    #[serde(default)]
+    pub midi_device: Option<String>,
+    #[serde(default)]
+    pub presets: Vec<RegionPreset>,
+    /// Input id → action.
+    #[serde(default)]
+    pub mappings: BTreeMap<String, String>,
+}
+
+impl Default for TriggerPrefs {
+    fn default() -> Self {
+        Self {
+            api_enabled: false,
+            api_port: DEFAULT_API_PORT,
+            midi_device: None,
+            presets: Vec::new(),
+            mappings: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_api_port() -> u16 {
+    DEFAULT_API_PORT
+}
+
+/// A saved screen region, in screenshot pixels.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionPreset {
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerAction {
+    /// `snip`: the snip overlay.
+    Snip,
+    /// `resnip_last`: capture the last snipped region again.
+    ResnipLast,
+    /// `preset:<n>`: snip region preset `n` (1-based).
+    Preset(usize),
+    /// `launcher`: the text launcher.
+    Launcher,
+}
+
+impl TriggerAction {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw {
+            "snip" => Ok(Self::Snip),
+            "resnip_last" => Ok(Self::ResnipLast),
+            "launcher" => Ok(Self::Launcher),
+            _ => match raw.strip_prefix("preset:").and_then(|n| n.parse().ok()) {
+                Some(n) if n >= 1 => Ok(Self::Preset(n)),
+                _ => Err(format!("Unknown trigger action '{}'", raw)),
+            },
+        }
+    }
+}
+
+/// Whether `input` is a well-formed input id.
+pub fn valid_input(input: &str) -> bool {
+    match input.split(':').collect::<Vec<_>>()[..] {
+        ["api", name] => valid_name(name),
+        ["midi", channel, note] => {
+            channel.parse::<u8>().is_ok_and(|c| (1..=16).contains(&c)) && note.parse::<u8>().is_ok_and(|n| n <= 127)
+        }
+        _ => false,
+    }
+}
+
+/// API trigger names: 1-40 of `a-z`, `0-9`, `-`, `_`.
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_CHARS
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// The input id of a raw MIDI message, for note-ons only. A note-on with
+/// velocity 0 is a note-off by convention.
+pub fn midi_input(message: &[u8]) -> Option<String> {
+    match message {
+        [status, note, velocity, ..] if status & 0xF0 == 0x90 && *velocity > 0 && *note <= 127 => {
+            Some(format!("midi:{}:{}", (status & 0x0F) + 1, note))
+        }
+        _ => None,
+    }
+}
+
+impl TriggerPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.api_port < 1024 {
+            return Err("Trigger API port must be 1024 or higher".to_string());
+        }
+        if self.presets.len() > MAX_PRESETS {
+            return Err(format!("At most {} region presets", MAX_PRESETS));
+        }
+        for preset in &self.presets {
+            if preset.name.trim().is_empty() || preset.name.chars().count() > MAX_NAME_CHARS {
+                return Err(format!("Preset names must be 1-{} characters", MAX_NAME_CHARS));
+            }
+            if preset.width < MIN_REGION_SIDE || preset.height < MIN_REGION_SIDE {
+                return Err(format!("Preset '{}' must be at least {1}x{1} pixels", preset.name, MIN_REGION_SIDE));
+            }
+        }
+        if self.mappings.len() > MAX_MAPPINGS {
+            return Err(format!("At most {} trigger mappings", MAX_MAPPINGS));
+        }
+        for (input, action) in &self.mappings {
+            if !valid_input(input) {
+                return Err(format!("Invalid trigger input '{}' (api:<name> or midi:<channel>:<note>)", input));
+            }
+            if let TriggerAction::Preset(n) = TriggerAction::parse(action)? {
+                if n > self.presets.len() {
+                    return Err(format!("'{}' maps to preset {}, but only {} exist", input, n, self.presets.len()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The action mapped to `input`, if any.
+    pub fn action_for(&self, input: &str) -> Option<TriggerAction> {
+        self.mappings.get(input).and_then(|a| TriggerAction::parse(a).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_and_midi_messages() {
+        assert!(valid_input("api:deck-1") && valid_input("midi:1:60") && valid_input("midi:16:127"));
+        for bad in ["api:", "api:Deck", "midi:0:60", "midi:17:60", "midi:1:128", "midi:1", "key:a"] {
+            assert!(!valid_input(bad), "{}", bad);
+        }
+        assert_eq!(midi_input(&[0x90, 60, 100]), Some("midi:1:60".to_string()));
+        assert_eq!(midi_input(&[0x9F, 36, 1]), Some("midi:16:36".to_string()));
+        assert_eq!(midi_input(&[0x90, 60, 0]), None);
+        assert_eq!(midi_input(&[0x80, 60, 64]), None);
+        assert_eq!(midi_input(&[0xB0, 7, 127]), None);
+    }
+
+    #[test]
+    fn validation_and_actions() {
+        let preset = RegionPreset { name: "Chart".into(), x: 0, y: 0, width: 400, height: 300 };
+        let mut prefs = TriggerPrefs { presets: vec![preset], ..TriggerPrefs::default() };
+        prefs.mappings.insert("midi:1:60".into(), "preset:1".into());
+        prefs.mappings.insert("api:again".into(), "resnip_last".into());
+        assert!(prefs.validate().is_ok());
+        assert_eq!(prefs.action_for("midi:1:60"), Some(TriggerAction::Preset(1)));
+        assert_eq!(prefs.action_for("api:again"), Some(TriggerAction::ResnipLast));
+        assert_eq!(prefs.action_for("api:other"), None);
+        prefs.mappings.insert("midi:1:61".into(), "preset:2".into());
+        assert!(prefs.validate().unwrap_err().contains("only 1"));
+        prefs.mappings.insert("midi:1:61".into(), "preset:0".into());
+        assert!(prefs.validate().is_err());
+        prefs.mappings.remove("midi:1:61");
+        prefs.api_port = 80;
+        assert!(prefs.validate().is_err());
+    }
+}
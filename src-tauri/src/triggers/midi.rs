@@ -0,0 +1,56 @@
+//! MIDI input — note-ons from a pad controller or keyboard as triggers.
+//!
+//! One input port is open at a time, chosen by name in settings
+//! (`triggers.midiDevice`). The connection lives until `disconnect` or
+//! the next `connect`; messages arrive on midir's thread.
+
+use super::config::midi_input;
+use std::sync::Mutex;
+
+const CLIENT_NAME: &str = "Omni-Glass";
+
+static CONNECTION: Mutex<Option<(String, midir::MidiInputConnection<()>)>> = Mutex::new(None);
+
+/// Names of the MIDI input ports present now.
+pub fn devices() -> Result<Vec<String>, String> {
+    let input = midir::MidiInput::new(CLIENT_NAME).map_err(|e| format!("MIDI unavailable: {}", e))?;
+    Ok(input.ports().iter().filter_map(|p| input.port_name(p).ok()).collect())
+}
+
+/// The port listened on, if connected.
+pub fn connected() -> Option<String> {
+    CONNECTION.lock().unwrap_or_else(|e| e.into_inner()).as_ref().map(|(name, _)| name.clone())
+}
+
+/// Listen on the port named `device`; `on_input` gets each note's input id.
+pub fn connect(device: &str, on_input: impl Fn(String) + Send + 'static) -> Result<(), String> {
+    disconnect();
+    let input = midir::MidiInput::new(CLIENT_NAME).map_err(|e| format!("MIDI unavailable: {}", e))?;
+    let port = input
+        .ports()
+        .into_iter()
+        .find(|p| input.port_name(p).is_ok_and(|name| name == device))
+        .ok_or_else(|| format!("MIDI device '{}' is not connected", device))?;
+    let connection = input
+        .connect(
+            &port,
+            "omni-glass-triggers",
+            move |_, message, _| {
+                if let Some(id) = midi_input(message) {
+                    on_input(id);
+                }
+            },
+            (),
+        )
+        .map_err(|e| format!("Could not open MIDI device '{}': {}", device, e))?;
+    log::info!("[TRIGGERS] Listening to MIDI device '{}'", device);
+    *CONNECTION.lock().unwrap_or_else(|e| e.into_inner()) = Some((device.to_string(), connection));
+    Ok(())
+}
+
+pub fn disconnect() {
+    if let Some((name, connection)) = CONNECTION.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        connection.close();
+        log::info!("[TRIGGERS] Closed MIDI device '{}'", name);
+    }
+}
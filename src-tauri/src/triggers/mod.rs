@@ -0,0 +1,230 @@
+//! Triggers domain — Stream Deck buttons and MIDI notes as snip actions.
+//!
+//! External inputs (`api:<name>` from the local API in `api.rs`,
+//! `midi:<channel>:<note>` from `midi.rs`) are mapped in settings
+//! (`triggers`, see `config.rs`) to actions: the snip overlay, a region
+//! preset, the last snipped region again, or the text launcher. Settings
+//! changes restart the API and reconnect MIDI (`apply`). An input with no
+//! mapping can be captured instead (`learn_trigger`) to build a mapping.
+
+pub mod api;
+pub mod config;
+mod midi;
+
+use crate::deeplink::parse::Region;
+use config::{RegionPreset, TriggerAction};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+const KEYCHAIN_SERVICE: &str = "omni-glass-triggers";
+const TOKEN_ACCOUNT: &str = "api-token";
+const DEFAULT_LEARN_SECS: u64 = 10;
+
+/// The last region snipped, for `resnip_last`.
+static LAST_REGION: Mutex<Option<Region>> = Mutex::new(None);
+static API_TASK: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+/// Set while `learn_trigger` waits for an input.
+static LEARN: Mutex<Option<oneshot::Sender<String>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerStatus {
+    /// Base URL of the local API while it is enabled.
+    pub api_url: Option<String>,
+    /// Bearer token for the local API.
+    pub api_token: Option<String>,
+    pub midi_devices: Vec<String>,
+    pub midi_connected: Option<String>,
+    /// Whether there is a region for `resnip_last` / `save_region_preset`.
+    pub has_last_region: bool,
+}
+
+/// Start the API and MIDI per settings. Called once from `setup`.
+pub fn attach(app: &tauri::AppHandle) {
+    apply(app);
+}
+
+/// Note a snipped region (screenshot pixels) for `resnip_last`.
+pub fn remember_region(x: u32, y: u32, width: u32, height: u32) {
+    *LAST_REGION.lock().unwrap_or_else(|e| e.into_inner()) = Some(Region { x, y, width, height });
+}
+
+/// The last snipped region, if any (Find Similar on Screen skips it).
+pub fn last_region() -> Option<Region> {
+    *LAST_REGION.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// (Re)start the local API and MIDI listener from the current settings.
+pub fn apply(app: &tauri::AppHandle) {
+    let prefs = crate::settings::current().triggers;
+    if let Some(task) = API_TASK.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        task.abort();
+    }
+    if prefs.api_enabled {
+        match api_token() {
+            Ok(token) => {
+                let handle = app.clone();
+                let on_trigger: api::OnTrigger = Arc::new(move |input| on_input(&handle, input));
+                let task = tauri::async_runtime::spawn(async move {
+                    if let Err(e) = api::serve(prefs.api_port, token, on_trigger).await {
+                        log::warn!("[TRIGGERS] {}", e);
+                    }
+                });
+                *API_TASK.lock().unwrap_or_else(|e| e.into_inner()) = Some(task);
+            }
+            Err(e) => log::warn!("[TRIGGERS] API not started: {}", e),
+        }
+    }
+    midi::disconnect();
+    if let Some(device) = prefs.midi_device.as_deref() {
+        let handle = app.clone();
+        if let Err(e) = midi::connect(device, move |input| {
+            if let Err(e) = on_input(&handle, &input) {
+                log::info!("[TRIGGERS] {}", e);
+            }
+        }) {
+            log::warn!("[TRIGGERS] {}", e);
+        }
+    }
+}
+
+/// Run the action mapped to `input`, or hand the input to a waiting
+/// `learn_trigger`. `Err` when nothing is mapped.
+fn on_input(app: &tauri::AppHandle, input: &str) -> Result<(), String> {
+    if let Some(learner) = LEARN.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        let _ = learner.send(input.to_string());
+        return Ok(());
+    }
+    let prefs = crate::settings::current().triggers;
+    let action = prefs.action_for(input).ok_or_else(|| format!("No action mapped to {}", input))?;
+    log::info!("[TRIGGERS] {} → {:?}", input, action);
+    // Windows and captures start on the main thread, as from the tray
+    let handle = app.clone();
+    app.run_on_main_thread(move || {
+        if let Err(e) = run(&handle, action, &prefs.presets) {
+            log::warn!("[TRIGGERS] {:?} failed: {}", action, e);
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Run `action` (also for launcher palette actions).
+pub(crate) fn run(app: &tauri::AppHandle, action: TriggerAction, presets: &[RegionPreset]) -> Result<(), String> {
+    match action {
+        TriggerAction::Snip => {
+            let now_ms = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            crate::tray::start_snip_mode(app, now_ms.as_millis() as f64);
+            Ok(())
+        }
+        TriggerAction::ResnipLast => {
+            let region = (*LAST_REGION.lock().unwrap_or_else(|e| e.into_inner())).ok_or("Nothing snipped yet")?;
+            crate::deeplink::snip_region(app, region)
+        }
+        TriggerAction::Preset(n) => {
+            let p = presets.get(n - 1).ok_or_else(|| format!("No region preset {}", n))?;
+            crate::deeplink::snip_region(app, Region { x: p.x, y: p.y, width: p.width, height: p.height })
+        }
+        TriggerAction::Launcher => crate::windows::show(app, crate::windows::spec::Window::Launcher),
+    }
+}
+
+/// The local API token, created on first use.
+fn api_token() -> Result<String, String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, TOKEN_ACCOUNT).map_err(|e| format!("Keychain error: {}", e))?;
+    match entry.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => {
+            let token = new_token();
+            entry.set_password(&token).map_err(|e| format!("Failed to save trigger token: {}", e))?;
+            Ok(token)
+        }
+        Err(e) => Err(format!("Keychain error: {}", e)),
+    }
+}
+
+/// 192 random bits as hex. `RandomState` keys come from the OS RNG.
+fn new_token() -> String {
+    use std::hash::{BuildHasher, Hasher};
+    let mut seed = Sha256::new();
+    for i in 0..4u64 {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u64(i);
+        seed.update(hasher.finish().to_le_bytes());
+    }
+    seed.update(format!("{:?}", std::time::SystemTime::now()));
+    seed.finalize()[..24].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: API address and token, MIDI devices, and whether a
+/// region can be re-snipped.
+#[tauri::command]
+pub fn get_trigger_status() -> TriggerStatus {
+    let prefs = crate::settings::current().triggers;
+    TriggerStatus {
+        api_url: prefs.api_enabled.then(|| format!("http://127.0.0.1:{}", prefs.api_port)),
+        api_token: api_token().map_err(|e| log::warn!("[TRIGGERS] {}", e)).ok(),
+        midi_devices: midi::devices().unwrap_or_else(|e| {
+            log::warn!("[TRIGGERS] {}", e);
+            Vec::new()
+        }),
+        midi_connected: midi::connected(),
+        has_last_region: LAST_REGION.lock().unwrap_or_else(|e| e.into_inner()).is_some(),
+    }
+}
+
+/// Tauri command: map `input` to `action`, or with `null` unmap it.
+#[tauri::command]
+pub fn set_trigger_mapping(app: tauri::AppHandle, input: String, action: Option<String>) -> Result<(), String> {
+    if !config::valid_input(&input) {
+        return Err(format!("Invalid trigger input '{}'", input));
+    }
+    if let Some(action) = &action {
+        TriggerAction::parse(action)?;
+    }
+    let mut mappings = serde_json::Map::new();
+    mappings.insert(input.clone(), serde_json::json!(action));
+    crate::settings::update(&app, serde_json::json!({ "triggers": { "mappings": mappings } }))?;
+    log::info!("[TRIGGERS] {} mapped to {:?}", input, action);
+    Ok(())
+}
+
+/// Tauri command: save the last snipped region as a preset. Returns its
+/// number, for `preset:<n>`.
+#[tauri::command]
+pub fn save_region_preset(app: tauri::AppHandle, name: String) -> Result<usize, String> {
+    let region = (*LAST_REGION.lock().unwrap_or_else(|e| e.into_inner())).ok_or("Snip a region first")?;
+    let mut presets = crate::settings::stored().triggers.presets;
+    let Region { x, y, width, height } = region;
+    presets.push(RegionPreset { name: name.trim().to_string(), x, y, width, height });
+    let number = presets.len();
+    crate::settings::update(&app, serde_json::json!({ "triggers": { "presets": presets } }))?;
+    Ok(number)
+}
+
+/// Tauri command: a new local API token; the Stream Deck plugin needs
+/// the new one.
+#[tauri::command]
+pub fn regenerate_trigger_token(app: tauri::AppHandle) -> Result<String, String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, TOKEN_ACCOUNT).map_err(|e| format!("Keychain error: {}", e))?;
+    let token = new_token();
+    entry.set_password(&token).map_err(|e| format!("Failed to save trigger token: {}", e))?;
+    apply(&app);
+    Ok(token)
+}
+
+/// Tauri command: wait for the next input (a MIDI note or API call) and
+/// return its id instead of running it; `null` after the timeout.
+#[tauri::command]
+pub async fn learn_trigger(timeout_secs: Option<u64>) -> Option<String> {
+    let (tx, rx) = oneshot::channel();
+    *LEARN.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+    let wait = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_LEARN_SECS).min(60));
+    let input = tokio::time::timeout(wait, rx).await.ok().and_then(Result::ok);
+    LEARN.lock().unwrap_or_else(|e| e.into_inner()).take();
+    input
+}
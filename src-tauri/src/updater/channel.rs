@@ -0,0 +1,145 @@
+//! Release channels and version rules (pure, no I/O).
+//!
+//! Each channel has its own feed in the updater JSON format. A release
+//! is offered when it is newer than the running build, unless the user
+//! skipped that exact version. The stable channel never offers
+//! pre-releases; and a beta build whose user switched back to stable is
+//! offered the latest stable release even though its number is lower —
+//! that is the one downgrade we allow, and it is marked as a rollback.
+
+use std::cmp::Ordering;
+
+pub const CHANNELS: &[&str] = &["stable", "beta"];
+
+/// Feed location (the `updates/` folder of the repository);
+/// `OMNI_GLASS_UPDATE_URL` overrides it (`{channel}` is replaced with the
+/// channel name).
+const DEFAULT_FEED_URL: &str = "https://raw.githubusercontent.com/goshtasb/OmniGlass/main/updates/{channel}.json";
+
+pub fn feed_url(channel: &str, override_url: Option<&str>) -> String {
+    override_url.unwrap_or(DEFAULT_FEED_URL).replace("{channel}", channel)
+}
+
+/// `major.minor.patch[-pre]`; build metadata is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    core: [u64; 3],
+    pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(raw: &str) -> Option<Version> {
+        let raw = raw.trim().trim_start_matches('v');
+        let raw = raw.split('+').next()?;
+        let (core, pre) = match raw.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return None,
+            None => (raw, None),
+        };
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let version = Version { core: [parts.next()??, parts.next()??, parts.next()??], pre };
+        parts.next().is_none().then_some(version)
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+}
+
+/// Semver pre-release order: numeric identifiers numerically, others
+/// lexically, numeric before alphanumeric, shorter first on a tie.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let order = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y),
+                };
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+        }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.core.cmp(&other.core).then_with(|| match (&self.pre, &other.pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => compare_pre(a, b),
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// How a remote release relates to the running build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offer {
+    None,
+    Upgrade,
+    /// Back from a pre-release to the stable channel's latest.
+    Rollback,
+}
+
+pub fn offer(current: &str, remote: &str, channel: &str, skipped: Option<&str>) -> Offer {
+    let (Some(current), Some(remote)) = (Version::parse(current), Version::parse(remote)) else {
+        return Offer::None;
+    };
+    if skipped.and_then(Version::parse).is_some_and(|s| s == remote) {
+        return Offer::None;
+    }
+    if channel == "stable" && remote.is_prerelease() {
+        return Offer::None;
+    }
+    match remote.cmp(&current) {
+        Ordering::Greater => Offer::Upgrade,
+        Ordering::Less if channel == "stable" && current.is_prerelease() => Offer::Rollback,
+        _ => Offer::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_order_like_semver() {
+        let v = |s| Version::parse(s).unwrap();
+        assert!(v("1.0.0-alpha") < v("1.0.0-alpha.1"));
+        assert!(v("1.0.0-alpha.1") < v("1.0.0-beta"));
+        assert!(v("1.0.0-beta.2") < v("1.0.0-beta.11"));
+        assert!(v("1.0.0-rc.1") < v("1.0.0"));
+        assert!(v("0.9.9") < v("v0.10.0+build.5"));
+        assert!(Version::parse("1.0").is_none());
+        assert!(Version::parse("1.0.0.0").is_none());
+        assert!(Version::parse("1.0.0-").is_none());
+    }
+
+    #[test]
+    fn offers_follow_channel_and_skip_rules() {
+        assert_eq!(offer("0.1.0", "0.2.0", "stable", None), Offer::Upgrade);
+        assert_eq!(offer("0.1.0", "0.2.0", "stable", Some("0.2.0")), Offer::None);
+        assert_eq!(offer("0.1.0", "0.2.0-beta.1", "stable", None), Offer::None);
+        assert_eq!(offer("0.1.0", "0.2.0-beta.1", "beta", None), Offer::Upgrade);
+        assert_eq!(offer("0.2.0-beta.1", "0.1.5", "stable", None), Offer::Rollback);
+        assert_eq!(offer("0.2.0-beta.1", "0.1.5", "beta", None), Offer::None);
+        assert_eq!(offer("0.2.0", "0.1.5", "stable", None), Offer::None);
+        assert_eq!(feed_url("beta", None), "https://raw.githubusercontent.com/goshtasb/OmniGlass/main/updates/beta.json");
+    }
+}
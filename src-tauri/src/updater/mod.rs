@@ -0,0 +1,204 @@
+//! In-app updates on top of `tauri-plugin-updater`.
+//!
+//! The plugin fetches the feed, verifies the minisign signature of the
+//! bundle against the build's public key, and installs it. This module
+//! adds what the plugin leaves to the app: release channels and skipped
+//! versions (rules in `channel.rs`, preferences in settings), a
+//! background check every few hours that downloads what it finds, and a
+//! notification plus a tray item once an update is ready to install.
+//!
+//! Builds made without `OMNI_GLASS_UPDATER_PUBKEY` set have no key to
+//! verify against, so updating is disabled rather than unverified.
+//! Checks and downloads use the app's proxy; the custom CA bundle does
+//! not reach the plugin's own HTTP client.
+
+pub mod channel;
+
+use channel::Offer;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+/// Minisign public key the release bundles are signed with.
+const PUBKEY: Option<&str> = option_env!("OMNI_GLASS_UPDATER_PUBKEY");
+/// Delay before the first background check, so startup stays quick.
+const FIRST_CHECK: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub notes: Option<String>,
+    pub date: Option<String>,
+    /// Going back from a pre-release to stable.
+    pub rollback: bool,
+    /// Downloaded and verified; installing needs no network.
+    pub downloaded: bool,
+}
+
+struct Downloaded {
+    update: Update,
+    bytes: Vec<u8>,
+}
+
+/// Update downloaded in the background, waiting to be installed.
+static READY: Mutex<Option<Downloaded>> = Mutex::new(None);
+
+fn info(update: &Update, downloaded: bool) -> UpdateInfo {
+    let channel = crate::settings::current().updates.channel;
+    UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        notes: update.body.clone(),
+        date: update.date.map(|d| d.to_string()),
+        rollback: channel::offer(&update.current_version, &update.version, &channel, None) == Offer::Rollback,
+        downloaded,
+    }
+}
+
+/// Version waiting to be installed, for the tray menu.
+pub fn ready_version() -> Option<String> {
+    READY.lock().unwrap_or_else(|e| e.into_inner()).as_ref().map(|d| d.update.version.clone())
+}
+
+/// The app's proxy (see `net`) for the feed, credentials included, since
+/// the updater plugin builds its own HTTP client.
+fn update_proxy(feed: &tauri::Url) -> Result<Option<tauri::Url>, String> {
+    let (proxy, bypass) = crate::net::proxy();
+    let Some(proxy) = proxy.filter(|_| !crate::net::config::bypassed(feed.host_str().unwrap_or(""), &bypass)) else {
+        return Ok(None);
+    };
+    let mut url: tauri::Url = proxy.url().parse().map_err(|e| format!("Invalid proxy: {}", e))?;
+    if let Some(user) = &proxy.username {
+        let _ = url.set_username(user);
+        let _ = url.set_password(proxy.password.as_deref());
+    }
+    Ok(Some(url))
+}
+
+/// Ask the feed for the current channel whether there is something to offer.
+async fn check(app: &tauri::AppHandle) -> Result<Option<Update>, String> {
+    let pubkey = PUBKEY.ok_or("Updates are not configured for this build")?;
+    let prefs = crate::settings::current().updates;
+    let override_url = std::env::var("OMNI_GLASS_UPDATE_URL").ok();
+    let url = channel::feed_url(&prefs.channel, override_url.as_deref());
+    let url: tauri::Url = url.parse().map_err(|e| format!("Invalid update URL: {}", e))?;
+
+    let proxy = update_proxy(&url)?;
+    let (chan, skipped) = (prefs.channel.clone(), prefs.skipped_version.clone());
+    let mut builder = app
+        .updater_builder()
+        .pubkey(pubkey)
+        .endpoints(vec![url])
+        .map_err(|e| e.to_string())?
+        .version_comparator(move |current, remote| {
+            channel::offer(&current.to_string(), &remote.version.to_string(), &chan, skipped.as_deref()) != Offer::None
+        });
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy);
+    }
+    let updater = builder.build().map_err(|e| e.to_string())?;
+    updater.check().await.map_err(|e| format!("Update check failed: {}", e))
+}
+
+/// Download and verify `update`, then hold it until the user installs.
+async fn download(app: &tauri::AppHandle, update: Update) -> Result<(), String> {
+    if ready_version().as_deref() == Some(update.version.as_str()) {
+        return Ok(());
+    }
+    log::info!("[UPDATER] Downloading {}", update.version);
+    let mut done = 0u64;
+    let progress = |chunk: usize, total: Option<u64>| {
+        done += chunk as u64;
+        crate::tray::status::downloading(app, done, total);
+    };
+    let bytes = update.download(progress, || {}).await;
+    crate::tray::status::downloaded(app);
+    let bytes = bytes.map_err(|e| format!("Update download failed: {}", e))?;
+    let version = update.version.clone();
+    *READY.lock().unwrap_or_else(|e| e.into_inner()) = Some(Downloaded { update, bytes });
+
+    crate::tray::refresh_menu(app);
+    let body = format!("Omni-Glass {} is ready. Choose \"Install Update\" in the menu bar.", version);
+    if let Err(e) = app.notification().builder().title("Update ready").body(body).show() {
+        log::warn!("[UPDATER] Failed to show notification: {}", e);
+    }
+    Ok(())
+}
+
+/// Background checks while auto-check is on. Called once from `setup`.
+pub fn spawn_checker(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_CHECK).await;
+        loop {
+            if crate::settings::current().updates.auto_check {
+                match check(&app).await {
+                    Ok(Some(update)) => {
+                        if let Err(e) = download(&app, update).await {
+                            log::warn!("[UPDATER] {}", e);
+                        }
+                    }
+                    Ok(None) => log::info!("[UPDATER] Up to date"),
+                    Err(e) => log::warn!("[UPDATER] {}", e),
+                }
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Install the downloaded update (downloading it first if needed) and
+/// restart. Only returns on failure.
+pub async fn install(app: &tauri::AppHandle) -> Result<(), String> {
+    if ready_version().is_none() {
+        let update = check(app).await?.ok_or("No update available")?;
+        download(app, update).await?;
+    }
+    let ready = READY.lock().unwrap_or_else(|e| e.into_inner()).take().ok_or("No update available")?;
+    log::info!("[UPDATER] Installing {}", ready.update.version);
+    ready.update.install(&ready.bytes).map_err(|e| format!("Update install failed: {}", e))?;
+    crate::history::disk::flush();
+    app.restart()
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: check the feed now. A found update starts downloading
+/// in the background; `downloaded` is true once it is ready.
+#[tauri::command]
+pub async fn check_for_updates(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    if let Some(ready) = READY.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Ok(Some(info(&ready.update, true)));
+    }
+    let Some(update) = check(&app).await? else { return Ok(None) };
+    let found = info(&update, false);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = download(&app, update).await {
+            log::warn!("[UPDATER] {}", e);
+        }
+    });
+    Ok(Some(found))
+}
+
+/// Tauri command: install the update and restart.
+#[tauri::command]
+pub async fn install_update(app: tauri::AppHandle) -> Result<(), String> {
+    install(&app).await
+}
+
+/// Tauri command: stop offering `version`.
+#[tauri::command]
+pub fn skip_update(app: tauri::AppHandle, version: String) -> Result<(), String> {
+    crate::settings::update(&app, serde_json::json!({ "updates": { "skippedVersion": version } }))?;
+    let mut ready = READY.lock().unwrap_or_else(|e| e.into_inner());
+    if ready.as_ref().is_some_and(|d| d.update.version == version) {
+        *ready = None;
+        drop(ready);
+        crate::tray::refresh_menu(&app);
+    }
+    Ok(())
+}
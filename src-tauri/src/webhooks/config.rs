@@ -0,0 +1,131 @@
+//! Webhook endpoints as stored in settings (`webhooks.endpoints`).
+//!
+//! Each endpoint's signing secret is not a setting; it lives in the OS
+//! keychain under the endpoint id (`mod.rs`).
+
+use serde::{Deserialize, Serialize};
+
+/// A snip was classified; its menu is ready.
+pub const SNIP_COMPLETED: &str = "snip.completed";
+/// An action finished on a snip, successfully or not.
+pub const ACTION_EXECUTED: &str = "action.executed";
+pub const EVENTS: &[&str] = &[SNIP_COMPLETED, ACTION_EXECUTED];
+
+const MAX_ENDPOINTS: usize = 10;
+const MAX_ID_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPrefs {
+    #[serde(default)]
+    pub endpoints: Vec<Webhook>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// Lowercase letters, digits, and `-`; names the keychain secret.
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    pub url: String,
+    /// Events delivered to this endpoint (`EVENTS`).
+    #[serde(default = "all_events")]
+    pub events: Vec<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Send the (redacted) snip and result text; off sends metadata only.
+    #[serde(default = "default_true")]
+    pub include_text: bool,
+}
+
+fn all_events() -> Vec<String> {
+    EVENTS.iter().map(|e| e.to_string()).collect()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Webhook {
+    /// Whether `event` should be delivered here.
+    pub fn wants(&self, event: &str) -> bool {
+        self.enabled && self.events.iter().any(|e| e == event)
+    }
+}
+
+impl WebhookPrefs {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.endpoints.len() > MAX_ENDPOINTS {
+            return Err(format!("At most {} webhooks", MAX_ENDPOINTS));
+        }
+        for (i, hook) in self.endpoints.iter().enumerate() {
+            validate_webhook(hook)?;
+            if self.endpoints[..i].iter().any(|h| h.id == hook.id) {
+                return Err(format!("Duplicate webhook id '{}'", hook.id));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Webhook> {
+        self.endpoints.iter().find(|h| h.id == id)
+    }
+}
+
+/// Payloads carry snip text, so plain `http://` is only accepted for a
+/// listener on this machine.
+fn validate_webhook(hook: &Webhook) -> Result<(), String> {
+    let id_ok = !hook.id.is_empty()
+        && hook.id.chars().count() <= MAX_ID_CHARS
+        && hook.id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !id_ok {
+        return Err(format!("Webhook id '{}' must be 1-{} of a-z, 0-9, -", hook.id, MAX_ID_CHARS));
+    }
+    let url = reqwest::Url::parse(&hook.url).map_err(|e| format!("Webhook '{}': invalid URL: {}", hook.id, e))?;
+    let loopback = matches!(url.host_str(), Some("localhost") | Some("127.0.0.1") | Some("[::1]"));
+    match url.scheme() {
+        "https" => {}
+        "http" if loopback => {}
+        _ => return Err(format!("Webhook '{}' must use https (http only for localhost)", hook.id)),
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(format!("Webhook '{}': put credentials in the secret, not the URL", hook.id));
+    }
+    if let Some(event) = hook.events.iter().find(|e| !EVENTS.contains(&e.as_str())) {
+        return Err(format!("Webhook '{}': unknown event '{}'", hook.id, event));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(id: &str, url: &str) -> Webhook {
+        serde_json::from_value(serde_json::json!({ "id": id, "url": url })).unwrap()
+    }
+
+    #[test]
+    fn defaults_and_event_filter() {
+        let mut h = hook("slack", "https://hooks.example.com/x");
+        assert!(h.wants(SNIP_COMPLETED) && h.wants(ACTION_EXECUTED) && h.include_text);
+        h.events = vec![ACTION_EXECUTED.to_string()];
+        assert!(!h.wants(SNIP_COMPLETED));
+        h.enabled = false;
+        assert!(!h.wants(ACTION_EXECUTED));
+    }
+
+    #[test]
+    fn validation() {
+        let ok = |hooks: Vec<Webhook>| WebhookPrefs { endpoints: hooks }.validate();
+        assert!(ok(vec![hook("slack", "https://hooks.example.com/x"), hook("local-bot", "http://127.0.0.1:8080/in")]).is_ok());
+        assert!(ok(vec![hook("a", "http://hooks.example.com/x")]).is_err());
+        assert!(ok(vec![hook("a", "https://user:pw@hooks.example.com/x")]).is_err());
+        assert!(ok(vec![hook("Slack Bot", "https://hooks.example.com/x")]).is_err());
+        assert!(ok(vec![hook("a", "https://a.example.com"), hook("a", "https://b.example.com")]).is_err());
+        let mut bad_event = hook("a", "https://a.example.com");
+        bad_event.events.push("snip.deleted".to_string());
+        assert!(ok(vec![bad_event]).is_err());
+    }
+}
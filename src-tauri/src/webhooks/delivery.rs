@@ -0,0 +1,111 @@
+//! Webhook retry policy and delivery log.
+//!
+//! A delivery is retried with exponential backoff on network errors,
+//! 5xx, and 429 (honouring `Retry-After`); any other 4xx means the
+//! receiver rejected the payload and retrying would not help. Each
+//! delivery's outcome is kept in a bounded log persisted to
+//! `~/.config/omni-glass/webhook-deliveries.json`, erased by a history
+//! purge.
+
+use crate::persist::JsonStore;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Attempts per delivery, the first included.
+pub const MAX_ATTEMPTS: u32 = 5;
+const BASE_DELAY: Duration = Duration::from_secs(2);
+const MAX_DELAY: Duration = Duration::from_secs(300);
+/// Entries kept (oldest dropped first).
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryEntry {
+    pub delivery_id: String,
+    pub webhook_id: String,
+    pub event: String,
+    /// `delivered`, `failed`, or `skipped`.
+    pub outcome: String,
+    /// Last HTTP status, if the receiver answered.
+    pub status: Option<u16>,
+    pub attempts: u32,
+    /// Why it failed or was skipped.
+    pub error: Option<String>,
+    /// Unix epoch seconds.
+    pub timestamp: u64,
+}
+
+/// How long to wait before attempt `attempt + 1`, or `None` to give up.
+/// `status` is `None` for a network error.
+pub fn retry_delay(attempt: u32, status: Option<u16>, retry_after: Option<Duration>) -> Option<Duration> {
+    let retryable = match status {
+        None => true,
+        Some(code) => code == 429 || (500..600).contains(&code),
+    };
+    if !retryable || attempt >= MAX_ATTEMPTS {
+        return None;
+    }
+    let backoff = BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    Some(retry_after.unwrap_or(backoff).min(MAX_DELAY))
+}
+
+static LOG: JsonStore<VecDeque<DeliveryEntry>> = JsonStore::compact("webhook-deliveries.json");
+
+/// Append a delivery's outcome.
+pub fn record(entry: DeliveryEntry) {
+    LOG.update_logged(|entries| push_bounded(entries, entry));
+}
+
+/// Forget every delivery and shred the log (history purge).
+pub fn purge() -> Result<(), String> {
+    LOG.erase(VecDeque::clear)
+}
+
+fn push_bounded(entries: &mut VecDeque<DeliveryEntry>, entry: DeliveryEntry) {
+    while entries.len() >= MAX_ENTRIES {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+/// The newest `limit` deliveries, newest last.
+pub fn recent(limit: usize) -> Vec<DeliveryEntry> {
+    LOG.read(|entries| entries.iter().skip(entries.len().saturating_sub(limit)).cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_and_give_up() {
+        assert_eq!(retry_delay(1, None, None), Some(Duration::from_secs(2)));
+        assert_eq!(retry_delay(3, Some(503), None), Some(Duration::from_secs(8)));
+        assert_eq!(retry_delay(2, Some(429), Some(Duration::from_secs(30))), Some(Duration::from_secs(30)));
+        assert_eq!(retry_delay(2, Some(429), Some(Duration::from_secs(3600))), Some(MAX_DELAY));
+        assert_eq!(retry_delay(1, Some(400), None), None);
+        assert_eq!(retry_delay(1, Some(410), None), None);
+        assert_eq!(retry_delay(MAX_ATTEMPTS, Some(500), None), None);
+    }
+
+    #[test]
+    fn log_is_bounded() {
+        let mut entries = VecDeque::new();
+        for i in 0..(MAX_ENTRIES as u64 + 3) {
+            let entry = DeliveryEntry {
+                delivery_id: i.to_string(),
+                webhook_id: "slack".into(),
+                event: "snip.completed".into(),
+                outcome: "delivered".into(),
+                status: Some(200),
+                attempts: 1,
+                error: None,
+                timestamp: i,
+            };
+            push_bounded(&mut entries, entry);
+        }
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries.front().unwrap().timestamp, 3);
+    }
+}
@@ -0,0 +1,189 @@
+//! Webhooks domain — snip events posted to team tooling (Slack bots,
+//! internal services).
+//!
+//! Endpoints are configured in settings (`webhooks.endpoints`, see
+//! `config.rs`); each endpoint's signing secret is kept in the OS
+//! keychain. `snip_completed` and `action_executed` are called from the
+//! pipeline; each matching endpoint gets a JSON payload (`payload.rs`)
+//! in the background, retried with backoff (`delivery.rs`). Payloads
+//! leave the machine, so nothing is sent in local-only or offline mode,
+//! or while history is paused; those deliveries are logged as skipped.
+
+pub mod config;
+pub mod delivery;
+pub mod payload;
+
+use crate::llm::{ActionMenu, ActionResult};
+use config::Webhook;
+use delivery::DeliveryEntry;
+use payload::Event;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const KEYCHAIN_SERVICE: &str = "omni-glass-webhooks";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Deliveries shown by `get_webhook_deliveries` without a limit.
+const DEFAULT_LOG_LIMIT: usize = 50;
+
+static NEXT_DELIVERY: AtomicU64 = AtomicU64::new(1);
+
+/// A snip was classified and its menu shown.
+pub fn snip_completed(text: &str, menu: &ActionMenu) {
+    fire(Event::SnipCompleted { text: text.to_string(), menu: menu.clone() });
+}
+
+/// An action finished on a snip.
+pub fn action_executed(snip_text: &str, result: &ActionResult) {
+    fire(Event::ActionExecuted { snip_text: snip_text.to_string(), result: Box::new(result.clone()) });
+}
+
+fn fire(event: Event) {
+    let hooks: Vec<Webhook> =
+        crate::settings::current().webhooks.endpoints.into_iter().filter(|h| h.wants(event.name())).collect();
+    for hook in hooks {
+        let delivery_id = new_delivery_id();
+        if let Err(reason) = may_send() {
+            log::info!("[WEBHOOK] Skipped {} to '{}': {}", event.name(), hook.id, reason);
+            delivery::record(entry(&hook, &event, &delivery_id, "skipped", None, 0, Some(reason)));
+            continue;
+        }
+        let event = event.clone();
+        tauri::async_runtime::spawn(async move {
+            let outcome = deliver(&hook, &event, &delivery_id, delivery::MAX_ATTEMPTS).await;
+            delivery::record(outcome);
+        });
+    }
+}
+
+/// Why nothing may be sent right now, if so.
+fn may_send() -> Result<(), String> {
+    crate::settings::cloud_allowed()?;
+    crate::net::connectivity::check()?;
+    if !crate::history::privacy::recording() {
+        return Err("History is paused".to_string());
+    }
+    Ok(())
+}
+
+/// POST `event` to `hook`, retrying up to `max_attempts` times.
+async fn deliver(hook: &Webhook, event: &Event, delivery_id: &str, max_attempts: u32) -> DeliveryEntry {
+    let body = match serde_json::to_vec(&event.payload(delivery_id, now_secs(), hook.include_text)) {
+        Ok(body) => body,
+        Err(e) => return entry(hook, event, delivery_id, "failed", None, 0, Some(e.to_string())),
+    };
+    let signature = secret(&hook.id).map(|s| payload::signature(&s, &body));
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let mut request = crate::net::client()
+            .post(&hook.url)
+            .timeout(REQUEST_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(payload::EVENT_HEADER, event.name())
+            .header(payload::DELIVERY_HEADER, delivery_id)
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header(payload::SIGNATURE_HEADER, signature);
+        }
+        let (status, retry_after, error) = match request.send().await {
+            Ok(resp) if resp.status().is_success() => {
+                log::info!("[WEBHOOK] Delivered {} to '{}' ({})", event.name(), hook.id, resp.status());
+                return entry(hook, event, delivery_id, "delivered", Some(resp.status().as_u16()), attempt, None);
+            }
+            Ok(resp) => {
+                let retry_after = resp
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok()?.trim().parse().ok())
+                    .map(Duration::from_secs);
+                (Some(resp.status().as_u16()), retry_after, format!("HTTP {}", resp.status()))
+            }
+            Err(e) => (None, None, e.to_string()),
+        };
+        match delivery::retry_delay(attempt, status, retry_after).filter(|_| attempt < max_attempts) {
+            Some(delay) => {
+                log::warn!("[WEBHOOK] '{}' attempt {} failed ({}); retrying in {:?}", hook.id, attempt, error, delay);
+                tokio::time::sleep(delay).await;
+            }
+            None => {
+                log::warn!("[WEBHOOK] Giving up on {} to '{}' after {} attempt(s): {}", event.name(), hook.id, attempt, error);
+                return entry(hook, event, delivery_id, "failed", status, attempt, Some(error));
+            }
+        }
+    }
+}
+
+fn entry(
+    hook: &Webhook,
+    event: &Event,
+    delivery_id: &str,
+    outcome: &str,
+    status: Option<u16>,
+    attempts: u32,
+    error: Option<String>,
+) -> DeliveryEntry {
+    DeliveryEntry {
+        delivery_id: delivery_id.to_string(),
+        webhook_id: hook.id.clone(),
+        event: event.name().to_string(),
+        outcome: outcome.to_string(),
+        status,
+        attempts,
+        error,
+        timestamp: now_secs(),
+    }
+}
+
+fn secret(id: &str) -> Option<String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, id).ok()?.get_password().ok()
+}
+
+fn new_delivery_id() -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+    format!("{:x}-{}", nanos, NEXT_DELIVERY.fetch_add(1, Ordering::Relaxed))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn configured(id: &str) -> Result<Webhook, String> {
+    crate::settings::current().webhooks.get(id).cloned().ok_or_else(|| format!("No webhook '{}'", id))
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: save (or with `null`, remove) a webhook's signing secret.
+#[tauri::command]
+pub fn set_webhook_secret(id: String, secret: Option<String>) -> Result<(), String> {
+    configured(&id)?;
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, &id).map_err(|e| format!("Keychain error: {}", e))?;
+    let secret = secret.filter(|s| !s.is_empty());
+    let secret_set = secret.is_some();
+    match secret {
+        Some(s) => entry.set_password(&s).map_err(|e| format!("Failed to save webhook secret: {}", e))?,
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(format!("Failed to remove webhook secret: {}", e)),
+        },
+    }
+    log::info!("[WEBHOOK] Secret for '{}' {}", id, if secret_set { "saved" } else { "removed" });
+    Ok(())
+}
+
+/// Tauri command: send a `ping` to a webhook once, no retries, and
+/// report the outcome (also added to the delivery log).
+#[tauri::command]
+pub async fn test_webhook(id: String) -> Result<DeliveryEntry, String> {
+    let hook = configured(&id)?;
+    crate::settings::cloud_allowed()?;
+    let outcome = deliver(&hook, &Event::Ping, &new_delivery_id(), 1).await;
+    delivery::record(outcome.clone());
+    Ok(outcome)
+}
+
+/// Tauri command: recent deliveries, newest last.
+#[tauri::command]
+pub fn get_webhook_deliveries(limit: Option<usize>) -> Vec<DeliveryEntry> {
+    delivery::recent(limit.unwrap_or(DEFAULT_LOG_LIMIT))
+}
@@ -0,0 +1,161 @@
+//! Webhook request bodies and signatures (pure, no I/O).
+//!
+//! Bodies are JSON. Snip and result text pass through redaction first and
+//! are left out entirely when the endpoint has `includeText` off. With a
+//! secret set, the body is signed with HMAC-SHA256 so the receiver can
+//! check it came from this app: `X-OmniGlass-Signature: sha256=<hex>`.
+
+use crate::llm::{ActionMenu, ActionResult};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+pub const EVENT_HEADER: &str = "X-OmniGlass-Event";
+pub const DELIVERY_HEADER: &str = "X-OmniGlass-Delivery";
+pub const SIGNATURE_HEADER: &str = "X-OmniGlass-Signature";
+
+/// Text sent per field, in characters.
+const MAX_TEXT_CHARS: usize = 8000;
+const BLOCK_SIZE: usize = 64;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Payload {
+    pub event: String,
+    pub delivery_id: String,
+    /// Unix epoch seconds.
+    pub timestamp: u64,
+    pub snip: SnipInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<ActionInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionInfo {
+    pub id: String,
+    pub status: String,
+    pub result_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// The event being reported, before it is shaped for one endpoint.
+#[derive(Debug, Clone)]
+pub enum Event {
+    SnipCompleted { text: String, menu: ActionMenu },
+    ActionExecuted { snip_text: String, result: Box<ActionResult> },
+    /// Sent by "Test" in settings; carries no snip data.
+    Ping,
+}
+
+impl Event {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::SnipCompleted { .. } => super::config::SNIP_COMPLETED,
+            Event::ActionExecuted { .. } => super::config::ACTION_EXECUTED,
+            Event::Ping => "ping",
+        }
+    }
+
+    /// The body for one endpoint; `include_text` off sends metadata only.
+    pub fn payload(&self, delivery_id: &str, timestamp: u64, include_text: bool) -> Payload {
+        let clean = |text: &str| include_text.then(|| truncate(&crate::safety::redact::redact_sensitive_data(text).cleaned_text));
+        let (snip, action) = match self {
+            Event::SnipCompleted { text, menu } => {
+                let snip = SnipInfo {
+                    content_type: Some(menu.content_type.clone()),
+                    summary: include_text.then(|| menu.summary.clone()),
+                    text: clean(text),
+                };
+                (snip, None)
+            }
+            Event::ActionExecuted { snip_text, result } => {
+                let body = &result.result;
+                let answer = body.text.as_ref().or(body.command.as_ref()).or(body.clipboard_content.as_ref());
+                let action = ActionInfo {
+                    id: result.action_id.clone(),
+                    status: result.status.clone(),
+                    result_type: body.result_type.clone(),
+                    text: answer.and_then(|a| clean(a)),
+                };
+                (SnipInfo { content_type: None, summary: None, text: clean(snip_text) }, Some(action))
+            }
+            Event::Ping => (SnipInfo { content_type: None, summary: None, text: None }, None),
+        };
+        Payload { event: self.name().to_string(), delivery_id: delivery_id.to_string(), timestamp, snip, action }
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_TEXT_CHARS {
+        return text.to_string();
+    }
+    text.chars().take(MAX_TEXT_CHARS).collect::<String>() + "…"
+}
+
+/// `sha256=<hex>` HMAC of `body` under `secret` (RFC 2104).
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut key = secret.as_bytes().to_vec();
+    if key.len() > BLOCK_SIZE {
+        key = Sha256::digest(&key).to_vec();
+    }
+    key.resize(BLOCK_SIZE, 0);
+    let pad = |byte: u8| key.iter().map(|k| k ^ byte).collect::<Vec<u8>>();
+    let inner = Sha256::new().chain_update(pad(0x36)).chain_update(body).finalize();
+    let outer = Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize();
+    let hex: String = outer.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::execute::ActionResultBody;
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_ne!(signature("a", b"body"), signature("b", b"body"));
+    }
+
+    #[test]
+    fn payload_redacts_and_respects_include_text() {
+        let result = ActionResult {
+            status: "success".into(),
+            action_id: "explain_this".into(),
+            result: ActionResultBody {
+                result_type: "text".into(),
+                text: Some("Card 4111 1111 1111 1111 is a test number".into()),
+                file_path: None,
+                command: None,
+                clipboard_content: None,
+                mime_type: None,
+                email: None,
+            },
+            metadata: None,
+        };
+        let event = Event::ActionExecuted { snip_text: "4111 1111 1111 1111".into(), result: Box::new(result) };
+        let full = event.payload("d1", 7, true);
+        assert_eq!(full.event, "action.executed");
+        let action = full.action.as_ref().unwrap();
+        assert!(!action.text.as_deref().unwrap().contains("4111 1111"));
+        assert!(!full.snip.text.as_deref().unwrap().contains("4111 1111"));
+        let bare = serde_json::to_value(event.payload("d2", 7, false)).unwrap();
+        assert_eq!(bare["action"]["resultType"], "text");
+        assert!(bare["action"].get("text").is_none() && bare["snip"].get("text").is_none());
+    }
+}
@@ -0,0 +1,55 @@
+//! Typed events from the backend to one window.
+//!
+//! Each event names the window it is for, so a snip's `capture-ready`
+//! reaches the overlay and not every open webview. Broadcasts that every
+//! window may care about (`settings-changed`, `locale-changed`, the
+//! streamed `action-menu-*` events) still use `app.emit`.
+
+use super::spec::Window;
+use tauri::Emitter;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowEvent {
+    /// The overlay's screenshot is stored; fetch it now.
+    CaptureReady,
+    /// A deep link left a query for the launcher (`take_launcher_query`).
+    LauncherQuery,
+    /// The default action for this content type is queued (`take_auto_action`).
+    AutoExecute { action_id: String },
+    /// The result window shows another result (`get_shown_result`).
+    ResultChanged,
+}
+
+impl WindowEvent {
+    /// Event name the frontend listens for.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowEvent::CaptureReady => "capture-ready",
+            WindowEvent::LauncherQuery => "launcher-query",
+            WindowEvent::AutoExecute { .. } => "action-auto-execute",
+            WindowEvent::ResultChanged => "result-changed",
+        }
+    }
+
+    pub fn target(&self) -> Window {
+        match self {
+            WindowEvent::CaptureReady => Window::Overlay,
+            WindowEvent::LauncherQuery => Window::Launcher,
+            WindowEvent::AutoExecute { .. } => Window::ActionMenu,
+            WindowEvent::ResultChanged => Window::Result,
+        }
+    }
+}
+
+/// Send `event` to its window. A window that isn't open misses it; each
+/// one asks for the same state on load.
+pub fn send(app: &tauri::AppHandle, event: WindowEvent) {
+    let (label, name) = (event.target().label(), event.name());
+    let sent = match &event {
+        WindowEvent::AutoExecute { action_id } => app.emit_to(label, name, action_id),
+        WindowEvent::CaptureReady | WindowEvent::LauncherQuery | WindowEvent::ResultChanged => app.emit_to(label, name, ()),
+    };
+    if let Err(e) = sent {
+        log::warn!("[WINDOWS] Failed to send '{}' to {}: {}", name, label, e);
+    }
+}
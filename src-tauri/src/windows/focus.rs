@@ -0,0 +1,74 @@
+//! The app that was active before the overlay took focus.
+//!
+//! Remembered when an overlay opens and given focus back when the snip is
+//! cancelled, so Escape returns the user to what they were doing instead
+//! of leaving Omni-Glass active with no window. A snip that reaches the
+//! action menu forgets it; the menu keeps focus. macOS goes through
+//! `NSWorkspace` (`focus_apple.rs`), Windows through the foreground window;
+//! elsewhere the window manager decides.
+
+use std::sync::Mutex;
+
+#[cfg(target_os = "macos")]
+type App = i32;
+#[cfg(target_os = "windows")]
+type App = isize;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+type App = ();
+
+static PREVIOUS: Mutex<Option<App>> = Mutex::new(None);
+
+/// Note the active app, unless it is this one.
+pub fn remember() {
+    *PREVIOUS.lock().unwrap_or_else(|e| e.into_inner()) = frontmost();
+}
+
+/// Keep focus where it goes next (the action menu, a window clicked).
+pub fn forget() {
+    PREVIOUS.lock().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+/// Give focus back to the remembered app. Call on the main thread.
+pub fn restore() {
+    let previous = PREVIOUS.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(app) = previous {
+        if !activate(app) {
+            log::info!("[WINDOWS] Previous app is gone; focus stays");
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn frontmost() -> Option<App> {
+    let pid = super::focus_apple::frontmost_pid();
+    (pid > 0 && pid as u32 != std::process::id()).then_some(pid)
+}
+
+#[cfg(target_os = "macos")]
+fn activate(pid: App) -> bool {
+    super::focus_apple::activate(pid)
+}
+
+#[cfg(target_os = "windows")]
+fn frontmost() -> Option<App> {
+    // SAFETY: no arguments; returns a null handle when nothing has focus.
+    let hwnd = unsafe { ::windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow() };
+    (!hwnd.is_invalid()).then_some(hwnd.0 as isize)
+}
+
+#[cfg(target_os = "windows")]
+fn activate(hwnd: App) -> bool {
+    use ::windows::Win32::{Foundation::HWND, UI::WindowsAndMessaging::SetForegroundWindow};
+    // SAFETY: a stale handle is rejected by the OS (returns false).
+    unsafe { SetForegroundWindow(HWND(hwnd as *mut core::ffi::c_void)) }.as_bool()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn frontmost() -> Option<App> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn activate(_: App) -> bool {
+    true
+}
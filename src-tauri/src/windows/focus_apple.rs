@@ -0,0 +1,24 @@
+//! Frontmost app (macOS) — `NSWorkspace` / `NSRunningApplication` via
+//! swift-bridge.
+//!
+//! `swift-src/focus_bridge.swift` reads the frontmost app's pid and
+//! brings an app back to the front, yielding activation first on macOS 14
+//! and later. Call `activate` on the main thread.
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Swift" {
+        /// 0 when no app is frontmost.
+        fn frontmost_app_pid() -> i32;
+        /// False when no app has `pid` any more.
+        fn activate_app(pid: i32) -> bool;
+    }
+}
+
+pub fn frontmost_pid() -> i32 {
+    ffi::frontmost_app_pid()
+}
+
+pub fn activate(pid: i32) -> bool {
+    ffi::activate_app(pid)
+}
@@ -0,0 +1,73 @@
+//! A ring around a spot on screen, for what Find Text on Screen found.
+//!
+//! The ring is a transparent, click-through window placed over the spot
+//! in physical pixels and closed after `SHOWN_FOR`. A newer ring replaces
+//! an older one, whose timer then leaves it alone. It can also bring the
+//! mouse pointer to the spot. `out_of_capture` keeps the app's own
+//! floating windows out of a capture of the whole screen.
+
+use super::spec::Window;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize};
+
+/// How long a ring stays up.
+const SHOWN_FOR: Duration = Duration::from_millis(1800);
+/// Time for the compositor to take hidden windows off screen.
+const HIDE_SETTLE: Duration = Duration::from_millis(150);
+
+/// Rings shown so far; a timer closes its ring only if no newer one came.
+static SHOWN: AtomicU64 = AtomicU64::new(0);
+
+/// Ring `(x, y, width, height)`, in physical screen pixels, replacing any
+/// ring up. With `point`, the mouse pointer moves to its middle.
+pub fn show(app: &AppHandle, (x, y, width, height): (f64, f64, f64, f64), point: bool) -> Result<(), String> {
+    close(app);
+    let generation = SHOWN.fetch_add(1, Ordering::SeqCst) + 1;
+    let ring = super::build(app, Window::Highlight, None)?;
+    let _ = ring.set_position(PhysicalPosition::new(x.round() as i32, y.round() as i32));
+    let _ = ring.set_size(PhysicalSize::new(width.round().max(1.0) as u32, height.round().max(1.0) as u32));
+    let _ = ring.set_ignore_cursor_events(true);
+    ring.show().map_err(|e| e.to_string())?;
+    if point {
+        ring.set_cursor_position(PhysicalPosition::new(width / 2.0, height / 2.0)).map_err(|e| e.to_string())?;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(SHOWN_FOR);
+        if SHOWN.load(Ordering::SeqCst) == generation {
+            close(&app);
+        }
+    });
+    Ok(())
+}
+
+fn close(app: &AppHandle) {
+    if let Some(ring) = app.get_webview_window(Window::Highlight.label()) {
+        let _ = ring.destroy();
+    }
+}
+
+/// Run `capture` with the launcher and trigger windows hidden and any
+/// ring closed, so a capture of the whole screen doesn't read the app
+/// itself. They come back, the launcher focused, once it returns.
+pub fn out_of_capture<T>(app: &AppHandle, capture: impl FnOnce() -> T) -> T {
+    close(app);
+    let shown: Vec<_> = [Window::Launcher, Window::Trigger]
+        .into_iter()
+        .filter_map(|w| app.get_webview_window(w.label()))
+        .filter(|w| w.is_visible().unwrap_or(false))
+        .collect();
+    for window in &shown {
+        let _ = window.hide();
+    }
+    std::thread::sleep(HIDE_SETTLE);
+    let result = capture();
+    for window in &shown {
+        let _ = window.show();
+    }
+    if let Some(first) = shown.first() {
+        let _ = first.set_focus();
+    }
+    result
+}
@@ -0,0 +1,234 @@
+//! App windows — creation, placement, and lifecycle in one place.
+//!
+//! The overlay, action menu, text launcher, settings, and result windows
+//! are built from their `spec::Spec` here instead of inline in each caller.
+//! `replace` is for windows that start over each time (overlay, action
+//! menu); `show` focuses an open one instead. Windows opened at a point
+//! are kept on the monitor the point is on (`spec::place`), and pinned
+//! windows come back on a monitor that still exists. The backend talks
+//! to one window with `events::send`. How an overlay is dismissed, and
+//! what it frees when it goes, is in `overlay.rs`; what the result
+//! window shows is in `result.rs`. The trigger window, the snip button
+//! on desktops without a tray icon, is hidden while an overlay is open so
+//! it stays out of the capture. The ring around text found on screen is
+//! in `highlight.rs`.
+
+pub mod events;
+mod focus;
+#[cfg(target_os = "macos")]
+mod focus_apple;
+pub mod highlight;
+pub mod overlay;
+pub mod result;
+pub mod spec;
+
+use crate::capture::dpi;
+use crate::session::state::PinnedWindow;
+use spec::{Bounds, Spec, Window};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+pub use events::{send, WindowEvent};
+
+/// Gap between the bottom of a snip and the action menu.
+const MENU_GAP: f64 = 8.0;
+
+/// Space between the trigger window and the screen's edges.
+const TRIGGER_MARGIN: f64 = 24.0;
+
+/// Where the last snip's action menu opened, in logical screen pixels.
+static LAST_SNIP: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+
+fn build(app: &AppHandle, window: Window, position: Option<(f64, f64)>) -> Result<WebviewWindow, String> {
+    let Spec { url, title, size, fullscreen, resizable, decorations, transparent, always_on_top, skip_taskbar, center, visible } =
+        window.spec();
+    let mut builder = WebviewWindowBuilder::new(app, window.label(), WebviewUrl::App(url.into()))
+        .title(title)
+        .fullscreen(fullscreen)
+        .resizable(resizable)
+        .decorations(decorations)
+        .transparent(transparent)
+        .always_on_top(always_on_top)
+        .skip_taskbar(skip_taskbar)
+        .visible(visible);
+    if let Some((width, height)) = size {
+        builder = builder.inner_size(width, height);
+    }
+    builder = match position {
+        Some((x, y)) => builder.position(x, y),
+        None if center => builder.center(),
+        None => builder,
+    };
+    let built = builder.build().map_err(|e| format!("Failed to create {} window: {}", window.label(), e))?;
+    if window == Window::Overlay {
+        overlay::watch(app, &built);
+    }
+    Ok(built)
+}
+
+/// Close any open `window` and build a fresh one.
+pub fn replace(app: &AppHandle, window: Window) -> Result<WebviewWindow, String> {
+    if let Some(existing) = app.get_webview_window(window.label()) {
+        log::info!("[WINDOWS] Replacing open {}", window.label());
+        let _ = existing.destroy();
+    }
+    build(app, window, None)
+}
+
+/// Focus `window` if it is open, otherwise open it.
+pub fn show(app: &AppHandle, window: Window) -> Result<(), String> {
+    match app.get_webview_window(window.label()) {
+        Some(open) => {
+            let _ = open.unminimize();
+            open.set_focus().map_err(|e| e.to_string())
+        }
+        None => build(app, window, None).map(|_| ()),
+    }
+}
+
+/// Focus the first of `windows` that is open. False if none is.
+pub fn focus_first(app: &AppHandle, windows: &[Window]) -> bool {
+    windows.iter().any(|w| app.get_webview_window(w.label()).is_some() && show(app, *w).is_ok())
+}
+
+/// Close `window` if it is open.
+pub fn close(app: &AppHandle, window: Window) -> Result<(), String> {
+    match app.get_webview_window(window.label()) {
+        Some(open) => open.close().map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Open the trigger window in the primary monitor's bottom-right corner,
+/// or focus it if it is open.
+pub fn open_trigger(app: &AppHandle) -> Result<(), String> {
+    if app.get_webview_window(Window::Trigger.label()).is_some() {
+        return show(app, Window::Trigger);
+    }
+    let size = Window::Trigger.spec().size.unwrap_or_default();
+    let corner = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|m| bounds(&m))
+        .map(|area| {
+            let at = (area.x + area.width - size.0 - TRIGGER_MARGIN, area.y + area.height - size.1 - TRIGGER_MARGIN);
+            spec::place(area, at, size)
+        });
+    build(app, Window::Trigger, corner).map(|_| ())
+}
+
+/// Hide or show the trigger window, if there is one.
+fn show_trigger(app: &AppHandle, visible: bool) {
+    if let Some(trigger) = app.get_webview_window(Window::Trigger.label()) {
+        let _ = if visible { trigger.show() } else { trigger.hide() };
+    }
+}
+
+/// Open the action menu (replacing any open one) under a snip, or
+/// centered; it takes the place of the overlay, which is closed.
+/// `snip_point` is in the overlay's CSS pixels, so it is taken relative
+/// to the overlay's monitor — the primary one when there is no overlay
+/// (session restore).
+///
+/// The menu is placed in physical pixels: with per-monitor DPI (Windows),
+/// logical positions on monitors of different scales don't line up.
+pub fn open_action_menu(app: &AppHandle, snip_point: Option<(f64, f64)>) -> Result<(), String> {
+    let overlay = app.get_webview_window(Window::Overlay.label());
+    let monitor = overlay.as_ref().and_then(|o| o.current_monitor().ok().flatten());
+    for open in [overlay, app.get_webview_window(Window::ActionMenu.label())].into_iter().flatten() {
+        let _ = open.destroy();
+    }
+    let monitor = monitor.or_else(|| app.primary_monitor().ok().flatten());
+    let physical = snip_point.zip(monitor).map(|((x, y), monitor)| {
+        let (scale, area) = (monitor.scale_factor(), physical_bounds(&monitor));
+        let (width, height) = Window::ActionMenu.spec().size.unwrap_or_default();
+        let at = dpi::to_physical((x, y + MENU_GAP), (area.x, area.y), scale);
+        (spec::place(area, at, (width * scale, height * scale)), scale)
+    });
+    let position = match physical {
+        Some(((x, y), scale)) => Some((x / scale, y / scale)),
+        None => snip_point.map(|(x, y)| (x, y + MENU_GAP)),
+    };
+    if position.is_some() {
+        *LAST_SNIP.lock().unwrap_or_else(|e| e.into_inner()) = position;
+    }
+    let menu = build(app, Window::ActionMenu, position)?;
+    if let Some(((x, y), _)) = physical {
+        let _ = menu.set_position(PhysicalPosition::new(x.round() as i32, y.round() as i32));
+    }
+    Ok(())
+}
+
+/// Where `window` opens: at `at` (logical screen pixels), else where the
+/// last snip's menu did, kept on that monitor. `None` (no snip yet)
+/// leaves it to the spec.
+fn near_snip(app: &AppHandle, window: Window, at: Option<(f64, f64)>) -> Option<(f64, f64)> {
+    let at = at.or_else(|| *LAST_SNIP.lock().unwrap_or_else(|e| e.into_inner()))?;
+    let area = monitor_at(app, at).or_else(|| app.primary_monitor().ok().flatten().map(|m| bounds(&m)))?;
+    Some(spec::place(area, at, window.spec().size.unwrap_or_default()))
+}
+
+/// The monitor `point` (logical pixels) is on.
+fn monitor_at(app: &AppHandle, (x, y): (f64, f64)) -> Option<Bounds> {
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(bounds)
+        .find(|b| (b.x..b.x + b.width).contains(&x) && (b.y..b.y + b.height).contains(&y))
+}
+
+fn physical_bounds(monitor: &tauri::Monitor) -> Bounds {
+    let (position, size) = (monitor.position(), monitor.size());
+    Bounds { x: f64::from(position.x), y: f64::from(position.y), width: f64::from(size.width), height: f64::from(size.height) }
+}
+
+fn bounds(monitor: &tauri::Monitor) -> Bounds {
+    let scale = monitor.scale_factor();
+    let (position, size) = (monitor.position().to_logical::<f64>(scale), monitor.size().to_logical::<f64>(scale));
+    Bounds { x: position.x, y: position.y, width: size.width, height: size.height }
+}
+
+// ── Pins ─────────────────────────────────────────────────────────────
+
+/// Where `window` is and what it shows, to reopen it after a restart.
+pub fn pin_of(window: &WebviewWindow) -> Result<PinnedWindow, String> {
+    let scale = window.scale_factor().map_err(|e| e.to_string())?;
+    let position = window.outer_position().map_err(|e| e.to_string())?.to_logical::<f64>(scale);
+    let size = window.inner_size().map_err(|e| e.to_string())?.to_logical::<f64>(scale);
+    let page = window.url().map_err(|e| e.to_string())?;
+    let url = match page.query() {
+        Some(q) => format!("{}?{}", page.path().trim_start_matches('/'), q),
+        None => page.path().trim_start_matches('/').to_string(),
+    };
+    let title = window.title().unwrap_or_default();
+    Ok(PinnedWindow {
+        label: window.label().to_string(),
+        url,
+        title,
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Reopen a pinned window unless it is open. One pinned on a monitor
+/// that is gone comes back on the primary monitor.
+pub fn reopen_pinned(app: &AppHandle, pin: &PinnedWindow) -> Result<(), String> {
+    if app.get_webview_window(&pin.label).is_some() {
+        return Ok(());
+    }
+    let still_there = monitor_at(app, (pin.x, pin.y)).is_some();
+    let (x, y) = match app.primary_monitor().ok().flatten() {
+        Some(primary) if !still_there => spec::place(bounds(&primary), (pin.x, pin.y), (pin.width, pin.height)),
+        _ => (pin.x, pin.y),
+    };
+    WebviewWindowBuilder::new(app, &pin.label, WebviewUrl::App(pin.url.clone().into()))
+        .title(&pin.title)
+        .inner_size(pin.width, pin.height)
+        .position(x, y)
+        .build()
+        .map(|_| ())
+        .map_err(|e| format!("Could not restore window '{}': {}", pin.label, e))
+}
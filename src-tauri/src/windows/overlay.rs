@@ -0,0 +1,120 @@
+//! Overlay lifecycle — how a snip that isn't finished ends.
+//!
+//! An open overlay is dismissed by Escape (a global shortcut while it is
+//! open, so it works even when the webview doesn't have key focus), by
+//! losing focus to another app, by `close_overlay`, or after `TIMEOUT`
+//! with no snip. Escape, `close_overlay`, and the timeout give focus back
+//! to the app that was active before (`focus.rs`).
+//!
+//! However the overlay goes — dismissed, replaced by the action menu, or
+//! torn down by crash recovery — its `Destroyed` event drops the stored
+//! capture, any speculative OCR, and any keyboard selection, so no path
+//! leaves the screenshot in memory. The trigger window is hidden while
+//! an overlay is open and shown again when it goes.
+
+use super::{focus, spec::Window};
+use crate::capture::CaptureState;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, WebviewWindow, WindowEvent};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// An overlay left open this long is dismissed.
+const TIMEOUT: Duration = Duration::from_secs(60);
+const ESCAPE: &str = "Escape";
+
+/// Bumped for each overlay, and when one is dismissed or hands its snip
+/// to the pipeline; a blur or timeout from an older one is ignored.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn armed(generation: u64) -> bool {
+    GENERATION.load(Ordering::SeqCst) == generation
+}
+
+/// Called by `windows::build` for each new overlay.
+pub(super) fn watch(app: &AppHandle, overlay: &WebviewWindow) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    focus::remember();
+    bind_escape(app);
+    super::show_trigger(app, false);
+
+    let handle = app.clone();
+    overlay.on_window_event(move |event| match event {
+        // Hidden while the screen is captured; only a shown overlay can lose focus
+        WindowEvent::Focused(false) if armed(generation) && visible(&handle) => {
+            log::info!("[WINDOWS] Overlay lost focus; dismissing");
+            // Focus went where the user clicked; leave it there
+            focus::forget();
+            let _ = dismiss(&handle);
+        }
+        WindowEvent::Destroyed => cleanup(&handle),
+        _ => {}
+    });
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(TIMEOUT).await;
+        if armed(generation) {
+            log::info!("[WINDOWS] Overlay open for {}s; dismissing", TIMEOUT.as_secs());
+            let _ = dismiss(&app);
+        }
+    });
+}
+
+fn visible(app: &AppHandle) -> bool {
+    app.get_webview_window(Window::Overlay.label()).is_some_and(|o| o.is_visible().unwrap_or(false))
+}
+
+/// The snip went to the pipeline: the overlay stays until the action
+/// menu replaces it, and focus goes to the menu.
+pub fn disarm() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    focus::forget();
+}
+
+/// Close the overlay without a snip and give focus back.
+pub fn dismiss(app: &AppHandle) -> Result<(), String> {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    let closed = super::close(app, Window::Overlay);
+    let _ = app.run_on_main_thread(focus::restore);
+    closed
+}
+
+/// The overlay is gone: free what it held. Skipped when a new overlay
+/// already replaced it; that one's capture is in use.
+fn cleanup(app: &AppHandle) {
+    if app.get_webview_window(Window::Overlay.label()).is_some() {
+        return;
+    }
+    let freed = app.state::<CaptureState>().release();
+    crate::pipeline_prefetch::cancel();
+    crate::selection::clear();
+    crate::tray::status::capturing(app, false);
+    super::show_trigger(app, true);
+    let shortcuts = app.global_shortcut();
+    if shortcuts.is_registered(ESCAPE) {
+        if let Err(e) = shortcuts.unregister(ESCAPE) {
+            log::warn!("[WINDOWS] Failed to release Escape: {}", e);
+        }
+    }
+    log::info!("[WINDOWS] Overlay closed; released {} bytes", freed);
+}
+
+fn bind_escape(app: &AppHandle) {
+    let shortcuts = app.global_shortcut();
+    if shortcuts.is_registered(ESCAPE) {
+        return;
+    }
+    let bound = shortcuts.on_shortcut(ESCAPE, |app, _shortcut, event| {
+        if event.state() == ShortcutState::Pressed {
+            crate::crash::guard(|| {
+                log::info!("[WINDOWS] Escape; dismissing overlay");
+                let _ = dismiss(app);
+            });
+        }
+    });
+    // The overlay's own Escape handler still works while it has focus
+    if let Err(e) = bound {
+        log::warn!("[WINDOWS] Could not bind Escape: {}", e);
+    }
+}
@@ -0,0 +1,159 @@
+//! The result window — a text answer in a window of its own.
+//!
+//! The action menu shows a result in place and closes as soon as it
+//! loses focus. The result window keeps one: decorated, resizable, opened
+//! where the last snip's menu was, and open until the user closes it. A
+//! result gets here popped out of the action menu, reopened as the last
+//! result, or from history (Recent Snips, `omniglass://history` links,
+//! restored and notified results, through `session::show_result`).
+//! There is one result window; another result replaces what it shows.
+//! `actions.resultOnTop` decides whether a new one stays above other
+//! windows, and pinning changes that for the open one.
+//!
+//! A result of the current snip can also show the snip itself with the
+//! recognized words laid over it where they were read (`get_text_spans`),
+//! so part of the text can be selected and copied without snipping again.
+
+use super::spec::Window;
+use super::{events::WindowEvent, send};
+use crate::llm::{self, execute::ActionResult};
+use crate::ocr::{self, RecognitionLevel, TextSpan};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+/// What the result window shows.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShownResult {
+    pub result: ActionResult,
+    /// Kept above other windows.
+    pub pinned: bool,
+    /// The result is for the current snip, so its image can be shown
+    /// with the text laid over it.
+    pub on_image: bool,
+}
+
+/// The current snip with the words recognized in it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnipText {
+    /// Base64-encoded PNG.
+    pub png: String,
+    pub width: u32,
+    pub height: u32,
+    /// In the image's pixels, origin top left.
+    pub spans: Vec<TextSpan>,
+}
+
+static SHOWN: Mutex<Option<ShownResult>> = Mutex::new(None);
+
+/// Show `result` in the result window. An open one shows it in place and
+/// keeps its pin; otherwise one opens at `at` (logical screen pixels), or
+/// near the last snip.
+pub fn open(app: &AppHandle, result: ActionResult, at: Option<(f64, f64)>) -> Result<(), String> {
+    let window = app.get_webview_window(Window::Result.label());
+    let on_image = of_current_snip(app);
+    let pinned = {
+        let mut shown = SHOWN.lock().unwrap_or_else(|e| e.into_inner());
+        let pinned = match (&window, shown.as_ref()) {
+            (Some(_), Some(open)) => open.pinned,
+            _ => crate::settings::current().actions.result_on_top,
+        };
+        *shown = Some(ShownResult { result, pinned, on_image });
+        pinned
+    };
+    match window {
+        Some(window) => {
+            send(app, WindowEvent::ResultChanged);
+            let _ = window.unminimize();
+            window.set_focus().map_err(|e| e.to_string())
+        }
+        None => {
+            let position = super::near_snip(app, Window::Result, at);
+            let window = super::build(app, Window::Result, position)?;
+            window.set_always_on_top(pinned).map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn last_result(app: &AppHandle) -> Option<(String, ActionResult)> {
+    app.state::<llm::ActionMenuState>().last_result.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Whether the last result answered the snip whose image is still held.
+/// History results answer older snips, whose images aren't kept.
+fn of_current_snip(app: &AppHandle) -> bool {
+    let state = app.state::<llm::ActionMenuState>();
+    if state.crop_png.lock().unwrap_or_else(|e| e.into_inner()).is_none() {
+        return false;
+    }
+    let snip_text = state.ocr_text.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    matches!((last_result(app), snip_text), (Some((answered, _)), Some(snip)) if answered == snip)
+}
+
+/// Read the words in `png` with their boxes.
+fn spans_of(png: &[u8]) -> Result<SnipText, String> {
+    let image = image::load_from_memory(png).map_err(|e| format!("Could not read the snip: {}", e))?.to_rgba8();
+    let (width, height) = image.dimensions();
+    let view = ocr::RgbaView::crop(&image, 0, 0, width, height).ok_or("The snip is empty")?;
+    let spans = ocr::recognize_spans_from_rgba(&view, RecognitionLevel::Accurate);
+    log::info!("[RESULT] {} words on the {}x{} snip", spans.len(), width, height);
+    let png = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, png);
+    Ok(SnipText { png, width, height, spans })
+}
+
+// ── Tauri commands ───────────────────────────────────────────────────
+
+/// Tauri command: the result the result window shows.
+#[tauri::command]
+pub fn get_shown_result() -> Option<ShownResult> {
+    SHOWN.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Tauri command: the current snip's image and the words read in it,
+/// with their boxes, for showing selectable text over the image.
+#[tauri::command]
+pub async fn get_text_spans(state: tauri::State<'_, llm::ActionMenuState>) -> Result<SnipText, String> {
+    let png = {
+        let guard = state.crop_png.lock().map_err(|e| e.to_string())?;
+        guard.clone().ok_or("No snip image available — snip first")?
+    };
+    tauri::async_runtime::spawn_blocking(move || spans_of(&png)).await.map_err(|e| e.to_string())?
+}
+
+/// Tauri command: move the action menu's result into the result window,
+/// opened where the menu was, and close the menu.
+#[tauri::command]
+pub fn pop_out_result(app: AppHandle, window: WebviewWindow) -> Result<(), String> {
+    let (_, result) = last_result(&app).ok_or("No result to pop out")?;
+    let scale = window.scale_factor().map_err(|e| e.to_string())?;
+    let at = window.outer_position().map_err(|e| e.to_string())?.to_logical::<f64>(scale);
+    open(&app, result, Some((at.x, at.y)))?;
+    super::close(&app, Window::ActionMenu)
+}
+
+/// Tauri command: show the last result again — in the result window when
+/// it is text, otherwise in the action menu.
+#[tauri::command]
+pub fn reopen_last_result(app: AppHandle) -> Result<(), String> {
+    let (snip_text, result) = last_result(&app).ok_or("No result to reopen yet")?;
+    crate::session::show_result(&app, snip_text, result)
+}
+
+/// Tauri command: a history snip's newest answer, or its menu when it
+/// has none.
+#[tauri::command]
+pub fn open_history_result(app: AppHandle, snip_id: u64) -> Result<(), String> {
+    crate::deeplink::open_history(&app, snip_id)
+}
+
+/// Tauri command: keep the result window above other windows, or not.
+#[tauri::command]
+pub fn pin_result(window: WebviewWindow, pinned: bool) -> Result<(), String> {
+    window.set_always_on_top(pinned).map_err(|e| e.to_string())?;
+    if let Some(shown) = SHOWN.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        shown.pinned = pinned;
+    }
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! What each app window looks like, and where it goes (pure, no I/O).
+//!
+//! Every window the app opens on its own is a `Window`, with one `Spec`
+//! holding its page, title, size, and chrome. `place` keeps a window that
+//! opens at a point (the action menu under a snip) on the monitor that
+//! point is on.
+
+/// The windows `windows/` owns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// Full-screen snip overlay, built hidden while the screen is captured.
+    Overlay,
+    /// Action menu and the result of a snip or restored result.
+    ActionMenu,
+    /// Text launcher (Type Command).
+    Launcher,
+    Settings,
+    /// A text result in a window of its own (`result.rs`).
+    Result,
+    /// Floating snip button, where a Linux desktop shows no tray icon.
+    Trigger,
+    /// A ring around text found on screen (`highlight.rs`).
+    Highlight,
+    /// First-run setup checklist (`onboarding`).
+    Onboarding,
+}
+
+/// How a window is built.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spec {
+    /// App-relative page.
+    pub url: &'static str,
+    pub title: &'static str,
+    /// Logical size; `None` for full-screen windows.
+    pub size: Option<(f64, f64)>,
+    pub fullscreen: bool,
+    pub resizable: bool,
+    pub decorations: bool,
+    pub transparent: bool,
+    pub always_on_top: bool,
+    pub skip_taskbar: bool,
+    /// Centered on the primary monitor unless opened at a point.
+    pub center: bool,
+    /// Built shown; the overlay waits for its capture.
+    pub visible: bool,
+}
+
+/// An ordinary decorated window; the specs below change what differs.
+const BASE: Spec = Spec {
+    url: "",
+    title: "Omni-Glass",
+    size: None,
+    fullscreen: false,
+    resizable: true,
+    decorations: true,
+    transparent: false,
+    always_on_top: false,
+    skip_taskbar: false,
+    center: false,
+    visible: true,
+};
+
+impl Window {
+    pub const ALL: [Window; 8] = [
+        Window::Overlay,
+        Window::ActionMenu,
+        Window::Launcher,
+        Window::Settings,
+        Window::Result,
+        Window::Trigger,
+        Window::Highlight,
+        Window::Onboarding,
+    ];
+
+    /// Webview label, which the frontend and `emit_to` also use.
+    pub fn label(self) -> &'static str {
+        match self {
+            Window::Overlay => "overlay",
+            Window::ActionMenu => "action-menu",
+            Window::Launcher => "text-launcher",
+            Window::Settings => "settings",
+            Window::Result => "result",
+            Window::Trigger => "trigger",
+            Window::Highlight => "highlight",
+            Window::Onboarding => "onboarding",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Window> {
+        Window::ALL.into_iter().find(|w| w.label() == label)
+    }
+
+    pub fn spec(self) -> Spec {
+        match self {
+            Window::Overlay => Spec {
+                url: "index.html",
+                title: "Omni-Glass Overlay",
+                fullscreen: true,
+                decorations: false,
+                transparent: true,
+                always_on_top: true,
+                skip_taskbar: true,
+                visible: false,
+                ..BASE
+            },
+            Window::ActionMenu => Spec {
+                url: "action-menu.html",
+                title: "Omni-Glass Actions",
+                size: Some((300.0, 280.0)),
+                decorations: false,
+                always_on_top: true,
+                skip_taskbar: true,
+                center: true,
+                ..BASE
+            },
+            Window::Launcher => Spec {
+                url: "text-launcher.html",
+                size: Some((600.0, 72.0)),
+                resizable: false,
+                decorations: false,
+                transparent: true,
+                always_on_top: true,
+                center: true,
+                ..BASE
+            },
+            Window::Settings => Spec { url: "settings.html", title: "Omni-Glass Settings", size: Some((520.0, 500.0)), ..BASE },
+            // Kept on top or not by the user's setting, applied once built
+            Window::Result => Spec {
+                url: "result.html",
+                title: "Omni-Glass Result",
+                size: Some((420.0, 360.0)),
+                skip_taskbar: true,
+                center: true,
+                ..BASE
+            },
+            // Placed in a corner by `open_trigger`, dragged from there
+            Window::Trigger => Spec {
+                url: "trigger.html",
+                title: "Omni-Glass",
+                size: Some((64.0, 120.0)),
+                resizable: false,
+                decorations: false,
+                transparent: true,
+                always_on_top: true,
+                skip_taskbar: true,
+                ..BASE
+            },
+            // Placed and sized over the spot by `highlight::show`
+            Window::Highlight => Spec {
+                url: "highlight.html",
+                title: "Omni-Glass",
+                size: Some((40.0, 40.0)),
+                resizable: false,
+                decorations: false,
+                transparent: true,
+                always_on_top: true,
+                skip_taskbar: true,
+                visible: false,
+                ..BASE
+            },
+            Window::Onboarding => Spec {
+                url: "onboarding.html",
+                title: "Welcome to Omni-Glass",
+                size: Some((480.0, 520.0)),
+                center: true,
+                ..BASE
+            },
+        }
+    }
+}
+
+/// A monitor's area in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Top-left corner for a window of `size` opening at `at`, moved as
+/// little as needed to stay inside `bounds`. A window larger than the
+/// monitor keeps its top-left corner on it.
+pub fn place(bounds: Bounds, at: (f64, f64), size: (f64, f64)) -> (f64, f64) {
+    let fit = |start: f64, len: f64, min: f64, span: f64| start.min(min + span - len).max(min);
+    (fit(at.0, size.0, bounds.x, bounds.width), fit(at.1, size.1, bounds.y, bounds.height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_round_trip_and_specs_match_the_pages() {
+        for window in Window::ALL {
+            assert_eq!(Window::from_label(window.label()), Some(window));
+        }
+        assert_eq!(Window::from_label("permission-prompt"), None);
+        let overlay = Window::Overlay.spec();
+        assert!(overlay.fullscreen && !overlay.visible && overlay.size.is_none());
+        assert_eq!(Window::ActionMenu.spec().size, Some((300.0, 280.0)));
+        assert!(!Window::Launcher.spec().resizable);
+        let result = Window::Result.spec();
+        assert!(result.resizable && result.decorations && !result.always_on_top);
+        let trigger = Window::Trigger.spec();
+        assert!(trigger.always_on_top && trigger.skip_taskbar && !trigger.decorations);
+        let highlight = Window::Highlight.spec();
+        assert!(highlight.transparent && !highlight.visible && !highlight.decorations);
+        assert!(Window::Onboarding.spec().center);
+    }
+
+    #[test]
+    fn placed_windows_stay_on_their_monitor() {
+        let second = Bounds { x: 1920.0, y: 0.0, width: 1440.0, height: 900.0 };
+        // Room to spare: unchanged
+        assert_eq!(place(second, (2000.0, 100.0), (300.0, 280.0)), (2000.0, 100.0));
+        // Snip at the bottom-right corner: pulled back inside
+        assert_eq!(place(second, (3300.0, 850.0), (300.0, 280.0)), (3060.0, 620.0));
+        // Left of the monitor (stale position): pushed onto it
+        assert_eq!(place(second, (100.0, -50.0), (300.0, 280.0)), (1920.0, 0.0));
+        // Bigger than the monitor: top-left corner on it
+        assert_eq!(place(second, (2500.0, 10.0), (2000.0, 1000.0)), (1920.0, 0.0));
+    }
+}
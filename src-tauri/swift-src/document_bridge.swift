@@ -0,0 +1,59 @@
+/// Omni-Glass Document Bridge — Vision document segmentation via swift-bridge FFI.
+///
+/// Called from Rust (src/document/apple.rs). Finds the outline of a page,
+/// receipt, or card in a snip with VNDetectDocumentSegmentationRequest;
+/// the perspective correction itself happens in Rust (src/document/warp.rs).
+
+import Foundation
+import Vision
+import CoreGraphics
+
+/// FFI entry point: the most confident document outline in raw RGBA
+/// pixels, read in place like `run_ocr_on_rgba`. Corners are normalized
+/// with the origin at the bottom left, as Vision reports them; `found`
+/// is false when there is none or the OS is older than macOS 12.
+func detect_document_in_rgba(data: UnsafeBufferPointer<UInt8>, width: UInt32, height: UInt32, bytes_per_row: UInt32) -> DocumentQuad {
+    let none = DocumentQuad(
+        found: false, confidence: 0.0,
+        top_left_x: 0.0, top_left_y: 0.0, top_right_x: 0.0, top_right_y: 0.0,
+        bottom_right_x: 0.0, bottom_right_y: 0.0, bottom_left_x: 0.0, bottom_left_y: 0.0
+    )
+    guard #available(macOS 12.0, *),
+          let base = data.baseAddress,
+          let provider = CGDataProvider(dataInfo: nil, data: base, size: data.count, releaseData: { _, _, _ in }),
+          let cgImage = CGImage(
+              width: Int(width),
+              height: Int(height),
+              bitsPerComponent: 8,
+              bitsPerPixel: 32,
+              bytesPerRow: Int(bytes_per_row),
+              space: CGColorSpaceCreateDeviceRGB(),
+              bitmapInfo: CGBitmapInfo(rawValue: CGImageAlphaInfo.noneSkipLast.rawValue),
+              provider: provider,
+              decode: nil,
+              shouldInterpolate: false,
+              intent: .defaultIntent
+          ) else {
+        return none
+    }
+
+    let request = VNDetectDocumentSegmentationRequest()
+    let handler = VNImageRequestHandler(cgImage: cgImage, options: [:])
+    do {
+        try handler.perform([request])
+    } catch {
+        return none
+    }
+    guard let best = request.results?.max(by: { $0.confidence < $1.confidence }) else {
+        return none
+    }
+
+    return DocumentQuad(
+        found: true,
+        confidence: Double(best.confidence),
+        top_left_x: Double(best.topLeft.x), top_left_y: Double(best.topLeft.y),
+        top_right_x: Double(best.topRight.x), top_right_y: Double(best.topRight.y),
+        bottom_right_x: Double(best.bottomRight.x), bottom_right_y: Double(best.bottomRight.y),
+        bottom_left_x: Double(best.bottomLeft.x), bottom_left_y: Double(best.bottomLeft.y)
+    )
+}
@@ -0,0 +1,23 @@
+/// Omni-Glass Focus Bridge — the frontmost app via swift-bridge FFI.
+///
+/// Called from Rust (src/windows/focus_apple.rs). The overlay remembers
+/// the frontmost app when it opens and, when a snip is cancelled, hands
+/// focus back to it instead of leaving Omni-Glass active with no window.
+
+import AppKit
+import Foundation
+
+/// FFI entry point: pid of the frontmost app, or 0.
+func frontmost_app_pid() -> Int32 {
+    return NSWorkspace.shared.frontmostApplication?.processIdentifier ?? 0
+}
+
+/// FFI entry point: bring the app with `pid` to the front. On macOS 14
+/// and later activation is cooperative, so this app yields it first.
+func activate_app(pid: Int32) -> Bool {
+    guard let app = NSRunningApplication(processIdentifier: pid) else { return false }
+    if #available(macOS 14.0, *) {
+        NSApp.yieldActivation(to: app)
+    }
+    return app.activate(options: [])
+}
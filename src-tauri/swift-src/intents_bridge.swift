@@ -0,0 +1,86 @@
+/// Omni-Glass Intents Bridge — Shortcuts actions via App Intents.
+///
+/// Each intent's perform() hands its input to Rust (src/intents/apple.rs)
+/// on a background queue and returns the reply as the action's output, so
+/// Shortcuts can chain OCR text and answers into further steps.
+/// App Intents need macOS 13; on older systems the types are simply absent.
+
+import AppIntents
+import Foundation
+
+/// An intent failed in Rust; Shortcuts shows the message.
+@available(macOS 13.0, *)
+struct OmniGlassIntentError: Error, CustomLocalizedStringResourceConvertible {
+    let message: String
+    var localizedStringResource: LocalizedStringResource { "\(message)" }
+}
+
+/// Run a blocking Rust call off the Swift concurrency pool and unwrap its reply.
+@available(macOS 13.0, *)
+private func callRust(_ body: @escaping () -> IntentReply) async throws -> String {
+    let (ok, text) = await withCheckedContinuation { (done: CheckedContinuation<(Bool, String), Never>) in
+        DispatchQueue.global(qos: .userInitiated).async {
+            let reply = body()
+            done.resume(returning: (reply.ok, reply.text.toString()))
+        }
+    }
+    guard ok else { throw OmniGlassIntentError(message: text) }
+    return text
+}
+
+@available(macOS 13.0, *)
+struct TakeSnipIntent: AppIntent {
+    static var title: LocalizedStringResource = "Take Snip"
+    static var description = IntentDescription("Open the Omni-Glass snip overlay to select part of the screen.")
+
+    func perform() async throws -> some IntentResult {
+        _ = try await callRust { intent_take_snip() }
+        return .result()
+    }
+}
+
+@available(macOS 13.0, *)
+struct OcrImageIntent: AppIntent {
+    static var title: LocalizedStringResource = "OCR Image"
+    static var description = IntentDescription("Recognize the text in an image, on this Mac.")
+
+    @Parameter(title: "Image", supportedTypeIdentifiers: ["public.image"])
+    var image: IntentFile
+
+    func perform() async throws -> some IntentResult & ReturnsValue<String> {
+        // Rust reads a path; Shortcuts files are often in memory only
+        let path = FileManager.default.temporaryDirectory
+            .appendingPathComponent("omni-glass-intent-\(UUID().uuidString)")
+        try image.data.write(to: path)
+        defer { try? FileManager.default.removeItem(at: path) }
+        let text = try await callRust { intent_ocr_image(path.path) }
+        return .result(value: text)
+    }
+}
+
+@available(macOS 13.0, *)
+struct AskAboutClipboardIntent: AppIntent {
+    static var title: LocalizedStringResource = "Ask About Clipboard"
+    static var description = IntentDescription(
+        "Ask Omni-Glass about the text on the clipboard. Without a question, it explains the text."
+    )
+
+    @Parameter(title: "Question")
+    var question: String?
+
+    func perform() async throws -> some IntentResult & ReturnsValue<String> {
+        let question = self.question ?? ""
+        let answer = try await callRust { intent_ask_clipboard(question) }
+        return .result(value: answer)
+    }
+}
+
+/// Suggested shortcuts, available without any setup.
+@available(macOS 13.0, *)
+struct OmniGlassShortcuts: AppShortcutsProvider {
+    static var appShortcuts: [AppShortcut] {
+        AppShortcut(intent: TakeSnipIntent(), phrases: ["Take a snip with \(.applicationName)"])
+        AppShortcut(intent: OcrImageIntent(), phrases: ["Read text in an image with \(.applicationName)"])
+        AppShortcut(intent: AskAboutClipboardIntent(), phrases: ["Ask \(.applicationName) about the clipboard"])
+    }
+}
@@ -67,7 +67,86 @@ func run_ocr_on_png_data(data: RustVec<UInt8>, level: Int32) -> OcrResult {
     return performOCR(on: cgImage, level: level)
 }
 
-/// Core OCR logic — shared between path-based and bytes-based entry points.
+/// FFI entry point: run OCR on raw RGBA pixels, read in place — no PNG
+/// encode or decode. `bytes_per_row` exceeds `width * 4` when the pixels
+/// are a region of a larger capture. The buffer is borrowed from Rust for
+/// the duration of the call only; Vision is done with it on return.
+/// level: 0 = accurate, 1 = fast
+func run_ocr_on_rgba(data: UnsafeBufferPointer<UInt8>, width: UInt32, height: UInt32, bytes_per_row: UInt32, level: Int32) -> OcrResult {
+    guard let cgImage = wrapRgba(data, width: width, height: height, bytesPerRow: bytes_per_row) else {
+        return OcrResult(
+            text: "ERROR: Failed to wrap \(width)x\(height) pixels".intoRustString(),
+            char_count: 0,
+            latency_ms: 0.0,
+            confidence: 0.0,
+            recognition_level: "error".intoRustString()
+        )
+    }
+
+    return performOCR(on: cgImage, level: level)
+}
+
+/// FFI entry point: recognized words with their boxes, for laying the text
+/// over the snip. One word per line, `line\tx\ty\twidth\theight\ttext`,
+/// the box normalized with the origin at the bottom left (Vision's own
+/// coordinates). Empty when nothing is recognized or the request fails.
+/// level: 0 = accurate, 1 = fast
+func run_ocr_spans_on_rgba(data: UnsafeBufferPointer<UInt8>, width: UInt32, height: UInt32, bytes_per_row: UInt32, level: Int32) -> RustString {
+    guard let cgImage = wrapRgba(data, width: width, height: height, bytesPerRow: bytes_per_row) else {
+        return "".intoRustString()
+    }
+
+    var report = ""
+    let request = VNRecognizeTextRequest { request, error in
+        guard error == nil,
+              let observations = request.results as? [VNRecognizedTextObservation] else {
+            return
+        }
+        for (line, observation) in observations.enumerated() {
+            guard let candidate = observation.topCandidates(1).first else { continue }
+            let string = candidate.string
+            string.enumerateSubstrings(in: string.startIndex..<string.endIndex, options: .byWords) { word, range, _, _ in
+                guard let word = word,
+                      let box = try? candidate.boundingBox(for: range)?.boundingBox else { return }
+                let text = word.replacingOccurrences(of: "\t", with: " ").replacingOccurrences(of: "\n", with: " ")
+                report += "\(line)\t\(box.origin.x)\t\(box.origin.y)\t\(box.width)\t\(box.height)\t\(text)\n"
+            }
+        }
+    }
+    request.recognitionLevel = (level == 1) ? .fast : .accurate
+    request.usesLanguageCorrection = true
+    request.automaticallyDetectsLanguage = true
+
+    do {
+        try VNImageRequestHandler(cgImage: cgImage, options: [:]).perform([request])
+    } catch {
+        return "".intoRustString()
+    }
+    return report.intoRustString()
+}
+
+/// Wrap borrowed RGBA rows in a CGImage without copying.
+private func wrapRgba(_ data: UnsafeBufferPointer<UInt8>, width: UInt32, height: UInt32, bytesPerRow: UInt32) -> CGImage? {
+    guard let base = data.baseAddress,
+          let provider = CGDataProvider(dataInfo: nil, data: base, size: data.count, releaseData: { _, _, _ in }) else {
+        return nil
+    }
+    return CGImage(
+        width: Int(width),
+        height: Int(height),
+        bitsPerComponent: 8,
+        bitsPerPixel: 32,
+        bytesPerRow: Int(bytesPerRow),
+        space: CGColorSpaceCreateDeviceRGB(),
+        bitmapInfo: CGBitmapInfo(rawValue: CGImageAlphaInfo.noneSkipLast.rawValue),
+        provider: provider,
+        decode: nil,
+        shouldInterpolate: false,
+        intent: .defaultIntent
+    )
+}
+
+/// Core OCR logic — shared between the path, bytes, and pixel entry points.
 private func performOCR(on cgImage: CGImage, level: Int32) -> OcrResult {
     let startTime = CFAbsoluteTimeGetCurrent()
     let recognitionLevel: VNRequestTextRecognitionLevel = (level == 1) ? .fast : .accurate
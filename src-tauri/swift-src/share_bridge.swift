@@ -0,0 +1,71 @@
+/// Omni-Glass Share Bridge — the macOS share sheet via swift-bridge FFI.
+///
+/// Called from Rust (src/share/apple.rs) on the main thread. The picker
+/// hangs from a view: the key window (the action menu) when there is one,
+/// else a transparent 1x1 panel at the mouse, closed once the user picks
+/// a service or dismisses the picker.
+
+import AppKit
+import Foundation
+
+/// Keeps the picker's delegate (and anchor panel) alive until it closes.
+private var activeShare: ShareDelegate?
+
+private final class ShareDelegate: NSObject, NSSharingServicePickerDelegate {
+    let subject: String
+    let panel: NSPanel?
+
+    init(subject: String, panel: NSPanel?) {
+        self.subject = subject
+        self.panel = panel
+    }
+
+    /// Called with the chosen service, or nil when the picker is dismissed.
+    func sharingServicePicker(_ picker: NSSharingServicePicker, didChoose service: NSSharingService?) {
+        // Mail and Notes use the subject as the title
+        service?.subject = subject
+        panel?.orderOut(nil)
+        activeShare = nil
+    }
+}
+
+/// FFI entry point: show the share picker for `text` and, when
+/// `image_path` is not empty, the PNG at that path.
+func show_share_picker(title: RustString, text: RustString, image_path: RustString) {
+    var items: [Any] = [text.toString()]
+    let path = image_path.toString()
+    if !path.isEmpty {
+        items.append(URL(fileURLWithPath: path))
+    }
+
+    let mouse = NSEvent.mouseLocation
+    var panel: NSPanel? = nil
+    let view: NSView
+    let rect: NSRect
+    if let window = NSApp.keyWindow, let content = window.contentView {
+        let point = content.convert(window.convertPoint(fromScreen: mouse), from: nil)
+        view = content
+        rect = NSRect(x: point.x, y: point.y, width: 1, height: 1)
+    } else {
+        let anchor = NSPanel(
+            contentRect: NSRect(x: mouse.x, y: mouse.y, width: 1, height: 1),
+            styleMask: [.borderless, .nonactivatingPanel],
+            backing: .buffered,
+            defer: false
+        )
+        anchor.isOpaque = false
+        anchor.backgroundColor = .clear
+        anchor.level = .floating
+        anchor.orderFrontRegardless()
+        panel = anchor
+        view = anchor.contentView!
+        rect = view.bounds
+    }
+
+    let picker = NSSharingServicePicker(items: items)
+    let delegate = ShareDelegate(subject: title.toString(), panel: panel)
+    activeShare = delegate
+    picker.delegate = delegate
+    NSApp.activate(ignoringOtherApps: true)
+    picker.show(relativeTo: rect, of: view, preferredEdge: .minY)
+}
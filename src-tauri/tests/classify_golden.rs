@@ -0,0 +1,141 @@
+//! Golden-file tests of the streaming classify pipelines against a mock
+//! provider.
+//!
+//! Each test serves a canned SSE stream (or error) from `mock_llm`, runs
+//! `classify_streaming` / `classify_streaming_gemini` on a mock Tauri app,
+//! and compares the emitted events — skeleton, each action, complete —
+//! with `tests/golden/classify/<name>.json`. No API keys or network needed.
+//!
+//! After an intended change to what the pipeline emits:
+//!   UPDATE_GOLDEN=1 cargo test --test classify_golden
+
+mod mock_llm;
+
+use mock_llm::{assert_golden, record, sse, MockLlm};
+use omni_glass_lib::llm::{classify_streaming, classify_streaming_gemini, ActionMenu};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::ResponseTemplate;
+
+const TRACEBACK: &str = "Traceback (most recent call last):\n  File \"app.py\", line 1, in <module>\n    import pandas\nModuleNotFoundError: No module named 'pandas'";
+
+const ERROR_MENU: &str = r#"```json
+{"contentType": "error", "confidence": 0.93, "summary": "Python ModuleNotFoundError: No module named 'pandas'", "detectedLanguage": "python", "actions": [{"id": "explain_error", "label": "Explain Error", "icon": "lightbulb", "priority": 1, "description": "Why the import failed", "requiresExecution": true}, {"id": "suggest_fix", "label": "Suggest Fix", "icon": "wrench", "priority": 2, "description": "Run \"pip install pandas\" in {this} environment", "requiresExecution": true}, {"id": "copy_text", "label": "Copy Text", "icon": "clipboard", "priority": 3, "description": "Copy the traceback", "requiresExecution": false}]}
+```"#;
+
+const TABLE_MENU: &str = r#"{"contentType": "table", "confidence": 0.88, "summary": "Quarterly sales by region", "detectedLanguage": null, "actions": [{"id": "export_csv", "label": "Export CSV", "icon": "table", "priority": 1, "description": "Save the table as CSV", "requiresExecution": true}, {"id": "copy_text", "label": "Copy Text", "icon": "clipboard", "priority": 2, "description": "Copy the raw text", "requiresExecution": false}]}"#;
+
+fn mock_app() -> tauri::App<tauri::test::MockRuntime> {
+    tauri::test::mock_app()
+}
+
+fn payload(menu: &ActionMenu) -> serde_json::Value {
+    serde_json::to_value(menu).unwrap()
+}
+
+#[tokio::test]
+async fn anthropic_streams_skeleton_then_actions_then_menu() {
+    let mock = MockLlm::start().await;
+    mock.anthropic(sse::ok(sse::anthropic(&sse::split(ERROR_MENU, 7)))).await;
+    let app = mock_app();
+    let recorder = record(app.handle());
+
+    let menu = classify_streaming(app.handle(), TRACEBACK, false, true, 0.95, "").await;
+
+    let events = recorder.events();
+    assert_golden("anthropic_error_menu", &events);
+    assert_eq!(events.last().unwrap().payload, payload(&menu));
+}
+
+#[tokio::test]
+async fn gemini_streams_skeleton_then_actions_then_menu() {
+    let mock = MockLlm::start().await;
+    mock.gemini(sse::ok(sse::gemini(&sse::split(TABLE_MENU, 11)))).await;
+    let app = mock_app();
+    let recorder = record(app.handle());
+
+    let menu = classify_streaming_gemini(app.handle(), "Region Q1 Q2\nNorth 10 12", true, false, 0.9, "").await;
+
+    let events = recorder.events();
+    assert_golden("gemini_table_menu", &events);
+    assert_eq!(events.last().unwrap().payload, payload(&menu));
+}
+
+#[tokio::test]
+async fn rate_limited_falls_back_without_retrying() {
+    let limited = || {
+        ResponseTemplate::new(429)
+            .set_body_json(json!({"type": "error", "error": {"type": "rate_limit_error", "message": "Rate limited"}}))
+    };
+    let mock = MockLlm::start().await;
+    mock.anthropic(limited()).await;
+    mock.gemini(limited()).await;
+
+    let app = mock_app();
+    let recorder = record(app.handle());
+    assert_eq!(classify_streaming(app.handle(), TRACEBACK, false, true, 0.95, "").await, ActionMenu::fallback());
+    assert_golden("rate_limited", &recorder.events());
+
+    let app = mock_app();
+    let recorder = record(app.handle());
+    assert_eq!(classify_streaming_gemini(app.handle(), TRACEBACK, false, true, 0.95, "").await, ActionMenu::fallback());
+    assert_golden("rate_limited", &recorder.events());
+}
+
+#[tokio::test]
+async fn truncated_stream_keeps_streamed_actions_and_falls_back() {
+    // The connection ends partway through the second action, with no message_stop
+    let cut = ERROR_MENU.find("\"label\": \"Suggest").unwrap();
+    let body = sse::anthropic_start() + &sse::anthropic_deltas(&sse::split(&ERROR_MENU[..cut], 7));
+    let mock = MockLlm::start().await;
+    mock.anthropic(sse::ok(body)).await;
+    let app = mock_app();
+    let recorder = record(app.handle());
+
+    let menu = classify_streaming(app.handle(), TRACEBACK, false, true, 0.95, "").await;
+
+    assert_eq!(menu, ActionMenu::fallback());
+    assert_golden("truncated", &recorder.events());
+}
+
+#[tokio::test]
+async fn malformed_json_falls_back() {
+    let text = r#"{"contentType": "text", "confidence": high, "summary": "Meeting notes", "actions": [{"id": "summarize", "label": "Summarize", "icon": "text", "priority": 1, "description": "Key points", "requiresExecution": true}]}"#;
+    let mock = MockLlm::start().await;
+    mock.anthropic(sse::ok(sse::anthropic(&sse::split(text, 5)))).await;
+    let app = mock_app();
+    let recorder = record(app.handle());
+
+    let menu = classify_streaming(app.handle(), "Notes: ship on Friday", false, false, 0.9, "").await;
+
+    assert_eq!(menu, ActionMenu::fallback());
+    assert_golden("malformed", &recorder.events());
+}
+
+#[tokio::test]
+async fn skeleton_arrives_while_the_stream_is_still_open() {
+    const GAP: Duration = Duration::from_millis(500);
+    let summary_end = ERROR_MENU.find("\"detectedLanguage\"").unwrap();
+    let stream = sse::anthropic(&[&ERROR_MENU[..summary_end], &ERROR_MENU[summary_end..]]);
+    // First chunk ends mid-event, right after the summary delta
+    let split_at = stream.find("detectedLanguage").unwrap() - 40;
+    let (head, rest) = stream.split_at(split_at);
+    let (actions, tail) = rest.split_at(rest.find("event: content_block_stop").unwrap());
+    let url = sse::paced(vec![head.to_string(), actions.to_string(), tail.to_string()], GAP).await;
+
+    let mock = MockLlm::start().await;
+    mock.anthropic_at(&url);
+    let app = mock_app();
+    let recorder = record(app.handle());
+    classify_streaming(app.handle(), TRACEBACK, false, true, 0.95, "").await;
+
+    let events = recorder.events();
+    let kinds: Vec<&str> = events.iter().map(|e| e.event).collect();
+    assert_eq!(
+        kinds,
+        ["action-menu-skeleton", "action-menu-item", "action-menu-item", "action-menu-item", "action-menu-complete"]
+    );
+    assert!(events[0].at < GAP, "skeleton at {:?}, before the first pause ended", events[0].at);
+    assert!(events[1].at >= GAP, "first action at {:?}", events[1].at);
+    assert!(events[4].at >= GAP * 2, "complete at {:?}", events[4].at);
+}
@@ -0,0 +1,159 @@
+//! LLM pipeline benchmark — replays recorded OCR texts through CLASSIFY
+//! and EXECUTE across providers and prompt versions.
+//!
+//! Scores each response for JSON validity (it parses into `ActionMenu` /
+//! `ActionResult`), the expected content type, the actions the case
+//! requires or forbids, and latency. Cases live in `test-corpus/llm/`.
+//!
+//! Calls paid APIs, so it only runs when asked:
+//!   cargo test --test llm_bench -- --ignored --nocapture
+//!
+//! Environment:
+//!   LLM_BENCH_PROVIDERS        anthropic,gemini (default: every provider with a key)
+//!   LLM_BENCH_CLASSIFY_PROMPTS candidate CLASSIFY system prompts, comma-separated files
+//!   LLM_BENCH_EXECUTE_PROMPTS  candidate EXECUTE system prompts, comma-separated files
+//!   LLM_BENCH_CASES            only these cases (file stems), comma-separated
+//!   LLM_BENCH_RUNS             repetitions per case (default 1)
+//!   LLM_BENCH_OUT              also write every run and the summary as JSON here
+//!
+//! The built-in prompts always run as version "current"; a candidate is
+//! named after its file stem.
+
+mod llm_bench_support;
+
+use llm_bench_support::{aggregate, load_corpus, providers, render, score_classify, score_execute, Run, Score};
+use omni_glass_lib::llm::prompts::{build_classify_message, CLASSIFY_SYSTEM_PROMPT, MAX_TOKENS};
+use omni_glass_lib::llm::prompts_execute::{build_execute_message, EXECUTE_MAX_TOKENS, EXECUTE_SYSTEM_PROMPT};
+use omni_glass_lib::safety::redact;
+use std::path::Path;
+use std::time::Instant;
+
+fn load_env() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let project_root = manifest_dir.parent().unwrap_or(manifest_dir);
+    let env_path = project_root.join(".env.local");
+    if env_path.exists() {
+        dotenvy::from_path(&env_path).ok();
+    }
+}
+
+fn env_list(name: &str) -> Vec<String> {
+    std::env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// ("current", built-in prompt) followed by each candidate file.
+fn prompt_versions(env: &str, builtin: &str) -> Vec<(String, String)> {
+    let mut versions = vec![("current".to_string(), builtin.to_string())];
+    for path in env_list(env) {
+        let prompt = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        let name = Path::new(&path).file_stem().unwrap_or_default().to_string_lossy().to_string();
+        versions.push((name, prompt));
+    }
+    versions
+}
+
+/// One request to time and score.
+struct Request<'a> {
+    prompt: &'a str,
+    system: &'a str,
+    phase: &'static str,
+    case: String,
+    user: String,
+    max_tokens: u32,
+}
+
+async fn run(client: &reqwest::Client, provider: &providers::Provider, request: Request<'_>, score: impl Fn(&str) -> Score) -> Run {
+    let start = Instant::now();
+    let response = providers::complete(client, provider, request.system, &request.user, request.max_tokens).await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let (score, error) = match response {
+        Ok(text) => (Some(score(&text)), None),
+        Err(e) => (None, Some(e)),
+    };
+    let outcome = match (&score, &error) {
+        (Some(s), _) => format!("json={} correct={} required={}/{}", s.valid_json, s.correct, s.required_found, s.required_total),
+        (None, e) => format!("ERROR {}", e.as_deref().unwrap_or_default()),
+    };
+    eprintln!("[BENCH] {} {} {} {} {:.0}ms {}", provider.id, request.prompt, request.phase, request.case, latency_ms, outcome);
+    Run {
+        provider: provider.id.to_string(),
+        prompt: request.prompt.to_string(),
+        phase: request.phase,
+        case: request.case,
+        latency_ms,
+        score,
+        error,
+    }
+}
+
+#[tokio::test]
+#[ignore = "calls paid LLM APIs; run with --ignored"]
+async fn llm_bench() {
+    load_env();
+    let only = std::env::var("LLM_BENCH_PROVIDERS").ok();
+    let providers = providers::configured(only.as_deref());
+    if providers.is_empty() {
+        eprintln!("SKIP: No API key for any benchmarked provider");
+        return;
+    }
+
+    let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-corpus/llm");
+    let wanted = env_list("LLM_BENCH_CASES");
+    let corpus: Vec<_> = load_corpus(&corpus_dir)
+        .expect("Failed to load corpus")
+        .into_iter()
+        .filter(|c| wanted.is_empty() || wanted.contains(&c.name))
+        .collect();
+    let classify_prompts = prompt_versions("LLM_BENCH_CLASSIFY_PROMPTS", CLASSIFY_SYSTEM_PROMPT);
+    let execute_prompts = prompt_versions("LLM_BENCH_EXECUTE_PROMPTS", EXECUTE_SYSTEM_PROMPT);
+    let repetitions: usize = std::env::var("LLM_BENCH_RUNS").ok().and_then(|n| n.parse().ok()).unwrap_or(1);
+
+    let client = reqwest::Client::new();
+    let mut runs = Vec::new();
+    for provider in &providers {
+        for (prompt, system) in &classify_prompts {
+            for case in corpus.iter().flat_map(|c| std::iter::repeat_n(c, repetitions)) {
+                let request = Request {
+                    prompt,
+                    system,
+                    phase: "classify",
+                    case: case.name.clone(),
+                    user: build_classify_message(&case.text, case.confidence, case.has_table, case.has_code, ""),
+                    max_tokens: MAX_TOKENS,
+                };
+                runs.push(run(&client, provider, request, |raw| score_classify(raw, &case.expect)).await);
+            }
+        }
+        for (prompt, system) in &execute_prompts {
+            for case in corpus.iter().flat_map(|c| std::iter::repeat_n(c, repetitions)) {
+                // Same pre-flight redaction as execute_action_anthropic
+                let clean = redact::redact_sensitive_data(&case.text).cleaned_text;
+                for action in &case.execute {
+                    let request = Request {
+                        prompt,
+                        system,
+                        phase: "execute",
+                        case: format!("{}/{}", case.name, action),
+                        user: build_execute_message(action, &clean, "macos"),
+                        max_tokens: EXECUTE_MAX_TOKENS,
+                    };
+                    runs.push(run(&client, provider, request, score_execute).await);
+                }
+            }
+        }
+    }
+
+    let rows = aggregate(&runs);
+    eprintln!("\n=== LLM Pipeline Benchmark ({} cases) ===\n{}", corpus.len(), render(&rows));
+    if let Ok(out) = std::env::var("LLM_BENCH_OUT") {
+        let report = serde_json::json!({ "summary": rows, "runs": runs });
+        std::fs::write(&out, serde_json::to_string_pretty(&report).unwrap()).expect("Failed to write LLM_BENCH_OUT");
+        eprintln!("Report written to {}", out);
+    }
+}
@@ -0,0 +1,257 @@
+//! Corpus loading, scoring, and the summary table for `tests/llm_bench.rs`.
+//!
+//! Everything here is pure except `load_corpus`; the HTTP calls live in
+//! `providers.rs`.
+
+pub mod providers;
+
+use omni_glass_lib::llm::execute::ActionResult;
+use omni_glass_lib::llm::streaming::strip_code_fences;
+use omni_glass_lib::llm::types::ActionMenu;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One recorded snip: OCR text plus what a good answer contains.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Case {
+    /// File stem in the corpus directory.
+    #[serde(skip)]
+    pub name: String,
+    pub text: String,
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+    #[serde(default)]
+    pub has_table: bool,
+    #[serde(default)]
+    pub has_code: bool,
+    pub expect: Expect,
+    /// Action ids to run through EXECUTE.
+    #[serde(default)]
+    pub execute: Vec<String>,
+}
+
+fn default_confidence() -> f64 {
+    0.95
+}
+
+/// Entries accept alternatives separated by `|` (`"explain_error|explain"`).
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Expect {
+    pub content_type: String,
+    /// Actions the menu must offer.
+    #[serde(default)]
+    pub actions: Vec<String>,
+    /// Actions the menu must not offer.
+    #[serde(default)]
+    pub forbidden: Vec<String>,
+}
+
+/// Every `*.json` case in `dir`, by name.
+pub fn load_corpus(dir: &Path) -> Result<Vec<Case>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let mut cases = Vec::new();
+    for path in entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|x| x == "json")) {
+        let text = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let mut case: Case = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        case.name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        cases.push(case);
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+fn accepts(alternatives: &str, value: &str) -> bool {
+    alternatives.split('|').any(|a| a == value)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Score {
+    /// Parsed into the app's own type (`ActionMenu` / `ActionResult`).
+    pub valid_json: bool,
+    /// CLASSIFY: an expected content type. EXECUTE: a non-empty success.
+    pub correct: bool,
+    pub required_found: usize,
+    pub required_total: usize,
+    pub forbidden_found: Vec<String>,
+}
+
+pub fn score_classify(raw: &str, expect: &Expect) -> Score {
+    let required_total = expect.actions.len();
+    let Ok(menu) = serde_json::from_str::<ActionMenu>(&strip_code_fences(raw)) else {
+        return Score { required_total, ..Score::default() };
+    };
+    let offers = |alternatives: &String| menu.actions.iter().any(|a| accepts(alternatives, &a.id));
+    Score {
+        valid_json: true,
+        correct: accepts(&expect.content_type, &menu.content_type),
+        required_found: expect.actions.iter().filter(|a| offers(a)).count(),
+        required_total,
+        forbidden_found: expect.forbidden.iter().filter(|a| offers(a)).cloned().collect(),
+    }
+}
+
+pub fn score_execute(raw: &str) -> Score {
+    let Ok(result) = serde_json::from_str::<ActionResult>(&strip_code_fences(raw)) else {
+        return Score::default();
+    };
+    let body = &result.result;
+    let answered = [&body.text, &body.command, &body.clipboard_content, &body.file_path]
+        .iter()
+        .any(|field| field.as_deref().is_some_and(|s| !s.trim().is_empty()))
+        || body.email.is_some();
+    Score { valid_json: true, correct: result.status == "success" && answered, ..Score::default() }
+}
+
+/// One request: a case (and action, for EXECUTE) through one provider
+/// and prompt version.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub provider: String,
+    pub prompt: String,
+    pub phase: &'static str,
+    pub case: String,
+    pub latency_ms: f64,
+    /// `None` when the request itself failed (`error` says why).
+    pub score: Option<Score>,
+    pub error: Option<String>,
+}
+
+/// Totals for one provider × prompt version × phase.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Row {
+    pub provider: String,
+    pub prompt: String,
+    pub phase: &'static str,
+    pub runs: usize,
+    pub errors: usize,
+    pub valid_json: usize,
+    pub correct: usize,
+    pub required_found: usize,
+    pub required_total: usize,
+    pub forbidden: usize,
+    pub median_ms: f64,
+    pub p95_ms: f64,
+}
+
+/// Group runs by provider, prompt, and phase, in first-seen order.
+pub fn aggregate(runs: &[Run]) -> Vec<Row> {
+    let mut keys: Vec<(&str, &str, &'static str)> = Vec::new();
+    for run in runs {
+        let key = (run.provider.as_str(), run.prompt.as_str(), run.phase);
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys.into_iter()
+        .map(|(provider, prompt, phase)| {
+            let group: Vec<&Run> =
+                runs.iter().filter(|r| (r.provider.as_str(), r.prompt.as_str(), r.phase) == (provider, prompt, phase)).collect();
+            let scores: Vec<&Score> = group.iter().filter_map(|r| r.score.as_ref()).collect();
+            let mut latencies: Vec<f64> = group.iter().filter(|r| r.score.is_some()).map(|r| r.latency_ms).collect();
+            latencies.sort_by(f64::total_cmp);
+            let at = |q: f64| latencies.get(((latencies.len() as f64 * q) as usize).min(latencies.len().saturating_sub(1))).copied();
+            Row {
+                provider: provider.to_string(),
+                prompt: prompt.to_string(),
+                phase,
+                runs: group.len(),
+                errors: group.len() - scores.len(),
+                valid_json: scores.iter().filter(|s| s.valid_json).count(),
+                correct: scores.iter().filter(|s| s.correct).count(),
+                required_found: scores.iter().map(|s| s.required_found).sum(),
+                required_total: scores.iter().map(|s| s.required_total).sum(),
+                forbidden: scores.iter().map(|s| s.forbidden_found.len()).sum(),
+                median_ms: at(0.5).unwrap_or(0.0),
+                p95_ms: at(0.95).unwrap_or(0.0),
+            }
+        })
+        .collect()
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    if whole == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.0}%", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+/// The summary table printed at the end of a benchmark run.
+pub fn render(rows: &[Row]) -> String {
+    let mut out = format!(
+        "{:<10} {:<16} {:<8} {:>5} {:>6} {:>6} {:>8} {:>9} {:>9} {:>9} {:>9}\n",
+        "provider", "prompt", "phase", "runs", "errors", "json", "correct", "required", "forbidden", "median", "p95"
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "{:<10} {:<16} {:<8} {:>5} {:>6} {:>6} {:>8} {:>9} {:>9} {:>7.0}ms {:>7.0}ms\n",
+            row.provider,
+            row.prompt,
+            row.phase,
+            row.runs,
+            row.errors,
+            percent(row.valid_json, row.runs - row.errors),
+            percent(row.correct, row.runs - row.errors),
+            percent(row.required_found, row.required_total),
+            row.forbidden,
+            row.median_ms,
+            row.p95_ms
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_menus_against_expectations() {
+        let expect = Expect {
+            content_type: "error|mixed".into(),
+            actions: vec!["explain_error|explain".into(), "suggest_fix".into()],
+            forbidden: vec!["run_command".into()],
+        };
+        let action = |id: &str| format!(r#"{{"id":"{id}","label":"L","icon":"code","priority":1,"description":"D","requiresExecution":true}}"#);
+        let raw = format!(
+            "```json\n{{\"contentType\":\"error\",\"confidence\":0.9,\"summary\":\"S\",\"detectedLanguage\":null,\"actions\":[{},{}]}}\n```",
+            action("explain"),
+            action("run_command")
+        );
+        let score = score_classify(&raw, &expect);
+        assert!(score.valid_json && score.correct);
+        assert_eq!((score.required_found, score.required_total), (1, 2));
+        assert_eq!(score.forbidden_found, vec!["run_command".to_string()]);
+
+        assert_eq!(score_classify("{\"contentType\": \"err", &expect), Score { required_total: 2, ..Score::default() });
+        let result = r#"{"status":"success","actionId":"explain","result":{"type":"text","text":"Because."},"metadata":null}"#;
+        assert!(score_execute(result).correct);
+        assert!(!score_execute(&result.replace("Because.", " ")).correct);
+    }
+
+    #[test]
+    fn aggregates_per_provider_prompt_and_phase() {
+        let run = |prompt: &str, ms: f64, correct: bool| Run {
+            provider: "anthropic".into(),
+            prompt: prompt.into(),
+            phase: "classify",
+            case: "c".into(),
+            latency_ms: ms,
+            score: Some(Score { valid_json: true, correct, required_found: 1, required_total: 2, ..Score::default() }),
+            error: None,
+        };
+        let failed = Run { score: None, error: Some("429".into()), ..run("current", 9999.0, false) };
+        let runs = vec![run("current", 300.0, true), run("terse", 100.0, false), run("current", 500.0, false), failed];
+        let rows = aggregate(&runs);
+        assert_eq!(rows.iter().map(|r| r.prompt.as_str()).collect::<Vec<_>>(), ["current", "terse"]);
+        let current = &rows[0];
+        assert_eq!((current.runs, current.errors, current.correct, current.required_found), (3, 1, 1, 2));
+        assert_eq!((current.median_ms, current.p95_ms), (500.0, 500.0));
+        assert!(render(&rows).contains("50%"));
+    }
+}
@@ -0,0 +1,76 @@
+//! Raw, non-streaming provider calls with an arbitrary system prompt.
+//!
+//! The app's pipelines take their prompts from constants; the benchmark
+//! needs to swap in candidate prompts, so it calls the APIs itself with
+//! the same models and generation settings as `llm/classify.rs` and
+//! `llm/gemini.rs`.
+
+use omni_glass_lib::llm::prompts::MODEL;
+use omni_glass_lib::llm::GEMINI_MODEL;
+
+pub struct Provider {
+    pub id: &'static str,
+    key: String,
+}
+
+const PROVIDERS: [(&str, &str); 2] = [("anthropic", "ANTHROPIC_API_KEY"), ("gemini", "GEMINI_API_KEY")];
+
+/// Providers with an API key, limited to the comma-separated `only` list.
+pub fn configured(only: Option<&str>) -> Vec<Provider> {
+    PROVIDERS
+        .iter()
+        .filter(|&&(id, _)| only.is_none_or(|list| list.split(',').any(|p| p.trim() == id)))
+        .filter_map(|&(id, env)| {
+            let key = std::env::var(env).ok().filter(|k| !k.is_empty())?;
+            Some(Provider { id, key })
+        })
+        .collect()
+}
+
+/// Send one system + user message; returns the model's text.
+pub async fn complete(
+    client: &reqwest::Client,
+    provider: &Provider,
+    system: &str,
+    user: &str,
+    max_tokens: u32,
+) -> Result<String, String> {
+    let request = match provider.id {
+        "gemini" => client
+            .post(format!(
+                "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+                GEMINI_MODEL, provider.key
+            ))
+            .json(&serde_json::json!({
+                "contents": [{"role": "user", "parts": [{"text": user}]}],
+                "systemInstruction": {"parts": [{"text": system}]},
+                "generationConfig": {
+                    "maxOutputTokens": max_tokens,
+                    "temperature": 0.1,
+                    "responseMimeType": "application/json"
+                }
+            })),
+        _ => client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", &provider.key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&serde_json::json!({
+                "model": MODEL,
+                "max_tokens": max_tokens,
+                "system": system,
+                "messages": [{"role": "user", "content": user}]
+            })),
+    };
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        return Err(format!("HTTP {}: {}", status.as_u16(), body["error"]["message"].as_str().unwrap_or("")));
+    }
+    let text = match provider.id {
+        "gemini" => body["candidates"][0]["content"]["parts"][0]["text"].as_str(),
+        _ => body["content"][0]["text"].as_str(),
+    };
+    text.map(str::to_string).ok_or_else(|| "response has no text".to_string())
+}
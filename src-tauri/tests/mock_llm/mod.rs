@@ -0,0 +1,143 @@
+//! Mock LLM providers for the classify integration tests.
+//!
+//! `MockLlm::start` runs a wiremock server and points both providers at
+//! it (`OMNI_GLASS_ANTHROPIC_URL` / `OMNI_GLASS_GEMINI_URL`) with fake
+//! keys. The environment is process-wide, so each test holds the mock
+//! for its whole run and tests in one binary take turns.
+//!
+//! `record` listens for the classify events on a mock Tauri app, and
+//! `assert_golden` compares them with `tests/golden/classify/<name>.json`.
+//! Run with `UPDATE_GOLDEN=1` to rewrite the files after an intended change.
+
+#![allow(dead_code)]
+
+pub mod sse;
+
+use omni_glass_lib::llm::types::ActionMenu;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::test::MockRuntime;
+use tauri::{AppHandle, Listener};
+use tokio::sync::MutexGuard;
+use wiremock::matchers::{body_partial_json, header, method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+pub const ANTHROPIC_KEY: &str = "test-anthropic-key";
+pub const GEMINI_KEY: &str = "test-gemini-key";
+
+static ENV: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+pub struct MockLlm {
+    pub server: MockServer,
+    _env: MutexGuard<'static, ()>,
+}
+
+impl MockLlm {
+    pub async fn start() -> Self {
+        let env = ENV.lock().await;
+        let server = MockServer::start().await;
+        std::env::set_var("OMNI_GLASS_ANTHROPIC_URL", server.uri());
+        std::env::set_var("OMNI_GLASS_GEMINI_URL", server.uri());
+        std::env::set_var("ANTHROPIC_API_KEY", ANTHROPIC_KEY);
+        std::env::set_var("GEMINI_API_KEY", GEMINI_KEY);
+        Self { server, _env: env }
+    }
+
+    /// Answer exactly one streaming Messages request with `response`.
+    pub async fn anthropic(&self, response: ResponseTemplate) {
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(header("x-api-key", ANTHROPIC_KEY))
+            .and(body_partial_json(json!({ "stream": true })))
+            .respond_with(response)
+            .expect(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer exactly one `streamGenerateContent` request with `response`.
+    pub async fn gemini(&self, response: ResponseTemplate) {
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/v1beta/models/[^/]+:streamGenerateContent$"))
+            .and(query_param("alt", "sse"))
+            .and(query_param("key", GEMINI_KEY))
+            .respond_with(response)
+            .expect(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Send Anthropic requests to a paced server (`sse::paced`) instead.
+    pub fn anthropic_at(&self, base_url: &str) {
+        std::env::set_var("OMNI_GLASS_ANTHROPIC_URL", base_url);
+    }
+}
+
+/// One event the pipeline emitted, and when.
+#[derive(Debug, Clone)]
+pub struct Emitted {
+    pub event: &'static str,
+    pub payload: Value,
+    pub at: Duration,
+}
+
+const EVENTS: [&str; 3] = ["action-menu-skeleton", "action-menu-item", "action-menu-complete"];
+
+/// Collects the classify events of `app` from now on.
+pub struct Recorder {
+    events: Arc<Mutex<Vec<Emitted>>>,
+}
+
+impl Recorder {
+    pub fn events(&self) -> Vec<Emitted> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+pub fn record(app: &AppHandle<MockRuntime>) -> Recorder {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let start = Instant::now();
+    for event in EVENTS {
+        let events = events.clone();
+        app.listen_any(event, move |e| {
+            let payload = serde_json::from_str(e.payload()).unwrap_or(Value::Null);
+            events.lock().unwrap().push(Emitted { event, payload, at: start.elapsed() });
+        });
+    }
+    Recorder { events }
+}
+
+/// Events as stored in a golden file. The fallback menu is localized,
+/// so it is written as the string `"<fallback>"`.
+fn golden_value(events: &[Emitted]) -> Value {
+    let fallback = serde_json::to_value(ActionMenu::fallback()).unwrap();
+    let events = events
+        .iter()
+        .map(|e| {
+            let payload = if e.payload == fallback { json!("<fallback>") } else { e.payload.clone() };
+            json!({ "event": e.event, "payload": payload })
+        })
+        .collect();
+    Value::Array(events)
+}
+
+pub fn assert_golden(name: &str, events: &[Emitted]) {
+    let file = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("tests/golden/classify/{}.json", name));
+    let actual = golden_value(events);
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        std::fs::write(&file, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+        eprintln!("Updated {}", file.display());
+        return;
+    }
+    let text = std::fs::read_to_string(&file)
+        .unwrap_or_else(|e| panic!("{}: {} (run with UPDATE_GOLDEN=1 to create it)", file.display(), e));
+    let expected: Value = serde_json::from_str(&text).unwrap();
+    assert!(
+        actual == expected,
+        "events differ from {}; got:\n{}",
+        file.display(),
+        serde_json::to_string_pretty(&actual).unwrap()
+    );
+}
@@ -0,0 +1,113 @@
+//! Provider SSE bodies, and a server that streams one at a set pace.
+//!
+//! The bodies carry no token usage: the pipelines would record it in the
+//! user's stats.
+
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use wiremock::ResponseTemplate;
+
+/// `text` in pieces of `size` bytes, so deltas end mid-key, mid-string
+/// and mid-escape. `text` must be ASCII.
+pub fn split(text: &str, size: usize) -> Vec<&str> {
+    text.as_bytes().chunks(size).map(|c| std::str::from_utf8(c).unwrap()).collect()
+}
+
+fn event(name: &str, data: serde_json::Value) -> String {
+    format!("event: {}\ndata: {}\n\n", name, data)
+}
+
+/// The events before the first text delta of a Messages stream.
+pub fn anthropic_start() -> String {
+    event(
+        "message_start",
+        json!({"type": "message_start", "message": {"id": "msg_mock", "type": "message", "role": "assistant", "content": []}}),
+    ) + &event("content_block_start", json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}))
+}
+
+pub fn anthropic_deltas(deltas: &[&str]) -> String {
+    deltas
+        .iter()
+        .map(|text| {
+            event(
+                "content_block_delta",
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": text}}),
+            )
+        })
+        .collect()
+}
+
+pub fn anthropic_end() -> String {
+    event("content_block_stop", json!({"type": "content_block_stop", "index": 0}))
+        + &event("message_delta", json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}}))
+        + &event("message_stop", json!({"type": "message_stop"}))
+}
+
+/// A complete Messages stream of `deltas`.
+pub fn anthropic(deltas: &[&str]) -> String {
+    anthropic_start() + &anthropic_deltas(deltas) + &anthropic_end()
+}
+
+/// A `streamGenerateContent?alt=sse` stream of `deltas`.
+pub fn gemini(deltas: &[&str]) -> String {
+    deltas
+        .iter()
+        .map(|text| {
+            let chunk = json!({"candidates": [{"content": {"role": "model", "parts": [{"text": text}]}}]});
+            format!("data: {}\n\n", chunk)
+        })
+        .collect()
+}
+
+/// A 200 response with `body` as its event stream.
+pub fn ok(body: String) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body.into_bytes(), "text/event-stream")
+}
+
+/// Serve one request on a local port, writing `chunks` with `gap` between
+/// them. wiremock sends a body in one piece, so pacing needs its own
+/// listener. Returns the base URL.
+pub async fn paced(chunks: Vec<String>, gap: Duration) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        read_request(&mut socket).await;
+        let head = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n";
+        socket.write_all(head.as_bytes()).await.unwrap();
+        for (i, chunk) in chunks.iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(gap).await;
+            }
+            let frame = format!("{:x}\r\n{}\r\n", chunk.len(), chunk);
+            socket.write_all(frame.as_bytes()).await.unwrap();
+            socket.flush().await.unwrap();
+        }
+        socket.write_all(b"0\r\n\r\n").await.unwrap();
+    });
+    url
+}
+
+/// Read the headers and the `content-length` body of one request.
+async fn read_request(socket: &mut tokio::net::TcpStream) {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        if n == 0 {
+            return;
+        }
+        request.extend_from_slice(&buf[..n]);
+        let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else { continue };
+        let head = String::from_utf8_lossy(&request[..end]).to_ascii_lowercase();
+        let length: usize = head
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0);
+        if request.len() >= end + 4 + length {
+            return;
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::time::Instant;
 #[tokio::test]
 async fn benchmark_registry_overhead() {
     let registry = omni_glass_lib::mcp::ToolRegistry::new();
+    let snip = omni_glass_lib::mcp::triggers::SnipContext::new("error: build failed", false, true);
     omni_glass_lib::mcp::builtins::register_builtins(&registry).await;
 
     let mut times = Vec::new();
@@ -10,14 +11,14 @@ async fn benchmark_registry_overhead() {
         let start = Instant::now();
         let all = registry.all_tools().await;
         let _ = all.iter().filter(|t| t.plugin_id != "builtin").count();
-        let _ = registry.tools_for_prompt().await;
+        let _ = registry.tools_for_prompt(&snip).await;
         times.push(start.elapsed());
     }
     times.sort();
     let median = times[times.len() / 2];
     let p99 = times[(times.len() as f64 * 0.99) as usize];
     let tools_count = registry.all_tools().await.len();
-    let prompt = registry.tools_for_prompt().await;
+    let prompt = registry.tools_for_prompt(&snip).await;
 
     eprintln!("=== Phase 2 Registry Hot-Path Benchmark ===");
     eprintln!("Builtin tools: {}", tools_count);
@@ -41,6 +42,7 @@ fn benchmark_approval_check() {
         entry: "index.js".to_string(),
         permissions: Permissions::default(),
         configuration: None,
+        extensions: Default::default(),
     };
 
     let store = approval::load_approvals();
@@ -99,6 +101,7 @@ fn benchmark_profile_generation() {
         entry: "index.js".to_string(),
         permissions: Permissions::default(),
         configuration: None,
+        extensions: Default::default(),
     };
     let dir = std::env::temp_dir().join("og-bench");
     let _ = std::fs::create_dir_all(&dir);
@@ -106,7 +109,7 @@ fn benchmark_profile_generation() {
     let mut times = Vec::new();
     for _ in 0..1000 {
         let start = Instant::now();
-        let _ = macos::generate_profile(&manifest, &dir).unwrap();
+        let _ = macos::generate_profile(&manifest, &dir, None).unwrap();
         times.push(start.elapsed());
     }
     times.sort();
@@ -29,7 +29,7 @@ fn no_network_cannot_connect() {
 
     let dir = setup_test_dir("no-net");
     let manifest = test_manifest("com.test.no-net", Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -69,7 +69,7 @@ fn with_network_can_connect() {
         ..Default::default()
     };
     let manifest = test_manifest("com.test.with-net", perms.clone());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -103,7 +103,7 @@ fn cannot_read_home_ssh() {
 
     let dir = setup_test_dir("no-ssh");
     let manifest = test_manifest("com.test.no-ssh", Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let home = dirs::home_dir().unwrap();
@@ -158,7 +158,7 @@ fn cannot_read_aws_credentials() {
 
     let dir = setup_test_dir("no-aws");
     let manifest = test_manifest("com.test.no-aws", Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -205,7 +205,7 @@ fn can_read_own_directory() {
     std::fs::write(dir.join("data.txt"), "test data").unwrap();
 
     let manifest = test_manifest("com.test.read-own", Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -255,7 +255,7 @@ fn readonly_filesystem_cannot_write() {
         ..Default::default()
     };
     let manifest = test_manifest("com.test.readonly", perms.clone());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -58,7 +58,7 @@ fn no_shell_cannot_spawn() {
 
     let dir = setup_test_dir("no-shell");
     let manifest = test_manifest("com.test.no-shell", Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -101,7 +101,7 @@ fn with_shell_can_run_declared_command() {
         ..Default::default()
     };
     let manifest = test_manifest("com.test.with-shell", perms.clone());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -138,7 +138,7 @@ fn cannot_write_global_tmp() {
 
     let dir = setup_test_dir("no-global-tmp");
     let manifest = test_manifest("com.test.no-tmp", Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let script = dir.join("test.js");
@@ -176,7 +176,7 @@ fn can_write_own_tmp() {
     let dir = setup_test_dir("own-tmp");
     let plugin_id = "com.test.own-tmp";
     let manifest = test_manifest(plugin_id, Permissions::default());
-    let profile = macos::generate_profile(&manifest, &dir).unwrap();
+    let profile = macos::generate_profile(&manifest, &dir, None).unwrap();
     let profile_path = macos::write_profile(&manifest.id, &profile).unwrap();
 
     let plugin_tmp = format!("/tmp/omni-glass-{}", plugin_id);
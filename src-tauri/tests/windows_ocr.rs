@@ -0,0 +1,97 @@
+//! Windows OCR end to end — Windows.Media.Ocr through the `ocr` module.
+//!
+//! Renders "HELLO WORLD" in a blocky 5×7 font onto a mock screenshot and
+//! reads it back both ways the pipeline does: from a PNG (the fallback
+//! and history path) and in place from the capture's pixels (the snip
+//! path, a region of a larger image).
+//!
+//! Windows-only: the engine is WinRT. Skipped when no OCR language pack
+//! is installed for the user's languages.
+
+#![cfg(target_os = "windows")]
+
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use omni_glass_lib::ocr::{self, RecognitionLevel, RgbaView};
+use std::io::Cursor;
+
+/// Screen pixels per font pixel.
+const SCALE: u32 = 8;
+/// Where the text starts in the mock screenshot.
+const ORIGIN: (u32, u32) = (120, 90);
+
+/// 5×7 glyphs, one row per byte, high bit of the low five = left column.
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        _ => [0; 7],
+    }
+}
+
+/// A 1280×400 white "screenshot" with `text` in black at `ORIGIN`.
+fn screenshot(text: &str) -> RgbaImage {
+    let mut image = RgbaImage::from_pixel(1280, 400, Rgba([255, 255, 255, 255]));
+    for (i, c) in text.chars().enumerate() {
+        let left = ORIGIN.0 + i as u32 * 6 * SCALE;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..5 {
+                if bits & (0x10 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        image.put_pixel(left + col * SCALE + dx, ORIGIN.1 + row as u32 * SCALE + dy, Rgba([0, 0, 0, 255]));
+                    }
+                }
+            }
+        }
+    }
+    image
+}
+
+fn letters(text: &str) -> String {
+    text.chars().filter(|c| c.is_ascii_alphabetic()).collect::<String>().to_uppercase()
+}
+
+fn engine_or_skip() -> bool {
+    if !ocr::available() {
+        eprintln!("SKIP: no Windows OCR language pack for the user's languages");
+        return false;
+    }
+    true
+}
+
+#[test]
+fn reads_text_from_png_bytes() {
+    if !engine_or_skip() {
+        return;
+    }
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(screenshot("HELLO WORLD"))
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .expect("PNG encode");
+    let output = ocr::recognize_text_from_bytes(png, RecognitionLevel::Fast);
+    eprintln!("[TEST] PNG OCR: {:?} in {:.0}ms", output.text, output.latency_ms);
+    assert_eq!(letters(&output.text), "HELLOWORLD");
+    assert!(output.confidence > 0.0);
+}
+
+#[test]
+fn reads_text_in_place_from_a_region_of_the_capture() {
+    if !engine_or_skip() {
+        return;
+    }
+    let image = screenshot("HELLO WORLD");
+    // The selection around the text, as process_snip would crop it
+    let (width, height) = (11 * 6 * SCALE + 40, 7 * SCALE + 40);
+    let view = RgbaView::crop(&image, ORIGIN.0 - 20, ORIGIN.1 - 20, width, height).expect("region inside the image");
+    assert!(view.stride > view.width as usize * 4, "a region, not the whole capture");
+    let output = ocr::recognize_text_from_rgba(&view, RecognitionLevel::Accurate);
+    eprintln!("[TEST] Pixel OCR: {:?} in {:.0}ms", output.text, output.latency_ms);
+    assert_eq!(letters(&output.text), "HELLOWORLD");
+}
@@ -0,0 +1,23 @@
+/**
+ * Action menu — cached answers.
+ *
+ * An answer served from the result cache says when it was produced, and
+ * a Regenerate button next to Copy runs the action again without the
+ * cache (`execute_action` with `regenerate`).
+ */
+
+/** Offer a fresh run of a cached answer; `regenerate` re-runs the action. */
+export function offerRegenerate(cachedAt: number, regenerate: () => Promise<void>): void {
+  const copyBtn = document.getElementById("btn-copy-result");
+  if (!copyBtn) return;
+  const btn = document.createElement("button");
+  btn.textContent = "Regenerate";
+  btn.title = `Cached answer from ${new Date(cachedAt * 1000).toLocaleString()}`;
+  btn.style.cssText = copyBtn.style.cssText;
+  btn.addEventListener("click", async () => {
+    btn.disabled = true;
+    btn.textContent = "Working...";
+    await regenerate();
+  });
+  copyBtn.before(btn);
+}
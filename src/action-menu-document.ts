@@ -0,0 +1,64 @@
+/**
+ * Action menu — Straighten Document (`straighten_document` in src-tauri/src/document).
+ *
+ * `straighten_document` finds the page in the snip, corrects its
+ * perspective, and reads it again. The straightened page replaces the
+ * snip's image and text in the backend, so the text shown here is what
+ * Copy Text and the other actions now use.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { escapeHtml, showFeedback } from "./action-menu-render";
+
+interface Straightened {
+  png: string;
+  width: number;
+  height: number;
+  text: string;
+}
+
+/** Run the Straighten Document action on the current snip. */
+export async function handleStraightenDocument(): Promise<void> {
+  showFeedback("Straightening...");
+  let page: Straightened;
+  try {
+    page = await invoke<Straightened>("straighten_document");
+  } catch (err) {
+    showFeedback(`${err}`, true);
+    return;
+  }
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+
+  const text = page.text || "No text found on the page";
+  actionsEl.innerHTML = `
+    <div style="padding: 12px 14px;">
+      <img src="data:image/png;base64,${page.png}" alt="Straightened document"
+           style="width: 100%; max-height: 220px; object-fit: contain; border-radius: 4px;
+                  background: rgba(255,255,255,0.05);">
+      <div style="margin-top: 8px; max-height: 120px; overflow-y: auto; white-space: pre-wrap;
+                  font-size: 12px; color: rgba(255,255,255,0.85);">${escapeHtml(text)}</div>
+      <div style="margin-top: 8px; display: flex; justify-content: flex-end;">
+        <button id="btn-copy-document" style="
+          background: rgba(74,222,128,0.15);
+          border: 1px solid rgba(74,222,128,0.4);
+          color: #4ade80;
+          padding: 4px 12px;
+          border-radius: 4px;
+          cursor: pointer;
+          font-size: 12px;
+        " ${page.text ? "" : "disabled"}>Copy Text</button>
+      </div>
+    </div>
+  `;
+  try {
+    await getCurrentWebviewWindow().setSize(new LogicalSize(360, 460));
+  } catch { /* resize not critical */ }
+
+  document.getElementById("btn-copy-document")?.addEventListener("click", async () => {
+    await invoke("copy_to_clipboard", { text: page.text });
+    showFeedback("Text copied");
+  });
+}
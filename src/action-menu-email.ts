@@ -0,0 +1,36 @@
+/**
+ * Action menu — Draft Email result (`draft_email` in src-tauri/src/email).
+ *
+ * The draft opens in the system mail client right away (`open_email`:
+ * MAPI on Windows, else a mailto: URL). A mailto: URL has a length
+ * limit; when the body had to be shortened, the full draft is copied
+ * so it can be pasted over.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { showFeedback, closeAfterDelay } from "./action-menu-render";
+import { ActionResult } from "./action-menu-results";
+
+interface Opened {
+  via: "mapi" | "mailto";
+  shortened: boolean;
+}
+
+/** Open an "email" result in the mail client. */
+export async function handleEmailResult(result: ActionResult): Promise<void> {
+  const draft = result.result.email;
+  if (!draft) {
+    showFeedback("The draft is empty", true);
+    return;
+  }
+  const opened = await invoke<Opened>("open_email", { draft });
+  if (opened.shortened) {
+    await invoke("copy_result", { snipId: null, actionId: result.actionId, flavor: null });
+    showFeedback("Opened in your mail client — the body was shortened, full draft copied");
+    closeAfterDelay(2500);
+    return;
+  }
+  const to = draft.to.length ? ` to ${draft.to.join(", ")}` : "";
+  showFeedback(`Opened draft${to} in your mail client`);
+  closeAfterDelay(1200);
+}
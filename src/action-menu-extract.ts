@@ -0,0 +1,194 @@
+/**
+ * Action menu — Extract with Regex (`extract_regex` in src-tauri/src/extract).
+ *
+ * A small workbench: type a pattern, pick a saved recipe, or describe
+ * what to pull out and let `suggest_pattern` write it. `run_extraction`
+ * applies the pattern in Rust and returns every match with its capture
+ * groups; Export CSV goes through the normal file-result save flow.
+ * Recipes are the `extract.recipes` setting.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { escapeHtml, showFeedback } from "./action-menu-render";
+import { ActionResult } from "./action-menu-results";
+import { handleFileResult } from "./action-menu-file";
+import { errorMessage } from "./errors";
+
+interface Extraction {
+  columns: string[];
+  rows: string[][];
+  truncated: boolean;
+}
+
+interface Recipe {
+  name: string;
+  pattern: string;
+  caseInsensitive: boolean;
+}
+
+interface SuggestedPattern {
+  pattern: string;
+  caseInsensitive: boolean;
+  explanation: string;
+}
+
+/** Rows shown in the panel; the CSV has all of them. */
+const SHOWN_ROWS = 50;
+
+const INPUT_STYLE = `
+  flex: 1; min-width: 0; padding: 5px 8px; font-size: 12px; color: #fff;
+  background: rgba(255,255,255,0.08); border: 1px solid rgba(255,255,255,0.15); border-radius: 4px;`;
+const BUTTON_STYLE = `
+  padding: 5px 10px; font-size: 12px; color: #fff; cursor: pointer;
+  background: #0f3460; border: 1px solid rgba(255,255,255,0.15); border-radius: 4px;`;
+
+let recipes: Recipe[] = [];
+
+/** Run the Extract with Regex action on the current snip. */
+export async function handleExtractRegex(): Promise<void> {
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+  recipes = await invoke<{ extract: { recipes: Recipe[] } }>("get_settings")
+    .then((s) => s.extract.recipes)
+    .catch(() => []);
+
+  actionsEl.innerHTML = `
+    <div style="padding: 10px 14px; display: flex; flex-direction: column; gap: 6px;">
+      <div style="display: flex; gap: 6px;">
+        <select id="extract-recipe" style="${INPUT_STYLE}">
+          <option value="">Saved recipes…</option>
+          ${recipes.map((r, i) => `<option value="${i}">${escapeHtml(r.name)}</option>`).join("")}
+        </select>
+        <button id="extract-delete" style="${BUTTON_STYLE}" title="Delete the selected recipe">Delete</button>
+      </div>
+      <div style="display: flex; gap: 6px;">
+        <input id="extract-ask" placeholder="Describe it: order numbers, IPs…" style="${INPUT_STYLE}">
+        <button id="extract-ask-btn" style="${BUTTON_STYLE}">Ask AI</button>
+      </div>
+      <div style="display: flex; gap: 6px; align-items: center;">
+        <input id="extract-pattern" placeholder="Regex, e.g. req=(?P&lt;id&gt;\\w+)" spellcheck="false"
+               style="${INPUT_STYLE} font-family: 'SF Mono', Menlo, monospace;">
+        <label style="font-size: 11px; color: rgba(255,255,255,0.6);" title="Case-insensitive">
+          <input id="extract-ci" type="checkbox"> Aa
+        </label>
+        <button id="extract-run" style="${BUTTON_STYLE}">Run</button>
+      </div>
+      <div id="extract-results" style="max-height: 220px; overflow: auto;"></div>
+      <div style="display: flex; gap: 6px;">
+        <input id="extract-name" placeholder="Recipe name" style="${INPUT_STYLE}">
+        <button id="extract-save" style="${BUTTON_STYLE}">Save</button>
+        <button id="extract-export" style="${BUTTON_STYLE}">Export CSV</button>
+      </div>
+    </div>
+  `;
+  try {
+    await getCurrentWebviewWindow().setSize(new LogicalSize(420, 460));
+  } catch { /* resize not critical */ }
+
+  const el = <T extends HTMLElement>(id: string) => document.getElementById(id) as T;
+  const pattern = el<HTMLInputElement>("extract-pattern");
+  const caseInsensitive = el<HTMLInputElement>("extract-ci");
+  const current = () => ({ pattern: pattern.value, caseInsensitive: caseInsensitive.checked });
+
+  el<HTMLSelectElement>("extract-recipe").addEventListener("change", (e) => {
+    const recipe = recipes[Number((e.target as HTMLSelectElement).value)];
+    if (!recipe) return;
+    pattern.value = recipe.pattern;
+    caseInsensitive.checked = recipe.caseInsensitive;
+    el<HTMLInputElement>("extract-name").value = recipe.name;
+    runExtraction(current());
+  });
+  el("extract-delete").addEventListener("click", () => deleteRecipe(el<HTMLSelectElement>("extract-recipe").value));
+  el("extract-run").addEventListener("click", () => runExtraction(current()));
+  pattern.addEventListener("keydown", (e) => { if (e.key === "Enter") runExtraction(current()); });
+  el("extract-ask-btn").addEventListener("click", async () => {
+    const suggested = await askForPattern(el<HTMLInputElement>("extract-ask").value);
+    if (!suggested) return;
+    pattern.value = suggested.pattern;
+    caseInsensitive.checked = suggested.caseInsensitive;
+    await runExtraction(current());
+    if (suggested.explanation) showFeedback(suggested.explanation);
+  });
+  el("extract-save").addEventListener("click", () => saveRecipe(el<HTMLInputElement>("extract-name").value, current()));
+  el("extract-export").addEventListener("click", async () => {
+    try {
+      await handleFileResult(await invoke<ActionResult>("export_extraction", current()));
+    } catch (err) {
+      showFeedback(errorMessage(err), true);
+    }
+  });
+  pattern.focus();
+}
+
+async function runExtraction(args: { pattern: string; caseInsensitive: boolean }): Promise<void> {
+  const resultsEl = document.getElementById("extract-results");
+  if (!resultsEl) return;
+  let found: Extraction;
+  try {
+    found = await invoke<Extraction>("run_extraction", args);
+  } catch (err) {
+    resultsEl.innerHTML = `<div style="font-size: 12px; color: #fca5a5;">${escapeHtml(`${err}`)}</div>`;
+    return;
+  }
+  const count = `${found.rows.length}${found.truncated ? "+" : ""} match${found.rows.length === 1 ? "" : "es"}`;
+  const cell = (text: string, header = false) => `
+    <${header ? "th" : "td"} style="padding: 2px 6px; text-align: left; white-space: nowrap;
+        border-bottom: 1px solid rgba(255,255,255,0.06); ${header ? "color: rgba(255,255,255,0.5);" : ""}">${escapeHtml(text)}</${header ? "th" : "td"}>`;
+  resultsEl.innerHTML = `
+    <div style="font-size: 11px; color: rgba(255,255,255,0.6); margin-bottom: 4px;">${count}</div>
+    <table style="border-collapse: collapse; font-size: 11px; font-family: 'SF Mono', Menlo, monospace; color: #fff;">
+      <tr>${found.columns.map((c) => cell(c, true)).join("")}</tr>
+      ${found.rows.slice(0, SHOWN_ROWS).map((row) => `<tr>${row.map((c) => cell(c)).join("")}</tr>`).join("")}
+    </table>
+    ${found.rows.length > SHOWN_ROWS ? `<div style="font-size: 11px; color: rgba(255,255,255,0.45);">First ${SHOWN_ROWS} shown; Export CSV has all.</div>` : ""}
+  `;
+}
+
+async function askForPattern(request: string): Promise<SuggestedPattern | null> {
+  const button = document.getElementById("extract-ask-btn") as HTMLButtonElement | null;
+  if (button) { button.disabled = true; button.textContent = "Asking…"; }
+  try {
+    return await invoke<SuggestedPattern>("suggest_pattern", { request });
+  } catch (err) {
+    showFeedback(`${err}`, true);
+    return null;
+  } finally {
+    if (button) { button.disabled = false; button.textContent = "Ask AI"; }
+  }
+}
+
+async function saveRecipe(name: string, args: { pattern: string; caseInsensitive: boolean }): Promise<void> {
+  const trimmed = name.trim();
+  if (!trimmed) {
+    showFeedback("Name the recipe first", true);
+    return;
+  }
+  const recipe = { name: trimmed, ...args };
+  const others = recipes.filter((r) => r.name.toLowerCase() !== trimmed.toLowerCase());
+  await updateRecipes([...others, recipe], `Saved "${trimmed}"`);
+}
+
+async function deleteRecipe(index: string): Promise<void> {
+  const recipe = recipes[Number(index)];
+  if (index === "" || !recipe) return;
+  await updateRecipes(recipes.filter((r) => r !== recipe), `Deleted "${recipe.name}"`);
+}
+
+/** Store the list and refresh the recipe picker. */
+async function updateRecipes(next: Recipe[], message: string): Promise<void> {
+  try {
+    await invoke("update_settings", { patch: { extract: { recipes: next } } });
+  } catch (err) {
+    showFeedback(`${err}`, true);
+    return;
+  }
+  recipes = next;
+  const select = document.getElementById("extract-recipe") as HTMLSelectElement | null;
+  if (select) {
+    select.innerHTML = `<option value="">Saved recipes…</option>`
+      + recipes.map((r, i) => `<option value="${i}">${escapeHtml(r.name)}</option>`).join("");
+  }
+  showFeedback(message);
+}
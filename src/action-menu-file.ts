@@ -0,0 +1,121 @@
+/**
+ * Action menu — file results: a native save dialog, then a "File saved"
+ * panel with Open File, Copy, and Done.
+ *
+ * A CSV export may be saved as TSV or XLSX instead; Rust converts it
+ * (export/mod.rs). A folder the export guard refuses is reported with a
+ * hint to pick another.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-shell";
+import { save } from "@tauri-apps/plugin-dialog";
+import { escapeHtml, showFeedback } from "./action-menu-render";
+import { ActionResult } from "./action-menu-results";
+import { errorCode, errorMessage } from "./errors";
+
+export async function handleFileResult(result: ActionResult): Promise<void> {
+  const content = result.result.text || "";
+  const filename = result.result.filePath || "export.csv";
+  const ext = filename.split(".").pop() || "csv";
+  const isTable = ext.toLowerCase() === "csv";
+
+  try {
+    const chosenPath = await save({
+      defaultPath: filename,
+      filters: isTable
+        ? [
+            { name: "CSV", extensions: ["csv"] },
+            { name: "TSV", extensions: ["tsv"] },
+            { name: "Excel", extensions: ["xlsx"] },
+          ]
+        : [{ name: ext.toUpperCase(), extensions: [ext] }],
+    });
+
+    if (!chosenPath) return;
+
+    // TSV and XLSX are converted from the CSV in Rust (export/mod.rs)
+    const chosenExt = chosenPath.split(".").pop()?.toLowerCase();
+    if (isTable && (chosenExt === "tsv" || chosenExt === "xlsx")) {
+      await invoke<string>("export_table", { filePath: chosenPath, content });
+    } else {
+      await invoke<string>("write_file_to_path", { filePath: chosenPath, content });
+    }
+    console.log(`[ACTION] File written to: ${chosenPath}`);
+
+    const savedName = chosenPath.split("/").pop() || filename;
+
+    const actionsEl = document.getElementById("menu-actions");
+    if (actionsEl) {
+      actionsEl.innerHTML = `
+        <div style="padding: 16px 14px; text-align: center;">
+          <div style="color: #4ade80; font-size: 14px; font-weight: 600; margin-bottom: 6px;">
+            File saved
+          </div>
+          <div style="
+            color: rgba(255,255,255,0.7);
+            font-size: 12px;
+            font-family: 'SF Mono', Menlo, monospace;
+            background: rgba(0,0,0,0.3);
+            padding: 6px 10px;
+            border-radius: 4px;
+            margin-bottom: 12px;
+            word-break: break-all;
+          ">${escapeHtml(savedName)}</div>
+          <div style="display: flex; gap: 8px; justify-content: center;">
+            <button id="btn-open-file" style="
+              background: rgba(74,222,128,0.15);
+              border: 1px solid rgba(74,222,128,0.4);
+              color: #4ade80;
+              padding: 5px 14px;
+              border-radius: 4px;
+              cursor: pointer;
+              font-size: 12px;
+            ">Open File</button>
+            <button id="btn-copy-file" style="
+              background: transparent;
+              border: 1px solid rgba(255,255,255,0.2);
+              color: rgba(255,255,255,0.7);
+              padding: 5px 14px;
+              border-radius: 4px;
+              cursor: pointer;
+              font-size: 12px;
+            ">Copy</button>
+            <button id="btn-close-file" style="
+              background: transparent;
+              border: 1px solid rgba(255,255,255,0.2);
+              color: rgba(255,255,255,0.7);
+              padding: 5px 14px;
+              border-radius: 4px;
+              cursor: pointer;
+              font-size: 12px;
+            ">Done</button>
+          </div>
+        </div>
+      `;
+
+      document.getElementById("btn-open-file")?.addEventListener("click", async () => {
+        try { await open(chosenPath); } catch { /* best effort */ }
+        try { await invoke("close_action_menu"); } catch { /* closing */ }
+      });
+
+      // Table for documents, the file itself for Finder / Explorer
+      document.getElementById("btn-copy-file")?.addEventListener("click", async () => {
+        try {
+          await invoke("copy_result", { snipId: null, actionId: result.actionId, flavor: null });
+          showFeedback("Copied");
+        } catch (err) {
+          showFeedback(`Copy failed: ${err}`, true);
+        }
+      });
+
+      document.getElementById("btn-close-file")?.addEventListener("click", async () => {
+        try { await invoke("close_action_menu"); } catch { /* closing */ }
+      });
+    }
+  } catch (err) {
+    // A refused folder is fixed by saving somewhere else
+    const hint = errorCode(err) === "export.path_refused" ? " Choose another folder." : "";
+    showFeedback(`File export failed: ${errorMessage(err)}${hint}`, true);
+  }
+}
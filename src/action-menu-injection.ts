@@ -0,0 +1,48 @@
+/**
+ * Action menu — prompt-injection warning (`safety` in src-tauri/src).
+ *
+ * Every snip's text is scanned for instructions aimed at the model. A
+ * suspicious snip still gets its menu — prompts only ever carry the text
+ * as untrusted data — but a banner above the menu names what was found,
+ * so answers are read with care. The pipeline emits
+ * "snip-injection-warning" right after opening this window, which may be
+ * before it listens, so the warning is also asked for on load; Rust hands
+ * it out once (`take_injection_warning`).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { escapeHtml } from "./action-menu-render";
+
+interface InjectionScan {
+  score: number;
+  isSuspicious: boolean;
+  signals: string[];
+}
+
+/** Show the warning above the menu, once. */
+function showWarning(scan: InjectionScan): void {
+  if (document.getElementById("injection-warning")) return;
+  const banner = document.createElement("div");
+  banner.id = "injection-warning";
+  banner.setAttribute("role", "alert");
+  banner.style.cssText =
+    "width:280px;box-sizing:border-box;margin-bottom:6px;padding:8px 14px;font-size:12px;color:#fcd34d;" +
+    "background:#2a2410;border:1px solid rgba(252,211,77,0.35);border-radius:8px";
+  const signals = scan.signals.map((s) => escapeHtml(s.replace(/_/g, " "))).join(", ");
+  banner.innerHTML = `
+    <div style="font-weight:600;margin-bottom:2px;">This snip may contain instructions aimed at the AI</div>
+    <div style="color:rgba(255,255,255,0.6);">Check answers before acting on them. Found: ${signals}</div>`;
+  // Outside #action-menu, which is redrawn as the menu streams in
+  document.getElementById("action-menu")!.before(banner);
+}
+
+/** Start showing the current snip's injection warning, if it has one. */
+export function mountInjectionWarning(): void {
+  const take = () =>
+    invoke<InjectionScan | null>("take_injection_warning")
+      .then((scan) => { if (scan) showWarning(scan); })
+      .catch(() => { /* no warning */ });
+  listen<InjectionScan>("snip-injection-warning", () => take());
+  take();
+}
@@ -0,0 +1,46 @@
+/**
+ * Action menu — keyboard.
+ *
+ * Escape closes the menu. Keys bound in settings (`actions.keys`) run
+ * actions directly; "@top" means the first action in the rendered menu.
+ * Copy Text is bound even when the menu doesn't list it.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+let keyBindings: Record<string, string> = { Enter: "@top", c: "copy_text" };
+
+/** Load the bindings from settings; the defaults stay if that fails. */
+export function loadKeyBindings(): void {
+  invoke<{ actions: { keys: Record<string, string> } }>("get_settings")
+    .then((settings) => { keyBindings = settings.actions.keys; })
+    .catch(() => { /* keep defaults */ });
+}
+
+/** The action a key runs in the current menu, if any. */
+function boundAction(key: string): string | undefined {
+  const bound = keyBindings[key];
+  if (!bound) return undefined;
+  const rows = Array.from(document.querySelectorAll<HTMLElement>(".action-row"));
+  return bound === "@top"
+    ? rows[0]?.dataset.actionId
+    : rows.find((row) => row.dataset.actionId === bound)?.dataset.actionId
+      ?? (bound === "copy_text" ? bound : undefined);
+}
+
+/** Run bound actions with `run`, except while `busy()`. */
+export function bindKeys(run: (actionId: string) => Promise<void>, busy: () => boolean): void {
+  document.addEventListener("keydown", async (e: KeyboardEvent) => {
+    if (e.key === "Escape") {
+      try { await invoke("close_action_menu"); } catch { /* closing */ }
+      return;
+    }
+    if (e.ctrlKey || e.metaKey || e.altKey || busy()) return;
+    const key = e.key === " " ? "Space" : e.key.length === 1 ? e.key.toLowerCase() : e.key;
+    const actionId = boundAction(key);
+    if (!actionId) return;
+    e.preventDefault();
+    console.log(`[ACTION] Key ${key}: ${actionId}`);
+    await run(actionId);
+  });
+}
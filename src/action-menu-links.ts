@@ -0,0 +1,98 @@
+/**
+ * Action menu — links: Open Link (`open_url` in src-tauri/src/links) and
+ * the web search actions.
+ *
+ * Links come from `extract_links`, already normalized and checked for
+ * phishing shapes. A single clean link opens right away. Otherwise the
+ * links are listed: OCR repairs and warnings are shown, and a flagged
+ * link opens only on a second click (`open_link` with `confirmed`).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-shell";
+import { escapeHtml, showFeedback, closeAfterDelay } from "./action-menu-render";
+
+/** Actions that open a link found in the snip. */
+export const OPEN_LINK_ACTIONS = ["open_url", "open_link"];
+
+/** Actions that search the web for the snip's text. */
+export const SEARCH_ACTIONS = ["search_web", "search_error", "search_command", "search_online", "search_docs"];
+
+interface Link {
+  url: string;
+  host: string;
+  correctedFrom: string | null;
+  warnings: string[];
+}
+
+/** Run the Open Link action on the current snip. */
+export async function handleOpenUrl(): Promise<void> {
+  const links = await invoke<Link[]>("extract_links");
+  if (links.length === 0) {
+    showFeedback("No link found in this snip", true);
+    return;
+  }
+  if (links.length === 1 && links[0].warnings.length === 0) {
+    await openLink(links[0], false);
+    return;
+  }
+  renderLinkList(links);
+}
+
+/** Search the web for the start of the snip's text. */
+export async function handleSearchWeb(): Promise<void> {
+  const text = await invoke<string>("get_ocr_text");
+  const query = text.slice(0, 200).trim();
+  await open(`https://www.google.com/search?q=${encodeURIComponent(query)}`);
+  showFeedback("Opening search...");
+  closeAfterDelay(800);
+}
+
+async function openLink(link: Link, confirmed: boolean): Promise<void> {
+  await invoke("open_link", { url: link.url, confirmed });
+  showFeedback(`Opening ${link.host}...`);
+  closeAfterDelay(800);
+}
+
+function renderLinkList(links: Link[]): void {
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+
+  actionsEl.innerHTML = links
+    .map((link, i) => {
+      const corrected = link.correctedFrom
+        ? `<div style="font-size: 11px; color: rgba(255,255,255,0.45);">Read as ${escapeHtml(link.correctedFrom)}</div>`
+        : "";
+      const warnings = link.warnings
+        .map((w) => `<div style="font-size: 11px; color: #fca5a5;">⚠ ${escapeHtml(w)}</div>`)
+        .join("");
+      return `
+        <div class="link-row" data-index="${i}" style="
+          padding: 8px 14px; cursor: pointer; border-bottom: 1px solid rgba(255,255,255,0.06);
+        ">
+          <div style="font-size: 13px; font-family: 'SF Mono', Menlo, monospace; word-break: break-all;
+                      color: ${link.warnings.length ? "#fca5a5" : "#fff"};">${escapeHtml(link.url)}</div>
+          ${corrected}${warnings}
+        </div>`;
+    })
+    .join("");
+
+  actionsEl.querySelectorAll<HTMLElement>(".link-row").forEach((row) => {
+    const link = links[Number(row.dataset.index)];
+    let armed = false;
+    row.addEventListener("mouseenter", () => { row.style.background = "rgba(255,255,255,0.06)"; });
+    row.addEventListener("mouseleave", () => { row.style.background = "transparent"; });
+    row.addEventListener("click", async () => {
+      if (link.warnings.length > 0 && !armed) {
+        armed = true;
+        showFeedback("This link looks suspicious. Click it again to open it anyway.", true);
+        return;
+      }
+      try {
+        await openLink(link, armed);
+      } catch (err) {
+        showFeedback(`Error: ${err}`, true);
+      }
+    });
+  });
+}
@@ -0,0 +1,54 @@
+/**
+ * Light Markdown for results shown in the action menu and the result
+ * window: code blocks, inline code, bold, line breaks. Text is escaped
+ * first, so model output can't inject markup.
+ */
+
+import { escapeHtml } from "./action-menu-render";
+
+/** Extract content from the first ``` code block, or null if none found. */
+export function extractCodeBlock(text: string): string | null {
+  const match = text.match(/```[\w]*\n([\s\S]*?)```/);
+  return match ? match[1].trim() : null;
+}
+
+/** Lightweight markdown to HTML: code blocks, inline code, bold, line breaks. */
+export function renderMarkdownLight(text: string): string {
+  let html = escapeHtml(text);
+
+  html = html.replace(
+    /```(\w*)\n([\s\S]*?)```/g,
+    (_match, _lang, code) => `<pre style="
+      background: rgba(0,0,0,0.4);
+      border: 1px solid rgba(255,255,255,0.1);
+      border-radius: 4px;
+      padding: 8px 10px;
+      margin: 6px 0;
+      font-family: 'SF Mono', Menlo, monospace;
+      font-size: 12px;
+      line-height: 1.4;
+      overflow-x: auto;
+      white-space: pre;
+      color: #e2e8f0;
+    ">${code.trim()}</pre>`
+  );
+
+  html = html.replace(
+    /`([^`]+)`/g,
+    `<code style="
+      background: rgba(0,0,0,0.3);
+      padding: 1px 4px;
+      border-radius: 3px;
+      font-family: 'SF Mono', Menlo, monospace;
+      font-size: 12px;
+    ">$1</code>`
+  );
+
+  html = html.replace(/\*\*([^*]+)\*\*/g, "<strong>$1</strong>");
+  html = html.replace(/\n/g, "<br>");
+  html = html.replace(/<pre([^>]*)>([\s\S]*?)<\/pre>/g, (_m, attrs, content) => {
+    return `<pre${attrs}>${content.replace(/<br>/g, "\n")}</pre>`;
+  });
+
+  return html;
+}
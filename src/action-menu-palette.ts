@@ -0,0 +1,78 @@
+/**
+ * Action menu — Extract Color Palette (`extract_palette` in src-tauri/src/palette).
+ *
+ * `extract_palette` clusters the snip's pixels into its dominant colors
+ * and renders them as a swatch strip. Each color row copies its hex code;
+ * "Copy All" copies the whole palette, one code per line.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { showFeedback } from "./action-menu-render";
+
+interface PaletteColor {
+  hex: string;
+  rgb: [number, number, number];
+  share: number;
+}
+
+interface Palette {
+  colors: PaletteColor[];
+  png: string;
+}
+
+/** Run the Extract Color Palette action on the current snip. */
+export async function handleExtractPalette(): Promise<void> {
+  let palette: Palette;
+  try {
+    palette = await invoke<Palette>("extract_palette");
+  } catch (err) {
+    showFeedback(`${err}`, true);
+    return;
+  }
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+
+  const rows = palette.colors.map((c, i) => `
+    <div class="palette-row" data-index="${i}" title="Copy ${c.hex}" style="
+      display: flex; align-items: center; gap: 8px; padding: 3px 4px; cursor: pointer; border-radius: 4px;">
+      <span style="width: 16px; height: 16px; border-radius: 3px; background: ${c.hex};
+                   border: 1px solid rgba(255,255,255,0.2);"></span>
+      <span style="font-family: 'SF Mono', Menlo, monospace; font-size: 12px; color: #fff;">${c.hex}</span>
+      <span style="font-size: 11px; color: rgba(255,255,255,0.45);">rgb(${c.rgb.join(", ")})</span>
+      <span style="margin-left: auto; font-size: 11px; color: rgba(255,255,255,0.45);">${Math.round(c.share * 100)}%</span>
+    </div>`).join("");
+  actionsEl.innerHTML = `
+    <div style="padding: 12px 14px;">
+      <img src="data:image/png;base64,${palette.png}" alt="Palette"
+           style="width: 100%; height: 40px; border-radius: 4px; image-rendering: pixelated;">
+      <div style="margin-top: 8px;">${rows}</div>
+      <div style="margin-top: 8px; display: flex; justify-content: flex-end;">
+        <button id="btn-copy-palette" style="
+          background: rgba(74,222,128,0.15);
+          border: 1px solid rgba(74,222,128,0.4);
+          color: #4ade80;
+          padding: 4px 12px;
+          border-radius: 4px;
+          cursor: pointer;
+          font-size: 12px;
+        ">Copy All</button>
+      </div>
+    </div>
+  `;
+  try {
+    await getCurrentWebviewWindow().setSize(new LogicalSize(320, 140 + palette.colors.length * 24));
+  } catch { /* resize not critical */ }
+
+  const copy = async (text: string, message: string) => {
+    await invoke("copy_to_clipboard", { text });
+    showFeedback(message);
+  };
+  actionsEl.querySelectorAll<HTMLElement>(".palette-row").forEach((row) => {
+    const color = palette.colors[Number(row.dataset.index)];
+    row.addEventListener("click", () => copy(color.hex, `${color.hex} copied`));
+  });
+  document.getElementById("btn-copy-palette")?.addEventListener("click", () =>
+    copy(palette.colors.map((c) => c.hex).join("\n"), "Palette copied"));
+}
@@ -0,0 +1,90 @@
+/**
+ * Action menu — picture snips (src-tauri/src/picture).
+ *
+ * A snip with little or no text gets an image menu instead of a
+ * classified one. Copy Image and Save Image work on the crop itself
+ * (`copy_result` with `copy_image`, `save_snip_image`); Find Similar on
+ * Screen lists other places the snip appears (`find_similar_on_screen`)
+ * and snips the one clicked (`snip_similar`). Describe Image goes
+ * through `execute_action` like any LLM action; Extract Color Palette
+ * has its own handler.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { closeAfterDelay, showFeedback } from "./action-menu-render";
+
+interface SimilarMatch {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  score: number;
+  png: string;
+}
+
+/** Copy the snipped image alone. */
+export async function handleCopyImage(): Promise<void> {
+  await invoke("copy_result", { snipId: null, actionId: "copy_image", flavor: "image" });
+  showFeedback("Image copied");
+  closeAfterDelay(800);
+}
+
+/** Save the snipped image through the save dialog. */
+export async function handleSaveImage(): Promise<void> {
+  const path = await invoke<string | null>("save_snip_image");
+  if (!path) return;
+  showFeedback(`Saved to ${path}`);
+  closeAfterDelay(1200);
+}
+
+/** Look for the snip elsewhere on screen and list what was found. */
+export async function handleFindSimilar(): Promise<void> {
+  showFeedback("Searching the screen...");
+  let found: SimilarMatch[];
+  try {
+    found = await invoke<SimilarMatch[]>("find_similar_on_screen");
+  } catch (err) {
+    showFeedback(`${err}`, true);
+    return;
+  }
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+  if (found.length === 0) {
+    showFeedback("Nothing else on screen looks like this");
+    return;
+  }
+
+  const rows = found.map((m, i) => `
+    <div class="similar-row" data-index="${i}" title="Snip this one" style="
+      display: flex; align-items: center; gap: 10px; padding: 4px; cursor: pointer; border-radius: 4px;">
+      <img src="data:image/png;base64,${m.png}" alt="Match ${i + 1}"
+           style="width: 48px; height: 48px; object-fit: contain; border-radius: 3px;
+                  background: rgba(255,255,255,0.05);">
+      <span style="font-size: 12px; color: #fff;">${m.x}, ${m.y}</span>
+      <span style="font-size: 11px; color: rgba(255,255,255,0.45);">${m.width}×${m.height}</span>
+      <span style="margin-left: auto; font-size: 11px; color: rgba(255,255,255,0.45);">${Math.round(m.score * 100)}%</span>
+    </div>`).join("");
+  actionsEl.innerHTML = `
+    <div style="padding: 12px 14px;">
+      <div style="font-size: 12px; color: rgba(255,255,255,0.6); margin-bottom: 6px;">
+        ${found.length} similar ${found.length === 1 ? "place" : "places"} on screen — click one to snip it
+      </div>
+      ${rows}
+    </div>
+  `;
+  try {
+    await getCurrentWebviewWindow().setSize(new LogicalSize(320, 90 + found.length * 56));
+  } catch { /* resize not critical */ }
+
+  actionsEl.querySelectorAll<HTMLElement>(".similar-row").forEach((row) => {
+    row.addEventListener("click", async () => {
+      try {
+        await invoke("snip_similar", { index: Number(row.dataset.index) });
+      } catch (err) {
+        showFeedback(`${err}`, true);
+      }
+    });
+  });
+}
@@ -0,0 +1,53 @@
+/**
+ * Action menu — Show as QR (`show_qr` in src-tauri/src/qr).
+ *
+ * `generate_qr` picks the payload (a normalized link, a WIFI: login, or
+ * the text itself) and renders it as a PNG, so a phone camera can pick
+ * it up. Used for the snip and, from a text result, for the result.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { LogicalSize } from "@tauri-apps/api/dpi";
+import { escapeHtml, showFeedback } from "./action-menu-render";
+
+interface QrImage {
+  png: string;
+  kind: "url" | "wifi" | "text";
+  caption: string;
+}
+
+/** Results at most this long get a "QR" button. */
+export const QR_MAX_RESULT_CHARS = 500;
+
+/** Run the Show as QR action on the current snip. */
+export async function handleShowQr(): Promise<void> {
+  const text = await invoke<string>("get_ocr_text");
+  await showQr(text);
+}
+
+/** Replace the menu contents with `text` as a QR code. */
+export async function showQr(text: string): Promise<void> {
+  let qr: QrImage;
+  try {
+    qr = await invoke<QrImage>("generate_qr", { text });
+  } catch (err) {
+    showFeedback(`${err}`, true);
+    return;
+  }
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+
+  const hint = qr.kind === "wifi" ? "Scan to join the network" : qr.kind === "url" ? "Scan to open the link" : "Scan to copy the text";
+  actionsEl.innerHTML = `
+    <div style="padding: 12px 14px; text-align: center;">
+      <img src="data:image/png;base64,${qr.png}" alt="QR code"
+           style="width: 240px; height: 240px; image-rendering: pixelated; border-radius: 4px;">
+      <div style="margin-top: 8px; font-size: 12px; color: rgba(255,255,255,0.9); word-break: break-all;">${escapeHtml(qr.caption)}</div>
+      <div style="margin-top: 2px; font-size: 11px; color: rgba(255,255,255,0.45);">${hint}</div>
+    </div>
+  `;
+  try {
+    await getCurrentWebviewWindow().setSize(new LogicalSize(300, 380));
+  } catch { /* resize not critical */ }
+}
@@ -50,6 +50,16 @@ const ICON_MAP: Record<string, string> = {
   eye: "\u{1F441}\u{FE0F}",
   edit: "\u{270F}\u{FE0F}",
   sparkles: "\u{2728}",
+  history: "\u{1F553}",
+  calendar: "\u{1F4C5}",
+  qr: "\u{1F4F1}",
+  currency: "\u{1F4B1}",
+  ruler: "\u{1F4CF}",
+  clock: "\u{1F552}",
+  regex: "\u{1F9EA}",
+  braces: "\u{1F5C2}\u{FE0F}",
+  palette: "\u{1F3A8}",
+  document: "\u{1F9FE}",
 };
 
 export function getIcon(name: string): string {
@@ -103,15 +113,15 @@ export function renderSkeleton(): void {
           <span style="font-size: 16px; width: 20px; text-align: center;">\u{1F4CB}</span>
           <span style="flex: 1; font-size: 14px;">Copy Text</span>
         </div>
-        <div style="padding:8px 14px;display:flex;align-items:center;gap:10px;">
+        <div class="action-placeholder" style="padding:8px 14px;display:flex;align-items:center;gap:10px;">
           <div class="shimmer" style="width:20px;height:20px;"></div>
           <div class="shimmer" style="flex:1;height:14px;"></div>
         </div>
-        <div style="padding:8px 14px;display:flex;align-items:center;gap:10px;">
+        <div class="action-placeholder" style="padding:8px 14px;display:flex;align-items:center;gap:10px;">
           <div class="shimmer" style="width:20px;height:20px;"></div>
           <div class="shimmer" style="flex:1;height:14px;"></div>
         </div>
-        <div style="padding:8px 14px;display:flex;align-items:center;gap:10px;">
+        <div class="action-placeholder" style="padding:8px 14px;display:flex;align-items:center;gap:10px;">
           <div class="shimmer" style="width:20px;height:20px;"></div>
           <div class="shimmer" style="flex:1;height:14px;"></div>
         </div>
@@ -133,6 +143,42 @@ export function updateSummary(skeleton: ActionMenuSkeleton): void {
   }
 }
 
+// ── Streamed actions ────────────────────────────────────────────────
+
+function actionRow(action: Action): string {
+  return `
+        <div class="action-row" data-action-id="${action.id}" style="
+          padding: 0 14px;
+          height: 36px;
+          display: flex;
+          align-items: center;
+          gap: 10px;
+          cursor: pointer;
+          transition: background 0.1s;
+        " title="${escapeHtml(action.description)}">
+          <span style="font-size: 16px; width: 20px; text-align: center;">${getIcon(action.icon)}</span>
+          <span style="flex: 1; font-size: 14px;">${escapeHtml(action.label)}</span>
+        </div>
+      `;
+}
+
+/** Add one action that finished streaming, in place of a placeholder.
+ *  The complete menu replaces these rows, sorted, when it arrives. */
+export function appendAction(action: Action): void {
+  const actionsEl = document.getElementById("menu-actions");
+  if (!actionsEl) return;
+  const rows = actionsEl.querySelectorAll<HTMLElement>(".action-row");
+  if ([...rows].some((row) => row.dataset.actionId === action.id)) return;
+  const placeholder = actionsEl.querySelector(".action-placeholder");
+  if (placeholder) {
+    placeholder.insertAdjacentHTML("beforebegin", actionRow(action));
+    placeholder.remove();
+  } else {
+    actionsEl.insertAdjacentHTML("beforeend", actionRow(action));
+  }
+  console.log(`[RENDER] Streamed action: ${action.id}`);
+}
+
 // ── Complete menu (State 2) ─────────────────────────────────────────
 
 export function renderMenu(menu: ActionMenu): void {
@@ -160,24 +206,7 @@ export function renderMenu(menu: ActionMenu): void {
         ${escapeHtml(menu.summary)}
       </div>
       <div id="menu-actions">
-      ${sorted
-        .map(
-          (action) => `
-        <div class="action-row" data-action-id="${action.id}" style="
-          padding: 0 14px;
-          height: 36px;
-          display: flex;
-          align-items: center;
-          gap: 10px;
-          cursor: pointer;
-          transition: background 0.1s;
-        " title="${escapeHtml(action.description)}">
-          <span style="font-size: 16px; width: 20px; text-align: center;">${getIcon(action.icon)}</span>
-          <span style="flex: 1; font-size: 14px;">${escapeHtml(action.label)}</span>
-        </div>
-      `
-        )
-        .join("")}
+      ${sorted.map(actionRow).join("")}
       </div>
     </div>
   `;
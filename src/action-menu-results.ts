@@ -1,17 +1,19 @@
 /**
  * Action menu result handlers — displays results from LLM execute.
  *
- * Handles text results (with code block extraction), file export
- * (native save dialog), and command confirmation (opens dialog window).
+ * Handles text results (with code block extraction, and "Pop Out" into
+ * the result window) and command confirmation (opens dialog window).
+ * File exports are in action-menu-file.ts.
  */
 
 import { invoke } from "@tauri-apps/api/core";
 import { emit } from "@tauri-apps/api/event";
 import { open } from "@tauri-apps/plugin-shell";
-import { save } from "@tauri-apps/plugin-dialog";
 import { WebviewWindow, getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { LogicalSize } from "@tauri-apps/api/dpi";
-import { escapeHtml, showFeedback, closeAfterDelay } from "./action-menu-render";
+import { showFeedback, closeAfterDelay } from "./action-menu-render";
+import { extractCodeBlock, renderMarkdownLight } from "./action-menu-markdown";
+import { showQr, QR_MAX_RESULT_CHARS } from "./action-menu-qr";
 
 // ── Types ────────────────────────────────────────────────────────────
 
@@ -22,11 +24,22 @@ export interface ActionResultBody {
   command?: string;
   clipboardContent?: string;
   mimeType?: string;
+  /** Set on "email" results (src-tauri/src/email). */
+  email?: EmailDraft;
+}
+
+export interface EmailDraft {
+  to: string[];
+  cc: string[];
+  subject: string;
+  body: string;
 }
 
 export interface ActionResultMeta {
   tokensUsed?: number;
   processingNote?: string;
+  /** Unix seconds — set when the result came from the history cache. */
+  cachedAt?: number;
 }
 
 export interface ActionResult {
@@ -38,14 +51,34 @@ export interface ActionResult {
 
 // ── Text result ──────────────────────────────────────────────────────
 
-export async function showTextResult(text: string): Promise<void> {
+/**
+ * The link of a plugin `link` result (src-tauri/src/mcp/render): a
+ * `text/uri-list` text whose last line is the URL.
+ */
+export function resultLink(body: ActionResultBody): string | undefined {
+  if (body.mimeType !== "text/uri-list" || !body.text) return undefined;
+  return body.text.trim().split("\n").pop();
+}
+
+/**
+ * Show a text answer. With `actionId`, "Copy All" copies it in rich
+ * formats via copy_result; otherwise as plain text. "Share" (only with
+ * `actionId`) opens the OS share sheet via share_result, and "Pop Out"
+ * moves it into the result window. A code block in the answer gets its
+ * own copy button. "Open Link" opens `link`, or else the first URL in
+ * the answer.
+ */
+export async function showTextResult(text: string, actionId?: string, link?: string): Promise<void> {
   const container = document.getElementById("action-menu")!;
   const wrapper = container.querySelector("div")!;
   wrapper.style.width = "380px";
 
   const codeBlock = extractCodeBlock(text);
+  // format_data's code block is the pretty-printed document, not a fix
+  const formatted = actionId === "format_data";
   const rendered = renderMarkdownLight(text);
-  const urlMatch = text.match(/https?:\/\/[^\s)]+/);
+  const urlMatch = link ? [link] : text.match(/https?:\/\/[^\s)]+/);
+  const qrable = text.trim().length <= QR_MAX_RESULT_CHARS;
 
   const actionsEl = document.getElementById("menu-actions");
   if (actionsEl) {
@@ -83,7 +116,34 @@ export async function showTextResult(text: string): Promise<void> {
           border-radius: 4px;
           cursor: pointer;
           font-size: 12px;
-        ">Copy Fix</button>` : ""}
+        ">${formatted ? "Copy Formatted" : "Copy Fix"}</button>` : ""}
+        ${qrable ? `<button id="btn-show-qr" title="Show as QR code" style="
+          background: transparent;
+          border: 1px solid rgba(255,255,255,0.2);
+          color: rgba(255,255,255,0.8);
+          padding: 4px 12px;
+          border-radius: 4px;
+          cursor: pointer;
+          font-size: 12px;
+        ">QR</button>` : ""}
+        ${actionId ? `<button id="btn-share-result" title="Share to Messages, AirDrop, Mail…" style="
+          background: transparent;
+          border: 1px solid rgba(255,255,255,0.2);
+          color: rgba(255,255,255,0.8);
+          padding: 4px 12px;
+          border-radius: 4px;
+          cursor: pointer;
+          font-size: 12px;
+        ">Share</button>` : ""}
+        ${actionId ? `<button id="btn-pop-out" title="Keep this result in its own window" style="
+          background: transparent;
+          border: 1px solid rgba(255,255,255,0.2);
+          color: rgba(255,255,255,0.8);
+          padding: 4px 12px;
+          border-radius: 4px;
+          cursor: pointer;
+          font-size: 12px;
+        ">Pop Out</button>` : ""}
         <button id="btn-copy-result" style="
           background: transparent;
           border: 1px solid rgba(255,255,255,0.2);
@@ -115,13 +175,37 @@ export async function showTextResult(text: string): Promise<void> {
     if (codeBlock) {
       document.getElementById("btn-copy-fix")?.addEventListener("click", async () => {
         await invoke("copy_to_clipboard", { text: codeBlock });
-        showFeedback("Fix copied");
+        showFeedback(formatted ? "Formatted copy on clipboard" : "Fix copied");
         closeAfterDelay(600);
       });
     }
 
+    if (qrable) {
+      document.getElementById("btn-show-qr")?.addEventListener("click", () => showQr(text));
+    }
+
+    document.getElementById("btn-share-result")?.addEventListener("click", async () => {
+      try {
+        await invoke("share_result", { snipId: null, actionId });
+      } catch (err) {
+        showFeedback(`Share failed: ${err}`, true);
+      }
+    });
+
+    document.getElementById("btn-pop-out")?.addEventListener("click", async () => {
+      try {
+        await invoke("pop_out_result");
+      } catch (err) {
+        showFeedback(`Pop out failed: ${err}`, true);
+      }
+    });
+
     document.getElementById("btn-copy-result")?.addEventListener("click", async () => {
-      await invoke("copy_to_clipboard", { text });
+      if (actionId) {
+        await invoke("copy_result", { snipId: null, actionId, flavor: null });
+      } else {
+        await invoke("copy_to_clipboard", { text });
+      }
       showFeedback("Copied");
       closeAfterDelay(600);
     });
@@ -144,80 +228,6 @@ export async function showTextResult(text: string): Promise<void> {
   }
 }
 
-// ── File result ──────────────────────────────────────────────────────
-
-export async function handleFileResult(result: ActionResult): Promise<void> {
-  const content = result.result.text || "";
-  const filename = result.result.filePath || "export.csv";
-  const ext = filename.split(".").pop() || "csv";
-
-  try {
-    const chosenPath = await save({
-      defaultPath: filename,
-      filters: [{ name: ext.toUpperCase(), extensions: [ext] }],
-    });
-
-    if (!chosenPath) return;
-
-    await invoke<string>("write_file_to_path", { filePath: chosenPath, content });
-    console.log(`[ACTION] File written to: ${chosenPath}`);
-
-    const savedName = chosenPath.split("/").pop() || filename;
-
-    const actionsEl = document.getElementById("menu-actions");
-    if (actionsEl) {
-      actionsEl.innerHTML = `
-        <div style="padding: 16px 14px; text-align: center;">
-          <div style="color: #4ade80; font-size: 14px; font-weight: 600; margin-bottom: 6px;">
-            File saved
-          </div>
-          <div style="
-            color: rgba(255,255,255,0.7);
-            font-size: 12px;
-            font-family: 'SF Mono', Menlo, monospace;
-            background: rgba(0,0,0,0.3);
-            padding: 6px 10px;
-            border-radius: 4px;
-            margin-bottom: 12px;
-            word-break: break-all;
-          ">${escapeHtml(savedName)}</div>
-          <div style="display: flex; gap: 8px; justify-content: center;">
-            <button id="btn-open-file" style="
-              background: rgba(74,222,128,0.15);
-              border: 1px solid rgba(74,222,128,0.4);
-              color: #4ade80;
-              padding: 5px 14px;
-              border-radius: 4px;
-              cursor: pointer;
-              font-size: 12px;
-            ">Open File</button>
-            <button id="btn-close-file" style="
-              background: transparent;
-              border: 1px solid rgba(255,255,255,0.2);
-              color: rgba(255,255,255,0.7);
-              padding: 5px 14px;
-              border-radius: 4px;
-              cursor: pointer;
-              font-size: 12px;
-            ">Done</button>
-          </div>
-        </div>
-      `;
-
-      document.getElementById("btn-open-file")?.addEventListener("click", async () => {
-        try { await open(chosenPath); } catch { /* best effort */ }
-        try { await invoke("close_action_menu"); } catch { /* closing */ }
-      });
-
-      document.getElementById("btn-close-file")?.addEventListener("click", async () => {
-        try { await invoke("close_action_menu"); } catch { /* closing */ }
-      });
-    }
-  } catch (err) {
-    showFeedback(`File export failed: ${err}`, true);
-  }
-}
-
 // ── Command result ───────────────────────────────────────────────────
 
 export async function handleCommandResult(result: ActionResult): Promise<void> {
@@ -252,52 +262,3 @@ export async function handleCommandResult(result: ActionResult): Promise<void> {
     showFeedback(`Error: ${err}`, true);
   }
 }
-
-// ── Internal helpers ─────────────────────────────────────────────────
-
-/** Extract content from the first ``` code block, or null if none found. */
-function extractCodeBlock(text: string): string | null {
-  const match = text.match(/```[\w]*\n([\s\S]*?)```/);
-  return match ? match[1].trim() : null;
-}
-
-/** Lightweight markdown to HTML: code blocks, inline code, bold, line breaks. */
-function renderMarkdownLight(text: string): string {
-  let html = escapeHtml(text);
-
-  html = html.replace(
-    /```(\w*)\n([\s\S]*?)```/g,
-    (_match, _lang, code) => `<pre style="
-      background: rgba(0,0,0,0.4);
-      border: 1px solid rgba(255,255,255,0.1);
-      border-radius: 4px;
-      padding: 8px 10px;
-      margin: 6px 0;
-      font-family: 'SF Mono', Menlo, monospace;
-      font-size: 12px;
-      line-height: 1.4;
-      overflow-x: auto;
-      white-space: pre;
-      color: #e2e8f0;
-    ">${code.trim()}</pre>`
-  );
-
-  html = html.replace(
-    /`([^`]+)`/g,
-    `<code style="
-      background: rgba(0,0,0,0.3);
-      padding: 1px 4px;
-      border-radius: 3px;
-      font-family: 'SF Mono', Menlo, monospace;
-      font-size: 12px;
-    ">$1</code>`
-  );
-
-  html = html.replace(/\*\*([^*]+)\*\*/g, "<strong>$1</strong>");
-  html = html.replace(/\n/g, "<br>");
-  html = html.replace(/<pre([^>]*)>([\s\S]*?)<\/pre>/g, (_m, attrs, content) => {
-    return `<pre${attrs}>${content.replace(/<br>/g, "\n")}</pre>`;
-  });
-
-  return html;
-}
@@ -6,22 +6,35 @@
  *   - Copy Text button (always available — OCR text is already stored)
  *   - 3 shimmer placeholders for loading actions
  *
+ * Actions stream in one by one, each replacing a placeholder as soon as
+ * its JSON object is complete.
+ *
  * State 2 (Complete): Fills in when the streaming LLM response finishes.
  *   - Real summary text (replaces shimmer)
  *   - All action buttons with icons and labels
  *
  * Events from Rust:
  *   - "action-menu-skeleton": { contentType, summary } — updates summary text
+ *   - "action-menu-item": one Action — appended while the menu streams
  *   - "action-menu-complete": full ActionMenu JSON — renders all actions
+ *   - "action-auto-execute": a default action queued for this content type
+ *   - "snip-injection-warning": handled in action-menu-injection.ts
+ *
+ * Keys are handled in action-menu-keys.ts, links and web search in
+ * action-menu-links.ts, and cached answers in action-menu-cache.ts.
  */
 
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { open } from "@tauri-apps/plugin-shell";
+import { errorCode, errorMessage } from "./errors";
+import { mountAnnouncer } from "./announcer";
+import { mountInjectionWarning } from "./action-menu-injection";
 
 import {
+  Action,
   ActionMenu,
   ActionMenuSkeleton,
+  appendAction,
   renderSkeleton,
   updateSummary,
   renderMenu,
@@ -33,9 +46,20 @@ import {
 import {
   ActionResult,
   showTextResult,
-  handleFileResult,
+  resultLink,
   handleCommandResult,
 } from "./action-menu-results";
+import { handleFileResult } from "./action-menu-file";
+
+import { OPEN_LINK_ACTIONS, SEARCH_ACTIONS, handleOpenUrl, handleSearchWeb } from "./action-menu-links";
+import { bindKeys, loadKeyBindings } from "./action-menu-keys";
+import { offerRegenerate } from "./action-menu-cache";
+import { handleEmailResult } from "./action-menu-email";
+import { handleShowQr } from "./action-menu-qr";
+import { handleExtractRegex } from "./action-menu-extract";
+import { handleExtractPalette } from "./action-menu-palette";
+import { handleStraightenDocument } from "./action-menu-document";
+import { handleCopyImage, handleFindSimilar, handleSaveImage } from "./action-menu-picture";
 
 // ── State ───────────────────────────────────────────────────────────
 
@@ -44,24 +68,61 @@ let actionInProgress = false;
 
 // ── Action execution ─────────────────────────────────────────────────
 
-async function executeAction(actionId: string): Promise<void> {
+async function executeAction(actionId: string, regenerate = false): Promise<void> {
   try {
     // Local actions — no LLM call needed
     if (actionId === "copy_text" || actionId === "copy_command" || actionId === "copy_traceback" || actionId === "copy_code") {
       const text = await invoke<string>("get_ocr_text");
-      await invoke("copy_to_clipboard", { text });
+      await invoke("copy_result", { snipId: null, actionId, flavor: null });
       showFeedback(`Copied ${text.length} chars`);
       closeAfterDelay(800);
       return;
     }
 
-    if (actionId === "search_web" || actionId === "search_error" || actionId === "search_command" || actionId === "search_online" || actionId === "search_docs") {
-      const text = await invoke<string>("get_ocr_text");
-      const query = text.slice(0, 200).trim();
-      const url = `https://www.google.com/search?q=${encodeURIComponent(query)}`;
-      await open(url);
-      showFeedback("Opening search...");
-      closeAfterDelay(800);
+    if (SEARCH_ACTIONS.includes(actionId)) {
+      await handleSearchWeb();
+      return;
+    }
+
+    if (OPEN_LINK_ACTIONS.includes(actionId)) {
+      await handleOpenUrl();
+      return;
+    }
+
+    if (actionId === "show_qr") {
+      await handleShowQr();
+      return;
+    }
+
+    if (actionId === "extract_palette") {
+      await handleExtractPalette();
+      return;
+    }
+
+    if (actionId === "straighten_document") {
+      await handleStraightenDocument();
+      return;
+    }
+
+    if (actionId === "copy_image") {
+      await handleCopyImage();
+      return;
+    }
+
+    if (actionId === "save_image") {
+      await handleSaveImage();
+      return;
+    }
+
+    if (actionId === "find_similar") {
+      await handleFindSimilar();
+      return;
+    }
+
+    // The workbench takes typing and opens a save dialog; keep the menu open
+    if (actionId === "extract_regex") {
+      actionInProgress = true;
+      await handleExtractRegex();
       return;
     }
 
@@ -69,7 +130,7 @@ async function executeAction(actionId: string): Promise<void> {
     actionInProgress = true;
     showLoading(actionId);
 
-    const result = await invoke<ActionResult>("execute_action", { actionId });
+    const result = await invoke<ActionResult>("execute_action", { actionId, regenerate });
     console.log(`[ACTION] Result: status=${result.status}, type=${result.result.type}`);
 
     if (result.status === "error") {
@@ -78,29 +139,44 @@ async function executeAction(actionId: string): Promise<void> {
       return;
     }
 
-    switch (result.result.type) {
-      case "text":
-        showTextResult(result.result.text || "No content returned.");
-        break;
-      case "clipboard":
-        if (result.result.clipboardContent) {
-          await invoke("copy_to_clipboard", { text: result.result.clipboardContent });
-          showFeedback("Copied to clipboard");
-          closeAfterDelay(800);
-        }
-        break;
-      case "file":
-        await handleFileResult(result);
-        break;
-      case "command":
-        await handleCommandResult(result);
-        break;
-      default:
-        showFeedback(`Unknown result type: ${result.result.type}`, true);
-    }
+    await showResult(result);
   } catch (err) {
     console.error(`[ACTION] Failed to execute ${actionId}:`, err);
-    showFeedback(`Error: ${err}`, true);
+    showFeedback(errorMessage(err), true);
+    // Nothing ran yet; the fix is a key in Settings
+    if (errorCode(err) === "llm.no_api_key") {
+      invoke("open_settings").catch(() => { /* best effort */ });
+    }
+  }
+}
+
+/** Show a successful result according to its type. */
+async function showResult(result: ActionResult): Promise<void> {
+  const actionId = result.actionId;
+  switch (result.result.type) {
+    case "text":
+      showTextResult(result.result.text || "No content returned.", actionId, resultLink(result.result));
+      if (result.metadata?.cachedAt) offerRegenerate(result.metadata.cachedAt, () => executeAction(actionId, true));
+      invoke("mark_result_viewed").catch(() => { /* best effort */ });
+      break;
+    case "clipboard":
+      if (result.result.clipboardContent) {
+        await invoke("copy_result", { snipId: null, actionId, flavor: null });
+        showFeedback("Copied to clipboard");
+        closeAfterDelay(800);
+      }
+      break;
+    case "file":
+      await handleFileResult(result);
+      break;
+    case "command":
+      await handleCommandResult(result);
+      break;
+    case "email":
+      await handleEmailResult(result);
+      break;
+    default:
+      showFeedback(`Unknown result type: ${result.result.type}`, true);
   }
 }
 
@@ -129,15 +205,45 @@ async function init(): Promise<void> {
     updateSummary(event.payload);
   });
 
+  listen<Action>("action-menu-item", (event) => {
+    if (!menuRendered) appendAction(event.payload);
+  });
+
   listen<ActionMenu>("action-menu-complete", (event) => {
     console.log("[RENDER] Received complete event:", event.payload.contentType);
     menuRendered = true;
-    renderMenu(event.payload);
+    // A streamed action may already be running; keep its result on screen
+    if (!actionInProgress) renderMenu(event.payload);
   });
 
+  listen<string>("action-auto-execute", () => runAutoAction());
+
+  loadKeyBindings();
+
+  if (await showRestoredResult()) return;
   pollForMenu();
 }
 
+/** Run the queued default action, if any. Rust hands it out only once. */
+async function runAutoAction(): Promise<void> {
+  const actionId = await invoke<string | null>("take_auto_action").catch(() => null);
+  if (!actionId || actionInProgress) return;
+  console.log(`[ACTION] Default action: ${actionId}`);
+  await executeAction(actionId);
+}
+
+/** Reopened with a result: an answer never seen before a restart, or a
+ *  clicked result notification. */
+async function showRestoredResult(): Promise<boolean> {
+  const restored = await invoke<ActionResult | null>("take_restored_result").catch(() => null);
+  if (!restored) return false;
+  console.log(`[RENDER] Showing restored result for ${restored.actionId}`);
+  menuRendered = true;
+  actionInProgress = true;
+  await showResult(restored).catch((err) => showFeedback(`Error: ${err}`, true));
+  return true;
+}
+
 // ── Polling ──────────────────────────────────────────────────────────
 
 async function pollForMenu(): Promise<void> {
@@ -157,6 +263,7 @@ async function pollForMenu(): Promise<void> {
       menuRendered = true;
       renderMenu(menu);
       clearInterval(timer);
+      runAutoAction();
     } catch {
       if (polls >= MAX_POLLS) {
         console.log(`[RENDER] Poll timeout after ${polls} attempts`);
@@ -168,15 +275,13 @@ async function pollForMenu(): Promise<void> {
 
 // ── Global event handlers ────────────────────────────────────────────
 
-document.addEventListener("keydown", async (e: KeyboardEvent) => {
-  if (e.key === "Escape") {
-    try { await invoke("close_action_menu"); } catch { /* closing */ }
-  }
-});
+bindKeys((actionId) => executeAction(actionId), () => actionInProgress);
 
 window.addEventListener("blur", async () => {
   if (!menuRendered || actionInProgress) return;
   try { await invoke("close_action_menu"); } catch { /* closing */ }
 });
 
+mountAnnouncer();
+mountInjectionWarning();
 init();
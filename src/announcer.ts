@@ -0,0 +1,35 @@
+/**
+ * Screen reader announcements (`selection` in src-tauri/src).
+ *
+ * The backend emits "a11y-announce" with a sentence to speak — the
+ * keyboard selection's size, OCR finishing — and this puts it in a
+ * visually hidden `aria-live` region. A window that opens after an
+ * announcement (the action menu, opened as OCR ends) asks for it once
+ * with `take_announcement`.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+/** Add the live region to this page and start speaking announcements. */
+export function mountAnnouncer(): void {
+  const region = document.createElement("div");
+  region.setAttribute("role", "status");
+  region.setAttribute("aria-live", "polite");
+  region.setAttribute("aria-atomic", "true");
+  region.style.cssText =
+    "position:absolute;width:1px;height:1px;margin:-1px;overflow:hidden;clip:rect(0 0 0 0);white-space:nowrap;border:0";
+  document.body.appendChild(region);
+
+  // Screen readers skip a region whose text didn't change, so clear it
+  // first and set the text on the next frame.
+  const speak = (text: string) => {
+    region.textContent = "";
+    requestAnimationFrame(() => { region.textContent = text; });
+  };
+
+  listen<string>("a11y-announce", (event) => speak(event.payload));
+  invoke<string | null>("take_announcement")
+    .then((text) => { if (text) speak(text); })
+    .catch(() => { /* nothing to say */ });
+}
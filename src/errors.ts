@@ -0,0 +1,31 @@
+/**
+ * Command errors — the `OmniError` shape some Tauri commands reject with.
+ *
+ * The snip, execute, and text-launcher commands, and those for capture
+ * permission, plugins, saving, and the network settings, reject with
+ * `{ kind, code, message }` (see src-tauri/src/error/).
+ * Other commands still reject with a plain string; both are handled here.
+ */
+
+export interface OmniError {
+  /** "capture" | "ocr" | "llm" | "safety" | "plugin" | "export" | "network" | "other" */
+  kind: string;
+  /** Stable reason, e.g. "llm.no_api_key" or "export.path_refused". */
+  code: string;
+  /** Localized, ready to show. */
+  message: string;
+}
+
+export function isOmniError(err: unknown): err is OmniError {
+  return typeof err === "object" && err !== null && "code" in err && "message" in err;
+}
+
+/** The code of a rejected command, or "other" for plain-string errors. */
+export function errorCode(err: unknown): string {
+  return isOmniError(err) ? err.code : "other";
+}
+
+/** Text to show for a rejected command. */
+export function errorMessage(err: unknown): string {
+  return isOmniError(err) ? err.message : String(err);
+}
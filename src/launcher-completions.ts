@@ -0,0 +1,103 @@
+/**
+ * Slash-command completions for the text launcher.
+ *
+ * Plugins declare launcher commands in their manifest (`/jira PROJ-123`).
+ * While the input starts with "/", matching commands are listed under it
+ * with their usage line; argument values declared by the plugin are
+ * suggested once the command name is typed. Tab accepts the first match.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+
+interface CommandArg {
+  name: string;
+  description: string;
+  required: boolean;
+  rest: boolean;
+  values: string[];
+}
+
+interface LauncherCommand {
+  name: string;
+  pluginId: string;
+  usage: string;
+  description: string;
+  args: CommandArg[];
+}
+
+interface Suggestion {
+  insert: string;
+  label: string;
+  detail: string;
+}
+
+let commands: LauncherCommand[] = [];
+
+function suggestionsFor(value: string): Suggestion[] {
+  if (!value.startsWith("/")) return [];
+  const [typedName, ...words] = value.slice(1).split(/\s+/);
+
+  // Still typing the command name
+  if (words.length === 0) {
+    return commands
+      .filter((c) => c.name.startsWith(typedName))
+      .map((c) => ({ insert: `/${c.name} `, label: c.usage, detail: c.description }));
+  }
+
+  // Typing an argument — suggest declared values for it
+  const cmd = commands.find((c) => c.name === typedName);
+  const arg = cmd?.args[words.length - 1];
+  if (!cmd || !arg || arg.values.length === 0) return [];
+  const partial = words[words.length - 1];
+  const head = value.slice(0, value.length - partial.length);
+  return arg.values
+    .filter((v) => v.startsWith(partial) && v !== partial)
+    .map((v) => ({ insert: `${head}${v} `, label: v, detail: arg.description }));
+}
+
+function render(area: HTMLElement, items: Suggestion[]): void {
+  area.innerHTML = items
+    .slice(0, 6)
+    .map(
+      (s) => `
+    <div style="padding: 6px 14px; font-size: 13px; color: #e2e8f0; border-top: 1px solid rgba(255,255,255,0.06);">
+      <span style="font-family: ui-monospace, monospace;">${escapeHtml(s.label)}</span>
+      <span style="color: #94a3b8; margin-left: 8px;">${escapeHtml(s.detail)}</span>
+    </div>`,
+    )
+    .join("");
+}
+
+/** Wire completions to the launcher input. `onChange` re-fits the window. */
+export async function attachCompletions(
+  input: HTMLInputElement,
+  area: HTMLElement,
+  onChange: () => Promise<void>,
+): Promise<void> {
+  try {
+    commands = await invoke<LauncherCommand[]>("get_launcher_commands");
+  } catch (err) {
+    console.warn("[LAUNCHER] Could not load slash commands:", err);
+  }
+  if (commands.length === 0) return;
+  input.placeholder = "Ask anything, or type / for plugin commands...";
+
+  let current: Suggestion[] = [];
+  input.addEventListener("input", async () => {
+    current = suggestionsFor(input.value);
+    render(area, current);
+    await onChange();
+  });
+  input.addEventListener("keydown", async (e) => {
+    if (e.key === "Tab" && current.length > 0) {
+      e.preventDefault();
+      input.value = current[0].insert;
+      input.dispatchEvent(new Event("input"));
+    }
+    if (e.key === "Enter") {
+      current = [];
+      render(area, current);
+    }
+  });
+}
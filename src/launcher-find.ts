@@ -0,0 +1,122 @@
+/**
+ * Find Text on Screen — the launcher's `find on screen: <text>` command
+ * (src-tauri/src/search).
+ *
+ * Every monitor is captured and read with word boxes, and the places the
+ * text appears are listed under the input; the launcher is hidden while
+ * the screen is captured, so it doesn't find its own input. The first
+ * place is ringed on screen right away. Clicking a place rings it
+ * (`show_screen_match`); its Point button also moves the mouse pointer
+ * there.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "./errors";
+
+/** What the palette's Find Text on Screen item fills in. */
+export const FIND_PREFIX = "find on screen: ";
+
+interface ScreenMatch {
+  monitor: number;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  text: string;
+  line: string;
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+/** The text to find, if `input` is a find command (`search::matcher::query_of`). */
+export function findQuery(input: string): string | null {
+  const match = /^\s*find on screen:(.*)$/i.exec(input);
+  return match ? match[1].trim() : null;
+}
+
+function renderMessage(area: HTMLElement, message: string, isError = false): void {
+  area.innerHTML = `
+    <div style="
+      padding: 10px 14px;
+      font-size: 13px;
+      color: ${isError ? "#fca5a5" : "rgba(255,255,255,0.5)"};
+      border-top: 1px solid rgba(255,255,255,0.08);
+    ">${escapeHtml(message)}</div>
+  `;
+}
+
+function renderMatches(area: HTMLElement, found: ScreenMatch[]): void {
+  const several = new Set(found.map((m) => m.monitor)).size > 1;
+  area.innerHTML = found
+    .map(
+      (m, i) => `
+    <div data-index="${i}" title="Show on screen" style="
+      display: flex; align-items: baseline; gap: 8px;
+      padding: 6px 14px; font-size: 13px; cursor: pointer;
+      border-top: 1px solid rgba(255,255,255,0.06);
+    ">
+      <span style="color: #e2e8f0; white-space: nowrap;">${escapeHtml(m.text)}</span>
+      <span style="color: #94a3b8; flex: 1; white-space: nowrap; overflow: hidden; text-overflow: ellipsis;">${escapeHtml(m.line)}</span>
+      <span style="color: #64748b; font-size: 11px; white-space: nowrap;">${several ? `Monitor ${m.monitor} · ` : ""}${m.x}, ${m.y}</span>
+      <button data-point="${i}" style="
+        background: transparent;
+        border: 1px solid rgba(255,255,255,0.2);
+        color: rgba(255,255,255,0.7);
+        padding: 1px 8px;
+        border-radius: 4px;
+        cursor: pointer;
+        font-size: 11px;
+      ">Point</button>
+    </div>`,
+    )
+    .join("");
+}
+
+async function showMatch(index: number, point: boolean): Promise<void> {
+  try {
+    await invoke("show_screen_match", { index, point });
+  } catch (err) {
+    console.error("[LAUNCHER] Could not show the match:", err);
+  }
+}
+
+/** Look for `query` on screen and list the places found in `area`. */
+export async function runFind(query: string, area: HTMLElement, onChange: () => Promise<void>): Promise<void> {
+  area.onclick = null;
+  if (!query) {
+    renderMessage(area, "Type the text to find after “find on screen:”");
+    await onChange();
+    return;
+  }
+  renderMessage(area, "Reading the screen...");
+  await onChange();
+
+  let found: ScreenMatch[];
+  try {
+    found = await invoke<ScreenMatch[]>("find_on_screen", { query });
+  } catch (err) {
+    renderMessage(area, errorMessage(err), true);
+    await onChange();
+    return;
+  }
+  if (found.length === 0) {
+    renderMessage(area, `“${query}” isn't on screen`);
+    await onChange();
+    return;
+  }
+
+  renderMatches(area, found);
+  area.onclick = async (e) => {
+    const target = e.target as HTMLElement;
+    const button = target.closest<HTMLElement>("[data-point]");
+    const row = target.closest<HTMLElement>("[data-index]");
+    if (button) await showMatch(Number(button.dataset.point), true);
+    else if (row) await showMatch(Number(row.dataset.index), false);
+  };
+  await onChange();
+  await showMatch(0, false);
+}
@@ -0,0 +1,115 @@
+/**
+ * Command palette for the text launcher.
+ *
+ * As you type, `search_launcher` fuzzy-matches built-in actions (snip,
+ * region presets, settings, pause history), plugin slash commands, and
+ * history snips. Arrow keys or the mouse pick one: actions and snips run
+ * through `run_launcher_item` (the launcher closes), a slash command is
+ * filled into the input for its arguments, and so is Find Text on Screen
+ * (`find on screen: `, launcher-find.ts). Enter with nothing picked
+ * falls through to the LLM router in text-launcher.ts.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { FIND_PREFIX } from "./launcher-find";
+
+interface PaletteItem {
+  id: string;
+  kind: "action" | "command" | "history";
+  title: string;
+  detail: string;
+}
+
+const KIND_LABELS: Record<PaletteItem["kind"], string> = {
+  action: "Action",
+  command: "Plugin",
+  history: "History",
+};
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+function render(area: HTMLElement, items: PaletteItem[], selected: number): void {
+  area.innerHTML = items
+    .map(
+      (item, i) => `
+    <div data-index="${i}" style="
+      display: flex; align-items: baseline; gap: 8px;
+      padding: 6px 14px; font-size: 13px; cursor: pointer;
+      border-top: 1px solid rgba(255,255,255,0.06);
+      background: ${i === selected ? "rgba(59,130,246,0.25)" : "transparent"};
+    ">
+      <span style="color: #e2e8f0; white-space: nowrap; overflow: hidden; text-overflow: ellipsis;">${escapeHtml(item.title)}</span>
+      <span style="color: #94a3b8; flex: 1; white-space: nowrap; overflow: hidden; text-overflow: ellipsis;">${escapeHtml(item.detail)}</span>
+      <span style="color: #64748b; font-size: 11px;">${KIND_LABELS[item.kind]}</span>
+    </div>`,
+    )
+    .join("");
+}
+
+/**
+ * Wire the palette to the launcher input. Attach before the submit
+ * handler: an Enter that picks an item is marked `defaultPrevented`.
+ */
+export function attachPalette(
+  input: HTMLInputElement,
+  area: HTMLElement,
+  onChange: () => Promise<void>,
+): void {
+  let items: PaletteItem[] = [];
+  let selected = -1;
+  let latest = 0;
+
+  const show = async (next: PaletteItem[]) => {
+    items = next;
+    selected = -1;
+    render(area, items, selected);
+    await onChange();
+  };
+
+  const pick = async (item: PaletteItem) => {
+    if (item.kind === "command" || item.id === "find_on_screen") {
+      await show([]);
+      input.value = item.kind === "command" ? `/${item.id} ` : FIND_PREFIX;
+      input.dispatchEvent(new Event("input"));
+      return;
+    }
+    try {
+      await invoke("run_launcher_item", { id: item.id });
+    } catch (err) {
+      console.error("[LAUNCHER] Palette item failed:", err);
+    }
+  };
+
+  input.addEventListener("input", async () => {
+    const query = input.value;
+    const ticket = ++latest;
+    const found = await invoke<PaletteItem[]>("search_launcher", { query }).catch(() => []);
+    // A slower answer for an older query must not replace a newer one
+    if (ticket === latest) await show(found);
+  });
+
+  input.addEventListener("keydown", async (e) => {
+    if (items.length === 0) return;
+    if (e.key === "ArrowDown" || e.key === "ArrowUp") {
+      e.preventDefault();
+      const step = e.key === "ArrowDown" ? 1 : -1;
+      selected = Math.min(Math.max(selected + step, -1), items.length - 1);
+      render(area, items, selected);
+    } else if (e.key === "Enter" && selected >= 0) {
+      e.preventDefault();
+      await pick(items[selected]);
+    } else if (e.key === "Enter") {
+      await show([]);
+    }
+  });
+
+  area.addEventListener("click", async (e) => {
+    const row = (e.target as HTMLElement).closest<HTMLElement>("[data-index]");
+    const item = row ? items[Number(row.dataset.index)] : undefined;
+    if (item) await pick(item);
+  });
+}
@@ -0,0 +1,155 @@
+/**
+ * First-run setup — the checklist from `get_onboarding_status` (see
+ * src-tauri/src/onboarding): screen capture, AI provider, self-test snip.
+ *
+ * Opened at launch until setup is finished or skipped. Each step that
+ * isn't done offers its next action: ask for Screen Recording access,
+ * open Settings to add an API key, or run the self-test. The checks
+ * re-run whenever this window gets focus back, so a permission granted in
+ * System Settings or a key saved in Settings shows up at once.
+ * Finish (or Skip for Now) calls `complete_onboarding`, which closes the
+ * window for good.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+import { errorMessage } from "./errors";
+
+type StepState = "ok" | "needsAction" | "failed" | "pending";
+
+interface Step {
+  id: string;
+  title: string;
+  state: StepState;
+  detail: string;
+  remedy: string | null;
+}
+
+interface OnboardingStatus {
+  steps: Step[];
+  ready: boolean;
+  completed: boolean;
+}
+
+const BUTTON_STYLE = `padding: 6px 12px; background: #16213e; color: #fff; cursor: pointer;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+
+const BADGES: Record<StepState, [string, string]> = {
+  ok: ["✓", "#4ade80"],
+  needsAction: ["!", "#facc15"],
+  failed: ["✕", "#f87171"],
+  pending: ["•", "rgba(255,255,255,0.4)"],
+};
+
+/** The button a step offers while it isn't done, if any. */
+function actionLabel(step: Step): string | null {
+  if (step.state === "ok") return null;
+  switch (step.id) {
+    case "screen_capture":
+      return step.state === "needsAction" ? "Grant Access" : null;
+    case "provider":
+      return "Open Settings";
+    case "self_test":
+      return step.state === "failed" ? "Run Again" : "Run Self-Test";
+    default:
+      return null;
+  }
+}
+
+function stepRow(step: Step): string {
+  const [mark, color] = BADGES[step.state];
+  const action = actionLabel(step);
+  return `
+    <div style="display: flex; gap: 12px; padding: 12px 0; border-bottom: 1px solid rgba(255,255,255,0.06);">
+      <div style="width: 22px; height: 22px; border-radius: 50%; border: 1px solid ${color}; color: ${color};
+                  display: flex; align-items: center; justify-content: center; font-size: 12px; flex-shrink: 0;">${mark}</div>
+      <div style="flex: 1;">
+        <div style="font-size: 14px; font-weight: 500;">${escapeHtml(step.title)}</div>
+        <div style="font-size: 12px; color: rgba(255,255,255,0.6); margin-top: 2px;">${escapeHtml(step.detail)}</div>
+        ${step.remedy ? `<div style="font-size: 12px; color: rgba(255,255,255,0.45); margin-top: 2px;">${escapeHtml(step.remedy)}</div>` : ""}
+        ${action ? `<button data-step="${escapeHtml(step.id)}" style="${BUTTON_STYLE} margin-top: 8px;">${action}</button>` : ""}
+      </div>
+    </div>`;
+}
+
+const container = document.getElementById("onboarding")!;
+let busy = false;
+
+/** Run a step's action. Returns a note to show once the checks re-run. */
+async function runStep(id: string, status: HTMLElement): Promise<string | null> {
+  switch (id) {
+    case "screen_capture": {
+      const granted = await invoke<boolean>("request_screen_capture_permission");
+      return granted ? null : "After allowing Omni-Glass in System Settings, quit and reopen it.";
+    }
+    case "provider":
+      await invoke("open_settings");
+      return null;
+    case "self_test":
+      status.textContent = "Capturing and reading a strip of your screen…";
+      await invoke("run_onboarding_self_test");
+      return null;
+    default:
+      return null;
+  }
+}
+
+async function render(): Promise<void> {
+  let status: OnboardingStatus;
+  try {
+    status = await invoke<OnboardingStatus>("get_onboarding_status");
+  } catch (e) {
+    container.innerHTML = `<div style="padding: 20px; color: #f87171;">Failed to load setup: ${escapeHtml(errorMessage(e))}</div>`;
+    return;
+  }
+
+  container.innerHTML = `
+    <div style="padding: 24px;">
+      <h1 style="font-size: 18px; font-weight: 600;">Welcome to Omni-Glass</h1>
+      <p style="font-size: 13px; color: rgba(255,255,255,0.6); margin-top: 6px;">
+        Snip any part of your screen to read, explain, or act on it. A few checks first:
+      </p>
+      <div style="margin-top: 12px;">${status.steps.map(stepRow).join("")}</div>
+      <div id="onboarding-status" style="margin-top: 10px; font-size: 12px; color: rgba(255,255,255,0.6); min-height: 16px;"></div>
+      <div style="display: flex; justify-content: flex-end; margin-top: 14px;">
+        <button id="onboarding-done" style="${BUTTON_STYLE} ${status.ready ? "background: #3b82f6; border: none;" : ""}">
+          ${status.ready ? "Finish" : "Skip for Now"}
+        </button>
+      </div>
+    </div>`;
+
+  const statusEl = document.getElementById("onboarding-status")!;
+  container.querySelectorAll<HTMLButtonElement>("[data-step]").forEach((button) => {
+    button.addEventListener("click", async () => {
+      if (busy) return;
+      busy = true;
+      button.disabled = true;
+      try {
+        const note = await runStep(button.dataset.step!, statusEl);
+        busy = false;
+        await render();
+        if (note) document.getElementById("onboarding-status")!.textContent = note;
+      } catch (e) {
+        busy = false;
+        button.disabled = false;
+        statusEl.textContent = errorMessage(e);
+        statusEl.style.color = "#f87171";
+      }
+    });
+  });
+  document.getElementById("onboarding-done")!.addEventListener("click", async () => {
+    try {
+      await invoke("complete_onboarding");
+    } catch (e) {
+      statusEl.textContent = errorMessage(e);
+      statusEl.style.color = "#f87171";
+    }
+  });
+}
+
+// Re-check when the user comes back from System Settings or Settings
+window.addEventListener("focus", () => {
+  if (!busy) render();
+});
+
+render();
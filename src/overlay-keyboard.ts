@@ -0,0 +1,84 @@
+/**
+ * Overlay — keyboard-only selection (`selection` in src-tauri/src).
+ *
+ * The rectangle lives on the Rust side; keys only say how to change it:
+ *   - Arrow (or Enter with nothing selected): start, a third of the screen
+ *     centered; then move it.
+ *   - Alt+Arrow: move that edge out. Ctrl/Cmd+Arrow: move it in.
+ *   - Shift: the large step (`capture.keyboard` settings).
+ *   - Enter: snip it.
+ * Each change is announced for screen readers by the backend.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { errorCode, errorMessage } from "./errors";
+
+/** A keyboard selection in the overlay's CSS pixels. */
+export interface KeyboardRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+const DIRECTIONS: Record<string, string> = {
+  ArrowUp: "up",
+  ArrowDown: "down",
+  ArrowLeft: "left",
+  ArrowRight: "right",
+};
+
+/**
+ * Handle selection keys on the overlay. `onChange` draws each new
+ * rectangle; `isDragging` keeps the keys out of a mouse drag.
+ */
+export function setupKeyboardSelection(
+  onChange: (rect: KeyboardRect) => void,
+  isDragging: () => boolean,
+): void {
+  let active = false;
+  let busy = false;
+
+  async function start(): Promise<void> {
+    const rect = await invoke<KeyboardRect>("start_keyboard_selection", {
+      width: window.innerWidth,
+      height: window.innerHeight,
+    });
+    active = true;
+    onChange(rect);
+  }
+
+  async function confirm(): Promise<void> {
+    active = false;
+    try {
+      await invoke("confirm_selection");
+    } catch (err) {
+      console.error(`Keyboard snip failed (${errorCode(err)}):`, errorMessage(err));
+      try { await invoke("close_overlay"); } catch { /* window may be gone */ }
+    }
+  }
+
+  document.addEventListener("keydown", async (e: KeyboardEvent) => {
+    const direction = DIRECTIONS[e.key];
+    if ((!direction && e.key !== "Enter") || isDragging() || busy) return;
+    e.preventDefault();
+    busy = true;
+    try {
+      if (!active) {
+        await start();
+      } else if (e.key === "Enter") {
+        await confirm();
+      } else {
+        const large = e.shiftKey;
+        const rect = e.altKey || e.ctrlKey || e.metaKey
+          ? await invoke<KeyboardRect>("expand_selection", { direction, large, shrink: !e.altKey })
+          : await invoke<KeyboardRect>("nudge_selection", { direction, large });
+        onChange(rect);
+      }
+    } catch (err) {
+      console.error("Keyboard selection:", errorMessage(err));
+    } finally {
+      busy = false;
+    }
+  });
+}
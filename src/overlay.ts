@@ -2,14 +2,21 @@
  * Overlay module — handles the fullscreen snip interaction.
  *
  * Flow:
- * 1. Fetches screenshot path from Rust via get_capture_info command.
+ * 1. Dims the screen at once, then fetches the screenshot's omni-image://
+ *    URL via get_capture_info (again on "capture-ready" if not captured yet).
  * 2. Draws it on a canvas with a 50% dark overlay.
- * 3. User drags a rectangle to select a region.
+ * 3. User drags a rectangle to select a region; whenever it rests for a
+ *    moment, prefetch_ocr starts OCR of it in the background.
  * 4. On mouseup, sends the rectangle coordinates to Rust via process_snip.
  * 5. Rust crops → OCR → LLM → opens action menu.
+ *
+ * Without a mouse, arrow keys select instead (overlay-keyboard.ts).
  */
 
-import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { errorCode, errorMessage } from "./errors";
+import { setupKeyboardSelection } from "./overlay-keyboard";
 
 interface SelectionRect {
   startX: number;
@@ -18,8 +25,19 @@ interface SelectionRect {
   endY: number;
 }
 
+/** A selection in screenshot pixels, as process_snip and prefetch_ocr take it. */
+interface ScreenshotRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/** Pause in the drag before the current rectangle is OCR'd speculatively. */
+const PREFETCH_DEBOUNCE_MS = 150;
+
 interface CaptureInfo {
-  image_path: string;
+  image_url: string;
   click_epoch_ms: number;
 }
 
@@ -32,6 +50,47 @@ export function setupOverlay(): void {
   let screenshotImage: HTMLImageElement | null = null;
   let selection: SelectionRect | null = null;
   let isDragging = false;
+  let prefetchTimer: number | undefined;
+
+  // Map CSS pixel coordinates to screenshot pixel coordinates.
+  // Use actual image dimensions — devicePixelRatio doesn't match the
+  // screenshot resolution on macOS scaled displays (e.g. "Looks like
+  // 1440x900" on a 2560x1600 panel gives dpr=2 but xcap captures at
+  // 2560x1600, not 2880x1800).
+  // Edges are rounded rather than the size, so at fractional Windows
+  // scales (125%, 150%) a selection against the right or bottom edge
+  // ends on the image's last pixel (capture/dpi.rs does the same).
+  function toScreenshotRect(x: number, y: number, w: number, h: number): ScreenshotRect {
+    const scaleX = (screenshotImage?.width || window.innerWidth) / window.innerWidth;
+    const scaleY = (screenshotImage?.height || window.innerHeight) / window.innerHeight;
+    const left = Math.round(x * scaleX);
+    const top = Math.round(y * scaleY);
+    return {
+      x: left,
+      y: top,
+      width: Math.round((x + w) * scaleX) - left,
+      height: Math.round((y + h) * scaleY) - top,
+    };
+  }
+
+  // Debounced: OCR the selection once the pointer rests. Each call
+  // supersedes the previous job on the Rust side.
+  function schedulePrefetch(): void {
+    window.clearTimeout(prefetchTimer);
+    if (!selection || !screenshotImage) return;
+    const w = Math.abs(selection.endX - selection.startX);
+    const h = Math.abs(selection.endY - selection.startY);
+    if (w < 10 || h < 10) return;
+    const rect = toScreenshotRect(
+      Math.min(selection.startX, selection.endX),
+      Math.min(selection.startY, selection.endY),
+      w,
+      h,
+    );
+    prefetchTimer = window.setTimeout(() => {
+      invoke("prefetch_ocr", { ...rect }).catch(() => { /* speculative only */ });
+    }, PREFETCH_DEBOUNCE_MS);
+  }
 
   // Resize canvas to fill the screen at physical pixel resolution.
   // All drawing uses CSS coordinates thanks to ctx.scale(dpr, dpr).
@@ -43,9 +102,18 @@ export function setupOverlay(): void {
     ctx.setTransform(dpr, 0, 0, dpr, 0, 0);
     if (screenshotImage) {
       drawOverlay();
+    } else {
+      drawPlaceholder();
     }
   }
 
+  // Until the screenshot has loaded, dim the live desktop behind the
+  // transparent window so the overlay appears at once.
+  function drawPlaceholder(): void {
+    ctx.fillStyle = "rgba(0, 0, 0, 0.5)";
+    ctx.fillRect(0, 0, window.innerWidth, window.innerHeight);
+  }
+
   // Draw the screenshot with dark overlay and selection rectangle.
   // All coordinates are in CSS pixels (ctx is scaled by dpr).
   function drawOverlay(): void {
@@ -110,11 +178,13 @@ export function setupOverlay(): void {
     selection.endX = e.clientX;
     selection.endY = e.clientY;
     drawOverlay();
+    schedulePrefetch();
   });
 
   canvas.addEventListener("mouseup", async (e: MouseEvent) => {
     if (!isDragging || !selection) return;
     isDragging = false;
+    window.clearTimeout(prefetchTimer);
     selection.endX = e.clientX;
     selection.endY = e.clientY;
 
@@ -133,33 +203,26 @@ export function setupOverlay(): void {
     console.log(`Selection: ${w}×${h} at (${x}, ${y})`);
 
     try {
-      // Map CSS pixel coordinates to screenshot pixel coordinates.
-      // Use actual image dimensions — devicePixelRatio doesn't match the
-      // screenshot resolution on macOS scaled displays (e.g. "Looks like
-      // 1440x900" on a 2560x1600 panel gives dpr=2 but xcap captures at
-      // 2560x1600, not 2880x1800).
-      const cssW = window.innerWidth;
-      const cssH = window.innerHeight;
-      const imgW = screenshotImage?.width || cssW;
-      const imgH = screenshotImage?.height || cssH;
-      const scaleX = imgW / cssW;
-      const scaleY = imgH / cssH;
-      console.log(`[PIPELINE] Starting process_snip... scale=${scaleX.toFixed(2)}x${scaleY.toFixed(2)} img=${imgW}x${imgH} css=${cssW}x${cssH}`);
+      const rect = toScreenshotRect(x, y, w, h);
+      console.log(`[PIPELINE] Starting process_snip... rect=${rect.width}x${rect.height} at (${rect.x}, ${rect.y}) img=${screenshotImage?.width}x${screenshotImage?.height} css=${window.innerWidth}x${window.innerHeight}`);
       await invoke("process_snip", {
-        x: Math.round(x * scaleX),
-        y: Math.round(y * scaleY),
-        width: Math.round(w * scaleX),
-        height: Math.round(h * scaleY),
+        ...rect,
         menuX: x,       // CSS pixels for action menu window position
         menuY: y + h,    // Bottom edge of bounding box
       });
       // Overlay is closed by Rust after pipeline completes
     } catch (err) {
-      console.error("Pipeline failed:", err);
+      console.error(`Pipeline failed (${errorCode(err)}):`, errorMessage(err));
       try { await invoke("close_overlay"); } catch { /* window may be gone */ }
     }
   });
 
+  setupKeyboardSelection((rect) => {
+    selection = { startX: rect.x, startY: rect.y, endX: rect.x + rect.width, endY: rect.y + rect.height };
+    drawOverlay();
+    schedulePrefetch();
+  }, () => isDragging);
+
   // Escape key closes the overlay
   document.addEventListener("keydown", async (e: KeyboardEvent) => {
     if (e.key === "Escape") {
@@ -167,48 +230,52 @@ export function setupOverlay(): void {
     }
   });
 
-  // Fetch screenshot info from Rust via command (not event).
-  // Commands only execute after JS is fully loaded — no race condition.
+  // Fetch screenshot info from Rust via command. The capture runs in the
+  // background, so if it isn't stored yet, ask again on "capture-ready".
+  let screenshotRequested = false;
+  const readyListener = listen("capture-ready", () => loadScreenshot());
   loadScreenshot();
 
   async function loadScreenshot(): Promise<void> {
+    if (screenshotRequested) return;
+    const fetchStartMs = Date.now();
+    let info: CaptureInfo;
     try {
-      const fetchStartMs = Date.now();
-      const info = await invoke<CaptureInfo>("get_capture_info");
-      const clickEpochMs = info.click_epoch_ms;
-      const commandMs = Date.now() - fetchStartMs;
-      console.log(`[LATENCY] get_capture_info: ${commandMs}ms`);
-
-      // Convert file path to asset URL that the webview can load
-      const assetUrl = convertFileSrc(info.image_path);
-      console.log(`[LATENCY] loading screenshot from: ${assetUrl}`);
-
-      const img = new Image();
-      img.onload = () => {
-        screenshotImage = img;
-        resizeCanvas();
-        const overlayVisibleMs = Date.now();
-        const clickToVisibleMs = overlayVisibleMs - clickEpochMs;
-        console.log(
-          `[LATENCY] overlay_visible: click-to-visible=${clickToVisibleMs.toFixed(1)}ms`
-        );
-        console.log(`Screenshot loaded: ${img.width}×${img.height}, dpr=${dpr}`);
-      };
-      img.onerror = (e) => {
-        console.error(`Failed to load screenshot from: ${assetUrl}`, e);
-        const errDiv = document.createElement("div");
-        errDiv.style.cssText = "position:fixed;top:20px;left:20px;color:red;font:16px monospace;z-index:9999;background:rgba(0,0,0,0.8);padding:12px;border-radius:4px;max-width:80vw;word-break:break-all";
-        errDiv.textContent = `IMG LOAD FAILED: ${assetUrl}`;
-        document.body.appendChild(errDiv);
-      };
-      img.src = assetUrl;
-    } catch (err) {
-      console.error("Failed to get capture info:", err);
+      info = await invoke<CaptureInfo>("get_capture_info");
+    } catch {
+      return; // not captured yet — "capture-ready" follows
+    }
+    if (screenshotRequested) return;
+    screenshotRequested = true;
+    readyListener.then((unlisten) => unlisten());
+
+    const clickEpochMs = info.click_epoch_ms;
+    const commandMs = Date.now() - fetchStartMs;
+    console.log(`[LATENCY] get_capture_info: ${commandMs}ms`);
+
+    // omni-image:// URL served from memory by the Rust side
+    const imageUrl = info.image_url;
+    console.log(`[LATENCY] loading screenshot from: ${imageUrl}`);
+
+    const img = new Image();
+    img.onload = () => {
+      screenshotImage = img;
+      resizeCanvas();
+      const overlayVisibleMs = Date.now();
+      const clickToVisibleMs = overlayVisibleMs - clickEpochMs;
+      console.log(
+        `[LATENCY] overlay_visible: click-to-visible=${clickToVisibleMs.toFixed(1)}ms`
+      );
+      console.log(`Screenshot loaded: ${img.width}×${img.height}, dpr=${dpr}`);
+    };
+    img.onerror = (e) => {
+      console.error(`Failed to load screenshot from: ${imageUrl}`, e);
       const errDiv = document.createElement("div");
-      errDiv.style.cssText = "position:fixed;top:20px;left:20px;color:red;font:16px monospace;z-index:9999;background:rgba(0,0,0,0.8);padding:12px;border-radius:4px";
-      errDiv.textContent = `get_capture_info FAILED: ${err}`;
+      errDiv.style.cssText = "position:fixed;top:20px;left:20px;color:red;font:16px monospace;z-index:9999;background:rgba(0,0,0,0.8);padding:12px;border-radius:4px;max-width:80vw;word-break:break-all";
+      errDiv.textContent = `IMG LOAD FAILED: ${imageUrl}`;
       document.body.appendChild(errDiv);
-    }
+    };
+    img.src = imageUrl;
   }
 
   window.addEventListener("resize", resizeCanvas);
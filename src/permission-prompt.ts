@@ -13,6 +13,7 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "./errors";
 
 interface FsPerm {
   path: string;
@@ -39,6 +40,7 @@ interface PendingPlugin {
   permissions: Permissions;
   riskLevel: string; // "Low" | "Medium" | "High"
   isUpdate: boolean;
+  unenforceable: string | null; // why the plugin can't start on this system
 }
 
 function escapeHtml(text: string): string {
@@ -168,6 +170,7 @@ function renderPlugin(plugin: PendingPlugin): void {
           line-height:1.8;
         ">
           ${renderPermissionList(plugin.permissions)}
+          ${plugin.unenforceable ? `<li style="color:#f59e0b">${escapeHtml(plugin.unenforceable)}</li>` : ""}
         </ul>
       </div>
 
@@ -226,11 +229,12 @@ async function handleDecision(pluginId: string, approved: boolean): Promise<void
     console.error("approve_plugin failed:", err);
     // Show error briefly before moving on
     if (buttonRow) {
-      buttonRow.innerHTML = `<div style="
-        color:#fca5a5;font-size:12px;width:100%;text-align:center;
-      ">Failed to load plugin</div>`;
+      const message = document.createElement("div");
+      message.style.cssText = "color:#fca5a5;font-size:12px;width:100%;text-align:center;";
+      message.textContent = errorMessage(err);
+      buttonRow.replaceChildren(message);
     }
-    await new Promise(r => setTimeout(r, 1500));
+    await new Promise(r => setTimeout(r, 3000));
   }
 
   // Check for more pending plugins
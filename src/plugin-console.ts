@@ -0,0 +1,113 @@
+/**
+ * Plugin console — live stderr + reload notices for one plugin.
+ *
+ * Opened by open_plugin_console (mcp/devmode). The plugin ID comes from
+ * the `?plugin=` query string.
+ *
+ * Flow:
+ * 1. stream_plugin_logs returns the buffered backlog and starts streaming
+ * 2. New lines arrive as `plugin-log` events (filtered to this plugin)
+ * 3. stop_plugin_logs on close so other plugins' consoles aren't affected
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+interface LogLine {
+  pluginId: string;
+  line: string;
+  timestamp: number;
+}
+
+/** Lines kept in the DOM — matches the backend buffer size. */
+const MAX_LINES = 500;
+
+const pluginId = new URLSearchParams(window.location.search).get("plugin") ?? "";
+
+const container = document.getElementById("plugin-console")!;
+container.innerHTML = `
+  <div style="display: flex; flex-direction: column; height: 100vh;">
+    <div style="
+      display: flex;
+      align-items: center;
+      justify-content: space-between;
+      padding: 8px 12px;
+      border-bottom: 1px solid rgba(255,255,255,0.1);
+      font-size: 12px;
+      color: #94a3b8;
+    ">
+      <span id="title"></span>
+      <button id="clear" style="
+        background: transparent;
+        color: #94a3b8;
+        border: 1px solid rgba(255,255,255,0.15);
+        border-radius: 4px;
+        padding: 2px 8px;
+        font-size: 11px;
+        cursor: pointer;
+      ">Clear</button>
+    </div>
+    <pre id="log" style="
+      flex: 1;
+      overflow-y: auto;
+      padding: 8px 12px;
+      font-family: ui-monospace, Menlo, monospace;
+      font-size: 12px;
+      line-height: 1.5;
+      color: #e2e8f0;
+      white-space: pre-wrap;
+      word-break: break-all;
+    "></pre>
+  </div>
+`;
+
+const title = document.getElementById("title")!;
+const log = document.getElementById("log")!;
+title.textContent = pluginId;
+
+function formatTime(ms: number): string {
+  return new Date(ms).toLocaleTimeString([], { hour12: false });
+}
+
+function append(entry: LogLine): void {
+  // Only follow the tail if the user hasn't scrolled up to read
+  const atBottom = log.scrollHeight - log.scrollTop - log.clientHeight < 24;
+
+  const row = document.createElement("div");
+  row.textContent = `${formatTime(entry.timestamp)}  ${entry.line}`;
+  if (entry.line.startsWith("[omni-glass]")) {
+    row.style.color = "#60a5fa";
+  }
+  log.appendChild(row);
+  while (log.childElementCount > MAX_LINES) {
+    log.firstElementChild?.remove();
+  }
+
+  if (atBottom) {
+    log.scrollTop = log.scrollHeight;
+  }
+}
+
+document.getElementById("clear")!.addEventListener("click", () => {
+  log.replaceChildren();
+});
+
+async function start(): Promise<void> {
+  if (!pluginId) {
+    log.textContent = "No plugin selected.";
+    return;
+  }
+  await listen<LogLine>("plugin-log", (event) => {
+    if (event.payload.pluginId === pluginId) {
+      append(event.payload);
+    }
+  });
+  const backlog = await invoke<LogLine[]>("stream_plugin_logs", { pluginId });
+  backlog.forEach(append);
+}
+
+window.addEventListener("beforeunload", () => {
+  invoke("stop_plugin_logs", { pluginId });
+});
+
+start();
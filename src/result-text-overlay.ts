@@ -0,0 +1,98 @@
+/**
+ * Result window — the snip with its text laid over it
+ * (`get_text_spans` in src-tauri/src/windows/result.rs).
+ *
+ * Each recognized word becomes a transparent, selectable span placed
+ * over the word in the image, so dragging across the picture selects
+ * the text under it and Cmd/Ctrl+C copies just that. Positions are in
+ * percent of the image, so the overlay follows the image as the window
+ * is resized.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+
+interface TextSpan {
+  text: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  line: number;
+}
+
+interface SnipText {
+  png: string;
+  width: number;
+  height: number;
+  spans: TextSpan[];
+}
+
+/** One word, absolutely placed; the trailing space or line break keeps
+ *  copied words apart and lines on lines of their own. */
+function spanHtml(span: TextSpan, next: TextSpan | undefined, snip: SnipText): string {
+  const pct = (value: number, of: number) => `${((value / of) * 100).toFixed(3)}%`;
+  const separator = !next ? "" : next.line !== span.line ? "\n" : " ";
+  return `<span class="ocr-word" data-width="${span.width}" data-height="${span.height}" style="
+    left: ${pct(span.x, snip.width)};
+    top: ${pct(span.y, snip.height)};
+    width: ${pct(span.width, snip.width)};
+    height: ${pct(span.height, snip.height)};
+  ">${escapeHtml(span.text)}${separator}</span>`;
+}
+
+/** Size each word's font to its box: its height sets the size, then a
+ *  horizontal scale stretches the text to the box's width. */
+function fitWords(layer: HTMLElement, image: HTMLImageElement, snip: SnipText): void {
+  const scale = image.clientWidth / snip.width;
+  layer.querySelectorAll<HTMLElement>(".ocr-word").forEach((word) => {
+    const boxWidth = Number(word.dataset.width) * scale;
+    const boxHeight = Number(word.dataset.height) * scale;
+    word.style.fontSize = `${boxHeight * 0.85}px`;
+    word.style.lineHeight = `${boxHeight}px`;
+    word.style.transform = "none";
+    const natural = word.scrollWidth;
+    if (natural > 0) word.style.transform = `scaleX(${boxWidth / natural})`;
+  });
+}
+
+/** Show the current snip in `container` with its words selectable over
+ *  it. Returns a message to show instead when there is nothing to lay out. */
+export async function renderTextOverlay(container: HTMLElement): Promise<string | null> {
+  let snip: SnipText;
+  try {
+    snip = await invoke<SnipText>("get_text_spans");
+  } catch (err) {
+    return `${err}`;
+  }
+  if (snip.spans.length === 0) return "No text found on the snip";
+
+  const sorted = [...snip.spans].sort((a, b) => a.line - b.line || a.x - b.x);
+  const words = sorted.map((span, i) => spanHtml(span, sorted[i + 1], snip)).join("");
+  container.innerHTML = `
+    <style>
+      .ocr-word {
+        position: absolute;
+        color: transparent;
+        white-space: pre;
+        transform-origin: 0 0;
+        cursor: text;
+        user-select: text;
+      }
+      .ocr-word::selection { background: rgba(96,165,250,0.35); color: transparent; }
+    </style>
+    <div style="position: relative; display: inline-block; width: 100%;">
+      <img id="ocr-image" src="data:image/png;base64,${snip.png}" alt="Snip"
+           style="display: block; width: 100%; height: auto; user-select: none; -webkit-user-drag: none;">
+      <div id="ocr-layer" style="position: absolute; inset: 0;">${words}</div>
+    </div>
+  `;
+
+  const image = document.getElementById("ocr-image") as HTMLImageElement;
+  const layer = document.getElementById("ocr-layer")!;
+  const fit = () => fitWords(layer, image, snip);
+  if (image.complete) fit();
+  else image.addEventListener("load", fit, { once: true });
+  new ResizeObserver(fit).observe(image);
+  return null;
+}
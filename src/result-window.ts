@@ -0,0 +1,134 @@
+/**
+ * Result window — a text answer in a window of its own.
+ *
+ * Opened by Rust (src-tauri/src/windows/result.rs) when a result is
+ * popped out of the action menu, reopened as the last result, or shown
+ * from history. Unlike the action menu it stays open when it loses
+ * focus and can be resized. A new result replaces this one in place
+ * (`result-changed`); "Pin" keeps the window above other windows. For a
+ * result of the current snip, "On Image" swaps the answer for the snip
+ * with its text selectable where it appears (result-text-overlay.ts).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { ActionResult } from "./action-menu-results";
+import { extractCodeBlock, renderMarkdownLight } from "./action-menu-markdown";
+import { renderTextOverlay } from "./result-text-overlay";
+
+interface ShownResult {
+  result: ActionResult;
+  pinned: boolean;
+  onImage: boolean;
+}
+
+const container = document.getElementById("result-window")!;
+
+/** Show `text` in the footer for a moment. */
+function flash(text: string): void {
+  const status = document.getElementById("result-status");
+  if (!status) return;
+  status.textContent = text;
+  setTimeout(() => { status.textContent = ""; }, 1500);
+}
+
+function render(shown: ShownResult): void {
+  const text = shown.result.result.text || "No content returned.";
+  const codeBlock = extractCodeBlock(text);
+  const formatted = shown.result.actionId === "format_data";
+
+  container.innerHTML = `
+    <div id="result-body" style="
+      flex: 1;
+      padding: 12px 14px;
+      font-size: 13px;
+      color: rgba(255,255,255,0.9);
+      line-height: 1.5;
+      overflow-y: auto;
+      word-wrap: break-word;
+      user-select: text;
+    ">${renderMarkdownLight(text)}</div>
+    <div style="
+      padding: 6px 14px 8px;
+      display: flex;
+      gap: 8px;
+      align-items: center;
+      border-top: 1px solid rgba(255,255,255,0.1);
+    ">
+      <span id="result-status" style="flex: 1; font-size: 12px; color: #4ade80;"></span>
+      ${shown.onImage ? `<button id="btn-on-image" title="Show the snip with its text selectable">On Image</button>` : ""}
+      <button id="btn-pin" class="${shown.pinned ? "on" : ""}" title="Keep above other windows">Pin</button>
+      ${codeBlock ? `<button id="btn-copy-fix">${formatted ? "Copy Formatted" : "Copy Fix"}</button>` : ""}
+      <button id="btn-copy">Copy All</button>
+      <button id="btn-close">Close</button>
+    </div>
+  `;
+
+  const pin = document.getElementById("btn-pin")!;
+  pin.addEventListener("click", async () => {
+    const pinned = !pin.classList.contains("on");
+    try {
+      await invoke("pin_result", { pinned });
+      pin.classList.toggle("on", pinned);
+    } catch (err) {
+      console.error("[RESULT] Failed to pin:", err);
+    }
+  });
+
+  const onImage = document.getElementById("btn-on-image");
+  onImage?.addEventListener("click", async () => {
+    const body = document.getElementById("result-body")!;
+    const showing = !onImage.classList.contains("on");
+    if (!showing) {
+      body.innerHTML = renderMarkdownLight(text);
+      onImage.classList.remove("on");
+      return;
+    }
+    const problem = await renderTextOverlay(body);
+    if (problem) {
+      flash(problem);
+      return;
+    }
+    onImage.classList.add("on");
+    flash("Select text on the image to copy part of it");
+  });
+
+  if (codeBlock) {
+    document.getElementById("btn-copy-fix")?.addEventListener("click", async () => {
+      await invoke("copy_to_clipboard", { text: codeBlock });
+      flash(formatted ? "Formatted copy on clipboard" : "Fix copied");
+    });
+  }
+
+  document.getElementById("btn-copy")?.addEventListener("click", async () => {
+    await invoke("copy_to_clipboard", { text });
+    flash("Copied");
+  });
+
+  document.getElementById("btn-close")?.addEventListener("click", () => {
+    getCurrentWebviewWindow().close().catch(() => { /* closing */ });
+  });
+
+  invoke("mark_result_viewed").catch(() => { /* best effort */ });
+}
+
+/** Fetch what Rust wants shown and render it. */
+async function refresh(): Promise<void> {
+  const shown = await invoke<ShownResult | null>("get_shown_result").catch(() => null);
+  if (!shown) {
+    container.innerHTML = `<div style="padding: 16px; color: rgba(255,255,255,0.5);">No result to show.</div>`;
+    return;
+  }
+  console.log(`[RESULT] Showing ${shown.result.actionId}`);
+  render(shown);
+}
+
+document.addEventListener("keydown", (e: KeyboardEvent) => {
+  if (e.key === "Escape") {
+    getCurrentWebviewWindow().close().catch(() => { /* closing */ });
+  }
+});
+
+listen("result-changed", () => refresh());
+refresh();
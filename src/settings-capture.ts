@@ -0,0 +1,156 @@
+/**
+ * Settings — Capture section: which monitor a snip captures, apps whose
+ * windows are never captured, and what a snip does while the screen is
+ * shared.
+ *
+ * Values go to the Rust settings store under `capture` (see
+ * capture/rules.rs in src-tauri). A rule matches any app whose name
+ * contains the text; "Blank" paints its windows over, "Refuse snip"
+ * stops the snip with a notification while one is on screen.
+ *
+ * While a meeting app or browser shares the screen (presenting/ in
+ * src-tauri), a snip asks first, is paused, or goes ahead as usual.
+ *
+ * When the screen can't be captured (Screen Recording off on macOS, a
+ * denied portal on Wayland), the section leads with the fix and, where the
+ * OS has one, a button to the setting (capture/permission.rs).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { errorCode, errorMessage } from "./errors";
+
+interface AppRule {
+  app: string;
+  action: "blank" | "refuse";
+}
+
+interface PresentingPrefs {
+  mode: "warn" | "pause" | "off";
+  indicators: string[];
+}
+
+interface CapturePrefs {
+  monitor: string | null;
+  appRules: AppRule[];
+  presenting: PresentingPrefs;
+}
+
+interface PermissionStatus {
+  state: "granted" | "denied" | "unavailable";
+  canOpenSettings: boolean;
+  remedy: string | null;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+
+/** One editable rule row; app names are user text, so no innerHTML. */
+function ruleRow(rule: AppRule, onChange: () => void): HTMLElement {
+  const row = document.createElement("div");
+  row.style.cssText = "display: flex; gap: 6px; margin-bottom: 6px;";
+  const app = document.createElement("input");
+  app.value = rule.app;
+  app.placeholder = "App name, e.g. 1Password";
+  app.style.cssText = INPUT_STYLE + " flex: 1;";
+  const action = document.createElement("select");
+  action.style.cssText = INPUT_STYLE;
+  action.add(new Option("Blank", "blank"));
+  action.add(new Option("Refuse snip", "refuse"));
+  action.value = rule.action;
+  const remove = document.createElement("button");
+  remove.textContent = "✕";
+  remove.style.cssText = INPUT_STYLE + " cursor: pointer;";
+  remove.addEventListener("click", () => { row.remove(); onChange(); });
+  app.addEventListener("change", onChange);
+  action.addEventListener("change", onChange);
+  row.append(app, action, remove);
+  return row;
+}
+
+/** Render the Capture section into `container` and wire its controls. */
+export async function renderCaptureSection(container: HTMLElement): Promise<void> {
+  let prefs: CapturePrefs;
+  let monitors: string[] = [];
+  let permission: PermissionStatus;
+  try {
+    prefs = (await invoke<{ capture: CapturePrefs }>("get_settings")).capture;
+    monitors = await invoke<string[]>("list_monitors");
+    permission = await invoke<PermissionStatus>("get_capture_permission_status");
+  } catch (e) {
+    console.error("Failed to load capture settings:", e);
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Capture
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div id="capture-permission" style="display: none; margin-bottom: 12px; font-size: 12px; color: #fbbf24;">
+          <div id="capture-permission-remedy" style="margin-bottom: 6px;"></div>
+          <button id="capture-permission-open" style="${INPUT_STYLE} cursor: pointer;">Open System Settings</button>
+        </div>
+        <label style="display: flex; align-items: center; gap: 8px; margin-bottom: 12px;">
+          <span style="font-size: 14px;">Monitor</span>
+          <select id="capture-monitor" style="${INPUT_STYLE} flex: 1; cursor: pointer;"></select>
+        </label>
+        <label style="display: flex; align-items: center; gap: 8px; margin-bottom: 12px;">
+          <span style="font-size: 14px;">While sharing the screen</span>
+          <select id="capture-presenting" style="${INPUT_STYLE} flex: 1; cursor: pointer;">
+            <option value="warn">Ask before snipping</option>
+            <option value="pause">Pause snips</option>
+            <option value="off">Snip as usual</option>
+          </select>
+        </label>
+        <div style="font-size: 12px; color: rgba(255,255,255,0.7); margin-bottom: 6px;">Never capture these apps</div>
+        <div id="capture-rules"></div>
+        <button id="capture-add-rule" style="${INPUT_STYLE} cursor: pointer;">Add app</button>
+        <div id="capture-status" style="margin-top: 8px; font-size: 12px; color: #f87171;"></div>
+      </div>
+    </section>`;
+
+  if (permission.state !== "granted") {
+    document.getElementById("capture-permission")!.style.display = "block";
+    document.getElementById("capture-permission-remedy")!.textContent = permission.remedy ?? "";
+    const openButton = document.getElementById("capture-permission-open")!;
+    openButton.style.display = permission.canOpenSettings ? "" : "none";
+    openButton.addEventListener("click", () => {
+      invoke("open_permission_settings").catch((err) => {
+        document.getElementById("capture-status")!.textContent = errorMessage(err);
+        // Nothing to open on this system; don't offer it again
+        if (errorCode(err) === "capture.no_settings") openButton.style.display = "none";
+      });
+    });
+  }
+
+  const monitor = document.getElementById("capture-monitor") as HTMLSelectElement;
+  monitor.add(new Option("Primary", ""));
+  monitors.forEach((name, i) => monitor.add(new Option(`${i + 1}: ${name}`, String(i + 1))));
+  monitor.value = prefs.monitor ?? "";
+  const presenting = document.getElementById("capture-presenting") as HTMLSelectElement;
+  presenting.value = prefs.presenting.mode;
+
+  const list = document.getElementById("capture-rules")!;
+  const status = document.getElementById("capture-status")!;
+  const save = async () => {
+    const appRules: AppRule[] = Array.from(list.children).map((row) => ({
+      app: (row.querySelector("input") as HTMLInputElement).value.trim(),
+      action: (row.querySelector("select") as HTMLSelectElement).value as AppRule["action"],
+    })).filter((r) => r.app);
+    try {
+      const sharing = { ...prefs.presenting, mode: presenting.value as PresentingPrefs["mode"] };
+      await invoke("update_settings", { patch: { capture: { monitor: monitor.value || null, appRules, presenting: sharing } } });
+      status.textContent = "";
+    } catch (err) {
+      status.textContent = String(err);
+    }
+  };
+  for (const rule of prefs.appRules) list.append(ruleRow(rule, save));
+  monitor.addEventListener("change", save);
+  presenting.addEventListener("change", save);
+  document.getElementById("capture-add-rule")!.addEventListener("click", () => {
+    list.append(ruleRow({ app: "", action: "blank" }, save));
+  });
+}
@@ -0,0 +1,104 @@
+/**
+ * Settings — Plugin Catalog section: community plugins from the catalog
+ * index, installed with one click (see src-tauri/src/mcp/install).
+ *
+ * The index is cached for six hours; Refresh fetches it again. Install
+ * passes the listing's download URL and SHA-256 to `install_plugin`, so
+ * the bundle is verified before it is unpacked. A plugin that asks for
+ * permissions opens the permission prompt and loads only once approved.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+import { errorMessage } from "./errors";
+
+interface CatalogListing {
+  id: string;
+  name: string;
+  version: string;
+  description: string;
+  downloadUrl: string;
+  sha256: string;
+  riskLevel: "low" | "medium" | "high";
+  installed: boolean;
+  blocked: boolean;
+}
+
+interface Catalog {
+  plugins: CatalogListing[];
+  fetchedAt: number;
+  fromCache: boolean;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+const RISK_COLORS = { low: "#4ade80", medium: "#facc15", high: "#f87171" };
+
+function listingRow(plugin: CatalogListing, i: number): string {
+  const action = plugin.blocked
+    ? `<span style="color: rgba(255,255,255,0.4);">Blocked by policy</span>`
+    : `<button data-install="${i}" style="${INPUT_STYLE} cursor: pointer;">${plugin.installed ? "Reinstall" : "Install"}</button>`;
+  return `
+    <div style="padding: 8px 0; border-bottom: 1px solid rgba(255,255,255,0.06); font-size: 12px;">
+      <div style="display: flex; align-items: center; gap: 8px;">
+        <span style="font-size: 13px; flex: 1;">${escapeHtml(plugin.name)} <span style="color: rgba(255,255,255,0.4);">v${escapeHtml(plugin.version)}</span></span>
+        <span style="color: ${RISK_COLORS[plugin.riskLevel]};">${plugin.riskLevel} risk</span>
+        ${action}
+      </div>
+      <div style="margin-top: 4px; color: rgba(255,255,255,0.5);">${escapeHtml(plugin.description)}</div>
+    </div>`;
+}
+
+/** Render the Plugin Catalog section into `container` and wire its controls. */
+export async function renderCatalogSection(container: HTMLElement, refresh = false): Promise<void> {
+  let catalog: Catalog | null = null;
+  let loadError = "";
+  try {
+    catalog = await invoke<Catalog>("list_catalog_plugins", { refresh });
+  } catch (e) {
+    loadError = errorMessage(e);
+  }
+  const plugins = catalog?.plugins ?? [];
+  const fetched = catalog ? new Date(catalog.fetchedAt * 1000).toLocaleString() : "";
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Plugin Catalog
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div style="display: flex; align-items: center; gap: 8px; font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 6px;">
+          <span style="flex: 1;">${catalog ? `Updated ${fetched}${catalog.fromCache ? " (cached)" : ""}` : escapeHtml(loadError)}</span>
+          <button id="catalog-refresh" style="${INPUT_STYLE} cursor: pointer;">Refresh</button>
+        </div>
+        <div>${plugins.map(listingRow).join("") || `<div style="font-size: 12px; color: rgba(255,255,255,0.4);">No plugins listed yet</div>`}</div>
+        <div id="catalog-status" style="margin-top: 8px; font-size: 12px; color: rgba(255,255,255,0.6);"></div>
+      </div>
+    </section>`;
+
+  const status = document.getElementById("catalog-status")!;
+  document.getElementById("catalog-refresh")!.addEventListener("click", () => renderCatalogSection(container, true));
+  container.querySelectorAll<HTMLButtonElement>("[data-install]").forEach((button) => {
+    const plugin = plugins[Number(button.dataset.install)];
+    button.addEventListener("click", async () => {
+      button.disabled = true;
+      status.style.color = "rgba(255,255,255,0.6)";
+      status.textContent = `Installing ${plugin.name}…`;
+      try {
+        const result = await invoke<{ needsApproval: boolean }>("install_plugin", {
+          source: plugin.downloadUrl,
+          sha256: plugin.sha256,
+        });
+        status.textContent = result.needsApproval
+          ? `${plugin.name} is installed and waiting for your approval.`
+          : `${plugin.name} is installed.`;
+        button.textContent = "Reinstall";
+      } catch (err) {
+        status.textContent = errorMessage(err);
+        status.style.color = "#f87171";
+      }
+      button.disabled = false;
+    });
+  });
+}
@@ -0,0 +1,33 @@
+/**
+ * Settings — "Export diagnostics…" in the About section.
+ *
+ * Asks where to save, then has the Rust side write the zip (logs,
+ * scrubbed settings, system info, latency stats, plugin health; see
+ * diagnostics/ in src-tauri). With "Include last snip" ticked, the zip
+ * also carries that snip's image, text, and AI responses, so a
+ * maintainer can replay it (replay/ in src-tauri). The result is shown
+ * next to the button.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+
+/** Wire the export button, the include-snip checkbox, and the status line. */
+export function wireDiagnosticsExport(button: HTMLElement, includeSnip: HTMLInputElement, status: HTMLElement): void {
+  button.addEventListener("click", async (e) => {
+    e.preventDefault();
+    const date = new Date().toISOString().slice(0, 10);
+    const filePath = await save({
+      defaultPath: `omni-glass-diagnostics-${date}.zip`,
+      filters: [{ name: "ZIP", extensions: ["zip"] }],
+    });
+    if (!filePath) return;
+    status.textContent = "Exporting…";
+    try {
+      const written = await invoke<string>("export_diagnostics", { filePath, includeSnip: includeSnip.checked });
+      status.textContent = `Saved ${written.split(/[\\/]/).pop()}`;
+    } catch (err) {
+      status.textContent = String(err);
+    }
+  });
+}
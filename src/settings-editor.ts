@@ -0,0 +1,95 @@
+/**
+ * Settings — Editor section: which editor "Open in Editor" uses
+ * (`open_in_editor` in src-tauri/src/editor).
+ *
+ * Saved under `editor.preferred`: null detects one, a known id picks
+ * that editor, anything else is run as a command line with the file
+ * appended (or put where `{file}` is).
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface EditorInfo {
+  id: string;
+  label: string;
+  installed: boolean;
+  terminal: boolean;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+
+/** Render the Editor section into `container` and wire its controls. */
+export async function renderEditorSection(container: HTMLElement): Promise<void> {
+  let preferred: string | null;
+  let editors: EditorInfo[];
+  let detected: string | null;
+  try {
+    preferred = (await invoke<{ editor: { preferred: string | null } }>("get_settings")).editor.preferred;
+    ({ editors, detected } = await invoke<{ editors: EditorInfo[]; detected: string | null }>("get_editors"));
+  } catch (e) {
+    console.error("Failed to load editor settings:", e);
+    return;
+  }
+
+  const options = editors
+    .map((e) => {
+      const notes = [e.terminal ? "terminal" : "", e.installed ? "" : "not found"].filter(Boolean).join(", ");
+      return `<option value="${e.id}">${e.label}${notes ? ` (${notes})` : ""}</option>`;
+    })
+    .join("");
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Editor
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div style="font-size: 14px; margin-bottom: 6px;">Open code in</div>
+        <select id="editor-choice" style="${INPUT_STYLE}">
+          <option value="">Automatic (${detected ?? "system text viewer"})</option>
+          ${options}
+          <option value="custom">Custom command…</option>
+        </select>
+        <input id="editor-command" type="text" spellcheck="false" placeholder="code --wait {file}"
+          style="${INPUT_STYLE} width: 100%; box-sizing: border-box; margin-top: 8px; display: none;
+                 font-family: 'SF Mono', Menlo, monospace; font-size: 12px;" />
+        <span id="editor-status" style="font-size: 12px; color: #f87171;"></span>
+      </div>
+    </section>`;
+
+  const select = document.getElementById("editor-choice") as HTMLSelectElement;
+  const command = document.getElementById("editor-command") as HTMLInputElement;
+  const status = document.getElementById("editor-status")!;
+  const known = new Set(editors.map((e) => e.id));
+
+  const show = (value: string | null) => {
+    const custom = value !== null && !known.has(value);
+    select.value = custom ? "custom" : value ?? "";
+    command.style.display = custom ? "block" : "none";
+    command.value = custom ? value : "";
+  };
+  show(preferred);
+
+  const save = async (value: string | null) => {
+    try {
+      const saved = await invoke<{ editor: { preferred: string | null } }>("update_settings", {
+        patch: { editor: { preferred: value } },
+      });
+      show(saved.editor.preferred);
+      status.textContent = "";
+    } catch (err) {
+      status.textContent = String(err);
+    }
+  };
+  select.addEventListener("change", () => {
+    if (select.value === "custom") {
+      command.style.display = "block";
+      command.focus();
+    } else {
+      save(select.value || null);
+    }
+  });
+  command.addEventListener("change", () => save(command.value.trim() || null));
+}
@@ -0,0 +1,59 @@
+/**
+ * Settings — Export section: the template for Markdown exports of a
+ * snip (`export_markdown` in src-tauri/src/export).
+ *
+ * Saved under `export.markdownTemplate`; "Reset" sends null, which
+ * restores the built-in template.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+const PLACEHOLDERS = "{title} {date} {type} {image} {code} {text} {results} {tags} {notes}";
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+
+/** Render the Export section into `container` and wire its controls. */
+export async function renderExportSection(container: HTMLElement): Promise<void> {
+  let template: string;
+  try {
+    template = (await invoke<{ export: { markdownTemplate: string } }>("get_settings")).export.markdownTemplate;
+  } catch (e) {
+    console.error("Failed to load export settings:", e);
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Export
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div style="font-size: 14px; margin-bottom: 6px;">Markdown template</div>
+        <textarea id="export-template" rows="8" spellcheck="false"
+          style="${INPUT_STYLE} width: 100%; box-sizing: border-box; font-family: 'SF Mono', Menlo, monospace; font-size: 12px;"></textarea>
+        <div style="font-size: 11px; color: rgba(255,255,255,0.4); margin: 6px 0 8px;">${PLACEHOLDERS}</div>
+        <button id="export-template-reset" style="${INPUT_STYLE} cursor: pointer;">Reset</button>
+        <span id="export-status" style="margin-left: 8px; font-size: 12px; color: #f87171;"></span>
+      </div>
+    </section>`;
+
+  const textarea = document.getElementById("export-template") as HTMLTextAreaElement;
+  const status = document.getElementById("export-status")!;
+  textarea.value = template;
+
+  const save = async (markdownTemplate: string | null) => {
+    try {
+      const saved = await invoke<{ export: { markdownTemplate: string } }>("update_settings", {
+        patch: { export: { markdownTemplate } },
+      });
+      textarea.value = saved.export.markdownTemplate;
+      status.textContent = "";
+    } catch (err) {
+      status.textContent = String(err);
+    }
+  };
+  textarea.addEventListener("change", () => save(textarea.value));
+  document.getElementById("export-template-reset")!.addEventListener("click", () => save(null));
+}
@@ -0,0 +1,271 @@
+/**
+ * Settings — General section: active profile, language, quality mode,
+ * launch at login, background mode, results on top, launcher hotkey (and
+ * the snip hotkey in effect), and offline mode.
+ *
+ * Everything persists through the Rust settings store (settings/).
+ * A failed change (e.g. the OS refused the login item) reverts the box.
+ * Profile switches made from the tray update the picker live; the
+ * offline hint follows `connectivity-changed`, the quality hint
+ * `quality-mode-changed`.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+interface GeneralSettings {
+  launchAtLogin: boolean;
+  backgroundMode: boolean;
+  focusExisting: boolean;
+  offlineMode: boolean;
+  actions: { resultOnTop: boolean };
+  hotkeys: { snip: string | null; launcher: string | null };
+  profiles: Record<string, { localOnly: boolean }>;
+  activeProfile: string | null;
+}
+
+interface QualityStatus {
+  mode: "full" | "saver";
+  setting: string;
+  reason: "setting" | "thermal" | "lowPower" | "lowBattery" | null;
+}
+
+const SAVER_REASONS: Record<string, string> = {
+  thermal: "the Mac is running hot",
+  lowPower: "low power mode is on",
+  lowBattery: "the battery is low",
+};
+
+interface LocaleInfo {
+  locale: string;
+  setting: string | null;
+  available: string[];
+}
+
+/** Fill the profile picker; names are user text, so no innerHTML. */
+function fillProfiles(picker: HTMLSelectElement, settings: GeneralSettings): void {
+  picker.add(new Option("None", ""));
+  for (const [name, profile] of Object.entries(settings.profiles)) {
+    picker.add(new Option(profile.localOnly ? `${name} (local only)` : name, name));
+  }
+  picker.value = settings.activeProfile ?? "";
+}
+
+function toggle(id: string, label: string, hint: string, checked: boolean): string {
+  return `
+    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+      <input type="checkbox" id="${id}" ${checked ? "checked" : ""} style="accent-color: #3b82f6;" />
+      <span style="font-size: 14px;">${label}</span>
+    </label>
+    <div style="margin-left: 24px; font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 10px;">
+      ${hint}
+    </div>`;
+}
+
+/** Render the General section into `container` and wire its toggles. */
+export async function renderGeneralSection(container: HTMLElement): Promise<void> {
+  let settings: GeneralSettings;
+  try {
+    settings = await invoke<GeneralSettings>("get_settings");
+  } catch (e) {
+    console.error("Failed to load general settings:", e);
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        General
+      </h2>
+      <div style="
+        background: #0f1629;
+        border: 1px solid rgba(255,255,255,0.1);
+        border-radius: 8px;
+        padding: 14px;
+      ">
+        <label style="display: flex; align-items: center; gap: 8px; margin-bottom: 12px;">
+          <span style="font-size: 14px;">Profile</span>
+          <select id="active-profile" style="
+            flex: 1;
+            padding: 6px 10px;
+            background: #16213e;
+            border: 1px solid rgba(255,255,255,0.15);
+            border-radius: 6px;
+            color: #fff;
+            font-size: 14px;
+            cursor: pointer;
+          "></select>
+        </label>
+        <label style="display: flex; align-items: center; gap: 8px; margin-bottom: 12px;">
+          <span style="font-size: 14px;">Language</span>
+          <select id="app-locale" style="
+            flex: 1;
+            padding: 6px 10px;
+            background: #16213e;
+            border: 1px solid rgba(255,255,255,0.15);
+            border-radius: 6px;
+            color: #fff;
+            font-size: 14px;
+            cursor: pointer;
+          "></select>
+        </label>
+        <label style="display: flex; align-items: center; gap: 8px;">
+          <span style="font-size: 14px;">Quality</span>
+          <select id="quality-mode" style="
+            flex: 1;
+            padding: 6px 10px;
+            background: #16213e;
+            border: 1px solid rgba(255,255,255,0.15);
+            border-radius: 6px;
+            color: #fff;
+            font-size: 14px;
+            cursor: pointer;
+          ">
+            <option value="auto">Automatic (save power on battery or heat)</option>
+            <option value="full">Always full quality</option>
+            <option value="saver">Always save power</option>
+          </select>
+        </label>
+        <div id="quality-hint" style="font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 12px;"></div>
+        ${toggle("launch-at-login", "Launch at login", "Start Omni-Glass in the menu bar when you log in.", settings.launchAtLogin)}
+        ${toggle("background-mode", "Menu bar only", "Hide the Dock icon (macOS).", settings.backgroundMode)}
+        ${toggle("focus-existing", "Relaunching shows Omni-Glass", "Opening the app while it runs brings its window forward.", settings.focusExisting)}
+        ${toggle("result-on-top", "Results stay on top", "Popped-out and reopened results open above other windows.", settings.actions.resultOnTop)}
+        <label style="display: flex; align-items: center; gap: 8px;">
+          <span style="font-size: 14px;">Launcher hotkey</span>
+          <input id="launcher-hotkey" type="text" placeholder="e.g. Alt+Space" style="
+            flex: 1;
+            padding: 6px 10px;
+            background: #16213e;
+            border: 1px solid rgba(255,255,255,0.15);
+            border-radius: 6px;
+            color: #fff;
+            font-size: 14px;
+          " />
+        </label>
+        <div id="launcher-hotkey-hint" style="font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 10px;">
+          Opens the command palette from anywhere. Leave empty for none.
+        </div>
+        <div id="snip-hotkey-hint" style="font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 10px;"></div>
+        ${toggle("offline-mode", "Offline mode", "Never call cloud providers; snips get a local copy menu.", settings.offlineMode)}
+        <div id="connectivity-hint" style="font-size: 12px; color: #fbbf24;"></div>
+      </div>
+    </section>`;
+
+  const wire = (id: string, command: string) => {
+    const box = document.getElementById(id) as HTMLInputElement;
+    box.addEventListener("change", async () => {
+      try {
+        await invoke(command, { enabled: box.checked });
+      } catch (err) {
+        console.error(`Failed to update ${id}:`, err);
+        box.checked = !box.checked;
+      }
+    });
+  };
+  wire("launch-at-login", "set_autostart");
+  wire("background-mode", "set_background_mode");
+  wire("offline-mode", "set_offline_mode");
+  const focusExisting = document.getElementById("focus-existing") as HTMLInputElement;
+  focusExisting.addEventListener("change", async () => {
+    try {
+      await invoke("update_settings", { patch: { focusExisting: focusExisting.checked } });
+    } catch (err) {
+      console.error("Failed to update focus-existing:", err);
+      focusExisting.checked = !focusExisting.checked;
+    }
+  });
+
+  const resultOnTop = document.getElementById("result-on-top") as HTMLInputElement;
+  resultOnTop.addEventListener("change", async () => {
+    try {
+      await invoke("update_settings", { patch: { actions: { resultOnTop: resultOnTop.checked } } });
+    } catch (err) {
+      console.error("Failed to update result-on-top:", err);
+      resultOnTop.checked = !resultOnTop.checked;
+    }
+  });
+
+  // On Windows the snip key in effect may be a fallback (settings/shortcut.rs)
+  const snipHint = document.getElementById("snip-hotkey-hint")!;
+  const showSnipHotkey = () => invoke<string | null>("get_snip_hotkey")
+    .then((key) => { snipHint.textContent = key ? `Snip hotkey: ${key}` : ""; })
+    .catch(() => {});
+  showSnipHotkey();
+
+  const hotkey = document.getElementById("launcher-hotkey") as HTMLInputElement;
+  const hotkeyHint = document.getElementById("launcher-hotkey-hint")!;
+  hotkey.value = settings.hotkeys.launcher ?? "";
+  hotkey.addEventListener("change", async () => {
+    const launcher = hotkey.value.trim() || null;
+    try {
+      await invoke("update_settings", { patch: { hotkeys: { launcher } } });
+      settings.hotkeys.launcher = launcher;
+      showSnipHotkey();
+      hotkeyHint.style.color = "rgba(255,255,255,0.5)";
+      hotkeyHint.textContent = "Opens the command palette from anywhere. Leave empty for none.";
+    } catch (err) {
+      hotkey.value = settings.hotkeys.launcher ?? "";
+      hotkeyHint.style.color = "#fca5a5";
+      hotkeyHint.textContent = String(err);
+    }
+  });
+
+  const hint = document.getElementById("connectivity-hint")!;
+  const showConnectivity = (c: { reachable: boolean }) => {
+    hint.textContent = c.reachable ? "" : "No network connection — working offline.";
+  };
+  invoke<{ reachable: boolean }>("get_connectivity").then(showConnectivity).catch(() => {});
+  listen<{ reachable: boolean }>("connectivity-changed", (event) => showConnectivity(event.payload));
+
+  const quality = document.getElementById("quality-mode") as HTMLSelectElement;
+  const qualityHint = document.getElementById("quality-hint")!;
+  const showQuality = (q: QualityStatus) => {
+    quality.value = q.setting;
+    const why = q.reason ? SAVER_REASONS[q.reason] : undefined;
+    qualityHint.textContent = q.mode === "saver" && why ? `Saving power — ${why}: fast OCR, lighter previews.` : "";
+  };
+  invoke<QualityStatus>("get_quality_mode").then(showQuality).catch(() => {});
+  listen<QualityStatus>("quality-mode-changed", (event) => showQuality(event.payload));
+  quality.addEventListener("change", async () => {
+    try {
+      showQuality(await invoke<QualityStatus>("set_quality_mode", { mode: quality.value }));
+    } catch (err) {
+      console.error("Failed to set quality mode:", err);
+    }
+  });
+
+  const picker = document.getElementById("active-profile") as HTMLSelectElement;
+  fillProfiles(picker, settings);
+  picker.addEventListener("change", async () => {
+    try {
+      await invoke("switch_profile", { name: picker.value || null });
+    } catch (err) {
+      console.error("Failed to switch profile:", err);
+      picker.value = settings.activeProfile ?? "";
+    }
+  });
+  listen<string | null>("profile-changed", (event) => {
+    settings.activeProfile = event.payload;
+    picker.value = event.payload ?? "";
+  });
+
+  const localePicker = document.getElementById("app-locale") as HTMLSelectElement;
+  try {
+    const info = await invoke<LocaleInfo>("get_locale");
+    const names = new Intl.DisplayNames([info.locale], { type: "language" });
+    localePicker.add(new Option("System default", ""));
+    for (const tag of info.available) localePicker.add(new Option(names.of(tag) ?? tag, tag));
+    localePicker.value = info.setting ?? "";
+  } catch (e) {
+    console.error("Failed to load locales:", e);
+  }
+  localePicker.addEventListener("change", async () => {
+    try {
+      await invoke("set_locale", { locale: localePicker.value || null });
+    } catch (err) {
+      console.error("Failed to set language:", err);
+    }
+  });
+}
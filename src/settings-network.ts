@@ -0,0 +1,123 @@
+/**
+ * Settings — Network section: proxy, custom CA bundle, connectivity test.
+ *
+ * Values go to the Rust settings store under `network` (see net/ in
+ * src-tauri); the proxy password goes to the OS keychain. "Save & Test"
+ * saves, then requests the Anthropic API through the new settings and
+ * shows what happened — proxy used, HTTP status, or the full error.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "./errors";
+
+interface NetworkPrefs {
+  proxyMode: "system" | "manual" | "none";
+  proxyHost: string | null;
+  proxyPort: number | null;
+  proxyUsername: string | null;
+  noProxy: string[];
+  caBundle: string | null;
+}
+
+interface ConnectivityReport {
+  url: string;
+  proxy: string | null;
+  ok: boolean;
+  status: number | null;
+  elapsedMs: number;
+  error: string | null;
+}
+
+const INPUT_STYLE = `width: 100%; padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px; box-sizing: border-box;`;
+
+function field(id: string, label: string, type = "text", placeholder = ""): string {
+  return `
+    <label style="display: block; font-size: 12px; color: rgba(255,255,255,0.7); margin: 8px 0 4px;">${label}</label>
+    <input id="${id}" type="${type}" placeholder="${placeholder}" style="${INPUT_STYLE}" />`;
+}
+
+const value = (id: string) => (document.getElementById(id) as HTMLInputElement).value.trim();
+
+/** Render the Network section into `container` and wire its controls. */
+export async function renderNetworkSection(container: HTMLElement): Promise<void> {
+  let prefs: NetworkPrefs;
+  try {
+    prefs = (await invoke<{ network: NetworkPrefs }>("get_settings")).network;
+  } catch (e) {
+    console.error("Failed to load network settings:", e);
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Network
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <label style="display: block; font-size: 12px; color: rgba(255,255,255,0.7); margin-bottom: 4px;">Proxy</label>
+        <select id="proxy-mode" style="${INPUT_STYLE} cursor: pointer;">
+          <option value="system">Use system settings</option>
+          <option value="manual">Manual</option>
+          <option value="none">No proxy</option>
+        </select>
+        <div id="manual-proxy">
+          ${field("proxy-host", "Host", "text", "proxy.example.com")}
+          ${field("proxy-port", "Port", "number", "3128")}
+          ${field("proxy-username", "User (optional)")}
+          ${field("proxy-password", "Password", "password", "Unchanged")}
+        </div>
+        ${field("no-proxy", "Bypass for hosts (comma-separated)", "text", "localhost, *.corp.example.com")}
+        ${field("ca-bundle", "Custom CA bundle (PEM file path)", "text", "/path/to/corp-root.pem")}
+        <button id="network-save" style="margin-top: 12px; padding: 6px 14px; background: #3b82f6; color: #fff;
+                border: none; border-radius: 6px; cursor: pointer; font-size: 13px;">Save &amp; Test</button>
+        <div id="network-status" style="margin-top: 8px; font-size: 12px; color: rgba(255,255,255,0.6);"></div>
+      </div>
+    </section>`;
+
+  const mode = document.getElementById("proxy-mode") as HTMLSelectElement;
+  const manual = document.getElementById("manual-proxy")!;
+  const set = (id: string, v: string | number | null) => {
+    (document.getElementById(id) as HTMLInputElement).value = v == null ? "" : String(v);
+  };
+  mode.value = prefs.proxyMode;
+  set("proxy-host", prefs.proxyHost);
+  set("proxy-port", prefs.proxyPort);
+  set("proxy-username", prefs.proxyUsername);
+  set("no-proxy", prefs.noProxy.join(", "));
+  set("ca-bundle", prefs.caBundle);
+  const showManual = () => { manual.style.display = mode.value === "manual" ? "block" : "none"; };
+  mode.addEventListener("change", showManual);
+  showManual();
+
+  const status = document.getElementById("network-status")!;
+  document.getElementById("network-save")!.addEventListener("click", async () => {
+    const network: NetworkPrefs = {
+      proxyMode: mode.value as NetworkPrefs["proxyMode"],
+      proxyHost: value("proxy-host") || null,
+      proxyPort: Number(value("proxy-port")) || null,
+      proxyUsername: value("proxy-username") || null,
+      noProxy: value("no-proxy").split(",").map((h) => h.trim()).filter(Boolean),
+      caBundle: value("ca-bundle") || null,
+    };
+    status.textContent = "Testing…";
+    try {
+      await invoke("update_settings", { patch: { network } });
+      const password = value("proxy-password");
+      if (network.proxyUsername && password) {
+        await invoke("set_proxy_password", { password });
+        set("proxy-password", "");
+      }
+      const report = await invoke<ConnectivityReport>("test_connectivity", { url: null });
+      const via = report.proxy ? `via ${report.proxy}` : "direct";
+      status.textContent = report.ok
+        ? `Connected ${via} — HTTP ${report.status} in ${report.elapsedMs} ms`
+        : `Failed ${via}: ${report.error}`;
+      status.style.color = report.ok ? "#4ade80" : "#f87171";
+    } catch (err) {
+      status.textContent = errorMessage(err);
+      status.style.color = "#f87171";
+    }
+  });
+}
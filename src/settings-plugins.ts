@@ -0,0 +1,156 @@
+/**
+ * Settings — Plugins section: what each loaded plugin may do, its
+ * secrets, and developer mode (see src-tauri/src/mcp).
+ *
+ * Permissions come from `get_plugin_permissions`; Revoke takes one away
+ * (`revoke_plugin_permission`) and restarts the plugin without it. A
+ * revoked permission stays revoked until the plugin is uninstalled.
+ * Secrets are the environment variables the manifest declares; they are
+ * kept in the OS keychain (`set_plugin_secret`, empty deletes) and
+ * reach the plugin the next time it starts.
+ *
+ * Developer mode loads a plugin from a folder on disk and reloads it on
+ * every change (`start_plugin_dev`); Console shows its logs.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+import { errorMessage } from "./errors";
+
+interface PermissionAudit {
+  permission: string;
+  revoked: boolean;
+  lastUsed: number | null;
+  usageTracked: boolean;
+}
+
+interface SecretStatus {
+  key: string;
+  isSet: boolean;
+}
+
+interface DevPlugin {
+  id: string;
+  path: string;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+const MUTED = "color: rgba(255,255,255,0.5);";
+
+function permissionRow(pluginId: string, audit: PermissionAudit): string {
+  const used = !audit.usageTracked
+    ? "use not tracked"
+    : audit.lastUsed
+      ? `last used ${new Date(audit.lastUsed * 1000).toLocaleString()}`
+      : "never used";
+  const action = audit.revoked
+    ? `<span style="color: #f87171;">Revoked</span>`
+    : `<button data-revoke="${escapeHtml(audit.permission)}" data-plugin="${escapeHtml(pluginId)}" style="${INPUT_STYLE} cursor: pointer; padding: 2px 8px;">Revoke</button>`;
+  return `
+    <div style="display: flex; align-items: center; gap: 8px; padding: 3px 0;">
+      <span style="flex: 1; font-family: 'SF Mono', Menlo, monospace; word-break: break-all;">${escapeHtml(audit.permission)}</span>
+      <span style="${MUTED}">${used}</span>
+      ${action}
+    </div>`;
+}
+
+function secretRow(pluginId: string, secret: SecretStatus): string {
+  return `
+    <div style="display: flex; align-items: center; gap: 8px; padding: 3px 0;">
+      <span style="flex: 1; font-family: 'SF Mono', Menlo, monospace;">${escapeHtml(secret.key)}</span>
+      <input type="password" data-secret="${escapeHtml(secret.key)}" data-plugin="${escapeHtml(pluginId)}"
+             placeholder="${secret.isSet ? "Saved — type to replace" : "Not set"}" style="${INPUT_STYLE} width: 180px;" />
+      <button data-save-secret="${escapeHtml(secret.key)}" data-plugin="${escapeHtml(pluginId)}" style="${INPUT_STYLE} cursor: pointer; padding: 2px 8px;">Save</button>
+    </div>`;
+}
+
+async function pluginBlock(pluginId: string): Promise<string> {
+  const permissions = await invoke<PermissionAudit[]>("get_plugin_permissions", { pluginId }).catch(() => []);
+  const secrets = await invoke<SecretStatus[]>("get_plugin_secret_status", { pluginId }).catch(() => []);
+  const none = `<div style="${MUTED}">None</div>`;
+  return `
+    <div style="padding: 8px 0; border-bottom: 1px solid rgba(255,255,255,0.06); font-size: 12px;">
+      <div style="font-size: 13px; margin-bottom: 4px;">${escapeHtml(pluginId)}</div>
+      <div style="${MUTED} margin: 4px 0 2px;">Permissions</div>
+      ${permissions.map((p) => permissionRow(pluginId, p)).join("") || none}
+      ${secrets.length ? `<div style="${MUTED} margin: 6px 0 2px;">Secrets</div>${secrets.map((s) => secretRow(pluginId, s)).join("")}` : ""}
+    </div>`;
+}
+
+function devRow(plugin: DevPlugin): string {
+  return `
+    <div style="display: flex; align-items: center; gap: 8px; padding: 3px 0; font-size: 12px;">
+      <span style="flex: 1; word-break: break-all;">${escapeHtml(plugin.id)} <span style="${MUTED}">${escapeHtml(plugin.path)}</span></span>
+      <button data-console="${escapeHtml(plugin.id)}" style="${INPUT_STYLE} cursor: pointer; padding: 2px 8px;">Console</button>
+      <button data-stop="${escapeHtml(plugin.id)}" style="${INPUT_STYLE} cursor: pointer; padding: 2px 8px;">Stop</button>
+    </div>`;
+}
+
+/** Render the Plugins section into `container` and wire its controls. */
+export async function renderPluginsSection(container: HTMLElement): Promise<void> {
+  let pluginIds: string[];
+  let dev: DevPlugin[];
+  try {
+    const health = await invoke<{ pluginId: string }[]>("get_plugin_health");
+    dev = await invoke<DevPlugin[]>("get_dev_plugins");
+    pluginIds = health.map((h) => h.pluginId);
+  } catch (e) {
+    console.error("Failed to load plugins:", e);
+    return;
+  }
+  const blocks = await Promise.all(pluginIds.map(pluginBlock));
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Plugins
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div>${blocks.join("") || `<div style="font-size: 12px; ${MUTED}">No plugins loaded</div>`}</div>
+        <div style="font-size: 12px; ${MUTED} margin: 12px 0 4px;">Developer mode</div>
+        <div>${dev.map(devRow).join("")}</div>
+        <div style="display: flex; gap: 8px; margin-top: 6px;">
+          <input id="plugin-dev-path" type="text" placeholder="/path/to/plugin" style="${INPUT_STYLE} flex: 1;" />
+          <button id="plugin-dev-start" style="${INPUT_STYLE} cursor: pointer;">Load</button>
+        </div>
+        <div id="plugins-status" style="margin-top: 8px; font-size: 12px; color: #f87171;"></div>
+      </div>
+    </section>`;
+
+  const status = document.getElementById("plugins-status")!;
+  // Run a change and redraw the section, or show why it failed
+  const act = async (work: () => Promise<unknown>) => {
+    try {
+      await work();
+      await renderPluginsSection(container);
+    } catch (err) {
+      status.textContent = errorMessage(err);
+    }
+  };
+
+  container.querySelectorAll<HTMLButtonElement>("[data-revoke]").forEach((button) => {
+    const { plugin, revoke } = button.dataset;
+    button.addEventListener("click", () => act(() => invoke("revoke_plugin_permission", { pluginId: plugin, permission: revoke })));
+  });
+  container.querySelectorAll<HTMLButtonElement>("[data-save-secret]").forEach((button) => {
+    const { plugin, saveSecret } = button.dataset;
+    const input = container.querySelector<HTMLInputElement>(`input[data-plugin="${plugin}"][data-secret="${saveSecret}"]`)!;
+    button.addEventListener("click", () =>
+      act(() => invoke("set_plugin_secret", { pluginId: plugin, key: saveSecret, value: input.value })),
+    );
+  });
+  container.querySelectorAll<HTMLButtonElement>("[data-console]").forEach((button) => {
+    button.addEventListener("click", () => invoke("open_plugin_console", { pluginId: button.dataset.console }).catch((err) => {
+      status.textContent = errorMessage(err);
+    }));
+  });
+  container.querySelectorAll<HTMLButtonElement>("[data-stop]").forEach((button) => {
+    button.addEventListener("click", () => act(() => invoke("stop_plugin_dev", { pluginId: button.dataset.stop })));
+  });
+  const path = document.getElementById("plugin-dev-path") as HTMLInputElement;
+  document.getElementById("plugin-dev-start")!.addEventListener("click", () => {
+    if (path.value.trim()) act(() => invoke("start_plugin_dev", { path: path.value.trim() }));
+  });
+}
@@ -0,0 +1,253 @@
+/**
+ * Settings — AI Provider section: the active provider, and per provider an
+ * API key field with Save and Test buttons.
+ *
+ * Keys are stored in the OS keychain by Rust (keyring crate); a saved key is
+ * shown masked and never read back. Switching provider redraws the section.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface ProviderInfo {
+  id: string;
+  name: string;
+  envKey: string;
+  costPerSnip: string;
+  speedStars: number;
+  qualityStars: number;
+}
+
+interface ProviderConfig {
+  activeProvider: string;
+  providers: ProviderInfo[];
+  configuredProviders: string[];
+}
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+function stars(count: number): string {
+  return "\u2605".repeat(count) + "\u2606".repeat(5 - count);
+}
+
+const MASK = "\u2022".repeat(16);
+
+export async function renderProviderSection(container: HTMLElement): Promise<void> {
+  let config: ProviderConfig;
+  try {
+    config = await invoke<ProviderConfig>("get_provider_config");
+  } catch (e) {
+    container.innerHTML = `<div style="color:#f87171;">Failed to load providers: ${escapeHtml(String(e))}</div>`;
+    return;
+  }
+
+  container.innerHTML = `
+  <section style="margin-bottom: 24px;">
+    <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+      AI Provider
+    </h2>
+
+    <div style="margin-bottom: 16px;">
+      <label style="font-size: 13px; color: rgba(255,255,255,0.7); display: block; margin-bottom: 6px;">
+        Active Provider
+      </label>
+      <select id="provider-select" style="
+        width: 100%;
+        padding: 8px 12px;
+        background: #16213e;
+        border: 1px solid rgba(255,255,255,0.15);
+        border-radius: 6px;
+        color: #fff;
+        font-size: 14px;
+        outline: none;
+        cursor: pointer;
+      ">
+        ${config.providers
+          .map(
+            (p) =>
+              `<option value="${p.id}" ${p.id === config.activeProvider ? "selected" : ""}>
+                ${escapeHtml(p.name)}${p.id === config.activeProvider ? " (Active)" : ""}
+              </option>`
+          )
+          .join("")}
+      </select>
+    </div>
+
+    <div id="provider-cards">
+      ${config.providers.map((p) => renderProviderCard(p, config)).join("")}
+    </div>
+  </section>
+  `;
+  attachHandlers(container, config);
+}
+
+function renderProviderCard(provider: ProviderInfo, config: ProviderConfig): string {
+  const isConfigured = config.configuredProviders.includes(provider.id);
+  const isActive = provider.id === config.activeProvider;
+
+  return `
+    <div class="provider-card" data-provider-id="${provider.id}" style="
+      background: ${isActive ? "#16213e" : "#0f1629"};
+      border: 1px solid ${isActive ? "#3b82f6" : "rgba(255,255,255,0.1)"};
+      border-radius: 8px;
+      padding: 14px;
+      margin-bottom: 10px;
+      transition: border-color 0.2s;
+    ">
+      <div style="display: flex; justify-content: space-between; align-items: center; margin-bottom: 10px;">
+        <span style="font-weight: 500; font-size: 14px;">${escapeHtml(provider.name)}</span>
+        ${isActive ? '<span style="font-size: 11px; background: #3b82f6; padding: 2px 8px; border-radius: 10px;">Active</span>' : ""}
+      </div>
+
+      <div style="display: flex; gap: 16px; font-size: 12px; color: rgba(255,255,255,0.6); margin-bottom: 10px;">
+        <span>Speed: ${stars(provider.speedStars)}</span>
+        <span>Quality: ${stars(provider.qualityStars)}</span>
+        <span>Cost: ${escapeHtml(provider.costPerSnip)}</span>
+      </div>
+
+      <div style="display: flex; gap: 8px; align-items: center;">
+        <input
+          type="password"
+          class="api-key-input"
+          data-provider="${provider.id}"
+          placeholder="API Key"
+          style="
+            flex: 1;
+            padding: 6px 10px;
+            background: #0d1117;
+            border: 1px solid rgba(255,255,255,0.15);
+            border-radius: 4px;
+            color: #fff;
+            font-size: 13px;
+            font-family: monospace;
+            outline: none;
+          "
+          value="${isConfigured ? MASK : ""}"
+        />
+        <button
+          class="save-key-btn"
+          data-provider="${provider.id}"
+          style="
+            padding: 6px 12px;
+            background: #16213e;
+            border: 1px solid rgba(255,255,255,0.2);
+            border-radius: 4px;
+            color: #fff;
+            font-size: 13px;
+            cursor: pointer;
+          "
+        >Save</button>
+        <button
+          class="test-btn"
+          data-provider="${provider.id}"
+          style="
+            padding: 6px 12px;
+            background: #16213e;
+            border: 1px solid rgba(255,255,255,0.2);
+            border-radius: 4px;
+            color: #fff;
+            font-size: 13px;
+            cursor: pointer;
+          "
+        >Test</button>
+        <span class="test-result" data-provider="${provider.id}" style="font-size: 14px; width: 20px; text-align: center;">
+          ${isConfigured ? "\u2713" : ""}
+        </span>
+      </div>
+    </div>
+  `;
+}
+
+function attachHandlers(container: HTMLElement, config: ProviderConfig): void {
+  // Provider selection dropdown
+  const select = document.getElementById("provider-select") as HTMLSelectElement;
+  select.addEventListener("change", async () => {
+    try {
+      await invoke("set_active_provider", { providerId: select.value });
+      await renderProviderSection(container);
+    } catch (e) {
+      console.error("Failed to set provider:", e);
+    }
+  });
+
+  // Save API key buttons
+  document.querySelectorAll(".save-key-btn").forEach((btn) => {
+    btn.addEventListener("click", async () => {
+      const providerId = (btn as HTMLElement).dataset.provider!;
+      const input = document.querySelector(
+        `.api-key-input[data-provider="${providerId}"]`
+      ) as HTMLInputElement;
+      const key = input.value.trim();
+
+      // Don't save masked placeholder
+      if (!key || key === MASK) {
+        return;
+      }
+
+      try {
+        await invoke("save_api_key", { providerId, apiKey: key });
+        const result = document.querySelector(
+          `.test-result[data-provider="${providerId}"]`
+        ) as HTMLElement;
+        result.textContent = "\u2713";
+        result.style.color = "#4ade80";
+        // Mask the input after saving
+        input.value = MASK;
+        input.type = "password";
+      } catch (e) {
+        console.error("Failed to save key:", e);
+        const result = document.querySelector(
+          `.test-result[data-provider="${providerId}"]`
+        ) as HTMLElement;
+        result.textContent = "\u2717";
+        result.style.color = "#f87171";
+      }
+    });
+  });
+
+  // Test connection buttons
+  document.querySelectorAll(".test-btn").forEach((btn) => {
+    btn.addEventListener("click", async () => {
+      const providerId = (btn as HTMLElement).dataset.provider!;
+      const result = document.querySelector(
+        `.test-result[data-provider="${providerId}"]`
+      ) as HTMLElement;
+      result.textContent = "\u22EF";
+      result.style.color = "#facc15";
+
+      try {
+        const ok = await invoke<boolean>("test_provider", { providerId });
+        result.textContent = ok ? "\u2713" : "\u2717";
+        result.style.color = ok ? "#4ade80" : "#f87171";
+      } catch (e) {
+        result.textContent = "\u2717";
+        result.style.color = "#f87171";
+        console.error("Test failed:", e);
+      }
+    });
+  });
+
+  // Focus/blur on key inputs — show/hide password
+  document.querySelectorAll(".api-key-input").forEach((input) => {
+    const el = input as HTMLInputElement;
+    el.addEventListener("focus", () => {
+      if (el.value === MASK) {
+        el.value = "";
+      }
+      el.type = "text";
+    });
+    el.addEventListener("blur", () => {
+      if (el.value === "") {
+        const providerId = el.dataset.provider!;
+        if (config.configuredProviders.includes(providerId)) {
+          el.value = MASK;
+        }
+      }
+      el.type = "password";
+    });
+  });
+}
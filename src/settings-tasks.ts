@@ -0,0 +1,108 @@
+/**
+ * Settings — Scheduled Tasks section: tool calls plugins want to run on
+ * an interval, and what their recent runs returned (see
+ * src-tauri/src/mcp/schedule).
+ *
+ * No task runs until it is approved here (`set_task_approved`). The
+ * approval covers the tool, arguments, and interval shown; a plugin
+ * update that changes any of them shows the task as not approved again.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+
+interface TaskInfo {
+  pluginId: string;
+  id: string;
+  tool: string;
+  every: string;
+  arguments: Record<string, unknown>;
+  description: string;
+  approved: boolean;
+  lastRun: number;
+}
+
+interface TaskRun {
+  pluginId: string;
+  taskId: string;
+  success: boolean;
+  text: string;
+  timestamp: number;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+const RUNS_SHOWN = 10;
+
+function taskRow(task: TaskInfo, i: number): string {
+  const args = JSON.stringify(task.arguments);
+  const lastRun = task.lastRun ? new Date(task.lastRun * 1000).toLocaleString() : "never";
+  return `
+    <div style="padding: 8px 0; border-bottom: 1px solid rgba(255,255,255,0.06); font-size: 12px;">
+      <div style="display: flex; align-items: center; gap: 8px;">
+        <span style="font-size: 13px; flex: 1;">${escapeHtml(task.description || task.id)}</span>
+        <span style="color: ${task.approved ? "#4ade80" : "rgba(255,255,255,0.4)"};">${task.approved ? "Approved" : "Not approved"}</span>
+        <button data-task="${i}" style="${INPUT_STYLE} cursor: pointer;">${task.approved ? "Stop" : "Approve"}</button>
+      </div>
+      <div style="margin-top: 4px; color: rgba(255,255,255,0.5); font-family: 'SF Mono', Menlo, monospace; word-break: break-all;">
+        ${escapeHtml(task.pluginId)} · ${escapeHtml(task.tool)}(${escapeHtml(args)}) every ${escapeHtml(task.every)}
+      </div>
+      <div style="margin-top: 2px; color: rgba(255,255,255,0.4);">Last run: ${lastRun}</div>
+    </div>`;
+}
+
+function runRow(run: TaskRun): string {
+  const when = new Date(run.timestamp * 1000).toLocaleString();
+  return `
+    <div style="font-size: 11px; padding: 2px 0; color: rgba(255,255,255,0.6);">
+      <span style="color: ${run.success ? "#4ade80" : "#f87171"};">${run.success ? "ok" : "failed"}</span>
+      ${escapeHtml(run.pluginId)}/${escapeHtml(run.taskId)} · ${when} · ${escapeHtml(run.text)}
+    </div>`;
+}
+
+/** Render the Scheduled Tasks section into `container` and wire its controls. */
+export async function renderTasksSection(container: HTMLElement): Promise<void> {
+  let tasks: TaskInfo[];
+  let runs: TaskRun[];
+  try {
+    tasks = await invoke<TaskInfo[]>("get_scheduled_tasks");
+    runs = await invoke<TaskRun[]>("get_task_history");
+  } catch (e) {
+    console.error("Failed to load scheduled tasks:", e);
+    return;
+  }
+  if (tasks.length === 0 && runs.length === 0) {
+    container.innerHTML = "";
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Scheduled Tasks
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div style="font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 6px;">
+          Plugins can call one of their tools on a schedule, without a snip. A task runs only after you approve it, and only as shown.
+        </div>
+        <div>${tasks.map(taskRow).join("")}</div>
+        <div id="tasks-status" style="margin-top: 8px; font-size: 12px; color: #f87171;"></div>
+        <div style="font-size: 12px; color: rgba(255,255,255,0.5); margin: 12px 0 4px;">Recent runs</div>
+        <div>${runs.slice(-RUNS_SHOWN).reverse().map(runRow).join("") || `<div style="font-size: 11px; color: rgba(255,255,255,0.4);">None yet</div>`}</div>
+      </div>
+    </section>`;
+
+  const status = document.getElementById("tasks-status")!;
+  container.querySelectorAll<HTMLButtonElement>("[data-task]").forEach((button) => {
+    const task = tasks[Number(button.dataset.task)];
+    button.addEventListener("click", async () => {
+      try {
+        await invoke("set_task_approved", { pluginId: task.pluginId, taskId: task.id, approved: !task.approved });
+        await renderTasksSection(container);
+      } catch (err) {
+        status.textContent = String(err);
+      }
+    });
+  });
+}
@@ -0,0 +1,198 @@
+/**
+ * Settings — Triggers section: Stream Deck buttons, scripts, and MIDI
+ * notes that start snips (see triggers/ in src-tauri).
+ *
+ * The local API and MIDI device are plain settings (`triggers.*`).
+ * Mappings go through `set_trigger_mapping`; "Learn" waits for the next
+ * button press or note with `learn_trigger` and offers to map it.
+ * Region presets are saved from the last snip with `save_region_preset`.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface RegionPreset {
+  name: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+interface TriggerPrefs {
+  apiEnabled: boolean;
+  apiPort: number;
+  midiDevice: string | null;
+  presets: RegionPreset[];
+  mappings: Record<string, string>;
+}
+
+interface TriggerStatus {
+  apiUrl: string | null;
+  apiToken: string | null;
+  midiDevices: string[];
+  midiConnected: string | null;
+  hasLastRegion: boolean;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+const LEARN_SECS = 10;
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+function actionOptions(presets: RegionPreset[], selected: string): string {
+  const actions: [string, string][] = [
+    ["snip", "Snip"],
+    ["resnip_last", "Snip last region again"],
+    ["launcher", "Text launcher"],
+    ...presets.map((p, i): [string, string] => [`preset:${i + 1}`, `Preset: ${p.name}`]),
+  ];
+  return actions
+    .map(([value, label]) => `<option value="${value}" ${value === selected ? "selected" : ""}>${escapeHtml(label)}</option>`)
+    .join("");
+}
+
+/** Mappings after removing preset `n`: its mappings go, later presets move up. */
+function withoutPreset(mappings: Record<string, string>, n: number): Record<string, string | null> {
+  const next: Record<string, string | null> = {};
+  for (const [input, action] of Object.entries(mappings)) {
+    const preset = action.startsWith("preset:") ? Number(action.slice(7)) : 0;
+    if (preset === n) next[input] = null;
+    else if (preset > n) next[input] = `preset:${preset - 1}`;
+  }
+  return next;
+}
+
+/** Render the Triggers section into `container` and wire its controls. */
+export async function renderTriggersSection(container: HTMLElement): Promise<void> {
+  let prefs: TriggerPrefs;
+  let state: TriggerStatus;
+  try {
+    prefs = (await invoke<{ triggers: TriggerPrefs }>("get_settings")).triggers;
+    state = await invoke<TriggerStatus>("get_trigger_status");
+  } catch (e) {
+    console.error("Failed to load trigger settings:", e);
+    return;
+  }
+
+  const devices = [...new Set([...state.midiDevices, ...(prefs.midiDevice ? [prefs.midiDevice] : [])])];
+  const mappingRows = Object.entries(prefs.mappings).map(([input, action]) => `
+    <div data-input="${input}" style="display: flex; align-items: center; gap: 8px; padding: 4px 0; font-size: 12px;">
+      <code style="flex: 1;">${escapeHtml(input)}</code>
+      <select data-field="action" style="${INPUT_STYLE}">${actionOptions(prefs.presets, action)}</select>
+      <button data-action="unmap" style="${INPUT_STYLE} cursor: pointer;">Remove</button>
+    </div>`).join("");
+  const presetRows = prefs.presets.map((p, i) => `
+    <div data-preset="${i + 1}" style="display: flex; align-items: center; gap: 8px; padding: 4px 0; font-size: 12px;">
+      <span style="flex: 1;">${i + 1}. ${escapeHtml(p.name)}
+        <span style="color: rgba(255,255,255,0.4);">${p.width}×${p.height} at ${p.x},${p.y}</span></span>
+      <button data-action="remove-preset" style="${INPUT_STYLE} cursor: pointer;">Remove</button>
+    </div>`).join("");
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Triggers
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px; font-size: 13px;">
+        <label style="display: flex; align-items: center; gap: 8px;">
+          <input type="checkbox" id="trigger-api" ${prefs.apiEnabled ? "checked" : ""} />
+          Local API for Stream Deck and scripts, port
+          <input id="trigger-port" type="number" min="1024" max="65535" value="${prefs.apiPort}" style="${INPUT_STYLE} width: 80px;" />
+        </label>
+        <div style="font-size: 11px; color: rgba(255,255,255,0.5); margin: 4px 0 0 24px;">
+          ${state.apiUrl ? `POST ${escapeHtml(state.apiUrl)}/trigger/&lt;name&gt; · ` : ""}Token:
+          <code id="trigger-token">${escapeHtml(state.apiToken ?? "unavailable")}</code>
+          <a href="#" id="trigger-regenerate" style="color: #60a5fa; text-decoration: none; margin-left: 6px;">Regenerate</a>
+        </div>
+        <label style="display: flex; align-items: center; gap: 8px; margin-top: 10px;">
+          MIDI device
+          <select id="trigger-midi" style="${INPUT_STYLE}">
+            <option value="">None</option>
+            ${devices.map((d) => `<option value="${escapeHtml(d)}" ${d === prefs.midiDevice ? "selected" : ""}>${escapeHtml(d)}</option>`).join("")}
+          </select>
+          <span style="font-size: 11px; color: rgba(255,255,255,0.4);">${state.midiConnected ? "Connected" : prefs.midiDevice ? "Not connected" : ""}</span>
+        </label>
+        <div style="font-size: 12px; color: rgba(255,255,255,0.5); margin: 12px 0 4px;">Mappings</div>
+        <div id="trigger-mappings">${mappingRows || `<div style="font-size: 11px; color: rgba(255,255,255,0.4);">None yet</div>`}</div>
+        <div style="display: flex; gap: 6px; margin-top: 6px;">
+          <button id="trigger-learn" style="${INPUT_STYLE} cursor: pointer;">Learn…</button>
+          <input id="trigger-name" placeholder="or API name, e.g. deck-1" style="${INPUT_STYLE} flex: 1;" />
+          <button id="trigger-add" style="${INPUT_STYLE} cursor: pointer;">Add</button>
+        </div>
+        <div style="font-size: 12px; color: rgba(255,255,255,0.5); margin: 12px 0 4px;">Region presets</div>
+        <div>${presetRows || `<div style="font-size: 11px; color: rgba(255,255,255,0.4);">None yet</div>`}</div>
+        <div style="display: flex; gap: 6px; margin-top: 6px;">
+          <input id="preset-name" placeholder="Preset name" style="${INPUT_STYLE} flex: 1;" />
+          <button id="preset-save" ${state.hasLastRegion ? "" : "disabled"} style="${INPUT_STYLE} cursor: pointer;"
+                  title="${state.hasLastRegion ? "" : "Snip a region first"}">Save last snip region</button>
+        </div>
+        <div id="trigger-status" style="margin-top: 8px; font-size: 12px; color: #f87171;"></div>
+      </div>
+    </section>`;
+
+  const status = document.getElementById("trigger-status")!;
+  const rerender = () => renderTriggersSection(container);
+  const run = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+      rerender();
+    } catch (err) {
+      status.style.color = "#f87171";
+      status.textContent = String(err);
+    }
+  };
+  const saveTriggers = (patch: Partial<Record<keyof TriggerPrefs, unknown>>) =>
+    run("update_settings", { patch: { triggers: patch } });
+  const map = (input: string, action: string | null) => run("set_trigger_mapping", { input, action });
+
+  document.getElementById("trigger-api")!.addEventListener("change", (e) =>
+    saveTriggers({ apiEnabled: (e.target as HTMLInputElement).checked }));
+  document.getElementById("trigger-port")!.addEventListener("change", (e) =>
+    saveTriggers({ apiPort: Number((e.target as HTMLInputElement).value) }));
+  document.getElementById("trigger-midi")!.addEventListener("change", (e) =>
+    saveTriggers({ midiDevice: (e.target as HTMLSelectElement).value || null }));
+  document.getElementById("trigger-regenerate")!.addEventListener("click", (e) => {
+    e.preventDefault();
+    run("regenerate_trigger_token", {});
+  });
+
+  container.querySelectorAll<HTMLElement>("[data-input]").forEach((row) => {
+    const input = row.dataset.input!;
+    row.querySelector("[data-field=action]")!.addEventListener("change", (e) => map(input, (e.target as HTMLSelectElement).value));
+    row.querySelector("[data-action=unmap]")!.addEventListener("click", () => map(input, null));
+  });
+  document.getElementById("trigger-add")!.addEventListener("click", () => {
+    const name = (document.getElementById("trigger-name") as HTMLInputElement).value.trim();
+    if (name) map(`api:${name}`, "snip");
+  });
+  document.getElementById("trigger-learn")!.addEventListener("click", async () => {
+    status.style.color = "rgba(255,255,255,0.6)";
+    status.textContent = `Press a Stream Deck button or MIDI pad within ${LEARN_SECS} seconds…`;
+    const input = await invoke<string | null>("learn_trigger", { timeoutSecs: LEARN_SECS }).catch(() => null);
+    if (input) map(input, prefs.mappings[input] ?? "snip");
+    else status.textContent = "Nothing received. Is the API enabled or the MIDI device connected?";
+  });
+
+  container.querySelectorAll<HTMLElement>("[data-preset]").forEach((row) => {
+    const n = Number(row.dataset.preset);
+    row.querySelector("[data-action=remove-preset]")!.addEventListener("click", () =>
+      saveTriggers({
+        presets: prefs.presets.filter((_, i) => i !== n - 1),
+        mappings: withoutPreset(prefs.mappings, n),
+      }));
+  });
+  document.getElementById("preset-save")!.addEventListener("click", () => {
+    const name = (document.getElementById("preset-name") as HTMLInputElement).value.trim();
+    if (!name) {
+      status.textContent = "Name the preset first";
+      return;
+    }
+    run("save_region_preset", { name });
+  });
+}
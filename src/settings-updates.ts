@@ -0,0 +1,159 @@
+/**
+ * Settings — Updates section: release channel, background checks, and
+ * "Check now" with Install / Skip for what it finds (see
+ * src-tauri/src/updater).
+ *
+ * Channel and auto-check are saved under `updates`. A found update
+ * starts downloading at once; Install downloads it first if it isn't
+ * ready yet, then restarts the app. Skip stores the version in
+ * `updates.skippedVersion` so it isn't offered again, until "Offer it
+ * again" clears it.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { escapeHtml } from "./action-menu-render";
+import { errorMessage } from "./errors";
+
+interface UpdatePrefs {
+  channel: "stable" | "beta";
+  autoCheck: boolean;
+  skippedVersion: string | null;
+}
+
+interface UpdateInfo {
+  version: string;
+  currentVersion: string;
+  notes: string | null;
+  date: string | null;
+  rollback: boolean;
+  downloaded: boolean;
+}
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+
+function offer(update: UpdateInfo): string {
+  const what = update.rollback
+    ? `Version ${escapeHtml(update.version)} is available (back to stable from ${escapeHtml(update.currentVersion)}).`
+    : `Version ${escapeHtml(update.version)} is available (you have ${escapeHtml(update.currentVersion)}).`;
+  const notes = update.notes
+    ? `<div style="margin-top: 4px; white-space: pre-wrap; color: rgba(255,255,255,0.6);">${escapeHtml(update.notes)}</div>`
+    : "";
+  return `
+    <div style="font-size: 13px;">${what} ${update.downloaded ? "Downloaded and ready." : "Downloading…"}</div>
+    ${notes}
+    <div style="display: flex; gap: 8px; margin-top: 8px;">
+      <button id="update-install" style="${INPUT_STYLE} cursor: pointer; background: #3b82f6; border: none;">Install and Restart</button>
+      <button id="update-skip" style="${INPUT_STYLE} cursor: pointer;">Skip This Version</button>
+    </div>`;
+}
+
+/** Render the Updates section into `container` and wire its controls. */
+export async function renderUpdatesSection(container: HTMLElement): Promise<void> {
+  let prefs: UpdatePrefs;
+  try {
+    prefs = (await invoke<{ updates: UpdatePrefs }>("get_settings")).updates;
+  } catch (e) {
+    console.error("Failed to load update settings:", e);
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Updates
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <label style="display: flex; align-items: center; gap: 8px; font-size: 14px; margin-bottom: 10px;">
+          Channel
+          <select id="update-channel" style="${INPUT_STYLE} cursor: pointer;">
+            <option value="stable">Stable</option>
+            <option value="beta">Beta (pre-releases)</option>
+          </select>
+        </label>
+        <label style="display: flex; align-items: center; gap: 8px; cursor: pointer; margin-bottom: 10px;">
+          <input type="checkbox" id="update-auto" ${prefs.autoCheck ? "checked" : ""} style="accent-color: #3b82f6;" />
+          <span style="font-size: 14px;">Check for updates automatically</span>
+        </label>
+        <div id="update-skipped" style="font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 10px;"></div>
+        <button id="update-check" style="${INPUT_STYLE} cursor: pointer;">Check Now</button>
+        <div id="update-offer" style="margin-top: 10px;"></div>
+        <div id="update-status" style="margin-top: 8px; font-size: 12px; color: rgba(255,255,255,0.6);"></div>
+      </div>
+    </section>`;
+
+  const channel = document.getElementById("update-channel") as HTMLSelectElement;
+  const auto = document.getElementById("update-auto") as HTMLInputElement;
+  const offerEl = document.getElementById("update-offer")!;
+  const status = document.getElementById("update-status")!;
+  const skipped = document.getElementById("update-skipped")!;
+  channel.value = prefs.channel;
+
+  const fail = (err: unknown) => {
+    status.textContent = errorMessage(err);
+    status.style.color = "#f87171";
+  };
+  const save = async (updates: Partial<UpdatePrefs>) => {
+    try {
+      await invoke("update_settings", { patch: { updates } });
+      status.textContent = "";
+    } catch (err) {
+      fail(err);
+    }
+  };
+  channel.addEventListener("change", async () => {
+    offerEl.innerHTML = "";
+    await save({ channel: channel.value as UpdatePrefs["channel"] });
+  });
+  auto.addEventListener("change", () => save({ autoCheck: auto.checked }));
+
+  const showSkipped = (version: string | null) => {
+    skipped.style.display = version ? "block" : "none";
+    if (!version) return;
+    skipped.textContent = `Version ${version} is skipped. `;
+    const again = document.createElement("a");
+    again.href = "#";
+    again.textContent = "Offer it again";
+    again.style.color = "#60a5fa";
+    again.addEventListener("click", async (e) => {
+      e.preventDefault();
+      await save({ skippedVersion: null });
+      showSkipped(null);
+    });
+    skipped.append(again);
+  };
+  showSkipped(prefs.skippedVersion);
+
+  document.getElementById("update-check")!.addEventListener("click", async () => {
+    offerEl.innerHTML = "";
+    status.style.color = "rgba(255,255,255,0.6)";
+    status.textContent = "Checking…";
+    let found: UpdateInfo | null;
+    try {
+      found = await invoke<UpdateInfo | null>("check_for_updates");
+    } catch (err) {
+      fail(err);
+      return;
+    }
+    status.textContent = found ? "" : "Omni-Glass is up to date.";
+    if (!found) return;
+    const update = found;
+
+    offerEl.innerHTML = offer(update);
+    document.getElementById("update-install")!.addEventListener("click", async () => {
+      status.textContent = "Installing… Omni-Glass restarts when done.";
+      // Resolves only on failure; success restarts the app
+      await invoke("install_update").catch(fail);
+    });
+    document.getElementById("update-skip")!.addEventListener("click", async () => {
+      try {
+        await invoke("skip_update", { version: update.version });
+        offerEl.innerHTML = "";
+        showSkipped(update.version);
+      } catch (err) {
+        fail(err);
+      }
+    });
+  });
+}
@@ -0,0 +1,182 @@
+/**
+ * Settings — Webhooks section: endpoints that receive snip events, and
+ * the recent delivery log.
+ *
+ * Endpoints are saved under `webhooks.endpoints` (see webhooks/ in
+ * src-tauri); the whole list is sent on every change, since a patch
+ * replaces arrays. Signing secrets go to the OS keychain through
+ * `set_webhook_secret`. "Test" sends a `ping` and shows the outcome.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface Webhook {
+  id: string;
+  name: string;
+  url: string;
+  events: string[];
+  enabled: boolean;
+  includeText: boolean;
+}
+
+interface DeliveryEntry {
+  deliveryId: string;
+  webhookId: string;
+  event: string;
+  outcome: "delivered" | "failed" | "skipped";
+  status: number | null;
+  attempts: number;
+  error: string | null;
+  timestamp: number;
+}
+
+const EVENTS = ["snip.completed", "action.executed"];
+const LOG_LIMIT = 20;
+
+const INPUT_STYLE = `padding: 6px 10px; background: #16213e; color: #fff;
+  border: 1px solid rgba(255,255,255,0.15); border-radius: 6px; font-size: 13px;`;
+const OUTCOME_COLOR = { delivered: "#4ade80", failed: "#f87171", skipped: "rgba(255,255,255,0.4)" };
+
+function escapeHtml(text: string): string {
+  const div = document.createElement("div");
+  div.textContent = text;
+  return div.innerHTML;
+}
+
+/** `Team Slack` → `team-slack`, unique among `taken`. */
+function slug(name: string, taken: string[]): string {
+  const base = name.toLowerCase().replace(/[^a-z0-9]+/g, "-").replace(/^-|-$/g, "").slice(0, 32) || "webhook";
+  let id = base;
+  for (let n = 2; taken.includes(id); n++) id = `${base}-${n}`;
+  return id;
+}
+
+function endpointRow(hook: Webhook): string {
+  const events = EVENTS.map((e) => `
+    <label style="margin-right: 10px;"><input type="checkbox" data-event="${e}" ${hook.events.includes(e) ? "checked" : ""} /> ${e}</label>`).join("");
+  return `
+    <div data-hook="${hook.id}" style="padding: 8px 0; border-bottom: 1px solid rgba(255,255,255,0.08); font-size: 12px;">
+      <div style="display: flex; align-items: center; gap: 8px;">
+        <input type="checkbox" data-field="enabled" ${hook.enabled ? "checked" : ""} title="Enabled" />
+        <span style="font-size: 13px;">${escapeHtml(hook.name || hook.id)}</span>
+        <span style="flex: 1; color: rgba(255,255,255,0.4); overflow: hidden; text-overflow: ellipsis;">${escapeHtml(hook.url)}</span>
+        <button data-action="test" style="${INPUT_STYLE} cursor: pointer;">Test</button>
+        <button data-action="remove" style="${INPUT_STYLE} cursor: pointer;">Remove</button>
+      </div>
+      <div style="margin-top: 4px; color: rgba(255,255,255,0.7);">
+        ${events}
+        <label><input type="checkbox" data-field="includeText" ${hook.includeText ? "checked" : ""} /> Include snip text (redacted)</label>
+      </div>
+    </div>`;
+}
+
+function deliveryRow(d: DeliveryEntry): string {
+  const when = new Date(d.timestamp * 1000).toLocaleString();
+  const detail = d.error ?? (d.status ? `HTTP ${d.status}` : "");
+  return `
+    <div style="font-size: 11px; padding: 2px 0; color: rgba(255,255,255,0.6);">
+      <span style="color: ${OUTCOME_COLOR[d.outcome]};">${d.outcome}</span>
+      ${escapeHtml(d.event)} → ${escapeHtml(d.webhookId)} · ${when}${d.attempts > 1 ? ` · ${d.attempts} attempts` : ""}
+      ${detail ? `· ${escapeHtml(detail)}` : ""}
+    </div>`;
+}
+
+/** Render the Webhooks section into `container` and wire its controls. */
+export async function renderWebhooksSection(container: HTMLElement): Promise<void> {
+  let hooks: Webhook[];
+  let deliveries: DeliveryEntry[];
+  try {
+    hooks = (await invoke<{ webhooks: { endpoints: Webhook[] } }>("get_settings")).webhooks.endpoints;
+    deliveries = await invoke<DeliveryEntry[]>("get_webhook_deliveries", { limit: LOG_LIMIT });
+  } catch (e) {
+    console.error("Failed to load webhook settings:", e);
+    return;
+  }
+
+  container.innerHTML = `
+    <section style="margin-bottom: 24px;">
+      <h2 style="font-size: 14px; font-weight: 500; color: rgba(255,255,255,0.5);
+                  text-transform: uppercase; letter-spacing: 0.05em; margin-bottom: 12px;">
+        Webhooks
+      </h2>
+      <div style="background: #0f1629; border: 1px solid rgba(255,255,255,0.1); border-radius: 8px; padding: 14px;">
+        <div style="font-size: 12px; color: rgba(255,255,255,0.5); margin-bottom: 6px;">
+          Post snip events as JSON to Slack bots or internal tools. Nothing is sent in local-only or offline mode, or while history is paused.
+        </div>
+        <div id="webhook-list">${hooks.map(endpointRow).join("")}</div>
+        <div style="display: flex; gap: 6px; margin-top: 10px;">
+          <input id="webhook-name" placeholder="Name" style="${INPUT_STYLE} width: 120px;" />
+          <input id="webhook-url" placeholder="https://hooks.example.com/…" style="${INPUT_STYLE} flex: 1;" />
+          <input id="webhook-secret" type="password" placeholder="Secret (optional)" style="${INPUT_STYLE} width: 140px;" />
+          <button id="webhook-add" style="${INPUT_STYLE} cursor: pointer;">Add</button>
+        </div>
+        <div id="webhook-status" style="margin-top: 8px; font-size: 12px; color: #f87171;"></div>
+        <div style="font-size: 12px; color: rgba(255,255,255,0.5); margin: 12px 0 4px;">Recent deliveries</div>
+        <div id="webhook-log">${deliveries.slice().reverse().map(deliveryRow).join("") || `<div style="font-size: 11px; color: rgba(255,255,255,0.4);">None yet</div>`}</div>
+      </div>
+    </section>`;
+
+  const status = document.getElementById("webhook-status")!;
+  const rerender = () => renderWebhooksSection(container);
+  const save = async (endpoints: Webhook[]): Promise<boolean> => {
+    try {
+      await invoke("update_settings", { patch: { webhooks: { endpoints } } });
+      return true;
+    } catch (err) {
+      status.textContent = String(err);
+      return false;
+    }
+  };
+
+  container.querySelectorAll<HTMLElement>("[data-hook]").forEach((row) => {
+    const hook = hooks.find((h) => h.id === row.dataset.hook)!;
+    row.querySelectorAll<HTMLInputElement>("input[type=checkbox]").forEach((box) => {
+      box.addEventListener("change", async () => {
+        const events = [...row.querySelectorAll<HTMLInputElement>("[data-event]")].filter((b) => b.checked).map((b) => b.dataset.event!);
+        const enabled = (row.querySelector("[data-field=enabled]") as HTMLInputElement).checked;
+        const includeText = (row.querySelector("[data-field=includeText]") as HTMLInputElement).checked;
+        const next = hooks.map((h) => (h.id === hook.id ? { ...h, events, enabled, includeText } : h));
+        if (await save(next)) hooks = next;
+        else rerender();
+      });
+    });
+    row.querySelector("[data-action=remove]")!.addEventListener("click", async () => {
+      await invoke("set_webhook_secret", { id: hook.id, secret: null }).catch(() => {});
+      if (await save(hooks.filter((h) => h.id !== hook.id))) rerender();
+    });
+    row.querySelector("[data-action=test]")!.addEventListener("click", async () => {
+      status.style.color = "rgba(255,255,255,0.6)";
+      status.textContent = `Testing ${hook.name || hook.id}…`;
+      try {
+        const result = await invoke<DeliveryEntry>("test_webhook", { id: hook.id });
+        await rerender();
+        const after = document.getElementById("webhook-status")!;
+        after.style.color = OUTCOME_COLOR[result.outcome];
+        after.textContent = result.outcome === "delivered" ? `Delivered — HTTP ${result.status}` : `Failed: ${result.error}`;
+      } catch (err) {
+        status.style.color = "#f87171";
+        status.textContent = String(err);
+      }
+    });
+  });
+
+  document.getElementById("webhook-add")!.addEventListener("click", async () => {
+    const input = (id: string) => document.getElementById(id) as HTMLInputElement;
+    const name = input("webhook-name").value.trim();
+    const url = input("webhook-url").value.trim();
+    const secret = input("webhook-secret").value;
+    if (!url) {
+      status.textContent = "Enter the webhook URL";
+      return;
+    }
+    const id = slug(name, hooks.map((h) => h.id));
+    const hook: Webhook = { id, name, url, events: [...EVENTS], enabled: true, includeText: true };
+    if (!(await save([...hooks, hook]))) return;
+    try {
+      if (secret) await invoke("set_webhook_secret", { id, secret });
+      rerender();
+    } catch (err) {
+      status.textContent = String(err);
+    }
+  });
+}
@@ -9,14 +9,33 @@
  *   - file → save to Desktop
  *   - text → display inline
  *
- * Enter = submit, Escape = close.
- * Window auto-resizes to fit response content.
+ * Plugin slash commands (`/jira PROJ-123`) skip the LLM and go straight
+ * to the plugin; completions come from launcher-completions.ts.
+ * `find on screen: <text>` skips it too and lists where the text is on
+ * screen (launcher-find.ts). While
+ * typing, launcher-palette.ts lists matching actions, commands, and
+ * history snips; arrow keys pick one instead of asking the LLM.
+ *
+ * An `omniglass://launcher?q=...` deep link fills the input with its
+ * query (`take_launcher_query`, on load or on the `launcher-query`
+ * event). It never runs it: any web page can open a link, so the user
+ * reads the query and presses Enter.
+ *
+ * Enter = submit (or run the picked item), Escape = close,
+ * Tab = accept completion, ↑/↓ = pick a palette item.
+ * Window auto-resizes to fit response content. What the result area shows
+ * is drawn by launcher-result.ts.
  */
 
 import { invoke } from "@tauri-apps/api/core";
-import { open } from "@tauri-apps/plugin-shell";
+import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
+import { errorMessage } from "./errors";
 import { LogicalSize } from "@tauri-apps/api/dpi";
+import { attachCompletions } from "./launcher-completions";
+import { attachPalette } from "./launcher-palette";
+import { findQuery, runFind } from "./launcher-find";
+import { showCommandConfirmation, showStatus, showTextResult } from "./launcher-result";
 
 const appWindow = getCurrentWindow();
 const WIDTH = 600;
@@ -62,12 +81,17 @@ function renderInput(): void {
         outline: none;
         font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif;
       " autofocus />
+      <div id="palette-area"></div>
+      <div id="completion-area"></div>
       <div id="result-area"></div>
     </div>
   `;
 
   const input = document.getElementById("text-input") as HTMLInputElement;
+  // Before the submit handler, so picking an item can claim the Enter
+  attachPalette(input, document.getElementById("palette-area")!, resizeToContent);
   input.addEventListener("keydown", async (e) => {
+    if (e.defaultPrevented) return;
     if (e.key === "Enter" && input.value.trim()) {
       e.preventDefault();
       await submitCommand(input.value.trim());
@@ -76,28 +100,35 @@ function renderInput(): void {
       await closeLauncher();
     }
   });
+  void attachCompletions(input, document.getElementById("completion-area")!, resizeToContent);
 }
 
 // ── Submit ───────────────────────────────────────────────────────────
 
 async function submitCommand(text: string): Promise<void> {
+  const find = findQuery(text);
+  if (find !== null) {
+    await runFind(find, resultArea(), resizeToContent);
+    return;
+  }
+
   const input = document.getElementById("text-input") as HTMLInputElement;
   input.disabled = true;
   input.style.opacity = "0.5";
 
-  showStatus("Thinking...");
+  showStatus(resultArea(), "Thinking...");
   await resizeToContent();
 
   try {
     const result = await invoke<TextCommandResult>("execute_text_command", { text });
 
     if (result.status === "error") {
-      showTextResult(result.text || "Something went wrong.", true);
+      showText(result.text || "Something went wrong.", true);
     } else {
       await handleResult(result);
     }
   } catch (err) {
-    showTextResult(`Error: ${err}`, true);
+    showText(errorMessage(err), true);
   }
 
   await resizeToContent();
@@ -119,7 +150,9 @@ interface TextCommandResult {
 async function handleResult(result: TextCommandResult): Promise<void> {
   switch (result.resultType) {
     case "command":
-      showCommandConfirmation(result);
+      showCommandConfirmation(
+        resultArea(), result.command || "", result.text || "", resizeToContent, closeLauncher,
+      );
       break;
     case "clipboard":
       await handleClipboard(result);
@@ -129,104 +162,21 @@ async function handleResult(result: TextCommandResult): Promise<void> {
       break;
     default:
       // "text" or unknown — check for URLs in the response
-      showTextResult(result.text || "Done.", false);
+      showText(result.text || "Done.", false);
       break;
   }
 }
 
-// ── Command execution (with confirmation) ───────────────────────────
-
-function showCommandConfirmation(result: TextCommandResult): void {
-  const area = document.getElementById("result-area")!;
-  const cmd = result.command || "";
-  const explanation = result.text || "";
-
-  area.innerHTML = `
-    ${explanation ? `<div style="
-      padding: 10px 14px;
-      font-size: 13px;
-      color: rgba(255,255,255,0.7);
-      border-top: 1px solid rgba(255,255,255,0.08);
-      line-height: 1.4;
-    ">${renderLight(explanation)}</div>` : ""}
-    <div style="
-      padding: 8px 14px;
-      border-top: 1px solid rgba(255,255,255,0.08);
-    ">
-      <div style="
-        font-size: 11px;
-        color: rgba(255,255,255,0.4);
-        margin-bottom: 4px;
-      ">Command to run:</div>
-      <pre style="
-        background: rgba(0,0,0,0.4);
-        border: 1px solid rgba(255,255,255,0.1);
-        border-radius: 4px;
-        padding: 8px 10px;
-        font-family: 'SF Mono', Menlo, monospace;
-        font-size: 12px;
-        color: #e2e8f0;
-        white-space: pre-wrap;
-        word-break: break-all;
-        margin: 0;
-      ">${escapeHtml(cmd)}</pre>
-    </div>
-    <div style="
-      padding: 6px 14px 8px;
-      display: flex;
-      gap: 8px;
-      justify-content: flex-end;
-      border-top: 1px solid rgba(255,255,255,0.08);
-    ">
-      <button id="btn-cancel" style="
-        background: transparent;
-        border: 1px solid rgba(255,255,255,0.2);
-        color: rgba(255,255,255,0.7);
-        padding: 4px 12px;
-        border-radius: 4px;
-        cursor: pointer;
-        font-size: 12px;
-      ">Cancel</button>
-      <button id="btn-run" style="
-        background: #3b82f6;
-        border: 1px solid #3b82f6;
-        color: white;
-        padding: 4px 14px;
-        border-radius: 4px;
-        cursor: pointer;
-        font-size: 12px;
-        font-weight: 500;
-      ">Run</button>
-    </div>
-  `;
-
-  document.getElementById("btn-cancel")?.addEventListener("click", () => closeLauncher());
-  document.getElementById("btn-run")?.addEventListener("click", async () => {
-    const btn = document.getElementById("btn-run") as HTMLButtonElement;
-    btn.disabled = true;
-    btn.textContent = "Running...";
-    btn.style.opacity = "0.6";
-
-    try {
-      const output = await invoke<string>("run_confirmed_command", { command: cmd });
-      showTextResult(output || "Command completed successfully.", false);
-    } catch (err) {
-      showTextResult(`Command failed: ${err}`, true);
-    }
-    await resizeToContent();
-  });
-}
-
 // ── Clipboard auto-copy ─────────────────────────────────────────────
 
 async function handleClipboard(result: TextCommandResult): Promise<void> {
   const content = result.clipboardContent || result.text || "";
   try {
     await invoke("copy_to_clipboard", { text: content });
-    showTextResult("Copied to clipboard.", false);
+    showText("Copied to clipboard.", false);
     setTimeout(() => closeLauncher(), 1000);
   } catch (err) {
-    showTextResult(`Failed to copy: ${err}`, true);
+    showText(`Failed to copy: ${err}`, true);
   }
 }
 
@@ -237,132 +187,18 @@ async function handleFile(result: TextCommandResult): Promise<void> {
   const content = result.fileContent || result.text || "";
   try {
     const path = await invoke<string>("write_to_desktop", { filename, content });
-    showTextResult(`Saved to: ${path}`, false);
+    showText(`Saved to: ${path}`, false);
   } catch (err) {
-    showTextResult(`Failed to save file: ${err}`, true);
-  }
-}
-
-// ── Result display ───────────────────────────────────────────────────
-
-function showStatus(message: string): void {
-  const area = document.getElementById("result-area")!;
-  area.innerHTML = `
-    <div style="
-      padding: 10px 14px;
-      font-size: 13px;
-      color: rgba(255,255,255,0.5);
-      border-top: 1px solid rgba(255,255,255,0.08);
-    ">${escapeHtml(message)}</div>
-  `;
-}
-
-function showTextResult(text: string, isError: boolean): void {
-  const area = document.getElementById("result-area")!;
-  const color = isError ? "#fca5a5" : "rgba(255,255,255,0.85)";
-
-  // Extract URLs from the text for clickable links
-  const urls = extractUrls(text);
-
-  area.innerHTML = `
-    <div style="
-      padding: 12px 14px;
-      font-size: 13px;
-      color: ${color};
-      line-height: 1.5;
-      max-height: 340px;
-      overflow-y: auto;
-      border-top: 1px solid rgba(255,255,255,0.08);
-      word-wrap: break-word;
-    ">${renderLight(text)}</div>
-    <div style="
-      padding: 6px 14px 8px;
-      display: flex;
-      gap: 8px;
-      justify-content: flex-end;
-      border-top: 1px solid rgba(255,255,255,0.08);
-    ">
-      ${urls.length > 0 ? `<button id="btn-open-url" style="
-        background: #3b82f6;
-        border: 1px solid #3b82f6;
-        color: white;
-        padding: 4px 12px;
-        border-radius: 4px;
-        cursor: pointer;
-        font-size: 12px;
-      ">Open Link</button>` : ""}
-      <button id="btn-copy" style="
-        background: transparent;
-        border: 1px solid rgba(255,255,255,0.2);
-        color: rgba(255,255,255,0.7);
-        padding: 4px 12px;
-        border-radius: 4px;
-        cursor: pointer;
-        font-size: 12px;
-      ">Copy</button>
-      <button id="btn-close" style="
-        background: transparent;
-        border: 1px solid rgba(255,255,255,0.2);
-        color: rgba(255,255,255,0.7);
-        padding: 4px 12px;
-        border-radius: 4px;
-        cursor: pointer;
-        font-size: 12px;
-      ">Close</button>
-    </div>
-  `;
-
-  if (urls.length > 0) {
-    document.getElementById("btn-open-url")?.addEventListener("click", async () => {
-      try { await open(urls[0]); } catch { /* ignore */ }
-      setTimeout(() => closeLauncher(), 500);
-    });
+    showText(`Failed to save file: ${err}`, true);
   }
-
-  document.getElementById("btn-copy")?.addEventListener("click", async () => {
-    await invoke("copy_to_clipboard", { text });
-    const btn = document.getElementById("btn-copy")!;
-    btn.textContent = "Copied";
-    setTimeout(() => closeLauncher(), 500);
-  });
-
-  document.getElementById("btn-close")?.addEventListener("click", () => closeLauncher());
 }
 
 // ── Helpers ──────────────────────────────────────────────────────────
 
-function escapeHtml(text: string): string {
-  const div = document.createElement("div");
-  div.textContent = text;
-  return div.innerHTML;
-}
-
-function extractUrls(text: string): string[] {
-  const urlPattern = /https?:\/\/[^\s<>"')\]]+/g;
-  return text.match(urlPattern) || [];
-}
+const resultArea = () => document.getElementById("result-area")!;
 
-/** Minimal markdown: code blocks, inline code, bold, line breaks. */
-function renderLight(text: string): string {
-  let html = escapeHtml(text);
-  html = html.replace(
-    /```(\w*)\n([\s\S]*?)```/g,
-    (_m, _lang, code) => `<pre style="
-      background:rgba(0,0,0,0.4);border:1px solid rgba(255,255,255,0.1);
-      border-radius:4px;padding:8px 10px;margin:6px 0;
-      font-family:'SF Mono',Menlo,monospace;font-size:12px;
-      line-height:1.4;overflow-x:auto;white-space:pre;color:#e2e8f0;
-    ">${code.trim()}</pre>`
-  );
-  html = html.replace(/`([^`]+)`/g,
-    `<code style="background:rgba(0,0,0,0.3);padding:1px 4px;border-radius:3px;font-family:'SF Mono',Menlo,monospace;font-size:12px;">$1</code>`
-  );
-  html = html.replace(/\*\*([^*]+)\*\*/g, "<strong>$1</strong>");
-  html = html.replace(/\n/g, "<br>");
-  html = html.replace(/<pre([^>]*)>([\s\S]*?)<\/pre>/g, (_m, a, c) =>
-    `<pre${a}>${c.replace(/<br>/g, "\n")}</pre>`
-  );
-  return html;
+function showText(text: string, isError: boolean): void {
+  showTextResult(resultArea(), text, isError, closeLauncher);
 }
 
 async function closeLauncher(): Promise<void> {
@@ -376,4 +212,17 @@ document.addEventListener("keydown", async (e: KeyboardEvent) => {
   if (e.key === "Escape") await closeLauncher();
 });
 
+/** Fill the input with the query a deep link left, if any; Enter runs it. */
+async function fillLinkedQuery(): Promise<void> {
+  const query = await invoke<string | null>("take_launcher_query").catch(() => null);
+  if (!query) return;
+  renderInput();
+  const input = document.getElementById("text-input") as HTMLInputElement;
+  input.value = query;
+  input.dispatchEvent(new Event("input"));
+  input.focus();
+}
+
 renderInput();
+void listen("launcher-query", () => fillLinkedQuery());
+void fillLinkedQuery();
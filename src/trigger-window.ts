@@ -0,0 +1,94 @@
+/**
+ * Trigger window — the floating snip button on Linux desktops that
+ * show no tray icon (GNOME without the AppIndicator extension, bare
+ * window managers).
+ *
+ * One big Snip button and two small ones for Type Command and Settings.
+ * The strip at the top drags the window. Its tooltip says how to get a
+ * tray icon instead.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+interface DesktopSupport {
+  tray: boolean;
+  capture: "direct" | "portal" | "unavailable";
+  remedy: string | null;
+}
+
+// ── Render ───────────────────────────────────────────────────────────
+
+const container = document.getElementById("trigger")!;
+container.innerHTML = `
+  <div style="
+    display: flex;
+    flex-direction: column;
+    align-items: center;
+    gap: 6px;
+    padding: 4px;
+    user-select: none;
+  ">
+    <div data-tauri-drag-region style="
+      width: 32px;
+      height: 6px;
+      border-radius: 3px;
+      background: rgba(255,255,255,0.35);
+      cursor: grab;
+    "></div>
+    <button class="btn" id="snip" aria-label="Snip Screen" title="Snip Screen" style="
+      width: 52px;
+      height: 52px;
+      border-radius: 50%;
+      font-size: 22px;
+    ">📷</button>
+    <div style="display: flex; gap: 6px;">
+      <button class="btn" id="type-command" aria-label="Type Command" title="Type Command">⌨️</button>
+      <button class="btn" id="settings" aria-label="Settings" title="Settings">⚙️</button>
+    </div>
+  </div>
+`;
+
+const style = document.createElement("style");
+style.textContent = `
+  .btn {
+    width: 24px;
+    height: 24px;
+    padding: 0;
+    border: 1px solid rgba(255,255,255,0.15);
+    border-radius: 6px;
+    background: #1a1a2e;
+    color: #e2e8f0;
+    font-size: 12px;
+    cursor: pointer;
+    box-shadow: 0 4px 16px rgba(0,0,0,0.4);
+  }
+  .btn:hover { background: #2a2a44; }
+  .btn:disabled { opacity: 0.5; cursor: not-allowed; }
+`;
+document.head.appendChild(style);
+
+// ── Actions ──────────────────────────────────────────────────────────
+
+const snip = document.getElementById("snip") as HTMLButtonElement;
+
+snip.addEventListener("click", () => {
+  invoke("start_snip").catch((e) => console.error("[TRIGGER] Snip failed:", e));
+});
+
+document.getElementById("type-command")?.addEventListener("click", () => {
+  invoke("open_text_launcher").catch((e) => console.error("[TRIGGER]", e));
+});
+
+document.getElementById("settings")?.addEventListener("click", () => {
+  invoke("open_settings").catch((e) => console.error("[TRIGGER]", e));
+});
+
+// ── What this desktop is missing ─────────────────────────────────────
+
+invoke<DesktopSupport>("get_desktop_support")
+  .then((support) => {
+    if (support.remedy) snip.title = `Snip Screen\n\n${support.remedy}`;
+    // No portal on Wayland: a snip can only fail, so say why up front
+    if (support.capture === "unavailable") snip.disabled = true;
+  })
+  .catch((e) => console.error("[TRIGGER] Desktop support unavailable:", e));
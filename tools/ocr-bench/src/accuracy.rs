@@ -0,0 +1,103 @@
+//! Accuracy scoring against ground truth: character and word error rates.
+//!
+//! CER = character edit distance / reference characters
+//! WER = word edit distance / reference words
+//!
+//! Both texts are normalized first: each line trimmed, runs of whitespace
+//! collapsed to one space, blank lines dropped. Vision's line breaks and
+//! column spacing vary with layout, and that isn't what a backend or
+//! preprocessing change is judged on. Case and punctuation count —
+//! a dropped bracket breaks a code fix.
+
+/// Edit counts for one image, or summed over a corpus.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Score {
+    pub char_edits: usize,
+    pub ref_chars: usize,
+    pub word_edits: usize,
+    pub ref_words: usize,
+}
+
+impl Score {
+    /// Character error rate. Can exceed 1.0 when the output is mostly noise.
+    pub fn cer(&self) -> f64 {
+        self.char_edits as f64 / self.ref_chars.max(1) as f64
+    }
+
+    /// Word error rate.
+    pub fn wer(&self) -> f64 {
+        self.word_edits as f64 / self.ref_words.max(1) as f64
+    }
+
+    /// Add `other`'s counts; the sum's rates weight each image by its length.
+    pub fn add(&mut self, other: &Score) {
+        self.char_edits += other.char_edits;
+        self.ref_chars += other.ref_chars;
+        self.word_edits += other.word_edits;
+        self.ref_words += other.ref_words;
+    }
+}
+
+pub fn normalize(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Levenshtein distance: insertions, deletions, and substitutions.
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(x != y);
+            current[j + 1] = substitute.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Score OCR output `hypothesis` against `reference`.
+pub fn score(hypothesis: &str, reference: &str) -> Score {
+    let (hypothesis, reference) = (normalize(hypothesis), normalize(reference));
+    let hyp_chars: Vec<char> = hypothesis.chars().collect();
+    let ref_chars: Vec<char> = reference.chars().collect();
+    let hyp_words: Vec<&str> = hypothesis.split_whitespace().collect();
+    let ref_words: Vec<&str> = reference.split_whitespace().collect();
+    Score {
+        char_edits: edit_distance(&hyp_chars, &ref_chars),
+        ref_chars: ref_chars.len(),
+        word_edits: edit_distance(&hyp_words, &ref_words),
+        ref_words: ref_words.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_edits_after_normalizing_whitespace() {
+        assert_eq!(edit_distance(&['k', 'i', 't', 't', 'e', 'n'], &['s', 'i', 't', 't', 'i', 'n', 'g']), 3);
+        assert_eq!(score("  fn main()  {\n\n}\n", "fn main() {\n}"), Score { ref_chars: 13, ref_words: 4, ..Score::default() });
+
+        let s = score("let x = 1O;", "let x = 10;");
+        assert_eq!((s.char_edits, s.ref_chars, s.word_edits, s.ref_words), (1, 11, 1, 4));
+        assert!((s.cer() - 1.0 / 11.0).abs() < 1e-9);
+        assert_eq!(s.wer(), 0.25);
+    }
+
+    #[test]
+    fn corpus_rates_weight_images_by_length() {
+        let mut total = Score::default();
+        total.add(&score("abcd", "abcd"));
+        total.add(&score("", "xy"));
+        assert_eq!((total.char_edits, total.ref_chars), (2, 6));
+        assert!((total.cer() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(score("noise", "").cer(), 5.0);
+    }
+}
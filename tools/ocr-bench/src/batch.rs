@@ -0,0 +1,180 @@
+//! Batch mode: every image in a directory → CSV (or JSON Lines) on
+//! stdout, summary on stderr.
+//!
+//! With `--ground-truth <dir>`, each image is paired with `<dir>/<stem>.txt`
+//! and scored (`accuracy.rs`). The CSV gains CER/WER columns per image,
+//! left empty for images without a reference, and the summary adds the
+//! corpus CER/WER of each mode. With `--format json`, the same data is
+//! written as `report.rs` records.
+
+use crate::accuracy::Score;
+use crate::report::{ImageRecord, Record, Summary, Tally};
+use crate::{ocr_ffi, RecognitionLevel};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The ground-truth text for `image`, if there is one.
+fn reference_for(ground_truth: Option<&Path>, image: &Path) -> Option<String> {
+    let stem = image.file_stem()?.to_string_lossy();
+    std::fs::read_to_string(ground_truth?.join(format!("{}.txt", stem))).ok()
+}
+
+/// The `,cer,wer` CSV columns of an image (empty without a reference).
+fn rates(score: Option<Score>) -> String {
+    score.map_or(",,".to_string(), |s| format!(",{:.4},{:.4}", s.cer(), s.wer()))
+}
+
+/// Returns the summary of each recognition level run, for `--baseline`.
+pub fn run_batch(dir_path: &str, use_fast: bool, compare: bool, ground_truth: Option<&Path>, json: bool) -> Vec<Summary> {
+    let dir = Path::new(dir_path);
+    if !dir.is_dir() {
+        eprintln!("Not a directory: {}", dir_path);
+        std::process::exit(1);
+    }
+    if let Some(truth) = ground_truth.filter(|d| !d.is_dir()) {
+        eprintln!("Not a directory: {}", truth.display());
+        std::process::exit(1);
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .expect("Failed to read directory")
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .map(|ext| ext == "png" || ext == "jpg" || ext == "jpeg")
+                .unwrap_or(false)
+        })
+        .collect();
+    entries.sort();
+
+    if entries.is_empty() {
+        eprintln!("No image files found in {}", dir_path);
+        std::process::exit(1);
+    }
+
+    let scoring = ground_truth.is_some();
+    let levels = match (compare, use_fast) {
+        (true, _) => vec![RecognitionLevel::Accurate, RecognitionLevel::Fast],
+        (false, true) => vec![RecognitionLevel::Fast],
+        (false, false) => vec![RecognitionLevel::Accurate],
+    };
+    // JSON records describe themselves; only CSV has a header
+    if !json && compare {
+        // CSV header for comparison mode
+        println!(
+            "filename,chars_accurate,vision_ms_accurate,wall_ms_accurate,conf_accurate,\
+             chars_fast,vision_ms_fast,wall_ms_fast,conf_fast,speedup{}",
+            if scoring { ",cer_accurate,wer_accurate,cer_fast,wer_fast" } else { "" }
+        );
+    } else if !json {
+        println!(
+            "filename,char_count,vision_ms,wall_ms,confidence,recognition_level{}",
+            if scoring { ",cer,wer" } else { "" }
+        );
+    }
+
+    let mut tallies: Vec<Tally> = levels.iter().map(|_| Tally::default()).collect();
+
+    for image_path in &entries {
+        let filename = image_path.file_name().unwrap().to_string_lossy().to_string();
+        let abs_str = image_path.to_str().unwrap();
+        let reference = reference_for(ground_truth, image_path);
+        if scoring && reference.is_none() {
+            eprintln!("[GROUND-TRUTH] No reference for {}; not scored", filename);
+        }
+
+        let runs: Vec<_> = levels
+            .iter()
+            .zip(&mut tallies)
+            .map(|(level, tally)| {
+                let (result, wall_us) = ocr_ffi(abs_str, *level);
+                let score = tally.add(result.latency_ms, &result.text, reference.as_deref());
+                (result, wall_us as f64 / 1000.0, score)
+            })
+            .collect();
+
+        if json {
+            for ((result, wall_ms, score), level) in runs.iter().zip(&levels) {
+                let record = Record::Image(ImageRecord {
+                    file: filename.clone(),
+                    level: level.name().to_string(),
+                    char_count: result.char_count,
+                    vision_ms: result.latency_ms,
+                    wall_ms: *wall_ms,
+                    confidence: result.confidence,
+                    cer: score.map(|s| s.cer()),
+                    wer: score.map(|s| s.wer()),
+                });
+                println!("{}", record.to_json());
+            }
+        } else if let [(accurate, accurate_wall_ms, accurate_score), (fast, fast_wall_ms, fast_score)] = &runs[..] {
+            let speedup = accurate.latency_ms / fast.latency_ms.max(0.1);
+            println!(
+                "{},{},{:.1},{:.2},{:.3},{},{:.1},{:.2},{:.3},{:.1}x{}",
+                filename,
+                accurate.char_count,
+                accurate.latency_ms,
+                accurate_wall_ms,
+                accurate.confidence,
+                fast.char_count,
+                fast.latency_ms,
+                fast_wall_ms,
+                fast.confidence,
+                speedup,
+                if scoring { format!("{}{}", rates(*accurate_score), rates(*fast_score)) } else { String::new() }
+            );
+        } else {
+            let (result, wall_ms, score) = &runs[0];
+            println!(
+                "{},{},{:.1},{:.2},{:.3},{}{}",
+                filename,
+                result.char_count,
+                result.latency_ms,
+                wall_ms,
+                result.confidence,
+                result.recognition_level,
+                if scoring { rates(*score) } else { String::new() }
+            );
+        }
+
+        std::io::stdout().flush().ok();
+    }
+
+    // Print summary
+    eprintln!("\n--- Benchmark Summary ---");
+    eprintln!("  Images processed: {}", entries.len());
+
+    levels
+        .iter()
+        .zip(&tallies)
+        .map(|(level, tally)| {
+            let summary = tally.summary(level.name());
+            print_summary(&summary, tally, level.target_ms());
+            if json {
+                println!("{}", Record::Summary(summary.clone()).to_json());
+            }
+            summary
+        })
+        .collect()
+}
+
+fn print_summary(summary: &Summary, tally: &Tally, target_ms: f64) {
+    let mut label = summary.level.clone();
+    label[..1].make_ascii_uppercase();
+    eprintln!("  [{}]", label);
+    eprintln!("    Median: {:.1}ms", summary.median_ms);
+    eprintln!("    Average: {:.1}ms", summary.average_ms);
+    eprintln!("    P99: {:.1}ms", summary.p99_ms);
+    eprintln!(
+        "    Target (< {:.0}ms): {}",
+        target_ms,
+        if summary.median_ms < target_ms { "PASS" } else { "FAIL" }
+    );
+    if tally.scored > 0 {
+        let score = &tally.score;
+        eprintln!("    CER: {:.2}% ({} edits / {} chars)", score.cer() * 100.0, score.char_edits, score.ref_chars);
+        eprintln!("    WER: {:.2}% ({} edits / {} words)", score.wer() * 100.0, score.word_edits, score.ref_words);
+        eprintln!("    Scored: {} of {} images", tally.scored, summary.images);
+    }
+}
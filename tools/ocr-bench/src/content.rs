@@ -0,0 +1,190 @@
+//! What synthetic scenes say: word lists, code and shell templates, and
+//! syntax highlighting (pure, no I/O). Names and numbers are picked at
+//! random so OCR can't lean on a language model's expectations; the
+//! shapes — prompts, tracebacks, `fn` signatures — are the ones real
+//! screenshots are full of.
+
+use crate::synth::{Rgb, Rng, Span};
+
+/// Editor color theme: background, plain text, keyword, string, comment, number.
+pub struct Theme(pub [Rgb; 6]);
+
+pub const THEMES: [Theme; 4] = [
+    Theme([[0xff, 0xff, 0xff], [0x24, 0x29, 0x2e], [0xd7, 0x3a, 0x49], [0x03, 0x2f, 0x62], [0x6a, 0x73, 0x7d], [0x00, 0x5c, 0xc5]]),
+    Theme([[0x28, 0x2c, 0x34], [0xab, 0xb2, 0xbf], [0xc6, 0x78, 0xdd], [0x98, 0xc3, 0x79], [0x5c, 0x63, 0x70], [0xd1, 0x9a, 0x66]]),
+    Theme([[0xfd, 0xf6, 0xe3], [0x65, 0x7b, 0x83], [0x85, 0x99, 0x00], [0x2a, 0xa1, 0x98], [0x93, 0xa1, 0xa1], [0xd3, 0x36, 0x82]]),
+    Theme([[0x27, 0x28, 0x22], [0xf8, 0xf8, 0xf2], [0xf9, 0x26, 0x72], [0xe6, 0xdb, 0x74], [0x75, 0x71, 0x5e], [0xae, 0x81, 0xff]]),
+];
+
+const KEYWORDS: &[&str] = &[
+    "fn", "let", "mut", "if", "else", "return", "match", "impl", "pub", "struct", "for", "in", "def", "import",
+    "from", "class", "None", "self", "while", "not", "and", "Ok", "Err", "Some",
+];
+const NOUNS: &[&str] = &["user", "config", "session", "token", "buffer", "record", "invoice", "cache", "request", "window", "order", "report"];
+const VERBS: &[&str] = &["load", "parse", "build", "fetch", "render", "update", "validate", "export", "merge", "resolve"];
+const TYPES: &[&str] = &["Config", "Session", "Record", "Invoice", "Report", "Snapshot"];
+const MODULES: &[&str] = &["requests", "numpy", "pandas", "yaml", "boto3", "dateutil"];
+const NAMES: &[&str] = &["Alice Moreau", "Bilal Khan", "Chen Wei", "Dana Kowalski", "Erik Lund", "Fatima Diallo", "Jonas Weber", "Maria Silva"];
+const STATUSES: &[&str] = &["Paid", "Pending", "Overdue", "Refunded", "Draft"];
+const MONTHS: &[&str] = &["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+const COMMITS: &[&str] = &["Fix race in cache refresh", "Add retry to upload client", "Bump serde to 1.0.200", "Remove unused imports", "Handle empty config file", "Speed up table parser"];
+pub const SETTINGS: &[(&str, &[&str])] = &[
+    ("Launch at login", &["On", "Off"]),
+    ("Check for updates", &["Daily", "Weekly", "Never"]),
+    ("Default language", &["English", "Deutsch", "Español"]),
+    ("Auto-save interval", &["30 seconds", "5 minutes", "Off"]),
+    ("Notification sound", &["Glass", "Ping", "None"]),
+    ("Appearance", &["Light", "Dark", "System"]),
+    ("Sync over cellular", &["Allowed", "Blocked"]),
+    ("Storage used", &["1.2 GB of 5 GB", "340 MB of 5 GB"]),
+];
+
+fn ident(rng: &mut Rng) -> String {
+    format!("{}_{}", rng.pick(VERBS), rng.pick(NOUNS))
+}
+
+/// Where a `//` or `#` comment starts, outside string literals.
+fn comment_start(line: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return Some(i),
+            '/' if !quoted && line[i..].starts_with("//") => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split `line` into spans colored by a theme: keywords, string
+/// literals, numbers, and a trailing comment.
+pub fn highlight(line: &str, theme: &Theme) -> Vec<Span> {
+    let [_, text, keyword, string, comment, number] = theme.0;
+    let (code, note) = line.split_at(comment_start(line).unwrap_or(line.len()));
+    let mut spans: Vec<Span> = Vec::new();
+    let mut push = |token: &str, color: Rgb| match spans.last_mut() {
+        Some(last) if last.color == color => last.text.push_str(token),
+        _ => spans.push(Span { text: token.to_string(), color }),
+    };
+    for (i, part) in code.split('"').enumerate() {
+        if i % 2 == 1 {
+            push(&format!("\"{}\"", part), string);
+            continue;
+        }
+        for token in part.split_inclusive(|c: char| !c.is_alphanumeric() && c != '_') {
+            let (word, rest) = token.split_at(token.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_').len());
+            let color = if KEYWORDS.contains(&word) {
+                keyword
+            } else if word.starts_with(|c: char| c.is_ascii_digit()) {
+                number
+            } else {
+                text
+            };
+            push(word, color);
+            push(rest, text);
+        }
+    }
+    if !note.is_empty() {
+        push(note, comment);
+    }
+    spans.retain(|s| !s.text.is_empty());
+    spans
+}
+
+pub fn code_lines(rng: &mut Rng) -> Vec<String> {
+    let (name, noun, ty, limit) = (ident(rng), *rng.pick(NOUNS), *rng.pick(TYPES), rng.range(8, 512));
+    if rng.range(0, 1) == 0 {
+        vec![
+            format!("// {} the {} and check its size", rng.pick(VERBS), noun),
+            format!("pub fn {}(input: &str, retries: usize) -> Result<{}, String> {{", name, ty),
+            format!("    let {} = input.trim().to_string();", noun),
+            format!("    if {}.len() > {} {{", noun, limit),
+            format!("        return Err(format!(\"{} too long: {{}}\", {}.len()));", noun, noun),
+            "    }".to_string(),
+            format!("    Ok({}::new({}, retries))", ty, noun),
+            "}".to_string(),
+        ]
+    } else {
+        vec![
+            format!("import {}", rng.pick(MODULES)),
+            String::new(),
+            format!("def {}(path, limit={}):", name, limit),
+            format!("    # {} every {} under path", rng.pick(VERBS), noun),
+            "    results = []".to_string(),
+            format!("    for {} in open(path):", noun),
+            format!("        if not {}.strip():", noun),
+            "            continue".to_string(),
+            format!("        results.append({}.upper())", noun),
+            "    return results[:limit]".to_string(),
+        ]
+    }
+}
+
+pub fn terminal_lines(rng: &mut Rng) -> Vec<String> {
+    let mut lines = Vec::new();
+    let count = rng.range(2, 3);
+    for command in rng.sample(&[0, 1, 2, 3], count) {
+        let prompt = format!("dev@studio ~/{} $ ", rng.pick(NOUNS));
+        match command {
+            0 => {
+                lines.push(format!("{}ls -la", prompt));
+                for _ in 0..rng.range(3, 5) {
+                    let (size, day, month) = (rng.range(96, 48_000), rng.range(1, 28), rng.pick(MONTHS));
+                    lines.push(format!("-rw-r--r--  1 dev  staff  {:>6} {} {:>2} 09:{:02} {}.rs", size, month, day, rng.range(0, 59), rng.pick(NOUNS)));
+                }
+            }
+            1 => {
+                lines.push(format!("{}git log --oneline -3", prompt));
+                for message in rng.sample(COMMITS, 3) {
+                    lines.push(format!("{:07x} {}", rng.next() & 0xfff_ffff, message));
+                }
+            }
+            2 => {
+                lines.push(format!("{}cargo build --release", prompt));
+                lines.push(format!("   Compiling {}-{} v0.{}.{}", rng.pick(NOUNS), rng.pick(NOUNS), rng.range(1, 9), rng.range(0, 20)));
+                lines.push(format!("    Finished release target(s) in {}.{:02}s", rng.range(2, 90), rng.range(0, 99)));
+            }
+            _ => {
+                let (module, file) = (rng.pick(MODULES), rng.pick(NOUNS));
+                lines.push(format!("{}python3 {}.py", prompt, file));
+                lines.push("Traceback (most recent call last):".to_string());
+                lines.push(format!("  File \"{}.py\", line {}, in <module>", file, rng.range(1, 40)));
+                lines.push(format!("ModuleNotFoundError: No module named '{}'", module));
+            }
+        }
+    }
+    lines
+}
+
+pub fn table_lines(rng: &mut Rng) -> Vec<Vec<String>> {
+    let mut rows = vec![vec!["Invoice", "Customer", "Date", "Amount", "Status"].into_iter().map(String::from).collect()];
+    for _ in 0..rng.range(5, 9) {
+        rows.push(vec![
+            format!("INV-{:04}", rng.range(1, 9999)),
+            rng.pick(NAMES).to_string(),
+            format!("{} {}, 2026", rng.pick(MONTHS), rng.range(1, 28)),
+            format!("${}.{:02}", rng.range(12, 9800), rng.range(0, 99)),
+            rng.pick(STATUSES).to_string(),
+        ]);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlighting_keeps_the_text() {
+        let theme = &THEMES[0];
+        let line = "    let n = \"a # b\".len() + 42; // count";
+        let spans = highlight(line, theme);
+        assert_eq!(spans.iter().map(|s| s.text.as_str()).collect::<String>(), line);
+        let color_of = |text: &str| spans.iter().find(|s| s.text.contains(text)).map(|s| s.color);
+        assert_eq!(color_of("let"), Some(theme.0[2]));
+        assert_eq!(color_of("\"a # b\""), Some(theme.0[3]));
+        assert_eq!(color_of("// count"), Some(theme.0[4]));
+        assert_eq!(color_of("42"), Some(theme.0[5]));
+    }
+}
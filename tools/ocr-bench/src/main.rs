@@ -10,9 +10,28 @@
 //!   cargo run -- --batch <directory>            All PNGs in directory → CSV
 //!   cargo run -- --batch <directory> --fast     Batch with fast mode
 //!   cargo run -- --batch <directory> --compare  Batch with both modes
-
-use std::io::Write;
-use std::path::{Path, PathBuf};
+//!   cargo run -- --batch <directory> --ground-truth <dir>
+//!                                               Batch scored against <dir>/<stem>.txt (CER/WER)
+//!   cargo run -- --ground-truth <directory>     Same, images and references in one directory
+//!   cargo run -- --batch <directory> --format json
+//!                                               JSON Lines: image records, then summary records
+//!   cargo run -- --batch <directory> --format json --baseline <file.json>
+//!                                               Exit 2 if median latency or CER regressed
+//!                [--max-latency-regression <percent, 20>] [--max-cer-regression <points, 1>]
+//!   cargo run -- generate <directory>           Synthetic images + ground truth (no OCR)
+//!                [--count N] [--seed N] [--kinds terminal,code,table,low-contrast,rotated]
+
+mod accuracy;
+mod batch;
+mod content;
+mod generate;
+mod render;
+mod report;
+mod synth;
+
+use report::Thresholds;
+use std::path::Path;
+use std::str::FromStr;
 use std::time::Instant;
 
 #[swift_bridge::bridge]
@@ -49,6 +68,21 @@ impl RecognitionLevel {
     fn as_i32(self) -> i32 {
         self as i32
     }
+
+    fn name(self) -> &'static str {
+        match self {
+            RecognitionLevel::Accurate => "accurate",
+            RecognitionLevel::Fast => "fast",
+        }
+    }
+
+    /// Median latency the level must stay under (Performance Targets).
+    fn target_ms(self) -> f64 {
+        match self {
+            RecognitionLevel::Accurate => 300.0,
+            RecognitionLevel::Fast => 100.0,
+        }
+    }
 }
 
 impl Default for RecognitionLevel {
@@ -76,13 +110,30 @@ fn main() {
     if args.len() < 2 {
         eprintln!("Usage:");
         eprintln!("  ocr-bench <image.png> [--fast] [--compare]");
-        eprintln!("  ocr-bench --batch <directory> [--fast] [--compare]");
+        eprintln!("  ocr-bench --batch <directory> [--fast] [--compare] [--ground-truth <dir>]");
+        eprintln!("  ocr-bench --ground-truth <directory> [--fast] [--compare]");
+        eprintln!("      [--format csv|json] [--baseline <file.json>] [--max-latency-regression <pct>] [--max-cer-regression <pts>]");
+        eprintln!("  ocr-bench generate <directory> [--count N] [--seed N] [--kinds <list>] [--mono-font <path>] [--ui-font <path>]");
         std::process::exit(1);
     }
 
+    if args[1] == "generate" {
+        generate::run(&args[2..]);
+        return;
+    }
+
     let use_fast = args.contains(&"--fast".to_string());
     let compare = args.contains(&"--compare".to_string());
     let warm = args.contains(&"--warm".to_string());
+    let ground_truth = flag_value(&args, "--ground-truth");
+    let json = match flag_value(&args, "--format").unwrap_or("csv") {
+        "csv" => false,
+        "json" => true,
+        other => {
+            eprintln!("Unknown format {:?} (expected csv or json)", other);
+            std::process::exit(1);
+        }
+    };
 
     // Simulate app startup warm-up: fire a throwaway recognition request
     // so Vision Framework loads its ML model before the real benchmark.
@@ -93,14 +144,63 @@ fn main() {
         eprintln!("[WARM-UP] Vision Framework initialized in {:.1}ms", warm_ms);
     }
 
-    if args[1] == "--batch" {
-        let dir = args.get(2).expect("--batch requires a directory path");
-        run_batch(dir, use_fast, compare);
+    if args[1] == "--batch" || args[1] == "--ground-truth" {
+        let dir = flag_value(&args, "--batch").or(ground_truth).unwrap_or_else(|| {
+            eprintln!("{} requires a directory path", args[1]);
+            std::process::exit(1);
+        });
+        // Bad thresholds fail before the run, not after it
+        let thresholds = Thresholds {
+            latency_pct: flag_number(&args, "--max-latency-regression", 20.0),
+            cer_points: flag_number(&args, "--max-cer-regression", 1.0),
+        };
+        let summaries = batch::run_batch(dir, use_fast, compare, ground_truth.map(Path::new), json);
+        if let Some(baseline) = flag_value(&args, "--baseline") {
+            gate(&summaries, baseline, &thresholds);
+        }
     } else {
         run_single(&args[1], use_fast, compare);
     }
 }
 
+/// The value after `flag`, e.g. the directory in `--ground-truth <dir>`.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let at = args.iter().position(|a| a == flag)?;
+    args.get(at + 1).map(String::as_str).filter(|v| !v.starts_with("--"))
+}
+
+/// Parse the number after `flag`, or fall back to `default`.
+fn flag_number<T: FromStr>(args: &[String], flag: &str, default: T) -> T {
+    flag_value(args, flag).map_or(default, |v| {
+        v.parse().unwrap_or_else(|_| {
+            eprintln!("{} expects a number, got {:?}", flag, v);
+            std::process::exit(1);
+        })
+    })
+}
+
+/// Compare this run with the summary records in `baseline`; exit with
+/// status 2 if it regressed past `thresholds`.
+fn gate(summaries: &[report::Summary], baseline: &str, thresholds: &Thresholds) {
+    let found = std::fs::read_to_string(baseline)
+        .map_err(|e| e.to_string())
+        .and_then(|text| report::parse_summaries(&text))
+        .and_then(|before| report::regressions(summaries, &before, thresholds));
+    match found {
+        Err(e) => {
+            eprintln!("[BASELINE] Cannot compare with {}: {}", baseline, e);
+            std::process::exit(1);
+        }
+        Ok(found) if found.is_empty() => eprintln!("[BASELINE] No regression against {}", baseline),
+        Ok(found) => {
+            for regression in &found {
+                eprintln!("[BASELINE] REGRESSION {}", regression);
+            }
+            std::process::exit(2);
+        }
+    }
+}
+
 /// Run OCR via FFI and measure wall-clock time from Rust side.
 fn ocr_ffi(abs_path: &str, level: RecognitionLevel) -> (ffi::OcrResult, u128) {
     let start = Instant::now();
@@ -166,119 +266,3 @@ fn run_single(image_path: &str, use_fast: bool, compare: bool) {
         println!("}}");
     }
 }
-
-fn run_batch(dir_path: &str, use_fast: bool, compare: bool) {
-    let dir = Path::new(dir_path);
-    if !dir.is_dir() {
-        eprintln!("Not a directory: {}", dir_path);
-        std::process::exit(1);
-    }
-
-    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
-        .expect("Failed to read directory")
-        .filter_map(|e| e.ok())
-        .map(|e| e.path())
-        .filter(|p| {
-            p.extension()
-                .map(|ext| ext == "png" || ext == "jpg" || ext == "jpeg")
-                .unwrap_or(false)
-        })
-        .collect();
-    entries.sort();
-
-    if entries.is_empty() {
-        eprintln!("No image files found in {}", dir_path);
-        std::process::exit(1);
-    }
-
-    if compare {
-        // CSV header for comparison mode
-        println!(
-            "filename,chars_accurate,vision_ms_accurate,wall_ms_accurate,conf_accurate,\
-             chars_fast,vision_ms_fast,wall_ms_fast,conf_fast,speedup"
-        );
-    } else {
-        println!("filename,char_count,vision_ms,wall_ms,confidence,recognition_level");
-    }
-
-    let mut latencies_accurate: Vec<f64> = Vec::new();
-    let mut latencies_fast: Vec<f64> = Vec::new();
-
-    for image_path in &entries {
-        let filename = image_path.file_name().unwrap().to_string_lossy().to_string();
-        let abs_str = image_path.to_str().unwrap();
-
-        if compare {
-            let (accurate, accurate_wall_us) = ocr_ffi(abs_str, RecognitionLevel::Accurate);
-            let (fast, fast_wall_us) = ocr_ffi(abs_str, RecognitionLevel::Fast);
-            let speedup = accurate.latency_ms / fast.latency_ms.max(0.1);
-
-            println!(
-                "{},{},{:.1},{:.2},{:.3},{},{:.1},{:.2},{:.3},{:.1}x",
-                filename,
-                accurate.char_count,
-                accurate.latency_ms,
-                accurate_wall_us as f64 / 1000.0,
-                accurate.confidence,
-                fast.char_count,
-                fast.latency_ms,
-                fast_wall_us as f64 / 1000.0,
-                fast.confidence,
-                speedup
-            );
-
-            latencies_accurate.push(accurate.latency_ms);
-            latencies_fast.push(fast.latency_ms);
-        } else {
-            let level = if use_fast { RecognitionLevel::Fast } else { RecognitionLevel::Accurate };
-            let (result, wall_us) = ocr_ffi(abs_str, level);
-
-            println!(
-                "{},{},{:.1},{:.2},{:.3},{}",
-                filename,
-                result.char_count,
-                result.latency_ms,
-                wall_us as f64 / 1000.0,
-                result.confidence,
-                result.recognition_level
-            );
-
-            if use_fast {
-                latencies_fast.push(result.latency_ms);
-            } else {
-                latencies_accurate.push(result.latency_ms);
-            }
-        }
-
-        std::io::stdout().flush().ok();
-    }
-
-    // Print summary
-    eprintln!("\n--- Benchmark Summary ---");
-    eprintln!("  Images processed: {}", entries.len());
-
-    if !latencies_accurate.is_empty() {
-        print_latency_summary("Accurate", &mut latencies_accurate, 300.0);
-    }
-    if !latencies_fast.is_empty() {
-        print_latency_summary("Fast", &mut latencies_fast, 100.0);
-    }
-}
-
-fn print_latency_summary(label: &str, latencies: &mut [f64], target_ms: f64) {
-    latencies.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let median = latencies[latencies.len() / 2];
-    let p99_idx = ((latencies.len() as f64 * 0.99).ceil() as usize).min(latencies.len() - 1);
-    let p99 = latencies[p99_idx];
-    let avg: f64 = latencies.iter().sum::<f64>() / latencies.len() as f64;
-
-    eprintln!("  [{}]", label);
-    eprintln!("    Median: {:.1}ms", median);
-    eprintln!("    Average: {:.1}ms", avg);
-    eprintln!("    P99: {:.1}ms", p99);
-    eprintln!(
-        "    Target (< {:.0}ms): {}",
-        target_ms,
-        if median < target_ms { "PASS" } else { "FAIL" }
-    );
-}
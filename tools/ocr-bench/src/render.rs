@@ -0,0 +1,144 @@
+//! Draws a `Scene` into an RGB image with TrueType fonts (ab_glyph).
+//!
+//! Fonts come from the system by default — Menlo and SF on macOS, DejaVu
+//! elsewhere — so the repo ships no font files. Text is drawn with
+//! anti-aliasing at Retina-like sizes (26-32px), then the whole image is
+//! rotated if the scene asks for it.
+
+use crate::synth::{Face, Rgb, Scene};
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use image::RgbImage;
+
+const MONO_FONTS: &[&str] = &[
+    "/System/Library/Fonts/Menlo.ttc",
+    "/System/Library/Fonts/SFNSMono.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf",
+];
+const UI_FONTS: &[&str] = &[
+    "/System/Library/Fonts/SFNS.ttf",
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+/// Margin around the text, in font sizes.
+const PADDING: f32 = 1.0;
+/// Line height, in font heights.
+const LINE_SPACING: f32 = 1.3;
+/// Distance between tab stops (the value column of a settings row), in
+/// font sizes.
+const TAB_STOP: f32 = 12.0;
+
+pub struct Fonts {
+    mono: FontVec,
+    ui: FontVec,
+}
+
+impl Fonts {
+    /// Load the given font files, or the first system font found.
+    pub fn load(mono: Option<&str>, ui: Option<&str>) -> Result<Self, String> {
+        Ok(Fonts { mono: load_font(mono, MONO_FONTS)?, ui: load_font(ui, UI_FONTS)? })
+    }
+}
+
+fn load_font(path: Option<&str>, fallbacks: &[&str]) -> Result<FontVec, String> {
+    let candidates = path.map_or_else(|| fallbacks.to_vec(), |p| vec![p]);
+    for candidate in &candidates {
+        if let Ok(bytes) = std::fs::read(candidate) {
+            // Index 0 of a collection (.ttc) is the regular weight
+            return FontVec::try_from_vec_and_index(bytes, 0).map_err(|e| format!("{}: {}", candidate, e));
+        }
+    }
+    Err(format!("No font found (tried {})", candidates.join(", ")))
+}
+
+/// `color` laid over `pixel` at `coverage` (0-1).
+fn blend(pixel: &mut image::Rgb<u8>, color: Rgb, coverage: f32) {
+    for (channel, target) in pixel.0.iter_mut().zip(color) {
+        *channel = (*channel as f32 + (target as f32 - *channel as f32) * coverage.min(1.0)).round() as u8;
+    }
+}
+
+pub fn render(scene: &Scene, fonts: &Fonts) -> RgbImage {
+    let font = match scene.face {
+        Face::Mono => &fonts.mono,
+        Face::Ui => &fonts.ui,
+    };
+    let scaled = font.as_scaled(PxScale::from(scene.font_px));
+    let line_height = (scaled.height() * LINE_SPACING).ceil();
+    let pad = (scene.font_px * PADDING).ceil();
+    let tab = scene.font_px * TAB_STOP;
+    // Where the pen goes after drawing `c` at `x` (from the left margin)
+    let advance = |x: f32, c: char| match c {
+        '\t' => ((x / tab).floor() + 1.0) * tab,
+        _ => x + scaled.h_advance(font.glyph_id(c)),
+    };
+    let text_width = scene
+        .lines
+        .iter()
+        .map(|line| line.iter().flat_map(|s| s.text.chars()).fold(0.0, advance))
+        .fold(0.0, f32::max);
+
+    let width = (text_width + 2.0 * pad).ceil() as u32;
+    let height = (line_height * scene.lines.len() as f32 + 2.0 * pad).ceil() as u32;
+    let mut image = RgbImage::from_pixel(width, height, image::Rgb(scene.background));
+
+    for (row, line) in scene.lines.iter().enumerate() {
+        let baseline = pad + row as f32 * line_height + scaled.ascent();
+        let mut x = 0.0;
+        for span in line {
+            for c in span.text.chars() {
+                let glyph = font.glyph_id(c).with_scale_and_position(scaled.scale(), point(pad + x, baseline));
+                x = advance(x, c);
+                if c.is_control() {
+                    continue;
+                }
+                let Some(outline) = font.outline_glyph(glyph) else { continue };
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    let (px, py) = (bounds.min.x as i64 + gx as i64, bounds.min.y as i64 + gy as i64);
+                    if (0..width as i64).contains(&px) && (0..height as i64).contains(&py) {
+                        blend(image.get_pixel_mut(px as u32, py as u32), span.color, coverage);
+                    }
+                });
+            }
+        }
+    }
+
+    if scene.angle == 0.0 {
+        image
+    } else {
+        rotate(&image, scene.angle, scene.background)
+    }
+}
+
+/// `image` turned `degrees` counter-clockwise on a canvas that fits all of
+/// it, sampled bilinearly; corners are filled with `background`.
+fn rotate(image: &RgbImage, degrees: f32, background: Rgb) -> RgbImage {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (w, h) = (image.width() as f32, image.height() as f32);
+    let out_w = (w * cos.abs() + h * sin.abs()).ceil();
+    let out_h = (w * sin.abs() + h * cos.abs()).ceil();
+    let sample = |x: f32, y: f32, channel: usize| -> f32 {
+        let inside = x >= 0.0 && y >= 0.0 && x < w && y < h;
+        if inside {
+            image.get_pixel(x as u32, y as u32).0[channel] as f32
+        } else {
+            background[channel] as f32
+        }
+    };
+
+    RgbImage::from_fn(out_w as u32, out_h as u32, |ox, oy| {
+        // Inverse-map each output pixel center into the source
+        let (dx, dy) = (ox as f32 + 0.5 - out_w / 2.0, oy as f32 + 0.5 - out_h / 2.0);
+        let sx = dx * cos - dy * sin + w / 2.0 - 0.5;
+        let sy = dx * sin + dy * cos + h / 2.0 - 0.5;
+        let (x0, y0) = (sx.floor(), sy.floor());
+        let (fx, fy) = (sx - x0, sy - y0);
+        let mut pixel = [0u8; 3];
+        for (channel, value) in pixel.iter_mut().enumerate() {
+            let top = sample(x0, y0, channel) * (1.0 - fx) + sample(x0 + 1.0, y0, channel) * fx;
+            let bottom = sample(x0, y0 + 1.0, channel) * (1.0 - fx) + sample(x0 + 1.0, y0 + 1.0, channel) * fx;
+            *value = (top * (1.0 - fy) + bottom * fy).round() as u8;
+        }
+        image::Rgb(pixel)
+    })
+}
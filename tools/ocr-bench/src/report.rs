@@ -0,0 +1,202 @@
+//! Batch results as data: per-level tallies, summary statistics, JSON
+//! records, and the comparison against a baseline (pure, no I/O).
+//!
+//! `--format json` writes JSON Lines: one `image` record per image and
+//! recognition level as it is processed, then one `summary` record per
+//! level. Saved to a file, that output is a baseline: `--baseline` reads
+//! its summary records and reports a regression when a level's median
+//! latency grew by more than a percentage, or its corpus CER by more
+//! than a number of points.
+
+use crate::accuracy::{self, Score};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "camelCase")]
+pub enum Record {
+    Image(ImageRecord),
+    Summary(Summary),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageRecord {
+    pub file: String,
+    pub level: String,
+    pub char_count: i64,
+    pub vision_ms: f64,
+    pub wall_ms: f64,
+    pub confidence: f64,
+    /// `None` without a ground-truth reference.
+    pub cer: Option<f64>,
+    pub wer: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub level: String,
+    pub images: usize,
+    pub median_ms: f64,
+    pub average_ms: f64,
+    pub p99_ms: f64,
+    /// Corpus rates over the scored images; `None` if none were scored.
+    pub cer: Option<f64>,
+    pub wer: Option<f64>,
+    pub scored: usize,
+}
+
+/// Latencies and accuracy of one recognition level across the batch.
+#[derive(Default)]
+pub struct Tally {
+    latencies: Vec<f64>,
+    pub score: Score,
+    pub scored: usize,
+}
+
+impl Tally {
+    /// Record one image; returns its score if it has a reference.
+    pub fn add(&mut self, latency_ms: f64, text: &str, reference: Option<&str>) -> Option<Score> {
+        self.latencies.push(latency_ms);
+        let score = accuracy::score(text, reference?);
+        self.score.add(&score);
+        self.scored += 1;
+        Some(score)
+    }
+
+    pub fn summary(&self, level: &str) -> Summary {
+        let mut sorted = self.latencies.clone();
+        sorted.sort_by(f64::total_cmp);
+        let at = |i: usize| sorted.get(i.min(sorted.len().saturating_sub(1))).copied().unwrap_or(0.0);
+        let scored = (self.scored > 0).then_some(self.score);
+        Summary {
+            level: level.to_string(),
+            images: sorted.len(),
+            median_ms: at(sorted.len() / 2),
+            average_ms: sorted.iter().sum::<f64>() / sorted.len().max(1) as f64,
+            p99_ms: at((sorted.len() as f64 * 0.99).ceil() as usize),
+            cer: scored.map(|s| s.cer()),
+            wer: scored.map(|s| s.wer()),
+            scored: self.scored,
+        }
+    }
+}
+
+impl Record {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// The summary records of a `--format json` run.
+pub fn parse_summaries(text: &str) -> Result<Vec<Summary>, String> {
+    let mut summaries = Vec::new();
+    for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(Record::Summary(summary)) => summaries.push(summary),
+            Ok(Record::Image(_)) => {}
+            Err(e) => return Err(format!("line {}: {}", i + 1, e)),
+        }
+    }
+    Ok(summaries)
+}
+
+/// How much worse than the baseline a run may be.
+pub struct Thresholds {
+    /// Median latency growth, in percent.
+    pub latency_pct: f64,
+    /// CER growth, in percentage points.
+    pub cer_points: f64,
+}
+
+/// One line per metric that regressed past `thresholds`. Levels missing
+/// from either side are skipped; none in common is an error, since
+/// nothing would be gated.
+pub fn regressions(current: &[Summary], baseline: &[Summary], thresholds: &Thresholds) -> Result<Vec<String>, String> {
+    let pairs: Vec<(&Summary, &Summary)> = current
+        .iter()
+        .filter_map(|now| baseline.iter().find(|before| before.level == now.level).map(|before| (now, before)))
+        .collect();
+    if pairs.is_empty() {
+        return Err("the baseline has no summary for the recognition levels of this run".to_string());
+    }
+
+    let mut found = Vec::new();
+    for (now, before) in pairs {
+        let growth = (now.median_ms / before.median_ms.max(0.1) - 1.0) * 100.0;
+        if growth > thresholds.latency_pct {
+            found.push(format!(
+                "{}: median {:.1}ms vs {:.1}ms (+{:.1}%, limit +{}%)",
+                now.level, now.median_ms, before.median_ms, growth, thresholds.latency_pct
+            ));
+        }
+        if let (Some(cer), Some(base)) = (now.cer, before.cer) {
+            let points = (cer - base) * 100.0;
+            if points > thresholds.cer_points {
+                found.push(format!(
+                    "{}: CER {:.2}% vs {:.2}% (+{:.2} points, limit +{})",
+                    now.level, cer * 100.0, base * 100.0, points, thresholds.cer_points
+                ));
+            }
+        }
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_records_round_trip_through_json_lines() {
+        let mut tally = Tally::default();
+        for (ms, text) in [(30.0, "let x = 1;"), (10.0, "let x = l;"), (20.0, "noise")] {
+            tally.add(ms, text, Some("let x = 1;"));
+        }
+        tally.add(40.0, "unscored", None);
+        let summary = tally.summary("fast");
+        assert_eq!((summary.images, summary.scored, summary.median_ms, summary.p99_ms), (4, 3, 30.0, 40.0));
+        assert_eq!(summary.average_ms, 25.0);
+
+        let image = Record::Image(ImageRecord {
+            file: "a.png".into(),
+            level: "fast".into(),
+            char_count: 8,
+            vision_ms: 10.0,
+            wall_ms: 11.5,
+            confidence: 0.9,
+            cer: None,
+            wer: None,
+        });
+        assert!(image.to_json().starts_with(r#"{"record":"image","file":"a.png""#));
+        let lines = format!("{}\n{}\n", image.to_json(), Record::Summary(summary.clone()).to_json());
+        assert_eq!(parse_summaries(&lines), Ok(vec![summary]));
+        assert!(parse_summaries("{not json").is_err());
+    }
+
+    #[test]
+    fn flags_latency_and_cer_regressions_past_thresholds() {
+        let summary = |level: &str, median_ms, cer| Summary {
+            level: level.into(),
+            images: 10,
+            median_ms,
+            average_ms: median_ms,
+            p99_ms: median_ms,
+            cer,
+            wer: cer,
+            scored: 10,
+        };
+        let baseline = [summary("accurate", 200.0, Some(0.020)), summary("fast", 50.0, None)];
+        let thresholds = Thresholds { latency_pct: 20.0, cer_points: 1.0 };
+
+        let ok = [summary("accurate", 230.0, Some(0.025)), summary("fast", 55.0, Some(0.5))];
+        assert_eq!(regressions(&ok, &baseline, &thresholds), Ok(vec![]));
+
+        let slow = [summary("accurate", 250.0, Some(0.040))];
+        let found = regressions(&slow, &baseline, &thresholds).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found[0].contains("+25.0%") && found[1].contains("+2.00 points"));
+
+        assert!(regressions(&ok, &[], &thresholds).is_err());
+    }
+}
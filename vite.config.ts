@@ -13,8 +13,13 @@ export default defineConfig({
         "confirm-dialog": resolve(__dirname, "confirm-dialog.html"),
         settings: resolve(__dirname, "settings.html"),
         "permission-prompt": resolve(__dirname, "permission-prompt.html"),
+        "plugin-console": resolve(__dirname, "plugin-console.html"),
         "text-launcher": resolve(__dirname, "text-launcher.html"),
         "tray-menu": resolve(__dirname, "tray-menu.html"),
+        trigger: resolve(__dirname, "trigger.html"),
+        highlight: resolve(__dirname, "highlight.html"),
+        result: resolve(__dirname, "result.html"),
+        onboarding: resolve(__dirname, "onboarding.html"),
       },
     },
   },